@@ -13,6 +13,10 @@ call_id: string,
  * Uses `#[serde(default)]` for backwards compatibility with older senders.
  */
 turn_id: string, changes: { [key in string]?: FileChange }, 
+/**
+ * Files whose binary diffs the patch leaves untouched.
+ */
+skipped?: Array<string>, 
 /**
  * Optional explanatory reason (e.g. request for extra write access).
  */
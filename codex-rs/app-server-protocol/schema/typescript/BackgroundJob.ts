@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackgroundJobStatus } from "./BackgroundJobStatus";
+
+/**
+ * A unified exec process whose output is logged under `CODEX_HOME` so it
+ * outlives the session that started it.
+ */
+export type BackgroundJob = { process_id: string, command: Array<string>, cwd: string, 
+/**
+ * Unix timestamp (seconds) when the job started.
+ */
+started_at: number, status: BackgroundJobStatus, exit_code: number | null, 
+/**
+ * Head and tail of the job's output, as last written to disk.
+ */
+log_path: string, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A working-tree snapshot taken at the start of a turn.
+ */
+export type Checkpoint = { 
+/**
+ * Value of `num_turns` that restores this checkpoint via `Op::UndoTurns`.
+ */
+turns_back: number, snapshot_id: string, turn_id: string | null, 
+/**
+ * Unified diff of the edits the turn made after this checkpoint, when
+ * the turn changed files through tracked tools.
+ */
+diff: string | null, };
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CompactionTier } from "./CompactionTier";
+
+/**
+ * Outcome of an automatic compaction.
+ */
+export type CompactionReport = { 
+/**
+ * The last tier that ran; the tiers configured before it ran too.
+ */
+tier: CompactionTier, tool_outputs_pruned: number, reasoning_items_dropped: number, 
+/**
+ * Estimated tokens in the history before compaction.
+ */
+tokens_before: number, 
+/**
+ * Estimated tokens in the history after compaction.
+ */
+tokens_after: number, };
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Strategies automatic compaction applies in order, stopping once the history
+ * fits under the compaction threshold.
+ */
+export type CompactionTier = "prune_tool_outputs" | "drop_reasoning" | "summarize";
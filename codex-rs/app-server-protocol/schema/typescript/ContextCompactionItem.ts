@@ -1,5 +1,10 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CompactionReport } from "./CompactionReport";
 
-export type ContextCompactionItem = { id: string, };
+export type ContextCompactionItem = { id: string, 
+/**
+ * Set on completion of an automatic compaction.
+ */
+report?: CompactionReport, };
@@ -4,6 +4,7 @@
 import type { ExecCommandSource } from "./ExecCommandSource";
 import type { ExecCommandStatus } from "./ExecCommandStatus";
 import type { ParsedCommand } from "./ParsedCommand";
+import type { SeccompProfile } from "./SeccompProfile";
 
 export type ExecCommandEndEvent = { 
 /**
@@ -61,4 +62,10 @@ formatted_output: string,
 /**
  * Completion status for this command execution.
  */
-status: ExecCommandStatus, };
+status: ExecCommandStatus, 
+/**
+ * Seccomp profiles that stopped the command under the Linux sandbox,
+ * either by killing it for a blocked syscall or by refusing to execute a
+ * program outside the spawn allowlist; absent for any other outcome.
+ */
+seccomp_denial?: Array<SeccompProfile>, };
@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Mirrors [`codex_file_search::ContentMatch`].
+ */
+export type FuzzyFileSearchContentMatch = { 
+/**
+ * 1-based line number.
+ */
+line_number: number, 
+/**
+ * 1-based column, in characters, of the first match on the line.
+ */
+column: number, line: string, };
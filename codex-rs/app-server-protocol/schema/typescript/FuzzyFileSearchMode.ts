@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Mirrors [`codex_file_search::FileSearchMode`].
+ */
+export type FuzzyFileSearchMode = "path" | "contentLiteral" | "contentRegex";
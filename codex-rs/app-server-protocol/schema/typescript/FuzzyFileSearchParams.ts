@@ -1,5 +1,10 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FuzzyFileSearchMode } from "./FuzzyFileSearchMode";
 
-export type FuzzyFileSearchParams = { query: string, roots: Array<string>, cancellationToken: string | null, };
+export type FuzzyFileSearchParams = { query: string, roots: Array<string>, cancellationToken: string | null, 
+/**
+ * Defaults to `path`.
+ */
+mode?: FuzzyFileSearchMode, };
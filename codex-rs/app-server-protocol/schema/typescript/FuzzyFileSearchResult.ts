@@ -1,8 +1,18 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FuzzyFileSearchContentMatch } from "./FuzzyFileSearchContentMatch";
 
 /**
  * Superset of [`codex_file_search::FileMatch`]
  */
-export type FuzzyFileSearchResult = { root: string, path: string, file_name: string, score: number, indices: Array<number> | null, };
+export type FuzzyFileSearchResult = { root: string, path: string, file_name: string, score: number, 
+/**
+ * Character indices into `path`, or into `content.line` for content
+ * matches.
+ */
+indices: Array<number> | null, 
+/**
+ * Set for content searches.
+ */
+content?: FuzzyFileSearchContentMatch, };
@@ -5,4 +5,4 @@
 /**
  * Details of a ghost commit created from a repository state.
  */
-export type GhostCommit = { id: string, parent: string | null, preexisting_untracked_files: Array<string>, preexisting_untracked_dirs: Array<string>, };
+export type GhostCommit = { id: string, parent: string | null, preexisting_untracked_files: Array<string>, preexisting_untracked_dirs: Array<string>, turn_id?: string, };
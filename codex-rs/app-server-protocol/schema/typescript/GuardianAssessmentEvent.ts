@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GuardianBackend } from "./GuardianBackend";
+import type { GuardianRiskLevel } from "./GuardianRiskLevel";
+import type { JsonValue } from "./serde_json/JsonValue";
+
+export type GuardianAssessmentEvent = { 
+/**
+ * Backend that scored the action.
+ */
+backend: GuardianBackend, 
+/**
+ * Whether the action was approved, i.e. `risk_score < approval_threshold`.
+ */
+approved: boolean, risk_level: GuardianRiskLevel, risk_score: number, 
+/**
+ * Scores at or above this value are denied.
+ */
+approval_threshold: number, rationale: string, 
+/**
+ * The planned action that was reviewed.
+ */
+action: JsonValue, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How guardian scores `on-request` approvals: with a reviewer model or with local rules.
+ */
+export type GuardianBackend = "model" | "rules";
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackgroundJob } from "./BackgroundJob";
+
+/**
+ * Response payload for `Op::ListBackgroundJobs`.
+ */
+export type ListBackgroundJobsResponseEvent = { 
+/**
+ * Jobs started with `persist: true`, oldest first.
+ */
+jobs: Array<BackgroundJob>, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Checkpoint } from "./Checkpoint";
+
+/**
+ * Response payload for `Op::ListCheckpoints`.
+ */
+export type ListCheckpointsResponseEvent = { 
+/**
+ * Checkpoints that can be undone to, newest first.
+ */
+checkpoints: Array<Checkpoint>, 
+/**
+ * Number of undos that `Op::Redo` can reapply.
+ */
+redo_depth: number, };
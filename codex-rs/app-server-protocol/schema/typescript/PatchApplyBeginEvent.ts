@@ -20,4 +20,8 @@ auto_approved: boolean,
 /**
  * The changes to be applied.
  */
-changes: { [key in string]?: FileChange }, };
+changes: { [key in string]?: FileChange }, 
+/**
+ * Files whose binary diffs the patch leaves untouched.
+ */
+skipped?: Array<string>, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Emitted when an AGENTS.md below the session's working directory is loaded
+ * because the agent edited a file under its directory.
+ */
+export type ProjectDocLoadedEvent = { path: string, 
+/**
+ * Edited file whose directory the doc governs.
+ */
+trigger_path: string, 
+/**
+ * Whether the doc was cut short to fit `project_doc_scoped_max_tokens`.
+ */
+truncated: boolean, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Named seccomp profiles the Linux sandbox can install on top of its network
+ * filter. A command that makes a blocked syscall is killed with `SIGSYS`,
+ * except under `NoProcessSpawnOutsideAllowlist`.
+ */
+export type SeccompProfile = "no-ptrace" | "no-mount" | "no-process-spawn" | "no-process-spawn-outside-allowlist";
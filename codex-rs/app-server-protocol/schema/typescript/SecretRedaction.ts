@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SecretRedaction = { 
+/**
+ * Id of the detector that matched.
+ */
+detector: string, 
+/**
+ * `low`, `medium` or `high`.
+ */
+confidence: string, 
+/**
+ * JSON pointer of the redacted string within the value, when it is structured.
+ */
+field?: string, 
+/**
+ * 1-based position of the match in the original string.
+ */
+line: number, column: number, };
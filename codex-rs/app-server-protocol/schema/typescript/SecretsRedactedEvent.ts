@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SecretRedaction } from "./SecretRedaction";
+
+/**
+ * Audit record for one redacted value. The secrets themselves are never included.
+ */
+export type SecretsRedactedEvent = { 
+/**
+ * What was redacted: `tool_output` or `rollout`.
+ */
+source: string, redactions: Array<SecretRedaction>, };
@@ -18,8 +18,11 @@ export type { ApplyPatchApprovalResponse } from "./ApplyPatchApprovalResponse";
 export type { AskForApproval } from "./AskForApproval";
 export type { AuthMode } from "./AuthMode";
 export type { BackgroundEventEvent } from "./BackgroundEventEvent";
+export type { BackgroundJob } from "./BackgroundJob";
+export type { BackgroundJobStatus } from "./BackgroundJobStatus";
 export type { ByteRange } from "./ByteRange";
 export type { CallToolResult } from "./CallToolResult";
+export type { Checkpoint } from "./Checkpoint";
 export type { ClientInfo } from "./ClientInfo";
 export type { ClientNotification } from "./ClientNotification";
 export type { ClientRequest } from "./ClientRequest";
@@ -37,6 +40,8 @@ export type { CollabResumeEndEvent } from "./CollabResumeEndEvent";
 export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
 export type { CollaborationMode } from "./CollaborationMode";
+export type { CompactionReport } from "./CompactionReport";
+export type { CompactionTier } from "./CompactionTier";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
@@ -69,6 +74,8 @@ export type { ForcedLoginMethod } from "./ForcedLoginMethod";
 export type { FunctionCallOutputBody } from "./FunctionCallOutputBody";
 export type { FunctionCallOutputContentItem } from "./FunctionCallOutputContentItem";
 export type { FunctionCallOutputPayload } from "./FunctionCallOutputPayload";
+export type { FuzzyFileSearchContentMatch } from "./FuzzyFileSearchContentMatch";
+export type { FuzzyFileSearchMode } from "./FuzzyFileSearchMode";
 export type { FuzzyFileSearchParams } from "./FuzzyFileSearchParams";
 export type { FuzzyFileSearchResponse } from "./FuzzyFileSearchResponse";
 export type { FuzzyFileSearchResult } from "./FuzzyFileSearchResult";
@@ -83,6 +90,9 @@ export type { GhostCommit } from "./GhostCommit";
 export type { GitDiffToRemoteParams } from "./GitDiffToRemoteParams";
 export type { GitDiffToRemoteResponse } from "./GitDiffToRemoteResponse";
 export type { GitSha } from "./GitSha";
+export type { GuardianAssessmentEvent } from "./GuardianAssessmentEvent";
+export type { GuardianBackend } from "./GuardianBackend";
+export type { GuardianRiskLevel } from "./GuardianRiskLevel";
 export type { HistoryEntry } from "./HistoryEntry";
 export type { ImageDetail } from "./ImageDetail";
 export type { ImageGenerationBeginEvent } from "./ImageGenerationBeginEvent";
@@ -94,6 +104,8 @@ export type { InitializeResponse } from "./InitializeResponse";
 export type { InputModality } from "./InputModality";
 export type { ItemCompletedEvent } from "./ItemCompletedEvent";
 export type { ItemStartedEvent } from "./ItemStartedEvent";
+export type { ListBackgroundJobsResponseEvent } from "./ListBackgroundJobsResponseEvent";
+export type { ListCheckpointsResponseEvent } from "./ListCheckpointsResponseEvent";
 export type { ListCustomPromptsResponseEvent } from "./ListCustomPromptsResponseEvent";
 export type { ListRemoteSkillsResponseEvent } from "./ListRemoteSkillsResponseEvent";
 export type { ListSkillsResponseEvent } from "./ListSkillsResponseEvent";
@@ -132,6 +144,7 @@ export type { PlanDeltaEvent } from "./PlanDeltaEvent";
 export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
+export type { ProjectDocLoadedEvent } from "./ProjectDocLoadedEvent";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
@@ -168,6 +181,9 @@ export type { ReviewOutputEvent } from "./ReviewOutputEvent";
 export type { ReviewRequest } from "./ReviewRequest";
 export type { ReviewTarget } from "./ReviewTarget";
 export type { SandboxPolicy } from "./SandboxPolicy";
+export type { SeccompProfile } from "./SeccompProfile";
+export type { SecretRedaction } from "./SecretRedaction";
+export type { SecretsRedactedEvent } from "./SecretsRedactedEvent";
 export type { ServerNotification } from "./ServerNotification";
 export type { ServerRequest } from "./ServerRequest";
 export type { ServiceTier } from "./ServiceTier";
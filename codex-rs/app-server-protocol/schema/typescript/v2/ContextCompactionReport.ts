@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextCompactionTier } from "./ContextCompactionTier";
+
+export type ContextCompactionReport = { 
+/**
+ * The last tier that ran; the tiers configured before it ran too.
+ */
+tier: ContextCompactionTier, toolOutputsPruned: number, reasoningItemsDropped: number, 
+/**
+ * Estimated tokens in the history before compaction.
+ */
+tokensBefore: number, 
+/**
+ * Estimated tokens in the history after compaction.
+ */
+tokensAfter: number, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Which controllers receive, and must answer, server requests such as approvals.
+ */
+export type ServerRequestOwnership = "firstController" | "ownerOnly" | "allControllers";
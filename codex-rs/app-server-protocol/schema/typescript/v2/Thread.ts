@@ -63,6 +63,12 @@ gitInfo: GitInfo | null,
  * Optional user-facing thread title.
  */
 name: string | null, 
+/**
+ * Authenticated client identities (bearer-token names or Unix peers) that started or
+ * drove the thread, oldest first. Only populated on `thread/read` and `thread/list`
+ * responses.
+ */
+clientIdentities: Array<string>, 
 /**
  * Only populated on `thread/resume`, `thread/rollback`, `thread/fork`, and `thread/read`
  * (when `includeTurns` is true) responses.
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadBackgroundJobStatus } from "./ThreadBackgroundJobStatus";
+
+export type ThreadBackgroundJob = { processId: string, command: Array<string>, cwd: string, 
+/**
+ * Unix timestamp (seconds) when the job started.
+ */
+startedAt: number, status: ThreadBackgroundJobStatus, exitCode: number | null, 
+/**
+ * File holding the head and tail of the job's output.
+ */
+logPath: string, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadCheckpoint = { 
+/**
+ * Value of `numTurns` that restores this checkpoint.
+ */
+turnsBack: number, snapshotId: string, 
+/**
+ * Turn that captured the checkpoint, when known.
+ */
+turnId: string | null, 
+/**
+ * Unified diff of the changes made by that turn, when one was recorded.
+ */
+diff: string | null, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadCheckpoint } from "./ThreadCheckpoint";
+
+export type ThreadCheckpointsListResponse = { 
+/**
+ * Checkpoints available to `thread/undo`, newest first.
+ */
+checkpoints: Array<ThreadCheckpoint>, 
+/**
+ * Number of undos that `thread/redo` can reapply.
+ */
+redoDepth: number, };
@@ -7,6 +7,8 @@ import type { ServiceTier } from "../ServiceTier";
 import type { JsonValue } from "../serde_json/JsonValue";
 import type { AskForApproval } from "./AskForApproval";
 import type { SandboxMode } from "./SandboxMode";
+import type { ServerRequestOwnership } from "./ServerRequestOwnership";
+import type { ThreadSubscriptionRole } from "./ThreadSubscriptionRole";
 
 /**
  * There are three ways to resume a thread:
@@ -32,6 +34,17 @@ path?: string | null, /**
  * Configuration overrides for the resumed thread, if any.
  */
 model?: string | null, modelProvider?: string | null, serviceTier?: ServiceTier | null | null, cwd?: string | null, approvalPolicy?: AskForApproval | null, sandbox?: SandboxMode | null, config?: { [key in string]?: JsonValue } | null, baseInstructions?: string | null, developerInstructions?: string | null, personality?: Personality | null, /**
+ * Role of this connection on the thread. Defaults to `controller` when no other
+ * connection is subscribed to the thread and to `observer` otherwise. Taking
+ * control of a thread other connections are subscribed to requires an
+ * authenticated identity that started or drove it.
+ */
+role?: ThreadSubscriptionRole | null, /**
+ * Replaces the thread's server request ownership. Only the thread's owner may
+ * set it: the connection that started the thread, or the first controller to
+ * join while no owner is connected.
+ */
+serverRequestOwnership?: ServerRequestOwnership | null, /**
  * If true, persist additional rollout EventMsg variants required to
  * reconstruct a richer thread history on subsequent resume/fork/read.
  */
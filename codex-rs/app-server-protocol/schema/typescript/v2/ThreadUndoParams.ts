@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadUndoParams = { threadId: string, 
+/**
+ * How many turns of local file changes to revert. Defaults to 1.
+ *
+ * Unlike `thread/rollback`, this restores the working tree from the
+ * snapshot taken at the start of the turn and can be reversed with
+ * `thread/redo`.
+ */
+numTurns?: number | null, };
@@ -228,10 +228,32 @@ client_request_definitions! {
         params: v2::ThreadBackgroundTerminalsCleanParams,
         response: v2::ThreadBackgroundTerminalsCleanResponse,
     },
+    #[experimental("thread/backgroundJobs/list")]
+    ThreadBackgroundJobsList => "thread/backgroundJobs/list" {
+        params: v2::ThreadBackgroundJobsListParams,
+        response: v2::ThreadBackgroundJobsListResponse,
+    },
+    #[experimental("thread/backgroundJobs/kill")]
+    ThreadBackgroundJobsKill => "thread/backgroundJobs/kill" {
+        params: v2::ThreadBackgroundJobsKillParams,
+        response: v2::ThreadBackgroundJobsKillResponse,
+    },
     ThreadRollback => "thread/rollback" {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
     },
+    ThreadUndo => "thread/undo" {
+        params: v2::ThreadUndoParams,
+        response: v2::ThreadUndoResponse,
+    },
+    ThreadRedo => "thread/redo" {
+        params: v2::ThreadRedoParams,
+        response: v2::ThreadRedoResponse,
+    },
+    ThreadCheckpointsList => "thread/checkpoints/list" {
+        params: v2::ThreadCheckpointsListParams,
+        response: v2::ThreadCheckpointsListResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
@@ -709,6 +731,24 @@ pub struct FuzzyFileSearchParams {
     pub roots: Vec<String>,
     // if provided, will cancel any previous request that used the same value
     pub cancellation_token: Option<String>,
+    /// Defaults to `path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub mode: Option<FuzzyFileSearchMode>,
+}
+
+/// Mirrors [`codex_file_search::FileSearchMode`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS, Default)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub enum FuzzyFileSearchMode {
+    /// Fuzzy-match file paths.
+    #[default]
+    Path,
+    /// Search file contents for the query as a literal string.
+    ContentLiteral,
+    /// Search file contents with the query as a regular expression.
+    ContentRegex,
 }
 
 /// Superset of [`codex_file_search::FileMatch`]
@@ -718,7 +758,23 @@ pub struct FuzzyFileSearchResult {
     pub path: String,
     pub file_name: String,
     pub score: u32,
+    /// Character indices into `path`, or into `content.line` for content
+    /// matches.
     pub indices: Option<Vec<u32>>,
+    /// Set for content searches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub content: Option<FuzzyFileSearchContentMatch>,
+}
+
+/// Mirrors [`codex_file_search::ContentMatch`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+pub struct FuzzyFileSearchContentMatch {
+    /// 1-based line number.
+    pub line_number: u32,
+    /// 1-based column, in characters, of the first match on the line.
+    pub column: u32,
+    pub line: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -732,6 +788,10 @@ pub struct FuzzyFileSearchResponse {
 pub struct FuzzyFileSearchSessionStartParams {
     pub session_id: String,
     pub roots: Vec<String>,
+    /// Defaults to `path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub mode: Option<FuzzyFileSearchMode>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Default)]
@@ -3,6 +3,7 @@ use crate::protocol::v2::CollabAgentTool;
 use crate::protocol::v2::CollabAgentToolCallStatus;
 use crate::protocol::v2::CommandAction;
 use crate::protocol::v2::CommandExecutionStatus;
+use crate::protocol::v2::ContextCompactionReport;
 use crate::protocol::v2::DynamicToolCallOutputContentItem;
 use crate::protocol::v2::DynamicToolCallStatus;
 use crate::protocol::v2::FileUpdateChange;
@@ -73,6 +74,9 @@ pub struct ThreadHistoryBuilder {
     turns: Vec<Turn>,
     current_turn: Option<PendingTurn>,
     next_item_index: i64,
+    /// Report from the last `Compacted` rollout item, attached to the
+    /// `ContextCompacted` event that follows it.
+    pending_compaction_report: Option<ContextCompactionReport>,
 }
 
 impl Default for ThreadHistoryBuilder {
@@ -87,6 +91,7 @@ impl ThreadHistoryBuilder {
             turns: Vec::new(),
             current_turn: None,
             next_item_index: 1,
+            pending_compaction_report: None,
         }
     }
 
@@ -762,9 +767,10 @@ impl ThreadHistoryBuilder {
 
     fn handle_context_compacted(&mut self, _payload: &ContextCompactedEvent) {
         let id = self.next_item_id();
+        let report = self.pending_compaction_report.take();
         self.ensure_turn()
             .items
-            .push(ThreadItem::ContextCompaction { id });
+            .push(ThreadItem::ContextCompaction { id, report });
     }
 
     fn handle_entered_review_mode(&mut self, payload: &codex_protocol::protocol::ReviewRequest) {
@@ -876,7 +882,8 @@ impl ThreadHistoryBuilder {
     /// This keeps compaction-only legacy turns from being dropped by
     /// `finish_current_turn` when they have no renderable items and were not
     /// explicitly opened.
-    fn handle_compacted(&mut self, _payload: &CompactedItem) {
+    fn handle_compacted(&mut self, payload: &CompactedItem) {
+        self.pending_compaction_report = payload.report.clone().map(ContextCompactionReport::from);
         self.ensure_turn().saw_compaction = true;
     }
 
@@ -1658,6 +1665,7 @@ mod tests {
                 duration: Duration::from_millis(12),
                 formatted_output: String::new(),
                 status: CoreExecCommandStatus::Completed,
+                seccomp_denial: None,
             }),
             EventMsg::McpToolCallEnd(McpToolCallEndEvent {
                 call_id: "mcp-1".into(),
@@ -1811,6 +1819,7 @@ mod tests {
                 duration: Duration::ZERO,
                 formatted_output: String::new(),
                 status: CoreExecCommandStatus::Declined,
+                seccomp_denial: None,
             }),
             EventMsg::PatchApplyEnd(PatchApplyEndEvent {
                 call_id: "patch-declined".into(),
@@ -1914,6 +1923,7 @@ mod tests {
                 duration: Duration::from_millis(5),
                 formatted_output: "done\n".into(),
                 status: CoreExecCommandStatus::Completed,
+                seccomp_denial: None,
             }),
             EventMsg::TurnComplete(TurnCompleteEvent {
                 turn_id: "turn-b".into(),
@@ -1996,6 +2006,7 @@ mod tests {
                 duration: Duration::from_millis(5),
                 formatted_output: "done\n".into(),
                 status: CoreExecCommandStatus::Completed,
+                seccomp_denial: None,
             }),
             EventMsg::TurnComplete(TurnCompleteEvent {
                 turn_id: "turn-b".into(),
@@ -2053,6 +2064,7 @@ mod tests {
                 )]
                 .into_iter()
                 .collect(),
+                skipped: Vec::new(),
             }),
         ];
 
@@ -2117,6 +2129,7 @@ mod tests {
                 .collect(),
                 reason: None,
                 grant_root: None,
+                skipped: Vec::new(),
             }),
         ];
 
@@ -2268,6 +2281,7 @@ mod tests {
             RolloutItem::Compacted(CompactedItem {
                 message: String::new(),
                 replacement_history: None,
+                report: None,
             }),
             RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
                 turn_id: "turn-compact".into(),
@@ -2287,6 +2301,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn attaches_compaction_report_to_context_compaction_item() {
+        let report = codex_protocol::protocol::CompactionReport {
+            tier: codex_protocol::protocol::CompactionTier::DropReasoning,
+            tool_outputs_pruned: 3,
+            reasoning_items_dropped: 2,
+            tokens_before: 9_000,
+            tokens_after: 6_000,
+        };
+        let items = vec![
+            RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: "turn-compact".into(),
+                model_context_window: None,
+                collaboration_mode_kind: Default::default(),
+            })),
+            RolloutItem::Compacted(CompactedItem {
+                message: String::new(),
+                replacement_history: Some(Vec::new()),
+                report: Some(report.clone()),
+            }),
+            RolloutItem::EventMsg(EventMsg::ContextCompacted(ContextCompactedEvent {})),
+            RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: "turn-compact".into(),
+                last_agent_message: None,
+            })),
+        ];
+
+        let turns = build_turns_from_rollout_items(&items);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(
+            turns[0].items,
+            vec![ThreadItem::ContextCompaction {
+                id: "item-1".into(),
+                report: Some(ContextCompactionReport::from(report)),
+            }]
+        );
+    }
+
     #[test]
     fn reconstructs_collab_resume_end_item() {
         let events = vec![
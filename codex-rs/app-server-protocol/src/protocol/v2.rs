@@ -45,7 +45,12 @@ use codex_protocol::plan_tool::PlanItemArg as CorePlanItemArg;
 use codex_protocol::plan_tool::StepStatus as CorePlanStepStatus;
 use codex_protocol::protocol::AgentStatus as CoreAgentStatus;
 use codex_protocol::protocol::AskForApproval as CoreAskForApproval;
+use codex_protocol::protocol::BackgroundJob as CoreBackgroundJob;
+use codex_protocol::protocol::BackgroundJobStatus as CoreBackgroundJobStatus;
+use codex_protocol::protocol::Checkpoint as CoreCheckpoint;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
+use codex_protocol::protocol::CompactionReport as CoreCompactionReport;
+use codex_protocol::protocol::CompactionTier as CoreCompactionTier;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
 use codex_protocol::protocol::ExecCommandStatus as CoreExecCommandStatus;
 use codex_protocol::protocol::ModelRerouteReason as CoreModelRerouteReason;
@@ -1985,6 +1990,33 @@ pub enum CommandExecOutputStream {
 
 // === Threads, Turns, and Items ===
 // Thread APIs
+/// How much a connection subscribed to a thread may do with it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadSubscriptionRole {
+    /// Can start, steer and interrupt turns and answer server requests.
+    Controller,
+    /// Receives thread events but cannot drive the thread or answer server requests.
+    #[default]
+    Observer,
+}
+
+/// Which controllers receive, and must answer, server requests such as approvals.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ServerRequestOwnership {
+    /// Every controller receives the request and the first answer wins.
+    #[default]
+    FirstController,
+    /// Only the thread's owner receives and answers requests. This is the only mode in
+    /// which another controller cannot answer while the owner is connected.
+    OwnerOnly,
+    /// Every controller receives the request and all of them must send the same answer.
+    AllControllers,
+}
+
 #[derive(
     Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS, ExperimentalApi,
 )]
@@ -2021,6 +2053,10 @@ pub struct ThreadStartParams {
     pub personality: Option<Personality>,
     #[ts(optional = nullable)]
     pub ephemeral: Option<bool>,
+    /// Who answers server requests for this thread. `ownerOnly` restricts them to the
+    /// connection that started the thread. Defaults to `firstController`.
+    #[ts(optional = nullable)]
+    pub server_request_ownership: Option<ServerRequestOwnership>,
     #[experimental("thread/start.dynamicTools")]
     #[ts(optional = nullable)]
     pub dynamic_tools: Option<Vec<DynamicToolSpec>>,
@@ -2129,6 +2165,17 @@ pub struct ThreadResumeParams {
     pub developer_instructions: Option<String>,
     #[ts(optional = nullable)]
     pub personality: Option<Personality>,
+    /// Role of this connection on the thread. Defaults to `controller` when no other
+    /// connection is subscribed to the thread and to `observer` otherwise. Taking
+    /// control of a thread other connections are subscribed to requires an
+    /// authenticated identity that started or drove it.
+    #[ts(optional = nullable)]
+    pub role: Option<ThreadSubscriptionRole>,
+    /// Replaces the thread's server request ownership. Only the thread's owner may
+    /// set it: the connection that started the thread, or the first controller to
+    /// join while no owner is connected.
+    #[ts(optional = nullable)]
+    pub server_request_ownership: Option<ServerRequestOwnership>,
     /// If true, persist additional rollout EventMsg variants required to
     /// reconstruct a richer thread history on subsequent resume/fork/read.
     #[experimental("thread/resume.persistFullHistory")]
@@ -2358,6 +2405,70 @@ pub struct ThreadBackgroundTerminalsCleanParams {
 #[ts(export_to = "v2/")]
 pub struct ThreadBackgroundTerminalsCleanResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundJobsListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundJobsListResponse {
+    /// Jobs recorded for the thread, oldest first.
+    pub jobs: Vec<ThreadBackgroundJob>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundJobsKillParams {
+    pub thread_id: String,
+    pub process_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundJobsKillResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundJob {
+    pub process_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    /// Unix timestamp (seconds) when the job started.
+    #[ts(type = "number")]
+    pub started_at: i64,
+    pub status: ThreadBackgroundJobStatus,
+    pub exit_code: Option<i32>,
+    /// File holding the head and tail of the job's output.
+    pub log_path: PathBuf,
+}
+
+v2_enum_from_core!(
+    pub enum ThreadBackgroundJobStatus from CoreBackgroundJobStatus {
+        Running, Exited, Lost
+    }
+);
+
+impl From<CoreBackgroundJob> for ThreadBackgroundJob {
+    fn from(value: CoreBackgroundJob) -> Self {
+        Self {
+            process_id: value.process_id,
+            command: value.command,
+            cwd: value.cwd,
+            started_at: value.started_at,
+            status: value.status.into(),
+            exit_code: value.exit_code,
+            log_path: value.log_path,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2382,6 +2493,78 @@ pub struct ThreadRollbackResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoParams {
+    pub thread_id: String,
+    /// How many turns of local file changes to revert. Defaults to 1.
+    ///
+    /// Unlike `thread/rollback`, this restores the working tree from the
+    /// snapshot taken at the start of the turn and can be reversed with
+    /// `thread/redo`.
+    #[ts(optional = nullable)]
+    pub num_turns: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadRedoParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadRedoResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointsListResponse {
+    /// Checkpoints available to `thread/undo`, newest first.
+    pub checkpoints: Vec<ThreadCheckpoint>,
+    /// Number of undos that `thread/redo` can reapply.
+    pub redo_depth: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpoint {
+    /// Value of `numTurns` that restores this checkpoint.
+    pub turns_back: u32,
+    pub snapshot_id: String,
+    /// Turn that captured the checkpoint, when known.
+    pub turn_id: Option<String>,
+    /// Unified diff of the changes made by that turn, when one was recorded.
+    pub diff: Option<String>,
+}
+
+impl From<CoreCheckpoint> for ThreadCheckpoint {
+    fn from(value: CoreCheckpoint) -> Self {
+        Self {
+            turns_back: value.turns_back,
+            snapshot_id: value.snapshot_id,
+            turn_id: value.turn_id,
+            diff: value.diff,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2917,6 +3100,11 @@ pub struct Thread {
     pub git_info: Option<GitInfo>,
     /// Optional user-facing thread title.
     pub name: Option<String>,
+    /// Authenticated client identities (bearer-token names or Unix peers) that started or
+    /// drove the thread, oldest first. Only populated on `thread/read` and `thread/list`
+    /// responses.
+    #[serde(default)]
+    pub client_identities: Vec<String>,
     /// Only populated on `thread/resume`, `thread/rollback`, `thread/fork`, and `thread/read`
     /// (when `includeTurns` is true) responses.
     /// For all other responses and notifications returning a Thread,
@@ -3610,7 +3798,11 @@ pub enum ThreadItem {
     ExitedReviewMode { id: String, review: String },
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
-    ContextCompaction { id: String },
+    ContextCompaction {
+        id: String,
+        /// What automatic compaction removed; null for manual compaction.
+        report: Option<ContextCompactionReport>,
+    },
 }
 
 impl ThreadItem {
@@ -3713,9 +3905,44 @@ impl From<CoreTurnItem> for ThreadItem {
                 revised_prompt: image.revised_prompt,
                 result: image.result,
             },
-            CoreTurnItem::ContextCompaction(compaction) => {
-                ThreadItem::ContextCompaction { id: compaction.id }
-            }
+            CoreTurnItem::ContextCompaction(compaction) => ThreadItem::ContextCompaction {
+                id: compaction.id,
+                report: compaction.report.map(ContextCompactionReport::from),
+            },
+        }
+    }
+}
+
+v2_enum_from_core!(
+    pub enum ContextCompactionTier from CoreCompactionTier {
+        PruneToolOutputs, DropReasoning, Summarize
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ContextCompactionReport {
+    /// The last tier that ran; the tiers configured before it ran too.
+    pub tier: ContextCompactionTier,
+    pub tool_outputs_pruned: u32,
+    pub reasoning_items_dropped: u32,
+    /// Estimated tokens in the history before compaction.
+    #[ts(type = "number")]
+    pub tokens_before: i64,
+    /// Estimated tokens in the history after compaction.
+    #[ts(type = "number")]
+    pub tokens_after: i64,
+}
+
+impl From<CoreCompactionReport> for ContextCompactionReport {
+    fn from(value: CoreCompactionReport) -> Self {
+        Self {
+            tier: value.tier.into(),
+            tool_outputs_pruned: value.tool_outputs_pruned,
+            reasoning_items_dropped: value.reasoning_items_dropped,
+            tokens_before: value.tokens_before,
+            tokens_after: value.tokens_after,
         }
     }
 }
@@ -13,7 +13,7 @@ use tracing::info_span;
 
 pub(crate) fn request_span(
     request: &JSONRPCRequest,
-    transport: AppServerTransport,
+    transport: &AppServerTransport,
     connection_id: ConnectionId,
     session: &ConnectionSessionState,
 ) -> Span {
@@ -29,6 +29,7 @@ pub(crate) fn request_span(
         app_server.api_version = "v2",
         app_server.client_name = field::Empty,
         app_server.client_version = field::Empty,
+        app_server.client_identity = field::Empty,
     );
 
     let initialize_client_info = initialize_client_info(request);
@@ -38,6 +39,12 @@ pub(crate) fn request_span(
     if let Some(client_version) = client_version(initialize_client_info.as_ref(), session) {
         span.record("app_server.client_version", client_version);
     }
+    if let Some(client_identity) = &session.client_identity {
+        span.record(
+            "app_server.client_identity",
+            field::display(client_identity),
+        );
+    }
 
     if let Some(traceparent) = request
         .trace
@@ -65,10 +72,11 @@ pub(crate) fn request_span(
     span
 }
 
-fn transport_name(transport: AppServerTransport) -> &'static str {
+fn transport_name(transport: &AppServerTransport) -> &'static str {
     match transport {
         AppServerTransport::Stdio => "stdio",
         AppServerTransport::WebSocket { .. } => "websocket",
+        AppServerTransport::Unix { .. } => "unix",
     }
 }
 
@@ -336,6 +336,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             call_id,
             turn_id,
             changes,
+            skipped: _,
             reason,
             grant_root,
         }) => {
@@ -98,6 +98,7 @@ use codex_app_server_protocol::ReviewStartResponse;
 use codex_app_server_protocol::ReviewTarget as ApiReviewTarget;
 use codex_app_server_protocol::SandboxMode;
 use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ServerRequestOwnership;
 use codex_app_server_protocol::ServerRequestResolvedNotification;
 use codex_app_server_protocol::SkillsConfigWriteParams;
 use codex_app_server_protocol::SkillsConfigWriteResponse;
@@ -111,8 +112,16 @@ use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
 use codex_app_server_protocol::ThreadArchivedNotification;
+use codex_app_server_protocol::ThreadBackgroundJob;
+use codex_app_server_protocol::ThreadBackgroundJobsKillParams;
+use codex_app_server_protocol::ThreadBackgroundJobsKillResponse;
+use codex_app_server_protocol::ThreadBackgroundJobsListParams;
+use codex_app_server_protocol::ThreadBackgroundJobsListResponse;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanParams;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanResponse;
+use codex_app_server_protocol::ThreadCheckpoint;
+use codex_app_server_protocol::ThreadCheckpointsListParams;
+use codex_app_server_protocol::ThreadCheckpointsListResponse;
 use codex_app_server_protocol::ThreadClosedNotification;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
@@ -137,6 +146,8 @@ use codex_app_server_protocol::ThreadRealtimeStartParams;
 use codex_app_server_protocol::ThreadRealtimeStartResponse;
 use codex_app_server_protocol::ThreadRealtimeStopParams;
 use codex_app_server_protocol::ThreadRealtimeStopResponse;
+use codex_app_server_protocol::ThreadRedoParams;
+use codex_app_server_protocol::ThreadRedoResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
@@ -148,9 +159,12 @@ use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
 use codex_app_server_protocol::ThreadStatus;
+use codex_app_server_protocol::ThreadSubscriptionRole;
 use codex_app_server_protocol::ThreadUnarchiveParams;
 use codex_app_server_protocol::ThreadUnarchiveResponse;
 use codex_app_server_protocol::ThreadUnarchivedNotification;
+use codex_app_server_protocol::ThreadUndoParams;
+use codex_app_server_protocol::ThreadUndoResponse;
 use codex_app_server_protocol::ThreadUnsubscribeParams;
 use codex_app_server_protocol::ThreadUnsubscribeResponse;
 use codex_app_server_protocol::ThreadUnsubscribeStatus;
@@ -613,6 +627,7 @@ impl CodexMessageProcessor {
         connection_id: ConnectionId,
         request: ClientRequest,
         app_server_client_name: Option<String>,
+        client_identity: Option<String>,
     ) {
         let to_connection_request_id = |request_id| ConnectionRequestId {
             connection_id,
@@ -625,16 +640,24 @@ impl CodexMessageProcessor {
             }
             // === v2 Thread/Turn APIs ===
             ClientRequest::ThreadStart { request_id, params } => {
-                self.thread_start(to_connection_request_id(request_id), params)
-                    .await;
+                self.thread_start(
+                    to_connection_request_id(request_id),
+                    params,
+                    client_identity.clone(),
+                )
+                .await;
             }
             ClientRequest::ThreadUnsubscribe { request_id, params } => {
                 self.thread_unsubscribe(to_connection_request_id(request_id), params)
                     .await;
             }
             ClientRequest::ThreadResume { request_id, params } => {
-                self.thread_resume(to_connection_request_id(request_id), params)
-                    .await;
+                self.thread_resume(
+                    to_connection_request_id(request_id),
+                    params,
+                    client_identity.clone(),
+                )
+                .await;
             }
             ClientRequest::ThreadFork { request_id, params } => {
                 self.thread_fork(to_connection_request_id(request_id), params)
@@ -667,10 +690,30 @@ impl CodexMessageProcessor {
                 )
                 .await;
             }
+            ClientRequest::ThreadBackgroundJobsList { request_id, params } => {
+                self.thread_background_jobs_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadBackgroundJobsKill { request_id, params } => {
+                self.thread_background_jobs_kill(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadRollback { request_id, params } => {
                 self.thread_rollback(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadUndo { request_id, params } => {
+                self.thread_undo(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadRedo { request_id, params } => {
+                self.thread_redo(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadCheckpointsList { request_id, params } => {
+                self.thread_checkpoints_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
@@ -716,6 +759,7 @@ impl CodexMessageProcessor {
                     to_connection_request_id(request_id),
                     params,
                     app_server_client_name.clone(),
+                    client_identity.clone(),
                 )
                 .await;
             }
@@ -1610,6 +1654,7 @@ impl CodexMessageProcessor {
                     self.config.permissions.sandbox_policy.get(),
                     None,
                     None,
+                    None,
                     managed_network_requirements_enabled,
                     NetworkProxyAuditMetadata::default(),
                 )
@@ -1787,7 +1832,12 @@ impl CodexMessageProcessor {
         }
     }
 
-    async fn thread_start(&self, request_id: ConnectionRequestId, params: ThreadStartParams) {
+    async fn thread_start(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadStartParams,
+        client_identity: Option<String>,
+    ) {
         let ThreadStartParams {
             model,
             model_provider,
@@ -1804,6 +1854,7 @@ impl CodexMessageProcessor {
             experimental_raw_events,
             personality,
             ephemeral,
+            server_request_ownership,
             persist_extended_history,
         } = params;
         let mut typesafe_overrides = self.build_thread_config_overrides(
@@ -1841,6 +1892,8 @@ impl CodexMessageProcessor {
                 persist_extended_history,
                 service_name,
                 experimental_raw_events,
+                server_request_ownership,
+                client_identity,
             )
             .await;
         });
@@ -1858,6 +1911,8 @@ impl CodexMessageProcessor {
         persist_extended_history: bool,
         service_name: Option<String>,
         experimental_raw_events: bool,
+        server_request_ownership: Option<ServerRequestOwnership>,
+        client_identity: Option<String>,
     ) {
         let config = match derive_config_from_params(
             &cli_overrides,
@@ -1925,6 +1980,26 @@ impl CodexMessageProcessor {
                     session_configured,
                     ..
                 } = new_conv;
+                Self::record_client_identity(thread.as_ref(), thread_id, client_identity).await;
+                // The starting connection owns the new thread.
+                listener_task_context
+                    .thread_state_manager
+                    .set_connection_role(
+                        thread_id,
+                        request_id.connection_id,
+                        ThreadSubscriptionRole::Controller,
+                    )
+                    .await;
+                if let Some(ownership) = server_request_ownership {
+                    listener_task_context
+                        .thread_state_manager
+                        .set_server_request_ownership(
+                            thread_id,
+                            ownership,
+                            request_id.connection_id,
+                        )
+                        .await;
+                }
                 let config_snapshot = thread.config_snapshot().await;
                 let mut thread = build_thread_from_snapshot(
                     thread_id,
@@ -2044,6 +2119,9 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
 
         let rollout_path =
             match find_thread_path_by_id_str(&self.config.codex_home, &thread_id.to_string()).await
@@ -2097,6 +2175,9 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
         let Some(name) = codex_core::util::normalize_thread_name(&name) else {
             self.send_invalid_request_error(
                 request_id,
@@ -2670,6 +2751,9 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
 
         let request = request_id.clone();
 
@@ -2705,6 +2789,104 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_undo(&self, request_id: ConnectionRequestId, params: ThreadUndoParams) {
+        let ThreadUndoParams {
+            thread_id,
+            num_turns,
+        } = params;
+
+        if num_turns == Some(0) {
+            self.send_invalid_request_error(request_id, "numTurns must be >= 1".to_string())
+                .await;
+            return;
+        }
+
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
+
+        let op = match num_turns {
+            Some(num_turns) => Op::UndoTurns { num_turns },
+            None => Op::Undo,
+        };
+        match thread.submit(op).await {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, ThreadUndoResponse {})
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to start undo: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn thread_redo(&self, request_id: ConnectionRequestId, params: ThreadRedoParams) {
+        let ThreadRedoParams { thread_id } = params;
+
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
+
+        match thread.submit(Op::Redo).await {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, ThreadRedoResponse {})
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to start redo: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn thread_checkpoints_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadCheckpointsListParams,
+    ) {
+        let ThreadCheckpointsListParams { thread_id } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let listing = thread.list_checkpoints().await;
+        self.outgoing
+            .send_response(
+                request_id,
+                ThreadCheckpointsListResponse {
+                    checkpoints: listing
+                        .checkpoints
+                        .into_iter()
+                        .map(ThreadCheckpoint::from)
+                        .collect(),
+                    redo_depth: listing.redo_depth,
+                },
+            )
+            .await;
+    }
+
     async fn thread_compact_start(
         &self,
         request_id: ConnectionRequestId,
@@ -2712,13 +2894,16 @@ impl CodexMessageProcessor {
     ) {
         let ThreadCompactStartParams { thread_id } = params;
 
-        let (_, thread) = match self.load_thread(&thread_id).await {
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
 
         match thread.submit(Op::Compact).await {
             Ok(_) => {
@@ -2740,13 +2925,16 @@ impl CodexMessageProcessor {
     ) {
         let ThreadBackgroundTerminalsCleanParams { thread_id } = params;
 
-        let (_, thread) = match self.load_thread(&thread_id).await {
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
 
         match thread.submit(Op::CleanBackgroundTerminals).await {
             Ok(_) => {
@@ -2764,6 +2952,70 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_background_jobs_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadBackgroundJobsListParams,
+    ) {
+        let ThreadBackgroundJobsListParams { thread_id } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let listing = thread.list_background_jobs().await;
+        self.outgoing
+            .send_response(
+                request_id,
+                ThreadBackgroundJobsListResponse {
+                    jobs: listing
+                        .jobs
+                        .into_iter()
+                        .map(ThreadBackgroundJob::from)
+                        .collect(),
+                },
+            )
+            .await;
+    }
+
+    async fn thread_background_jobs_kill(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadBackgroundJobsKillParams,
+    ) {
+        let ThreadBackgroundJobsKillParams {
+            thread_id,
+            process_id,
+        } = params;
+
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
+
+        if thread.kill_background_job(&process_id).await {
+            self.outgoing
+                .send_response(request_id, ThreadBackgroundJobsKillResponse {})
+                .await;
+        } else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("no running background job with id {process_id}"),
+            )
+            .await;
+        }
+    }
+
     async fn thread_list(&self, request_id: ConnectionRequestId, params: ThreadListParams) {
         let ThreadListParams {
             cursor,
@@ -2831,16 +3083,17 @@ impl CodexMessageProcessor {
             .loaded_statuses_for_threads(status_ids)
             .await;
 
-        let data = threads
-            .into_iter()
-            .map(|(conversation_id, mut thread)| {
-                thread.name = names.get(&conversation_id).cloned();
-                if let Some(status) = statuses.get(&thread.id) {
-                    thread.status = status.clone();
-                }
-                thread
-            })
-            .collect();
+        let state_db_ctx = get_state_db(&self.config).await;
+        let mut data = Vec::with_capacity(threads.len());
+        for (conversation_id, mut thread) in threads {
+            thread.name = names.get(&conversation_id).cloned();
+            if let Some(status) = statuses.get(&thread.id) {
+                thread.status = status.clone();
+            }
+            thread.client_identities =
+                read_client_identities(state_db_ctx.as_ref(), conversation_id).await;
+            data.push(thread);
+        }
         let response = ThreadListResponse { data, next_cursor };
         self.outgoing.send_response(request_id, response).await;
     }
@@ -3003,6 +3256,11 @@ impl CodexMessageProcessor {
             build_thread_from_snapshot(thread_uuid, &config_snapshot, loaded_rollout_path)
         };
         self.attach_thread_name(thread_uuid, &mut thread).await;
+        let state_db_ctx = match loaded_thread_state_db {
+            Some(state_db_ctx) => Some(state_db_ctx),
+            None => get_state_db(&self.config).await,
+        };
+        thread.client_identities = read_client_identities(state_db_ctx.as_ref(), thread_uuid).await;
 
         if include_turns && let Some(rollout_path) = rollout_path.as_ref() {
             match read_rollout_items_from_rollout(rollout_path).await {
@@ -3057,6 +3315,7 @@ impl CodexMessageProcessor {
         self.command_exec_manager
             .connection_closed(connection_id)
             .await;
+        self.outgoing.connection_closed(connection_id).await;
         self.thread_state_manager
             .remove_connection(connection_id)
             .await;
@@ -3090,7 +3349,12 @@ impl CodexMessageProcessor {
         }
     }
 
-    async fn thread_resume(&mut self, request_id: ConnectionRequestId, params: ThreadResumeParams) {
+    async fn thread_resume(
+        &mut self,
+        request_id: ConnectionRequestId,
+        params: ThreadResumeParams,
+        client_identity: Option<String>,
+    ) {
         if let Ok(thread_id) = ThreadId::from_string(&params.thread_id)
             && self
                 .pending_thread_unloads
@@ -3108,8 +3372,19 @@ impl CodexMessageProcessor {
             return;
         }
 
+        if params.role == Some(ThreadSubscriptionRole::Observer)
+            && params.server_request_ownership.is_some()
+        {
+            self.send_invalid_request_error(
+                request_id,
+                "observers cannot set serverRequestOwnership".to_string(),
+            )
+            .await;
+            return;
+        }
+
         if self
-            .resume_running_thread(request_id.clone(), &params)
+            .resume_running_thread(request_id.clone(), &params, client_identity.as_deref())
             .await
         {
             return;
@@ -3129,6 +3404,8 @@ impl CodexMessageProcessor {
             base_instructions,
             developer_instructions,
             personality,
+            role,
+            server_request_ownership,
             persist_extended_history,
         } = params;
 
@@ -3213,6 +3490,19 @@ impl CodexMessageProcessor {
                     .await;
                     return;
                 };
+                if !self
+                    .apply_thread_subscription_settings(
+                        &request_id,
+                        thread.as_ref(),
+                        thread_id,
+                        role,
+                        server_request_ownership,
+                        client_identity,
+                    )
+                    .await
+                {
+                    return;
+                }
                 // Auto-attach a thread listener when resuming a thread.
                 Self::log_listener_attach_result(
                     self.ensure_conversation_listener(
@@ -3280,6 +3570,7 @@ impl CodexMessageProcessor {
         &mut self,
         request_id: ConnectionRequestId,
         params: &ThreadResumeParams,
+        client_identity: Option<&str>,
     ) -> bool {
         if let Ok(existing_thread_id) = ThreadId::from_string(&params.thread_id)
             && let Ok(existing_thread) = self.thread_manager.get_thread(existing_thread_id).await
@@ -3418,6 +3709,20 @@ impl CodexMessageProcessor {
                 return true;
             };
 
+            if !self
+                .apply_thread_subscription_settings(
+                    &request_id,
+                    existing_thread.as_ref(),
+                    existing_thread_id,
+                    params.role,
+                    params.server_request_ownership,
+                    client_identity.map(str::to_string),
+                )
+                .await
+            {
+                return true;
+            }
+
             let command = crate::thread_state::ThreadListenerCommand::SendThreadResumeResponse(
                 Box::new(crate::thread_state::PendingThreadResumeRequest {
                     request_id: request_id.clone(),
@@ -3441,6 +3746,92 @@ impl CodexMessageProcessor {
         false
     }
 
+    /// Sends an error and returns false when the connection may not take the requested
+    /// role, or when a connection other than the thread's owner tries to change server
+    /// request ownership. Without a requested role the connection keeps its current one:
+    /// it drives a thread nobody else is subscribed to and observes one that others
+    /// hold. Taking control of a thread others hold requires an authenticated identity
+    /// that already started or drove it.
+    async fn apply_thread_subscription_settings(
+        &self,
+        request_id: &ConnectionRequestId,
+        thread: &CodexThread,
+        thread_id: ThreadId,
+        role: Option<ThreadSubscriptionRole>,
+        server_request_ownership: Option<ServerRequestOwnership>,
+        client_identity: Option<String>,
+    ) -> bool {
+        let current_role = self
+            .thread_state_manager
+            .connection_role(thread_id, request_id.connection_id)
+            .await;
+        let role = role.unwrap_or(current_role);
+        if role == ThreadSubscriptionRole::Controller
+            && current_role == ThreadSubscriptionRole::Observer
+        {
+            let known_identity = match client_identity.as_deref() {
+                Some(client_identity) => {
+                    read_client_identities(thread.state_db().as_ref(), thread_id)
+                        .await
+                        .iter()
+                        .any(|identity| identity == client_identity)
+                }
+                None => false,
+            };
+            if !known_identity {
+                self.send_invalid_request_error(
+                    request_id.clone(),
+                    format!(
+                        "controlling thread {thread_id} while other connections are subscribed requires an authenticated identity that started or drove it"
+                    ),
+                )
+                .await;
+                return false;
+            }
+        }
+        self.thread_state_manager
+            .set_connection_role(thread_id, request_id.connection_id, role)
+            .await;
+        if role == ThreadSubscriptionRole::Controller {
+            Self::record_client_identity(thread, thread_id, client_identity).await;
+        }
+        if let Some(ownership) = server_request_ownership
+            && !self
+                .thread_state_manager
+                .set_server_request_ownership(thread_id, ownership, request_id.connection_id)
+                .await
+        {
+            self.send_invalid_request_error(
+                request_id.clone(),
+                format!("only the owner of thread {thread_id} may set serverRequestOwnership"),
+            )
+            .await;
+            return false;
+        }
+        true
+    }
+
+    /// Sends an error and returns false when the connection only observes the thread.
+    async fn ensure_thread_controller(
+        &self,
+        request_id: &ConnectionRequestId,
+        thread_id: ThreadId,
+    ) -> bool {
+        let role = self
+            .thread_state_manager
+            .connection_role(thread_id, request_id.connection_id)
+            .await;
+        if role == ThreadSubscriptionRole::Observer {
+            self.send_invalid_request_error(
+                request_id.clone(),
+                format!("connection observes thread {thread_id} and cannot drive it"),
+            )
+            .await;
+            return false;
+        }
+        true
+    }
+
     async fn resume_thread_from_history(
         &self,
         request_id: ConnectionRequestId,
@@ -5521,24 +5912,29 @@ impl CodexMessageProcessor {
         request_id: ConnectionRequestId,
         params: TurnStartParams,
         app_server_client_name: Option<String>,
+        client_identity: Option<String>,
     ) {
         if let Err(error) = Self::validate_v2_input_limit(&params.input) {
             self.outgoing.send_error(request_id, error).await;
             return;
         }
-        let (_, thread) = match self.load_thread(&params.thread_id).await {
+        let (thread_id, thread) = match self.load_thread(&params.thread_id).await {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
         if let Err(error) =
             Self::set_app_server_client_name(thread.as_ref(), app_server_client_name).await
         {
             self.outgoing.send_error(request_id, error).await;
             return;
         }
+        Self::record_client_identity(thread.as_ref(), thread_id, client_identity).await;
 
         let collaboration_modes_config = CollaborationModesConfig {
             default_mode_request_user_input: thread.enabled(Feature::DefaultModeRequestUserInput),
@@ -5613,6 +6009,26 @@ impl CodexMessageProcessor {
         }
     }
 
+    /// Records the transport-level identity of the connection on the thread's metadata.
+    /// Failures are logged rather than surfaced: the identity is an audit aid, not a gate.
+    async fn record_client_identity(
+        thread: &CodexThread,
+        thread_id: ThreadId,
+        client_identity: Option<String>,
+    ) {
+        let (Some(client_identity), Some(state_db)) = (client_identity, thread.state_db()) else {
+            return;
+        };
+        if let Err(err) = state_db
+            .record_thread_client_identity(thread_id, &client_identity, Utc::now())
+            .await
+        {
+            warn!(
+                "failed to record client identity {client_identity} for thread {thread_id}: {err}"
+            );
+        }
+    }
+
     async fn set_app_server_client_name(
         thread: &CodexThread,
         app_server_client_name: Option<String>,
@@ -5628,13 +6044,16 @@ impl CodexMessageProcessor {
     }
 
     async fn turn_steer(&self, request_id: ConnectionRequestId, params: TurnSteerParams) {
-        let (_, thread) = match self.load_thread(&params.thread_id).await {
+        let (thread_id, thread) = match self.load_thread(&params.thread_id).await {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
                 return;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return;
+        }
 
         if params.expected_turn_id.is_empty() {
             self.send_invalid_request_error(
@@ -5700,6 +6119,9 @@ impl CodexMessageProcessor {
                 return None;
             }
         };
+        if !self.ensure_thread_controller(&request_id, thread_id).await {
+            return None;
+        }
 
         match self
             .ensure_conversation_listener(
@@ -6049,6 +6471,12 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if !self
+            .ensure_thread_controller(&request_id, parent_thread_id)
+            .await
+        {
+            return;
+        }
 
         let (review_request, display_text) = match Self::review_request_from_target(target) {
             Ok(value) => value,
@@ -6105,6 +6533,12 @@ impl CodexMessageProcessor {
                 return;
             }
         };
+        if !self
+            .ensure_thread_controller(&request_id, thread_uuid)
+            .await
+        {
+            return;
+        }
 
         let request = request_id.clone();
 
@@ -6325,11 +6759,15 @@ impl CodexMessageProcessor {
                                 .await;
                         }
 
+                        let request_routing = thread_state_manager
+                            .server_request_routing(conversation_id)
+                            .await;
                         let thread_outgoing = ThreadScopedOutgoingMessageSender::new(
                             outgoing_for_task.clone(),
                             subscribed_connection_ids,
                             conversation_id,
-                        );
+                        )
+                        .with_request_routing(request_routing);
                         apply_bespoke_event_handling(
                             event.clone(),
                             conversation_id,
@@ -6398,6 +6836,7 @@ impl CodexMessageProcessor {
             query,
             roots,
             cancellation_token,
+            mode,
         } = params;
 
         let cancel_flag = match cancellation_token.clone() {
@@ -6417,7 +6856,10 @@ impl CodexMessageProcessor {
 
         let results = match query.as_str() {
             "" => vec![],
-            _ => run_fuzzy_file_search(query, roots, cancel_flag.clone()).await,
+            _ => {
+                run_fuzzy_file_search(query, roots, mode.unwrap_or_default(), cancel_flag.clone())
+                    .await
+            }
         };
 
         if let Some(token) = cancellation_token {
@@ -6438,7 +6880,11 @@ impl CodexMessageProcessor {
         request_id: ConnectionRequestId,
         params: FuzzyFileSearchSessionStartParams,
     ) {
-        let FuzzyFileSearchSessionStartParams { session_id, roots } = params;
+        let FuzzyFileSearchSessionStartParams {
+            session_id,
+            roots,
+            mode,
+        } = params;
         if session_id.is_empty() {
             let error = JSONRPCErrorError {
                 code: INVALID_REQUEST_ERROR_CODE,
@@ -6449,8 +6895,12 @@ impl CodexMessageProcessor {
             return;
         }
 
-        let session =
-            start_fuzzy_file_search_session(session_id.clone(), roots, self.outgoing.clone());
+        let session = start_fuzzy_file_search_session(
+            session_id.clone(),
+            roots,
+            mode.unwrap_or_default(),
+            self.outgoing.clone(),
+        );
         match session {
             Ok(session) => {
                 let mut sessions = self.fuzzy_search_sessions.lock().await;
@@ -6838,9 +7288,16 @@ async fn handle_pending_thread_resume_request(
         reasoning_effort,
     };
     outgoing.send_response(request_id, response).await;
-    outgoing
-        .replay_requests_to_connection_for_thread(connection_id, conversation_id)
-        .await;
+    // Observers never receive server requests, so only controllers get pending ones replayed.
+    if thread_state_manager
+        .connection_role(conversation_id, connection_id)
+        .await
+        == ThreadSubscriptionRole::Controller
+    {
+        outgoing
+            .replay_requests_to_connection_for_thread(connection_id, conversation_id)
+            .await;
+    }
     let _attached = thread_state_manager
         .try_add_connection_to_thread(conversation_id, connection_id)
         .await;
@@ -7218,6 +7675,24 @@ async fn read_history_cwd_from_state_db(
     }
 }
 
+/// Returns the client identities recorded for a thread, or none when the state DB is
+/// unavailable. Like `record_client_identity`, failures are logged rather than surfaced.
+async fn read_client_identities(
+    state_db_ctx: Option<&StateDbHandle>,
+    thread_id: ThreadId,
+) -> Vec<String> {
+    let Some(state_db_ctx) = state_db_ctx else {
+        return Vec::new();
+    };
+    match state_db_ctx.list_thread_client_identities(thread_id).await {
+        Ok(identities) => identities,
+        Err(err) => {
+            warn!("failed to read client identities for thread {thread_id}: {err}");
+            Vec::new()
+        }
+    }
+}
+
 async fn read_summary_from_state_db_by_thread_id(
     config: &Config,
     thread_id: ThreadId,
@@ -7621,6 +8096,7 @@ fn build_thread_from_snapshot(
         source: config_snapshot.session_source.clone().into(),
         git_info: None,
         name: None,
+        client_identities: Vec::new(),
         turns: Vec::new(),
     }
 }
@@ -7663,6 +8139,7 @@ pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
         source: source.into(),
         git_info,
         name: None,
+        client_identities: Vec::new(),
         turns: Vec::new(),
     }
 }
@@ -7672,6 +8149,7 @@ mod tests {
     use super::*;
     use crate::outgoing_message::OutgoingEnvelope;
     use crate::outgoing_message::OutgoingMessage;
+    use crate::outgoing_message::ServerRequestRouting;
     use anyhow::Result;
     use codex_app_server_protocol::ServerRequestPayload;
     use codex_app_server_protocol::ToolRequestUserInputParams;
@@ -7749,6 +8227,8 @@ mod tests {
             base_instructions: None,
             developer_instructions: None,
             personality: None,
+            role: None,
+            server_request_ownership: None,
             persist_extended_history: false,
         };
         let config_snapshot = ThreadConfigSnapshot {
@@ -8125,4 +8605,136 @@ mod tests {
         assert!(!manager.has_subscribers(thread_id).await);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn implicit_subscription_observes_a_held_thread() -> Result<()> {
+        let manager = ThreadStateManager::new();
+        let thread_id = ThreadId::from_string("ad7f0408-99b8-4f6e-a46f-bd0eec433370")?;
+        let holder = ConnectionId(1);
+        let joiner = ConnectionId(2);
+        let granted = ConnectionId(3);
+        for connection in [holder, joiner, granted] {
+            manager.connection_initialized(connection).await;
+        }
+        manager
+            .set_connection_role(thread_id, granted, ThreadSubscriptionRole::Controller)
+            .await;
+        for connection in [holder, joiner, granted] {
+            manager
+                .try_ensure_connection_subscribed(thread_id, connection, false)
+                .await
+                .expect("connection should be live");
+        }
+
+        assert_eq!(
+            manager.connection_role(thread_id, holder).await,
+            ThreadSubscriptionRole::Controller
+        );
+        assert_eq!(
+            manager.connection_role(thread_id, joiner).await,
+            ThreadSubscriptionRole::Observer
+        );
+        assert_eq!(
+            manager.connection_role(thread_id, granted).await,
+            ThreadSubscriptionRole::Controller
+        );
+        assert_eq!(
+            manager.server_request_routing(thread_id).await.responders,
+            vec![holder, granted]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn server_requests_route_to_controllers_per_ownership() -> Result<()> {
+        let manager = ThreadStateManager::new();
+        let thread_id = ThreadId::from_string("ad7f0408-99b8-4f6e-a46f-bd0eec433370")?;
+        let controller = ConnectionId(1);
+        let observer = ConnectionId(2);
+        let approver = ConnectionId(3);
+        for connection in [controller, observer, approver] {
+            manager.connection_initialized(connection).await;
+            manager
+                .try_ensure_connection_subscribed(thread_id, connection, false)
+                .await
+                .expect("connection should be live");
+        }
+        // Joining a thread others hold observes it unless the controller role is granted.
+        manager
+            .set_connection_role(thread_id, approver, ThreadSubscriptionRole::Controller)
+            .await;
+
+        assert_eq!(
+            manager.connection_role(thread_id, observer).await,
+            ThreadSubscriptionRole::Observer
+        );
+        assert_eq!(
+            manager.server_request_routing(thread_id).await,
+            ServerRequestRouting {
+                responders: vec![controller, approver],
+                ownership: ServerRequestOwnership::FirstController,
+            }
+        );
+
+        // The first controller owns the thread; nobody else may change ownership.
+        assert!(
+            !manager
+                .set_server_request_ownership(
+                    thread_id,
+                    ServerRequestOwnership::OwnerOnly,
+                    approver,
+                )
+                .await
+        );
+        assert!(
+            manager
+                .set_server_request_ownership(
+                    thread_id,
+                    ServerRequestOwnership::OwnerOnly,
+                    controller,
+                )
+                .await
+        );
+        assert_eq!(
+            manager.server_request_routing(thread_id).await,
+            ServerRequestRouting {
+                responders: vec![controller],
+                ownership: ServerRequestOwnership::OwnerOnly,
+            }
+        );
+
+        manager.remove_connection(controller).await;
+        assert_eq!(
+            manager.server_request_routing(thread_id).await,
+            ServerRequestRouting {
+                responders: vec![approver],
+                ownership: ServerRequestOwnership::FirstController,
+            }
+        );
+
+        // With the owner gone, the next controller to join takes over.
+        manager
+            .set_connection_role(thread_id, approver, ThreadSubscriptionRole::Controller)
+            .await;
+        assert_eq!(
+            manager.server_request_routing(thread_id).await,
+            ServerRequestRouting {
+                responders: vec![approver],
+                ownership: ServerRequestOwnership::OwnerOnly,
+            }
+        );
+
+        // A connection that is not subscribed observes while others hold the thread.
+        manager.remove_connection(observer).await;
+        assert_eq!(
+            manager.connection_role(thread_id, observer).await,
+            ThreadSubscriptionRole::Observer
+        );
+        manager.remove_connection(approver).await;
+        assert_eq!(
+            manager.connection_role(thread_id, observer).await,
+            ThreadSubscriptionRole::Controller
+        );
+        Ok(())
+    }
 }
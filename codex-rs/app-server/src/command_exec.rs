@@ -739,6 +739,7 @@ mod tests {
             network_sandbox_policy: NetworkSandboxPolicy::from(&sandbox_policy),
             justification: None,
             arg0: None,
+            seccomp_profiles: Vec::new(),
         }
     }
 
@@ -850,6 +851,7 @@ mod tests {
                     network_sandbox_policy: NetworkSandboxPolicy::from(&sandbox_policy),
                     justification: None,
                     arg0: None,
+                    seccomp_profiles: Vec::new(),
                 },
                 started_network_proxy: None,
                 tty: false,
@@ -5,6 +5,8 @@ use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use codex_app_server_protocol::FuzzyFileSearchContentMatch;
+use codex_app_server_protocol::FuzzyFileSearchMode;
 use codex_app_server_protocol::FuzzyFileSearchResult;
 use codex_app_server_protocol::FuzzyFileSearchSessionCompletedNotification;
 use codex_app_server_protocol::FuzzyFileSearchSessionUpdatedNotification;
@@ -20,6 +22,7 @@ const MAX_THREADS: usize = 12;
 pub(crate) async fn run_fuzzy_file_search(
     query: String,
     roots: Vec<String>,
+    mode: FuzzyFileSearchMode,
     cancellation_flag: Arc<AtomicBool>,
 ) -> Vec<FuzzyFileSearchResult> {
     if roots.is_empty() {
@@ -45,6 +48,7 @@ pub(crate) async fn run_fuzzy_file_search(
                 limit,
                 threads,
                 compute_indices: true,
+                mode: file_search_mode(mode),
                 ..Default::default()
             },
             Some(cancellation_flag),
@@ -52,20 +56,7 @@ pub(crate) async fn run_fuzzy_file_search(
     })
     .await
     {
-        Ok(Ok(res)) => res
-            .matches
-            .into_iter()
-            .map(|m| {
-                let file_name = m.path.file_name().unwrap_or_default();
-                FuzzyFileSearchResult {
-                    root: m.root.to_string_lossy().to_string(),
-                    path: m.path.to_string_lossy().to_string(),
-                    file_name: file_name.to_string_lossy().to_string(),
-                    score: m.score,
-                    indices: m.indices,
-                }
-            })
-            .collect::<Vec<_>>(),
+        Ok(Ok(res)) => res.matches.iter().map(to_result).collect::<Vec<_>>(),
         Ok(Err(err)) => {
             warn!("fuzzy-file-search failed: {err}");
             Vec::new()
@@ -113,6 +104,7 @@ impl Drop for FuzzyFileSearchSession {
 pub(crate) fn start_fuzzy_file_search_session(
     session_id: String,
     roots: Vec<String>,
+    mode: FuzzyFileSearchMode,
     outgoing: Arc<OutgoingMessageSender>,
 ) -> anyhow::Result<FuzzyFileSearchSession> {
     #[expect(clippy::expect_used)]
@@ -143,6 +135,7 @@ pub(crate) fn start_fuzzy_file_search_session(
             limit,
             threads,
             compute_indices: true,
+            mode: file_search_mode(mode),
             ..Default::default()
         },
         reporter,
@@ -223,20 +216,7 @@ impl file_search::SessionReporter for SessionReporterImpl {
 }
 
 fn collect_files(snapshot: &file_search::FileSearchSnapshot) -> Vec<FuzzyFileSearchResult> {
-    let mut files = snapshot
-        .matches
-        .iter()
-        .map(|m| {
-            let file_name = m.path.file_name().unwrap_or_default();
-            FuzzyFileSearchResult {
-                root: m.root.to_string_lossy().to_string(),
-                path: m.path.to_string_lossy().to_string(),
-                file_name: file_name.to_string_lossy().to_string(),
-                score: m.score,
-                indices: m.indices.clone(),
-            }
-        })
-        .collect::<Vec<_>>();
+    let mut files = snapshot.matches.iter().map(to_result).collect::<Vec<_>>();
 
     files.sort_by(file_search::cmp_by_score_desc_then_path_asc::<
         FuzzyFileSearchResult,
@@ -245,3 +225,30 @@ fn collect_files(snapshot: &file_search::FileSearchSnapshot) -> Vec<FuzzyFileSea
     >(|f| f.score, |f| f.path.as_str()));
     files
 }
+
+fn file_search_mode(mode: FuzzyFileSearchMode) -> file_search::FileSearchMode {
+    match mode {
+        FuzzyFileSearchMode::Path => file_search::FileSearchMode::Path,
+        FuzzyFileSearchMode::ContentLiteral => file_search::FileSearchMode::ContentLiteral,
+        FuzzyFileSearchMode::ContentRegex => file_search::FileSearchMode::ContentRegex,
+    }
+}
+
+fn to_result(m: &file_search::FileMatch) -> FuzzyFileSearchResult {
+    let file_name = m.path.file_name().unwrap_or_default();
+    FuzzyFileSearchResult {
+        root: m.root.to_string_lossy().to_string(),
+        path: m.path.to_string_lossy().to_string(),
+        file_name: file_name.to_string_lossy().to_string(),
+        score: m.score,
+        indices: m.indices.clone(),
+        content: m
+            .content
+            .as_ref()
+            .map(|content| FuzzyFileSearchContentMatch {
+                line_number: u32::try_from(content.line_number).unwrap_or(u32::MAX),
+                column: u32::try_from(content.column).unwrap_or(u32::MAX),
+                line: content.line.clone(),
+            }),
+    }
+}
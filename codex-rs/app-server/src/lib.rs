@@ -28,7 +28,9 @@ use crate::transport::OutboundConnectionState;
 use crate::transport::TransportEvent;
 use crate::transport::route_outgoing_envelope;
 use crate::transport::start_stdio_connection;
+use crate::transport::start_unix_socket_acceptor;
 use crate::transport::start_websocket_acceptor;
+use crate::transport_auth::WebSocketAuth;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::JSONRPCMessage;
@@ -72,10 +74,12 @@ mod server_request_error;
 mod thread_state;
 mod thread_status;
 mod transport;
+mod transport_auth;
 
 pub use crate::error_code::INPUT_TOO_LARGE_ERROR_CODE;
 pub use crate::error_code::INVALID_PARAMS_ERROR_CODE;
 pub use crate::transport::AppServerTransport;
+pub use crate::transport_auth::AppServerAuthArgs;
 
 const LOG_FORMAT_ENV_VAR: &str = "LOG_FORMAT";
 
@@ -341,6 +345,25 @@ pub async fn run_main_with_transport(
     loader_overrides: LoaderOverrides,
     default_analytics_enabled: bool,
     transport: AppServerTransport,
+) -> IoResult<()> {
+    run_main_with_transport_and_auth(
+        arg0_paths,
+        cli_config_overrides,
+        loader_overrides,
+        default_analytics_enabled,
+        transport,
+        AppServerAuthArgs::default(),
+    )
+    .await
+}
+
+pub async fn run_main_with_transport_and_auth(
+    arg0_paths: Arg0DispatchPaths,
+    cli_config_overrides: CliConfigOverrides,
+    loader_overrides: LoaderOverrides,
+    default_analytics_enabled: bool,
+    transport: AppServerTransport,
+    auth_args: AppServerAuthArgs,
 ) -> IoResult<()> {
     let (transport_event_tx, mut transport_event_rx) =
         mpsc::channel::<TransportEvent>(CHANNEL_CAPACITY);
@@ -350,27 +373,42 @@ pub async fn run_main_with_transport(
 
     enum TransportRuntime {
         Stdio,
-        WebSocket {
+        Listener {
             accept_handle: JoinHandle<()>,
             shutdown_token: CancellationToken,
         },
     }
 
     let mut stdio_handles = Vec::<JoinHandle<()>>::new();
-    let transport_runtime = match transport {
+    let transport_runtime = match transport.clone() {
         AppServerTransport::Stdio => {
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
             TransportRuntime::Stdio
         }
         AppServerTransport::WebSocket { bind_address } => {
+            let auth = WebSocketAuth::from_args(&auth_args)?;
             let shutdown_token = CancellationToken::new();
             let accept_handle = start_websocket_acceptor(
                 bind_address,
+                auth,
+                transport_event_tx.clone(),
+                shutdown_token.clone(),
+            )
+            .await?;
+            TransportRuntime::Listener {
+                accept_handle,
+                shutdown_token,
+            }
+        }
+        AppServerTransport::Unix { socket_path } => {
+            let shutdown_token = CancellationToken::new();
+            let accept_handle = start_unix_socket_acceptor(
+                socket_path,
                 transport_event_tx.clone(),
                 shutdown_token.clone(),
             )
             .await?;
-            TransportRuntime::WebSocket {
+            TransportRuntime::Listener {
                 accept_handle,
                 shutdown_token,
             }
@@ -601,8 +639,8 @@ pub async fn run_main_with_transport(
         let mut thread_created_rx = processor.thread_created_receiver();
         let mut running_turn_count_rx = processor.subscribe_running_assistant_turn_count();
         let mut connections = HashMap::<ConnectionId, ConnectionState>::new();
-        let websocket_accept_shutdown = match &transport_runtime {
-            TransportRuntime::WebSocket { shutdown_token, .. } => Some(shutdown_token.clone()),
+        let listener_accept_shutdown = match &transport_runtime {
+            TransportRuntime::Listener { shutdown_token, .. } => Some(shutdown_token.clone()),
             TransportRuntime::Stdio => None,
         };
         async move {
@@ -617,7 +655,7 @@ pub async fn run_main_with_transport(
                     shutdown_state.update(running_turn_count, connections.len()),
                     ShutdownAction::Finish
                 ) {
-                    if let Some(shutdown_token) = &websocket_accept_shutdown {
+                    if let Some(shutdown_token) = &listener_accept_shutdown {
                         shutdown_token.cancel();
                     }
                     let _ = outbound_control_tx
@@ -648,6 +686,7 @@ pub async fn run_main_with_transport(
                                 connection_id,
                                 writer,
                                 disconnect_sender,
+                                client_identity,
                             } => {
                                 let outbound_initialized = Arc::new(AtomicBool::new(false));
                                 let outbound_experimental_api_enabled =
@@ -678,6 +717,7 @@ pub async fn run_main_with_transport(
                                         outbound_initialized,
                                         outbound_experimental_api_enabled,
                                         outbound_opted_out_notification_methods,
+                                        client_identity,
                                     ),
                                 );
                             }
@@ -709,7 +749,7 @@ pub async fn run_main_with_transport(
                                             .process_request(
                                                 connection_id,
                                                 request,
-                                                transport,
+                                                &transport,
                                                 &mut connection_state.session,
                                                 &connection_state.outbound_initialized,
                                             )
@@ -742,7 +782,7 @@ pub async fn run_main_with_transport(
                                             warn!("dropping response from unknown connection: {connection_id:?}");
                                             continue;
                                         }
-                                        processor.process_response(response).await;
+                                        processor.process_response(connection_id, response).await;
                                     }
                                     JSONRPCMessage::Notification(notification) => {
                                         if !connections.contains_key(&connection_id) {
@@ -756,7 +796,7 @@ pub async fn run_main_with_transport(
                                             warn!("dropping error from unknown connection: {connection_id:?}");
                                             continue;
                                         }
-                                        processor.process_error(err).await;
+                                        processor.process_error(connection_id, err).await;
                                     }
                                 }
                             }
@@ -802,7 +842,7 @@ pub async fn run_main_with_transport(
     let _ = processor_handle.await;
     let _ = outbound_handle.await;
 
-    if let TransportRuntime::WebSocket {
+    if let TransportRuntime::Listener {
         accept_handle,
         shutdown_token,
     } = transport_runtime
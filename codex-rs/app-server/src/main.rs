@@ -1,6 +1,7 @@
 use clap::Parser;
+use codex_app_server::AppServerAuthArgs;
 use codex_app_server::AppServerTransport;
-use codex_app_server::run_main_with_transport;
+use codex_app_server::run_main_with_transport_and_auth;
 use codex_arg0::Arg0DispatchPaths;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_core::config_loader::LoaderOverrides;
@@ -14,13 +15,16 @@ const MANAGED_CONFIG_PATH_ENV_VAR: &str = "CODEX_APP_SERVER_MANAGED_CONFIG_PATH"
 #[derive(Debug, Parser)]
 struct AppServerArgs {
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `unix://PATH`.
     #[arg(
         long = "listen",
         value_name = "URL",
         default_value = AppServerTransport::DEFAULT_LISTEN_URL
     )]
     listen: AppServerTransport,
+
+    #[command(flatten)]
+    auth: AppServerAuthArgs,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -33,12 +37,13 @@ fn main() -> anyhow::Result<()> {
         };
         let transport = args.listen;
 
-        run_main_with_transport(
+        run_main_with_transport_and_auth(
             arg0_paths,
             CliConfigOverrides::default(),
             loader_overrides,
             false,
             transport,
+            args.auth,
         )
         .await?;
         Ok(())
@@ -13,6 +13,7 @@ use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::transport::AppServerTransport;
+use crate::transport_auth::ClientIdentity;
 use async_trait::async_trait;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
@@ -145,6 +146,8 @@ pub(crate) struct ConnectionSessionState {
     pub(crate) opted_out_notification_methods: HashSet<String>,
     pub(crate) app_server_client_name: Option<String>,
     pub(crate) client_version: Option<String>,
+    /// Identity established by the transport (Unix peer, bearer token or client certificate).
+    pub(crate) client_identity: Option<ClientIdentity>,
 }
 
 pub(crate) struct MessageProcessorArgs {
@@ -232,7 +235,7 @@ impl MessageProcessor {
         &mut self,
         connection_id: ConnectionId,
         request: JSONRPCRequest,
-        transport: AppServerTransport,
+        transport: &AppServerTransport,
         session: &mut ConnectionSessionState,
         outbound_initialized: &AtomicBool,
     ) {
@@ -451,6 +454,10 @@ impl MessageProcessor {
                             connection_id,
                             other,
                             session.app_server_client_name.clone(),
+                            session
+                                .client_identity
+                                .as_ref()
+                                .map(ToString::to_string),
                         )
                         .boxed()
                         .await;
@@ -501,16 +508,24 @@ impl MessageProcessor {
     }
 
     /// Handle a standalone JSON-RPC response originating from the peer.
-    pub(crate) async fn process_response(&mut self, response: JSONRPCResponse) {
+    pub(crate) async fn process_response(
+        &mut self,
+        connection_id: ConnectionId,
+        response: JSONRPCResponse,
+    ) {
         tracing::info!("<- response: {:?}", response);
         let JSONRPCResponse { id, result, .. } = response;
-        self.outgoing.notify_client_response(id, result).await
+        self.outgoing
+            .notify_client_response(connection_id, id, result)
+            .await
     }
 
     /// Handle an error object received from the peer.
-    pub(crate) async fn process_error(&mut self, err: JSONRPCError) {
+    pub(crate) async fn process_error(&mut self, connection_id: ConnectionId, err: JSONRPCError) {
         tracing::error!("<- error: {:?}", err);
-        self.outgoing.notify_client_error(err.id, err.error).await;
+        self.outgoing
+            .notify_client_error(connection_id, err.id, err.error)
+            .await;
     }
 
     async fn handle_config_read(&self, request_id: ConnectionRequestId, params: ConfigReadParams) {
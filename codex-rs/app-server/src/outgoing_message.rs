@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
@@ -8,6 +9,7 @@ use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::Result;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
+use codex_app_server_protocol::ServerRequestOwnership;
 use codex_app_server_protocol::ServerRequestPayload;
 use codex_protocol::ThreadId;
 use serde::Serialize;
@@ -17,6 +19,7 @@ use tokio::sync::oneshot;
 use tracing::warn;
 
 use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::server_request_error::TURN_TRANSITION_PENDING_REQUEST_ERROR_REASON;
 
 #[cfg(test)]
@@ -57,13 +60,73 @@ pub(crate) struct OutgoingMessageSender {
 pub(crate) struct ThreadScopedOutgoingMessageSender {
     outgoing: Arc<OutgoingMessageSender>,
     connection_ids: Arc<Vec<ConnectionId>>,
+    request_routing: Arc<ServerRequestRouting>,
     thread_id: ThreadId,
 }
 
+/// Which connections receive a thread's server requests and how their answers combine.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ServerRequestRouting {
+    pub(crate) responders: Vec<ConnectionId>,
+    pub(crate) ownership: ServerRequestOwnership,
+}
+
 struct PendingCallbackEntry {
     callback: oneshot::Sender<ClientRequestResult>,
     thread_id: Option<ThreadId>,
     request: ServerRequest,
+    /// Connections allowed to answer. `None` accepts an answer from any connection.
+    responders: Option<HashSet<ConnectionId>>,
+    ownership: ServerRequestOwnership,
+    /// Answers collected so far when every controller has to answer.
+    answers: HashMap<ConnectionId, Result>,
+}
+
+impl PendingCallbackEntry {
+    fn accepts_answer_from(&self, connection_id: ConnectionId) -> bool {
+        self.responders
+            .as_ref()
+            .is_none_or(|responders| responders.contains(&connection_id))
+    }
+
+    /// A connection that (re)subscribes may pick up the request unless it is reserved for
+    /// an owner that is still connected.
+    fn accepts_new_responder(&self) -> bool {
+        self.ownership != ServerRequestOwnership::OwnerOnly
+            || self.responders.as_ref().is_some_and(HashSet::is_empty)
+    }
+
+    /// Under `AllControllers`, the combined outcome once every responder has answered. With
+    /// no controller left to agree, the request fails instead of waiting forever.
+    fn agreed_outcome(&self) -> Option<ClientRequestResult> {
+        let responders = self.responders.as_ref()?;
+        if responders.is_empty() {
+            return Some(Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "no controller is subscribed to answer the server request".to_string(),
+                data: None,
+            }));
+        }
+        if responders
+            .iter()
+            .any(|connection_id| !self.answers.contains_key(connection_id))
+        {
+            return None;
+        }
+        let mut answers = responders
+            .iter()
+            .filter_map(|connection_id| self.answers.get(connection_id));
+        let first = answers.next()?;
+        if answers.all(|answer| answer == first) {
+            Some(Ok(first.clone()))
+        } else {
+            Some(Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "controllers sent conflicting answers to the server request".to_string(),
+                data: None,
+            }))
+        }
+    }
 }
 
 impl ThreadScopedOutgoingMessageSender {
@@ -72,22 +135,35 @@ impl ThreadScopedOutgoingMessageSender {
         connection_ids: Vec<ConnectionId>,
         thread_id: ThreadId,
     ) -> Self {
+        let request_routing = ServerRequestRouting {
+            responders: connection_ids.clone(),
+            ownership: ServerRequestOwnership::FirstController,
+        };
         Self {
             outgoing,
             connection_ids: Arc::new(connection_ids),
+            request_routing: Arc::new(request_routing),
             thread_id,
         }
     }
 
+    /// Restricts server requests to the thread's eligible controllers. Notifications still
+    /// go to every subscribed connection.
+    pub(crate) fn with_request_routing(mut self, request_routing: ServerRequestRouting) -> Self {
+        self.request_routing = Arc::new(request_routing);
+        self
+    }
+
     pub(crate) async fn send_request(
         &self,
         payload: ServerRequestPayload,
     ) -> (RequestId, oneshot::Receiver<ClientRequestResult>) {
         self.outgoing
             .send_request_to_connections(
-                Some(self.connection_ids.as_slice()),
+                Some(self.request_routing.responders.as_slice()),
                 payload,
                 Some(self.thread_id),
+                self.request_routing.ownership,
             )
             .await
     }
@@ -149,7 +225,13 @@ impl OutgoingMessageSender {
         &self,
         request: ServerRequestPayload,
     ) -> (RequestId, oneshot::Receiver<ClientRequestResult>) {
-        self.send_request_to_connections(None, request, None).await
+        self.send_request_to_connections(
+            None,
+            request,
+            None,
+            ServerRequestOwnership::FirstController,
+        )
+        .await
     }
 
     fn next_request_id(&self) -> RequestId {
@@ -161,22 +243,33 @@ impl OutgoingMessageSender {
         connection_ids: Option<&[ConnectionId]>,
         request: ServerRequestPayload,
         thread_id: Option<ThreadId>,
+        ownership: ServerRequestOwnership,
     ) -> (RequestId, oneshot::Receiver<ClientRequestResult>) {
         let id = self.next_request_id();
         let outgoing_message_id = id.clone();
         let request = request.request_with_id(outgoing_message_id.clone());
 
         let (tx_approve, rx_approve) = oneshot::channel();
+        let entry = PendingCallbackEntry {
+            callback: tx_approve,
+            thread_id,
+            request: request.clone(),
+            responders: connection_ids
+                .map(|connection_ids| connection_ids.iter().copied().collect()),
+            ownership,
+            answers: HashMap::new(),
+        };
+        if ownership == ServerRequestOwnership::AllControllers
+            && let Some(outcome) = entry.agreed_outcome()
+        {
+            if let Err(err) = entry.callback.send(outcome) {
+                warn!("could not notify callback for {outgoing_message_id:?} due to: {err:?}");
+            }
+            return (outgoing_message_id, rx_approve);
+        }
         {
             let mut request_id_to_callback = self.request_id_to_callback.lock().await;
-            request_id_to_callback.insert(
-                id,
-                PendingCallbackEntry {
-                    callback: tx_approve,
-                    thread_id,
-                    request: request.clone(),
-                },
-            );
+            request_id_to_callback.insert(id, entry);
         }
 
         let outgoing_message = OutgoingMessage::Request(request);
@@ -218,12 +311,28 @@ impl OutgoingMessageSender {
         (outgoing_message_id, rx_approve)
     }
 
+    /// Re-sends the thread's pending requests to a controller that (re)subscribed, adding it
+    /// to the connections allowed to answer them.
     pub(crate) async fn replay_requests_to_connection_for_thread(
         &self,
         connection_id: ConnectionId,
         thread_id: ThreadId,
     ) {
-        let requests = self.pending_requests_for_thread(thread_id).await;
+        let requests = {
+            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
+            let mut requests = request_id_to_callback
+                .values_mut()
+                .filter(|entry| entry.thread_id == Some(thread_id) && entry.accepts_new_responder())
+                .map(|entry| {
+                    if let Some(responders) = entry.responders.as_mut() {
+                        responders.insert(connection_id);
+                    }
+                    entry.request.clone()
+                })
+                .collect::<Vec<_>>();
+            requests.sort_by(|left, right| left.id().cmp(right.id()));
+            requests
+        };
         for request in requests {
             if let Err(err) = self
                 .sender
@@ -238,23 +347,62 @@ impl OutgoingMessageSender {
         }
     }
 
-    pub(crate) async fn notify_client_response(&self, id: RequestId, result: Result) {
-        let entry = self.take_request_callback(&id).await;
-
-        match entry {
-            Some((id, entry)) => {
-                if let Err(err) = entry.callback.send(Ok(result)) {
-                    warn!("could not notify callback for {id:?} due to: {err:?}");
-                }
-            }
-            None => {
+    pub(crate) async fn notify_client_response(
+        &self,
+        connection_id: ConnectionId,
+        id: RequestId,
+        result: Result,
+    ) {
+        let entry = {
+            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
+            let Some(entry) = request_id_to_callback.get_mut(&id) else {
                 warn!("could not find callback for {id:?}");
+                return;
+            };
+            if !entry.accepts_answer_from(connection_id) {
+                warn!("ignoring response for {id:?} from connection {connection_id:?}");
+                return;
             }
+            let outcome = if entry.ownership == ServerRequestOwnership::AllControllers {
+                entry.answers.insert(connection_id, result);
+                let Some(outcome) = entry.agreed_outcome() else {
+                    return;
+                };
+                outcome
+            } else {
+                Ok(result)
+            };
+            request_id_to_callback
+                .remove(&id)
+                .map(|entry| (entry, outcome))
+        };
+
+        if let Some((entry, outcome)) = entry
+            && let Err(err) = entry.callback.send(outcome)
+        {
+            warn!("could not notify callback for {id:?} due to: {err:?}");
         }
     }
 
-    pub(crate) async fn notify_client_error(&self, id: RequestId, error: JSONRPCErrorError) {
-        let entry = self.take_request_callback(&id).await;
+    /// An error from any connection allowed to answer resolves the request, even when the
+    /// thread requires every controller to agree.
+    pub(crate) async fn notify_client_error(
+        &self,
+        connection_id: ConnectionId,
+        id: RequestId,
+        error: JSONRPCErrorError,
+    ) {
+        let entry = {
+            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
+            match request_id_to_callback.get(&id) {
+                Some(entry) if !entry.accepts_answer_from(connection_id) => {
+                    warn!("ignoring error for {id:?} from connection {connection_id:?}");
+                    return;
+                }
+                Some(_) => request_id_to_callback.remove_entry(&id),
+                None => None,
+            }
+        };
 
         match entry {
             Some((id, entry)) => {
@@ -269,6 +417,43 @@ impl OutgoingMessageSender {
         }
     }
 
+    /// Drops a closed connection from the responders of pending requests. Requests that
+    /// were only waiting on that connection's agreement resolve with the remaining answers.
+    pub(crate) async fn connection_closed(&self, connection_id: ConnectionId) {
+        let resolved = {
+            let mut request_id_to_callback = self.request_id_to_callback.lock().await;
+            let mut agreed = Vec::new();
+            for (request_id, entry) in request_id_to_callback.iter_mut() {
+                let Some(responders) = entry.responders.as_mut() else {
+                    continue;
+                };
+                if !responders.remove(&connection_id) {
+                    continue;
+                }
+                entry.answers.remove(&connection_id);
+                if entry.ownership == ServerRequestOwnership::AllControllers
+                    && let Some(outcome) = entry.agreed_outcome()
+                {
+                    agreed.push((request_id.clone(), outcome));
+                }
+            }
+            agreed
+                .into_iter()
+                .filter_map(|(request_id, outcome)| {
+                    request_id_to_callback
+                        .remove(&request_id)
+                        .map(|entry| (request_id, entry, outcome))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (request_id, entry, outcome) in resolved {
+            if let Err(err) = entry.callback.send(outcome) {
+                warn!("could not notify callback for {request_id:?} due to: {err:?}");
+            }
+        }
+    }
+
     pub(crate) async fn cancel_request(&self, id: &RequestId) -> bool {
         self.take_request_callback(id).await.is_some()
     }
@@ -281,6 +466,7 @@ impl OutgoingMessageSender {
         request_id_to_callback.remove_entry(id)
     }
 
+    #[cfg(test)]
     pub(crate) async fn pending_requests_for_thread(
         &self,
         thread_id: ThreadId,
@@ -508,6 +694,7 @@ mod tests {
     use codex_app_server_protocol::ModelReroutedNotification;
     use codex_app_server_protocol::RateLimitSnapshot;
     use codex_app_server_protocol::RateLimitWindow;
+    use codex_app_server_protocol::ThreadSubscriptionRole;
     use codex_app_server_protocol::ToolRequestUserInputParams;
     use codex_protocol::ThreadId;
     use pretty_assertions::assert_eq;
@@ -780,7 +967,7 @@ mod tests {
         };
 
         outgoing
-            .notify_client_error(request_id, error.clone())
+            .notify_client_error(ConnectionId(1), request_id, error.clone())
             .await;
 
         let result = timeout(Duration::from_secs(1), wait_for_result)
@@ -906,4 +1093,295 @@ mod tests {
                 .is_empty()
         );
     }
+
+    fn file_change_approval(thread_id: ThreadId, item_id: &str) -> ServerRequestPayload {
+        ServerRequestPayload::FileChangeRequestApproval(FileChangeRequestApprovalParams {
+            thread_id: thread_id.to_string(),
+            turn_id: "turn-1".to_string(),
+            item_id: item_id.to_string(),
+            reason: None,
+            grant_root: None,
+        })
+    }
+
+    fn routed_thread_sender(
+        outgoing: &Arc<OutgoingMessageSender>,
+        thread_id: ThreadId,
+        responders: Vec<ConnectionId>,
+        ownership: ServerRequestOwnership,
+    ) -> ThreadScopedOutgoingMessageSender {
+        ThreadScopedOutgoingMessageSender::new(
+            outgoing.clone(),
+            vec![ConnectionId(1), ConnectionId(2), ConnectionId(3)],
+            thread_id,
+        )
+        .with_request_routing(ServerRequestRouting {
+            responders,
+            ownership,
+        })
+    }
+
+    #[tokio::test]
+    async fn routed_requests_only_reach_and_accept_answers_from_responders() {
+        let (tx, mut rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let thread_id = ThreadId::new();
+        let thread_outgoing = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1)],
+            ServerRequestOwnership::FirstController,
+        );
+
+        let (request_id, mut waiter) = thread_outgoing
+            .send_request(file_change_approval(thread_id, "call-1"))
+            .await;
+        let Some(OutgoingEnvelope::ToConnection { connection_id, .. }) = rx.recv().await else {
+            panic!("expected targeted request envelope");
+        };
+        assert_eq!(connection_id, ConnectionId(1));
+        assert!(rx.try_recv().is_err());
+
+        outgoing
+            .notify_client_response(ConnectionId(2), request_id.clone(), json!({ "ok": true }))
+            .await;
+        assert!(waiter.try_recv().is_err());
+
+        outgoing
+            .notify_client_response(ConnectionId(1), request_id, json!({ "ok": false }))
+            .await;
+        let result = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should not time out")
+            .expect("waiter should receive a callback");
+        assert_eq!(result, Ok(json!({ "ok": false })));
+    }
+
+    #[tokio::test]
+    async fn all_controllers_must_send_matching_answers() {
+        let (tx, _rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let thread_id = ThreadId::new();
+        let thread_outgoing = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1), ConnectionId(2)],
+            ServerRequestOwnership::AllControllers,
+        );
+
+        let (agreed_id, mut agreed_waiter) = thread_outgoing
+            .send_request(file_change_approval(thread_id, "call-1"))
+            .await;
+        let (disputed_id, disputed_waiter) = thread_outgoing
+            .send_request(file_change_approval(thread_id, "call-2"))
+            .await;
+
+        outgoing
+            .notify_client_response(ConnectionId(1), agreed_id.clone(), json!({ "ok": true }))
+            .await;
+        assert!(agreed_waiter.try_recv().is_err());
+        outgoing
+            .notify_client_response(ConnectionId(2), agreed_id, json!({ "ok": true }))
+            .await;
+        assert_eq!(
+            agreed_waiter
+                .await
+                .expect("waiter should receive a callback"),
+            Ok(json!({ "ok": true }))
+        );
+
+        outgoing
+            .notify_client_response(ConnectionId(1), disputed_id.clone(), json!({ "ok": true }))
+            .await;
+        outgoing
+            .notify_client_response(ConnectionId(2), disputed_id, json!({ "ok": false }))
+            .await;
+        let result = disputed_waiter
+            .await
+            .expect("waiter should receive a callback");
+        assert_eq!(
+            result.map_err(|error| error.code),
+            Err(INVALID_REQUEST_ERROR_CODE)
+        );
+    }
+
+    #[tokio::test]
+    async fn closing_a_controller_resolves_requests_waiting_on_its_agreement() {
+        let (tx, _rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let thread_id = ThreadId::new();
+        let thread_outgoing = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1), ConnectionId(2)],
+            ServerRequestOwnership::AllControllers,
+        );
+
+        let (request_id, waiter) = thread_outgoing
+            .send_request(file_change_approval(thread_id, "call-1"))
+            .await;
+        outgoing
+            .notify_client_response(ConnectionId(1), request_id, json!({ "ok": true }))
+            .await;
+        outgoing.connection_closed(ConnectionId(2)).await;
+
+        let result = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should not time out")
+            .expect("waiter should receive a callback");
+        assert_eq!(result, Ok(json!({ "ok": true })));
+    }
+
+    #[tokio::test]
+    async fn all_controllers_request_fails_when_no_controller_remains() {
+        let (tx, _rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let thread_id = ThreadId::new();
+
+        let (_request_id, waiter) = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            Vec::new(),
+            ServerRequestOwnership::AllControllers,
+        )
+        .send_request(file_change_approval(thread_id, "call-1"))
+        .await;
+        let result = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should not time out")
+            .expect("waiter should receive a callback");
+        assert_eq!(
+            result.map_err(|error| error.code),
+            Err(INVALID_REQUEST_ERROR_CODE)
+        );
+
+        let (_request_id, waiter) = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1)],
+            ServerRequestOwnership::AllControllers,
+        )
+        .send_request(file_change_approval(thread_id, "call-2"))
+        .await;
+        outgoing.connection_closed(ConnectionId(1)).await;
+        let result = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should not time out")
+            .expect("waiter should receive a callback");
+        assert_eq!(
+            result.map_err(|error| error.code),
+            Err(INVALID_REQUEST_ERROR_CODE)
+        );
+    }
+
+    #[tokio::test]
+    async fn self_declared_controller_cannot_answer_owner_only_requests() {
+        let manager = crate::thread_state::ThreadStateManager::new();
+        let thread_id = ThreadId::new();
+        let owner = ConnectionId(1);
+        let intruder = ConnectionId(2);
+        for connection in [owner, intruder] {
+            manager.connection_initialized(connection).await;
+            manager
+                .try_ensure_connection_subscribed(thread_id, connection, false)
+                .await
+                .expect("connection should be live");
+        }
+        // Claiming the controller role grants nothing beyond what the owner allows.
+        manager
+            .set_connection_role(thread_id, intruder, ThreadSubscriptionRole::Controller)
+            .await;
+        assert!(
+            !manager
+                .set_server_request_ownership(
+                    thread_id,
+                    ServerRequestOwnership::FirstController,
+                    intruder,
+                )
+                .await
+        );
+        assert!(
+            manager
+                .set_server_request_ownership(thread_id, ServerRequestOwnership::OwnerOnly, owner)
+                .await
+        );
+
+        let (tx, mut rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let (request_id, mut waiter) = ThreadScopedOutgoingMessageSender::new(
+            outgoing.clone(),
+            manager.subscribed_connection_ids(thread_id).await,
+            thread_id,
+        )
+        .with_request_routing(manager.server_request_routing(thread_id).await)
+        .send_request(file_change_approval(thread_id, "call-1"))
+        .await;
+        let Some(OutgoingEnvelope::ToConnection { connection_id, .. }) = rx.recv().await else {
+            panic!("expected targeted request envelope");
+        };
+        assert_eq!(connection_id, owner);
+        assert!(rx.try_recv().is_err());
+
+        outgoing
+            .notify_client_response(intruder, request_id.clone(), json!({ "ok": true }))
+            .await;
+        assert!(waiter.try_recv().is_err());
+
+        outgoing
+            .notify_client_response(owner, request_id, json!({ "ok": false }))
+            .await;
+        let result = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait should not time out")
+            .expect("waiter should receive a callback");
+        assert_eq!(result, Ok(json!({ "ok": false })));
+    }
+
+    #[tokio::test]
+    async fn replay_skips_requests_reserved_for_a_connected_owner() {
+        let (tx, mut rx) = mpsc::channel::<OutgoingEnvelope>(8);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let thread_id = ThreadId::new();
+
+        let (shared_id, shared_waiter) = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1)],
+            ServerRequestOwnership::FirstController,
+        )
+        .send_request(file_change_approval(thread_id, "call-1"))
+        .await;
+        let (_reserved_id, _reserved_waiter) = routed_thread_sender(
+            &outgoing,
+            thread_id,
+            vec![ConnectionId(1)],
+            ServerRequestOwnership::OwnerOnly,
+        )
+        .send_request(file_change_approval(thread_id, "call-2"))
+        .await;
+        while rx.try_recv().is_ok() {}
+
+        outgoing
+            .replay_requests_to_connection_for_thread(ConnectionId(3), thread_id)
+            .await;
+        let Some(OutgoingEnvelope::ToConnection {
+            connection_id,
+            message: OutgoingMessage::Request(request),
+        }) = rx.recv().await
+        else {
+            panic!("expected replayed request envelope");
+        };
+        assert_eq!((connection_id, request.id()), (ConnectionId(3), &shared_id));
+        assert!(rx.try_recv().is_err());
+
+        outgoing
+            .notify_client_response(ConnectionId(3), shared_id, json!({ "ok": true }))
+            .await;
+        assert_eq!(
+            shared_waiter
+                .await
+                .expect("waiter should receive a callback"),
+            Ok(json!({ "ok": true }))
+        );
+    }
 }
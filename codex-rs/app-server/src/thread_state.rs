@@ -1,7 +1,10 @@
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
+use crate::outgoing_message::ServerRequestRouting;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ServerRequestOwnership;
 use codex_app_server_protocol::ThreadHistoryBuilder;
+use codex_app_server_protocol::ThreadSubscriptionRole;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnError;
 use codex_core::CodexThread;
@@ -119,6 +122,17 @@ impl ThreadState {
 struct ThreadEntry {
     state: Arc<Mutex<ThreadState>>,
     connection_ids: HashSet<ConnectionId>,
+    // Subscribers that observe the thread: late joiners without a controller
+    // grant and connections demoted through `set_connection_role`. Every other
+    // subscriber is a controller.
+    observer_ids: HashSet<ConnectionId>,
+    // Connections granted the controller role through `set_connection_role`,
+    // possibly before they subscribed.
+    granted_controller_ids: HashSet<ConnectionId>,
+    request_ownership: ServerRequestOwnership,
+    // The controller that started the thread, or the first controller to join
+    // while no owner was connected. Only the owner may change request ownership.
+    owner: Option<ConnectionId>,
 }
 
 impl Default for ThreadEntry {
@@ -126,6 +140,72 @@ impl Default for ThreadEntry {
         Self {
             state: Arc::new(Mutex::new(ThreadState::default())),
             connection_ids: HashSet::new(),
+            observer_ids: HashSet::new(),
+            granted_controller_ids: HashSet::new(),
+            request_ownership: ServerRequestOwnership::default(),
+            owner: None,
+        }
+    }
+}
+
+impl ThreadEntry {
+    fn forget_connection(&mut self, connection_id: ConnectionId) {
+        self.connection_ids.remove(&connection_id);
+        self.observer_ids.remove(&connection_id);
+        self.granted_controller_ids.remove(&connection_id);
+        if self.owner == Some(connection_id) {
+            self.owner = None;
+        }
+    }
+
+    /// Adds `connection_id` to the subscribers. A connection without an explicit
+    /// role drives a thread nobody else is subscribed to and observes one that
+    /// others hold.
+    fn subscribe(&mut self, connection_id: ConnectionId) {
+        let others_subscribed = !self.connection_ids.is_empty();
+        if !self.connection_ids.insert(connection_id) || self.observer_ids.contains(&connection_id)
+        {
+            return;
+        }
+        if others_subscribed && !self.granted_controller_ids.contains(&connection_id) {
+            self.observer_ids.insert(connection_id);
+        } else {
+            self.admit_controller(connection_id);
+        }
+    }
+
+    /// Records `connection_id` as a controller, making it the owner when the
+    /// thread has none.
+    fn admit_controller(&mut self, connection_id: ConnectionId) {
+        self.observer_ids.remove(&connection_id);
+        if self.owner.is_none() {
+            self.owner = Some(connection_id);
+        }
+    }
+
+    fn request_routing(&self) -> ServerRequestRouting {
+        let mut controllers = self
+            .connection_ids
+            .difference(&self.observer_ids)
+            .copied()
+            .collect::<Vec<_>>();
+        controllers.sort_by_key(|connection_id| connection_id.0);
+        match self.request_ownership {
+            ServerRequestOwnership::OwnerOnly => match self.owner {
+                Some(owner) if controllers.contains(&owner) => ServerRequestRouting {
+                    responders: vec![owner],
+                    ownership: ServerRequestOwnership::OwnerOnly,
+                },
+                // Without a connected owner, any controller may pick the request up.
+                _ => ServerRequestRouting {
+                    responders: controllers,
+                    ownership: ServerRequestOwnership::FirstController,
+                },
+            },
+            ownership => ServerRequestRouting {
+                responders: controllers,
+                ownership,
+            },
         }
     }
 }
@@ -164,6 +244,84 @@ impl ThreadStateManager {
             .unwrap_or_default()
     }
 
+    /// Connections that receive the thread's server requests, and how their answers combine.
+    pub(crate) async fn server_request_routing(&self, thread_id: ThreadId) -> ServerRequestRouting {
+        let state = self.state.lock().await;
+        state
+            .threads
+            .get(&thread_id)
+            .map(ThreadEntry::request_routing)
+            .unwrap_or_default()
+    }
+
+    /// Role of `connection_id` on the thread. A connection that is not subscribed
+    /// observes the thread while other connections are subscribed to it, and may only
+    /// drive it once nobody else is.
+    pub(crate) async fn connection_role(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+    ) -> ThreadSubscriptionRole {
+        let state = self.state.lock().await;
+        let Some(thread_entry) = state.threads.get(&thread_id) else {
+            return ThreadSubscriptionRole::Controller;
+        };
+        let observes = if thread_entry.connection_ids.contains(&connection_id) {
+            thread_entry.observer_ids.contains(&connection_id)
+        } else {
+            !thread_entry.connection_ids.is_empty()
+        };
+        if observes {
+            ThreadSubscriptionRole::Observer
+        } else {
+            ThreadSubscriptionRole::Controller
+        }
+    }
+
+    pub(crate) async fn set_connection_role(
+        &self,
+        thread_id: ThreadId,
+        connection_id: ConnectionId,
+        role: ThreadSubscriptionRole,
+    ) {
+        let mut state = self.state.lock().await;
+        if !state.live_connections.contains(&connection_id) {
+            return;
+        }
+        let thread_entry = state.threads.entry(thread_id).or_default();
+        match role {
+            ThreadSubscriptionRole::Controller => {
+                thread_entry.granted_controller_ids.insert(connection_id);
+                thread_entry.admit_controller(connection_id);
+            }
+            ThreadSubscriptionRole::Observer => {
+                thread_entry.granted_controller_ids.remove(&connection_id);
+                thread_entry.observer_ids.insert(connection_id);
+                if thread_entry.owner == Some(connection_id) {
+                    thread_entry.owner = None;
+                }
+            }
+        }
+    }
+
+    /// Replaces the thread's server request ownership. Only the thread's owner
+    /// may do so, and `OwnerOnly` always reserves requests for the owner. Returns
+    /// false, leaving ownership unchanged, for any other connection.
+    pub(crate) async fn set_server_request_ownership(
+        &self,
+        thread_id: ThreadId,
+        ownership: ServerRequestOwnership,
+        connection_id: ConnectionId,
+    ) -> bool {
+        let mut state = self.state.lock().await;
+        let thread_entry = state.threads.entry(thread_id).or_default();
+        if thread_entry.owner != Some(connection_id) {
+            return false;
+        }
+        thread_entry.request_ownership = ownership;
+        true
+    }
+
     pub(crate) async fn thread_state(&self, thread_id: ThreadId) -> Arc<Mutex<ThreadState>> {
         let mut state = self.state.lock().await;
         state.threads.entry(thread_id).or_default().state.clone()
@@ -222,7 +380,7 @@ impl ThreadStateManager {
                 }
             }
             if let Some(thread_entry) = state.threads.get_mut(&thread_id) {
-                thread_entry.connection_ids.remove(&connection_id);
+                thread_entry.forget_connection(connection_id);
             }
         };
 
@@ -255,7 +413,7 @@ impl ThreadStateManager {
                 .or_default()
                 .insert(thread_id);
             let thread_entry = state.threads.entry(thread_id).or_default();
-            thread_entry.connection_ids.insert(connection_id);
+            thread_entry.subscribe(connection_id);
             thread_entry.state.clone()
         };
         {
@@ -285,8 +443,7 @@ impl ThreadStateManager {
             .threads
             .entry(thread_id)
             .or_default()
-            .connection_ids
-            .insert(connection_id);
+            .subscribe(connection_id);
         true
     }
 
@@ -298,10 +455,9 @@ impl ThreadStateManager {
                 .thread_ids_by_connection
                 .remove(&connection_id)
                 .unwrap_or_default();
-            for thread_id in &thread_ids {
-                if let Some(thread_entry) = state.threads.get_mut(thread_id) {
-                    thread_entry.connection_ids.remove(&connection_id);
-                }
+            // Roles can be recorded before a subscription exists, so scrub every thread.
+            for thread_entry in state.threads.values_mut() {
+                thread_entry.forget_connection(connection_id);
             }
             thread_ids
                 .into_iter()
@@ -798,6 +798,7 @@ mod tests {
             source,
             git_info: None,
             name: None,
+            client_identities: Vec::new(),
             turns: Vec::new(),
         }
     }
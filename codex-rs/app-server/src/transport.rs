@@ -4,6 +4,9 @@ use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingError;
 use crate::outgoing_message::OutgoingMessage;
+use crate::transport_auth::ClientIdentity;
+use crate::transport_auth::WebSocketAuth;
+use crate::transport_auth::client_certificate_identity;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_app_server_protocol::ServerRequest;
@@ -17,6 +20,7 @@ use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -24,6 +28,8 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
@@ -31,8 +37,16 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tokio_tungstenite::accept_async_with_config;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::accept_hdr_async_with_config;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse as HandshakeErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request as HandshakeRequest;
+use tokio_tungstenite::tungstenite::handshake::server::Response as HandshakeResponse;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::header::WWW_AUTHENTICATE;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
@@ -51,34 +65,69 @@ fn colorize(text: &str, style: Style) -> String {
 }
 
 #[allow(clippy::print_stderr)]
-fn print_websocket_startup_banner(addr: SocketAddr) {
+fn print_websocket_startup_banner(addr: SocketAddr, auth: &WebSocketAuth) {
     let title = colorize("codex app-server (WebSockets)", Style::new().bold().cyan());
     let listening_label = colorize("listening on:", Style::new().dimmed());
-    let listen_url = colorize(&format!("ws://{addr}"), Style::new().green());
+    let scheme = if auth.tls_acceptor().is_some() {
+        "wss"
+    } else {
+        "ws"
+    };
+    let listen_url = colorize(&format!("{scheme}://{addr}"), Style::new().green());
     let note_label = colorize("note:", Style::new().dimmed());
     eprintln!("{title}");
     eprintln!("  {listening_label} {listen_url}");
-    if addr.ip().is_loopback() {
+    if auth.is_authenticated() {
+        let mut methods = Vec::new();
+        if auth.requires_token() {
+            methods.push("bearer token");
+        }
+        if auth.requires_client_certificate() {
+            methods.push("client certificate");
+        }
+        eprintln!(
+            "  {note_label} client authentication: {}",
+            methods.join(" + ")
+        );
+    } else if addr.ip().is_loopback() {
         eprintln!(
             "  {note_label} binds localhost only (use SSH port-forwarding for remote access)"
         );
     } else {
         eprintln!(
-            "  {note_label} this is a raw WS server; consider running behind TLS/auth for real remote use"
+            "  {note_label} no client authentication configured; consider --ws-token-file or --tls-client-ca for real remote use"
         );
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg(unix)]
+#[allow(clippy::print_stderr)]
+fn print_unix_socket_startup_banner(socket_path: &std::path::Path) {
+    let title = colorize("codex app-server (Unix socket)", Style::new().bold().cyan());
+    let listening_label = colorize("listening on:", Style::new().dimmed());
+    let listen_url = colorize(
+        &format!("unix://{}", socket_path.display()),
+        Style::new().green(),
+    );
+    let note_label = colorize("note:", Style::new().dimmed());
+    eprintln!("{title}");
+    eprintln!("  {listening_label} {listen_url}");
+    eprintln!("  {note_label} access is limited by the socket file permissions (0600 by default)");
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppServerTransport {
     Stdio,
     WebSocket { bind_address: SocketAddr },
+    Unix { socket_path: PathBuf },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum AppServerTransportParseError {
     UnsupportedListenUrl(String),
     InvalidWebSocketListenUrl(String),
+    InvalidUnixListenUrl(String),
 }
 
 impl std::fmt::Display for AppServerTransportParseError {
@@ -86,12 +135,16 @@ impl std::fmt::Display for AppServerTransportParseError {
         match self {
             AppServerTransportParseError::UnsupportedListenUrl(listen_url) => write!(
                 f,
-                "unsupported --listen URL `{listen_url}`; expected `stdio://` or `ws://IP:PORT`"
+                "unsupported --listen URL `{listen_url}`; expected `stdio://`, `ws://IP:PORT` or `unix://PATH`"
             ),
             AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url) => write!(
                 f,
                 "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT`"
             ),
+            AppServerTransportParseError::InvalidUnixListenUrl(listen_url) => write!(
+                f,
+                "invalid unix socket --listen URL `{listen_url}`; expected `unix://PATH`"
+            ),
         }
     }
 }
@@ -113,6 +166,17 @@ impl AppServerTransport {
             return Ok(Self::WebSocket { bind_address });
         }
 
+        if let Some(socket_path) = listen_url.strip_prefix("unix://") {
+            if socket_path.is_empty() {
+                return Err(AppServerTransportParseError::InvalidUnixListenUrl(
+                    listen_url.to_string(),
+                ));
+            }
+            return Ok(Self::Unix {
+                socket_path: PathBuf::from(socket_path),
+            });
+        }
+
         Err(AppServerTransportParseError::UnsupportedListenUrl(
             listen_url.to_string(),
         ))
@@ -133,6 +197,7 @@ pub(crate) enum TransportEvent {
         connection_id: ConnectionId,
         writer: mpsc::Sender<OutgoingMessage>,
         disconnect_sender: Option<CancellationToken>,
+        client_identity: Option<ClientIdentity>,
     },
     ConnectionClosed {
         connection_id: ConnectionId,
@@ -155,12 +220,16 @@ impl ConnectionState {
         outbound_initialized: Arc<AtomicBool>,
         outbound_experimental_api_enabled: Arc<AtomicBool>,
         outbound_opted_out_notification_methods: Arc<RwLock<HashSet<String>>>,
+        client_identity: Option<ClientIdentity>,
     ) -> Self {
         Self {
             outbound_initialized,
             outbound_experimental_api_enabled,
             outbound_opted_out_notification_methods,
-            session: ConnectionSessionState::default(),
+            session: ConnectionSessionState {
+                client_identity,
+                ..Default::default()
+            },
         }
     }
 }
@@ -213,6 +282,7 @@ pub(crate) async fn start_stdio_connection(
             connection_id,
             writer: writer_tx,
             disconnect_sender: None,
+            client_identity: None,
         })
         .await
         .map_err(|_| std::io::Error::new(ErrorKind::BrokenPipe, "processor unavailable"))?;
@@ -271,13 +341,14 @@ pub(crate) async fn start_stdio_connection(
 
 pub(crate) async fn start_websocket_acceptor(
     bind_address: SocketAddr,
+    auth: WebSocketAuth,
     transport_event_tx: mpsc::Sender<TransportEvent>,
     shutdown_token: CancellationToken,
 ) -> IoResult<JoinHandle<()>> {
     let listener = TcpListener::bind(bind_address).await?;
     let local_addr = listener.local_addr()?;
-    print_websocket_startup_banner(local_addr);
-    info!("app-server websocket listening on ws://{local_addr}");
+    print_websocket_startup_banner(local_addr, &auth);
+    info!("app-server websocket listening on {local_addr}");
 
     let connection_counter = Arc::new(AtomicU64::new(1));
     Ok(tokio::spawn(async move {
@@ -294,10 +365,12 @@ pub(crate) async fn start_websocket_acceptor(
                             let connection_id =
                                 ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
                             let transport_event_tx_for_connection = transport_event_tx.clone();
+                            let auth = auth.clone();
                             tokio::spawn(async move {
                                 run_websocket_connection(
                                     connection_id,
                                     stream,
+                                    auth,
                                     transport_event_tx_for_connection,
                                 )
                                 .await;
@@ -316,16 +389,73 @@ pub(crate) async fn start_websocket_acceptor(
 async fn run_websocket_connection(
     connection_id: ConnectionId,
     stream: TcpStream,
+    auth: WebSocketAuth,
     transport_event_tx: mpsc::Sender<TransportEvent>,
 ) {
-    let websocket_stream =
-        match accept_async_with_config(stream, Some(WebSocketConfig::default())).await {
-            Ok(stream) => stream,
-            Err(err) => {
-                warn!("failed to complete websocket handshake: {err}");
-                return;
+    let Some(tls_acceptor) = auth.tls_acceptor().cloned() else {
+        run_websocket_handshake(connection_id, stream, &auth, None, transport_event_tx).await;
+        return;
+    };
+    let tls_stream = match tls_acceptor.accept(stream).await {
+        Ok(tls_stream) => tls_stream,
+        Err(err) => {
+            warn!("failed to complete TLS handshake: {err}");
+            return;
+        }
+    };
+    let certificate_identity =
+        client_certificate_identity(tls_stream.get_ref().1.peer_certificates());
+    run_websocket_handshake(
+        connection_id,
+        tls_stream,
+        &auth,
+        certificate_identity,
+        transport_event_tx,
+    )
+    .await;
+}
+
+/// Completes the WebSocket upgrade, rejecting it with `401` when a bearer token is required
+/// and missing. A token identity takes precedence over the client certificate fingerprint.
+async fn run_websocket_handshake<S>(
+    connection_id: ConnectionId,
+    stream: S,
+    auth: &WebSocketAuth,
+    certificate_identity: Option<ClientIdentity>,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let mut token_identity = None;
+    let handshake = accept_hdr_async_with_config(
+        stream,
+        |request: &HandshakeRequest, response: HandshakeResponse| {
+            let authorization = request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+            match auth.authenticate_bearer(authorization) {
+                Ok(identity) => {
+                    token_identity = identity;
+                    Ok(response)
+                }
+                Err(()) => Err(unauthorized_response()),
             }
-        };
+        },
+        Some(WebSocketConfig::default()),
+    )
+    .await;
+    let websocket_stream = match handshake {
+        Ok(stream) => stream,
+        Err(err) => {
+            warn!("failed to complete websocket handshake: {err}");
+            return;
+        }
+    };
+    let client_identity = token_identity.or(certificate_identity);
+    if let Some(client_identity) = &client_identity {
+        info!(%client_identity, "websocket client authenticated");
+    }
 
     let (writer_tx, writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
     let writer_tx_for_reader = writer_tx.clone();
@@ -335,6 +465,7 @@ async fn run_websocket_connection(
             connection_id,
             writer: writer_tx,
             disconnect_sender: Some(disconnect_token.clone()),
+            client_identity,
         })
         .await
         .is_err()
@@ -376,15 +507,24 @@ async fn run_websocket_connection(
         .await;
 }
 
-async fn run_websocket_outbound_loop(
-    mut websocket_writer: futures::stream::SplitSink<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-        WebSocketMessage,
-    >,
+fn unauthorized_response() -> HandshakeErrorResponse {
+    let mut response =
+        HandshakeErrorResponse::new(Some("missing or invalid bearer token".to_string()));
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+async fn run_websocket_outbound_loop<S>(
+    mut websocket_writer: futures::stream::SplitSink<WebSocketStream<S>, WebSocketMessage>,
     mut writer_rx: mpsc::Receiver<OutgoingMessage>,
     mut writer_control_rx: mpsc::Receiver<WebSocketMessage>,
     disconnect_token: CancellationToken,
-) {
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     loop {
         tokio::select! {
             _ = disconnect_token.cancelled() => {
@@ -413,16 +553,16 @@ async fn run_websocket_outbound_loop(
     }
 }
 
-async fn run_websocket_inbound_loop(
-    mut websocket_reader: futures::stream::SplitStream<
-        tokio_tungstenite::WebSocketStream<TcpStream>,
-    >,
+async fn run_websocket_inbound_loop<S>(
+    mut websocket_reader: futures::stream::SplitStream<WebSocketStream<S>>,
     transport_event_tx: mpsc::Sender<TransportEvent>,
     writer_tx_for_reader: mpsc::Sender<OutgoingMessage>,
     writer_control_tx: mpsc::Sender<WebSocketMessage>,
     connection_id: ConnectionId,
     disconnect_token: CancellationToken,
-) {
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     loop {
         tokio::select! {
             _ = disconnect_token.cancelled() => {
@@ -468,6 +608,239 @@ async fn run_websocket_inbound_loop(
     }
 }
 
+#[cfg(unix)]
+pub(crate) async fn start_unix_socket_acceptor(
+    socket_path: PathBuf,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+    shutdown_token: CancellationToken,
+) -> IoResult<JoinHandle<()>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    remove_stale_unix_socket(&socket_path).await?;
+    let listener = bind_owner_only_unix_socket(&socket_path)?;
+    // Only the owning user may connect; widen the mode (e.g. to a shared group) explicitly
+    // with chmod after startup if other local accounts need access. The socket is already
+    // 0600 when it appears at `socket_path`; this only guards against a surprising rename.
+    if let Err(err) = std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))
+    {
+        let _ = std::fs::remove_file(&socket_path);
+        return Err(err);
+    }
+    print_unix_socket_startup_banner(&socket_path);
+    info!("app-server listening on unix://{}", socket_path.display());
+
+    let connection_counter = Arc::new(AtomicU64::new(1));
+    Ok(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    info!("unix socket acceptor shutting down");
+                    break;
+                }
+                accept_result = listener.accept() => {
+                    match accept_result {
+                        Ok((stream, _peer_addr)) => {
+                            let client_identity = match stream.peer_cred() {
+                                Ok(credentials) => Some(ClientIdentity::UnixPeer {
+                                    uid: credentials.uid(),
+                                    pid: credentials.pid(),
+                                }),
+                                Err(err) => {
+                                    warn!("failed to read unix socket peer credentials: {err}");
+                                    None
+                                }
+                            };
+                            info!(?client_identity, "unix socket client connected");
+                            let connection_id =
+                                ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
+                            let transport_event_tx_for_connection = transport_event_tx.clone();
+                            tokio::spawn(async move {
+                                run_unix_socket_connection(
+                                    connection_id,
+                                    stream,
+                                    client_identity,
+                                    transport_event_tx_for_connection,
+                                )
+                                .await;
+                            });
+                        }
+                        Err(err) => {
+                            error!("failed to accept unix socket connection: {err}");
+                        }
+                    }
+                }
+            }
+        }
+        if let Err(err) = std::fs::remove_file(&socket_path)
+            && err.kind() != ErrorKind::NotFound
+        {
+            warn!(
+                "failed to remove unix socket {}: {err}",
+                socket_path.display()
+            );
+        }
+    }))
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn start_unix_socket_acceptor(
+    socket_path: PathBuf,
+    _transport_event_tx: mpsc::Sender<TransportEvent>,
+    _shutdown_token: CancellationToken,
+) -> IoResult<JoinHandle<()>> {
+    Err(std::io::Error::new(
+        ErrorKind::Unsupported,
+        format!(
+            "unix://{} is not supported on this platform",
+            socket_path.display()
+        ),
+    ))
+}
+
+/// Binds the socket inside a fresh 0700 directory, restricts it to 0600 and only then
+/// renames it to `socket_path`, so no other user can connect in the window between
+/// `bind` (which applies the umask) and `chmod`.
+#[cfg(unix)]
+fn bind_owner_only_unix_socket(
+    socket_path: &std::path::Path,
+) -> IoResult<tokio::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let parent = socket_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    // `tempfile` creates the directory with mode 0700; it is removed again on drop.
+    let staging_dir = tempfile::Builder::new()
+        .prefix(".codex-sock")
+        .tempdir_in(parent)?;
+    let staging_path = staging_dir.path().join("s");
+    let listener = tokio::net::UnixListener::bind(&staging_path)?;
+    std::fs::set_permissions(&staging_path, std::fs::Permissions::from_mode(0o600))?;
+    std::fs::rename(&staging_path, socket_path)?;
+    Ok(listener)
+}
+
+/// Removes a socket file left behind by a previous server, refusing to touch anything that
+/// is not a socket or that still accepts connections.
+#[cfg(unix)]
+async fn remove_stale_unix_socket(socket_path: &std::path::Path) -> IoResult<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match tokio::fs::symlink_metadata(socket_path).await {
+        Ok(metadata) if metadata.file_type().is_socket() => {}
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", socket_path.display()),
+            ));
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    }
+    if tokio::net::UnixStream::connect(socket_path).await.is_ok() {
+        return Err(std::io::Error::new(
+            ErrorKind::AddrInUse,
+            format!(
+                "another server is already listening on {}",
+                socket_path.display()
+            ),
+        ));
+    }
+    tokio::fs::remove_file(socket_path).await
+}
+
+#[cfg(unix)]
+async fn run_unix_socket_connection(
+    connection_id: ConnectionId,
+    stream: tokio::net::UnixStream,
+    client_identity: Option<ClientIdentity>,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) {
+    let (writer_tx, mut writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
+    let writer_tx_for_reader = writer_tx.clone();
+    let disconnect_token = CancellationToken::new();
+    if transport_event_tx
+        .send(TransportEvent::ConnectionOpened {
+            connection_id,
+            writer: writer_tx,
+            disconnect_sender: Some(disconnect_token.clone()),
+            client_identity,
+        })
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let (read_half, mut write_half) = stream.into_split();
+    let outbound_disconnect_token = disconnect_token.clone();
+    let mut outbound_task = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = outbound_disconnect_token.cancelled() => break,
+                outgoing_message = writer_rx.recv() => {
+                    let Some(outgoing_message) = outgoing_message else {
+                        break;
+                    };
+                    let Some(mut json) = serialize_outgoing_message(outgoing_message) else {
+                        continue;
+                    };
+                    json.push('\n');
+                    if let Err(err) = write_half.write_all(json.as_bytes()).await {
+                        warn!("failed to write to unix socket: {err}");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    let inbound_disconnect_token = disconnect_token.clone();
+    let transport_event_tx_for_reader = transport_event_tx.clone();
+    let mut inbound_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(read_half).lines();
+        loop {
+            tokio::select! {
+                _ = inbound_disconnect_token.cancelled() => break,
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        if !forward_incoming_message(
+                            &transport_event_tx_for_reader,
+                            &writer_tx_for_reader,
+                            connection_id,
+                            &line,
+                        )
+                        .await
+                        {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("unix socket receive error: {err}");
+                        break;
+                    }
+                },
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = &mut outbound_task => {
+            disconnect_token.cancel();
+            inbound_task.abort();
+        }
+        _ = &mut inbound_task => {
+            disconnect_token.cancel();
+            outbound_task.abort();
+        }
+    }
+
+    let _ = transport_event_tx
+        .send(TransportEvent::ConnectionClosed { connection_id })
+        .await;
+}
+
 async fn forward_incoming_message(
     transport_event_tx: &mpsc::Sender<TransportEvent>,
     writer: &mpsc::Sender<OutgoingMessage>,
@@ -717,8 +1090,100 @@ mod tests {
             .expect_err("unsupported scheme should fail");
         assert_eq!(
             err.to_string(),
-            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://` or `ws://IP:PORT`"
+            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://`, `ws://IP:PORT` or `unix://PATH`"
+        );
+    }
+
+    #[test]
+    fn app_server_transport_parses_unix_listen_url() {
+        let transport = AppServerTransport::from_listen_url("unix:///run/user/1000/codex.sock")
+            .expect("unix listen URL should parse");
+        assert_eq!(
+            transport,
+            AppServerTransport::Unix {
+                socket_path: PathBuf::from("/run/user/1000/codex.sock"),
+            }
+        );
+
+        let err = AppServerTransport::from_listen_url("unix://")
+            .expect_err("empty socket path should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "invalid unix socket --listen URL `unix://`; expected `unix://PATH`"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_socket_acceptor_records_peer_identity_and_cleans_up() {
+        use std::os::unix::fs::MetadataExt;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = temp_dir.path().join("app-server.sock");
+        // A leftover socket file from a crashed server must not block startup.
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).expect("bind stale socket"));
+        let (transport_event_tx, mut transport_event_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let shutdown_token = CancellationToken::new();
+        let accept_handle = start_unix_socket_acceptor(
+            socket_path.clone(),
+            transport_event_tx,
+            shutdown_token.clone(),
+        )
+        .await
+        .expect("unix socket acceptor should start");
+
+        let metadata = std::fs::metadata(&socket_path).expect("socket metadata");
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        // The staging directory the socket was bound in is gone.
+        assert_eq!(
+            std::fs::read_dir(temp_dir.path())
+                .expect("read temp dir")
+                .count(),
+            1
+        );
+
+        let mut client = tokio::net::UnixStream::connect(&socket_path)
+            .await
+            .expect("connect to unix socket");
+        let opened = timeout(Duration::from_secs(5), transport_event_rx.recv())
+            .await
+            .expect("connection should open")
+            .expect("transport event");
+        let TransportEvent::ConnectionOpened {
+            connection_id,
+            client_identity,
+            ..
+        } = opened
+        else {
+            panic!("expected connection opened event");
+        };
+        assert_eq!(
+            client_identity.as_ref().map(ToString::to_string),
+            Some(format!("unix:uid={}", metadata.uid()))
         );
+
+        client
+            .write_all(b"{\"method\":\"initialized\"}\n")
+            .await
+            .expect("write to unix socket");
+        let incoming = timeout(Duration::from_secs(5), transport_event_rx.recv())
+            .await
+            .expect("message should arrive")
+            .expect("transport event");
+        let TransportEvent::IncomingMessage {
+            connection_id: incoming_connection_id,
+            message: JSONRPCMessage::Notification(notification),
+        } = incoming
+        else {
+            panic!("expected incoming notification");
+        };
+        assert_eq!(incoming_connection_id, connection_id);
+        assert_eq!(notification.method, "initialized");
+
+        shutdown_token.cancel();
+        accept_handle.await.expect("acceptor should exit");
+        assert!(!socket_path.exists());
     }
 
     #[tokio::test]
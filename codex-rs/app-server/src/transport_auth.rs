@@ -0,0 +1,358 @@
+//! Client authentication for the app-server listeners.
+//!
+//! Unix domain sockets rely on file permissions and record the connecting peer's
+//! credentials. WebSocket listeners can require a bearer token (from `--ws-token-file`),
+//! a client certificate signed by `--tls-client-ca`, or both.
+
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::pem::PemObject;
+use rustls::server::WebPkiClientVerifier;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fmt;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_rustls::TlsAcceptor;
+
+/// Authentication options for `--listen ws://…`. They are ignored by the other transports.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct AppServerAuthArgs {
+    /// File with one `<identity> <token>` pair per line. When set, WebSocket clients must
+    /// send `Authorization: Bearer <token>` with the upgrade request.
+    #[arg(long = "ws-token-file", value_name = "PATH")]
+    pub ws_token_file: Option<PathBuf>,
+
+    /// PEM certificate chain served by the WebSocket listener (enables TLS).
+    #[arg(long = "tls-cert", value_name = "PATH", requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key for `--tls-cert`.
+    #[arg(long = "tls-key", value_name = "PATH", requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// PEM CA bundle used to require and verify client certificates (mTLS).
+    #[arg(long = "tls-client-ca", value_name = "PATH", requires = "tls_cert")]
+    pub tls_client_ca: Option<PathBuf>,
+}
+
+/// The authenticated identity behind a connection. Recorded on the connection session and
+/// in the metadata of every thread the connection starts or drives.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ClientIdentity {
+    UnixPeer { uid: u32, pid: Option<i32> },
+    BearerToken { name: String },
+    ClientCertificate { sha256_fingerprint: String },
+}
+
+impl fmt::Display for ClientIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientIdentity::UnixPeer { uid, .. } => write!(f, "unix:uid={uid}"),
+            ClientIdentity::BearerToken { name } => write!(f, "token:{name}"),
+            ClientIdentity::ClientCertificate { sha256_fingerprint } => {
+                write!(f, "cert:sha256:{sha256_fingerprint}")
+            }
+        }
+    }
+}
+
+/// Loaded WebSocket authentication state shared by every accepted connection.
+#[derive(Clone, Default)]
+pub(crate) struct WebSocketAuth {
+    tokens: Vec<NamedToken>,
+    tls_acceptor: Option<TlsAcceptor>,
+    requires_client_certificate: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct NamedToken {
+    name: String,
+    token: String,
+}
+
+impl WebSocketAuth {
+    pub(crate) fn from_args(args: &AppServerAuthArgs) -> IoResult<Self> {
+        let tokens = match &args.ws_token_file {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).map_err(|err| {
+                    std::io::Error::new(
+                        err.kind(),
+                        format!("failed to read --ws-token-file {}: {err}", path.display()),
+                    )
+                })?;
+                parse_token_file(&contents).map_err(|message| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid --ws-token-file {}: {message}", path.display()),
+                    )
+                })?
+            }
+            None => Vec::new(),
+        };
+        let tls_acceptor = match (&args.tls_cert, &args.tls_key) {
+            (Some(cert), Some(key)) => Some(build_tls_acceptor(
+                cert,
+                key,
+                args.tls_client_ca.as_deref(),
+            )?),
+            _ => None,
+        };
+        Ok(Self {
+            tokens,
+            tls_acceptor,
+            requires_client_certificate: args.tls_client_ca.is_some(),
+        })
+    }
+
+    pub(crate) fn requires_token(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    pub(crate) fn requires_client_certificate(&self) -> bool {
+        self.requires_client_certificate
+    }
+
+    pub(crate) fn tls_acceptor(&self) -> Option<&TlsAcceptor> {
+        self.tls_acceptor.as_ref()
+    }
+
+    pub(crate) fn is_authenticated(&self) -> bool {
+        self.requires_token() || self.requires_client_certificate
+    }
+
+    /// Resolves the `Authorization` header of an upgrade request to a token identity.
+    /// Returns `Ok(None)` when no token file is configured.
+    pub(crate) fn authenticate_bearer(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<Option<ClientIdentity>, ()> {
+        if !self.requires_token() {
+            return Ok(None);
+        }
+        let presented = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or(())?;
+        // Compare against every entry so the time taken does not reveal which one matched.
+        let mut matched = None;
+        for entry in &self.tokens {
+            if constant_time_eq(entry.token.as_bytes(), presented.as_bytes()) && matched.is_none() {
+                matched = Some(entry.name.clone());
+            }
+        }
+        matched
+            .map(|name| Some(ClientIdentity::BearerToken { name }))
+            .ok_or(())
+    }
+}
+
+/// Fingerprint of the leaf certificate a client presented during the TLS handshake.
+pub(crate) fn client_certificate_identity(
+    peer_certificates: Option<&[CertificateDer<'_>]>,
+) -> Option<ClientIdentity> {
+    let leaf = peer_certificates?.first()?;
+    let digest = Sha256::digest(leaf.as_ref());
+    Some(ClientIdentity::ClientCertificate {
+        sha256_fingerprint: format!("{digest:x}"),
+    })
+}
+
+fn parse_token_file(contents: &str) -> Result<Vec<NamedToken>, String> {
+    let mut tokens = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(name), Some(token), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!(
+                "line {} must have the form `<identity> <token>`",
+                index + 1
+            ));
+        };
+        if tokens.iter().any(|entry: &NamedToken| entry.name == name) {
+            return Err(format!("line {}: duplicate identity `{name}`", index + 1));
+        }
+        tokens.push(NamedToken {
+            name: name.to_string(),
+            token: token.to_string(),
+        });
+    }
+    if tokens.is_empty() {
+        return Err("no tokens defined".to_string());
+    }
+    Ok(tokens)
+}
+
+fn build_tls_acceptor(
+    cert_path: &Path,
+    key_path: &Path,
+    client_ca_path: Option<&Path>,
+) -> IoResult<TlsAcceptor> {
+    codex_utils_rustls_provider::ensure_rustls_crypto_provider();
+
+    let certs = read_certificates(cert_path, "--tls-cert")?;
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|err| {
+        std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to read --tls-key {}: {err}", key_path.display()),
+        )
+    })?;
+
+    let builder = ServerConfig::builder();
+    let builder = match client_ca_path {
+        Some(client_ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certificates(client_ca_path, "--tls-client-ca")? {
+                roots.add(cert).map_err(|err| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "invalid certificate in --tls-client-ca {}: {err}",
+                            client_ca_path.display()
+                        ),
+                    )
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let config = builder
+        .with_single_cert(certs, key)
+        .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn read_certificates(path: &Path, flag: &str) -> IoResult<Vec<CertificateDer<'static>>> {
+    let read = || -> Result<Vec<_>, rustls::pki_types::pem::Error> {
+        CertificateDer::pem_file_iter(path)?.collect()
+    };
+    let certs = read().map_err(|err| {
+        std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("failed to read {flag} {}: {err}", path.display()),
+        )
+    })?;
+    if certs.is_empty() {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("{flag} {} contains no certificates", path.display()),
+        ));
+    }
+    Ok(certs)
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    left.iter()
+        .zip(right)
+        .fold(0u8, |acc, (left, right)| acc | (left ^ right))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn auth_with_tokens(contents: &str) -> WebSocketAuth {
+        WebSocketAuth {
+            tokens: parse_token_file(contents).expect("valid token file"),
+            tls_acceptor: None,
+            requires_client_certificate: false,
+        }
+    }
+
+    #[test]
+    fn parses_token_file_and_skips_comments() {
+        let tokens = parse_token_file("# local tools\n\neditor  s3cret\nci-bot other\n")
+            .expect("valid token file");
+        assert_eq!(
+            tokens,
+            vec![
+                NamedToken {
+                    name: "editor".to_string(),
+                    token: "s3cret".to_string(),
+                },
+                NamedToken {
+                    name: "ci-bot".to_string(),
+                    token: "other".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_and_duplicate_token_lines() {
+        assert_eq!(
+            parse_token_file("editor\n"),
+            Err("line 1 must have the form `<identity> <token>`".to_string())
+        );
+        assert_eq!(
+            parse_token_file("editor a\neditor b\n"),
+            Err("line 2: duplicate identity `editor`".to_string())
+        );
+        assert_eq!(
+            parse_token_file("# nothing\n"),
+            Err("no tokens defined".to_string())
+        );
+    }
+
+    #[test]
+    fn bearer_tokens_resolve_to_their_identity() {
+        let auth = auth_with_tokens("editor s3cret\nci-bot other\n");
+
+        assert_eq!(
+            auth.authenticate_bearer(Some("Bearer other")),
+            Ok(Some(ClientIdentity::BearerToken {
+                name: "ci-bot".to_string(),
+            }))
+        );
+        assert_eq!(auth.authenticate_bearer(Some("Bearer wrong")), Err(()));
+        assert_eq!(auth.authenticate_bearer(Some("Basic other")), Err(()));
+        assert_eq!(auth.authenticate_bearer(None), Err(()));
+        assert_eq!(WebSocketAuth::default().authenticate_bearer(None), Ok(None));
+    }
+
+    #[test]
+    fn client_identities_display_stable_names() {
+        assert_eq!(
+            ClientIdentity::UnixPeer {
+                uid: 1000,
+                pid: Some(42),
+            }
+            .to_string(),
+            "unix:uid=1000"
+        );
+        assert_eq!(
+            ClientIdentity::BearerToken {
+                name: "editor".to_string(),
+            }
+            .to_string(),
+            "token:editor"
+        );
+        let cert = CertificateDer::from(b"not really a certificate".to_vec());
+        assert_eq!(
+            client_certificate_identity(Some(&[cert]))
+                .as_ref()
+                .map(ToString::to_string),
+            Some(format!(
+                "cert:sha256:{:x}",
+                Sha256::digest(b"not really a certificate")
+            ))
+        );
+    }
+}
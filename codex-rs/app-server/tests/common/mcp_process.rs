@@ -25,6 +25,7 @@ use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::ExperimentalFeatureListParams;
 use codex_app_server_protocol::FeedbackUploadParams;
+use codex_app_server_protocol::FuzzyFileSearchMode;
 use codex_app_server_protocol::GetAccountParams;
 use codex_app_server_protocol::GetAuthStatusParams;
 use codex_app_server_protocol::GetConversationSummaryParams;
@@ -739,6 +740,21 @@ impl McpProcess {
         self.send_request("fuzzyFileSearch", Some(params)).await
     }
 
+    /// Send a `fuzzyFileSearch` JSON-RPC request with an explicit search mode.
+    pub async fn send_fuzzy_file_search_request_with_mode(
+        &mut self,
+        query: &str,
+        roots: Vec<String>,
+        mode: FuzzyFileSearchMode,
+    ) -> anyhow::Result<i64> {
+        let params = serde_json::json!({
+            "query": query,
+            "roots": roots,
+            "mode": mode,
+        });
+        self.send_request("fuzzyFileSearch", Some(params)).await
+    }
+
     pub async fn send_fuzzy_file_search_session_start_request(
         &mut self,
         session_id: &str,
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use anyhow::anyhow;
 use app_test_support::McpProcess;
+use codex_app_server_protocol::FuzzyFileSearchMode;
 use codex_app_server_protocol::FuzzyFileSearchSessionCompletedNotification;
 use codex_app_server_protocol::FuzzyFileSearchSessionUpdatedNotification;
 use codex_app_server_protocol::JSONRPCResponse;
@@ -232,6 +233,55 @@ async fn test_fuzzy_file_search_sorts_and_includes_indices() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fuzzy_file_search_content_mode_returns_line_matches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    std::fs::write(
+        root.path().join("notes.txt"),
+        "first line\nfind the needle here\n",
+    )?;
+    // Only file contents are searched, so a matching file name is ignored.
+    std::fs::write(root.path().join("needle.txt"), "no match in here\n")?;
+
+    let mut mcp = initialized_mcp(&codex_home).await?;
+    let root_path = root.path().to_string_lossy().to_string();
+    let request_id = mcp
+        .send_fuzzy_file_search_request_with_mode(
+            "needle",
+            vec![root_path.clone()],
+            FuzzyFileSearchMode::ContentLiteral,
+        )
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(
+        resp.result,
+        json!({
+            "files": [
+                {
+                    "root": root_path,
+                    "path": "notes.txt",
+                    "file_name": "notes.txt",
+                    "score": 169,
+                    "indices": [9, 10, 11, 12, 13, 14],
+                    "content": {
+                        "line_number": 2,
+                        "column": 10,
+                        "line": "find the needle here",
+                    },
+                },
+            ]
+        })
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_fuzzy_file_search_accepts_cancellation_token() -> Result<()> {
     let codex_home = TempDir::new()?;
@@ -2,6 +2,7 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use app_test_support::create_mock_responses_server_sequence_unchecked;
+use app_test_support::to_response;
 use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::JSONRPCError;
@@ -10,6 +11,10 @@ use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadReadParams;
+use codex_app_server_protocol::ThreadReadResponse;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
 use futures::SinkExt;
 use futures::StreamExt;
 use serde_json::json;
@@ -27,7 +32,12 @@ use tokio::time::timeout;
 use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Error as WebSocketError;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
 
 pub(super) const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
 
@@ -79,15 +89,114 @@ async fn websocket_transport_routes_per_connection_handshake_and_responses() ->
     Ok(())
 }
 
+#[tokio::test]
+async fn websocket_transport_requires_bearer_token_from_token_file() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let token_file = codex_home.path().join("ws-tokens");
+    std::fs::write(&token_file, "# local tools\neditor s3cret-token\n")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server_with_args(
+        codex_home.path(),
+        bind_addr,
+        &["--ws-token-file", token_file.to_string_lossy().as_ref()],
+    )
+    .await?;
+
+    // Without a token the upgrade is rejected once the listener is up.
+    let url = format!("ws://{bind_addr}");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match connect_async(&url).await {
+            Err(WebSocketError::Http(response)) => {
+                assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+                break;
+            }
+            Ok(_) => bail!("unauthenticated websocket connection should be rejected"),
+            Err(err) => {
+                if Instant::now() >= deadline {
+                    bail!("failed to reach websocket server at {url}: {err}");
+                }
+                sleep(Duration::from_millis(50)).await;
+            }
+        }
+    }
+
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert(
+        AUTHORIZATION,
+        HeaderValue::from_static("Bearer wrong-token"),
+    );
+    match connect_async(request).await {
+        Err(WebSocketError::Http(response)) => {
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        Ok(_) => bail!("websocket connection with a wrong token should be rejected"),
+        Err(err) => bail!("unexpected websocket error for a wrong token: {err}"),
+    }
+
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert(
+        AUTHORIZATION,
+        HeaderValue::from_static("Bearer s3cret-token"),
+    );
+    let (mut ws, _response) = connect_async(request).await?;
+    send_initialize_request(&mut ws, 1, "ws_token_client").await?;
+    let init = read_response_for_id(&mut ws, 1).await?;
+    assert_eq!(init.id, RequestId::Integer(1));
+
+    // The authenticated identity is recorded on the threads the connection starts.
+    send_request(
+        &mut ws,
+        "thread/start",
+        2,
+        Some(serde_json::to_value(ThreadStartParams::default())?),
+    )
+    .await?;
+    let start = to_response::<ThreadStartResponse>(read_response_for_id(&mut ws, 2).await?)?;
+    send_request(
+        &mut ws,
+        "thread/read",
+        3,
+        Some(serde_json::to_value(ThreadReadParams {
+            thread_id: start.thread.id,
+            include_turns: false,
+        })?),
+    )
+    .await?;
+    let read = to_response::<ThreadReadResponse>(read_response_for_id(&mut ws, 3).await?)?;
+    assert_eq!(
+        read.thread.client_identities,
+        vec!["token:editor".to_string()]
+    );
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
 pub(super) async fn spawn_websocket_server(
     codex_home: &Path,
     bind_addr: SocketAddr,
+) -> Result<Child> {
+    spawn_websocket_server_with_args(codex_home, bind_addr, &[]).await
+}
+
+pub(super) async fn spawn_websocket_server_with_args(
+    codex_home: &Path,
+    bind_addr: SocketAddr,
+    extra_args: &[&str],
 ) -> Result<Child> {
     let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
         .context("should find app-server binary")?;
     let mut cmd = Command::new(program);
     cmd.arg("--listen")
         .arg(format!("ws://{bind_addr}"))
+        .args(extra_args)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
@@ -133,6 +242,30 @@ pub(super) async fn connect_websocket(bind_addr: SocketAddr) -> Result<WsClient>
     }
 }
 
+pub(super) async fn connect_websocket_with_token(
+    bind_addr: SocketAddr,
+    token: &str,
+) -> Result<WsClient> {
+    let url = format!("ws://{bind_addr}");
+    let authorization = HeaderValue::from_str(&format!("Bearer {token}"))?;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let mut request = url.as_str().into_client_request()?;
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, authorization.clone());
+        match connect_async(request).await {
+            Ok((stream, _response)) => return Ok(stream),
+            Err(err) => {
+                if Instant::now() >= deadline {
+                    bail!("failed to connect websocket to {url}: {err}");
+                }
+                sleep(Duration::from_millis(50)).await;
+            }
+        }
+    }
+}
+
 pub(super) async fn send_initialize_request(
     stream: &mut WsClient,
     id: i64,
@@ -253,7 +386,7 @@ pub(super) async fn read_response_and_notification_for_method(
     Ok((response, notification))
 }
 
-async fn read_error_for_id(stream: &mut WsClient, id: i64) -> Result<JSONRPCError> {
+pub(super) async fn read_error_for_id(stream: &mut WsClient, id: i64) -> Result<JSONRPCError> {
     let target_id = RequestId::Integer(id);
     loop {
         let message = read_jsonrpc_message(stream).await?;
@@ -34,6 +34,7 @@ mod thread_name_websocket;
 mod thread_read;
 mod thread_resume;
 mod thread_rollback;
+mod thread_roles_websocket;
 mod thread_start;
 mod thread_status;
 mod thread_unarchive;
@@ -240,6 +240,7 @@ async fn skills_changed_notification_is_emitted_after_skill_change() -> Result<(
             developer_instructions: None,
             personality: None,
             ephemeral: None,
+            server_request_ownership: None,
             dynamic_tools: None,
             mock_experimental_field: None,
             experimental_raw_events: false,
@@ -0,0 +1,234 @@
+use super::connection_handling_websocket::DEFAULT_READ_TIMEOUT;
+use super::connection_handling_websocket::WsClient;
+use super::connection_handling_websocket::connect_websocket;
+use super::connection_handling_websocket::connect_websocket_with_token;
+use super::connection_handling_websocket::create_config_toml;
+use super::connection_handling_websocket::read_error_for_id;
+use super::connection_handling_websocket::read_response_for_id;
+use super::connection_handling_websocket::reserve_local_addr;
+use super::connection_handling_websocket::send_initialize_request;
+use super::connection_handling_websocket::send_request;
+use super::connection_handling_websocket::spawn_websocket_server;
+use super::connection_handling_websocket::spawn_websocket_server_with_args;
+use anyhow::Context;
+use anyhow::Result;
+use app_test_support::create_fake_rollout_with_text_elements;
+use app_test_support::create_mock_responses_server_repeating_assistant;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ServerRequestOwnership;
+use codex_app_server_protocol::ThreadResumeParams;
+use codex_app_server_protocol::ThreadResumeResponse;
+use codex_app_server_protocol::ThreadSubscriptionRole;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn observers_cannot_drive_a_thread() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let conversation_id = create_fake_rollout_with_text_elements(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Saved user message",
+        Vec::new(),
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server(codex_home.path(), bind_addr).await?;
+
+    let result = async {
+        let mut controller = connect_websocket(bind_addr).await?;
+        let mut observer = connect_websocket(bind_addr).await?;
+        initialize_client(&mut controller, 1, "ide").await?;
+        initialize_client(&mut observer, 2, "dashboard").await?;
+
+        resume_thread(&mut controller, 10, &conversation_id, None).await?;
+        resume_thread(
+            &mut observer,
+            20,
+            &conversation_id,
+            Some(ThreadSubscriptionRole::Observer),
+        )
+        .await?;
+
+        send_request(
+            &mut observer,
+            "turn/start",
+            21,
+            Some(serde_json::to_value(TurnStartParams {
+                thread_id: conversation_id.clone(),
+                input: vec![V2UserInput::Text {
+                    text: "Hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                ..Default::default()
+            })?),
+        )
+        .await?;
+        let error = timeout(DEFAULT_READ_TIMEOUT, read_error_for_id(&mut observer, 21)).await??;
+        assert_eq!(
+            error.error.message,
+            format!("connection observes thread {conversation_id} and cannot drive it")
+        );
+
+        send_request(
+            &mut observer,
+            "thread/resume",
+            22,
+            Some(serde_json::to_value(ThreadResumeParams {
+                thread_id: conversation_id.clone(),
+                role: Some(ThreadSubscriptionRole::Observer),
+                server_request_ownership: Some(ServerRequestOwnership::OwnerOnly),
+                ..Default::default()
+            })?),
+        )
+        .await?;
+        let error = timeout(DEFAULT_READ_TIMEOUT, read_error_for_id(&mut observer, 22)).await??;
+        assert_eq!(
+            error.error.message,
+            "observers cannot set serverRequestOwnership"
+        );
+        Ok(())
+    }
+    .await;
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    result
+}
+
+#[tokio::test]
+async fn joining_controllers_need_an_identity_that_drove_the_thread() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    let conversation_id = create_fake_rollout_with_text_elements(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Saved user message",
+        Vec::new(),
+        Some("mock_provider"),
+        None,
+    )?;
+    let token_file = codex_home.path().join("ws-tokens");
+    std::fs::write(&token_file, "editor editor-token\nviewer viewer-token\n")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server_with_args(
+        codex_home.path(),
+        bind_addr,
+        &["--ws-token-file", token_file.to_string_lossy().as_ref()],
+    )
+    .await?;
+
+    let result = async {
+        let mut editor = connect_websocket_with_token(bind_addr, "editor-token").await?;
+        let mut viewer = connect_websocket_with_token(bind_addr, "viewer-token").await?;
+        let mut second_editor = connect_websocket_with_token(bind_addr, "editor-token").await?;
+        initialize_client(&mut editor, 1, "ide").await?;
+        initialize_client(&mut viewer, 2, "dashboard").await?;
+        initialize_client(&mut second_editor, 3, "ide-window").await?;
+
+        // The connection that loads the thread drives it.
+        resume_thread(&mut editor, 10, &conversation_id, None).await?;
+
+        // Joining without a role observes.
+        resume_thread(&mut viewer, 20, &conversation_id, None).await?;
+        send_request(
+            &mut viewer,
+            "turn/start",
+            21,
+            Some(serde_json::to_value(TurnStartParams {
+                thread_id: conversation_id.clone(),
+                input: vec![V2UserInput::Text {
+                    text: "Hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                ..Default::default()
+            })?),
+        )
+        .await?;
+        let error = timeout(DEFAULT_READ_TIMEOUT, read_error_for_id(&mut viewer, 21)).await??;
+        assert_eq!(
+            error.error.message,
+            format!("connection observes thread {conversation_id} and cannot drive it")
+        );
+
+        // Asking to drive it requires an identity that already did.
+        send_request(
+            &mut viewer,
+            "thread/resume",
+            22,
+            Some(serde_json::to_value(ThreadResumeParams {
+                thread_id: conversation_id.clone(),
+                role: Some(ThreadSubscriptionRole::Controller),
+                ..Default::default()
+            })?),
+        )
+        .await?;
+        let error = timeout(DEFAULT_READ_TIMEOUT, read_error_for_id(&mut viewer, 22)).await??;
+        assert_eq!(
+            error.error.message,
+            format!(
+                "controlling thread {conversation_id} while other connections are subscribed requires an authenticated identity that started or drove it"
+            )
+        );
+
+        resume_thread(
+            &mut second_editor,
+            30,
+            &conversation_id,
+            Some(ThreadSubscriptionRole::Controller),
+        )
+        .await?;
+        Ok(())
+    }
+    .await;
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    result
+}
+
+async fn initialize_client(client: &mut WsClient, id: i64, name: &str) -> Result<()> {
+    send_initialize_request(client, id, name).await?;
+    timeout(DEFAULT_READ_TIMEOUT, read_response_for_id(client, id)).await??;
+    Ok(())
+}
+
+async fn resume_thread(
+    client: &mut WsClient,
+    id: i64,
+    thread_id: &str,
+    role: Option<ThreadSubscriptionRole>,
+) -> Result<()> {
+    send_request(
+        client,
+        "thread/resume",
+        id,
+        Some(serde_json::to_value(ThreadResumeParams {
+            thread_id: thread_id.to_string(),
+            role,
+            ..Default::default()
+        })?),
+    )
+    .await?;
+    let response: JSONRPCResponse =
+        timeout(DEFAULT_READ_TIMEOUT, read_response_for_id(client, id)).await??;
+    let resume = to_response::<ThreadResumeResponse>(response)?;
+    assert_eq!(resume.thread.id, thread_id);
+    Ok(())
+}
@@ -13,12 +13,14 @@ use crate::ApplyPatchArgs;
 use crate::ApplyPatchError;
 use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
+use crate::HunkMatchOptions;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::parser::parse_patch_envelope;
+use crate::unified_diff_from_chunks_with_options;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,15 +132,27 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_options(argv, cwd, &HunkMatchOptions::default())
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], locating update chunks according
+/// to `options` so the reported diffs match what applying the patch will do.
+pub fn maybe_parse_apply_patch_verified_with_options(
+    argv: &[String],
+    cwd: &Path,
+    options: &HunkMatchOptions,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
+    // Only the envelope counts here: unified diff detection would flag ordinary
+    // scripts such as `patch -p1 <<'EOF'` or `git apply <<'EOF'`.
     if let [body] = argv
-        && parse_patch(body).is_ok()
+        && parse_patch_envelope(body).is_ok()
     {
         return MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ImplicitInvocation);
     }
     if let Some((_, script)) = parse_shell_script(argv)
-        && parse_patch(script).is_ok()
+        && parse_patch_envelope(script).is_ok()
     {
         return MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::ImplicitInvocation);
     }
@@ -148,6 +162,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
             patch,
             hunks,
             workdir,
+            skipped,
         }) => {
             let effective_cwd = workdir
                 .as_ref()
@@ -187,7 +202,8 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        } = match unified_diff_from_chunks_with_options(&path, &chunks, 1, options)
+                        {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -204,10 +220,15 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                     }
                 }
             }
+            let skipped = skipped
+                .into_iter()
+                .map(|path| effective_cwd.join(path))
+                .collect();
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
                 changes,
                 patch,
                 cwd: effective_cwd,
+                skipped,
             })
         }
         MaybeApplyPatch::ShellParseError(e) => MaybeApplyPatchVerified::ShellParseError(e),
@@ -371,6 +392,7 @@ fn extract_apply_patch_from_bash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::unified_diff_from_chunks;
     use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -760,6 +782,7 @@ PATCH"#,
                 )]),
                 patch: argv[1].clone(),
                 cwd: session_dir.path().to_path_buf(),
+                skipped: Vec::new(),
             })
         );
     }
@@ -810,4 +833,85 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_unified_diff_goes_through_verified_path() {
+        let session_dir = tempdir().unwrap();
+        fs::write(
+            session_dir.path().join("greeting.txt"),
+            "hello
+world
+",
+        )
+        .unwrap();
+
+        let diff = r#"diff --git a/greeting.txt b/greeting.txt
+index 1111111..2222222 100644
+--- a/greeting.txt
++++ b/greeting.txt
+@@ -1,2 +1,2 @@
+-hello
++goodbye
+ world
+diff --git a/logo.png b/logo.png
+Binary files a/logo.png and b/logo.png differ
+diff --git a/notes.md b/notes.md
+new file mode 100644
+--- /dev/null
++++ b/notes.md
+@@ -0,0 +1 @@
++# Notes"#;
+        let argv = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            format!("apply_patch <<'EOF'\n{diff}\nEOF"),
+        ];
+
+        let result = maybe_parse_apply_patch_verified(&argv, session_dir.path());
+        let action = match result {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected verified body, got {other:?}"),
+        };
+
+        assert_eq!(
+            action.changes(),
+            &HashMap::from([
+                (
+                    session_dir.path().join("greeting.txt"),
+                    ApplyPatchFileChange::Update {
+                        unified_diff: "@@ -1,2 +1,2 @@\n-hello\n+goodbye\n world\n".to_string(),
+                        move_path: None,
+                        new_content: "goodbye\nworld\n".to_string(),
+                    },
+                ),
+                (
+                    session_dir.path().join("notes.md"),
+                    ApplyPatchFileChange::Add {
+                        content: "# Notes\n".to_string(),
+                    },
+                ),
+            ])
+        );
+        assert_eq!(action.skipped, vec![session_dir.path().join("logo.png")]);
+    }
+
+    #[test]
+    fn test_diff_heredocs_for_other_tools_are_not_apply_patch() {
+        let session_dir = tempdir().unwrap();
+        let diff = "diff --git a/greeting.txt b/greeting.txt\n--- a/greeting.txt\n+++ b/greeting.txt\n@@ -1 +1 @@\n-hello\n+goodbye";
+
+        for command in ["patch -p1", "git apply"] {
+            let argv = vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                format!("{command} <<'EOF'\n{diff}\nEOF"),
+            ];
+
+            assert_matches!(
+                maybe_parse_apply_patch_verified(&argv, session_dir.path()),
+                MaybeApplyPatchVerified::NotApplyPatch,
+                "{command}"
+            );
+        }
+    }
 }
@@ -2,6 +2,8 @@ mod invocation;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod transaction;
+mod unified_diff;
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -18,6 +20,7 @@ use similar::TextDiff;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_options;
 pub use standalone_executable::main;
 
 use crate::invocation::ExtractHeredocError;
@@ -82,6 +85,80 @@ impl PartialEq for IoError {
     }
 }
 
+/// Controls how leniently update chunks are located in the target file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HunkMatchOptions {
+    /// Minimum similarity (0.0-1.0) at which a chunk whose context lines no
+    /// longer match exactly is applied to the closest region of the file. Lines
+    /// the chunk removes must still match up to whitespace. `None` disables
+    /// fuzzy matching.
+    pub fuzzy_threshold: Option<f32>,
+    /// Treat `@@` change contexts as scope headers (e.g. `@@ fn parse`): match
+    /// them ignoring modifiers and trailing signature, and look for the chunk's
+    /// lines inside that scope before searching the rest of the file.
+    pub anchor_to_scope: bool,
+}
+
+/// Fuzzy matching stays on by default: lines a chunk removes must match up to
+/// whitespace regardless, so at 0.9 a stale chunk can only land where its
+/// context differs by about one line in ten, and `@@` contexts are never
+/// matched approximately.
+impl Default for HunkMatchOptions {
+    fn default() -> Self {
+        Self {
+            fuzzy_threshold: Some(0.9),
+            anchor_to_scope: true,
+        }
+    }
+}
+
+const FUZZY_THRESHOLD_ARG: &str = "--fuzzy-threshold=";
+const NO_FUZZY_ARG: &str = "--no-fuzzy";
+const NO_SCOPE_ANCHOR_ARG: &str = "--no-scope-anchor";
+
+impl HunkMatchOptions {
+    /// Arguments that carry these options after the patch in a
+    /// [`CODEX_CORE_APPLY_PATCH_ARG1`] invocation; see [`Self::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![match self.fuzzy_threshold {
+            Some(threshold) => format!("{FUZZY_THRESHOLD_ARG}{threshold}"),
+            None => NO_FUZZY_ARG.to_string(),
+        }];
+        if !self.anchor_to_scope {
+            args.push(NO_SCOPE_ANCHOR_ARG.to_string());
+        }
+        args
+    }
+
+    /// Parses arguments produced by [`Self::to_args`]. Options that are not
+    /// given keep their defaults.
+    pub fn from_args<I, S>(args: I) -> std::result::Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = Self::default();
+        for arg in args {
+            let arg = arg.as_ref();
+            if let Some(threshold) = arg.strip_prefix(FUZZY_THRESHOLD_ARG) {
+                let threshold = threshold
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|threshold| (0.0..=1.0).contains(threshold))
+                    .ok_or_else(|| format!("invalid fuzzy threshold: {threshold}"))?;
+                options.fuzzy_threshold = Some(threshold);
+            } else if arg == NO_FUZZY_ARG {
+                options.fuzzy_threshold = None;
+            } else if arg == NO_SCOPE_ANCHOR_ARG {
+                options.anchor_to_scope = false;
+            } else {
+                return Err(format!("unexpected argument: {arg}"));
+            }
+        }
+        Ok(options)
+    }
+}
+
 /// Both the raw PATCH argument to `apply_patch` as well as the PATCH argument
 /// parsed into hunks.
 #[derive(Debug, PartialEq)]
@@ -89,6 +166,8 @@ pub struct ApplyPatchArgs {
     pub patch: String,
     pub hunks: Vec<Hunk>,
     pub workdir: Option<String>,
+    /// Files whose binary sections in a unified diff were left untouched.
+    pub skipped: Vec<PathBuf>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -135,6 +214,9 @@ pub struct ApplyPatchAction {
 
     /// The working directory that was used to resolve relative paths in the patch.
     pub cwd: PathBuf,
+
+    /// Files whose binary sections in a unified diff are left untouched.
+    pub skipped: Vec<PathBuf>,
 }
 
 impl ApplyPatchAction {
@@ -175,6 +257,7 @@ impl ApplyPatchAction {
                 .expect("path should have parent")
                 .to_path_buf(),
             patch,
+            skipped: Vec::new(),
         }
     }
 }
@@ -185,8 +268,49 @@ pub fn apply_patch(
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
-    let hunks = match parse_patch(patch) {
-        Ok(source) => source.hunks,
+    let hunks = parse_patch_reporting_errors(patch, stderr)?;
+    apply_hunks(&hunks, stdout, stderr)?;
+
+    Ok(())
+}
+
+/// Like [`apply_patch`], but either every hunk is applied or none is. See
+/// [`apply_hunks_atomically`].
+pub fn apply_patch_atomically(
+    patch: &str,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_patch_atomically_with_options(patch, &HunkMatchOptions::default(), stdout, stderr)
+}
+
+/// Like [`apply_patch_atomically`], locating chunks according to `options`.
+pub fn apply_patch_atomically_with_options(
+    patch: &str,
+    options: &HunkMatchOptions,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    let hunks = parse_patch_reporting_errors(patch, stderr)?;
+    apply_hunks_atomically(&hunks, options, stdout, stderr)
+}
+
+fn parse_patch_reporting_errors(
+    patch: &str,
+    stderr: &mut impl std::io::Write,
+) -> Result<Vec<Hunk>, ApplyPatchError> {
+    match parse_patch(patch) {
+        Ok(source) => {
+            for path in &source.skipped {
+                writeln!(
+                    stderr,
+                    "Skipped {}: binary diffs cannot be applied with apply_patch",
+                    path.display()
+                )
+                .map_err(ApplyPatchError::from)?;
+            }
+            Ok(source.hunks)
+        }
         Err(e) => {
             match &e {
                 InvalidPatchError(message) => {
@@ -203,13 +327,9 @@ pub fn apply_patch(
                     .map_err(ApplyPatchError::from)?;
                 }
             }
-            return Err(ApplyPatchError::ParseError(e));
+            Err(ApplyPatchError::ParseError(e))
         }
-    };
-
-    apply_hunks(&hunks, stdout, stderr)?;
-
-    Ok(())
+    }
 }
 
 /// Applies hunks and continues to update stdout/stderr
@@ -217,6 +337,16 @@ pub fn apply_hunks(
     hunks: &[Hunk],
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_hunks_with_options(hunks, &HunkMatchOptions::default(), stdout, stderr)
+}
+
+/// Like [`apply_hunks`], with explicit control over how chunks are located.
+pub fn apply_hunks_with_options(
+    hunks: &[Hunk],
+    options: &HunkMatchOptions,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
 ) -> Result<(), ApplyPatchError> {
     let _existing_paths: Vec<&Path> = hunks
         .iter()
@@ -245,29 +375,66 @@ pub fn apply_hunks(
         .collect::<Vec<&Path>>();
 
     // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks) {
+    match apply_hunks_to_files(hunks, options) {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
         }
-        Err(err) => {
-            let msg = err.to_string();
-            writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
-            if let Some(io) = err.downcast_ref::<std::io::Error>() {
-                Err(ApplyPatchError::from(io))
-            } else {
-                Err(ApplyPatchError::IoError(IoError {
-                    context: msg,
-                    source: std::io::Error::other(err),
-                }))
+        Err(err) => report_apply_error(err, stderr),
+    }
+}
+
+/// Applies hunks all-or-nothing: every hunk is checked and every new file body
+/// is staged before any target is touched, then the staged files are renamed
+/// into place. If anything fails, every touched path (including deleted files
+/// and move sources) is restored and nothing is reported as changed.
+pub fn apply_hunks_atomically(
+    hunks: &[Hunk],
+    options: &HunkMatchOptions,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    match transaction::apply_hunks_atomically(hunks, options) {
+        Ok(affected) => {
+            print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
+            Ok(())
+        }
+        Err(failure) => {
+            if !failure.left_changed.is_empty() {
+                writeln!(
+                    stderr,
+                    "Rollback failed; these paths may still be modified:"
+                )
+                .map_err(ApplyPatchError::from)?;
+                for path in &failure.left_changed {
+                    writeln!(stderr, "  {}", path.display()).map_err(ApplyPatchError::from)?;
+                }
             }
+            report_apply_error(failure.error, stderr)
         }
     }
 }
 
+fn report_apply_error(
+    err: anyhow::Error,
+    stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    let msg = err.to_string();
+    writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
+    if let Some(io) = err.downcast_ref::<std::io::Error>() {
+        Err(ApplyPatchError::from(io))
+    } else {
+        Err(ApplyPatchError::IoError(IoError {
+            context: msg,
+            source: std::io::Error::other(err),
+        }))
+    }
+}
+
 /// Applies each parsed patch hunk to the filesystem.
 /// Returns an error if any of the changes could not be applied.
 /// Tracks file paths affected by applying a patch.
+#[derive(Debug, Default, PartialEq)]
 pub struct AffectedPaths {
     pub added: Vec<PathBuf>,
     pub modified: Vec<PathBuf>,
@@ -276,7 +443,10 @@ pub struct AffectedPaths {
 
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
 /// Returns an error if the patch could not be applied.
-fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
+fn apply_hunks_to_files(
+    hunks: &[Hunk],
+    options: &HunkMatchOptions,
+) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
@@ -307,9 +477,11 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                 path,
                 move_path,
                 chunks,
+                executable,
             } => {
                 let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
+                    derive_new_contents_from_chunks(path, chunks, options)?;
+                let target = move_path.as_ref().unwrap_or(path);
                 if let Some(dest) = move_path {
                     if let Some(parent) = dest.parent()
                         && !parent.as_os_str().is_empty()
@@ -328,6 +500,18 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                         .with_context(|| format!("Failed to write file {}", path.display()))?;
                     modified.push(path.clone());
                 }
+                if let Some(executable) = executable {
+                    std::fs::metadata(target)
+                        .and_then(|metadata| {
+                            std::fs::set_permissions(
+                                target,
+                                with_executable_bit(metadata.permissions(), *executable),
+                            )
+                        })
+                        .with_context(|| {
+                            format!("Failed to change the mode of {}", target.display())
+                        })?;
+                }
             }
         }
     }
@@ -343,11 +527,40 @@ struct AppliedPatch {
     new_contents: String,
 }
 
+/// Sets the executable bit for everyone who can read the file, like
+/// `chmod +x`, or clears it for everyone.
+#[cfg(unix)]
+pub(crate) fn with_executable_bit(
+    mut permissions: std::fs::Permissions,
+    executable: bool,
+) -> std::fs::Permissions {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = permissions.mode();
+    let mode = if executable {
+        mode | ((mode & 0o444) >> 2)
+    } else {
+        mode & !0o111
+    };
+    permissions.set_mode(mode);
+    permissions
+}
+
+/// Files have no executable bit outside Unix.
+#[cfg(not(unix))]
+pub(crate) fn with_executable_bit(
+    permissions: std::fs::Permissions,
+    _executable: bool,
+) -> std::fs::Permissions {
+    permissions
+}
+
 /// Return *only* the new file contents (joined into a single `String`) after
 /// applying the chunks to the file at `path`.
 fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
+    options: &HunkMatchOptions,
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let original_contents = match std::fs::read_to_string(path) {
         Ok(contents) => contents,
@@ -358,7 +571,16 @@ fn derive_new_contents_from_chunks(
             }));
         }
     };
+    derive_new_contents(path, original_contents, chunks, options)
+}
 
+/// Applies the chunks to `original_contents`, which were read from `path`.
+fn derive_new_contents(
+    path: &Path,
+    original_contents: String,
+    chunks: &[UpdateFileChunk],
+    options: &HunkMatchOptions,
+) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
 
     // Drop the trailing empty element that results from the final newline so
@@ -367,7 +589,7 @@ fn derive_new_contents_from_chunks(
         original_lines.pop();
     }
 
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
+    let replacements = compute_replacements(&original_lines, path, chunks, options)?;
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
@@ -387,27 +609,60 @@ fn compute_replacements(
     original_lines: &[String],
     path: &Path,
     chunks: &[UpdateFileChunk],
+    options: &HunkMatchOptions,
 ) -> std::result::Result<Vec<(usize, usize, Vec<String>)>, ApplyPatchError> {
     let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
     let mut line_index: usize = 0;
 
     for chunk in chunks {
+        // When the chunk's change context names a scope, approximate matches
+        // are looked up inside that scope first so similar lines elsewhere in
+        // the file are not picked by mistake.
+        let mut scope_end: Option<usize> = None;
+
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
         // adjust our `line_index` to continue from there.
         if let Some(ctx_line) = &chunk.change_context {
-            if let Some(idx) = seek_sequence::seek_sequence(
-                original_lines,
-                std::slice::from_ref(ctx_line),
-                line_index,
-                false,
-            ) {
-                line_index = idx + 1;
-            } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
+            let ctx_pattern = std::slice::from_ref(ctx_line);
+            let found =
+                seek_sequence::seek_sequence(original_lines, ctx_pattern, line_index, false)
+                    .or_else(|| {
+                        options
+                            .anchor_to_scope
+                            .then(|| {
+                                seek_sequence::seek_scope_header(
+                                    original_lines,
+                                    ctx_line,
+                                    line_index,
+                                )
+                            })
+                            .flatten()
+                    });
+            // A single line is too little to tell a renamed scope from a
+            // similarly named neighbour (`fn parse_args` vs `fn parse_arg(`), so
+            // the context is never matched fuzzily; the closest line is only
+            // reported in the error.
+            match found {
+                Some(idx) => {
+                    line_index = idx + 1;
+                    if options.anchor_to_scope {
+                        scope_end = Some(seek_sequence::scope_end(original_lines, idx));
+                    }
+                }
+                None => {
+                    let closest = seek_sequence::closest_sequence(
+                        original_lines,
+                        ctx_pattern,
+                        line_index,
+                        None,
+                    );
+                    return Err(ApplyPatchError::ComputeReplacements(format!(
+                        "Failed to find context '{}' in {}{}",
+                        ctx_line,
+                        path.display(),
+                        describe_closest_match(original_lines, closest),
+                    )));
+                }
             }
         }
 
@@ -456,15 +711,38 @@ fn compute_replacements(
             );
         }
 
-        if let Some(start_idx) = found {
-            replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
-            line_index = start_idx + pattern.len();
-        } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+        let found = match found {
+            Some(start_idx) => Ok((start_idx, pattern.len(), new_slice.to_vec())),
+            None => seek_fuzzy(
+                original_lines,
+                pattern,
+                line_index,
+                scope_end,
+                chunk.line_hint,
+                options,
+            )
+            .and_then(|fuzzy| {
+                let region = &original_lines[fuzzy.start..fuzzy.start + fuzzy.len];
+                match rebase_onto_region(pattern, region, new_slice) {
+                    Some(new_lines) => Ok((fuzzy.start, fuzzy.len, new_lines)),
+                    None => Err(Some(fuzzy)),
+                }
+            }),
+        };
+
+        match found {
+            Ok((start_idx, old_len, new_lines)) => {
+                replacements.push((start_idx, old_len, new_lines));
+                line_index = start_idx + old_len;
+            }
+            Err(closest) => {
+                return Err(ApplyPatchError::ComputeReplacements(format!(
+                    "Failed to find expected lines in {}:\n{}{}",
+                    path.display(),
+                    chunk.old_lines.join("\n"),
+                    describe_closest_match(original_lines, closest),
+                )));
+            }
         }
     }
 
@@ -473,6 +751,123 @@ fn compute_replacements(
     Ok(replacements)
 }
 
+/// Falls back to the closest approximate region once every exact strategy has
+/// failed, trying the enclosing scope (when known) before the rest of the file.
+/// `line_hint` picks between equally close regions. Returns the region when it
+/// clears the configured threshold and is unambiguous; otherwise returns the
+/// closest candidate (if any) for the error.
+fn seek_fuzzy(
+    lines: &[String],
+    pattern: &[String],
+    start: usize,
+    scope_end: Option<usize>,
+    line_hint: Option<usize>,
+    options: &HunkMatchOptions,
+) -> std::result::Result<seek_sequence::FuzzyMatch, Option<seek_sequence::FuzzyMatch>> {
+    let accepts = |candidate: &seek_sequence::FuzzyMatch| {
+        options
+            .fuzzy_threshold
+            .is_some_and(|threshold| candidate.similarity >= threshold && !candidate.ambiguous)
+    };
+    if let Some(in_scope) = scope_end
+        .and_then(|end| seek_sequence::closest_sequence(&lines[..end], pattern, start, line_hint))
+        .filter(accepts)
+    {
+        return Ok(in_scope);
+    }
+    let closest = seek_sequence::closest_sequence(lines, pattern, start, line_hint);
+    match closest {
+        Some(candidate) if accepts(&candidate) => Ok(candidate),
+        _ => Err(closest),
+    }
+}
+
+/// For a fuzzily matched chunk, keeps the file's current text for lines the
+/// chunk only uses as context, so stale context in the patch does not revert
+/// edits that happened since the model last read the file. The region may be
+/// a line or two longer or shorter than the chunk: lines added to it since
+/// then are carried through, and context lines removed since then stay gone.
+///
+/// Only context may be stale: returns `None` unless every line the chunk
+/// removes or replaces is present in `region`, up to whitespace and
+/// punctuation normalisation, so a fuzzy match never deletes text the patch
+/// did not name.
+fn rebase_onto_region(
+    old_lines: &[String],
+    region: &[String],
+    new_lines: &[String],
+) -> Option<Vec<String>> {
+    let alignment = seek_sequence::align_lines(old_lines, region);
+    let mut rebased = Vec::with_capacity(region.len() + new_lines.len());
+    // First region line not yet emitted or removed; region lines before an
+    // aligned line that no chunk line claimed are carried through as-is.
+    let mut next_region_idx = 0;
+    let mut carry_until = |rebased: &mut Vec<String>, region_idx: usize| {
+        if region_idx > next_region_idx {
+            rebased.extend_from_slice(&region[next_region_idx..region_idx]);
+        }
+        next_region_idx = next_region_idx.max(region_idx + 1);
+    };
+    for op in similar::capture_diff_slices(similar::Algorithm::Myers, old_lines, new_lines) {
+        match op {
+            similar::DiffOp::Equal { old_index, len, .. } => {
+                for &region_idx in alignment[old_index..old_index + len].iter().flatten() {
+                    carry_until(&mut rebased, region_idx);
+                    rebased.push(region[region_idx].clone());
+                }
+            }
+            similar::DiffOp::Delete {
+                old_index, old_len, ..
+            }
+            | similar::DiffOp::Replace {
+                old_index, old_len, ..
+            } => {
+                for idx in old_index..old_index + old_len {
+                    let region_idx = alignment[idx].filter(|&region_idx| {
+                        seek_sequence::lines_equivalent(&old_lines[idx], &region[region_idx])
+                    })?;
+                    carry_until(&mut rebased, region_idx);
+                }
+                if let similar::DiffOp::Replace {
+                    new_index, new_len, ..
+                } = op
+                {
+                    rebased.extend_from_slice(&new_lines[new_index..new_index + new_len]);
+                }
+            }
+            similar::DiffOp::Insert {
+                new_index, new_len, ..
+            } => rebased.extend_from_slice(&new_lines[new_index..new_index + new_len]),
+        }
+    }
+    carry_until(&mut rebased, region.len());
+    Some(rebased)
+}
+
+/// Renders the closest candidate region for an error message so the model can
+/// repair its patch without re-reading the file.
+fn describe_closest_match(lines: &[String], closest: Option<seek_sequence::FuzzyMatch>) -> String {
+    let Some(closest) = closest else {
+        return String::new();
+    };
+    if closest.similarity < seek_sequence::MIN_REPORTED_SIMILARITY {
+        return String::new();
+    }
+    let qualifier = if closest.ambiguous { ", ambiguous" } else { "" };
+    let first = closest.start + 1;
+    let last = closest.start + closest.len;
+    let location = if first == last {
+        format!("line {first}")
+    } else {
+        format!("lines {first}-{last}")
+    };
+    format!(
+        "\nClosest match at {location} ({:.0}% similar{qualifier}):\n{}",
+        closest.similarity * 100.0,
+        lines[closest.start..last].join("\n"),
+    )
+}
+
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
 /// returning the modified file contents as a vector of lines.
 fn apply_replacements(
@@ -519,11 +914,20 @@ pub fn unified_diff_from_chunks_with_context(
     path: &Path,
     chunks: &[UpdateFileChunk],
     context: usize,
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    unified_diff_from_chunks_with_options(path, chunks, context, &HunkMatchOptions::default())
+}
+
+pub fn unified_diff_from_chunks_with_options(
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    context: usize,
+    options: &HunkMatchOptions,
 ) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
     let AppliedPatch {
         original_contents,
         new_contents,
-    } = derive_new_contents_from_chunks(path, chunks)?;
+    } = derive_new_contents_from_chunks(path, chunks, options)?;
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
     Ok(ApplyPatchFileUpdate {
@@ -833,6 +1237,324 @@ mod tests {
         assert_eq!(String::from_utf8(stderr).unwrap(), "");
     }
 
+    #[test]
+    fn test_fuzzy_match_applies_stale_chunk_and_keeps_file_context() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.rs");
+        fs::write(
+            &path,
+            "fn main() {\n    let total = 1;\n    println!(\"total = {total}\");\n}\n",
+        )
+        .unwrap();
+
+        // The context line for `println!` predates an edit to the file.
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ fn main() {{
+-    let total = 1;
++    let total = 2;
+     println!("{{total}}");
+ }}"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "fn main() {\n    let total = 2;\n    println!(\"total = {total}\");\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_can_be_disabled_and_reports_closest_region() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.txt");
+        fs::write(&path, "alpha\nbravo charlie\ndelta\n").unwrap();
+        let patch = parse_patch(&wrap_patch(&format!(
+            "*** Update File: {}\n@@\n alpha\n bravo charlie!\n-delta\n+DELTA",
+            path.display()
+        )))
+        .unwrap();
+        let chunks = match patch.hunks.as_slice() {
+            [Hunk::UpdateFile { chunks, .. }] => chunks,
+            _ => panic!("Expected a single UpdateFile hunk"),
+        };
+        let strict = HunkMatchOptions {
+            fuzzy_threshold: None,
+            ..HunkMatchOptions::default()
+        };
+
+        let err = unified_diff_from_chunks_with_options(&path, chunks, 1, &strict).unwrap_err();
+
+        assert_eq!(
+            err,
+            ApplyPatchError::ComputeReplacements(format!(
+                "Failed to find expected lines in {}:\nalpha\nbravo charlie!\ndelta\nClosest match at lines 1-3 (99% similar):\nalpha\nbravo charlie\ndelta",
+                path.display()
+            ))
+        );
+        assert_eq!(
+            unified_diff_from_chunks(&path, chunks).unwrap().content,
+            "alpha\nbravo charlie\nDELTA\n"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_never_removes_lines_the_patch_did_not_name() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.txt");
+        fs::write(&path, "alpha\nbravo charlie\ndelta\n").unwrap();
+        let patch = parse_patch(&wrap_patch(&format!(
+            "*** Update File: {}\n@@\n alpha\n-bravo charlie!\n+BRAVO\n delta",
+            path.display()
+        )))
+        .unwrap();
+        let chunks = match patch.hunks.as_slice() {
+            [Hunk::UpdateFile { chunks, .. }] => chunks,
+            _ => panic!("Expected a single UpdateFile hunk"),
+        };
+
+        let err = unified_diff_from_chunks(&path, chunks).unwrap_err();
+
+        assert_eq!(
+            err,
+            ApplyPatchError::ComputeReplacements(format!(
+                "Failed to find expected lines in {}:\nalpha\nbravo charlie!\ndelta\nClosest match at lines 1-3 (99% similar):\nalpha\nbravo charlie\ndelta",
+                path.display()
+            ))
+        );
+    }
+
+    /// Twelve distinct lines, long enough that one stale line plus one line
+    /// added or removed since still clears the default fuzzy threshold.
+    const STALE_LINES: [&str; 12] = [
+        "alpha one",
+        "bravo two",
+        "charlie three",
+        "delta four",
+        "echo five",
+        "foxtrot six",
+        "golf seven",
+        "hotel eight",
+        "india nine",
+        "juliet ten",
+        "kilo eleven",
+        "lima twelve",
+    ];
+
+    /// An update that replaces the last of `context` lines (`charlie three`
+    /// is stale) with `LIMA`.
+    fn stale_context_patch(path: &Path, context: &[&str]) -> String {
+        let (last, context) = context.split_last().unwrap();
+        let context: String = context
+            .iter()
+            .map(|line| match *line {
+                "charlie three" => " charlie three!\n".to_string(),
+                line => format!(" {line}\n"),
+            })
+            .collect();
+        wrap_patch(&format!(
+            "*** Update File: {}\n@@\n{context}-{last}\n+LIMA",
+            path.display()
+        ))
+    }
+
+    #[test]
+    fn test_fuzzy_match_keeps_lines_added_inside_the_chunk() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.txt");
+        let mut current = STALE_LINES.to_vec();
+        current.insert(2, "user added line");
+        fs::write(&path, format!("{}\n", current.join("\n"))).unwrap();
+        let patch = stale_context_patch(&path, &STALE_LINES);
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        current[12] = "LIMA";
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n", current.join("\n"))
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_does_not_restore_removed_context() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stale.txt");
+        fs::write(&path, format!("{}\n", STALE_LINES.join("\n"))).unwrap();
+        let mut context = STALE_LINES.to_vec();
+        context.insert(2, "removed by the user");
+        let patch = stale_context_patch(&path, &context);
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        let mut expected = STALE_LINES.to_vec();
+        expected[11] = "LIMA";
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n", expected.join("\n"))
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_line_numbers_pick_between_equal_fuzzy_matches() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("dup.txt");
+        let original =
+            "start\nopen()\nvalue = 1\nclose()\nmiddle\nopen()\nvalue = 1\nclose()\nend\n";
+        fs::write(&path, original).unwrap();
+        let hunk = "@@\n open()\n value = 2\n-close()\n+close(); // done";
+
+        // Both blocks match the stale context equally well.
+        let envelope = wrap_patch(&format!("*** Update File: {}\n{hunk}", path.display()));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        assert!(apply_patch(&envelope, &mut stdout, &mut stderr).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        let diff = format!(
+            "--- {path}\n+++ {path}\n@@ -6,3 +6,3 @@\n open()\n value = 2\n-close()\n+close(); // done\n",
+            path = path.display()
+        );
+        apply_patch(&diff, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "start\nopen()\nvalue = 1\nclose()\nmiddle\nopen()\nvalue = 1\nclose(); // done\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_skips_binary_files_and_applies_the_rest() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        let logo = dir.path().join("logo.png");
+        fs::write(&path, "one\n").unwrap();
+        let diff = format!(
+            "--- {path}\n+++ {path}\n@@ -1 +1 @@\n-one\n+1\nBinary files {logo} and {logo} differ\n",
+            path = path.display(),
+            logo = logo.display()
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&diff, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n");
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!(
+                "Skipped {}: binary diffs cannot be applied with apply_patch\n",
+                logo.display()
+            )
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executable_bit_changes_are_applied() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("run.sh");
+        fs::write(&path, "echo hi\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let mode_change = |executable| {
+            vec![Hunk::UpdateFile {
+                path: path.clone(),
+                move_path: None,
+                chunks: Vec::new(),
+                executable: Some(executable),
+            }]
+        };
+        let mode = || fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_hunks(&mode_change(true), &mut stdout, &mut stderr).unwrap();
+        assert_eq!(mode(), 0o755);
+
+        apply_hunks_atomically(
+            &mode_change(false),
+            &HunkMatchOptions::default(),
+            &mut stdout,
+            &mut stderr,
+        )
+        .unwrap();
+        assert_eq!(mode(), 0o644);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "echo hi\n");
+    }
+
+    #[test]
+    fn test_change_context_anchors_to_scope_header() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("scoped.rs");
+        fs::write(
+            &path,
+            "pub(crate) fn second(input: u8) {\n    let value = compute(2);\n    log(value);\n}\n\nfn third() {\n    let value = compute(2);\n    log(value);\n}\n",
+        )
+        .unwrap();
+
+        // `@@ fn second` omits the visibility and signature, and the context
+        // line is stale in a way that matches both functions equally well, so
+        // only the scope tells them apart.
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@ fn second
+     let value = compute(3);
+-    log(value);
++    log(value * 2);"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "pub(crate) fn second(input: u8) {\n    let value = compute(2);\n    log(value * 2);\n}\n\nfn third() {\n    let value = compute(2);\n    log(value);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_change_context_is_never_matched_fuzzily() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("args.rs");
+        let original = "fn parse_arg(input: &str) -> Arg {\n    let value = input.trim();\n    Arg::new(value)\n}\n";
+        fs::write(&path, original).unwrap();
+
+        // `fn parse_args` is one character away from `fn parse_arg(` but names
+        // a different function; the chunk must not land inside `parse_arg`.
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@ fn parse_args
+     let value = input.trim();
+-    Arg::new(value)
++    Arg::new(value.to_lowercase())"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let err = apply_patch(&patch, &mut stdout, &mut stderr).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("Failed to find context 'fn parse_args'"),
+            "unexpected error: {err}"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+    }
+
     #[test]
     fn test_unified_diff() {
         // Start with a file containing four lines.
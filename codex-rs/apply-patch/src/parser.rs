@@ -22,7 +22,12 @@
 //!
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
+//!
+//! Input without a `*** Begin Patch` line that contains a unified diff file
+//! header (`diff --git`, or `---` followed by `+++`) is parsed as a unified diff
+//! instead; see [`crate::unified_diff`].
 use crate::ApplyPatchArgs;
+use crate::unified_diff;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -72,6 +77,10 @@ pub enum Hunk {
         /// Chunks should be in order, i.e. the `change_context` of one chunk
         /// should occur later in the file than the previous chunk.
         chunks: Vec<UpdateFileChunk>,
+
+        /// Sets (`Some(true)`) or clears the executable bit. Only unified diffs
+        /// with `old mode`/`new mode` headers change it.
+        executable: Option<bool>,
     },
 }
 
@@ -101,15 +110,29 @@ pub struct UpdateFileChunk {
     /// If set to true, `old_lines` must occur at the end of the source file.
     /// (Tolerance around trailing newlines should be encouraged.)
     pub is_end_of_file: bool,
+
+    /// Zero-based line where a unified diff's `@@ -a,b` header placed
+    /// `old_lines`. Only used to choose between equally close fuzzy matches.
+    pub line_hint: Option<usize>,
 }
 
 pub fn parse_patch(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
-    let mode = if PARSE_IN_STRICT_MODE {
+    parse_patch_text(patch, default_parse_mode())
+}
+
+/// Parses only the `*** Begin Patch` envelope, never a unified diff. Used to
+/// recognize patch bodies that were run without invoking `apply_patch`, where
+/// treating arbitrary diff-like text as a patch would misfire.
+pub(crate) fn parse_patch_envelope(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
+    parse_envelope_text(patch, default_parse_mode())
+}
+
+fn default_parse_mode() -> ParseMode {
+    if PARSE_IN_STRICT_MODE {
         ParseMode::Strict
     } else {
         ParseMode::Lenient
-    };
-    parse_patch_text(patch, mode)
+    }
 }
 
 enum ParseMode {
@@ -152,6 +175,26 @@ enum ParseMode {
 }
 
 fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, ParseError> {
+    // Unified diffs are parsed untrimmed: a trailing blank context line is
+    // significant there.
+    let raw_lines: Vec<&str> = patch.lines().collect();
+    if !raw_lines
+        .iter()
+        .any(|line| line.trim() == BEGIN_PATCH_MARKER)
+        && unified_diff::looks_like_unified_diff(&raw_lines)
+    {
+        let diff = unified_diff::parse_unified_diff(&raw_lines)?;
+        return Ok(ApplyPatchArgs {
+            hunks: diff.hunks,
+            patch: patch.to_string(),
+            workdir: None,
+            skipped: diff.skipped,
+        });
+    }
+    parse_envelope_text(patch, mode)
+}
+
+fn parse_envelope_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, ParseError> {
     let lines: Vec<&str> = patch.trim().lines().collect();
     let lines: &[&str] = match check_patch_boundaries_strict(&lines) {
         Ok(()) => &lines,
@@ -179,6 +222,7 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
         hunks,
         patch,
         workdir: None,
+        skipped: Vec::new(),
     })
 }
 
@@ -327,6 +371,7 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
                 path: PathBuf::from(path),
                 move_path: move_path.map(PathBuf::from),
                 chunks,
+                executable: None,
             },
             parsed_lines,
         ));
@@ -380,6 +425,7 @@ fn parse_update_file_chunk(
         old_lines: Vec::new(),
         new_lines: Vec::new(),
         is_end_of_file: false,
+        line_hint: None,
     };
     let mut parsed_lines = 0;
     for line in &lines[start_index..] {
@@ -516,11 +562,13 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("path/update.py"),
                 move_path: Some(PathBuf::from("path/update2.py")),
+                executable: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: Some("def f():".to_string()),
                     old_lines: vec!["    pass".to_string()],
                     new_lines: vec!["    return 123".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    line_hint: None
                 }]
             }
         ]
@@ -543,11 +591,13 @@ fn test_parse_patch() {
             UpdateFile {
                 path: PathBuf::from("file.py"),
                 move_path: None,
+                executable: None,
                 chunks: vec![UpdateFileChunk {
                     change_context: None,
                     old_lines: vec![],
                     new_lines: vec!["line".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    line_hint: None
                 }],
             },
             AddFile {
@@ -573,11 +623,13 @@ fn test_parse_patch() {
         vec![UpdateFile {
             path: PathBuf::from("file2.py"),
             move_path: None,
+            executable: None,
             chunks: vec![UpdateFileChunk {
                 change_context: None,
                 old_lines: vec!["import foo".to_string()],
                 new_lines: vec!["import foo".to_string(), "bar".to_string()],
                 is_end_of_file: false,
+                line_hint: None,
             }],
         }]
     );
@@ -593,11 +645,13 @@ fn test_parse_patch_lenient() {
     let expected_patch = vec![UpdateFile {
         path: PathBuf::from("file2.py"),
         move_path: None,
+        executable: None,
         chunks: vec![UpdateFileChunk {
             change_context: None,
             old_lines: vec!["import foo".to_string()],
             new_lines: vec!["import foo".to_string(), "bar".to_string()],
             is_end_of_file: false,
+            line_hint: None,
         }],
     }];
     let expected_error =
@@ -614,6 +668,7 @@ fn test_parse_patch_lenient() {
             hunks: expected_patch.clone(),
             patch: patch_text.to_string(),
             workdir: None,
+            skipped: Vec::new(),
         })
     );
 
@@ -628,6 +683,7 @@ fn test_parse_patch_lenient() {
             hunks: expected_patch.clone(),
             patch: patch_text.to_string(),
             workdir: None,
+            skipped: Vec::new(),
         })
     );
 
@@ -642,6 +698,7 @@ fn test_parse_patch_lenient() {
             hunks: expected_patch,
             patch: patch_text.to_string(),
             workdir: None,
+            skipped: Vec::new(),
         })
     );
 
@@ -743,7 +800,8 @@ fn test_update_file_chunk() {
                     "add".to_string(),
                     "context2".to_string()
                 ],
-                is_end_of_file: false
+                is_end_of_file: false,
+                line_hint: None
             }),
             6
         ))
@@ -755,9 +813,39 @@ fn test_update_file_chunk() {
                 change_context: None,
                 old_lines: vec![],
                 new_lines: vec!["line".to_string()],
-                is_end_of_file: true
+                is_end_of_file: true,
+                line_hint: None
             }),
             3
         ))
     );
 }
+
+#[test]
+fn test_parse_patch_detects_unified_diff() {
+    let diff = "diff --git a/src/app.py b/src/app.py\nindex 1111111..2222222 100644\n--- a/src/app.py\n+++ b/src/app.py\n@@ -1,2 +1,2 @@ def main():\n import os\n-print(os.getcwd())\n+print(os.getcwd(), flush=True)\n";
+
+    assert_eq!(
+        parse_patch_text(diff, ParseMode::Strict),
+        Ok(ApplyPatchArgs {
+            hunks: vec![UpdateFile {
+                path: PathBuf::from("src/app.py"),
+                move_path: None,
+                executable: None,
+                chunks: vec![UpdateFileChunk {
+                    change_context: None,
+                    old_lines: vec!["import os".to_string(), "print(os.getcwd())".to_string()],
+                    new_lines: vec![
+                        "import os".to_string(),
+                        "print(os.getcwd(), flush=True)".to_string()
+                    ],
+                    is_end_of_file: false,
+                    line_hint: Some(0),
+                }],
+            }],
+            patch: diff.to_string(),
+            workdir: None,
+            skipped: Vec::new(),
+        })
+    );
+}
@@ -72,27 +72,6 @@ pub(crate) fn seek_sequence(
     // fuzzy behaviour of `git apply` which ignores minor byte-level
     // differences when locating context lines.
     // ------------------------------------------------------------------
-
-    fn normalise(s: &str) -> String {
-        s.trim()
-            .chars()
-            .map(|c| match c {
-                // Various dash / hyphen code-points → ASCII '-'
-                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
-                | '\u{2212}' => '-',
-                // Fancy single quotes → '\''
-                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
-                // Fancy double quotes → '"'
-                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
-                // Non-breaking space and other odd spaces → normal space
-                '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
-                | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
-                | '\u{3000}' => ' ',
-                other => other,
-            })
-            .collect::<String>()
-    }
-
     for i in search_start..=lines.len().saturating_sub(pattern.len()) {
         let mut ok = true;
         for (p_idx, pat) in pattern.iter().enumerate() {
@@ -109,8 +88,348 @@ pub(crate) fn seek_sequence(
     None
 }
 
+fn normalise(s: &str) -> String {
+    s.trim()
+        .chars()
+        .map(|c| match c {
+            // Various dash / hyphen code-points → ASCII '-'
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+            | '\u{2212}' => '-',
+            // Fancy single quotes → '\''
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            // Fancy double quotes → '"'
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            // Non-breaking space and other odd spaces → normal space
+            '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
+            | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
+            | '\u{3000}' => ' ',
+            other => other,
+        })
+        .collect::<String>()
+}
+
+/// Whether two lines are the same once whitespace and typographic punctuation
+/// are normalised, the most lenient comparison [`seek_sequence`] makes.
+pub(crate) fn lines_equivalent(lhs: &str, rhs: &str) -> bool {
+    normalise(lhs) == normalise(rhs)
+}
+
+/// Candidates scoring below this similarity are too different to be worth
+/// showing back to the model as "closest match".
+pub(crate) const MIN_REPORTED_SIMILARITY: f32 = 0.5;
+
+/// A second, non-overlapping candidate within this margin of the best one
+/// makes a fuzzy match ambiguous, in which case it is never applied unless a
+/// line hint singles one out.
+const AMBIGUITY_MARGIN: f32 = 0.02;
+
+/// Upper bound on pattern-line × file-line similarity computations for fuzzy
+/// scoring, so huge files fail fast instead of stalling the turn.
+const MAX_FUZZY_COMPARISONS: usize = 250_000;
+
+/// Upper bound on edit-distance cells evaluated while scoring regions. Each
+/// candidate offset costs about `pattern.len()²` cells, so this also limits
+/// how long a chunk may be for fuzzy matching.
+const MAX_FUZZY_ALIGNMENT_CELLS: usize = 10_000_000;
+
+/// Lines longer than this are only compared exactly; a character-level diff
+/// of two long lines costs quadratic time.
+const MAX_FUZZY_LINE_CHARS: usize = 256;
+
+/// Best approximate location of a pattern in the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FuzzyMatch {
+    /// Index of the first line of the candidate region.
+    pub(crate) start: usize,
+    /// Number of file lines in the candidate region. May differ from the
+    /// pattern length by one when the file gained or lost a line.
+    pub(crate) len: usize,
+    /// `1.0 - edit_distance / max(len, pattern.len())`, where the line-level
+    /// edit distance charges `1.0 - line_similarity` per substituted line.
+    pub(crate) similarity: f32,
+    /// Whether another, non-overlapping region scored almost as well.
+    pub(crate) ambiguous: bool,
+}
+
+/// Scores every region of `lines` starting at or after `start` against
+/// `pattern` with a line-level edit distance and returns the closest one.
+///
+/// Regions are tried with the pattern's length and with one line more or less,
+/// so a stale context that is missing a line (or has an extra one) can still be
+/// located. When several regions score within [`AMBIGUITY_MARGIN`] of each
+/// other, the one starting closest to `line_hint` wins. Returns `None` for an
+/// empty pattern or when the search would be too expensive.
+pub(crate) fn closest_sequence(
+    lines: &[String],
+    pattern: &[String],
+    start: usize,
+    line_hint: Option<usize>,
+) -> Option<FuzzyMatch> {
+    if pattern.is_empty() || start >= lines.len() {
+        return None;
+    }
+    let haystack = &lines[start..];
+    let comparisons = pattern.len().saturating_mul(haystack.len());
+    if comparisons > MAX_FUZZY_COMPARISONS
+        || comparisons.saturating_mul(pattern.len() + 2) > MAX_FUZZY_ALIGNMENT_CELLS
+    {
+        return None;
+    }
+
+    let normalised_pattern: Vec<String> = pattern.iter().map(|line| normalise(line)).collect();
+    let normalised_haystack: Vec<String> = haystack.iter().map(|line| normalise(line)).collect();
+    let similarities: Vec<Vec<f32>> = normalised_pattern
+        .iter()
+        .map(|pat| {
+            normalised_haystack
+                .iter()
+                .map(|line| line_similarity(pat, line))
+                .collect()
+        })
+        .collect();
+
+    let min_len = pattern.len().saturating_sub(1).max(1);
+    let max_len = pattern.len() + 1;
+    let mut scored: Vec<FuzzyMatch> = Vec::new();
+    for offset in 0..haystack.len() {
+        let mut best_here: Option<FuzzyMatch> = None;
+        for len in min_len..=max_len {
+            if offset + len > haystack.len() {
+                break;
+            }
+            let distance = region_edit_distance(&similarities, offset, len);
+            let similarity = 1.0 - distance / len.max(pattern.len()) as f32;
+            if best_here.is_none_or(|best| similarity > best.similarity) {
+                best_here = Some(FuzzyMatch {
+                    start: start + offset,
+                    len,
+                    similarity,
+                    ambiguous: false,
+                });
+            }
+        }
+        scored.extend(best_here);
+    }
+
+    // Best first; among equal scores the later region, as before hints existed.
+    scored.sort_by(|lhs, rhs| {
+        rhs.similarity
+            .total_cmp(&lhs.similarity)
+            .then(rhs.start.cmp(&lhs.start))
+    });
+    let best = *scored.first()?;
+    // The best region plus every non-overlapping one that scores almost as well.
+    let mut contenders: Vec<FuzzyMatch> = Vec::new();
+    for candidate in scored
+        .iter()
+        .take_while(|candidate| best.similarity - candidate.similarity <= AMBIGUITY_MARGIN)
+    {
+        let overlaps = |kept: &FuzzyMatch| {
+            candidate.start < kept.start + kept.len && kept.start < candidate.start + candidate.len
+        };
+        if !contenders.iter().any(overlaps) {
+            contenders.push(*candidate);
+        }
+    }
+
+    let Some(line_hint) = line_hint.filter(|_| contenders.len() > 1) else {
+        return Some(FuzzyMatch {
+            ambiguous: contenders.len() > 1,
+            ..best
+        });
+    };
+    let distance = |candidate: &FuzzyMatch| candidate.start.abs_diff(line_hint);
+    contenders.sort_by_key(distance);
+    let chosen = contenders[0];
+    Some(FuzzyMatch {
+        ambiguous: distance(&contenders[1]) == distance(&chosen),
+        ..chosen
+    })
+}
+
+/// Line-level edit distance between the whole pattern and the `len` file lines
+/// starting at `offset`, using precomputed per-line similarities.
+fn region_edit_distance(similarities: &[Vec<f32>], offset: usize, len: usize) -> f32 {
+    let mut previous: Vec<f32> = (0..=len).map(|j| j as f32).collect();
+    for (i, row) in similarities.iter().enumerate() {
+        let mut current = vec![0.0; len + 1];
+        current[0] = (i + 1) as f32;
+        for j in 1..=len {
+            let substitute = previous[j - 1] + (1.0 - row[offset + j - 1]);
+            let delete = previous[j] + 1.0;
+            let insert = current[j - 1] + 1.0;
+            current[j] = substitute.min(delete).min(insert);
+        }
+        previous = current;
+    }
+    previous[len]
+}
+
+/// Pairs each pattern line with the region line it was matched against by the
+/// same edit distance [`closest_sequence`] uses, or `None` when the line has no
+/// counterpart in `region`.
+pub(crate) fn align_lines(pattern: &[String], region: &[String]) -> Vec<Option<usize>> {
+    let normalised_region: Vec<String> = region.iter().map(|line| normalise(line)).collect();
+    let similarities: Vec<Vec<f32>> = pattern
+        .iter()
+        .map(|pat| {
+            let pat = normalise(pat);
+            normalised_region
+                .iter()
+                .map(|line| line_similarity(&pat, line))
+                .collect()
+        })
+        .collect();
+
+    let rows = pattern.len();
+    let cols = region.len();
+    let mut cost = vec![vec![0.0_f32; cols + 1]; rows + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i as f32;
+    }
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j as f32;
+    }
+    for i in 1..=rows {
+        for j in 1..=cols {
+            let substitute = cost[i - 1][j - 1] + (1.0 - similarities[i - 1][j - 1]);
+            let delete = cost[i - 1][j] + 1.0;
+            let insert = cost[i][j - 1] + 1.0;
+            cost[i][j] = substitute.min(delete).min(insert);
+        }
+    }
+
+    let mut alignment = vec![None; rows];
+    let (mut i, mut j) = (rows, cols);
+    while i > 0 && j > 0 {
+        let substitute = cost[i - 1][j - 1] + (1.0 - similarities[i - 1][j - 1]);
+        if cost[i][j] == substitute {
+            alignment[i - 1] = Some(j - 1);
+            i -= 1;
+            j -= 1;
+        } else if cost[i][j] == cost[i - 1][j] + 1.0 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    alignment
+}
+
+fn line_similarity(lhs: &str, rhs: &str) -> f32 {
+    if lhs == rhs {
+        return 1.0;
+    }
+    let (lhs_len, rhs_len) = (lhs.chars().count(), rhs.chars().count());
+    if lhs_len == 0 || rhs_len == 0 || lhs_len.max(rhs_len) > MAX_FUZZY_LINE_CHARS {
+        return 0.0;
+    }
+    // The diff ratio can never exceed this bound, so very differently sized
+    // lines are treated as unrelated without running the diff.
+    let upper_bound = 2.0 * lhs_len.min(rhs_len) as f32 / (lhs_len + rhs_len) as f32;
+    if upper_bound < MIN_REPORTED_SIMILARITY {
+        return 0.0;
+    }
+    similar::TextDiff::from_chars(lhs, rhs).ratio()
+}
+
+/// Modifiers that may precede a scope keyword without changing which scope a
+/// `@@` context refers to, e.g. `@@ fn run` should anchor `pub async fn run(`.
+const SCOPE_MODIFIERS: [&str; 16] = [
+    "pub",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "export",
+    "default",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "abstract",
+    "final",
+    "override",
+    "virtual",
+];
+
+fn strip_scope_modifiers(line: &str) -> &str {
+    let mut rest = line.trim();
+    loop {
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let word = &rest[..word_end];
+        if word_end == rest.len() || !(SCOPE_MODIFIERS.contains(&word) || word.starts_with("pub("))
+        {
+            return rest;
+        }
+        rest = rest[word_end..].trim_start();
+    }
+}
+
+/// Finds the first line at or after `start` that declares the scope named by a
+/// `@@` change context, ignoring visibility/async-style modifiers and anything
+/// after the name, so `@@ fn parse` anchors `pub(crate) fn parse(input: &str) {`
+/// but not `fn parse_args(`.
+pub(crate) fn seek_scope_header(lines: &[String], context: &str, start: usize) -> Option<usize> {
+    let context = strip_scope_modifiers(context);
+    if context.is_empty() {
+        return None;
+    }
+    let ends_in_identifier = context
+        .chars()
+        .last()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_');
+    lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .find_map(|(idx, line)| {
+            let rest = strip_scope_modifiers(line).strip_prefix(context)?;
+            let at_boundary = !ends_in_identifier
+                || !rest
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            at_boundary.then_some(idx)
+        })
+}
+
+/// Returns the exclusive end of the scope opened by the header at `header`.
+///
+/// The scope runs until the next non-blank line indented no deeper than the
+/// header. A closing `}`/`)`/`]` or `end` at that indentation is part of the
+/// scope, unless it continues the header (e.g. `) -> Result<()> {`).
+pub(crate) fn scope_end(lines: &[String], header: usize) -> usize {
+    let Some(header_line) = lines.get(header) else {
+        return lines.len();
+    };
+    let header_indent = indentation(header_line);
+    for (idx, line) in lines.iter().enumerate().skip(header + 1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || indentation(line) > header_indent {
+            continue;
+        }
+        let closes =
+            trimmed.starts_with(['}', ')', ']']) || trimmed == "end" || trimmed.starts_with("end ");
+        if closes && (trimmed.ends_with('{') || trimmed.ends_with(':')) {
+            continue;
+        }
+        return if closes { idx + 1 } else { idx };
+    }
+    lines.len()
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::align_lines;
+    use super::closest_sequence;
+    use super::scope_end;
+    use super::seek_scope_header;
     use super::seek_sequence;
     use std::string::ToString;
 
@@ -148,4 +467,107 @@ mod tests {
         // Should not panic – must return None when pattern cannot possibly fit.
         assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
     }
+
+    #[test]
+    fn test_closest_sequence_scores_stale_context() {
+        let lines = to_vec(&["fn main() {", "    let x = 1;", "    run(x);", "}"]);
+        let pattern = to_vec(&["fn main() {", "    let x = 2;", "    run(x);", "}"]);
+
+        let closest = closest_sequence(&lines, &pattern, 0, None).expect("candidate");
+
+        assert_eq!((closest.start, closest.len), (0, 4));
+        assert!(closest.similarity > 0.95, "{closest:?}");
+        assert!(!closest.ambiguous);
+    }
+
+    #[test]
+    fn test_closest_sequence_tolerates_a_missing_line() {
+        let lines = to_vec(&["header", "alpha", "beta", "gamma", "footer"]);
+        let pattern = to_vec(&["alpha", "gamma"]);
+
+        let closest = closest_sequence(&lines, &pattern, 0, None).expect("candidate");
+
+        assert_eq!((closest.start, closest.len), (1, 3));
+    }
+
+    #[test]
+    fn test_closest_sequence_flags_ambiguous_regions() {
+        let lines = to_vec(&[
+            "open()",
+            "value = 1",
+            "close()",
+            "open()",
+            "value = 1",
+            "close()",
+        ]);
+        let pattern = to_vec(&["open()", "value = 2", "close()"]);
+
+        let closest = closest_sequence(&lines, &pattern, 0, None).expect("candidate");
+
+        assert!(closest.ambiguous);
+    }
+
+    #[test]
+    fn test_closest_sequence_line_hint_picks_between_equal_regions() {
+        let lines = to_vec(&[
+            "open()",
+            "value = 1",
+            "close()",
+            "open()",
+            "value = 1",
+            "close()",
+        ]);
+        let pattern = to_vec(&["open()", "value = 2", "close()"]);
+
+        let near_second = closest_sequence(&lines, &pattern, 0, Some(4)).expect("candidate");
+        assert_eq!((near_second.start, near_second.ambiguous), (3, false));
+
+        let near_first = closest_sequence(&lines, &pattern, 0, Some(0)).expect("candidate");
+        assert_eq!((near_first.start, near_first.ambiguous), (0, false));
+    }
+
+    #[test]
+    fn test_closest_sequence_gives_up_on_long_patterns() {
+        let lines: Vec<String> = (0..2_000).map(|idx| format!("line {idx}")).collect();
+        let pattern: Vec<String> = (0..100).map(|idx| format!("line {idx}!")).collect();
+
+        assert_eq!(closest_sequence(&lines, &pattern, 0, None), None);
+    }
+
+    #[test]
+    fn test_align_lines_skips_lines_missing_from_region() {
+        let pattern = to_vec(&["alpha", "beta", "gamma"]);
+        let region = to_vec(&["alpha", "gamma"]);
+
+        assert_eq!(align_lines(&pattern, &region), vec![Some(0), None, Some(1)]);
+    }
+
+    #[test]
+    fn test_seek_scope_header_ignores_modifiers_and_requires_word_boundary() {
+        let lines = to_vec(&[
+            "fn parse_args() {",
+            "}",
+            "pub(crate) async fn parse(input: &str) {",
+            "}",
+        ]);
+
+        assert_eq!(seek_scope_header(&lines, "fn parse", 0), Some(2));
+        assert_eq!(seek_scope_header(&lines, "fn missing", 0), None);
+    }
+
+    #[test]
+    fn test_scope_end_handles_braces_and_indentation() {
+        let rust = to_vec(&[
+            "fn a(",
+            "    x: u8,",
+            ") {",
+            "    body(x);",
+            "}",
+            "fn b() {}",
+        ]);
+        assert_eq!(scope_end(&rust, 0), 5);
+
+        let python = to_vec(&["def a():", "    return 1", "", "def b():"]);
+        assert_eq!(scope_end(&python, 0), 3);
+    }
 }
@@ -9,9 +9,11 @@ pub fn main() -> ! {
 /// We would prefer to return `std::process::ExitCode`, but its `exit_process()`
 /// method is still a nightly API and we want main() to return !.
 pub fn run_main() -> i32 {
-    // Expect either one argument (the full apply_patch payload) or read it from stdin.
-    let mut args = std::env::args_os();
+    // Expect either one argument (the full apply_patch payload) or read it from stdin,
+    // optionally preceded by `--atomic`.
+    let mut args = std::env::args_os().peekable();
     let _argv0 = args.next();
+    let atomic = args.next_if(|arg| arg == "--atomic").is_some();
 
     let patch_arg = match args.next() {
         Some(arg) => match arg.into_string() {
@@ -27,7 +29,9 @@ pub fn run_main() -> i32 {
             match std::io::stdin().read_to_string(&mut buf) {
                 Ok(_) => {
                     if buf.is_empty() {
-                        eprintln!("Usage: apply_patch 'PATCH'\n       echo 'PATCH' | apply_patch");
+                        eprintln!(
+                            "Usage: apply_patch [--atomic] 'PATCH'\n       echo 'PATCH' | apply_patch [--atomic]"
+                        );
                         return 2;
                     }
                     buf
@@ -48,7 +52,12 @@ pub fn run_main() -> i32 {
 
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
-    match crate::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
+    let result = if atomic {
+        crate::apply_patch_atomically(&patch_arg, &mut stdout, &mut stderr)
+    } else {
+        crate::apply_patch(&patch_arg, &mut stdout, &mut stderr)
+    };
+    match result {
         Ok(()) => {
             // Flush to ensure output ordering when used in pipelines.
             let _ = stdout.flush();
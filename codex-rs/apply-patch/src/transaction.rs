@@ -0,0 +1,608 @@
+//! All-or-nothing application of a parsed patch.
+//!
+//! The work happens in three phases so that a failure at any point can be
+//! undone:
+//!
+//! 1. **Plan**: compute the final state of every touched path in memory. Later
+//!    hunks see the results of earlier ones, and any hunk that does not apply
+//!    fails the patch before the filesystem is touched.
+//! 2. **Stage**: write every new file body to a temp file next to its target,
+//!    so the final rename never crosses a filesystem boundary.
+//! 3. **Commit**: move each existing target aside to a backup, then rename the
+//!    staged file into place (deleted paths and move sources are simply left
+//!    moved aside). Writes go through symlinks to the file they point at, as an
+//!    in-place write would, so a patched symlink stays a symlink.
+//!
+//! Every filesystem change made during staging and commit is journaled; on
+//! failure the journal is replayed in reverse to restore the original tree.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use tempfile::NamedTempFile;
+
+use crate::AffectedPaths;
+use crate::AppliedPatch;
+use crate::ApplyPatchError;
+use crate::HunkMatchOptions;
+use crate::IoError;
+use crate::derive_new_contents;
+use crate::parser::Hunk;
+use crate::with_executable_bit;
+
+/// Why an atomic apply failed, and what it could not undo.
+pub(crate) struct TransactionFailure {
+    pub(crate) error: anyhow::Error,
+    /// Paths that could not be restored after the failure. Empty when the
+    /// rollback left the tree exactly as it was.
+    pub(crate) left_changed: Vec<PathBuf>,
+}
+
+pub(crate) fn apply_hunks_atomically(
+    hunks: &[Hunk],
+    options: &HunkMatchOptions,
+) -> Result<AffectedPaths, TransactionFailure> {
+    let plan = plan(hunks, options).map_err(|error| TransactionFailure {
+        error,
+        left_changed: Vec::new(),
+    })?;
+
+    let mut journal = Journal::default();
+    match commit(&plan.targets, &plan.executable, &mut journal) {
+        Ok(()) => {
+            journal.discard_backups();
+            Ok(plan.affected)
+        }
+        Err(error) => Err(TransactionFailure {
+            error,
+            left_changed: journal.roll_back(),
+        }),
+    }
+}
+
+struct Plan {
+    /// Final state of every touched path, in the order the patch first touches
+    /// it. `None` means the path no longer exists afterwards.
+    targets: Vec<(PathBuf, Option<String>)>,
+    /// Targets whose executable bit the patch sets or clears.
+    executable: HashMap<PathBuf, bool>,
+    affected: AffectedPaths,
+}
+
+/// In-memory view of the files the patch touches, layered over the disk.
+#[derive(Default)]
+struct Overlay {
+    state: HashMap<PathBuf, Option<String>>,
+    order: Vec<PathBuf>,
+}
+
+impl Overlay {
+    fn set(&mut self, path: &Path, contents: Option<String>) {
+        if self.state.insert(path.to_path_buf(), contents).is_none() {
+            self.order.push(path.to_path_buf());
+        }
+    }
+
+    /// Returns `Some(exists)` when the patch has already decided the fate of
+    /// `path`, `None` when the disk is authoritative.
+    fn exists(&self, path: &Path) -> Option<bool> {
+        self.state.get(path).map(Option::is_some)
+    }
+
+    fn read(&self, path: &Path) -> Result<String, ApplyPatchError> {
+        let read = match self.state.get(path) {
+            Some(Some(contents)) => Ok(contents.clone()),
+            // Deleted or moved away by an earlier hunk.
+            Some(None) => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No such file or directory",
+            )),
+            None => std::fs::read_to_string(path),
+        };
+        read.map_err(|source| {
+            ApplyPatchError::IoError(IoError {
+                context: format!("Failed to read file to update {}", path.display()),
+                source,
+            })
+        })
+    }
+
+    fn into_targets(mut self) -> Vec<(PathBuf, Option<String>)> {
+        self.order
+            .into_iter()
+            .filter_map(|path| {
+                let contents = self.state.remove(&path)?;
+                Some((path, contents))
+            })
+            .collect()
+    }
+}
+
+fn plan(hunks: &[Hunk], options: &HunkMatchOptions) -> anyhow::Result<Plan> {
+    if hunks.is_empty() {
+        anyhow::bail!("No files were modified.");
+    }
+
+    let mut overlay = Overlay::default();
+    let mut executable_bits = HashMap::new();
+    let mut affected = AffectedPaths::default();
+    for hunk in hunks {
+        match hunk {
+            Hunk::AddFile { path, contents } => {
+                ensure_writable_target(path)?;
+                overlay.set(path, Some(contents.clone()));
+                affected.added.push(path.clone());
+            }
+            Hunk::DeleteFile { path } => {
+                let exists = match overlay.exists(path) {
+                    Some(exists) => exists,
+                    None => std::fs::metadata(path)
+                        .with_context(|| format!("Failed to delete file {}", path.display()))?
+                        .is_file(),
+                };
+                if !exists {
+                    anyhow::bail!("Failed to delete file {}", path.display());
+                }
+                overlay.set(path, None);
+                affected.deleted.push(path.clone());
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+                executable,
+            } => {
+                let original_contents = overlay.read(path)?;
+                let AppliedPatch { new_contents, .. } =
+                    derive_new_contents(path, original_contents, chunks, options)?;
+                if let Some(executable) = executable {
+                    executable_bits.insert(move_path.as_ref().unwrap_or(path).clone(), *executable);
+                }
+                if let Some(dest) = move_path {
+                    ensure_writable_target(dest)?;
+                    overlay.set(path, None);
+                    overlay.set(dest, Some(new_contents));
+                    affected.modified.push(dest.clone());
+                } else {
+                    overlay.set(path, Some(new_contents));
+                    affected.modified.push(path.clone());
+                }
+            }
+        }
+    }
+
+    Ok(Plan {
+        targets: overlay.into_targets(),
+        executable: executable_bits,
+        affected,
+    })
+}
+
+/// Rejects targets that a plain write would fail on, so the failure surfaces
+/// while planning rather than after other files have been committed.
+fn ensure_writable_target(path: &Path) -> anyhow::Result<()> {
+    if path.is_dir() {
+        return Err(std::io::Error::from(std::io::ErrorKind::IsADirectory))
+            .with_context(|| format!("Failed to write file {}", path.display()));
+    }
+    Ok(())
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+fn commit(
+    targets: &[(PathBuf, Option<String>)],
+    executable: &HashMap<PathBuf, bool>,
+    journal: &mut Journal,
+) -> anyhow::Result<()> {
+    // Stage every new body before any target is touched. Staged files that are
+    // never persisted are removed when they are dropped.
+    let mut staged = Vec::with_capacity(targets.len());
+    for (target, contents) in targets {
+        let (target, staged_file) = match contents {
+            Some(contents) => {
+                let executable = executable.get(target).copied();
+                let target = resolve_symlinks(target);
+                let staged_file = stage(&target, contents, executable, journal)?;
+                (target, Some(staged_file))
+            }
+            // Deleting or moving a symlink removes the link, not its target.
+            None => (target.clone(), None),
+        };
+        staged.push((target, staged_file));
+    }
+
+    for (target, staged_file) in staged {
+        let target = &target;
+        let backup = if target.symlink_metadata().is_ok() {
+            let backup = backup_path(target);
+            std::fs::rename(target, &backup)
+                .with_context(|| format!("Failed to back up {}", target.display()))?;
+            Some(backup)
+        } else {
+            None
+        };
+        journal.steps.push(Step {
+            target: target.clone(),
+            backup,
+            placed: false,
+        });
+
+        if let Some(staged_file) = staged_file {
+            staged_file
+                .persist(target)
+                .map_err(|err| err.error)
+                .with_context(|| format!("Failed to write file {}", target.display()))?;
+            if let Some(step) = journal.steps.last_mut() {
+                step.placed = true;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Follows `path` through any symlinks to the file a write would land in,
+/// including the missing destination of a dangling link.
+fn resolve_symlinks(path: &Path) -> PathBuf {
+    let mut resolved = path.to_path_buf();
+    // Bounded like the kernel's own symlink limit, so a link cycle stops.
+    for _ in 0..40 {
+        let Ok(link) = std::fs::read_link(&resolved) else {
+            break;
+        };
+        resolved = parent_dir(&resolved).join(link);
+    }
+    resolved
+}
+
+fn stage(
+    target: &Path,
+    contents: &str,
+    executable: Option<bool>,
+    journal: &mut Journal,
+) -> anyhow::Result<NamedTempFile> {
+    let parent = parent_dir(target);
+    journal.create_dir_all(parent).with_context(|| {
+        format!(
+            "Failed to create parent directories for {}",
+            target.display()
+        )
+    })?;
+    let mut staged_file = NamedTempFile::new_in(parent)
+        .and_then(|mut file| file.write_all(contents.as_bytes()).map(|()| file))
+        .with_context(|| format!("Failed to write file {}", target.display()))?;
+    staged_file
+        .flush()
+        .with_context(|| format!("Failed to write file {}", target.display()))?;
+    // Keep the mode of the file being replaced, as an in-place write would.
+    if let Ok(metadata) = std::fs::metadata(target) {
+        staged_file
+            .as_file()
+            .set_permissions(metadata.permissions())
+            .with_context(|| format!("Failed to write file {}", target.display()))?;
+    }
+    if let Some(executable) = executable {
+        let file = staged_file.as_file();
+        file.metadata()
+            .and_then(|metadata| {
+                file.set_permissions(with_executable_bit(metadata.permissions(), executable))
+            })
+            .with_context(|| format!("Failed to change the mode of {}", target.display()))?;
+    }
+    Ok(staged_file)
+}
+
+/// Picks an unused sibling path to move `target` aside to.
+fn backup_path(target: &Path) -> PathBuf {
+    let parent = parent_dir(target);
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let pid = std::process::id();
+    let mut attempt = 0u32;
+    loop {
+        let candidate = parent.join(format!(".{name}.apply_patch-{pid}-{attempt}.bak"));
+        if candidate.symlink_metadata().is_err() {
+            return candidate;
+        }
+        attempt += 1;
+    }
+}
+
+/// One committed target: where its original was moved, and whether a new
+/// file was renamed into its place.
+struct Step {
+    target: PathBuf,
+    backup: Option<PathBuf>,
+    placed: bool,
+}
+
+#[derive(Default)]
+struct Journal {
+    steps: Vec<Step>,
+    created_dirs: Vec<PathBuf>,
+}
+
+impl Journal {
+    /// Like [`std::fs::create_dir_all`], but records each directory it creates
+    /// so a rollback can remove them again.
+    fn create_dir_all(&mut self, dir: &Path) -> std::io::Result<()> {
+        let missing: Vec<&Path> = dir
+            .ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .take_while(|ancestor| ancestor.symlink_metadata().is_err())
+            .collect();
+        for ancestor in missing.into_iter().rev() {
+            std::fs::create_dir(ancestor)?;
+            self.created_dirs.push(ancestor.to_path_buf());
+        }
+        Ok(())
+    }
+
+    fn discard_backups(self) {
+        for backup in self.steps.into_iter().filter_map(|step| step.backup) {
+            let _ = std::fs::remove_file(backup);
+        }
+    }
+
+    /// Undoes every journaled change, newest first. Returns the targets that
+    /// could not be restored.
+    fn roll_back(self) -> Vec<PathBuf> {
+        let mut left_changed = Vec::new();
+        for step in self.steps.into_iter().rev() {
+            let restored = match &step.backup {
+                // Renaming the backup over the target also discards whatever
+                // was placed there.
+                Some(backup) => std::fs::rename(backup, &step.target).is_ok(),
+                None => !step.placed || std::fs::remove_file(&step.target).is_ok(),
+            };
+            if !restored {
+                left_changed.push(step.target);
+            }
+        }
+        for dir in self.created_dirs.into_iter().rev() {
+            let _ = std::fs::remove_dir(dir);
+        }
+        left_changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_patch;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn hunks(dir: &Path, body: &str) -> Vec<Hunk> {
+        let body = body.replace("{dir}", &dir.display().to_string());
+        parse_patch(&format!("*** Begin Patch\n{body}\n*** End Patch"))
+            .expect("patch should parse")
+            .hunks
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn applies_every_kind_of_hunk() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("update.txt"), "one\ntwo\n").unwrap();
+        fs::write(dir.path().join("delete.txt"), "gone\n").unwrap();
+        fs::write(dir.path().join("move.txt"), "old\n").unwrap();
+
+        let hunks = hunks(
+            dir.path(),
+            "*** Add File: {dir}/nested/add.txt\n+new\n\
+             *** Delete File: {dir}/delete.txt\n\
+             *** Update File: {dir}/update.txt\n@@\n one\n-two\n+2\n\
+             *** Update File: {dir}/move.txt\n*** Move to: {dir}/moved.txt\n@@\n-old\n+new",
+        );
+        let affected = apply_hunks_atomically(&hunks, &HunkMatchOptions::default())
+            .unwrap_or_else(|failure| panic!("{}", failure.error));
+
+        assert_eq!(
+            affected,
+            AffectedPaths {
+                added: vec![dir.path().join("nested/add.txt")],
+                modified: vec![dir.path().join("update.txt"), dir.path().join("moved.txt")],
+                deleted: vec![dir.path().join("delete.txt")],
+            }
+        );
+        assert_eq!(names(dir.path()), vec!["moved.txt", "nested", "update.txt"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("update.txt")).unwrap(),
+            "one\n2\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("moved.txt")).unwrap(),
+            "new\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("nested/add.txt")).unwrap(),
+            "new\n"
+        );
+    }
+
+    #[test]
+    fn later_hunks_see_earlier_ones() {
+        let dir = tempdir().unwrap();
+        let hunks = hunks(
+            dir.path(),
+            "*** Add File: {dir}/a.txt\n+first\n\
+             *** Update File: {dir}/a.txt\n@@\n-first\n+second\n\
+             *** Update File: {dir}/a.txt\n*** Move to: {dir}/b.txt\n@@\n-second\n+third",
+        );
+        apply_hunks_atomically(&hunks, &HunkMatchOptions::default())
+            .unwrap_or_else(|failure| panic!("{}", failure.error));
+
+        assert_eq!(names(dir.path()), vec!["b.txt"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("b.txt")).unwrap(),
+            "third\n"
+        );
+    }
+
+    #[test]
+    fn failing_hunk_leaves_tree_untouched() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("delete.txt"), "keep\n").unwrap();
+        fs::write(dir.path().join("move.txt"), "old\n").unwrap();
+
+        let hunks = hunks(
+            dir.path(),
+            "*** Add File: {dir}/nested/add.txt\n+new\n\
+             *** Delete File: {dir}/delete.txt\n\
+             *** Update File: {dir}/move.txt\n*** Move to: {dir}/moved.txt\n@@\n-old\n+new\n\
+             *** Update File: {dir}/missing.txt\n@@\n-old\n+new",
+        );
+        let Err(failure) = apply_hunks_atomically(&hunks, &HunkMatchOptions::default()) else {
+            panic!("patch should fail");
+        };
+
+        assert_eq!(
+            failure.error.to_string(),
+            format!(
+                "Failed to read file to update {}: No such file or directory (os error 2)",
+                dir.path().join("missing.txt").display()
+            )
+        );
+        assert!(failure.left_changed.is_empty());
+        assert_eq!(names(dir.path()), vec!["delete.txt", "move.txt"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("delete.txt")).unwrap(),
+            "keep\n"
+        );
+    }
+
+    #[test]
+    fn staging_failure_removes_created_directories() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("blocker"), "a file, not a dir\n").unwrap();
+
+        let hunks = hunks(
+            dir.path(),
+            "*** Add File: {dir}/fresh/deep/add.txt\n+new\n\
+             *** Add File: {dir}/blocker/add.txt\n+new",
+        );
+        let Err(failure) = apply_hunks_atomically(&hunks, &HunkMatchOptions::default()) else {
+            panic!("patch should fail");
+        };
+
+        assert!(failure.left_changed.is_empty());
+        assert_eq!(names(dir.path()), vec!["blocker"]);
+    }
+
+    #[test]
+    fn roll_back_restores_committed_targets() {
+        let dir = tempdir().unwrap();
+        let replaced = dir.path().join("replaced.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let added = dir.path().join("new/added.txt");
+        fs::write(&replaced, "original\n").unwrap();
+        fs::write(&deleted, "original\n").unwrap();
+
+        let targets = vec![
+            (replaced.clone(), Some("patched\n".to_string())),
+            (deleted.clone(), None),
+            (added.clone(), Some("added\n".to_string())),
+        ];
+        let mut journal = Journal::default();
+        commit(&targets, &HashMap::new(), &mut journal).unwrap();
+        assert_eq!(fs::read_to_string(&replaced).unwrap(), "patched\n");
+        assert!(!deleted.exists());
+        assert!(added.exists());
+
+        assert!(journal.roll_back().is_empty());
+        assert_eq!(names(dir.path()), vec!["deleted.txt", "replaced.txt"]);
+        assert_eq!(fs::read_to_string(&replaced).unwrap(), "original\n");
+        assert_eq!(fs::read_to_string(&deleted).unwrap(), "original\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn updates_write_through_symlinks() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("real.txt"), "old\n").unwrap();
+        std::os::unix::fs::symlink("real.txt", dir.path().join("link.txt")).unwrap();
+
+        let hunks = hunks(
+            dir.path(),
+            "*** Update File: {dir}/link.txt\n@@\n-old\n+new",
+        );
+        apply_hunks_atomically(&hunks, &HunkMatchOptions::default())
+            .unwrap_or_else(|failure| panic!("{}", failure.error));
+
+        assert!(
+            fs::symlink_metadata(dir.path().join("link.txt"))
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(names(dir.path()), vec!["link.txt", "real.txt"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("real.txt")).unwrap(),
+            "new\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commit_failure_rolls_back_earlier_targets() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let locked = dir.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(dir.path().join("update.txt"), "old\n").unwrap();
+        fs::write(locked.join("delete.txt"), "keep\n").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o555)).unwrap();
+        if fs::write(locked.join("probe"), "").is_ok() {
+            // Running with privileges that ignore directory permissions.
+            return;
+        }
+
+        // The update is committed before the delete fails to move its target
+        // out of the read-only directory.
+        let hunks = hunks(
+            dir.path(),
+            "*** Update File: {dir}/update.txt\n@@\n-old\n+new\n\
+             *** Delete File: {dir}/locked/delete.txt",
+        );
+        let result = apply_hunks_atomically(&hunks, &HunkMatchOptions::default());
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        let Err(failure) = result else {
+            panic!("patch should fail");
+        };
+
+        assert_eq!(
+            failure.error.to_string(),
+            format!("Failed to back up {}", locked.join("delete.txt").display())
+        );
+        assert!(failure.left_changed.is_empty());
+        assert_eq!(names(dir.path()), vec!["locked", "update.txt"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("update.txt")).unwrap(),
+            "old\n"
+        );
+        assert_eq!(names(&locked), vec!["delete.txt"]);
+    }
+}
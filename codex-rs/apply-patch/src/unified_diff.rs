@@ -0,0 +1,843 @@
+//! Converts standard unified diffs (`diff -u`, `git diff`, `git format-patch`)
+//! into the same [`Hunk`]s the `*** Begin Patch` envelope produces, so they go
+//! through the same verification, approval and sandbox checks.
+//!
+//! Supported input:
+//! - plain `--- old` / `+++ new` file headers, optionally followed by a tab and
+//!   a timestamp;
+//! - `diff --git a/old b/new` sections with their extended headers (`index`,
+//!   `new file mode`, `deleted file mode`, `rename from`/`rename to`,
+//!   `similarity index`);
+//! - input must start with a diff or mail header; after that, anything before
+//!   the first file header (e.g. the mail header and diffstat of
+//!   `git format-patch` output) and between sections is ignored.
+//!
+//! Hunks are located by their context lines exactly like envelope chunks; the
+//! `@@ -a,b` line numbers only break ties between equally close fuzzy matches.
+//! `old mode`/`new mode` headers that toggle the executable bit are applied
+//! along with the file's hunks. Binary sections are skipped, leaving that file
+//! untouched while the rest of the diff applies. Copies, symlinks and
+//! submodules cannot be expressed as apply_patch hunks and are rejected with a
+//! [`ParseError`].
+
+use std::path::PathBuf;
+
+use crate::parser::Hunk;
+use crate::parser::ParseError;
+use crate::parser::ParseError::*;
+use crate::parser::UpdateFileChunk;
+
+const GIT_DIFF_HEADER: &str = "diff --git ";
+const DIFF_COMMAND_HEADER: &str = "diff ";
+/// First-line headers of `git format-patch` output and mailed patches.
+const MAIL_HEADERS: [&str; 3] = ["From ", "From: ", "Subject: "];
+const OLD_FILE_HEADER: &str = "--- ";
+const NEW_FILE_HEADER: &str = "+++ ";
+const HUNK_HEADER: &str = "@@ ";
+const NO_NEWLINE_MARKER: &str = "\\ No newline at end of file";
+const DEV_NULL: &str = "/dev/null";
+const REGULAR_FILE_MODE: &str = "100644";
+const EXECUTABLE_FILE_MODE: &str = "100755";
+/// `diff -r` reports binary files with a single line and no file header.
+const BINARY_FILES_PREFIX: &str = "Binary files ";
+const BINARY_FILES_SUFFIX: &str = " differ";
+
+/// Returns true when `lines` start, after any blank lines, with a unified diff
+/// header: a `diff ` command line (`diff --git`, `diff -ru`), a `--- ` line
+/// directly followed by a `+++ ` line, or the mail header `git format-patch`
+/// emits. Text that merely contains a diff somewhere, such as a shell script
+/// piping one into `patch`, is not a unified diff.
+pub(crate) fn looks_like_unified_diff(lines: &[&str]) -> bool {
+    let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return false;
+    };
+    let line = lines[first];
+    line.starts_with(DIFF_COMMAND_HEADER)
+        || is_file_header_pair(lines, first)
+        || MAIL_HEADERS.iter().any(|header| line.starts_with(header))
+}
+
+fn is_file_header_pair(lines: &[&str], idx: usize) -> bool {
+    lines
+        .get(idx)
+        .is_some_and(|line| line.starts_with(OLD_FILE_HEADER))
+        && lines
+            .get(idx + 1)
+            .is_some_and(|next| next.starts_with(NEW_FILE_HEADER))
+}
+
+pub(crate) struct ParsedUnifiedDiff {
+    pub(crate) hunks: Vec<Hunk>,
+    /// Files whose binary sections were skipped.
+    pub(crate) skipped: Vec<PathBuf>,
+}
+
+/// Parses every file section of a unified diff into hunks.
+pub(crate) fn parse_unified_diff(lines: &[&str]) -> Result<ParsedUnifiedDiff, ParseError> {
+    let mut hunks = Vec::new();
+    let mut skipped = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        if lines[idx].starts_with(GIT_DIFF_HEADER) || is_file_header_pair(lines, idx) {
+            let (section, consumed) = parse_file_section(&lines[idx..], idx + 1)?;
+            match section {
+                Section::Hunk(hunk) => hunks.push(hunk),
+                Section::Binary(path) => skipped.push(path),
+                Section::Unchanged => {}
+            }
+            idx += consumed;
+        } else {
+            if let Some(path) = plain_binary_files_path(lines[idx]) {
+                skipped.push(path);
+            }
+            // Mail headers, commit messages, diffstats, `diff -r` command lines
+            // and the `-- ` signature of format-patch output.
+            idx += 1;
+        }
+    }
+    if hunks.is_empty() {
+        let message = if skipped.is_empty() {
+            "unified diff does not contain any file changes".to_string()
+        } else {
+            format!(
+                "unified diff only changes binary files, which cannot be applied with apply_patch: {}",
+                skipped
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        return Err(InvalidPatchError(message));
+    }
+    Ok(ParsedUnifiedDiff { hunks, skipped })
+}
+
+/// Returns the new path of a `diff -r` style `Binary files <old> and <new>
+/// differ` line, or the whole `<old> and <new>` text when it is ambiguous.
+fn plain_binary_files_path(line: &str) -> Option<PathBuf> {
+    let paths = line
+        .strip_prefix(BINARY_FILES_PREFIX)?
+        .strip_suffix(BINARY_FILES_SUFFIX)?;
+    let mut separators = paths.match_indices(" and ");
+    let path = match (separators.next(), separators.next()) {
+        (Some((split, separator)), None) => {
+            let (old, new) = (&paths[..split], &paths[split + separator.len()..]);
+            match plain_diff_paths(parse_header_path(old), parse_header_path(new)) {
+                (Some(path), _) => path,
+                (None, _) => PathBuf::from(paths),
+            }
+        }
+        _ => PathBuf::from(paths),
+    };
+    Some(path)
+}
+
+/// What one file section of a unified diff amounts to.
+enum Section {
+    Hunk(Hunk),
+    /// A binary diff for the given path, which apply_patch cannot apply.
+    Binary(PathBuf),
+    /// Nothing apply_patch needs to do, e.g. an identical mode line.
+    Unchanged,
+}
+
+#[derive(Default)]
+struct FileSection {
+    /// Whether the section started with a `diff --git` line.
+    is_git: bool,
+    old_path: Option<PathBuf>,
+    new_path: Option<PathBuf>,
+    rename_from: Option<PathBuf>,
+    rename_to: Option<PathBuf>,
+    old_mode: Option<String>,
+    new_mode: Option<String>,
+    is_new_file: bool,
+    is_deleted_file: bool,
+    is_binary: bool,
+    chunks: Vec<UpdateFileChunk>,
+    /// Whether the last added line was followed by `\ No newline at end of file`.
+    new_missing_final_newline: bool,
+}
+
+/// Parses one file section starting at its `diff --git` or `--- ` header.
+fn parse_file_section(lines: &[&str], line_number: usize) -> Result<(Section, usize), ParseError> {
+    let mut section = FileSection::default();
+    let mut idx = 0;
+
+    if let Some(paths) = lines[0].strip_prefix(GIT_DIFF_HEADER) {
+        section.is_git = true;
+        if let Some((old, new)) = split_git_header_paths(paths) {
+            section.old_path = Some(old);
+            section.new_path = Some(new);
+        }
+        idx = 1;
+        while idx < lines.len() {
+            let line = lines[idx];
+            if line.starts_with(GIT_DIFF_HEADER) || line.starts_with(HUNK_HEADER) {
+                break;
+            }
+            if is_file_header_pair(lines, idx) {
+                break;
+            }
+            // A binary section has no hunks, so a further `Binary files` line
+            // reports another file, e.g. in `diff -r` output.
+            if section.is_binary && line.starts_with(BINARY_FILES_PREFIX) {
+                break;
+            }
+            parse_extended_header(line, &mut section, line_number + idx)?;
+            idx += 1;
+        }
+    }
+
+    // File headers after a binary section belong to the next file.
+    if !section.is_binary && is_file_header_pair(lines, idx) {
+        section.old_path =
+            parse_header_path(&lines[idx][OLD_FILE_HEADER.len()..]).or(section.old_path.take());
+        section.new_path =
+            parse_header_path(&lines[idx + 1][NEW_FILE_HEADER.len()..]).or(section.new_path.take());
+        if lines[idx][OLD_FILE_HEADER.len()..].starts_with(DEV_NULL) {
+            section.is_new_file = true;
+        }
+        if lines[idx + 1][NEW_FILE_HEADER.len()..].starts_with(DEV_NULL) {
+            section.is_deleted_file = true;
+        }
+        idx += 2;
+    }
+
+    while idx < lines.len() && lines[idx].starts_with(HUNK_HEADER) {
+        idx += parse_hunk(&lines[idx..], line_number + idx, &mut section)?;
+    }
+
+    Ok((section.into_section(line_number)?, idx))
+}
+
+fn parse_extended_header(
+    line: &str,
+    section: &mut FileSection,
+    line_number: usize,
+) -> Result<(), ParseError> {
+    let unsupported = |what: &str| InvalidHunkError {
+        message: format!("{what} cannot be applied with apply_patch: '{line}'"),
+        line_number,
+    };
+    if let Some(mode) = line.strip_prefix("new file mode ") {
+        section.is_new_file = true;
+        section.new_mode = Some(mode.trim().to_string());
+    } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+        section.is_deleted_file = true;
+        section.old_mode = Some(mode.trim().to_string());
+    } else if let Some(mode) = line.strip_prefix("old mode ") {
+        section.old_mode = Some(mode.trim().to_string());
+    } else if let Some(mode) = line.strip_prefix("new mode ") {
+        section.new_mode = Some(mode.trim().to_string());
+    } else if let Some(path) = line.strip_prefix("rename from ") {
+        section.rename_from = Some(PathBuf::from(unquote_path(path)));
+    } else if let Some(path) = line.strip_prefix("rename to ") {
+        section.rename_to = Some(PathBuf::from(unquote_path(path)));
+    } else if line.starts_with("copy from ") || line.starts_with("copy to ") {
+        return Err(unsupported("copying a file"));
+    } else if line.starts_with(BINARY_FILES_PREFIX) || line == "GIT binary patch" {
+        section.is_binary = true;
+    }
+    // `index`, `similarity index`, `dissimilarity index` and the data lines of
+    // a binary patch carry no information apply_patch needs.
+    Ok(())
+}
+
+/// Parses a single `@@ -a,b +c,d @@` hunk and returns the number of lines it
+/// spans, header included.
+fn parse_hunk(
+    lines: &[&str],
+    line_number: usize,
+    section: &mut FileSection,
+) -> Result<usize, ParseError> {
+    let HunkHeader {
+        old_start,
+        old_count: mut old_remaining,
+        new_count: mut new_remaining,
+    } = parse_hunk_header(lines[0]).ok_or_else(|| InvalidHunkError {
+        message: format!("invalid unified diff hunk header: '{}'", lines[0]),
+        line_number,
+    })?;
+
+    let mut chunk = UpdateFileChunk {
+        change_context: None,
+        old_lines: Vec::new(),
+        new_lines: Vec::new(),
+        is_end_of_file: false,
+        // `-0,0` (nothing before the hunk) has no line to point at.
+        line_hint: old_start.checked_sub(1),
+    };
+    let mut idx = 1;
+    let mut last_kind = ' ';
+    while old_remaining > 0 || new_remaining > 0 || lines.get(idx) == Some(&NO_NEWLINE_MARKER) {
+        let Some(line) = lines.get(idx) else {
+            // Editors commonly strip a trailing blank context line.
+            if old_remaining == new_remaining {
+                chunk.old_lines.push(String::new());
+                chunk.new_lines.push(String::new());
+                old_remaining -= 1;
+                new_remaining -= 1;
+                continue;
+            }
+            return Err(InvalidHunkError {
+                message: "unified diff hunk ended before its declared line counts".to_string(),
+                line_number: line_number + idx,
+            });
+        };
+        if *line == NO_NEWLINE_MARKER {
+            match last_kind {
+                '+' => section.new_missing_final_newline = true,
+                ' ' => {
+                    section.new_missing_final_newline = true;
+                    chunk.is_end_of_file = true;
+                }
+                _ => chunk.is_end_of_file = true,
+            }
+            idx += 1;
+            continue;
+        }
+        let (kind, text) = match line.chars().next() {
+            // A bare empty line is a blank context line whose leading space was
+            // stripped, as GNU patch also accepts.
+            None => (' ', ""),
+            Some(kind) => (kind, &line[kind.len_utf8()..]),
+        };
+        match kind {
+            ' ' if old_remaining > 0 && new_remaining > 0 => {
+                chunk.old_lines.push(text.to_string());
+                chunk.new_lines.push(text.to_string());
+                old_remaining -= 1;
+                new_remaining -= 1;
+            }
+            '-' if old_remaining > 0 => {
+                chunk.old_lines.push(text.to_string());
+                old_remaining -= 1;
+            }
+            '+' if new_remaining > 0 => {
+                chunk.new_lines.push(text.to_string());
+                new_remaining -= 1;
+            }
+            _ => {
+                return Err(InvalidHunkError {
+                    message: format!(
+                        "unexpected line in unified diff hunk (expected {old_remaining} more old and {new_remaining} more new lines): '{line}'"
+                    ),
+                    line_number: line_number + idx,
+                });
+            }
+        }
+        last_kind = kind;
+        idx += 1;
+    }
+
+    section.chunks.push(chunk);
+    Ok(idx)
+}
+
+struct HunkHeader {
+    /// One-based first line of the old range.
+    old_start: usize,
+    old_count: usize,
+    new_count: usize,
+}
+
+/// Parses `@@ -start[,count] +start[,count] @@ [heading]`.
+fn parse_hunk_header(line: &str) -> Option<HunkHeader> {
+    let rest = line.strip_prefix(HUNK_HEADER)?;
+    let (ranges, _heading) = rest.split_once(" @@")?;
+    let (old_range, new_range) = ranges.split_once(' ')?;
+    let range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(old_range.strip_prefix('-')?)?;
+    let (_, new_count) = range(new_range.strip_prefix('+')?)?;
+    Some(HunkHeader {
+        old_start,
+        old_count,
+        new_count,
+    })
+}
+
+impl FileSection {
+    fn into_section(self, line_number: usize) -> Result<Section, ParseError> {
+        let path_error = |message: &str| InvalidHunkError {
+            message: message.to_string(),
+            line_number,
+        };
+        for mode in [&self.old_mode, &self.new_mode].into_iter().flatten() {
+            match mode.as_str() {
+                "100644" | "100755" => {}
+                "120000" => return Err(path_error("symlinks cannot be applied with apply_patch")),
+                "160000" => {
+                    return Err(path_error("submodules cannot be applied with apply_patch"));
+                }
+                other => return Err(path_error(&format!("unsupported file mode {other}"))),
+            }
+        }
+        // Only regular files get this far, so a mode change toggles the
+        // executable bit.
+        let executable = match (self.old_mode.as_deref(), self.new_mode.as_deref()) {
+            (Some(old_mode), Some(new_mode)) if old_mode != new_mode => {
+                Some(new_mode == EXECUTABLE_FILE_MODE)
+            }
+            _ => None,
+        };
+
+        let (old_path, new_path) = if self.is_git {
+            (
+                self.rename_from
+                    .or(self.old_path.map(|path| strip_prefix(path, "a/"))),
+                self.rename_to
+                    .or(self.new_path.map(|path| strip_prefix(path, "b/"))),
+            )
+        } else {
+            plain_diff_paths(self.old_path, self.new_path)
+        };
+
+        if self.is_binary {
+            let path = new_path
+                .or(old_path)
+                .ok_or_else(|| path_error("binary file in diff has no path"))?;
+            return Ok(Section::Binary(path));
+        }
+
+        if self.is_new_file {
+            if self
+                .new_mode
+                .as_deref()
+                .is_some_and(|mode| mode != REGULAR_FILE_MODE)
+            {
+                return Err(path_error(
+                    "adding an executable file cannot be applied with apply_patch; add it and run `chmod` separately",
+                ));
+            }
+            let path = new_path.ok_or_else(|| path_error("new file in diff has no path"))?;
+            let mut contents = self
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.new_lines.iter())
+                .map(|line| format!("{line}\n"))
+                .collect::<String>();
+            if self.new_missing_final_newline {
+                contents.pop();
+            }
+            return Ok(Section::Hunk(Hunk::AddFile { path, contents }));
+        }
+
+        if self.is_deleted_file {
+            let path = old_path.ok_or_else(|| path_error("deleted file in diff has no path"))?;
+            return Ok(Section::Hunk(Hunk::DeleteFile { path }));
+        }
+
+        let path = old_path.ok_or_else(|| path_error("file in diff has no path"))?;
+        let move_path = new_path.filter(|new_path| *new_path != path);
+        if self.chunks.is_empty() && move_path.is_none() && executable.is_none() {
+            return Ok(Section::Unchanged);
+        }
+        for chunk in &self.chunks {
+            if chunk.old_lines.is_empty() {
+                return Err(path_error(&format!(
+                    "hunk for {} adds lines without any context lines to anchor them; regenerate the diff with context (e.g. `git diff -U3`)",
+                    path.display()
+                )));
+            }
+        }
+        Ok(Section::Hunk(Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks: self.chunks,
+            executable,
+        }))
+    }
+}
+
+/// Splits the `a/<old> b/<new>` part of a `diff --git` line. Quoted paths are
+/// unquoted; unquoted paths containing ` b/` are ambiguous and left to the
+/// `---`/`+++` or `rename` headers.
+fn split_git_header_paths(paths: &str) -> Option<(PathBuf, PathBuf)> {
+    let (old, new) = if paths.starts_with('"') {
+        let end = closing_quote(paths)?;
+        (&paths[..=end], paths[end + 1..].trim_start())
+    } else {
+        let mut candidates = paths
+            .match_indices(" b/")
+            .chain(paths.match_indices(" \"b/"));
+        let (split, _) = candidates.next()?;
+        if candidates.next().is_some() {
+            return None;
+        }
+        (&paths[..split], &paths[split + 1..])
+    };
+    Some((
+        PathBuf::from(unquote_path(old)),
+        PathBuf::from(unquote_path(new)),
+    ))
+}
+
+/// Parses the path from a `---`/`+++` header, dropping any tab-separated
+/// timestamp. Returns `None` for `/dev/null`.
+fn parse_header_path(header: &str) -> Option<PathBuf> {
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    if path == DEV_NULL {
+        return None;
+    }
+    Some(PathBuf::from(unquote_path(path)))
+}
+
+fn strip_prefix(path: PathBuf, prefix: &str) -> PathBuf {
+    match path.strip_prefix(prefix) {
+        Ok(stripped) => stripped.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
+/// Resolves the file a plain (non-git) diff section applies to. Plain diffs
+/// never rename: `diff -ru old/ new/` style headers that only differ in their
+/// first component are treated as the same relative path, like `patch -p1`.
+fn plain_diff_paths(
+    old_path: Option<PathBuf>,
+    new_path: Option<PathBuf>,
+) -> (Option<PathBuf>, Option<PathBuf>) {
+    let old_path = old_path.map(|path| strip_prefix(path, "a/"));
+    let new_path = new_path.map(|path| strip_prefix(path, "b/"));
+    let path = match (old_path, new_path) {
+        (Some(old), Some(new)) if old != new => {
+            let old_rest: PathBuf = old.components().skip(1).collect();
+            let new_rest: PathBuf = new.components().skip(1).collect();
+            if !old_rest.as_os_str().is_empty() && old_rest == new_rest {
+                old_rest
+            } else {
+                old
+            }
+        }
+        (Some(path), _) | (None, Some(path)) => path,
+        (None, None) => return (None, None),
+    };
+    (Some(path.clone()), Some(path))
+}
+
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in quoted.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(idx),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Undoes git's C-style quoting of paths with special characters.
+fn unquote_path(path: &str) -> String {
+    let path = path.trim();
+    let Some(inner) = path
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some(digit @ '0'..='7') => {
+                let mut value = digit.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    if let Some(next) = chars.peek().and_then(|c| c.to_digit(8)) {
+                        value = value * 8 + next;
+                        chars.next();
+                    }
+                }
+                bytes.push(u8::try_from(value).unwrap_or(u8::MAX));
+            }
+            Some(other) => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(diff: &str) -> Result<Vec<Hunk>, ParseError> {
+        let lines: Vec<&str> = diff.lines().collect();
+        parse_unified_diff(&lines).map(|diff| diff.hunks)
+    }
+
+    /// A chunk from a hunk that starts at line 1 (`@@ -1,n`).
+    fn chunk(old_lines: &[&str], new_lines: &[&str]) -> UpdateFileChunk {
+        UpdateFileChunk {
+            change_context: None,
+            old_lines: old_lines.iter().map(ToString::to_string).collect(),
+            new_lines: new_lines.iter().map(ToString::to_string).collect(),
+            is_end_of_file: false,
+            line_hint: Some(0),
+        }
+    }
+
+    #[test]
+    fn only_leading_headers_mark_a_unified_diff() {
+        let looks_like = |text: &str| looks_like_unified_diff(&text.lines().collect::<Vec<_>>());
+
+        assert!(looks_like("\ndiff --git a/x b/x\n"));
+        assert!(looks_like("--- x\n+++ x\n@@ -1 +1 @@\n"));
+        assert!(looks_like("From 1234 Mon Sep 17 00:00:00 2001\n"));
+        assert!(!looks_like("patch -p1 <<'EOF'\n--- x\n+++ x\n"));
+        assert!(!looks_like("git apply <<'EOF'\ndiff --git a/x b/x\n"));
+    }
+
+    #[test]
+    fn parses_plain_unified_diff_with_timestamps() {
+        let diff = "--- src/lib.rs\t2024-01-01 00:00:00\n+++ src/lib.rs\t2024-01-02 00:00:00\n@@ -1,3 +1,3 @@\n fn a() {}\n-fn b() {}\n+fn b() { todo!() }\n fn c() {}\n";
+
+        assert_eq!(
+            parse(diff),
+            Ok(vec![Hunk::UpdateFile {
+                path: PathBuf::from("src/lib.rs"),
+                move_path: None,
+                executable: None,
+                chunks: vec![chunk(
+                    &["fn a() {}", "fn b() {}", "fn c() {}"],
+                    &["fn a() {}", "fn b() { todo!() }", "fn c() {}"],
+                )],
+            }])
+        );
+    }
+
+    #[test]
+    fn parses_format_patch_with_add_delete_and_rename() {
+        let diff = r#"From 1234 Mon Sep 17 00:00:00 2001
+From: Dev <dev@example.com>
+Subject: [PATCH] Reorganize
+
+---
+ new.txt | 2 ++
+ 2 files changed
+
+diff --git a/new.txt b/new.txt
+new file mode 100644
+index 0000000..1111111
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1,2 @@
++hello
++world
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index 2222222..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/a.rs b/b.rs
+similarity index 90%
+rename from a.rs
+rename to b.rs
+index 3333333..4444444 100644
+--- a/a.rs
++++ b/b.rs
+@@ -1,2 +1,2 @@
+ keep
+-old
++new
+diff --git a/moved.txt b/renamed.txt
+similarity index 100%
+rename from moved.txt
+rename to renamed.txt
+--
+2.43.0
+"#;
+
+        assert_eq!(
+            parse(diff),
+            Ok(vec![
+                Hunk::AddFile {
+                    path: PathBuf::from("new.txt"),
+                    contents: "hello\nworld\n".to_string(),
+                },
+                Hunk::DeleteFile {
+                    path: PathBuf::from("old.txt"),
+                },
+                Hunk::UpdateFile {
+                    path: PathBuf::from("a.rs"),
+                    move_path: Some(PathBuf::from("b.rs")),
+                    executable: None,
+                    chunks: vec![chunk(&["keep", "old"], &["keep", "new"])],
+                },
+                Hunk::UpdateFile {
+                    path: PathBuf::from("moved.txt"),
+                    move_path: Some(PathBuf::from("renamed.txt")),
+                    executable: None,
+                    chunks: Vec::new(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn no_newline_marker_controls_new_file_contents_and_eof_anchor() {
+        let diff = "--- /dev/null\n+++ b/script.sh\n@@ -0,0 +1 @@\n+echo hi\n\\ No newline at end of file\n--- a/notes.txt\n+++ b/notes.txt\n@@ -1,2 +1,2 @@\n one\n-two\n\\ No newline at end of file\n+two\n";
+
+        let mut expected_chunk = chunk(&["one", "two"], &["one", "two"]);
+        expected_chunk.is_end_of_file = true;
+        assert_eq!(
+            parse(diff),
+            Ok(vec![
+                Hunk::AddFile {
+                    path: PathBuf::from("script.sh"),
+                    contents: "echo hi".to_string(),
+                },
+                Hunk::UpdateFile {
+                    path: PathBuf::from("notes.txt"),
+                    move_path: None,
+                    executable: None,
+                    chunks: vec![expected_chunk],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn skips_binary_sections_and_keeps_the_rest() {
+        let diff = "diff --git a/logo.png b/logo.png\nindex 1..2 100644\nBinary files a/logo.png and b/logo.png differ\ndiff --git a/icon.png b/icon.png\nindex 3..4 100644\nGIT binary patch\nliteral 5\nMcmZ?wbhEHbKLP\n\nliteral 0\nKcmV+b0RR6000031\n\nBinary files old/font.ttf and new/font.ttf differ\n--- a/notes.txt\n+++ b/notes.txt\n@@ -1 +1 @@\n-one\n+1\n";
+        let lines: Vec<&str> = diff.lines().collect();
+        let parsed = parse_unified_diff(&lines).expect("text section applies");
+
+        assert_eq!(
+            parsed.hunks,
+            vec![Hunk::UpdateFile {
+                path: PathBuf::from("notes.txt"),
+                move_path: None,
+                chunks: vec![chunk(&["one"], &["1"])],
+                executable: None,
+            }]
+        );
+        assert_eq!(
+            parsed.skipped,
+            vec![
+                PathBuf::from("logo.png"),
+                PathBuf::from("icon.png"),
+                PathBuf::from("font.ttf"),
+            ]
+        );
+
+        let binary_only =
+            "diff --git a/logo.png b/logo.png\nBinary files a/logo.png and b/logo.png differ\n";
+        assert_eq!(
+            parse(binary_only),
+            Err(InvalidPatchError(
+                "unified diff only changes binary files, which cannot be applied with apply_patch: logo.png".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn converts_executable_bit_changes() {
+        let diff = "diff --git a/run.sh b/run.sh\nold mode 100644\nnew mode 100755\ndiff --git a/lib.sh b/lib.sh\nold mode 100755\nnew mode 100644\nindex 1..2\n--- a/lib.sh\n+++ b/lib.sh\n@@ -1 +1 @@\n-old\n+new\ndiff --git a/same.sh b/same.sh\nold mode 100755\nnew mode 100755\n";
+
+        assert_eq!(
+            parse(diff),
+            Ok(vec![
+                Hunk::UpdateFile {
+                    path: PathBuf::from("run.sh"),
+                    move_path: None,
+                    chunks: Vec::new(),
+                    executable: Some(true),
+                },
+                Hunk::UpdateFile {
+                    path: PathBuf::from("lib.sh"),
+                    move_path: None,
+                    chunks: vec![chunk(&["old"], &["new"])],
+                    executable: Some(false),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn hunk_headers_give_line_hints() {
+        let diff = "--- a/x.txt\n+++ b/x.txt\n@@ -7,2 +7,2 @@ fn heading()\n keep\n-old\n+new\n@@ -20 +20 @@\n-a\n+b\n";
+        let hunks = parse(diff);
+        let Ok([Hunk::UpdateFile { chunks, .. }]) = hunks.as_deref() else {
+            panic!("expected one update");
+        };
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.line_hint)
+                .collect::<Vec<_>>(),
+            vec![Some(6), Some(19)]
+        );
+    }
+
+    #[test]
+    fn rejects_contextless_insertions() {
+        let contextless = "--- a/x.txt\n+++ b/x.txt\n@@ -3,0 +4 @@\n+inserted\n";
+        assert_eq!(
+            parse(contextless),
+            Err(InvalidHunkError {
+                message: "hunk for x.txt adds lines without any context lines to anchor them; regenerate the diff with context (e.g. `git diff -U3`)".to_string(),
+                line_number: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn unquotes_git_paths() {
+        assert_eq!(
+            split_git_header_paths(r#""a/with space.txt" "b/caf\303\251.txt""#),
+            Some((
+                PathBuf::from("a/with space.txt"),
+                PathBuf::from("b/café.txt")
+            ))
+        );
+        assert_eq!(
+            split_git_header_paths("a/src/main.rs b/src/main.rs"),
+            Some((
+                PathBuf::from("a/src/main.rs"),
+                PathBuf::from("b/src/main.rs")
+            ))
+        );
+    }
+
+    #[test]
+    fn plain_diff_paths_strip_differing_top_directory() {
+        assert_eq!(
+            plain_diff_paths(
+                Some(PathBuf::from("proj.orig/src/x.c")),
+                Some(PathBuf::from("proj/src/x.c"))
+            ),
+            (
+                Some(PathBuf::from("src/x.c")),
+                Some(PathBuf::from("src/x.c"))
+            )
+        );
+        assert_eq!(
+            plain_diff_paths(Some(PathBuf::from("x.c.orig")), Some(PathBuf::from("x.c"))),
+            (
+                Some(PathBuf::from("x.c.orig")),
+                Some(PathBuf::from("x.c.orig"))
+            )
+        );
+    }
+}
@@ -0,0 +1,4 @@
+def greet(name):
+    greeting = "Howdy"
+    print(f"{greeting}, {name}!")
+    return greeting
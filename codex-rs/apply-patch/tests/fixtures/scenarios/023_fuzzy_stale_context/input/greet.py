@@ -0,0 +1,4 @@
+def greet(name):
+    greeting = "Hello"
+    print(f"{greeting}, {name}!")
+    return greeting
@@ -255,3 +255,50 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
 
     Ok(())
 }
+
+#[test]
+fn test_apply_patch_cli_atomic_failure_rolls_back_earlier_hunks() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    fs::write(tmp.path().join("delete.txt"), "keep\n")?;
+    fs::write(tmp.path().join("source.txt"), "old\n")?;
+
+    apply_patch_command(tmp.path())?
+        .arg("--atomic")
+        .arg("*** Begin Patch\n*** Add File: created.txt\n+hello\n*** Delete File: delete.txt\n*** Update File: source.txt\n*** Move to: renamed.txt\n@@\n-old\n+new\n*** Update File: missing.txt\n@@\n-old\n+new\n*** End Patch")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr("Failed to read file to update missing.txt: No such file or directory (os error 2)\n");
+
+    assert!(!tmp.path().join("created.txt").exists());
+    assert!(!tmp.path().join("renamed.txt").exists());
+    assert_eq!(fs::read_to_string(tmp.path().join("delete.txt"))?, "keep\n");
+    assert_eq!(fs::read_to_string(tmp.path().join("source.txt"))?, "old\n");
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_patch_cli_atomic_applies_all_hunks() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    fs::write(tmp.path().join("delete.txt"), "obsolete\n")?;
+    fs::write(tmp.path().join("source.txt"), "old\n")?;
+
+    apply_patch_command(tmp.path())?
+        .arg("--atomic")
+        .arg("*** Begin Patch\n*** Add File: nested/created.txt\n+hello\n*** Delete File: delete.txt\n*** Update File: source.txt\n*** Move to: renamed.txt\n@@\n-old\n+new\n*** End Patch")
+        .assert()
+        .success()
+        .stdout("Success. Updated the following files:\nA nested/created.txt\nM renamed.txt\nD delete.txt\n")
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("nested/created.txt"))?,
+        "hello\n"
+    );
+    assert_eq!(fs::read_to_string(tmp.path().join("renamed.txt"))?, "new\n");
+    assert!(!tmp.path().join("delete.txt").exists());
+    assert!(!tmp.path().join("source.txt").exists());
+
+    Ok(())
+}
@@ -89,16 +89,31 @@ pub fn arg0_dispatch() -> Option<Arg0PathEntryGuard> {
     let argv1 = args.next().unwrap_or_default();
     if argv1 == CODEX_CORE_APPLY_PATCH_ARG1 {
         let patch_arg = args.next().and_then(|s| s.to_str().map(str::to_owned));
-        let exit_code = match patch_arg {
-            Some(patch_arg) => {
+        // Hunk matching options configured in core follow the patch.
+        let options = codex_apply_patch::HunkMatchOptions::from_args(
+            args.map(|arg| arg.to_string_lossy().into_owned()),
+        );
+        let exit_code = match (patch_arg, options) {
+            (_, Err(err)) => {
+                eprintln!("Error: {CODEX_CORE_APPLY_PATCH_ARG1}: {err}");
+                1
+            }
+            (Some(patch_arg), Ok(options)) => {
                 let mut stdout = std::io::stdout();
                 let mut stderr = std::io::stderr();
-                match codex_apply_patch::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
+                // Patches from the agent are applied all-or-nothing, so a failing hunk
+                // never leaves the worktree half patched.
+                match codex_apply_patch::apply_patch_atomically_with_options(
+                    &patch_arg,
+                    &options,
+                    &mut stdout,
+                    &mut stderr,
+                ) {
                     Ok(()) => 0,
                     Err(_) => 1,
                 }
             }
-            None => {
+            (None, Ok(_)) => {
                 eprintln!("Error: {CODEX_CORE_APPLY_PATCH_ARG1} requires a UTF-8 PATCH argument.");
                 1
             }
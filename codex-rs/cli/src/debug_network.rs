@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::Config;
+use codex_network_proxy::har::Har;
+use codex_network_proxy::har::HarEntry;
+use codex_protocol::ThreadId;
+use codex_state::NetworkConnection;
+use codex_state::NetworkConnectionDecision;
+use codex_state::StateRuntime;
+use codex_state::state_db_path;
+
+#[derive(Debug, clap::Parser)]
+pub struct DebugNetworkCommand {
+    /// Thread (session) id whose network proxy traffic should be summarized.
+    #[arg(long = "thread", value_name = "THREAD_ID")]
+    pub thread_id: String,
+
+    /// Write request/response pairs captured with `network.capture_har` to this HAR file.
+    #[arg(long, value_name = "FILE")]
+    pub har: Option<PathBuf>,
+}
+
+pub async fn run_debug_network_command(cmd: DebugNetworkCommand, config: &Config) -> Result<()> {
+    let thread_id = ThreadId::from_string(&cmd.thread_id)
+        .with_context(|| format!("invalid thread id: {}", cmd.thread_id))?;
+    let state_path = state_db_path(config.sqlite_home.as_path());
+    if !tokio::fs::try_exists(&state_path).await? {
+        bail!("No state db found at {}.", state_path.display());
+    }
+    let state_db =
+        StateRuntime::init(config.sqlite_home.clone(), config.model_provider_id.clone()).await?;
+    let connections = state_db.list_network_connections(thread_id).await?;
+
+    if connections.is_empty() {
+        println!("No network connections recorded for thread {thread_id}.");
+    } else {
+        print!("{}", format_network_summary(&connections));
+    }
+
+    if let Some(path) = cmd.har {
+        let entries = har_entries(&connections)?;
+        let entry_count = entries.len();
+        let har = serde_json::to_string_pretty(&Har::new(entries))?;
+        tokio::fs::write(&path, har)
+            .await
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        println!("Wrote {entry_count} HAR entries to {}.", path.display());
+        if entry_count == 0 {
+            println!("Set `network.capture_har = true` to capture MITM'd requests.");
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct HostSummary {
+    allowed: usize,
+    denied: usize,
+    bytes_sent: u64,
+    bytes_received: u64,
+    methods: BTreeSet<String>,
+    processes: BTreeSet<String>,
+    rules: BTreeSet<String>,
+    deny_reasons: BTreeSet<String>,
+}
+
+/// Renders one block per host, in host order.
+fn format_network_summary(connections: &[NetworkConnection]) -> String {
+    let mut hosts: BTreeMap<String, HostSummary> = BTreeMap::new();
+    for connection in connections {
+        let host = match connection.port {
+            Some(port) => format!("{}:{port}", connection.host),
+            None => connection.host.clone(),
+        };
+        let summary = hosts.entry(host).or_default();
+        match connection.decision {
+            NetworkConnectionDecision::Allow => summary.allowed += 1,
+            NetworkConnectionDecision::Deny => summary.denied += 1,
+        }
+        summary.bytes_sent += connection.bytes_sent.unwrap_or_default();
+        summary.bytes_received += connection.bytes_received.unwrap_or_default();
+        summary.methods.extend(connection.method.clone());
+        summary.processes.extend(connection.process.clone());
+        summary.rules.extend(connection.rule.clone());
+        if connection.decision == NetworkConnectionDecision::Deny {
+            summary.deny_reasons.extend(connection.reason.clone());
+        }
+    }
+
+    let mut output = String::new();
+    for (host, summary) in hosts {
+        output.push_str(&format!(
+            "{host}: {} allowed, {} denied, {} bytes sent, {} bytes received\n",
+            summary.allowed, summary.denied, summary.bytes_sent, summary.bytes_received
+        ));
+        for (label, values) in [
+            ("methods", &summary.methods),
+            ("processes", &summary.processes),
+            ("rules", &summary.rules),
+            ("deny reasons", &summary.deny_reasons),
+        ] {
+            if !values.is_empty() {
+                let values = values.iter().cloned().collect::<Vec<_>>().join(", ");
+                output.push_str(&format!("  {label}: {values}\n"));
+            }
+        }
+    }
+    output
+}
+
+fn har_entries(connections: &[NetworkConnection]) -> Result<Vec<HarEntry>> {
+    connections
+        .iter()
+        .filter_map(|connection| connection.har_entry.as_deref())
+        .map(|entry| serde_json::from_str(entry).context("failed to parse stored HAR entry"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn connection(
+        host: &str,
+        method: &str,
+        decision: NetworkConnectionDecision,
+        reason: Option<&str>,
+        rule: Option<&str>,
+    ) -> NetworkConnection {
+        NetworkConnection {
+            thread_id: ThreadId::new(),
+            process: Some("call-1".to_string()),
+            client_addr: Some("127.0.0.1:50000".to_string()),
+            protocol: "https".to_string(),
+            host: host.to_string(),
+            port: Some(443),
+            method: Some(method.to_string()),
+            path: Some("/".to_string()),
+            decision,
+            reason: reason.map(str::to_string),
+            rule: rule.map(str::to_string),
+            status: None,
+            bytes_sent: Some(10),
+            bytes_received: (decision == NetworkConnectionDecision::Allow).then_some(100),
+            har_entry: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn format_network_summary_groups_by_host() {
+        let connections = vec![
+            connection(
+                "registry.npmjs.org",
+                "GET",
+                NetworkConnectionDecision::Allow,
+                None,
+                Some("allow GET registry.npmjs.org/**"),
+            ),
+            connection(
+                "example.com",
+                "GET",
+                NetworkConnectionDecision::Deny,
+                Some("not_allowed"),
+                None,
+            ),
+            connection(
+                "registry.npmjs.org",
+                "PUT",
+                NetworkConnectionDecision::Deny,
+                Some("request_rule_not_allowed"),
+                None,
+            ),
+        ];
+
+        assert_eq!(
+            format_network_summary(&connections),
+            "example.com:443: 0 allowed, 1 denied, 10 bytes sent, 0 bytes received
+  methods: GET
+  processes: call-1
+  deny reasons: not_allowed
+registry.npmjs.org:443: 1 allowed, 1 denied, 20 bytes sent, 100 bytes received
+  methods: GET, PUT
+  processes: call-1
+  rules: allow GET registry.npmjs.org/**
+  deny reasons: request_rule_not_allowed
+"
+        );
+    }
+}
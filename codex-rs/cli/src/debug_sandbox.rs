@@ -10,6 +10,7 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::NetworkProxyAuditMetadata;
 use codex_core::exec_env::create_env;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
+use codex_core::sandboxing::command_rules::CommandRuleSandbox;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
@@ -60,6 +61,7 @@ pub async fn run_command_under_landlock(
 ) -> anyhow::Result<()> {
     let LandlockCommand {
         full_auto,
+        print_plan,
         config_overrides,
         command,
     } = command;
@@ -68,7 +70,7 @@ pub async fn run_command_under_landlock(
         command,
         config_overrides,
         codex_linux_sandbox_exe,
-        SandboxType::Landlock,
+        SandboxType::Landlock { print_plan },
         false,
     )
     .await
@@ -97,7 +99,9 @@ pub async fn run_command_under_windows(
 enum SandboxType {
     #[cfg(target_os = "macos")]
     Seatbelt,
-    Landlock,
+    Landlock {
+        print_plan: bool,
+    },
     Windows,
 }
 
@@ -223,6 +227,7 @@ async fn run_command_under_sandbox(
                 config.permissions.sandbox_policy.get(),
                 None,
                 None,
+                None,
                 managed_network_requirements_enabled,
                 NetworkProxyAuditMetadata::default(),
             )
@@ -249,13 +254,29 @@ async fn run_command_under_sandbox(
             )
             .await?
         }
-        SandboxType::Landlock => {
+        SandboxType::Landlock { print_plan } => {
             use codex_core::features::Feature;
             #[expect(clippy::expect_used)]
             let codex_linux_sandbox_exe = config
                 .codex_linux_sandbox_exe
                 .expect("codex-linux-sandbox executable not found");
             let use_bwrap_sandbox = config.features.enabled(Feature::UseLinuxSandboxBwrap);
+            let exec_policy = codex_core::load_exec_policy(&config.config_layer_stack).await?;
+            let command_rules = CommandRuleSandbox::for_command(
+                &exec_policy,
+                &command,
+                cwd.as_path(),
+                config.permissions.sandbox_policy.get(),
+                &env,
+            );
+            let write_scope = config
+                .permissions
+                .sandbox_write_scope
+                .for_command(&command_rules);
+            let seccomp = config
+                .permissions
+                .sandbox_seccomp
+                .for_command(config.permissions.sandbox_policy.get(), &command_rules)?;
             spawn_command_under_linux_sandbox(
                 codex_linux_sandbox_exe,
                 command,
@@ -263,6 +284,9 @@ async fn run_command_under_sandbox(
                 config.permissions.sandbox_policy.get(),
                 sandbox_policy_cwd.as_path(),
                 use_bwrap_sandbox,
+                &write_scope,
+                &seccomp,
+                print_plan,
                 stdio_policy,
                 network.as_ref(),
                 env,
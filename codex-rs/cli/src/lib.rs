@@ -29,6 +29,11 @@ pub struct LandlockCommand {
     #[arg(long = "full-auto", default_value_t = false)]
     pub full_auto: bool,
 
+    /// Print the bubblewrap mounts, namespaces, and seccomp/landlock rules that would be
+    /// applied to the command, then exit without running it.
+    #[arg(long = "print-plan", default_value_t = false)]
+    pub print_plan: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
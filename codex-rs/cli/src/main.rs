@@ -36,13 +36,18 @@ use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod debug_network;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod secrets_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::debug_network::DebugNetworkCommand;
+use crate::debug_network::run_debug_network_command;
 use crate::mcp_cmd::McpCli;
+use crate::secrets_cmd::SecretsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -102,6 +107,9 @@ enum Subcommand {
     /// Start Codex as an MCP server (stdio).
     McpServer,
 
+    /// Manage secrets that can be injected into sandboxed commands.
+    Secrets(SecretsCli),
+
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
@@ -166,6 +174,9 @@ enum DebugSubcommand {
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
 
+    /// Summarize network proxy traffic recorded for a thread.
+    Network(DebugNetworkCommand),
+
     /// Internal: reset local memory state for a fresh start.
     #[clap(hide = true)]
     ClearMemories,
@@ -314,7 +325,7 @@ struct AppServerCommand {
     subcommand: Option<AppServerSubcommand>,
 
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `unix://PATH`.
     #[arg(
         long = "listen",
         value_name = "URL",
@@ -322,6 +333,9 @@ struct AppServerCommand {
     )]
     listen: codex_app_server::AppServerTransport,
 
+    #[command(flatten)]
+    auth: codex_app_server::AppServerAuthArgs,
+
     /// Controls whether analytics are enabled by default.
     ///
     /// Analytics are disabled by default for app-server. Users have to explicitly opt in
@@ -602,15 +616,23 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Secrets(mut secrets_cli)) => {
+            prepend_config_flags(
+                &mut secrets_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            secrets_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
-                codex_app_server::run_main_with_transport(
+                codex_app_server::run_main_with_transport_and_auth(
                     arg0_paths.clone(),
                     root_config_overrides,
                     codex_core::config_loader::LoaderOverrides::default(),
                     app_server_cli.analytics_default_enabled,
                     transport,
+                    app_server_cli.auth,
                 )
                 .await?;
             }
@@ -758,6 +780,9 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             DebugSubcommand::AppServer(cmd) => {
                 run_debug_app_server_command(cmd).await?;
             }
+            DebugSubcommand::Network(cmd) => {
+                run_debug_network(cmd, &root_config_overrides, &interactive).await?;
+            }
             DebugSubcommand::ClearMemories => {
                 run_debug_clear_memories_command(&root_config_overrides, &interactive).await?;
             }
@@ -887,6 +912,23 @@ fn maybe_print_under_development_feature_warning(
     );
 }
 
+async fn run_debug_network(
+    cmd: DebugNetworkCommand,
+    root_config_overrides: &CliConfigOverrides,
+    interactive: &TuiCli,
+) -> anyhow::Result<()> {
+    let cli_kv_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let overrides = ConfigOverrides {
+        config_profile: interactive.config_profile.clone(),
+        ..Default::default()
+    };
+    let config =
+        Config::load_with_cli_overrides_and_harness_overrides(cli_kv_overrides, overrides).await?;
+    run_debug_network_command(cmd, &config).await
+}
+
 async fn run_debug_clear_memories_command(
     root_config_overrides: &CliConfigOverrides,
     interactive: &TuiCli,
@@ -1467,6 +1509,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn app_server_listen_unix_url_parses_with_auth_flags() {
+        let app_server = app_server_from_args(
+            [
+                "codex",
+                "app-server",
+                "--listen",
+                "unix:///tmp/codex.sock",
+                "--ws-token-file",
+                "/etc/codex/tokens",
+            ]
+            .as_ref(),
+        );
+        assert_eq!(
+            app_server.listen,
+            codex_app_server::AppServerTransport::Unix {
+                socket_path: PathBuf::from("/tmp/codex.sock"),
+            }
+        );
+        assert_eq!(
+            app_server.auth.ws_token_file,
+            Some(PathBuf::from("/etc/codex/tokens"))
+        );
+    }
+
+    #[test]
+    fn app_server_tls_key_requires_cert() {
+        let parse_result = MultitoolCli::try_parse_from([
+            "codex",
+            "app-server",
+            "--listen",
+            "ws://127.0.0.1:4500",
+            "--tls-key",
+            "key.pem",
+        ]);
+        assert!(parse_result.is_err());
+    }
+
     #[test]
     fn app_server_listen_invalid_url_fails_to_parse() {
         let parse_result =
@@ -1487,6 +1567,35 @@ mod tests {
         assert_eq!(feature, "unified_exec");
     }
 
+    #[test]
+    fn secrets_set_parses_name_and_scope() {
+        let cli =
+            MultitoolCli::try_parse_from(["codex", "secrets", "set", "GITHUB_TOKEN", "--project"])
+                .expect("parse should succeed");
+        let Some(Subcommand::Secrets(secrets_cli)) = cli.subcommand else {
+            panic!("expected secrets subcommand");
+        };
+        let secrets_cmd::SecretsSubcommand::Set(args) = secrets_cli.subcommand else {
+            panic!("expected secrets set");
+        };
+        assert_eq!(args.name, "GITHUB_TOKEN");
+        assert_eq!(args.value, None);
+        assert!(args.scope.project);
+    }
+
+    #[test]
+    fn secrets_scope_flags_conflict() {
+        let result = MultitoolCli::try_parse_from([
+            "codex",
+            "secrets",
+            "list",
+            "--env",
+            "repo",
+            "--project",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn features_disable_parses_feature_name() {
         let cli = MultitoolCli::try_parse_from(["codex", "features", "disable", "shell_tool"])
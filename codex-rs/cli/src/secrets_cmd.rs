@@ -0,0 +1,295 @@
+use std::io::IsTerminal;
+use std::io::Read;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::ArgGroup;
+use codex_core::config::Config;
+use codex_core::exec_env::CODEX_THREAD_ID_ENV_VAR;
+use codex_core::spawn::CODEX_SANDBOX_ENV_VAR;
+use codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
+use codex_secrets::SecretListEntry;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Subcommands:
+/// - `set`    — store a secret (value read from stdin unless `--value` is given)
+/// - `get`    — print a stored secret (interactive terminals only)
+/// - `list`   — list stored secret names (with `--json`)
+/// - `delete` — remove a stored secret
+///
+/// Secrets are global unless `--env <ID>` or `--project` selects an environment scope.
+/// `[[secrets.inject]]` entries in config.toml expose them to matching commands.
+#[derive(Debug, clap::Parser)]
+pub struct SecretsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SecretsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecretsSubcommand {
+    Set(SetArgs),
+    Get(GetArgs),
+    List(ListArgs),
+    Delete(DeleteArgs),
+}
+
+#[derive(Debug, Default, clap::Args)]
+#[command(group(ArgGroup::new("scope").args(["env", "project"]).multiple(false)))]
+pub struct ScopeArgs {
+    /// Use the environment scope with this id instead of the global scope.
+    #[arg(long = "env", value_name = "ENV_ID")]
+    pub env: Option<String>,
+
+    /// Use the environment scope of the current project (git repository name, or a hash
+    /// of the working directory).
+    #[arg(long)]
+    pub project: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Name of the secret (A-Z, 0-9 and _).
+    pub name: String,
+
+    /// Secret value. Prefer piping it on stdin so it does not end up in shell history.
+    #[arg(long)]
+    pub value: Option<String>,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// Name of the secret to print. Refused when stdout is not a terminal or inside a
+    /// command run by Codex; this guards against accidents, it does not stop a determined
+    /// agent.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the stored secret names as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DeleteArgs {
+    /// Name of the secret to remove.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+impl SecretsCli {
+    pub async fn run(self) -> Result<()> {
+        let SecretsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let manager = SecretsManager::new(config.codex_home.clone(), config.secrets.backend);
+
+        match subcommand {
+            SecretsSubcommand::Set(args) => run_set(&manager, &config, args),
+            SecretsSubcommand::Get(args) => run_get(&manager, &config, args),
+            SecretsSubcommand::List(args) => run_list(&manager, &config, args),
+            SecretsSubcommand::Delete(args) => run_delete(&manager, &config, args),
+        }
+    }
+}
+
+fn run_set(manager: &SecretsManager, config: &Config, args: SetArgs) -> Result<()> {
+    let SetArgs { name, value, scope } = args;
+    let name = SecretName::new(&name)?;
+    let scope = resolve_scope(&scope, config)?;
+    let value = match value {
+        Some(value) => value,
+        None => read_secret_from_stdin(&name)?,
+    };
+    if value.is_empty() {
+        bail!("secret value must not be empty");
+    }
+
+    manager
+        .set(&scope, &name, &value)
+        .with_context(|| format!("failed to store secret {name}"))?;
+    println!("Stored secret {name} ({}).", describe_scope(&scope));
+    Ok(())
+}
+
+fn run_get(manager: &SecretsManager, config: &Config, args: GetArgs) -> Result<()> {
+    ensure_get_is_interactive(std::io::stdout().is_terminal(), |key| {
+        std::env::var_os(key).is_some()
+    })?;
+    let GetArgs { name, scope } = args;
+    let name = SecretName::new(&name)?;
+    let scope = resolve_scope(&scope, config)?;
+
+    match manager
+        .get(&scope, &name)
+        .with_context(|| format!("failed to read secret {name}"))?
+    {
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => bail!("No secret named {name} ({}).", describe_scope(&scope)),
+    }
+}
+
+/// Environment variables Codex sets on the commands it runs for the agent.
+const AGENT_COMMAND_ENV_VARS: [&str; 3] = [
+    CODEX_SANDBOX_ENV_VAR,
+    CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR,
+    CODEX_THREAD_ID_ENV_VAR,
+];
+
+/// `get` only prints to a terminal, and never inside a command Codex runs for the agent:
+/// output scrubbing only matches the literal value, so piping it through `base64` or
+/// similar would leak the secret to the model.
+///
+/// This is a convenience check against accidental leaks, not a security boundary. A
+/// command can unset these variables and allocate a pseudo-terminal (e.g.
+/// `env -u CODEX_THREAD_ID script -qc 'codex secrets get NAME'`), and anything that can
+/// read `CODEX_HOME` and the OS keyring can decrypt the store directly.
+fn ensure_get_is_interactive(
+    stdout_is_terminal: bool,
+    env_is_set: impl Fn(&str) -> bool,
+) -> Result<()> {
+    if let Some(key) = AGENT_COMMAND_ENV_VARS
+        .into_iter()
+        .find(|key| env_is_set(key))
+    {
+        bail!("refusing to print a secret inside a Codex-run command ({key} is set)");
+    }
+    if !stdout_is_terminal {
+        bail!("refusing to print a secret when stdout is not a terminal");
+    }
+    Ok(())
+}
+
+fn run_list(manager: &SecretsManager, config: &Config, args: ListArgs) -> Result<()> {
+    let ListArgs { json, scope } = args;
+    let scope_filter = if scope.env.is_some() || scope.project {
+        Some(resolve_scope(&scope, config)?)
+    } else {
+        None
+    };
+
+    let mut entries = manager
+        .list(scope_filter.as_ref())
+        .context("failed to list secrets")?;
+    entries.sort_by(|a, b| {
+        describe_scope(&a.scope)
+            .cmp(&describe_scope(&b.scope))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    if json {
+        let json_entries = entries
+            .iter()
+            .map(|SecretListEntry { scope, name }| {
+                let environment = match scope {
+                    SecretScope::Global => None,
+                    SecretScope::Environment(environment_id) => Some(environment_id.as_str()),
+                };
+                serde_json::json!({
+                    "name": name.as_str(),
+                    "scope": if environment.is_some() { "environment" } else { "global" },
+                    "environment": environment,
+                })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&json_entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No secrets stored. Add one with `codex secrets set <NAME>`.");
+        return Ok(());
+    }
+
+    let name_width = entries
+        .iter()
+        .map(|entry| entry.name.as_str().len())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+    println!("{:<name_width$}  Scope", "Name");
+    for entry in &entries {
+        println!(
+            "{:<name_width$}  {}",
+            entry.name.as_str(),
+            describe_scope(&entry.scope)
+        );
+    }
+    Ok(())
+}
+
+fn run_delete(manager: &SecretsManager, config: &Config, args: DeleteArgs) -> Result<()> {
+    let DeleteArgs { name, scope } = args;
+    let name = SecretName::new(&name)?;
+    let scope = resolve_scope(&scope, config)?;
+
+    let removed = manager
+        .delete(&scope, &name)
+        .with_context(|| format!("failed to delete secret {name}"))?;
+    if removed {
+        println!("Deleted secret {name} ({}).", describe_scope(&scope));
+    } else {
+        println!("No secret named {name} ({}).", describe_scope(&scope));
+    }
+    Ok(())
+}
+
+fn resolve_scope(args: &ScopeArgs, config: &Config) -> Result<SecretScope> {
+    match (&args.env, args.project) {
+        (Some(environment_id), _) => SecretScope::environment(environment_id.clone()),
+        (None, true) => SecretScope::environment(environment_id_from_cwd(&config.cwd)),
+        (None, false) => Ok(SecretScope::Global),
+    }
+}
+
+fn describe_scope(scope: &SecretScope) -> String {
+    match scope {
+        SecretScope::Global => "global".to_string(),
+        SecretScope::Environment(environment_id) => format!("environment {environment_id}"),
+    }
+}
+
+fn read_secret_from_stdin(name: &SecretName) -> Result<String> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        bail!(
+            "pass the value of {name} on stdin or with --value, e.g. `printenv {name} | codex secrets set {name}`"
+        );
+    }
+
+    let mut buffer = String::new();
+    stdin
+        .read_to_string(&mut buffer)
+        .context("failed to read secret from stdin")?;
+    Ok(buffer.trim_end_matches(['\r', '\n']).to_string())
+}
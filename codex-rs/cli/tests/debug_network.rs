@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use anyhow::Result;
+use codex_protocol::ThreadId;
+use codex_state::NetworkConnection;
+use codex_state::NetworkConnectionDecision;
+use codex_state::StateRuntime;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[tokio::test]
+async fn debug_network_summarizes_thread_and_exports_har() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let runtime =
+        StateRuntime::init(codex_home.path().to_path_buf(), "test-provider".to_string()).await?;
+    let thread_id = ThreadId::new();
+    let har_entry = json!({
+        "startedDateTime": "2025-01-01T00:00:00.000Z",
+        "time": 12.0,
+        "request": {
+            "method": "GET",
+            "url": "https://registry.npmjs.org/left-pad",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "queryString": [],
+            "headersSize": -1,
+            "bodySize": 0
+        },
+        "response": {
+            "status": 200,
+            "statusText": "OK",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 2, "mimeType": "application/json", "text": "{}" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": 2
+        },
+        "cache": {},
+        "timings": { "send": 0.0, "wait": 12.0, "receive": 0.0 }
+    });
+    runtime
+        .insert_network_connection(&NetworkConnection {
+            thread_id,
+            process: Some("call-1".to_string()),
+            client_addr: Some("127.0.0.1:50000".to_string()),
+            protocol: "https".to_string(),
+            host: "registry.npmjs.org".to_string(),
+            port: Some(443),
+            method: Some("GET".to_string()),
+            path: Some("/left-pad".to_string()),
+            decision: NetworkConnectionDecision::Allow,
+            reason: None,
+            rule: None,
+            status: Some(200),
+            bytes_sent: Some(0),
+            bytes_received: Some(2),
+            har_entry: Some(har_entry.to_string()),
+            created_at: 1,
+        })
+        .await?;
+    drop(runtime);
+
+    let har_path = codex_home.path().join("traffic.har");
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args([
+        "debug",
+        "network",
+        "--thread",
+        &thread_id.to_string(),
+        "--har",
+        har_path.to_str().expect("utf-8 path"),
+    ])
+    .assert()
+    .success()
+    .stdout(contains(
+        "registry.npmjs.org:443: 1 allowed, 0 denied, 0 bytes sent, 2 bytes received",
+    ))
+    .stdout(contains("Wrote 1 HAR entries"));
+
+    let har: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&har_path)?)?;
+    assert_eq!(har["log"]["version"], json!("1.2"));
+    assert_eq!(har["log"]["entries"], json!([har_entry]));
+
+    Ok(())
+}
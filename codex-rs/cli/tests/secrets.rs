@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home)
+        .env_remove("CODEX_SANDBOX")
+        .env_remove("CODEX_SANDBOX_NETWORK_DISABLED")
+        .env_remove("CODEX_THREAD_ID");
+    Ok(cmd)
+}
+
+#[test]
+fn secrets_get_refuses_non_terminal_stdout() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["secrets", "get", "API_TOKEN"])
+        .assert()
+        .failure()
+        .stderr(contains("stdout is not a terminal"));
+
+    Ok(())
+}
+
+#[test]
+fn secrets_get_refuses_codex_run_commands() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .env("CODEX_SANDBOX", "seatbelt")
+        .args(["secrets", "get", "API_TOKEN"])
+        .assert()
+        .failure()
+        .stderr(contains("CODEX_SANDBOX is set"));
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use codex_state::CodeIndexFile;
+use codex_state::CodeSymbol;
+use codex_state::CodeSymbolRole;
+use codex_state::StateRuntime;
+use ignore::WalkBuilder;
+use tracing::warn;
+
+use crate::Language;
+use crate::Tag;
+use crate::TagRole;
+use crate::extract_tags;
+
+/// Files larger than this are skipped; they are almost always generated or
+/// vendored and would dominate parse time.
+pub const MAX_INDEXED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Counts reported by [`CodeIndex::refresh`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshStats {
+    /// Files (re-)parsed because they were new or changed.
+    pub indexed: usize,
+    /// Files whose fingerprint matched the stored one.
+    pub unchanged: usize,
+    /// Previously indexed files that no longer exist.
+    pub removed: usize,
+}
+
+/// Symbol index for one workspace root, persisted in the state DB so that
+/// only files whose size or mtime changed are re-parsed between calls.
+#[derive(Clone)]
+pub struct CodeIndex {
+    db: Arc<StateRuntime>,
+    root: PathBuf,
+}
+
+struct CandidateFile {
+    path: PathBuf,
+    relative: String,
+    language: Language,
+    modified_at_ms: i64,
+    size_bytes: i64,
+}
+
+impl CodeIndex {
+    pub fn new(db: Arc<StateRuntime>, root: PathBuf) -> Self {
+        Self { db, root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Brings the stored index in line with the files on disk.
+    pub async fn refresh(&self) -> anyhow::Result<RefreshStats> {
+        let root = self.root.clone();
+        let candidates = tokio::task::spawn_blocking(move || collect_candidates(&root)).await?;
+        let mut stored = self.db.code_index_files(&self.root).await?;
+        let mut stats = RefreshStats::default();
+
+        for candidate in candidates {
+            if let Some(previous) = stored.remove(&candidate.relative)
+                && previous.modified_at_ms == candidate.modified_at_ms
+                && previous.size_bytes == candidate.size_bytes
+                && previous.language == candidate.language.as_str()
+            {
+                stats.unchanged += 1;
+                continue;
+            }
+            let path = candidate.path.clone();
+            let language = candidate.language;
+            let tags = tokio::task::spawn_blocking(move || {
+                std::fs::read_to_string(&path).map(|source| extract_tags(language, &source))
+            })
+            .await?;
+            let tags = match tags {
+                Ok(tags) => tags,
+                Err(err) => {
+                    // Unreadable or non-UTF-8 files are indexed with no
+                    // symbols so they are not retried until they change.
+                    warn!(
+                        "failed to read {} for code index: {err}",
+                        candidate.path.display()
+                    );
+                    Vec::new()
+                }
+            };
+            let file = CodeIndexFile {
+                path: candidate.relative.clone(),
+                language: language.as_str().to_string(),
+                modified_at_ms: candidate.modified_at_ms,
+                size_bytes: candidate.size_bytes,
+            };
+            let symbols: Vec<CodeSymbol> = tags
+                .into_iter()
+                .map(|tag| to_symbol(&candidate.relative, tag))
+                .collect();
+            self.db
+                .replace_code_index_file(&self.root, &file, &symbols)
+                .await?;
+            stats.indexed += 1;
+        }
+
+        let removed: Vec<String> = stored.into_keys().collect();
+        stats.removed = removed.len();
+        self.db
+            .remove_code_index_files(&self.root, &removed)
+            .await?;
+        Ok(stats)
+    }
+
+    /// Definitions of `symbol`. A qualified name such as `Parser::parse` or
+    /// `Parser.parse` only matches definitions inside `Parser`.
+    pub async fn definitions(&self, symbol: &str, limit: usize) -> anyhow::Result<Vec<CodeSymbol>> {
+        let (container, name) = split_qualified(symbol);
+        self.db
+            .find_code_symbols(
+                &self.root,
+                name,
+                container,
+                CodeSymbolRole::Definition,
+                limit,
+            )
+            .await
+    }
+
+    /// Call sites and other uses of `symbol`. A qualified name only matches
+    /// uses written with that qualifier, e.g. `Parser::new()`.
+    pub async fn references(&self, symbol: &str, limit: usize) -> anyhow::Result<Vec<CodeSymbol>> {
+        let (container, name) = split_qualified(symbol);
+        self.db
+            .find_code_symbols(
+                &self.root,
+                name,
+                container,
+                CodeSymbolRole::Reference,
+                limit,
+            )
+            .await
+    }
+
+    /// Definitions in one file, in source order. `path` may be absolute or
+    /// relative to the index root.
+    pub async fn outline(&self, path: &Path) -> anyhow::Result<Vec<CodeSymbol>> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.db
+            .code_symbols_in_file(&self.root, &relative_key(relative))
+            .await
+    }
+}
+
+/// Splits `Foo::bar` / `Foo.bar` into `(Some("Foo"), "bar")`, keeping only
+/// the innermost qualifier since that is what the index stores.
+fn split_qualified(symbol: &str) -> (Option<&str>, &str) {
+    let symbol = symbol.trim();
+    let (qualifier, name) = match symbol.rsplit_once("::") {
+        Some(split) => split,
+        None => match symbol.rsplit_once('.') {
+            Some(split) => split,
+            None => return (None, symbol),
+        },
+    };
+    let container = qualifier
+        .rsplit([':', '.'])
+        .next()
+        .filter(|container| !container.is_empty());
+    (container, name)
+}
+
+fn to_symbol(path: &str, tag: Tag) -> CodeSymbol {
+    CodeSymbol {
+        path: path.to_string(),
+        name: tag.name,
+        kind: tag.kind,
+        role: match tag.role {
+            TagRole::Definition => CodeSymbolRole::Definition,
+            TagRole::Reference => CodeSymbolRole::Reference,
+        },
+        container: tag.container,
+        start_line: tag.start_line,
+        end_line: tag.end_line,
+        snippet: tag.snippet,
+    }
+}
+
+fn relative_key(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Walks `root` honoring ignore files and returns the source files worth
+/// indexing along with their fingerprints.
+fn collect_candidates(root: &Path) -> Vec<CandidateFile> {
+    let mut candidates = Vec::new();
+    for entry in WalkBuilder::new(root).build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!("skipping entry while indexing code: {err}");
+                continue;
+            }
+        };
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Some(language) = Language::from_path(entry.path()) else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.len() > MAX_INDEXED_FILE_BYTES {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let modified_at_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX))
+            .unwrap_or_default();
+        candidates.push(CandidateFile {
+            path: entry.path().to_path_buf(),
+            relative: relative_key(relative),
+            language,
+            modified_at_ms,
+            size_bytes: i64::try_from(metadata.len()).unwrap_or(i64::MAX),
+        });
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodeIndex;
+    use super::RefreshStats;
+    use super::split_qualified;
+    use codex_state::StateRuntime;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn split_qualified_names() {
+        assert_eq!(split_qualified("parse"), (None, "parse"));
+        assert_eq!(split_qualified("Parser::parse"), (Some("Parser"), "parse"));
+        assert_eq!(
+            split_qualified("crate::tags::Parser::parse"),
+            (Some("Parser"), "parse")
+        );
+        assert_eq!(split_qualified("Repo.load"), (Some("Repo"), "load"));
+    }
+
+    #[tokio::test]
+    async fn refresh_is_incremental() {
+        let codex_home = TempDir::new().expect("codex home");
+        let workspace = TempDir::new().expect("workspace");
+        let db = StateRuntime::init(codex_home.path().to_path_buf(), "test-provider".to_string())
+            .await
+            .expect("state db");
+        let root = workspace.path().to_path_buf();
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub struct Parser;\n\nimpl Parser {\n    pub fn parse(&self) {}\n}\n",
+        )
+        .expect("write lib.rs");
+        std::fs::write(root.join("main.py"), "def main():\n    parse()\n").expect("write main.py");
+        std::fs::write(root.join("notes.md"), "# not code\n").expect("write notes");
+
+        let index = CodeIndex::new(db, root.clone());
+        assert_eq!(
+            index.refresh().await.expect("first refresh"),
+            RefreshStats {
+                indexed: 2,
+                unchanged: 0,
+                removed: 0,
+            }
+        );
+
+        let definitions = index
+            .definitions("Parser::parse", 10)
+            .await
+            .expect("definitions");
+        assert_eq!(
+            definitions
+                .iter()
+                .map(|symbol| (symbol.path.as_str(), symbol.start_line))
+                .collect::<Vec<_>>(),
+            vec![("src/lib.rs", 4)]
+        );
+        let references = index.references("parse", 10).await.expect("references");
+        assert_eq!(
+            references
+                .iter()
+                .map(|symbol| (symbol.path.as_str(), symbol.start_line))
+                .collect::<Vec<_>>(),
+            vec![("main.py", 2)]
+        );
+
+        std::fs::remove_file(root.join("main.py")).expect("remove main.py");
+        assert_eq!(
+            index.refresh().await.expect("second refresh"),
+            RefreshStats {
+                indexed: 0,
+                unchanged: 1,
+                removed: 1,
+            }
+        );
+        assert_eq!(
+            index
+                .references("parse", 10)
+                .await
+                .expect("references")
+                .len(),
+            0
+        );
+
+        let outline = index
+            .outline(&root.join(Path::new("src/lib.rs")))
+            .await
+            .expect("outline");
+        assert_eq!(
+            outline
+                .iter()
+                .map(|symbol| (symbol.kind.as_str(), symbol.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("struct", "Parser"), ("method", "parse")]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use tree_sitter::Query;
+
+/// Source languages the symbol index understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Rust,
+    Python,
+    Go,
+    TypeScript,
+    /// TSX grammar; also used for plain JavaScript and JSX since it is a
+    /// superset of both for the constructs we tag.
+    Tsx,
+}
+
+impl Language {
+    /// Picks a language from the file extension, if it is one we index.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::Tsx),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::Go => "go",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+        }
+    }
+
+    pub(crate) fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        }
+    }
+
+    /// Tags query for this language. Every pattern captures the symbol as
+    /// `@name`, the tagged node as `@definition.<kind>` or
+    /// `@reference.<kind>`, and optionally a qualifier as `@container`.
+    pub(crate) fn tags_query(self) -> &'static Query {
+        static RUST: LazyLock<Query> = LazyLock::new(|| compile(Language::Rust, RUST_TAGS));
+        static PYTHON: LazyLock<Query> = LazyLock::new(|| compile(Language::Python, PYTHON_TAGS));
+        static GO: LazyLock<Query> = LazyLock::new(|| compile(Language::Go, GO_TAGS));
+        static TYPESCRIPT: LazyLock<Query> =
+            LazyLock::new(|| compile(Language::TypeScript, TYPESCRIPT_TAGS));
+        static TSX: LazyLock<Query> = LazyLock::new(|| compile(Language::Tsx, TYPESCRIPT_TAGS));
+        match self {
+            Self::Rust => &RUST,
+            Self::Python => &PYTHON,
+            Self::Go => &GO,
+            Self::TypeScript => &TYPESCRIPT,
+            Self::Tsx => &TSX,
+        }
+    }
+
+    /// Node kinds that scope the definitions nested inside them, paired with
+    /// the field holding the scope's name.
+    pub(crate) fn container_fields(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Rust => &[
+                ("impl_item", "type"),
+                ("trait_item", "name"),
+                ("mod_item", "name"),
+            ],
+            Self::Python => &[("class_definition", "name")],
+            Self::Go => &[("method_declaration", "receiver")],
+            Self::TypeScript | Self::Tsx => &[
+                ("class_declaration", "name"),
+                ("abstract_class_declaration", "name"),
+                ("interface_declaration", "name"),
+                ("internal_module", "name"),
+            ],
+        }
+    }
+}
+
+fn compile(language: Language, source: &str) -> Query {
+    #[expect(clippy::expect_used)]
+    Query::new(&language.grammar(), source).expect("valid tags query")
+}
+
+const RUST_TAGS: &str = r#"
+(function_item name: (identifier) @name) @definition.function
+(function_signature_item name: (identifier) @name) @definition.function
+(struct_item name: (type_identifier) @name) @definition.struct
+(enum_item name: (type_identifier) @name) @definition.enum
+(union_item name: (type_identifier) @name) @definition.union
+(trait_item name: (type_identifier) @name) @definition.trait
+(type_item name: (type_identifier) @name) @definition.type
+(mod_item name: (identifier) @name) @definition.module
+(macro_definition name: (identifier) @name) @definition.macro
+(const_item name: (identifier) @name) @definition.constant
+(static_item name: (identifier) @name) @definition.constant
+
+(call_expression function: (identifier) @name) @reference.call
+(call_expression
+  function: (field_expression field: (field_identifier) @name)) @reference.call
+(call_expression
+  function: (scoped_identifier path: (_) @container name: (identifier) @name)) @reference.call
+(macro_invocation macro: (identifier) @name) @reference.macro
+(impl_item trait: (type_identifier) @name) @reference.implementation
+(impl_item
+  trait: (scoped_type_identifier name: (type_identifier) @name)) @reference.implementation
+"#;
+
+const PYTHON_TAGS: &str = r#"
+(class_definition name: (identifier) @name) @definition.class
+(function_definition name: (identifier) @name) @definition.function
+
+(call function: (identifier) @name) @reference.call
+(call
+  function: (attribute object: (identifier) @container attribute: (identifier) @name)) @reference.call
+(call function: (attribute attribute: (identifier) @name)) @reference.call
+"#;
+
+const GO_TAGS: &str = r#"
+(function_declaration name: (identifier) @name) @definition.function
+(method_declaration name: (field_identifier) @name) @definition.method
+(type_spec name: (type_identifier) @name) @definition.type
+(const_spec name: (identifier) @name) @definition.constant
+
+(call_expression function: (identifier) @name) @reference.call
+(call_expression
+  function: (selector_expression
+    operand: (identifier) @container
+    field: (field_identifier) @name)) @reference.call
+(call_expression
+  function: (selector_expression field: (field_identifier) @name)) @reference.call
+"#;
+
+const TYPESCRIPT_TAGS: &str = r#"
+(function_declaration name: (identifier) @name) @definition.function
+(generator_function_declaration name: (identifier) @name) @definition.function
+(class_declaration name: (type_identifier) @name) @definition.class
+(abstract_class_declaration name: (type_identifier) @name) @definition.class
+(method_definition name: (property_identifier) @name) @definition.method
+(interface_declaration name: (type_identifier) @name) @definition.interface
+(type_alias_declaration name: (type_identifier) @name) @definition.type
+(enum_declaration name: (identifier) @name) @definition.enum
+(lexical_declaration
+  (variable_declarator
+    name: (identifier) @name
+    value: [(arrow_function) (function_expression)])) @definition.function
+
+(call_expression function: (identifier) @name) @reference.call
+(call_expression
+  function: (member_expression
+    object: (identifier) @container
+    property: (property_identifier) @name)) @reference.call
+(call_expression
+  function: (member_expression property: (property_identifier) @name)) @reference.call
+(new_expression constructor: (identifier) @name) @reference.class
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::Language;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    #[test]
+    fn detects_language_from_extension() {
+        assert_eq!(
+            Language::from_path(Path::new("src/lib.rs")),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("app/view.tsx")),
+            Some(Language::Tsx)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("app/index.js")),
+            Some(Language::Tsx)
+        );
+        assert_eq!(Language::from_path(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn tags_queries_compile() {
+        for language in [
+            Language::Rust,
+            Language::Python,
+            Language::Go,
+            Language::TypeScript,
+            Language::Tsx,
+        ] {
+            assert!(
+                language.tags_query().capture_names().contains(&"name"),
+                "{} query has no @name capture",
+                language.as_str()
+            );
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Structured code search over a tree-sitter symbol index.
+//!
+//! [`extract_tags`] turns one source file into definition and reference
+//! [`Tag`]s; [`CodeIndex`] keeps those tags for a whole workspace in the
+//! state DB and refreshes them incrementally based on file size and mtime.
+
+mod index;
+mod language;
+mod tags;
+
+pub use index::CodeIndex;
+pub use index::MAX_INDEXED_FILE_BYTES;
+pub use index::RefreshStats;
+pub use language::Language;
+pub use tags::Tag;
+pub use tags::TagRole;
+pub use tags::extract_tags;
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+use tree_sitter::Parser;
+use tree_sitter::QueryCursor;
+use tree_sitter::StreamingIterator;
+
+use crate::Language;
+
+/// Longest snippet kept per symbol, in bytes.
+const MAX_SNIPPET_BYTES: usize = 200;
+
+/// Whether a tag declares a symbol or uses it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagRole {
+    Definition,
+    Reference,
+}
+
+/// One symbol occurrence found by a language's tags query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    /// `function`, `method`, `struct`, `call`, ... taken from the capture
+    /// name in the tags query.
+    pub kind: String,
+    pub role: TagRole,
+    /// Enclosing type/trait/class/module for definitions, or the explicit
+    /// qualifier (`Foo` in `Foo::bar()`) for references.
+    pub container: Option<String>,
+    /// 1-based, inclusive.
+    pub start_line: u32,
+    /// 1-based, inclusive.
+    pub end_line: u32,
+    pub snippet: String,
+}
+
+/// Parses `source` and returns its tags in source order.
+///
+/// Returns an empty list when the grammar cannot be loaded or the file fails
+/// to parse; a broken file should never fail the whole index.
+pub fn extract_tags(language: Language, source: &str) -> Vec<Tag> {
+    let mut parser = Parser::new();
+    if parser.set_language(&language.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let bytes = source.as_bytes();
+    let lines: Vec<&str> = source.lines().collect();
+    let query = language.tags_query();
+    let capture_names = query.capture_names();
+
+    // Several patterns can match the same occurrence (e.g. `a.b()` with and
+    // without a captured qualifier); keep one tag per name node, preferring
+    // the one that carries a container.
+    let mut tags: HashMap<(usize, TagRole), Tag> = HashMap::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), bytes);
+    while let Some(m) = matches.next() {
+        let mut name_node = None;
+        let mut tagged = None;
+        let mut qualifier = None;
+        for capture in m.captures {
+            let capture_name = capture_names[capture.index as usize];
+            if capture_name == "name" {
+                name_node = Some(capture.node);
+            } else if capture_name == "container" {
+                qualifier = capture.node.utf8_text(bytes).ok();
+            } else if let Some(kind) = capture_name.strip_prefix("definition.") {
+                tagged = Some((capture.node, TagRole::Definition, kind));
+            } else if let Some(kind) = capture_name.strip_prefix("reference.") {
+                tagged = Some((capture.node, TagRole::Reference, kind));
+            }
+        }
+        let (Some(name_node), Some((node, role, kind))) = (name_node, tagged) else {
+            continue;
+        };
+        let Ok(name) = name_node.utf8_text(bytes) else {
+            continue;
+        };
+
+        let (container, kind, start_row, end_row) = match role {
+            TagRole::Definition => {
+                let scope = enclosing_scope(language, node, bytes);
+                // Functions declared inside a type are methods; functions in
+                // a module are still plain functions.
+                let kind = match &scope {
+                    Some(scope) if kind == "function" && !scope.is_module => "method",
+                    _ => kind,
+                };
+                (
+                    scope.map(|scope| scope.name),
+                    kind,
+                    node.start_position().row,
+                    node.end_position().row,
+                )
+            }
+            TagRole::Reference => (
+                qualifier.map(str::to_string),
+                kind,
+                name_node.start_position().row,
+                name_node.start_position().row,
+            ),
+        };
+        let tag = Tag {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            role,
+            container,
+            start_line: line_number(start_row),
+            end_line: line_number(end_row),
+            snippet: snippet(lines.get(start_row).copied().unwrap_or_default()),
+        };
+        let key = (name_node.start_byte(), role);
+        match tags.get(&key) {
+            Some(existing) if existing.container.is_some() || tag.container.is_none() => {}
+            _ => {
+                tags.insert(key, tag);
+            }
+        }
+    }
+
+    let mut tags: Vec<Tag> = tags.into_values().collect();
+    tags.sort_by(|a, b| {
+        (a.start_line, a.role == TagRole::Reference, &a.name).cmp(&(
+            b.start_line,
+            b.role == TagRole::Reference,
+            &b.name,
+        ))
+    });
+    tags
+}
+
+struct Scope {
+    name: String,
+    is_module: bool,
+}
+
+/// Nearest scope around `node`, such as the `impl` type or class a method is
+/// declared in.
+fn enclosing_scope(language: Language, node: Node<'_>, bytes: &[u8]) -> Option<Scope> {
+    let fields = language.container_fields();
+    let mut current = Some(node);
+    while let Some(candidate) = current {
+        if let Some((kind, field)) = fields.iter().find(|(kind, _)| *kind == candidate.kind()) {
+            // A class is not its own container, but a Go method's receiver is.
+            let names_itself = candidate.id() == node.id() && *field == "name";
+            if !names_itself
+                && let Some(name) = candidate
+                    .child_by_field_name(field)
+                    .and_then(|scope| type_name(scope, bytes))
+            {
+                return Some(Scope {
+                    name,
+                    is_module: matches!(*kind, "mod_item" | "internal_module"),
+                });
+            }
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Bare type name for a type-ish node, stripping generics, pointers, and
+/// path qualifiers so `&mut foo::Bar<T>` becomes `Bar`.
+fn type_name(node: Node<'_>, bytes: &[u8]) -> Option<String> {
+    match node.kind() {
+        "generic_type" | "reference_type" | "parameter_declaration" => {
+            type_name(node.child_by_field_name("type")?, bytes)
+        }
+        "scoped_type_identifier" => type_name(node.child_by_field_name("name")?, bytes),
+        "parameter_list" | "pointer_type" => type_name(node.named_child(0)?, bytes),
+        _ => node.utf8_text(bytes).ok().map(str::to_string),
+    }
+}
+
+fn line_number(row: usize) -> u32 {
+    u32::try_from(row.saturating_add(1)).unwrap_or(u32::MAX)
+}
+
+fn snippet(line: &str) -> String {
+    let line = line.trim();
+    if line.len() <= MAX_SNIPPET_BYTES {
+        return line.to_string();
+    }
+    let mut end = MAX_SNIPPET_BYTES;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &line[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tag;
+    use super::TagRole;
+    use super::extract_tags;
+    use crate::Language;
+    use pretty_assertions::assert_eq;
+
+    /// `(role, kind, container, name, line)` for compact assertions.
+    fn summarize(tags: &[Tag]) -> Vec<(TagRole, &str, Option<&str>, &str, u32)> {
+        tags.iter()
+            .map(|tag| {
+                (
+                    tag.role,
+                    tag.kind.as_str(),
+                    tag.container.as_deref(),
+                    tag.name.as_str(),
+                    tag.start_line,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_definitions_and_references() {
+        let source = r#"struct Parser;
+
+impl Parser {
+    fn parse(&self) -> u32 {
+        helper()
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser
+    }
+}
+
+fn helper() -> u32 {
+    let parser = Parser::new();
+    parser.parse();
+    println!("done");
+    0
+}
+"#;
+        let tags = extract_tags(Language::Rust, source);
+        assert_eq!(
+            summarize(&tags),
+            vec![
+                (TagRole::Definition, "struct", None, "Parser", 1),
+                (TagRole::Definition, "method", Some("Parser"), "parse", 4),
+                (TagRole::Reference, "call", None, "helper", 5),
+                (TagRole::Reference, "implementation", None, "Default", 9),
+                (TagRole::Definition, "method", Some("Parser"), "default", 10),
+                (TagRole::Definition, "function", None, "helper", 15),
+                (TagRole::Reference, "call", Some("Parser"), "new", 16),
+                (TagRole::Reference, "call", None, "parse", 17),
+                (TagRole::Reference, "macro", None, "println", 18),
+            ]
+        );
+        let parse = &tags[1];
+        assert_eq!(
+            (parse.end_line, parse.snippet.as_str()),
+            (6, "fn parse(&self) -> u32 {")
+        );
+    }
+
+    #[test]
+    fn python_methods_are_scoped_to_their_class() {
+        let source = r#"class Repo:
+    def load(self):
+        return os.path.join("a", "b")
+
+
+def main():
+    Repo().load()
+"#;
+        assert_eq!(
+            summarize(&extract_tags(Language::Python, source)),
+            vec![
+                (TagRole::Definition, "class", None, "Repo", 1),
+                (TagRole::Definition, "method", Some("Repo"), "load", 2),
+                (TagRole::Reference, "call", None, "join", 3),
+                (TagRole::Definition, "function", None, "main", 6),
+                (TagRole::Reference, "call", None, "Repo", 7),
+                (TagRole::Reference, "call", None, "load", 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_methods_use_receiver_as_container() {
+        let source = r#"package main
+
+type Server struct{}
+
+func (s *Server) Start() error {
+	return fmt.Errorf("x")
+}
+"#;
+        assert_eq!(
+            summarize(&extract_tags(Language::Go, source)),
+            vec![
+                (TagRole::Definition, "type", None, "Server", 3),
+                (TagRole::Definition, "method", Some("Server"), "Start", 5),
+                (TagRole::Reference, "call", Some("fmt"), "Errorf", 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_classes_functions_and_arrows() {
+        let source = r#"interface Shape {}
+
+export class Circle {
+  area(): number {
+    return compute(this);
+  }
+}
+
+const compute = (shape: Shape) => new Circle();
+"#;
+        assert_eq!(
+            summarize(&extract_tags(Language::TypeScript, source)),
+            vec![
+                (TagRole::Definition, "interface", None, "Shape", 1),
+                (TagRole::Definition, "class", None, "Circle", 3),
+                (TagRole::Definition, "method", Some("Circle"), "area", 4),
+                (TagRole::Reference, "call", None, "compute", 5),
+                (TagRole::Definition, "function", None, "compute", 9),
+                (TagRole::Reference, "class", None, "Circle", 9),
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::responses::ResponsesOptions;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::build_anthropic_messages_body;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::translation::custom_tool_names;
+use crate::sse::anthropic::spawn_anthropic_messages_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+
+pub const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+pub const ANTHROPIC_API_KEY_HEADER: &str = "x-api-key";
+const DEFAULT_ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Streams turns from providers that speak the Anthropic Messages API
+/// (`/v1/messages`).
+pub struct AnthropicMessagesClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicMessagesClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+
+    /// Translates `request` into an Anthropic Messages body and streams the
+    /// response back as Responses-style events.
+    ///
+    /// The provider's bearer token is sent as `x-api-key` unless the provider
+    /// config already sets that header, and `anthropic-version` defaults to
+    /// the stable API version.
+    pub async fn stream_request(
+        &self,
+        request: ResponsesApiRequest,
+        options: ResponsesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let ResponsesOptions {
+            conversation_id,
+            extra_headers,
+            ..
+        } = options;
+
+        let body = build_anthropic_messages_body(&request);
+        let mut headers = extra_headers;
+        headers.extend(build_conversation_headers(conversation_id));
+
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, Self::path(), headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                if !req.headers.contains_key(ANTHROPIC_VERSION_HEADER) {
+                    req.headers.insert(
+                        ANTHROPIC_VERSION_HEADER,
+                        HeaderValue::from_static(DEFAULT_ANTHROPIC_VERSION),
+                    );
+                }
+                if let Some(authorization) = req.headers.remove(http::header::AUTHORIZATION)
+                    && !req.headers.contains_key(ANTHROPIC_API_KEY_HEADER)
+                    && let Some(api_key) = authorization
+                        .to_str()
+                        .ok()
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                        .and_then(|api_key| HeaderValue::from_str(api_key).ok())
+                {
+                    req.headers.insert(ANTHROPIC_API_KEY_HEADER, api_key);
+                }
+            })
+            .await?;
+
+        Ok(spawn_anthropic_messages_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            custom_tool_names(&request.tools),
+        ))
+    }
+}
@@ -0,0 +1,416 @@
+//! Builds Anthropic Messages (`/v1/messages`) request bodies from a
+//! [`ResponsesApiRequest`].
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::translation::custom_tool_arguments;
+use crate::requests::translation::function_tools;
+use crate::requests::translation::parse_base64_data_url;
+use crate::requests::translation::reasoning_text;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// Prefix used to store Anthropic `redacted_thinking` payloads in
+/// `ResponseItem::Reasoning::encrypted_content` so they can be replayed.
+pub const ANTHROPIC_REDACTED_THINKING_PREFIX: &str = "anthropic-redacted-thinking:";
+
+/// Output token budget used when extended thinking is disabled.
+const DEFAULT_MAX_OUTPUT_TOKENS: u64 = 8_192;
+
+/// Translates a Responses API request into a streaming Anthropic Messages body.
+pub fn build_anthropic_messages_body(request: &ResponsesApiRequest) -> Value {
+    let mut messages = AnthropicMessages::default();
+    for item in &request.input {
+        messages.push_item(item);
+    }
+
+    let thinking_budget = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+        .and_then(thinking_budget_tokens);
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), json!(request.model));
+    body.insert(
+        "max_tokens".to_string(),
+        json!(DEFAULT_MAX_OUTPUT_TOKENS + thinking_budget.unwrap_or(0)),
+    );
+    if !request.instructions.is_empty() {
+        body.insert("system".to_string(), json!(request.instructions));
+    }
+    body.insert(
+        "messages".to_string(),
+        Value::Array(messages.into_messages()),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+
+    let tools = function_tools(&request.tools);
+    if !tools.is_empty() {
+        let tools = tools
+            .into_iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "input_schema": tool.parameters,
+                })
+            })
+            .collect();
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            json!({
+                "type": "auto",
+                "disable_parallel_tool_use": !request.parallel_tool_calls,
+            }),
+        );
+    }
+
+    if let Some(budget_tokens) = thinking_budget {
+        body.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+        );
+    }
+
+    Value::Object(body)
+}
+
+/// Maps a reasoning effort onto an extended-thinking token budget.
+fn thinking_budget_tokens(effort: ReasoningEffort) -> Option<u64> {
+    match effort {
+        ReasoningEffort::None => None,
+        ReasoningEffort::Minimal => Some(1_024),
+        ReasoningEffort::Low => Some(4_096),
+        ReasoningEffort::Medium => Some(10_240),
+        ReasoningEffort::High => Some(20_480),
+        ReasoningEffort::XHigh => Some(32_768),
+    }
+}
+
+/// Accumulates Anthropic messages, merging consecutive content blocks that
+/// share a role so that `tool_use` and `tool_result` blocks stay paired.
+#[derive(Default)]
+struct AnthropicMessages {
+    messages: Vec<(&'static str, Vec<Value>)>,
+}
+
+impl AnthropicMessages {
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                let blocks = content.iter().filter_map(content_block).collect();
+                self.push_blocks(role, blocks);
+            }
+            ResponseItem::Reasoning {
+                summary,
+                content,
+                encrypted_content: Some(encrypted_content),
+                ..
+            } => {
+                let block = match encrypted_content.strip_prefix(ANTHROPIC_REDACTED_THINKING_PREFIX)
+                {
+                    Some(data) => json!({ "type": "redacted_thinking", "data": data }),
+                    None => json!({
+                        "type": "thinking",
+                        "thinking": reasoning_text(summary, content.as_deref()).unwrap_or_default(),
+                        "signature": encrypted_content,
+                    }),
+                };
+                self.push_blocks("assistant", vec![block]);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => self.push_tool_use(call_id, name, arguments),
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => self.push_tool_use(call_id, name, &custom_tool_arguments(input)),
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            } => {
+                let arguments = serde_json::to_string(action).unwrap_or_default();
+                self.push_tool_use(call_id, "local_shell", &arguments);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_blocks("user", vec![tool_result_block(call_id, output)]);
+            }
+            // Thinking blocks without a signature are rejected by the API, so
+            // unsigned reasoning (for example from another provider) is dropped.
+            ResponseItem::Reasoning {
+                encrypted_content: None,
+                ..
+            }
+            | ResponseItem::LocalShellCall { call_id: None, .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    fn push_tool_use(&mut self, call_id: &str, name: &str, arguments: &str) {
+        let input = serde_json::from_str::<Value>(arguments)
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({}));
+        self.push_blocks(
+            "assistant",
+            vec![json!({
+                "type": "tool_use",
+                "id": call_id,
+                "name": name,
+                "input": input,
+            })],
+        );
+    }
+
+    fn push_blocks(&mut self, role: &'static str, blocks: Vec<Value>) {
+        if blocks.is_empty() {
+            return;
+        }
+        match self.messages.last_mut() {
+            Some((last_role, last_blocks)) if *last_role == role => last_blocks.extend(blocks),
+            _ => self.messages.push((role, blocks)),
+        }
+    }
+
+    fn into_messages(self) -> Vec<Value> {
+        self.messages
+            .into_iter()
+            .map(|(role, content)| json!({ "role": role, "content": content }))
+            .collect()
+    }
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            // The Messages API rejects empty text blocks.
+            (!text.is_empty()).then(|| json!({ "type": "text", "text": text }))
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    match parse_base64_data_url(image_url) {
+        Some((media_type, data)) => json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        }),
+        None => json!({
+            "type": "image",
+            "source": { "type": "url", "url": image_url },
+        }),
+    }
+}
+
+fn tool_result_block(call_id: &str, output: &FunctionCallOutputPayload) -> Value {
+    let content = match &output.body {
+        FunctionCallOutputBody::Text(text) => json!(text),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({ "type": "text", "text": text })
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                        image_block(image_url)
+                    }
+                })
+                .collect(),
+        ),
+    };
+    let mut block = json!({
+        "type": "tool_result",
+        "tool_use_id": call_id,
+        "content": content,
+    });
+    if output.success == Some(false)
+        && let Value::Object(block) = &mut block
+    {
+        block.insert("is_error".to_string(), Value::Bool(true));
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::ReasoningItemContent;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "claude-sonnet".to_string(),
+            instructions: "be helpful".to_string(),
+            input,
+            tools: vec![json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": { "type": "object", "properties": {} },
+            })],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: false,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            service_tier: None,
+            prompt_cache_key: None,
+            text: None,
+        }
+    }
+
+    #[test]
+    fn translates_history_into_paired_tool_blocks() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox rules".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "thinking".to_string(),
+                }]),
+                encrypted_content: Some("sig".to_string()),
+            },
+            ResponseItem::Reasoning {
+                id: "rs_2".to_string(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(format!("{ANTHROPIC_REDACTED_THINKING_PREFIX}opaque")),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload::from_text("file.txt".to_string()),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "thanks".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+
+        let body = build_anthropic_messages_body(&request(input));
+
+        assert_eq!(body["system"], json!("be helpful"));
+        assert_eq!(
+            body["messages"],
+            json!([
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": "sandbox rules" },
+                        {
+                            "type": "image",
+                            "source": { "type": "base64", "media_type": "image/png", "data": "AAAA" },
+                        },
+                    ],
+                },
+                {
+                    "role": "assistant",
+                    "content": [
+                        { "type": "thinking", "thinking": "thinking", "signature": "sig" },
+                        { "type": "redacted_thinking", "data": "opaque" },
+                        { "type": "tool_use", "id": "toolu_1", "name": "shell", "input": { "cmd": "ls" } },
+                    ],
+                },
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "tool_result", "tool_use_id": "toolu_1", "content": "file.txt" },
+                        { "type": "text", "text": "thanks" },
+                    ],
+                },
+            ])
+        );
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "name": "shell",
+                "description": "Runs a command",
+                "input_schema": { "type": "object", "properties": {} },
+            }])
+        );
+        assert_eq!(
+            body["tool_choice"],
+            json!({ "type": "auto", "disable_parallel_tool_use": true })
+        );
+        assert_eq!(body["max_tokens"], json!(DEFAULT_MAX_OUTPUT_TOKENS));
+        assert!(body.get("thinking").is_none());
+    }
+
+    #[test]
+    fn enables_thinking_from_reasoning_effort() {
+        let mut request = request(Vec::new());
+        request.reasoning = Some(Reasoning {
+            effort: Some(ReasoningEffort::Medium),
+            summary: None,
+        });
+
+        let body = build_anthropic_messages_body(&request);
+
+        assert_eq!(
+            body["thinking"],
+            json!({ "type": "enabled", "budget_tokens": 10_240 })
+        );
+        assert_eq!(
+            body["max_tokens"],
+            json!(DEFAULT_MAX_OUTPUT_TOKENS + 10_240)
+        );
+    }
+}
@@ -0,0 +1,473 @@
+//! Builds Chat Completions (`/v1/chat/completions`) request bodies from a
+//! [`ResponsesApiRequest`].
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::translation::custom_tool_arguments;
+use crate::requests::translation::function_tools;
+use crate::requests::translation::reasoning_text;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// Translates a Responses API request into a streaming Chat Completions body.
+pub fn build_chat_completions_body(request: &ResponsesApiRequest) -> Value {
+    let mut messages = ChatMessages::default();
+    if !request.instructions.is_empty() {
+        messages.push(json!({
+            "role": "system",
+            "content": request.instructions,
+        }));
+    }
+    for item in &request.input {
+        messages.push_item(item);
+    }
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), json!(request.model));
+    body.insert(
+        "messages".to_string(),
+        Value::Array(messages.into_messages()),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+    body.insert(
+        "stream_options".to_string(),
+        json!({ "include_usage": true }),
+    );
+
+    let tools = function_tools(&request.tools);
+    if !tools.is_empty() {
+        let tools = tools
+            .into_iter()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.parameters,
+                    },
+                })
+            })
+            .collect();
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert("tool_choice".to_string(), json!(request.tool_choice));
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            Value::Bool(request.parallel_tool_calls),
+        );
+    }
+
+    if let Some(effort) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+    {
+        body.insert("reasoning_effort".to_string(), json!(effort));
+    }
+
+    if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+        body.insert(
+            "response_format".to_string(),
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": format.name,
+                    "schema": format.schema,
+                    "strict": format.strict,
+                },
+            }),
+        );
+    }
+
+    Value::Object(body)
+}
+
+/// Accumulates chat messages while folding tool calls into the preceding
+/// assistant message and deferring tool-output images until the run of `tool`
+/// messages ends.
+#[derive(Default)]
+struct ChatMessages {
+    messages: Vec<Value>,
+    pending_reasoning: Option<String>,
+    pending_images: Vec<Value>,
+}
+
+impl ChatMessages {
+    fn push(&mut self, message: Value) {
+        self.flush_pending_images();
+        self.messages.push(message);
+    }
+
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => self.push_message(role, content),
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                if let Some(text) = reasoning_text(summary, content.as_deref()) {
+                    self.pending_reasoning = Some(text);
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => self.push_tool_call(call_id, name, arguments.clone()),
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => self.push_tool_call(call_id, name, custom_tool_arguments(input)),
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(action),
+                ..
+            } => {
+                let arguments = serde_json::to_string(action).unwrap_or_default();
+                self.push_tool_call(call_id, "local_shell", arguments);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_tool_output(call_id, output);
+            }
+            ResponseItem::LocalShellCall { call_id: None, .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    fn push_message(&mut self, role: &str, content: &[ContentItem]) {
+        match role {
+            "assistant" => {
+                let text = content
+                    .iter()
+                    .filter_map(|item| match item {
+                        ContentItem::OutputText { text } | ContentItem::InputText { text } => {
+                            Some(text.as_str())
+                        }
+                        ContentItem::InputImage { .. } => None,
+                    })
+                    .collect::<String>();
+                let mut message = json!({ "role": "assistant", "content": text });
+                self.attach_pending_reasoning(&mut message);
+                self.push(message);
+            }
+            role => {
+                let role = if role == "developer" { "system" } else { role };
+                self.push(json!({
+                    "role": role,
+                    "content": chat_content(content),
+                }));
+            }
+        }
+    }
+
+    fn push_tool_call(&mut self, call_id: &str, name: &str, arguments: String) {
+        let tool_call = json!({
+            "id": call_id,
+            "type": "function",
+            "function": {
+                "name": name,
+                "arguments": arguments,
+            },
+        });
+
+        let reuse_last = self.pending_images.is_empty()
+            && self
+                .messages
+                .last()
+                .is_some_and(|message| message["role"] == "assistant");
+        if !reuse_last {
+            let mut message = json!({ "role": "assistant", "content": Value::Null });
+            self.attach_pending_reasoning(&mut message);
+            self.push(message);
+        }
+        if let Some(Value::Object(message)) = self.messages.last_mut() {
+            let tool_calls = message
+                .entry("tool_calls")
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(tool_calls) = tool_calls {
+                tool_calls.push(tool_call);
+            }
+        }
+    }
+
+    fn push_tool_output(&mut self, call_id: &str, output: &FunctionCallOutputPayload) {
+        let text = match &output.body {
+            FunctionCallOutputBody::Text(text) => text.clone(),
+            FunctionCallOutputBody::ContentItems(items) => {
+                let mut texts = Vec::new();
+                for item in items {
+                    match item {
+                        FunctionCallOutputContentItem::InputText { text } => {
+                            texts.push(text.as_str());
+                        }
+                        FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                            self.pending_images.push(image_part(image_url));
+                        }
+                    }
+                }
+                texts.join("\n")
+            }
+        };
+        self.messages.push(json!({
+            "role": "tool",
+            "tool_call_id": call_id,
+            "content": text,
+        }));
+    }
+
+    fn attach_pending_reasoning(&mut self, message: &mut Value) {
+        if let (Some(reasoning), Value::Object(message)) = (self.pending_reasoning.take(), message)
+        {
+            message.insert("reasoning_content".to_string(), Value::String(reasoning));
+        }
+    }
+
+    fn flush_pending_images(&mut self) {
+        if self.pending_images.is_empty() {
+            return;
+        }
+        let images = std::mem::take(&mut self.pending_images);
+        self.messages.push(json!({
+            "role": "user",
+            "content": images,
+        }));
+    }
+
+    fn into_messages(mut self) -> Vec<Value> {
+        self.flush_pending_images();
+        self.messages
+    }
+}
+
+/// Uses plain string content when a message is text-only, which is the most
+/// widely supported shape across OpenAI-compatible servers.
+fn chat_content(content: &[ContentItem]) -> Value {
+    let has_image = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if !has_image {
+        return Value::String(
+            content
+                .iter()
+                .filter_map(|item| match item {
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        Some(text.as_str())
+                    }
+                    ContentItem::InputImage { .. } => None,
+                })
+                .collect(),
+        );
+    }
+
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url } => image_part(image_url),
+            })
+            .collect(),
+    )
+}
+
+fn image_part(image_url: &str) -> Value {
+    json!({
+        "type": "image_url",
+        "image_url": { "url": image_url },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ReasoningItemContent;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "local-model".to_string(),
+            instructions: "be helpful".to_string(),
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            service_tier: None,
+            prompt_cache_key: None,
+            text: None,
+        }
+    }
+
+    #[test]
+    fn translates_history_with_tool_calls_reasoning_and_images() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox rules".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "look".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                ],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "thinking".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "patch".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload::from_content_items(vec![
+                    FunctionCallOutputContentItem::InputText {
+                        text: "file.txt".to_string(),
+                    },
+                    FunctionCallOutputContentItem::InputImage {
+                        image_url: "data:image/png;base64,BBBB".to_string(),
+                        detail: None,
+                    },
+                ]),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call_2".to_string(),
+                output: FunctionCallOutputPayload::from_text("done".to_string()),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "all set".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+
+        let body = build_chat_completions_body(&request(input, Vec::new()));
+
+        assert_eq!(
+            body["messages"],
+            json!([
+                { "role": "system", "content": "be helpful" },
+                { "role": "system", "content": "sandbox rules" },
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": "look" },
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,AAAA" } },
+                    ],
+                },
+                {
+                    "role": "assistant",
+                    "content": null,
+                    "reasoning_content": "thinking",
+                    "tool_calls": [
+                        {
+                            "id": "call_1",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": "{\"cmd\":\"ls\"}" },
+                        },
+                        {
+                            "id": "call_2",
+                            "type": "function",
+                            "function": { "name": "apply_patch", "arguments": "{\"input\":\"patch\"}" },
+                        },
+                    ],
+                },
+                { "role": "tool", "tool_call_id": "call_1", "content": "file.txt" },
+                { "role": "tool", "tool_call_id": "call_2", "content": "done" },
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,BBBB" } },
+                    ],
+                },
+                { "role": "assistant", "content": "all set" },
+            ])
+        );
+        assert!(body.get("tools").is_none());
+        assert_eq!(body["stream_options"], json!({ "include_usage": true }));
+    }
+
+    #[test]
+    fn includes_function_tools_and_reasoning_effort() {
+        let mut request = request(
+            Vec::new(),
+            vec![json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": { "type": "object", "properties": {} },
+            })],
+        );
+        request.reasoning = Some(crate::common::Reasoning {
+            effort: Some(codex_protocol::openai_models::ReasoningEffort::High),
+            summary: None,
+        });
+
+        let body = build_chat_completions_body(&request);
+
+        assert_eq!(
+            body["tools"],
+            json!([{
+                "type": "function",
+                "function": {
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "parameters": { "type": "object", "properties": {} },
+                },
+            }])
+        );
+        assert_eq!(body["tool_choice"], json!("auto"));
+        assert_eq!(body["parallel_tool_calls"], json!(true));
+        assert_eq!(body["reasoning_effort"], json!("high"));
+    }
+}
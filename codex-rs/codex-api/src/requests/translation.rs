@@ -0,0 +1,217 @@
+//! Shared helpers for translating Responses API requests into the shapes
+//! understood by Chat Completions and Anthropic Messages providers.
+//!
+//! Freeform (`custom`) tools have no equivalent outside the Responses API, so
+//! they are exposed as function tools that take a single string argument. The
+//! stream parsers use [`custom_tool_names`] to turn calls to those tools back
+//! into `ResponseItem::CustomToolCall`.
+
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Name of the single string argument used to carry freeform tool input.
+pub(crate) const CUSTOM_TOOL_INPUT_FIELD: &str = "input";
+
+/// A tool definition reduced to name, description and JSON schema.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FunctionTool {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) parameters: Value,
+}
+
+/// Converts Responses API tool JSON into function tools.
+///
+/// Hosted tools (`local_shell`, `web_search`, `image_generation`) are dropped
+/// because non-Responses providers cannot execute them.
+pub(crate) fn function_tools(tools: &[Value]) -> Vec<FunctionTool> {
+    tools.iter().filter_map(function_tool).collect()
+}
+
+/// Returns the names of freeform tools in a Responses API tool list.
+pub(crate) fn custom_tool_names(tools: &[Value]) -> HashSet<String> {
+    tools
+        .iter()
+        .filter(|tool| tool_type(tool) == Some("custom"))
+        .filter_map(tool_name)
+        .collect()
+}
+
+/// Wraps freeform tool input in the JSON arguments object used on the wire.
+pub(crate) fn custom_tool_arguments(input: &str) -> String {
+    json!({ CUSTOM_TOOL_INPUT_FIELD: input }).to_string()
+}
+
+/// Extracts freeform tool input from JSON arguments produced by the model.
+///
+/// Falls back to the raw arguments when the model did not produce the expected
+/// object so that the tool handler can report a useful parse error.
+pub(crate) fn custom_tool_input(arguments: &str) -> String {
+    serde_json::from_str::<Value>(arguments)
+        .ok()
+        .and_then(|value| {
+            value
+                .get(CUSTOM_TOOL_INPUT_FIELD)
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+        })
+        .unwrap_or_else(|| arguments.to_string())
+}
+
+/// Flattens a reasoning item into plain text, preferring raw reasoning content
+/// over summaries.
+pub(crate) fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> Option<String> {
+    let text = match content {
+        Some(content) if !content.is_empty() => content
+            .iter()
+            .map(|entry| match entry {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => text.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join(""),
+        _ => summary
+            .iter()
+            .map(|entry| match entry {
+                ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Splits a `data:<media type>;base64,<data>` URL into media type and payload.
+pub(crate) fn parse_base64_data_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("data:")?;
+    let (meta, data) = rest.split_once(',')?;
+    let media_type = meta.strip_suffix(";base64")?;
+    Some((media_type, data))
+}
+
+fn function_tool(tool: &Value) -> Option<FunctionTool> {
+    let name = tool_name(tool)?;
+    let description = tool
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    match tool_type(tool)? {
+        "function" => Some(FunctionTool {
+            name,
+            description,
+            parameters: tool
+                .get("parameters")
+                .cloned()
+                .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+        }),
+        "custom" => Some(FunctionTool {
+            name,
+            description: custom_tool_description(description, tool.get("format")),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    CUSTOM_TOOL_INPUT_FIELD: {
+                        "type": "string",
+                        "description": "Raw tool input.",
+                    },
+                },
+                "required": [CUSTOM_TOOL_INPUT_FIELD],
+                "additionalProperties": false,
+            }),
+        }),
+        _ => None,
+    }
+}
+
+fn custom_tool_description(description: String, format: Option<&Value>) -> String {
+    let Some(format) = format else {
+        return description;
+    };
+    let syntax = format.get("syntax").and_then(Value::as_str);
+    let definition = format.get("definition").and_then(Value::as_str);
+    match (syntax, definition) {
+        (Some(syntax), Some(definition)) => format!(
+            "{description}\n\nThe `{CUSTOM_TOOL_INPUT_FIELD}` argument must match this {syntax} grammar:\n{definition}"
+        ),
+        _ => description,
+    }
+}
+
+fn tool_type(tool: &Value) -> Option<&str> {
+    tool.get("type").and_then(Value::as_str)
+}
+
+fn tool_name(tool: &Value) -> Option<String> {
+    tool.get("name")
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn converts_function_and_custom_tools_and_drops_hosted_tools() {
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": { "type": "object", "properties": { "cmd": { "type": "string" } } },
+            }),
+            json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Applies a patch",
+                "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+            }),
+            json!({ "type": "web_search" }),
+            json!({ "type": "local_shell" }),
+        ];
+
+        let converted = function_tools(&tools);
+
+        assert_eq!(
+            converted
+                .iter()
+                .map(|tool| tool.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["shell", "apply_patch"]
+        );
+        assert_eq!(
+            converted[0].parameters,
+            json!({ "type": "object", "properties": { "cmd": { "type": "string" } } })
+        );
+        assert!(converted[1].description.contains("lark grammar"));
+        assert_eq!(
+            custom_tool_names(&tools),
+            HashSet::from(["apply_patch".to_string()])
+        );
+    }
+
+    #[test]
+    fn parses_base64_data_urls() {
+        assert_eq!(
+            parse_base64_data_url("data:image/png;base64,AAAA"),
+            Some(("image/png", "AAAA"))
+        );
+        assert_eq!(parse_base64_data_url("https://example.com/cat.png"), None);
+    }
+
+    #[test]
+    fn custom_tool_input_round_trips_through_arguments() {
+        let input = "*** Begin Patch\n*** End Patch";
+        assert_eq!(custom_tool_input(&custom_tool_arguments(input)), input);
+        assert_eq!(custom_tool_input("not json"), "not json");
+    }
+}
@@ -0,0 +1,537 @@
+//! Parses Anthropic Messages SSE streams into Responses-style
+//! [`ResponseEvent`]s.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::requests::anthropic::ANTHROPIC_REDACTED_THINKING_PREFIX;
+use crate::sse::output_items::OutputItemAssembler;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub fn spawn_anthropic_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tool_names: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_anthropic_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        custom_tool_names,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart {
+        message: AnthropicMessage,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: AnthropicContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: AnthropicDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        delta: AnthropicMessageDelta,
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    MessageStop,
+    Error {
+        error: AnthropicError,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessage {
+    id: String,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        #[serde(default)]
+        input: Value,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessageDelta {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl From<AnthropicError> for ApiError {
+    fn from(error: AnthropicError) -> Self {
+        let message = error.message.unwrap_or_default();
+        match error.kind.as_deref() {
+            Some("overloaded_error") => ApiError::ServerOverloaded,
+            Some("rate_limit_error") => ApiError::Retryable {
+                message,
+                delay: None,
+            },
+            Some("invalid_request_error") if message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            Some("invalid_request_error") => ApiError::InvalidRequest { message },
+            _ => ApiError::Stream(message),
+        }
+    }
+}
+
+struct PendingToolUse {
+    id: String,
+    name: String,
+    input: Value,
+    partial_json: String,
+}
+
+/// Tracks a single Anthropic message while events arrive.
+struct AnthropicStreamState {
+    items: OutputItemAssembler,
+    tool_uses: HashMap<usize, PendingToolUse>,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    stop_reason: Option<String>,
+}
+
+impl AnthropicStreamState {
+    fn new(custom_tool_names: HashSet<String>) -> Self {
+        Self {
+            items: OutputItemAssembler::new(custom_tool_names),
+            tool_uses: HashMap::new(),
+            input_tokens: 0,
+            cached_input_tokens: 0,
+            output_tokens: 0,
+            stop_reason: None,
+        }
+    }
+
+    fn record_usage(&mut self, usage: AnthropicUsage) {
+        if let Some(input_tokens) = usage.input_tokens {
+            // Anthropic reports cache reads and writes separately from
+            // `input_tokens`; Codex counts cached tokens as part of the input.
+            self.cached_input_tokens = usage.cache_read_input_tokens.unwrap_or(0);
+            self.input_tokens = input_tokens
+                + self.cached_input_tokens
+                + usage.cache_creation_input_tokens.unwrap_or(0);
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: self.input_tokens + self.output_tokens,
+        }
+    }
+
+    /// Returns the events produced by `event` and whether the message is done.
+    fn handle_event(
+        &mut self,
+        event: AnthropicStreamEvent,
+    ) -> Result<(Vec<ResponseEvent>, bool), ApiError> {
+        let events = match event {
+            AnthropicStreamEvent::MessageStart { message } => {
+                self.items.set_response_id(message.id);
+                if let Some(usage) = message.usage {
+                    self.record_usage(usage);
+                }
+                vec![ResponseEvent::Created]
+            }
+            AnthropicStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => match content_block {
+                AnthropicContentBlock::Text { text } if !text.is_empty() => {
+                    self.items.push_text_delta(text)
+                }
+                AnthropicContentBlock::Thinking { thinking } if !thinking.is_empty() => {
+                    self.items.push_reasoning_delta(thinking)
+                }
+                AnthropicContentBlock::RedactedThinking { data } => self
+                    .items
+                    .push_opaque_reasoning(format!("{ANTHROPIC_REDACTED_THINKING_PREFIX}{data}")),
+                AnthropicContentBlock::ToolUse { id, name, input } => {
+                    self.tool_uses.insert(
+                        index,
+                        PendingToolUse {
+                            id,
+                            name,
+                            input,
+                            partial_json: String::new(),
+                        },
+                    );
+                    Vec::new()
+                }
+                AnthropicContentBlock::Text { .. }
+                | AnthropicContentBlock::Thinking { .. }
+                | AnthropicContentBlock::Unknown => Vec::new(),
+            },
+            AnthropicStreamEvent::ContentBlockDelta { index, delta } => match delta {
+                AnthropicDelta::TextDelta { text } => self.items.push_text_delta(text),
+                AnthropicDelta::ThinkingDelta { thinking } => {
+                    self.items.push_reasoning_delta(thinking)
+                }
+                AnthropicDelta::SignatureDelta { signature } => {
+                    self.items.push_reasoning_signature(signature)
+                }
+                AnthropicDelta::InputJsonDelta { partial_json } => {
+                    if let Some(tool_use) = self.tool_uses.get_mut(&index) {
+                        tool_use.partial_json.push_str(&partial_json);
+                    }
+                    Vec::new()
+                }
+                AnthropicDelta::Unknown => Vec::new(),
+            },
+            AnthropicStreamEvent::ContentBlockStop { index } => {
+                match self.tool_uses.remove(&index) {
+                    Some(tool_use) => {
+                        let arguments = if tool_use.partial_json.trim().is_empty() {
+                            match tool_use.input {
+                                Value::Object(_) => tool_use.input.to_string(),
+                                _ => "{}".to_string(),
+                            }
+                        } else {
+                            tool_use.partial_json
+                        };
+                        self.items
+                            .push_tool_call(tool_use.id, tool_use.name, arguments)
+                    }
+                    None => self.items.close_open_item().into_iter().collect(),
+                }
+            }
+            AnthropicStreamEvent::MessageDelta { delta, usage } => {
+                if let Some(usage) = usage {
+                    self.record_usage(usage);
+                }
+                if delta.stop_reason.is_some() {
+                    self.stop_reason = delta.stop_reason;
+                }
+                Vec::new()
+            }
+            AnthropicStreamEvent::MessageStop => {
+                if self.stop_reason.as_deref() == Some("max_tokens") {
+                    return Err(ApiError::Stream(
+                        "Incomplete response returned, reason: max_output_tokens".to_string(),
+                    ));
+                }
+                let mut events: Vec<ResponseEvent> =
+                    self.items.close_open_item().into_iter().collect();
+                events.push(ResponseEvent::Completed {
+                    response_id: self.items.response_id().to_string(),
+                    token_usage: Some(self.token_usage()),
+                });
+                return Ok((events, true));
+            }
+            AnthropicStreamEvent::Error { error } => return Err(error.into()),
+            AnthropicStreamEvent::Unknown => Vec::new(),
+        };
+        Ok((events, false))
+    }
+}
+
+pub async fn process_anthropic_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tool_names: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = AnthropicStreamState::new(custom_tool_names);
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: AnthropicStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_event(event) {
+            Ok((events, done)) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+                if done {
+                    return;
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use bytes::Bytes;
+    use codex_client::TransportError;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::ReasoningItemContent;
+    use codex_protocol::models::ResponseItem;
+    use futures::stream;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    async fn collect_events(
+        events: Vec<Value>,
+        custom_tool_names: HashSet<String>,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event["type"].as_str().unwrap_or_default().to_string();
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+        let stream = stream::iter(vec![Ok::<Bytes, TransportError>(Bytes::from(body))]);
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        tokio::spawn(process_anthropic_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_secs(1),
+            None,
+            custom_tool_names,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn assembles_thinking_text_tool_use_and_usage() {
+        let events = collect_events(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 10, "cache_read_input_tokens": 4, "output_tokens": 1}}}),
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "hmm"}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+                json!({"type": "ping"}),
+                json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Hi"}}),
+                json!({"type": "content_block_stop", "index": 1}),
+                json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "apply_patch", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"input\":"}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "\"patch\"}"}}),
+                json!({"type": "content_block_stop", "index": 2}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 20}}),
+                json!({"type": "message_stop"}),
+            ],
+            HashSet::from(["apply_patch".to_string()]),
+        )
+        .await;
+        let events: Vec<ResponseEvent> = events.into_iter().map(Result::unwrap).collect();
+
+        let done_items: Vec<&ResponseItem> = events
+            .iter()
+            .filter_map(|event| match event {
+                ResponseEvent::OutputItemDone(item) => Some(item),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            done_items,
+            vec![
+                &ResponseItem::Reasoning {
+                    id: "rs_msg_1_0".to_string(),
+                    summary: Vec::new(),
+                    content: Some(vec![ReasoningItemContent::ReasoningText {
+                        text: "hmm".to_string(),
+                    }]),
+                    encrypted_content: Some("sig".to_string()),
+                },
+                &ResponseItem::Message {
+                    id: Some("msg_msg_1_1".to_string()),
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "Hi".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+                &ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "toolu_1".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "patch".to_string(),
+                },
+            ]
+        );
+        assert_matches!(
+            events.last(),
+            Some(ResponseEvent::Completed {
+                response_id,
+                token_usage: Some(TokenUsage {
+                    input_tokens: 14,
+                    cached_input_tokens: 4,
+                    output_tokens: 20,
+                    reasoning_output_tokens: 0,
+                    total_tokens: 34,
+                }),
+            }) if response_id == "msg_1"
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_overloaded_errors() {
+        let events = collect_events(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_1"}}),
+                json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+            ],
+            HashSet::new(),
+        )
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::ServerOverloaded)));
+    }
+
+    #[tokio::test]
+    async fn reports_max_tokens_as_incomplete() {
+        let events = collect_events(
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_1"}}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "max_tokens"}}),
+                json!({"type": "message_stop"}),
+            ],
+            HashSet::new(),
+        )
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(message))) if message.contains("max_output_tokens"));
+    }
+}
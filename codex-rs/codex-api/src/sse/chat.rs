@@ -0,0 +1,534 @@
+//! Parses Chat Completions SSE streams into Responses-style [`ResponseEvent`]s.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::sse::output_items::OutputItemAssembler;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+
+pub fn spawn_chat_completions_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tool_names: HashSet<String>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_chat_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        custom_tool_names,
+    ));
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+    #[serde(default)]
+    error: Option<ChatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: Option<ChatDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// Emitted by vLLM, llama.cpp and DeepSeek-compatible servers.
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    /// Emitted by Ollama and OpenRouter.
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(val: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .map(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .map(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatError {
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl From<ChatError> for ApiError {
+    fn from(error: ChatError) -> Self {
+        let code = error.code.as_ref().and_then(|code| code.as_str());
+        match code {
+            Some("context_length_exceeded") => ApiError::ContextWindowExceeded,
+            Some("insufficient_quota") => ApiError::QuotaExceeded,
+            _ => ApiError::Stream(
+                error
+                    .message
+                    .unwrap_or_else(|| "chat completions stream error".to_string()),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Tracks a single chat completion while chunks arrive.
+struct ChatStreamState {
+    items: OutputItemAssembler,
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    finish_reason: Option<String>,
+    token_usage: Option<TokenUsage>,
+    created: bool,
+}
+
+impl ChatStreamState {
+    fn new(custom_tool_names: HashSet<String>) -> Self {
+        Self {
+            items: OutputItemAssembler::new(custom_tool_names),
+            tool_calls: BTreeMap::new(),
+            finish_reason: None,
+            token_usage: None,
+            created: false,
+        }
+    }
+
+    fn handle_chunk(&mut self, chunk: ChatCompletionChunk) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(error) = chunk.error {
+            return Err(error.into());
+        }
+
+        let mut events = Vec::new();
+        if let Some(id) = chunk.id {
+            self.items.set_response_id(id);
+        }
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if let Some(usage) = chunk.usage {
+            self.token_usage = Some(usage.into());
+        }
+
+        // Codex never requests `n > 1`, so only the first choice is relevant.
+        let Some(choice) = chunk.choices.into_iter().next() else {
+            return Ok(events);
+        };
+        let delta = choice.delta.unwrap_or_default();
+        if let Some(reasoning) = delta.reasoning_content.or(delta.reasoning)
+            && !reasoning.is_empty()
+        {
+            events.extend(self.items.push_reasoning_delta(reasoning));
+        }
+        if let Some(content) = delta.content
+            && !content.is_empty()
+        {
+            events.extend(self.items.push_text_delta(content));
+        }
+        for tool_call in delta.tool_calls {
+            let index = tool_call.index.unwrap_or(self.tool_calls.len());
+            let pending = self.tool_calls.entry(index).or_default();
+            if let Some(id) = tool_call.id
+                && !id.is_empty()
+            {
+                pending.id = Some(id);
+            }
+            if let Some(function) = tool_call.function {
+                if let Some(name) = function.name {
+                    pending.name.push_str(&name);
+                }
+                if let Some(arguments) = function.arguments {
+                    pending.arguments.push_str(&arguments);
+                }
+            }
+        }
+        if let Some(finish_reason) = choice.finish_reason {
+            self.finish_reason = Some(finish_reason);
+        }
+        Ok(events)
+    }
+
+    fn finish(mut self) -> Result<Vec<ResponseEvent>, ApiError> {
+        if self.finish_reason.as_deref() == Some("length") {
+            return Err(ApiError::Stream(
+                "Incomplete response returned, reason: max_output_tokens".to_string(),
+            ));
+        }
+
+        let mut events: Vec<ResponseEvent> = self.items.close_open_item().into_iter().collect();
+        for (_, tool_call) in std::mem::take(&mut self.tool_calls) {
+            let call_id = match tool_call.id {
+                Some(id) => id,
+                None => self.items.next_call_id(),
+            };
+            let arguments = if tool_call.arguments.trim().is_empty() {
+                "{}".to_string()
+            } else {
+                tool_call.arguments
+            };
+            events.extend(
+                self.items
+                    .push_tool_call(call_id, tool_call.name, arguments),
+            );
+        }
+        events.push(ResponseEvent::Completed {
+            response_id: self.items.response_id().to_string(),
+            token_usage: self.token_usage,
+        });
+        Ok(events)
+    }
+}
+
+pub async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tool_names: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState::new(custom_tool_names);
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some OpenAI-compatible servers close the stream after the
+                // final chunk without sending `[DONE]`.
+                if state.finish_reason.is_some() {
+                    send_final_events(&tx_event, state.finish()).await;
+                } else {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before chat completion finished".into(),
+                        )))
+                        .await;
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            send_final_events(&tx_event, state.finish()).await;
+            return;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_chunk(chunk) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+async fn send_final_events(
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    events: Result<Vec<ResponseEvent>, ApiError>,
+) {
+    match events {
+        Ok(events) => {
+            for event in events {
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+        }
+        Err(error) => {
+            let _ = tx_event.send(Err(error)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use bytes::Bytes;
+    use codex_client::TransportError;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::ReasoningItemContent;
+    use codex_protocol::models::ResponseItem;
+    use futures::stream;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+
+    async fn collect_events(
+        chunks: Vec<Value>,
+        done: bool,
+        custom_tool_names: HashSet<String>,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        if done {
+            body.push_str("data: [DONE]\n\n");
+        }
+        let stream = stream::iter(vec![Ok::<Bytes, TransportError>(Bytes::from(body))]);
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_secs(1),
+            None,
+            custom_tool_names,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn assembles_reasoning_text_and_usage() {
+        let events = collect_events(
+            vec![
+                json!({"id": "chatcmpl-1", "choices": [{"delta": {"role": "assistant", "reasoning_content": "think"}}]}),
+                json!({"id": "chatcmpl-1", "choices": [{"delta": {"content": "Hel"}}]}),
+                json!({"id": "chatcmpl-1", "choices": [{"delta": {"content": "lo"}, "finish_reason": "stop"}]}),
+                json!({"id": "chatcmpl-1", "choices": [], "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15, "prompt_tokens_details": {"cached_tokens": 4}}}),
+            ],
+            true,
+            HashSet::new(),
+        )
+        .await;
+        let events: Vec<ResponseEvent> = events.into_iter().map(Result::unwrap).collect();
+
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { id, .. }) if id == "rs_chatcmpl-1_0"
+        );
+        assert_matches!(&events[2], ResponseEvent::ReasoningContentDelta { delta, .. } if delta == "think");
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning { content: Some(content), .. })
+                if content == &vec![ReasoningItemContent::ReasoningText { text: "think".to_string() }]
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { .. })
+        );
+        assert_matches!(&events[5], ResponseEvent::OutputTextDelta(delta) if delta == "Hel");
+        assert_matches!(&events[6], ResponseEvent::OutputTextDelta(delta) if delta == "lo");
+        assert_matches!(
+            &events[7],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if content == &vec![ContentItem::OutputText { text: "Hello".to_string() }]
+        );
+        match &events[8] {
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+            } => {
+                assert_eq!(response_id, "chatcmpl-1");
+                assert_eq!(
+                    token_usage,
+                    &Some(TokenUsage {
+                        input_tokens: 10,
+                        cached_input_tokens: 4,
+                        output_tokens: 5,
+                        reasoning_output_tokens: 0,
+                        total_tokens: 15,
+                    })
+                );
+            }
+            other => panic!("expected completed event, got {other:?}"),
+        }
+        assert_eq!(events.len(), 9);
+    }
+
+    #[tokio::test]
+    async fn assembles_streamed_tool_calls_and_maps_custom_tools() {
+        let events = collect_events(
+            vec![
+                json!({"id": "c", "choices": [{"delta": {"tool_calls": [{"index": 0, "id": "call_a", "function": {"name": "shell", "arguments": "{\"cmd\":"}}]}}]}),
+                json!({"id": "c", "choices": [{"delta": {"tool_calls": [{"index": 0, "function": {"arguments": "\"ls\"}"}}]}}]}),
+                json!({"id": "c", "choices": [{"delta": {"tool_calls": [{"index": 1, "id": "call_b", "function": {"name": "apply_patch", "arguments": "{\"input\":\"patch\"}"}}]}, "finish_reason": "tool_calls"}]}),
+            ],
+            false,
+            HashSet::from(["apply_patch".to_string()]),
+        )
+        .await;
+        let items: Vec<ResponseItem> = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(ResponseEvent::OutputItemDone(item)) => Some(item),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            items,
+            vec![
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: "{\"cmd\":\"ls\"}".to_string(),
+                    call_id: "call_a".to_string(),
+                },
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call_b".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "patch".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_stream_errors() {
+        let events = collect_events(
+            vec![json!({"error": {"code": "context_length_exceeded", "message": "too long"}})],
+            false,
+            HashSet::new(),
+        )
+        .await;
+
+        assert_matches!(events.as_slice(), [Err(ApiError::ContextWindowExceeded)]);
+    }
+
+    #[tokio::test]
+    async fn errors_when_stream_closes_without_finishing() {
+        let events = collect_events(
+            vec![json!({"id": "c", "choices": [{"delta": {"content": "partial"}}]})],
+            false,
+            HashSet::new(),
+        )
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+    }
+}
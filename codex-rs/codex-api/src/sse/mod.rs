@@ -1,3 +1,6 @@
+pub mod anthropic;
+pub mod chat;
+pub(crate) mod output_items;
 pub mod responses;
 
 pub use responses::process_sse;
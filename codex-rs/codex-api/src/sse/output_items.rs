@@ -0,0 +1,212 @@
+//! Assembles Responses-style output items from the incremental text, reasoning
+//! and tool-call deltas streamed by Chat Completions and Anthropic Messages
+//! providers.
+//!
+//! Codex expects each streamed item to be bracketed by `OutputItemAdded` and
+//! `OutputItemDone`, with at most one text or reasoning item open at a time.
+
+use crate::common::ResponseEvent;
+use crate::requests::translation::custom_tool_input;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use std::collections::HashSet;
+
+enum OpenItem {
+    Message {
+        id: String,
+        text: String,
+    },
+    Reasoning {
+        id: String,
+        text: String,
+        signature: Option<String>,
+    },
+}
+
+pub(crate) struct OutputItemAssembler {
+    response_id: String,
+    custom_tool_names: HashSet<String>,
+    open_item: Option<OpenItem>,
+    next_item_index: usize,
+}
+
+impl OutputItemAssembler {
+    pub(crate) fn new(custom_tool_names: HashSet<String>) -> Self {
+        Self {
+            response_id: String::new(),
+            custom_tool_names,
+            open_item: None,
+            next_item_index: 0,
+        }
+    }
+
+    pub(crate) fn response_id(&self) -> &str {
+        &self.response_id
+    }
+
+    pub(crate) fn set_response_id(&mut self, response_id: String) {
+        if self.response_id.is_empty() {
+            self.response_id = response_id;
+        }
+    }
+
+    pub(crate) fn push_text_delta(&mut self, delta: String) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        if !matches!(self.open_item, Some(OpenItem::Message { .. })) {
+            events.extend(self.close_open_item());
+            let id = self.next_item_id("msg");
+            events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                id.clone(),
+                String::new(),
+            )));
+            self.open_item = Some(OpenItem::Message {
+                id,
+                text: String::new(),
+            });
+        }
+        if let Some(OpenItem::Message { text, .. }) = self.open_item.as_mut() {
+            text.push_str(&delta);
+        }
+        events.push(ResponseEvent::OutputTextDelta(delta));
+        events
+    }
+
+    pub(crate) fn push_reasoning_delta(&mut self, delta: String) -> Vec<ResponseEvent> {
+        let mut events = self.ensure_reasoning_open();
+        if let Some(OpenItem::Reasoning { text, .. }) = self.open_item.as_mut() {
+            text.push_str(&delta);
+        }
+        events.push(ResponseEvent::ReasoningContentDelta {
+            delta,
+            content_index: 0,
+        });
+        events
+    }
+
+    /// Attaches an opaque signature to the open reasoning item so it can be
+    /// replayed to the provider on the next request.
+    pub(crate) fn push_reasoning_signature(&mut self, signature: String) -> Vec<ResponseEvent> {
+        let events = self.ensure_reasoning_open();
+        if let Some(OpenItem::Reasoning {
+            signature: existing,
+            ..
+        }) = self.open_item.as_mut()
+        {
+            existing.get_or_insert_default().push_str(&signature);
+        }
+        events
+    }
+
+    /// Emits a complete reasoning item whose content is opaque to Codex.
+    pub(crate) fn push_opaque_reasoning(
+        &mut self,
+        encrypted_content: String,
+    ) -> Vec<ResponseEvent> {
+        let mut events: Vec<ResponseEvent> = self.close_open_item().into_iter().collect();
+        let id = self.next_item_id("rs");
+        events.push(ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+            id,
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(encrypted_content),
+        }));
+        events
+    }
+
+    /// Closes any open item and emits a completed tool call, mapping calls to
+    /// freeform tools back to `CustomToolCall`.
+    pub(crate) fn push_tool_call(
+        &mut self,
+        call_id: String,
+        name: String,
+        arguments: String,
+    ) -> Vec<ResponseEvent> {
+        let mut events: Vec<ResponseEvent> = self.close_open_item().into_iter().collect();
+        let item = if self.custom_tool_names.contains(&name) {
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id,
+                name,
+                input: custom_tool_input(&arguments),
+            }
+        } else {
+            ResponseItem::FunctionCall {
+                id: None,
+                name,
+                arguments,
+                call_id,
+            }
+        };
+        events.push(ResponseEvent::OutputItemDone(item));
+        events
+    }
+
+    /// Generates an id for tool calls that arrive without one.
+    pub(crate) fn next_call_id(&mut self) -> String {
+        self.next_item_id("call")
+    }
+
+    pub(crate) fn close_open_item(&mut self) -> Option<ResponseEvent> {
+        let item = match self.open_item.take()? {
+            OpenItem::Message { id, text } => assistant_message(id, text),
+            OpenItem::Reasoning {
+                id,
+                text,
+                signature,
+            } => ResponseItem::Reasoning {
+                id,
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText { text }]),
+                encrypted_content: signature,
+            },
+        };
+        Some(ResponseEvent::OutputItemDone(item))
+    }
+
+    fn ensure_reasoning_open(&mut self) -> Vec<ResponseEvent> {
+        if matches!(self.open_item, Some(OpenItem::Reasoning { .. })) {
+            return Vec::new();
+        }
+        let mut events: Vec<ResponseEvent> = self.close_open_item().into_iter().collect();
+        let id = self.next_item_id("rs");
+        events.push(ResponseEvent::OutputItemAdded(ResponseItem::Reasoning {
+            id: id.clone(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: None,
+        }));
+        self.open_item = Some(OpenItem::Reasoning {
+            id,
+            text: String::new(),
+            signature: None,
+        });
+        events
+    }
+
+    fn next_item_id(&mut self, prefix: &str) -> String {
+        let index = self.next_item_index;
+        self.next_item_index += 1;
+        if self.response_id.is_empty() {
+            format!("{prefix}_{index}")
+        } else {
+            format!("{prefix}_{}_{index}", self.response_id)
+        }
+    }
+}
+
+fn assistant_message(id: String, text: String) -> ResponseItem {
+    let content = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![ContentItem::OutputText { text }]
+    };
+    ResponseItem::Message {
+        id: Some(id),
+        role: "assistant".to_string(),
+        content,
+        end_turn: None,
+        phase: None,
+    }
+}
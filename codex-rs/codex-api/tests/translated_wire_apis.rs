@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AnthropicMessagesClient;
+use codex_api::AuthProvider;
+use codex_api::ChatCompletionsClient;
+use codex_api::Provider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::ResponsesOptions;
+use codex_api::provider::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::header_exists;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[derive(Clone)]
+struct StaticAuth(&'static str);
+
+impl AuthProvider for StaticAuth {
+    fn bearer_token(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+fn provider(base_url: String) -> Provider {
+    Provider {
+        name: "test".to_string(),
+        base_url,
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: true,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn request() -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "test-model".to_string(),
+        instructions: "be brief".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list files".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: vec![json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Runs a command",
+            "strict": false,
+            "parameters": { "type": "object", "properties": { "cmd": { "type": "string" } } },
+        })],
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: false,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+    }
+}
+
+fn sse_body(events: &[(Option<&str>, String)]) -> String {
+    events
+        .iter()
+        .map(|(event, data)| match event {
+            Some(event) => format!("event: {event}\ndata: {data}\n\n"),
+            None => format!("data: {data}\n\n"),
+        })
+        .collect()
+}
+
+async fn collect(mut stream: codex_api::ResponseStream) -> Result<Vec<ResponseEvent>> {
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    Ok(events)
+}
+
+fn done_items(events: &[ResponseEvent]) -> Vec<&ResponseItem> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn chat_completions_client_streams_translated_events() -> Result<()> {
+    let server = MockServer::start().await;
+    let body = sse_body(&[
+        (
+            None,
+            json!({"id": "chatcmpl-1", "choices": [{"delta": {"content": "Checking"}}]})
+                .to_string(),
+        ),
+        (
+            None,
+            json!({"id": "chatcmpl-1", "choices": [{"delta": {"tool_calls": [{"index": 0, "id": "call_1", "function": {"name": "shell", "arguments": "{\"cmd\":\"ls\"}"}}]}, "finish_reason": "tool_calls"}]})
+                .to_string(),
+        ),
+        (None, "[DONE]".to_string()),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(header("authorization", "Bearer sk-chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ChatCompletionsClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(format!("{}/v1", server.uri())),
+        StaticAuth("sk-chat"),
+    );
+    let stream = client
+        .stream_request(request(), ResponsesOptions::default())
+        .await?;
+    let events = collect(stream).await?;
+
+    assert_eq!(
+        done_items(&events),
+        vec![
+            &ResponseItem::Message {
+                id: Some("msg_chatcmpl-1_0".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Checking".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            &ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                call_id: "call_1".to_string(),
+            },
+        ]
+    );
+    assert!(matches!(
+        events.last(),
+        Some(ResponseEvent::Completed { response_id, .. }) if response_id == "chatcmpl-1"
+    ));
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let sent: Value = serde_json::from_slice(&requests[0].body)?;
+    assert_eq!(
+        sent["messages"],
+        json!([
+            { "role": "system", "content": "be brief" },
+            { "role": "user", "content": "list files" },
+        ])
+    );
+    assert_eq!(sent["tools"][0]["function"]["name"], json!("shell"));
+    assert_eq!(sent["stream"], json!(true));
+    Ok(())
+}
+
+#[tokio::test]
+async fn anthropic_messages_client_streams_translated_events() -> Result<()> {
+    let server = MockServer::start().await;
+    let events = [
+        json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 12, "output_tokens": 1}}}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Checking"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+        json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"cmd\":\"ls\"}"}}),
+        json!({"type": "content_block_stop", "index": 1}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 7}}),
+        json!({"type": "message_stop"}),
+    ];
+    let body = sse_body(
+        &events
+            .iter()
+            .map(|event| (event["type"].as_str(), event.to_string()))
+            .collect::<Vec<_>>(),
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-api-key", "sk-ant"))
+        .and(header_exists("anthropic-version"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicMessagesClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(format!("{}/v1", server.uri())),
+        StaticAuth("sk-ant"),
+    );
+    let stream = client
+        .stream_request(request(), ResponsesOptions::default())
+        .await?;
+    let events = collect(stream).await?;
+
+    assert_eq!(
+        done_items(&events),
+        vec![
+            &ResponseItem::Message {
+                id: Some("msg_msg_1_0".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Checking".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            &ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+        ]
+    );
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert!(requests[0].headers.get("authorization").is_none());
+    let sent: Value = serde_json::from_slice(&requests[0].body)?;
+    assert_eq!(sent["system"], json!("be brief"));
+    assert_eq!(
+        sent["messages"],
+        json!([{ "role": "user", "content": [{ "type": "text", "text": "list files" }] }])
+    );
+    assert_eq!(sent["tools"][0]["input_schema"]["type"], json!("object"));
+    Ok(())
+}
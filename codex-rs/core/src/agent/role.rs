@@ -1,14 +1,16 @@
 //! Applies agent-role configuration layers on top of an existing session config.
 //!
 //! Roles are selected at spawn time and are loaded with the same config machinery as
-//! `config.toml`. This module resolves built-in and user-defined role files, inserts the role as a
-//! high-precedence layer, and preserves the caller's current profile/provider unless the role
-//! explicitly takes ownership of model selection. It does not decide when to spawn a sub-agent or
-//! which role to use; the multi-agent tool handler owns that orchestration.
+//! `config.toml`. This module resolves built-in and user-defined role files (declared under
+//! `[agents]` or discovered in `agents/*.toml`), inserts the role as a high-precedence layer, and
+//! preserves the caller's current profile/provider unless the role explicitly takes ownership of
+//! model selection. It does not decide when to spawn a sub-agent or which role to use; the
+//! multi-agent tool handler owns that orchestration.
 
 use crate::config::AgentRoleConfig;
 use crate::config::Config;
 use crate::config::ConfigOverrides;
+use crate::config::ROLE_METADATA_KEYS;
 use crate::config::deserialize_config_toml_with_base;
 use crate::config_loader::ConfigLayerEntry;
 use crate::config_loader::ConfigLayerStack;
@@ -64,8 +66,14 @@ pub(crate) async fn apply_role_to_config(
         )
     };
 
-    let role_config_toml: TomlValue = toml::from_str(&role_config_contents)
+    let mut role_config_toml: TomlValue = toml::from_str(&role_config_contents)
         .map_err(|_| AGENT_TYPE_UNAVAILABLE_ERROR.to_string())?;
+    // Role files discovered under `agents/` carry their own description; it is not config.
+    if let Some(table) = role_config_toml.as_table_mut() {
+        for key in ROLE_METADATA_KEYS {
+            table.remove(key);
+        }
+    }
     deserialize_config_toml_with_base(role_config_toml.clone(), role_config_base)
         .map_err(|_| AGENT_TYPE_UNAVAILABLE_ERROR.to_string())?;
     let role_layer_toml = resolve_relative_paths_in_config_toml(role_config_toml, role_config_base)
@@ -431,6 +439,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn apply_role_file_with_metadata_applies_model_effort_and_tool_allowlist() {
+        let (home, mut config) = test_config_with_cli_overrides(Vec::new()).await;
+        let role_path = write_role_config(
+            &home,
+            "test-writer.toml",
+            r#"description = "Writes focused unit tests"
+nickname_candidates = ["Ada"]
+model = "role-model"
+model_reasoning_effort = "high"
+
+[tools]
+allowed = ["shell_command", "apply_patch"]
+"#,
+        )
+        .await;
+        config.agent_roles.insert(
+            "test-writer".to_string(),
+            AgentRoleConfig {
+                description: Some("Writes focused unit tests".to_string()),
+                config_file: Some(role_path),
+                nickname_candidates: Some(vec!["Ada".to_string()]),
+            },
+        );
+
+        apply_role_to_config(&mut config, Some("test-writer"))
+            .await
+            .expect("role file with metadata should apply");
+
+        assert_eq!(config.model.as_deref(), Some("role-model"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            config.tool_allowlist,
+            Some(vec!["shell_command".to_string(), "apply_patch".to_string()])
+        );
+    }
+
     #[tokio::test]
     async fn apply_role_preserves_active_profile_and_model_provider() {
         let home = TempDir::new().expect("create temp dir");
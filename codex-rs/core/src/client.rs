@@ -34,6 +34,8 @@ use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use codex_api::AnthropicMessagesClient as ApiAnthropicMessagesClient;
+use codex_api::ChatCompletionsClient as ApiChatCompletionsClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::MemoriesClient as ApiMemoriesClient;
@@ -812,6 +814,79 @@ impl ModelClientSession {
         }
     }
 
+    /// Streams a turn via the Anthropic Messages API when `wire_api` is
+    /// [`WireApi::Anthropic`], and via the Chat Completions API otherwise.
+    ///
+    /// The request is built exactly as for the Responses API and translated
+    /// by `codex-api`, so both wire APIs share prompt construction, auth
+    /// recovery and event handling with the Responses path.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_translated_api(
+        &self,
+        wire_api: WireApi,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        service_tier: Option<ServiceTier>,
+        turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let (request_telemetry, sse_telemetry) =
+                Self::build_streaming_telemetry(session_telemetry);
+            let options = self.build_responses_options(turn_metadata_header, Compression::None);
+
+            let request = self.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort,
+                summary,
+                service_tier,
+            )?;
+            let stream_result = if wire_api == WireApi::Anthropic {
+                ApiAnthropicMessagesClient::new(
+                    transport,
+                    client_setup.api_provider,
+                    client_setup.api_auth,
+                )
+                .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                .stream_request(request, options)
+                .await
+            } else {
+                ApiChatCompletionsClient::new(
+                    transport,
+                    client_setup.api_provider,
+                    client_setup.api_auth,
+                )
+                .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                .stream_request(request, options)
+                .await
+            };
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(stream, session_telemetry.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the Responses API over WebSocket transport.
     #[allow(clippy::too_many_arguments)]
     async fn stream_responses_websocket(
@@ -972,7 +1047,8 @@ impl ModelClientSession {
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
     /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
     /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// Responses API transport otherwise. Chat Completions and Anthropic Messages providers are
+    /// always streamed over HTTP.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -1018,6 +1094,19 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::Chat | WireApi::Anthropic => {
+                self.stream_translated_api(
+                    wire_api,
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    summary,
+                    service_tier,
+                    turn_metadata_header,
+                )
+                .await
+            }
         }
     }
 
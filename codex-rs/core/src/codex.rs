@@ -48,6 +48,8 @@ use crate::stream_events_utils::raw_assistant_output_text_from_item;
 use crate::stream_events_utils::record_completed_response_item;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_bytes_for_tokens;
+use crate::truncate::approx_token_count;
 use crate::turn_metadata::TurnMetadataState;
 use crate::util::error_or_panic;
 use crate::ws_version_from_features;
@@ -59,6 +61,8 @@ use codex_app_server_protocol::McpServerElicitationRequest;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
+use codex_hooks::HookEventBeforeTurn;
+use codex_hooks::HookEventSessionStart;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
 use codex_hooks::Hooks;
@@ -163,6 +167,7 @@ use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
+use crate::exec_env::SecretEnvInjector;
 use codex_config::CONFIG_TOML_FILE;
 
 mod rollout_reconstruction;
@@ -213,24 +218,32 @@ use crate::mentions::collect_tool_mentions_from_messages;
 use crate::network_policy_decision::execpolicy_network_rule_amendment;
 use crate::plugins::PluginsManager;
 use crate::plugins::build_plugin_injections;
+use crate::project_doc::discover_project_doc_paths_from;
 use crate::project_doc::get_user_instructions;
+use crate::project_doc::load_project_doc;
+use crate::project_doc::nearest_project_doc;
+use crate::project_doc::truncate_at_char_boundary;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::CompactedItem;
+use crate::protocol::CompactionTier;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::ListBackgroundJobsResponseEvent;
+use crate::protocol::ListCheckpointsResponseEvent;
 use crate::protocol::McpServerRefreshConfig;
 use crate::protocol::ModelRerouteEvent;
 use crate::protocol::ModelRerouteReason;
 use crate::protocol::NetworkApprovalContext;
 use crate::protocol::Op;
 use crate::protocol::PlanDeltaEvent;
+use crate::protocol::ProjectDocLoadedEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
@@ -256,6 +269,8 @@ use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
 use crate::rollout::policy::EventPersistenceMode;
+use crate::secret_redaction::log_redactions;
+use crate::secret_redaction::redact_exec_event;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -271,6 +286,7 @@ use crate::skills::injection::app_id_from_path;
 use crate::skills::injection::tool_kind_for_path;
 use crate::skills::resolve_skill_dependencies_for_turn;
 use crate::state::ActiveTurn;
+use crate::state::RedoEntry;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::state_db;
@@ -279,6 +295,7 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tasks::UndoAction;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -286,6 +303,7 @@ use crate::tools::js_repl::JsReplHandle;
 use crate::tools::js_repl::resolve_compatible_node;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::network_approval::build_blocked_request_observer;
+use crate::tools::network_approval::build_network_connection_observer;
 use crate::tools::network_approval::build_network_policy_decider;
 use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
@@ -295,6 +313,7 @@ use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::turn_timing::TurnTimingState;
 use crate::turn_timing::record_turn_ttfm_metric;
 use crate::turn_timing::record_turn_ttft_metric;
+use crate::unified_exec::JobStore;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
@@ -759,7 +778,8 @@ impl TurnContext {
         })
         .with_web_search_config(self.tools_config.web_search_config.clone())
         .with_allow_login_shell(self.tools_config.allow_login_shell)
-        .with_agent_roles(config.agent_roles.clone());
+        .with_agent_roles(config.agent_roles.clone())
+        .with_tool_allowlist(self.tools_config.tool_allowlist.clone());
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -1027,6 +1047,7 @@ impl Session {
         sandbox_policy: &SandboxPolicy,
         network_policy_decider: Option<Arc<dyn codex_network_proxy::NetworkPolicyDecider>>,
         blocked_request_observer: Option<Arc<dyn codex_network_proxy::BlockedRequestObserver>>,
+        connection_observer: Option<Arc<dyn codex_network_proxy::NetworkConnectionObserver>>,
         managed_network_requirements_enabled: bool,
         audit_metadata: NetworkProxyAuditMetadata,
     ) -> anyhow::Result<(StartedNetworkProxy, SessionNetworkProxyRuntime)> {
@@ -1035,6 +1056,7 @@ impl Session {
                 sandbox_policy,
                 network_policy_decider,
                 blocked_request_observer,
+                connection_observer,
                 managed_network_requirements_enabled,
                 audit_metadata,
             )
@@ -1143,7 +1165,8 @@ impl Session {
         })
         .with_web_search_config(per_turn_config.web_search_config.clone())
         .with_allow_login_shell(per_turn_config.permissions.allow_login_shell)
-        .with_agent_roles(per_turn_config.agent_roles.clone());
+        .with_agent_roles(per_turn_config.agent_roles.clone())
+        .with_tool_allowlist(per_turn_config.tool_allowlist.clone());
 
         let cwd = session_configuration.cwd.clone();
         let turn_metadata_state = Arc::new(TurnMetadataState::new(
@@ -1503,6 +1526,13 @@ impl Session {
         } else {
             None
         };
+        let connection_observer = state_db_ctx.as_ref().map(|state_db| {
+            build_network_connection_observer(
+                Arc::clone(state_db),
+                conversation_id,
+                Arc::clone(&network_approval),
+            )
+        });
         let network_policy_decider =
             network_policy_decider_session
                 .as_ref()
@@ -1519,6 +1549,7 @@ impl Session {
                     config.permissions.sandbox_policy.get(),
                     network_policy_decider.as_ref().map(Arc::clone),
                     blocked_request_observer.as_ref().map(Arc::clone),
+                    connection_observer,
                     managed_network_requirements_enabled,
                     network_proxy_audit_metadata,
                 )
@@ -1551,8 +1582,11 @@ impl Session {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                ..config.hooks.clone()
             }),
             rollout: Mutex::new(rollout_recorder),
+            secret_redactor: crate::secret_redaction::build_redactor(&config.secret_redaction),
+            secret_env: SecretEnvInjector::load(&config.secrets, &config.codex_home, &config.cwd),
             user_shell: Arc::new(default_shell),
             shell_snapshot_tx,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -1604,6 +1638,19 @@ impl Session {
             let mut guard = network_policy_decider_session.write().await;
             *guard = Arc::downgrade(&sess);
         }
+        // A session_start hook may deny the session, so run it before clients are
+        // told the session is configured.
+        if let Some(message) = sess
+            .dispatch_session_lifecycle_hook(HookEvent::SessionStart {
+                event: HookEventSessionStart {
+                    thread_id: conversation_id,
+                    model: session_configuration.collaboration_mode.model().to_string(),
+                },
+            })
+            .await
+        {
+            return Err(anyhow::anyhow!(message));
+        }
         // Dispatch the SessionConfiguredEvent first and then report any errors.
         // If resuming, include converted initial messages in the payload so UIs can render them immediately.
         let initial_messages = initial_history.get_event_msgs();
@@ -1917,6 +1964,9 @@ impl Session {
                 if let Some(selected_tools) = restored_tool_selection {
                     self.set_mcp_tool_selection(selected_tools).await;
                 }
+                if self.features().enabled(Feature::PersistentJobs) {
+                    self.reattach_background_jobs().await;
+                }
 
                 // Defer seeding the session's initial context until the first turn starts so
                 // turn/start overrides can be merged before we write to the rollout.
@@ -2471,7 +2521,8 @@ impl Session {
         self.conversation.clear_active_handoff().await;
     }
 
-    pub(crate) async fn send_event_raw(&self, event: Event) {
+    pub(crate) async fn send_event_raw(&self, mut event: Event) {
+        self.redact_injected_secrets(&mut event.msg);
         // Record the last known agent status.
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
@@ -2484,12 +2535,22 @@ impl Session {
         }
     }
 
+    /// Injected secret values are scrubbed from command output even with
+    /// `secret_redaction` disabled.
+    fn redact_injected_secrets(&self, msg: &mut EventMsg) {
+        if let Some(redactor) = self.services.secret_env.output_redactor() {
+            let findings = redact_exec_event(redactor, msg);
+            log_redactions("exec_output", &findings);
+        }
+    }
+
     /// Persist the event to the rollout file, flush it, and only then deliver it to clients.
     ///
     /// Most events can be delivered immediately after queueing the rollout write, but some
     /// clients (e.g. app-server thread/rollback) re-read the rollout file synchronously on
     /// receipt of the event and depend on the marker already being visible on disk.
-    pub(crate) async fn send_event_raw_flushed(&self, event: Event) {
+    pub(crate) async fn send_event_raw_flushed(&self, mut event: Event) {
+        self.redact_injected_secrets(&mut event.msg);
         // Record the last known agent status.
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
@@ -2785,6 +2846,7 @@ impl Session {
         turn_context: &TurnContext,
         call_id: String,
         changes: HashMap<PathBuf, FileChange>,
+        skipped: Vec<PathBuf>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
     ) -> oneshot::Receiver<ReviewDecision> {
@@ -2809,6 +2871,7 @@ impl Session {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             changes,
+            skipped,
             reason,
             grant_root,
         });
@@ -3307,6 +3370,177 @@ impl Session {
         state.reference_context_item()
     }
 
+    /// Notes that a turn captured a new undo checkpoint.
+    pub(crate) async fn record_checkpoint(&self) {
+        let mut state = self.state.lock().await;
+        state.checkpoints.record_snapshot();
+    }
+
+    pub(crate) async fn push_redo(&self, entry: RedoEntry) {
+        let mut state = self.state.lock().await;
+        state.checkpoints.push_redo(entry);
+    }
+
+    pub(crate) async fn pop_redo(&self) -> Option<RedoEntry> {
+        let mut state = self.state.lock().await;
+        state.checkpoints.pop_redo()
+    }
+
+    pub(crate) async fn list_checkpoints(&self) -> ListCheckpointsResponseEvent {
+        let state = self.state.lock().await;
+        ListCheckpointsResponseEvent {
+            checkpoints: state.checkpoints.checkpoints(state.history.raw_items()),
+            redo_depth: u32::try_from(state.checkpoints.redo_depth()).unwrap_or(u32::MAX),
+        }
+    }
+
+    pub(crate) async fn job_store(&self) -> JobStore {
+        JobStore::new(&self.codex_home().await, self.conversation_id)
+    }
+
+    pub(crate) async fn list_background_jobs(&self) -> ListBackgroundJobsResponseEvent {
+        let running = self.services.unified_exec_manager.running_job_ids().await;
+        ListBackgroundJobsResponseEvent {
+            jobs: self.job_store().await.list_jobs(&running).await,
+        }
+    }
+
+    /// Queues the nearest project doc of each edited file for the next
+    /// sampling request, skipping docs that are already in context. Docs share
+    /// the `project_doc_scoped_max_tokens` budget for the session.
+    pub(crate) async fn load_scoped_project_docs(
+        &self,
+        turn_context: &TurnContext,
+        edited_paths: &[PathBuf],
+    ) {
+        let config = turn_context.config.as_ref();
+        let budget = config.project_doc_scoped_max_tokens;
+        if budget == 0 || config.project_doc_max_bytes == 0 || edited_paths.is_empty() {
+            return;
+        }
+        let startup_docs = match discover_project_doc_paths_from(config, &turn_context.cwd) {
+            Ok(paths) => paths,
+            Err(err) => {
+                warn!("failed to discover project docs: {err}");
+                return;
+            }
+        };
+
+        for edited_path in edited_paths {
+            let doc_path = match nearest_project_doc(config, &turn_context.cwd, edited_path) {
+                Ok(Some(doc_path)) => doc_path,
+                Ok(None) => continue,
+                Err(err) => {
+                    warn!(
+                        "failed to find project doc for {}: {err}",
+                        edited_path.display()
+                    );
+                    continue;
+                }
+            };
+            if startup_docs.contains(&doc_path) {
+                continue;
+            }
+            let remaining_tokens = {
+                let state = self.state.lock().await;
+                if state.scoped_project_docs.contains(&doc_path) {
+                    continue;
+                }
+                state.scoped_project_docs.remaining_tokens(budget)
+            };
+            if remaining_tokens == 0 {
+                warn!(
+                    "skipping project doc {}: project_doc_scoped_max_tokens exhausted",
+                    doc_path.display()
+                );
+                return;
+            }
+
+            let max_bytes =
+                approx_bytes_for_tokens(remaining_tokens).min(config.project_doc_max_bytes);
+            // One byte over the budget, so an oversize doc is still reported as truncated.
+            let mut text =
+                match load_project_doc(config, doc_path.clone(), max_bytes.saturating_add(1)).await
+                {
+                    Ok(doc) => doc.text,
+                    Err(err) => {
+                        warn!("failed to read project doc {}: {err}", doc_path.display());
+                        continue;
+                    }
+                };
+            let truncated = text.len() > max_bytes;
+            truncate_at_char_boundary(&mut text, max_bytes);
+            if text.trim().is_empty() {
+                continue;
+            }
+            let directory = doc_path
+                .parent()
+                .unwrap_or(doc_path.as_path())
+                .to_string_lossy()
+                .into_owned();
+            let tokens = approx_token_count(&text);
+            let instructions = UserInstructions { directory, text };
+            let item = ResponseInputItem::Message {
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: instructions.serialize_to_text(),
+                }],
+            };
+            if self.inject_response_items(vec![item]).await.is_err() {
+                warn!(
+                    "dropping project doc {}: no active turn",
+                    doc_path.display()
+                );
+                continue;
+            }
+            self.state
+                .lock()
+                .await
+                .scoped_project_docs
+                .record(doc_path.clone(), tokens);
+            self.send_event(
+                turn_context,
+                EventMsg::ProjectDocLoaded(ProjectDocLoadedEvent {
+                    path: doc_path,
+                    trigger_path: edited_path.clone(),
+                    truncated,
+                }),
+            )
+            .await;
+        }
+    }
+
+    /// Takes back persistent jobs left running by an earlier session of this
+    /// thread.
+    async fn reattach_background_jobs(&self) {
+        let job_store = self.job_store().await;
+        let records = job_store.load_records().await;
+        let reattached = self
+            .services
+            .unified_exec_manager
+            .reattach_jobs(self.conversation_id, &job_store, &records)
+            .await;
+        if reattached > 0 {
+            info!("reattached {reattached} background jobs");
+        }
+    }
+
+    /// Emits the turn's cumulative diff and keeps it as the preview for the
+    /// checkpoint taken at the start of the turn.
+    pub(crate) async fn send_turn_diff(&self, turn_context: &TurnContext, unified_diff: String) {
+        {
+            let mut state = self.state.lock().await;
+            state
+                .checkpoints
+                .record_turn_diff(&turn_context.sub_id, unified_diff.clone());
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }),
+        )
+        .await;
+    }
+
     /// Persist the latest turn context snapshot for the first real user turn and for
     /// steady-state turns that emit model-visible context updates.
     ///
@@ -3695,6 +3929,73 @@ impl Session {
         &self.services.hooks
     }
 
+    /// Runs the session_start or session_end hooks. Returns an error message
+    /// when a `fail_closed` hook failed or a session_start hook denied the
+    /// session, and the event should not go ahead.
+    async fn dispatch_session_lifecycle_hook(&self, hook_event: HookEvent) -> Option<String> {
+        let is_session_start = matches!(hook_event, HookEvent::SessionStart { .. });
+        let (cwd, client) = {
+            let state = self.state.lock().await;
+            (
+                state.session_configuration.cwd.clone(),
+                state.session_configuration.app_server_client_name.clone(),
+            )
+        };
+        let hook_outcomes = self
+            .hooks()
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd,
+                client,
+                triggered_at: chrono::Utc::now(),
+                hook_event,
+            })
+            .await;
+
+        for hook_outcome in hook_outcomes {
+            let hook_name = hook_outcome.hook_name;
+            match hook_outcome.result {
+                HookResult::Success => {}
+                HookResult::FailedContinue(error) => {
+                    warn!(
+                        hook_name = %hook_name,
+                        error = %error,
+                        "session lifecycle hook failed; continuing"
+                    );
+                }
+                HookResult::FailedAbort(error) => {
+                    warn!(
+                        hook_name = %hook_name,
+                        error = %error,
+                        "session lifecycle hook failed; aborting"
+                    );
+                    return Some(format!(
+                        "session lifecycle hook '{hook_name}' failed: {error}"
+                    ));
+                }
+                HookResult::Deny { reason } if is_session_start => {
+                    return Some(format!(
+                        "session_start hook '{hook_name}' denied the session: {reason}"
+                    ));
+                }
+                HookResult::Deny { reason } => {
+                    warn!(
+                        hook_name = %hook_name,
+                        reason = %reason,
+                        "session_end hook denied shutdown; shutdown cannot be blocked"
+                    );
+                }
+                HookResult::ModifyToolInput(_) | HookResult::RequireApproval { .. } => {
+                    warn!(
+                        hook_name = %hook_name,
+                        "session lifecycle hook returned a tool decision; ignoring"
+                    );
+                }
+            }
+        }
+        None
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
@@ -3981,7 +4282,27 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     false
                 }
                 Op::Undo => {
-                    handlers::undo(&sess, sub.id.clone()).await;
+                    handlers::undo(&sess, sub.id.clone(), UndoAction::Undo { num_turns: 1 }).await;
+                    false
+                }
+                Op::UndoTurns { num_turns } => {
+                    handlers::undo(&sess, sub.id.clone(), UndoAction::Undo { num_turns }).await;
+                    false
+                }
+                Op::Redo => {
+                    handlers::undo(&sess, sub.id.clone(), UndoAction::Redo).await;
+                    false
+                }
+                Op::ListCheckpoints => {
+                    handlers::list_checkpoints(&sess, sub.id.clone()).await;
+                    false
+                }
+                Op::ListBackgroundJobs => {
+                    handlers::list_background_jobs(&sess, sub.id.clone()).await;
+                    false
+                }
+                Op::KillBackgroundJob { process_id } => {
+                    handlers::kill_background_job(&sess, sub.id.clone(), process_id).await;
                     false
                 }
                 Op::Compact => {
@@ -4076,10 +4397,13 @@ mod handlers {
     use crate::rollout::RolloutRecorder;
     use crate::rollout::session_index;
     use crate::tasks::CompactTask;
+    use crate::tasks::UndoAction;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use codex_hooks::HookEvent;
+    use codex_hooks::HookEventSessionEnd;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
@@ -4568,12 +4892,40 @@ mod handlers {
         }
     }
 
-    pub async fn undo(sess: &Arc<Session>, sub_id: String) {
+    pub async fn undo(sess: &Arc<Session>, sub_id: String, action: UndoAction) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
-        sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
+        sess.spawn_task(turn_context, Vec::new(), UndoTask::new(action))
             .await;
     }
 
+    pub async fn list_checkpoints(sess: &Session, sub_id: String) {
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListCheckpointsResponse(sess.list_checkpoints().await),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn list_background_jobs(sess: &Session, sub_id: String) {
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListBackgroundJobsResponse(sess.list_background_jobs().await),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn kill_background_job(sess: &Session, sub_id: String, process_id: String) {
+        if !sess
+            .services
+            .unified_exec_manager
+            .kill_process(&process_id)
+            .await
+        {
+            warn!("kill requested for unknown background job {process_id}");
+        }
+        list_background_jobs(sess, sub_id).await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -4829,11 +5181,24 @@ mod handlers {
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         let _ = sess.conversation.shutdown().await;
+        sess.services
+            .unified_exec_manager
+            .park_persistent_processes(sess.conversation_id)
+            .await;
         sess.services
             .unified_exec_manager
             .terminate_all_processes()
             .await;
         info!("Shutting down Codex instance");
+        // Config load rejects `fail_closed` for session_end hooks, so there is
+        // no abort to honor here.
+        let _ = sess
+            .dispatch_session_lifecycle_hook(HookEvent::SessionEnd {
+                event: HookEventSessionEnd {
+                    thread_id: sess.conversation_id,
+                },
+            })
+            .await;
         let history = sess.clone_history().await;
         let turn_count = history
             .raw_items()
@@ -5103,6 +5468,78 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
         .collect()
 }
 
+/// Runs `BeforeTurn` hooks for the incoming user input. Returns the message to
+/// surface to the user when a hook denies or aborts the turn.
+async fn dispatch_before_turn_hook(
+    sess: &Session,
+    turn_context: &TurnContext,
+    input: &[UserInput],
+) -> Option<String> {
+    let input_messages = input
+        .iter()
+        .filter_map(|item| match item {
+            UserInput::Text { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .collect();
+    let hook_outcomes = sess
+        .hooks()
+        .dispatch(HookPayload {
+            session_id: sess.conversation_id,
+            cwd: turn_context.cwd.clone(),
+            client: turn_context.app_server_client_name.clone(),
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::BeforeTurn {
+                event: HookEventBeforeTurn {
+                    thread_id: sess.conversation_id,
+                    turn_id: turn_context.sub_id.clone(),
+                    input_messages,
+                },
+            },
+        })
+        .await;
+
+    for hook_outcome in hook_outcomes {
+        let hook_name = hook_outcome.hook_name;
+        match hook_outcome.result {
+            HookResult::Success => {}
+            HookResult::FailedContinue(error) => {
+                warn!(
+                    turn_id = %turn_context.sub_id,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_turn hook failed; continuing"
+                );
+            }
+            HookResult::FailedAbort(error) => {
+                warn!(
+                    turn_id = %turn_context.sub_id,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_turn hook failed; aborting turn"
+                );
+                return Some(format!(
+                    "before_turn hook '{hook_name}' failed and aborted the turn: {error}"
+                ));
+            }
+            HookResult::Deny { reason } => {
+                return Some(format!(
+                    "before_turn hook '{hook_name}' denied the turn: {reason}"
+                ));
+            }
+            HookResult::ModifyToolInput(_) | HookResult::RequireApproval { .. } => {
+                warn!(
+                    turn_id = %turn_context.sub_id,
+                    hook_name = %hook_name,
+                    "before_turn hook returned a tool decision; ignoring"
+                );
+            }
+        }
+    }
+
+    None
+}
+
 /// Takes a user message as input and runs a loop where, at each sampling request, the model
 /// replies with either:
 ///
@@ -5137,6 +5574,17 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    if let Some(message) = dispatch_before_turn_hook(&sess, &turn_context, &input).await {
+        sess.send_event(
+            &turn_context,
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: None,
+            }),
+        )
+        .await;
+        return None;
+    }
     // TODO(ccunningham): Pre-turn compaction runs before context updates and the
     // new user message are recorded. Estimate pending incoming items (context
     // diffs/full reinjection + user input) and trigger compaction preemptively
@@ -5412,6 +5860,7 @@ pub(crate) async fn run_turn(
                         &sess,
                         &turn_context,
                         InitialContextInjection::BeforeLastUserMessage,
+                        auto_compact_limit,
                     )
                     .await
                     .is_err()
@@ -5468,6 +5917,26 @@ pub(crate) async fn run_turn(
                                     abort_message = Some(message);
                                 }
                             }
+                            HookResult::Deny { reason } => {
+                                warn!(
+                                    turn_id = %turn_context.sub_id,
+                                    hook_name = %hook_name,
+                                    reason = %reason,
+                                    "after_agent hook denied turn completion"
+                                );
+                                if abort_message.is_none() {
+                                    abort_message = Some(format!(
+                                        "after_agent hook '{hook_name}' denied turn completion: {reason}"
+                                    ));
+                                }
+                            }
+                            HookResult::ModifyToolInput(_) | HookResult::RequireApproval { .. } => {
+                                warn!(
+                                    turn_id = %turn_context.sub_id,
+                                    hook_name = %hook_name,
+                                    "after_agent hook returned a tool decision; ignoring"
+                                );
+                            }
                         }
                     }
                     if let Some(message) = abort_message {
@@ -5536,7 +6005,13 @@ async fn run_pre_sampling_compact(
         .unwrap_or(i64::MAX);
     // Compact if the total usage tokens are greater than the auto compact limit
     if total_usage_tokens >= auto_compact_limit {
-        run_auto_compact(sess, turn_context, InitialContextInjection::DoNotInject).await?;
+        run_auto_compact(
+            sess,
+            turn_context,
+            InitialContextInjection::DoNotInject,
+            auto_compact_limit,
+        )
+        .await?;
     }
     Ok(())
 }
@@ -5579,6 +6054,7 @@ async fn maybe_run_previous_model_inline_compact(
             sess,
             &previous_model_turn_context,
             InitialContextInjection::DoNotInject,
+            new_auto_compact_limit,
         )
         .await?;
         return Ok(true);
@@ -5586,16 +6062,31 @@ async fn maybe_run_previous_model_inline_compact(
     Ok(false)
 }
 
+/// Runs the configured compaction tiers until the history fits under `token_limit`.
+/// Summarization only happens when pruning alone was not enough.
 async fn run_auto_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
+    token_limit: i64,
 ) -> CodexResult<()> {
+    let tiers = &turn_context.config.compaction.tiers;
+    if tiers.is_empty() {
+        return Ok(());
+    }
+    let tiered = compact::apply_pruning_tiers(sess, turn_context, token_limit).await;
+    // The server-reported usage that triggered compaction can exceed the local
+    // estimate, so only skip summarizing when pruning itself relieved the limit.
+    if tiered.relieved(token_limit) || !tiers.contains(&CompactionTier::Summarize) {
+        compact::commit_tiered_compaction(sess, turn_context, tiered).await;
+        return Ok(());
+    }
     if should_use_remote_compact_task(&turn_context.provider) {
         run_inline_remote_auto_compact_task(
             Arc::clone(sess),
             Arc::clone(turn_context),
             initial_context_injection,
+            tiered,
         )
         .await?;
     } else {
@@ -5603,6 +6094,7 @@ async fn run_auto_compact(
             Arc::clone(sess),
             Arc::clone(turn_context),
             initial_context_injection,
+            tiered,
         )
         .await?;
     }
@@ -6183,6 +6675,8 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::RealtimeConversationRealtime(_)
         | EventMsg::RealtimeConversationClosed(_)
         | EventMsg::ModelReroute(_)
+        | EventMsg::GuardianAssessment(_)
+        | EventMsg::SecretsRedacted(_)
         | EventMsg::ContextCompacted(_)
         | EventMsg::ThreadRolledBack(_)
         | EventMsg::TurnStarted(_)
@@ -6227,6 +6721,9 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::ListCheckpointsResponse(_)
+        | EventMsg::ListBackgroundJobsResponse(_)
+        | EventMsg::ProjectDocLoaded(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
         | EventMsg::RemoteSkillDownloaded(_)
@@ -6865,8 +7362,7 @@ async fn try_run_sampling_request(
             tracker.get_unified_diff()
         };
         if let Ok(Some(unified_diff)) = unified_diff {
-            let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
-            sess.clone().send_event(&turn_context, msg).await;
+            sess.send_turn_diff(&turn_context, unified_diff).await;
         }
     }
 
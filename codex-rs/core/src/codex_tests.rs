@@ -748,6 +748,7 @@ async fn reconstruct_history_uses_replacement_history_verbatim() {
     let rollout_items = vec![RolloutItem::Compacted(CompactedItem {
         message: String::new(),
         replacement_history: Some(replacement_history.clone()),
+        report: None,
     })];
 
     let reconstructed = session
@@ -1764,6 +1765,71 @@ async fn wait_for_thread_rollback_failed(rx: &async_channel::Receiver<Event>) ->
     }
 }
 
+#[tokio::test]
+async fn injected_secrets_are_scrubbed_from_exec_events_and_the_rollout() {
+    let (mut session, turn_context) = make_session_and_context().await;
+    let (tx_event, rx_event) = async_channel::unbounded();
+    session.tx_event = tx_event;
+    session.services.secret_env =
+        crate::exec_env::SecretEnvInjector::scrubbing_for_testing(&["injected-token-value"]);
+    let session = Arc::new(session);
+    let config = session.get_config().await;
+    let recorder = RolloutRecorder::new(
+        config.as_ref(),
+        RolloutRecorderParams::new(
+            ThreadId::default(),
+            None,
+            SessionSource::Exec,
+            BaseInstructions::default(),
+            Vec::new(),
+            EventPersistenceMode::Extended,
+        ),
+        None,
+        None,
+    )
+    .await
+    .expect("create rollout recorder");
+    let rollout_path = recorder.rollout_path().to_path_buf();
+    *session.services.rollout.lock().await = Some(recorder);
+    session.ensure_rollout_materialized().await;
+
+    let output = "token=injected-token-value\n".to_string();
+    session
+        .send_event(
+            &turn_context,
+            EventMsg::ExecCommandEnd(crate::protocol::ExecCommandEndEvent {
+                call_id: "call-1".to_string(),
+                process_id: None,
+                turn_id: turn_context.sub_id.clone(),
+                command: vec!["printenv".to_string()],
+                cwd: turn_context.cwd.clone(),
+                parsed_cmd: Vec::new(),
+                source: crate::protocol::ExecCommandSource::Agent,
+                interaction_input: None,
+                stdout: output.clone(),
+                stderr: String::new(),
+                aggregated_output: output.clone(),
+                exit_code: 0,
+                duration: Duration::ZERO,
+                formatted_output: output,
+                status: crate::protocol::ExecCommandStatus::Completed,
+                seccomp_denial: None,
+            }),
+        )
+        .await;
+    session.flush_rollout().await;
+
+    let event = rx_event.recv().await.expect("exec end event");
+    let EventMsg::ExecCommandEnd(end) = event.msg else {
+        panic!("expected ExecCommandEnd, got {:?}", event.msg);
+    };
+    assert_eq!(end.stdout, "token=[REDACTED_SECRET]\n");
+    assert_eq!(end.aggregated_output, "token=[REDACTED_SECRET]\n");
+    let rollout = std::fs::read_to_string(&rollout_path).expect("read rollout");
+    assert!(!rollout.contains("injected-token-value"));
+    assert!(rollout.contains("token=[REDACTED_SECRET]"));
+}
+
 async fn attach_rollout_recorder(session: &Arc<Session>) -> PathBuf {
     let config = session.get_config().await;
     let recorder = RolloutRecorder::new(
@@ -2074,8 +2140,11 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         ),
         hooks: Hooks::new(HooksConfig {
             legacy_notify_argv: config.notify.clone(),
+            ..HooksConfig::default()
         }),
         rollout: Mutex::new(None),
+        secret_redactor: crate::secret_redaction::build_redactor(&config.secret_redaction),
+        secret_env: crate::exec_env::SecretEnvInjector::default(),
         user_shell: Arc::new(default_user_shell()),
         shell_snapshot_tx: watch::channel(None).0,
         show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -2481,8 +2550,11 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         ),
         hooks: Hooks::new(HooksConfig {
             legacy_notify_argv: config.notify.clone(),
+            ..HooksConfig::default()
         }),
         rollout: Mutex::new(None),
+        secret_redactor: crate::secret_redaction::build_redactor(&config.secret_redaction),
+        secret_env: crate::exec_env::SecretEnvInjector::default(),
         user_shell: Arc::new(default_user_shell()),
         shell_snapshot_tx: watch::channel(None).0,
         show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -3681,6 +3753,7 @@ async fn sample_rollout(
     rollout_items.push(RolloutItem::Compacted(CompactedItem {
         message: summary1.to_string(),
         replacement_history: None,
+        report: None,
     }));
 
     let user2 = ResponseItem::Message {
@@ -3723,6 +3796,7 @@ async fn sample_rollout(
     rollout_items.push(RolloutItem::Compacted(CompactedItem {
         message: summary2.to_string(),
         replacement_history: None,
+        report: None,
     }));
 
     let user3 = ResponseItem::Message {
@@ -15,6 +15,8 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ListBackgroundJobsResponseEvent;
+use codex_protocol::protocol::ListCheckpointsResponseEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TokenUsage;
@@ -128,6 +130,26 @@ impl CodexThread {
             .await;
     }
 
+    /// Turn checkpoints available to undo, newest first.
+    pub async fn list_checkpoints(&self) -> ListCheckpointsResponseEvent {
+        self.codex.session.list_checkpoints().await
+    }
+
+    /// Persistent background jobs recorded for this thread, oldest first.
+    pub async fn list_background_jobs(&self) -> ListBackgroundJobsResponseEvent {
+        self.codex.session.list_background_jobs().await
+    }
+
+    /// Terminates a background job. Returns `false` when no such process is running.
+    pub async fn kill_background_job(&self, process_id: &str) -> bool {
+        self.codex
+            .session
+            .services
+            .unified_exec_manager
+            .kill_process(process_id)
+            .await
+    }
+
     pub fn rollout_path(&self) -> Option<PathBuf> {
         self.rollout_path.clone()
     }
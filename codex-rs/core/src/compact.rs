@@ -9,9 +9,13 @@ use crate::codex::PreviousTurnSettings;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::context_manager::ContextManager;
+use crate::context_manager::is_user_turn_boundary;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
+use crate::protocol::CompactionReport;
+use crate::protocol::CompactionTier;
 use crate::protocol::EventMsg;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
@@ -22,6 +26,8 @@ use crate::util::backoff;
 use codex_protocol::items::ContextCompactionItem;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
@@ -31,6 +37,8 @@ use tracing::error;
 pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt.md");
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
+/// Older tool outputs are cut down to this many tokens by the `prune_tool_outputs` tier.
+const PRUNED_TOOL_OUTPUT_MAX_TOKENS: usize = 256;
 
 /// Controls whether compaction replacement history must include initial context.
 ///
@@ -51,10 +59,147 @@ pub(crate) fn should_use_remote_compact_task(provider: &ModelProviderInfo) -> bo
     provider.is_openai()
 }
 
+/// History produced by the tiers that run before summarization, together with
+/// the report describing what they removed.
+pub(crate) struct TieredCompaction {
+    pub(crate) history: ContextManager,
+    pub(crate) report: CompactionReport,
+}
+
+impl TieredCompaction {
+    fn changed(&self) -> bool {
+        self.report.tool_outputs_pruned > 0 || self.report.reasoning_items_dropped > 0
+    }
+
+    /// Whether the pruning tiers changed the history and brought the estimate
+    /// below `token_limit`.
+    pub(crate) fn relieved(&self, token_limit: i64) -> bool {
+        self.changed() && self.report.tokens_after < token_limit
+    }
+}
+
+/// Applies the configured non-summarizing tiers to a copy of the session history,
+/// stopping as soon as the estimated token count drops below `token_limit`.
+pub(crate) async fn apply_pruning_tiers(
+    sess: &Session,
+    turn_context: &TurnContext,
+    token_limit: i64,
+) -> TieredCompaction {
+    let mut history = sess.clone_history().await;
+    let tokens_before = history.estimate_token_count(turn_context).unwrap_or(0);
+    let mut report = CompactionReport {
+        tier: CompactionTier::Summarize,
+        tool_outputs_pruned: 0,
+        reasoning_items_dropped: 0,
+        tokens_before,
+        tokens_after: tokens_before,
+    };
+    let compaction = &turn_context.config.compaction;
+    for tier in &compaction.tiers {
+        if report.tokens_after < token_limit {
+            break;
+        }
+        let mut items = history.raw_items().to_vec();
+        match tier {
+            CompactionTier::PruneToolOutputs => {
+                report.tool_outputs_pruned +=
+                    prune_tool_outputs(&mut items, compaction.keep_recent_tool_outputs);
+            }
+            CompactionTier::DropReasoning => {
+                report.reasoning_items_dropped += drop_reasoning_items(&mut items);
+            }
+            CompactionTier::Summarize => continue,
+        }
+        history.replace(items);
+        report.tier = *tier;
+        report.tokens_after = history
+            .estimate_token_count(turn_context)
+            .unwrap_or(report.tokens_after);
+    }
+    TieredCompaction { history, report }
+}
+
+/// Installs the history produced by the pruning tiers without summarizing.
+/// Does nothing when the tiers did not change anything.
+pub(crate) async fn commit_tiered_compaction(
+    sess: &Session,
+    turn_context: &TurnContext,
+    tiered: TieredCompaction,
+) {
+    if !tiered.changed() {
+        return;
+    }
+    let TieredCompaction { history, report } = tiered;
+    let mut item = ContextCompactionItem::new();
+    item.report = Some(report.clone());
+    let compaction_item = TurnItem::ContextCompaction(item);
+    sess.emit_turn_item_started(turn_context, &compaction_item)
+        .await;
+    let new_history = history.raw_items().to_vec();
+    let compacted_item = CompactedItem {
+        message: String::new(),
+        replacement_history: Some(new_history.clone()),
+        report: Some(report),
+    };
+    // Pruning keeps the initial context in place, so the current baseline stays valid.
+    let reference_context_item = sess.reference_context_item().await;
+    sess.replace_compacted_history(new_history, reference_context_item, compacted_item)
+        .await;
+    sess.recompute_token_usage(turn_context).await;
+    sess.emit_turn_item_completed(turn_context, compaction_item)
+        .await;
+}
+
+/// Truncates the output of every tool call except the `keep_recent` most recent
+/// ones. Returns how many outputs were shortened.
+pub(crate) fn prune_tool_outputs(items: &mut [ResponseItem], keep_recent: usize) -> u32 {
+    let policy = TruncationPolicy::Tokens(PRUNED_TOOL_OUTPUT_MAX_TOKENS);
+    let mut pruned = 0;
+    let outputs = items.iter_mut().rev().filter_map(|item| match item {
+        ResponseItem::FunctionCallOutput { output, .. }
+        | ResponseItem::CustomToolCallOutput { output, .. } => Some(output),
+        _ => None,
+    });
+    for output in outputs.skip(keep_recent) {
+        let text = output.body.to_text().unwrap_or_default();
+        if approx_token_count(&text) <= PRUNED_TOOL_OUTPUT_MAX_TOKENS
+            && matches!(output.body, FunctionCallOutputBody::Text(_))
+        {
+            continue;
+        }
+        *output = FunctionCallOutputPayload {
+            body: FunctionCallOutputBody::Text(truncate_text(&text, policy)),
+            success: output.success,
+        };
+        pruned += 1;
+    }
+    pruned
+}
+
+/// Removes reasoning items that precede the last user message. Reasoning from
+/// the turn in progress is kept. Returns how many items were removed.
+pub(crate) fn drop_reasoning_items(items: &mut Vec<ResponseItem>) -> u32 {
+    let Some(last_user_index) = items.iter().rposition(is_user_turn_boundary) else {
+        return 0;
+    };
+    let mut dropped = 0;
+    let mut index = 0;
+    items.retain(|item| {
+        let keep = index > last_user_index || !matches!(item, ResponseItem::Reasoning { .. });
+        index += 1;
+        if !keep {
+            dropped += 1;
+        }
+        keep
+    });
+    dropped
+}
+
 pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
+    tiered: TieredCompaction,
 ) -> CodexResult<()> {
     let prompt = turn_context.compact_prompt().to_string();
     let input = vec![UserInput::Text {
@@ -63,7 +208,14 @@ pub(crate) async fn run_inline_auto_compact_task(
         text_elements: Vec::new(),
     }];
 
-    run_compact_task_inner(sess, turn_context, input, initial_context_injection).await?;
+    run_compact_task_inner(
+        sess,
+        turn_context,
+        input,
+        initial_context_injection,
+        Some(tiered),
+    )
+    .await?;
     Ok(())
 }
 
@@ -83,6 +235,7 @@ pub(crate) async fn run_compact_task(
         turn_context,
         input,
         InitialContextInjection::DoNotInject,
+        None,
     )
     .await
 }
@@ -92,13 +245,20 @@ async fn run_compact_task_inner(
     turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     initial_context_injection: InitialContextInjection,
+    tiered: Option<TieredCompaction>,
 ) -> CodexResult<()> {
-    let compaction_item = TurnItem::ContextCompaction(ContextCompactionItem::new());
-    sess.emit_turn_item_started(&turn_context, &compaction_item)
-        .await;
+    let mut compaction_item = ContextCompactionItem::new();
+    sess.emit_turn_item_started(
+        &turn_context,
+        &TurnItem::ContextCompaction(compaction_item.clone()),
+    )
+    .await;
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
 
-    let mut history = sess.clone_history().await;
+    let (mut history, mut report) = match tiered {
+        Some(TieredCompaction { history, report }) => (history, Some(report)),
+        None => (sess.clone_history().await, None),
+    };
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
@@ -214,15 +374,25 @@ async fn run_compact_task_inner(
         InitialContextInjection::DoNotInject => None,
         InitialContextInjection::BeforeLastUserMessage => Some(turn_context.to_turn_context_item()),
     };
+    if let Some(report) = report.as_mut() {
+        report.tier = CompactionTier::Summarize;
+        let mut compacted = ContextManager::new();
+        compacted.replace(new_history.clone());
+        report.tokens_after = compacted
+            .estimate_token_count(turn_context.as_ref())
+            .unwrap_or(report.tokens_after);
+    }
     let compacted_item = CompactedItem {
         message: summary_text.clone(),
         replacement_history: Some(new_history.clone()),
+        report: report.clone(),
     };
     sess.replace_compacted_history(new_history, reference_context_item, compacted_item)
         .await;
     sess.recompute_token_usage(&turn_context).await;
 
-    sess.emit_turn_item_completed(&turn_context, compaction_item)
+    compaction_item.report = report;
+    sess.emit_turn_item_completed(&turn_context, TurnItem::ContextCompaction(compaction_item))
         .await;
     let warning = EventMsg::Warning(WarningEvent {
         message: "Heads up: Long threads and multiple compactions can cause the model to be less accurate. Start a new thread when possible to keep threads small and targeted.".to_string(),
@@ -627,6 +797,79 @@ do things
         assert_eq!(summary, summary_text);
     }
 
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn tool_output(call_id: &str, text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    fn reasoning(id: &str) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: id.to_string(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some("encrypted".to_string()),
+        }
+    }
+
+    #[test]
+    fn prune_tool_outputs_keeps_recent_and_short_outputs() {
+        let long_output = "line\n".repeat(2_000);
+        let mut items = vec![
+            tool_output("old-long", &long_output),
+            tool_output("old-short", "ok"),
+            tool_output("recent-long", &long_output),
+        ];
+
+        let pruned = prune_tool_outputs(&mut items, 1);
+
+        assert_eq!(pruned, 1);
+        let ResponseItem::FunctionCallOutput { output, .. } = &items[0] else {
+            panic!("expected function call output");
+        };
+        let text = output.body.to_text().unwrap_or_default();
+        assert!(approx_token_count(&text) < approx_token_count(&long_output));
+        assert_eq!(items[1], tool_output("old-short", "ok"));
+        assert_eq!(items[2], tool_output("recent-long", &long_output));
+    }
+
+    #[test]
+    fn drop_reasoning_items_keeps_current_turn_reasoning() {
+        let mut items = vec![
+            user_message("first"),
+            reasoning("r1"),
+            tool_output("call-1", "done"),
+            user_message("second"),
+            reasoning("r2"),
+        ];
+
+        let dropped = drop_reasoning_items(&mut items);
+
+        assert_eq!(dropped, 1);
+        assert_eq!(
+            items,
+            vec![
+                user_message("first"),
+                tool_output("call-1", "done"),
+                user_message("second"),
+                reasoning("r2"),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn process_compacted_history_replaces_developer_messages() {
         let compacted_history = vec![
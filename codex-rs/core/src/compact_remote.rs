@@ -4,6 +4,7 @@ use crate::Prompt;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::compact::InitialContextInjection;
+use crate::compact::TieredCompaction;
 use crate::compact::insert_initial_context_before_last_real_user_or_summary;
 use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
@@ -12,6 +13,7 @@ use crate::context_manager::is_codex_generated_item;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
+use crate::protocol::CompactionTier;
 use crate::protocol::EventMsg;
 use crate::protocol::TurnStartedEvent;
 use codex_protocol::items::ContextCompactionItem;
@@ -26,8 +28,15 @@ pub(crate) async fn run_inline_remote_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
+    tiered: TieredCompaction,
 ) -> CodexResult<()> {
-    run_remote_compact_task_inner(&sess, &turn_context, initial_context_injection).await?;
+    run_remote_compact_task_inner(
+        &sess,
+        &turn_context,
+        initial_context_injection,
+        Some(tiered),
+    )
+    .await?;
     Ok(())
 }
 
@@ -42,16 +51,24 @@ pub(crate) async fn run_remote_compact_task(
     });
     sess.send_event(&turn_context, start_event).await;
 
-    run_remote_compact_task_inner(&sess, &turn_context, InitialContextInjection::DoNotInject).await
+    run_remote_compact_task_inner(
+        &sess,
+        &turn_context,
+        InitialContextInjection::DoNotInject,
+        None,
+    )
+    .await
 }
 
 async fn run_remote_compact_task_inner(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
+    tiered: Option<TieredCompaction>,
 ) -> CodexResult<()> {
     if let Err(err) =
-        run_remote_compact_task_inner_impl(sess, turn_context, initial_context_injection).await
+        run_remote_compact_task_inner_impl(sess, turn_context, initial_context_injection, tiered)
+            .await
     {
         let event = EventMsg::Error(
             err.to_error_event(Some("Error running remote compact task".to_string())),
@@ -66,11 +83,18 @@ async fn run_remote_compact_task_inner_impl(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
+    tiered: Option<TieredCompaction>,
 ) -> CodexResult<()> {
-    let compaction_item = TurnItem::ContextCompaction(ContextCompactionItem::new());
-    sess.emit_turn_item_started(turn_context, &compaction_item)
-        .await;
-    let mut history = sess.clone_history().await;
+    let mut compaction_item = ContextCompactionItem::new();
+    sess.emit_turn_item_started(
+        turn_context,
+        &TurnItem::ContextCompaction(compaction_item.clone()),
+    )
+    .await;
+    let (mut history, mut report) = match tiered {
+        Some(TieredCompaction { history, report }) => (history, Some(report)),
+        None => (sess.clone_history().await, None),
+    };
     let base_instructions = sess.get_base_instructions().await;
     let deleted_items = trim_function_call_history_to_fit_context_window(
         &mut history,
@@ -137,15 +161,25 @@ async fn run_remote_compact_task_inner_impl(
         InitialContextInjection::DoNotInject => None,
         InitialContextInjection::BeforeLastUserMessage => Some(turn_context.to_turn_context_item()),
     };
+    if let Some(report) = report.as_mut() {
+        let mut compacted = ContextManager::new();
+        compacted.replace(new_history.clone());
+        report.tier = CompactionTier::Summarize;
+        report.tokens_after = compacted
+            .estimate_token_count(turn_context.as_ref())
+            .unwrap_or(report.tokens_after);
+    }
     let compacted_item = CompactedItem {
         message: String::new(),
         replacement_history: Some(new_history.clone()),
+        report: report.clone(),
     };
     sess.replace_compacted_history(new_history, reference_context_item, compacted_item)
         .await;
     sess.recompute_token_usage(turn_context).await;
 
-    sess.emit_turn_item_completed(turn_context, compaction_item)
+    compaction_item.report = report;
+    sess.emit_turn_item_completed(turn_context, TurnItem::ContextCompaction(compaction_item))
         .await;
     Ok(())
 }
@@ -0,0 +1,113 @@
+//! Discovers agent roles declared as standalone files in `agents/` directories.
+//!
+//! A role file such as `~/.codex/agents/test-writer.toml` or
+//! `<repo>/.codex/agents/security-reviewer.toml` defines one role named after the file stem. The
+//! file is a regular config layer (model, `developer_instructions`, `sandbox_mode`,
+//! `model_reasoning_effort`, `[tools] allowed`, ...) plus the role metadata keys listed in
+//! [`ROLE_METADATA_KEYS`], which describe the role to the spawning agent and are stripped before
+//! the layer is applied.
+
+use super::AgentRoleConfig;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+use codex_app_server_protocol::ConfigLayerSource;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Directory, next to `config.toml` or inside a project `.codex/` folder, holding role files.
+pub(crate) const AGENTS_DIR_NAME: &str = "agents";
+
+/// Keys a role file may set that describe the role rather than configure the agent.
+pub(crate) const ROLE_METADATA_KEYS: [&str; 2] = ["description", "nickname_candidates"];
+
+#[derive(Deserialize)]
+struct RoleFileMetadata {
+    description: Option<String>,
+    nickname_candidates: Option<Vec<String>>,
+}
+
+/// Loads role files from the `agents/` directory of the user config folder and of every enabled
+/// project `.codex/` folder. Files from higher-precedence layers replace same-named roles from
+/// lower ones, so a repository can override a role from `~/.codex/agents`.
+pub(crate) fn discover_agent_role_files(
+    config_layer_stack: &ConfigLayerStack,
+) -> std::io::Result<BTreeMap<String, AgentRoleConfig>> {
+    let mut roles = BTreeMap::new();
+    for layer in
+        config_layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+    {
+        if !matches!(
+            layer.name,
+            ConfigLayerSource::User { .. } | ConfigLayerSource::Project { .. }
+        ) {
+            continue;
+        }
+        let Some(config_folder) = layer.config_folder() else {
+            continue;
+        };
+        let agents_dir = config_folder.as_path().join(AGENTS_DIR_NAME);
+        roles.extend(load_agents_dir(&agents_dir)?);
+    }
+    Ok(roles)
+}
+
+fn load_agents_dir(agents_dir: &Path) -> std::io::Result<Vec<(String, AgentRoleConfig)>> {
+    let entries = match std::fs::read_dir(agents_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut role_files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .collect::<Vec<PathBuf>>();
+    role_files.sort();
+
+    let mut roles = Vec::with_capacity(role_files.len());
+    for path in role_files {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if !is_valid_role_name(name) {
+            tracing::warn!(
+                "ignoring agent role file {}: role names may only contain letters, digits, `-` and `_`",
+                path.display()
+            );
+            continue;
+        }
+        // A broken role file in a checked-out repository must not stop Codex from starting.
+        match load_role_file(name, &path) {
+            Ok(role) => roles.push((name.to_string(), role)),
+            Err(err) => tracing::warn!("ignoring agent role file {}: {err}", path.display()),
+        }
+    }
+    Ok(roles)
+}
+
+fn load_role_file(name: &str, path: &Path) -> std::io::Result<AgentRoleConfig> {
+    let invalid = |message: String| std::io::Error::new(ErrorKind::InvalidInput, message);
+    let contents = std::fs::read_to_string(path)?;
+    let metadata: RoleFileMetadata =
+        toml::from_str(&contents).map_err(|err| invalid(err.message().to_string()))?;
+    let nickname_candidates = super::Config::normalize_agent_role_nickname_candidates(
+        name,
+        metadata.nickname_candidates.as_deref(),
+    )
+    .map_err(|err| invalid(err.to_string()))?;
+    Ok(AgentRoleConfig {
+        description: metadata.description,
+        config_file: Some(path.to_path_buf()),
+        nickname_candidates,
+    })
+}
+
+fn is_valid_role_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
@@ -2,6 +2,7 @@ use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::edit::apply_blocking;
 use crate::config::types::FeedbackConfigToml;
+use crate::config::types::GuardianBackend;
 use crate::config::types::HistoryPersistence;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::MemoriesConfig;
@@ -9,14 +10,18 @@ use crate::config::types::MemoriesToml;
 use crate::config::types::ModelAvailabilityNuxConfig;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::SecretInjection;
 use crate::config_loader::RequirementSource;
 use crate::features::Feature;
 use assert_matches::assert_matches;
 use codex_config::CONFIG_TOML_FILE;
+use codex_network_proxy::NetworkRequestRule;
+use codex_network_proxy::NetworkRequestRuleAction;
 use codex_protocol::permissions::FileSystemAccessMode;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSpecialPath;
+use codex_protocol::protocol::CompactionTier;
 use serde::Deserialize;
 use tempfile::tempdir;
 
@@ -155,6 +160,285 @@ consolidation_model = "gpt-5"
     );
 }
 
+#[test]
+fn guardian_toml_parses_and_clamps_threshold() {
+    let guardian = r#"
+[guardian]
+backend = "rules"
+approval_threshold = 0
+instructions = """
+  Treat anything touching deploy/ as high risk.
+"""
+"#;
+    let guardian_cfg =
+        toml::from_str::<ConfigToml>(guardian).expect("TOML deserialization should succeed");
+    assert_eq!(
+        guardian_cfg
+            .guardian
+            .as_ref()
+            .map(|guardian| guardian.backend),
+        Some(Some(GuardianBackend::Rules))
+    );
+
+    let config = Config::load_from_base_config_with_overrides(
+        guardian_cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from guardian settings");
+    assert_eq!(
+        config.guardian,
+        GuardianConfig {
+            backend: GuardianBackend::Rules,
+            approval_threshold: 1,
+            instructions: Some("Treat anything touching deploy/ as high risk.".to_string()),
+        }
+    );
+}
+
+#[test]
+fn compaction_toml_clamps_threshold_and_dedupes_tiers() {
+    let compaction = r#"
+[compaction]
+threshold_percent = 0
+tiers = ["drop_reasoning", "summarize", "drop_reasoning"]
+keep_recent_tool_outputs = 2
+"#;
+    let compaction_cfg =
+        toml::from_str::<ConfigToml>(compaction).expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        compaction_cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from compaction settings");
+    assert_eq!(
+        config.compaction,
+        CompactionConfig {
+            threshold_percent: Some(1),
+            tiers: vec![CompactionTier::DropReasoning, CompactionTier::Summarize],
+            keep_recent_tool_outputs: 2,
+        }
+    );
+}
+
+#[test]
+fn apply_patch_toml_clamps_threshold_and_can_disable_fuzzy_matching() {
+    let load = |toml: &str| {
+        let cfg = toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+        Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config from apply_patch settings")
+        .apply_patch
+    };
+
+    assert_eq!(load(""), HunkMatchOptions::default());
+    assert_eq!(
+        load("[apply_patch]\nfuzzy_threshold_percent = 20\nanchor_to_scope = false\n"),
+        HunkMatchOptions {
+            fuzzy_threshold: Some(0.5),
+            anchor_to_scope: false,
+        }
+    );
+    assert_eq!(
+        load("[apply_patch]\nfuzzy_match = false\nfuzzy_threshold_percent = 95\n"),
+        HunkMatchOptions {
+            fuzzy_threshold: None,
+            anchor_to_scope: true,
+        }
+    );
+}
+
+#[test]
+fn config_toml_deserializes_hook_commands() {
+    let toml = r#"
+[[hooks.before_tool_use]]
+name = "org-policy"
+command = ["/usr/local/bin/org-policy", "--tool"]
+timeout_ms = 5000
+fail_closed = true
+
+[[hooks.session_start]]
+command = ["audit-log"]
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from hook settings");
+    assert_eq!(
+        config.hooks,
+        HooksConfig {
+            before_tool_use: vec![codex_hooks::CommandHookConfig {
+                name: Some("org-policy".to_string()),
+                argv: vec![
+                    "/usr/local/bin/org-policy".to_string(),
+                    "--tool".to_string(),
+                ],
+                timeout: Some(Duration::from_millis(5000)),
+                fail_closed: true,
+            }],
+            session_start: vec![codex_hooks::CommandHookConfig {
+                name: None,
+                argv: vec!["audit-log".to_string()],
+                timeout: None,
+                fail_closed: false,
+            }],
+            ..HooksConfig::default()
+        }
+    );
+}
+
+#[test]
+fn config_rejects_fail_closed_session_end_hooks() {
+    let toml = r#"
+[[hooks.session_end]]
+command = ["audit-log"]
+fail_closed = true
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let err = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect_err("fail_closed session_end hook should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn config_toml_deserializes_secret_redaction() {
+    let toml = r#"
+[secret_redaction]
+disable_builtin = ["bearer_token"]
+enable_builtin = ["high_entropy"]
+min_confidence = "medium"
+
+[[secret_redaction.detectors]]
+id = "internal_token"
+pattern = "itk_[a-z0-9]{32}"
+confidence = "high"
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from secret redaction settings");
+    assert_eq!(
+        config.secret_redaction,
+        SecretRedactionConfig {
+            enabled: true,
+            disabled_builtins: vec!["bearer_token".to_string()],
+            enabled_builtins: vec!["high_entropy".to_string()],
+            min_confidence: codex_secrets::RedactionConfidence::Medium,
+            custom_detectors: vec![codex_secrets::CustomSecretDetector {
+                id: "internal_token".to_string(),
+                pattern: "itk_[a-z0-9]{32}".to_string(),
+                confidence: codex_secrets::RedactionConfidence::High,
+            }],
+        }
+    );
+}
+
+#[test]
+fn invalid_secret_detector_is_rejected() {
+    let toml = r#"
+[[secret_redaction.detectors]]
+id = "broken"
+pattern = "itk_[a-z"
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let err = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect_err("invalid regex should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(
+        err.to_string().contains("invalid secret_redaction config"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn config_toml_deserializes_secret_injections() {
+    let toml = r#"
+[[secrets.inject]]
+secret = "GITHUB_TOKEN"
+env_var = "GH_TOKEN"
+commands = ["gh", "git  push"]
+
+[[secrets.inject]]
+secret = "NPM_TOKEN"
+execpolicy_allowed = true
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from secrets settings");
+    assert_eq!(
+        config.secrets,
+        SecretsConfig {
+            backend: codex_secrets::SecretsBackendKind::Local,
+            inject: vec![
+                SecretInjection {
+                    secret: codex_secrets::SecretName::new("GITHUB_TOKEN").expect("valid name"),
+                    env_var: "GH_TOKEN".to_string(),
+                    commands: vec![
+                        vec!["gh".to_string()],
+                        vec!["git".to_string(), "push".to_string()],
+                    ],
+                    execpolicy_allowed: false,
+                },
+                SecretInjection {
+                    secret: codex_secrets::SecretName::new("NPM_TOKEN").expect("valid name"),
+                    env_var: "NPM_TOKEN".to_string(),
+                    commands: Vec::new(),
+                    execpolicy_allowed: true,
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn secret_injection_requires_a_match_condition() {
+    let toml = r#"
+[[secrets.inject]]
+secret = "GITHUB_TOKEN"
+"#;
+    let cfg: ConfigToml = toml::from_str(toml).expect("TOML deserialization should succeed");
+
+    let err = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect_err("injection without commands should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "secrets.inject `GITHUB_TOKEN`: set `commands` or `execpolicy_allowed = true`"
+    );
+}
+
 #[test]
 fn config_toml_deserializes_model_availability_nux() {
     let toml = r#"
@@ -218,6 +502,12 @@ proxy_url = "http://127.0.0.1:43128"
 enable_socks5 = false
 allow_upstream_proxy = false
 allowed_domains = ["openai.com"]
+mitm = true
+
+[[permissions.workspace.network.request_rules]]
+domain = "openai.com"
+methods = ["GET"]
+action = "allow"
 "#;
     let cfg: ConfigToml =
         toml::from_str(toml).expect("TOML deserialization should succeed for permissions profiles");
@@ -258,6 +548,15 @@ allowed_domains = ["openai.com"]
                         denied_domains: None,
                         allow_unix_sockets: None,
                         allow_local_binding: None,
+                        mitm: Some(true),
+                        request_rules: Some(vec![NetworkRequestRule {
+                            domain: "openai.com".to_string(),
+                            methods: vec!["GET".to_string()],
+                            path: None,
+                            action: NetworkRequestRuleAction::Allow,
+                        }]),
+                        capture_har: None,
+                        registry_mirror: None,
                     }),
                 },
             )]),
@@ -1296,6 +1595,47 @@ profile = "project"
     Ok(())
 }
 
+#[tokio::test]
+async fn project_config_can_only_lower_guardian_approval_threshold() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    let workspace_key = workspace.path().to_string_lossy().replace('\\', "\\\\");
+    std::fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        format!(
+            r#"
+[guardian]
+approval_threshold = 60
+
+[projects."{workspace_key}"]
+trust_level = "trusted"
+"#,
+        ),
+    )?;
+    let project_config_dir = workspace.path().join(".codex");
+    std::fs::create_dir_all(&project_config_dir)?;
+
+    let mut thresholds = Vec::new();
+    for project_threshold in [100, 40] {
+        std::fs::write(
+            project_config_dir.join(CONFIG_TOML_FILE),
+            format!("[guardian]\napproval_threshold = {project_threshold}\n"),
+        )?;
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .harness_overrides(ConfigOverrides {
+                cwd: Some(workspace.path().to_path_buf()),
+                ..Default::default()
+            })
+            .build()
+            .await?;
+        thresholds.push(config.guardian.approval_threshold);
+    }
+
+    assert_eq!(thresholds, vec![60, 40]);
+    Ok(())
+}
+
 #[test]
 fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
     let codex_home = TempDir::new()?;
@@ -2674,6 +3014,119 @@ nickname_candidates = ["Hypatia", "Noether"]
     Ok(())
 }
 
+#[tokio::test]
+async fn agent_role_files_are_discovered_from_user_and_project_agents_dirs() -> std::io::Result<()>
+{
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    let workspace_key = workspace.path().to_string_lossy().replace('\\', "\\\\");
+    let user_agents_dir = codex_home.path().join("agents");
+    std::fs::create_dir_all(&user_agents_dir)?;
+    std::fs::write(
+        user_agents_dir.join("reviewer.toml"),
+        "description = \"User reviewer\"\nmodel = \"gpt-5\"\n",
+    )?;
+    std::fs::write(
+        user_agents_dir.join("test-writer.toml"),
+        "description = \"Writes tests\"\nnickname_candidates = [\"Ada\"]\n",
+    )?;
+    std::fs::write(
+        user_agents_dir.join("bad name.toml"),
+        "description = \"x\"\n",
+    )?;
+    std::fs::write(
+        user_agents_dir.join("declared.toml"),
+        "description = \"From file\"\n",
+    )?;
+    std::fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        format!(
+            r#"[agents.declared]
+description = "From config.toml"
+
+[projects."{workspace_key}"]
+trust_level = "trusted"
+"#
+        ),
+    )?;
+    let project_agents_dir = workspace.path().join(".codex").join("agents");
+    std::fs::create_dir_all(&project_agents_dir)?;
+    std::fs::write(
+        project_agents_dir.join("reviewer.toml"),
+        "description = \"Repo reviewer\"\n",
+    )?;
+    std::fs::write(project_agents_dir.join("broken.toml"), "description = [\n")?;
+
+    let config = ConfigBuilder::default()
+        .codex_home(codex_home.path().to_path_buf())
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(workspace.path().to_path_buf()),
+            ..Default::default()
+        })
+        .build()
+        .await?;
+
+    let descriptions = config
+        .agent_roles
+        .iter()
+        .map(|(name, role)| (name.as_str(), role.description.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        descriptions,
+        vec![
+            ("declared", Some("From config.toml")),
+            ("reviewer", Some("Repo reviewer")),
+            ("test-writer", Some("Writes tests")),
+        ]
+    );
+    assert_eq!(
+        config
+            .agent_roles
+            .get("reviewer")
+            .and_then(|role| role.config_file.as_deref())
+            .map(|path| path.starts_with(workspace.path())),
+        Some(true)
+    );
+    assert_eq!(
+        config
+            .agent_roles
+            .get("test-writer")
+            .and_then(|role| role.nickname_candidates.clone()),
+        Some(vec!["Ada".to_string()])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_tool_allowlist_overrides_base() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"profile = "narrow"
+
+[tools]
+allowed = ["shell_command", "apply_patch"]
+
+[profiles.narrow.tools]
+allowed = ["mcp__docs__*"]
+"#,
+    )?;
+
+    let config = ConfigBuilder::default()
+        .codex_home(codex_home.path().to_path_buf())
+        .fallback_cwd(Some(codex_home.path().to_path_buf()))
+        .build()
+        .await?;
+
+    assert_eq!(
+        config.tool_allowlist,
+        Some(vec!["mcp__docs__*".to_string()])
+    );
+
+    Ok(())
+}
+
 #[test]
 fn load_config_normalizes_agent_role_nickname_candidates() -> std::io::Result<()> {
     let codex_home = TempDir::new()?;
@@ -3017,6 +3470,8 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_write_scope: Default::default(),
+                sandbox_seccomp: Default::default(),
             },
             enforce_residency: Constrained::allow_any(None),
             user_instructions: None,
@@ -3029,12 +3484,17 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             mcp_oauth_callback_url: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            project_doc_scoped_max_tokens: PROJECT_DOC_SCOPED_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            hooks: HooksConfig::default(),
+            secret_redaction: SecretRedactionConfig::default(),
+            secrets: SecretsConfig::default(),
+            guardian: GuardianConfig::default(),
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             codex_home: fixture.codex_home(),
             sqlite_home: fixture.codex_home(),
@@ -3071,11 +3531,14 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
+            apply_patch: HunkMatchOptions::default(),
             web_search_mode: Constrained::allow_any(WebSearchMode::Cached),
             web_search_config: None,
+            tool_allowlist: None,
             use_experimental_unified_exec_tool: !cfg!(windows),
             background_terminal_max_timeout: DEFAULT_MAX_BACKGROUND_TERMINAL_TIMEOUT_MS,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            compaction: CompactionConfig::default(),
             features: Features::with_defaults().into(),
             suppress_unstable_features_warning: false,
             active_profile: Some("o3".to_string()),
@@ -3183,6 +3646,8 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            sandbox_write_scope: Default::default(),
+            sandbox_seccomp: Default::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -3195,12 +3660,17 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         mcp_oauth_callback_url: None,
         model_providers: fixture.model_provider_map.clone(),
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+        project_doc_scoped_max_tokens: PROJECT_DOC_SCOPED_MAX_TOKENS,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        hooks: HooksConfig::default(),
+        secret_redaction: SecretRedactionConfig::default(),
+        secrets: SecretsConfig::default(),
+        guardian: GuardianConfig::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -3237,11 +3707,14 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         forced_chatgpt_workspace_id: None,
         forced_login_method: None,
         include_apply_patch_tool: false,
+        apply_patch: HunkMatchOptions::default(),
         web_search_mode: Constrained::allow_any(WebSearchMode::Cached),
         web_search_config: None,
+        tool_allowlist: None,
         use_experimental_unified_exec_tool: !cfg!(windows),
         background_terminal_max_timeout: DEFAULT_MAX_BACKGROUND_TERMINAL_TIMEOUT_MS,
         ghost_snapshot: GhostSnapshotConfig::default(),
+        compaction: CompactionConfig::default(),
         features: Features::with_defaults().into(),
         suppress_unstable_features_warning: false,
         active_profile: Some("gpt3".to_string()),
@@ -3316,6 +3789,8 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            sandbox_write_scope: Default::default(),
+            sandbox_seccomp: Default::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -3328,12 +3803,17 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         mcp_oauth_callback_url: None,
         model_providers: fixture.model_provider_map.clone(),
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+        project_doc_scoped_max_tokens: PROJECT_DOC_SCOPED_MAX_TOKENS,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        hooks: HooksConfig::default(),
+        secret_redaction: SecretRedactionConfig::default(),
+        secrets: SecretsConfig::default(),
+        guardian: GuardianConfig::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -3370,11 +3850,14 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         forced_chatgpt_workspace_id: None,
         forced_login_method: None,
         include_apply_patch_tool: false,
+        apply_patch: HunkMatchOptions::default(),
         web_search_mode: Constrained::allow_any(WebSearchMode::Cached),
         web_search_config: None,
+        tool_allowlist: None,
         use_experimental_unified_exec_tool: !cfg!(windows),
         background_terminal_max_timeout: DEFAULT_MAX_BACKGROUND_TERMINAL_TIMEOUT_MS,
         ghost_snapshot: GhostSnapshotConfig::default(),
+        compaction: CompactionConfig::default(),
         features: Features::with_defaults().into(),
         suppress_unstable_features_warning: false,
         active_profile: Some("zdr".to_string()),
@@ -3435,6 +3918,8 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            sandbox_write_scope: Default::default(),
+            sandbox_seccomp: Default::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -3447,12 +3932,17 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         mcp_oauth_callback_url: None,
         model_providers: fixture.model_provider_map.clone(),
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+        project_doc_scoped_max_tokens: PROJECT_DOC_SCOPED_MAX_TOKENS,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        hooks: HooksConfig::default(),
+        secret_redaction: SecretRedactionConfig::default(),
+        secrets: SecretsConfig::default(),
+        guardian: GuardianConfig::default(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -3489,11 +3979,14 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         forced_chatgpt_workspace_id: None,
         forced_login_method: None,
         include_apply_patch_tool: false,
+        apply_patch: HunkMatchOptions::default(),
         web_search_mode: Constrained::allow_any(WebSearchMode::Cached),
         web_search_config: None,
+        tool_allowlist: None,
         use_experimental_unified_exec_tool: !cfg!(windows),
         background_terminal_max_timeout: DEFAULT_MAX_BACKGROUND_TERMINAL_TIMEOUT_MS,
         ghost_snapshot: GhostSnapshotConfig::default(),
+        compaction: CompactionConfig::default(),
         features: Features::with_defaults().into(),
         suppress_unstable_features_warning: false,
         active_profile: Some("gpt5".to_string()),
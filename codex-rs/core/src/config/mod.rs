@@ -1,9 +1,17 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::agent_roles::discover_agent_role_files;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::ApplyPatchToml;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::CompactionConfig;
+use crate::config::types::CompactionToml;
+use crate::config::types::DEFAULT_GUARDIAN_APPROVAL_THRESHOLD;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::GuardianConfig;
+use crate::config::types::GuardianToml;
 use crate::config::types::History;
+use crate::config::types::HooksToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
@@ -17,7 +25,12 @@ use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
 use crate::config::types::PluginConfig;
+use crate::config::types::SandboxSeccompToml;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SandboxWriteScopeToml;
+use crate::config::types::SecretRedactionToml;
+use crate::config::types::SecretsConfig;
+use crate::config::types::SecretsToml;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
@@ -54,12 +67,17 @@ use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::ReadOnlyAccess;
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::seccomp::SandboxSeccomp;
+use crate::sandboxing::write_scope::SandboxWriteScope;
 use crate::unified_exec::DEFAULT_MAX_BACKGROUND_TERMINAL_TIMEOUT_MS;
 use crate::unified_exec::MIN_EMPTY_YIELD_TIME_MS;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use crate::windows_sandbox::resolve_windows_sandbox_mode;
+use codex_app_server_protocol::ConfigLayerSource;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_apply_patch::HunkMatchOptions;
+use codex_hooks::HooksConfig;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::Personality;
@@ -78,6 +96,8 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_secrets::SecretRedactionConfig;
+use codex_secrets::SecretRedactor;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use schemars::JsonSchema;
@@ -98,6 +118,7 @@ use codex_network_proxy::NetworkProxyConfig;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
+mod agent_roles;
 pub mod edit;
 mod managed_features;
 mod network_proxy_spec;
@@ -111,11 +132,13 @@ pub use codex_config::ConstraintError;
 pub use codex_config::ConstraintResult;
 pub use codex_network_proxy::NetworkProxyAuditMetadata;
 
+pub(crate) use agent_roles::ROLE_METADATA_KEYS;
 pub use managed_features::ManagedFeatures;
 pub use network_proxy_spec::NetworkProxySpec;
 pub use network_proxy_spec::StartedNetworkProxy;
 pub use permissions::FilesystemPermissionToml;
 pub use permissions::FilesystemPermissionsToml;
+pub use permissions::NetworkRegistryMirrorToml;
 pub use permissions::NetworkToml;
 pub use permissions::PermissionProfileToml;
 pub use permissions::PermissionsToml;
@@ -129,6 +152,8 @@ pub use codex_git::GhostSnapshotConfig;
 /// files are *silently truncated* to this size so we do not take up too much of
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
+/// Token budget shared by all directory-scoped project docs loaded mid-session.
+pub(crate) const PROJECT_DOC_SCOPED_MAX_TOKENS: usize = 4 * 1024;
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
 pub(crate) const DEFAULT_AGENT_MAX_DEPTH: i32 = 1;
 pub(crate) const DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS: Option<u64> = None;
@@ -191,6 +216,10 @@ pub struct Permissions {
     /// Optional macOS seatbelt extension profile used to extend default
     /// seatbelt permissions when running under seatbelt.
     pub macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
+    /// Read-only globs and deny-read paths enforced by the Linux sandbox.
+    pub sandbox_write_scope: SandboxWriteScope,
+    /// Seccomp profiles the Linux sandbox installs per sandbox mode.
+    pub sandbox_seccomp: SandboxSeccomp,
 }
 
 /// Application configuration loaded from disk and merged with overrides.
@@ -361,6 +390,10 @@ pub struct Config {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
+    /// Token budget for AGENTS.md files loaded mid-session when the agent
+    /// edits files under their directory. Zero disables scoped loading.
+    pub project_doc_scoped_max_tokens: usize,
+
     /// Additional filenames to try when looking for project-level docs.
     pub project_doc_fallback_filenames: Vec<String>,
 
@@ -381,6 +414,19 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// External hook commands configured under `[hooks]`. The legacy `notify`
+    /// command is tracked separately in [`Config::notify`].
+    pub hooks: HooksConfig,
+
+    /// Secret detectors applied to tool outputs, rollout files, and memory outputs.
+    pub secret_redaction: SecretRedactionConfig,
+
+    /// Secret storage backend and the secrets injected into command environments.
+    pub secrets: SecretsConfig,
+
+    /// How guardian reviews `on-request` approvals when `features.guardian_approval` is on.
+    pub guardian: GuardianConfig,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -481,12 +527,18 @@ pub struct Config {
     /// model info's default preference.
     pub include_apply_patch_tool: bool,
 
+    /// How leniently `apply_patch` locates update chunks in the target file.
+    pub apply_patch: HunkMatchOptions,
+
     /// Explicit or feature-derived web search mode.
     pub web_search_mode: Constrained<WebSearchMode>,
 
     /// Additional parameters for the web search tool when it is enabled.
     pub web_search_config: Option<WebSearchConfig>,
 
+    /// Tool names (or `prefix*` patterns) the model may use. `None` allows every enabled tool.
+    pub tool_allowlist: Option<Vec<String>>,
+
     /// If set to `true`, used only the experimental unified exec tool.
     pub use_experimental_unified_exec_tool: bool,
 
@@ -497,6 +549,9 @@ pub struct Config {
     /// Settings for ghost snapshots (used for undo).
     pub ghost_snapshot: GhostSnapshotConfig,
 
+    /// Settings for automatic compaction.
+    pub compaction: CompactionConfig,
+
     /// Centralized feature flags; source of truth for feature gating.
     pub features: ManagedFeatures,
 
@@ -1067,6 +1122,13 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Read-only globs and deny-read paths enforced by the Linux sandbox.
+    pub sandbox_write_scope: Option<SandboxWriteScopeToml>,
+
+    /// Named seccomp profiles (e.g. `no-ptrace`) installed by the Linux
+    /// sandbox, per sandbox mode.
+    pub sandbox_seccomp: Option<SandboxSeccompToml>,
+
     /// Default named permissions profile to apply from the `[permissions]`
     /// table.
     pub default_permissions: Option<String>,
@@ -1146,6 +1208,10 @@ pub struct ConfigToml {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
+    /// Token budget for AGENTS.md files loaded mid-session when the agent
+    /// edits files under their directory. Zero disables scoped loading.
+    pub project_doc_scoped_max_tokens: Option<usize>,
+
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
@@ -1255,6 +1321,18 @@ pub struct ConfigToml {
     /// Memories subsystem settings.
     pub memories: Option<MemoriesToml>,
 
+    /// External commands run on session, turn, and tool lifecycle events.
+    pub hooks: Option<HooksToml>,
+
+    /// Secret detectors applied to tool outputs, rollout files, and memory outputs.
+    pub secret_redaction: Option<SecretRedactionToml>,
+
+    /// Secret storage backend and the secrets injected into command environments.
+    pub secrets: Option<SecretsToml>,
+
+    /// Guardian backend, approval threshold, and extra review instructions.
+    pub guardian: Option<GuardianToml>,
+
     /// User-level skill config entries keyed by SKILL.md path.
     pub skills: Option<SkillsConfig>,
 
@@ -1275,6 +1353,14 @@ pub struct ConfigToml {
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
 
+    /// Settings for automatic compaction.
+    #[serde(default)]
+    pub compaction: Option<CompactionToml>,
+
+    /// Hunk matching settings for `apply_patch`.
+    #[serde(default)]
+    pub apply_patch: Option<ApplyPatchToml>,
+
     /// Markers used to detect the project root when searching parent
     /// directories for `.codex` folders. Defaults to [".git"] when unset.
     #[serde(default)]
@@ -1390,6 +1476,11 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Restricts the tools offered to the model to these names. A trailing `*` matches a
+    /// prefix, e.g. `mcp__docs__*`. When unset, every enabled tool is offered.
+    #[serde(default)]
+    pub allowed: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1465,6 +1556,10 @@ pub struct GhostSnapshotToml {
     pub ignore_large_untracked_dirs: Option<i64>,
     /// Disable all ghost snapshot warning events.
     pub disable_warnings: Option<bool>,
+    /// Snapshot directories that are not Git repositories into a
+    /// content-addressed store under `CODEX_HOME/snapshots`, so undo works
+    /// there too. Defaults to false.
+    pub snapshot_outside_git: Option<bool>,
 }
 
 impl ConfigToml {
@@ -1760,6 +1855,22 @@ fn resolve_web_search_config(
     }
 }
 
+fn resolve_tool_allowlist(
+    config_toml: &ConfigToml,
+    config_profile: &ConfigProfile,
+) -> Option<Vec<String>> {
+    config_profile
+        .tools
+        .as_ref()
+        .and_then(|tools| tools.allowed.clone())
+        .or_else(|| {
+            config_toml
+                .tools
+                .as_ref()
+                .and_then(|tools| tools.allowed.clone())
+        })
+}
+
 pub(crate) fn resolve_web_search_mode_for_turn(
     web_search_mode: &Constrained<WebSearchMode>,
     sandbox_policy: &SandboxPolicy,
@@ -1929,6 +2040,18 @@ impl Config {
             ));
         }
 
+        if cfg.hooks.as_ref().is_some_and(|hooks| {
+            hooks
+                .session_end
+                .iter()
+                .any(|hook| hook.fail_closed == Some(true))
+        }) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "`fail_closed` is not supported for `hooks.session_end`: shutdown cannot be blocked",
+            ));
+        }
+
         let windows_sandbox_level = match windows_sandbox_mode {
             Some(WindowsSandboxModeToml::Elevated) => WindowsSandboxLevel::Elevated,
             Some(WindowsSandboxModeToml::Unelevated) => WindowsSandboxLevel::RestrictedToken,
@@ -2040,6 +2163,7 @@ impl Config {
         let web_search_mode = resolve_web_search_mode(&cfg, &config_profile, &features)
             .unwrap_or(WebSearchMode::Cached);
         let web_search_config = resolve_web_search_config(&cfg, &config_profile);
+        let tool_allowlist = resolve_tool_allowlist(&cfg, &config_profile);
 
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list, replacing any
@@ -2091,7 +2215,7 @@ impl Config {
                 "agents.max_depth must be at least 1",
             ));
         }
-        let agent_roles = cfg
+        let declared_agent_roles = cfg
             .agents
             .as_ref()
             .map(|agents| {
@@ -2119,6 +2243,9 @@ impl Config {
             })
             .transpose()?
             .unwrap_or_default();
+        // Roles declared under `[agents]` win over same-named `agents/*.toml` files.
+        let mut agent_roles = discover_agent_role_files(&config_layer_stack)?;
+        agent_roles.extend(declared_agent_roles);
         let agent_job_max_runtime_seconds = cfg
             .agents
             .as_ref()
@@ -2165,6 +2292,14 @@ impl Config {
             {
                 config.disable_warnings = disable_warnings;
             }
+            if cfg
+                .ghost_snapshot
+                .as_ref()
+                .and_then(|ghost_snapshot| ghost_snapshot.snapshot_outside_git)
+                .unwrap_or(false)
+            {
+                config.shadow_store = Some(codex_home.join("snapshots"));
+            }
             config
         };
 
@@ -2301,6 +2436,36 @@ impl Config {
         let mcp_servers = constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_servers.as_ref())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let secret_redaction: SecretRedactionConfig =
+            cfg.secret_redaction.clone().unwrap_or_default().into();
+        SecretRedactor::from_config(&secret_redaction).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid secret_redaction config: {e}"),
+            )
+        })?;
+        let secrets = SecretsConfig::try_from(cfg.secrets.clone().unwrap_or_default())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let mut guardian: GuardianConfig = cfg.guardian.clone().unwrap_or_default().into();
+        if let Some(approval_threshold) =
+            project_capped_guardian_approval_threshold(&config_layer_stack)
+        {
+            guardian.approval_threshold = approval_threshold.clamp(1, 100);
+        }
+        let sandbox_write_scope =
+            SandboxWriteScope::from_toml(cfg.sandbox_write_scope.clone().unwrap_or_default());
+        if cfg!(target_os = "linux")
+            && !sandbox_write_scope.is_empty()
+            && !features.enabled(Feature::UseLinuxSandboxBwrap)
+        {
+            startup_warnings.push(
+                "`sandbox_write_scope` is only enforced by the bubblewrap Linux sandbox; sandboxed commands it applies to will be refused until `features.use_linux_sandbox_bwrap` is enabled."
+                    .to_string(),
+            );
+        }
+        let sandbox_seccomp =
+            SandboxSeccomp::from_toml(cfg.sandbox_seccomp.clone().unwrap_or_default());
+
         let (network_requirements, network_requirements_source) = match network_requirements {
             Some(Sourced { value, source }) => (Some(value), Some(source)),
             None => (None, None),
@@ -2360,6 +2525,8 @@ impl Config {
                 shell_environment_policy,
                 windows_sandbox_mode,
                 macos_seatbelt_profile_extensions: None,
+                sandbox_write_scope,
+                sandbox_seccomp,
             },
             enforce_residency: enforce_residency.value,
             notify: cfg.notify,
@@ -2380,6 +2547,9 @@ impl Config {
             mcp_oauth_callback_url: cfg.mcp_oauth_callback_url.clone(),
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
+            project_doc_scoped_max_tokens: cfg
+                .project_doc_scoped_max_tokens
+                .unwrap_or(PROJECT_DOC_SCOPED_MAX_TOKENS),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
                 .unwrap_or_default()
@@ -2398,6 +2568,10 @@ impl Config {
             agent_max_depth,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
+            hooks: cfg.hooks.unwrap_or_default().into(),
+            secret_redaction,
+            secrets,
+            guardian,
             agent_job_max_runtime_seconds,
             codex_home,
             sqlite_home,
@@ -2446,11 +2620,14 @@ impl Config {
             forced_chatgpt_workspace_id,
             forced_login_method,
             include_apply_patch_tool: include_apply_patch_tool_flag,
+            apply_patch: cfg.apply_patch.unwrap_or_default().into(),
             web_search_mode: constrained_web_search_mode.value,
             web_search_config,
+            tool_allowlist,
             use_experimental_unified_exec_tool,
             background_terminal_max_timeout,
             ghost_snapshot,
+            compaction: cfg.compaction.unwrap_or_default().into(),
             features,
             suppress_unstable_features_warning: cfg
                 .suppress_unstable_features_warning
@@ -2676,6 +2853,39 @@ impl Config {
     }
 }
 
+/// A project's `.codex/config.toml` may only make guardian stricter: its
+/// `guardian.approval_threshold` can lower the value from the other layers (or the
+/// default) but never raise it. Returns `None` when no project layer sets it.
+fn project_capped_guardian_approval_threshold(config_layer_stack: &ConfigLayerStack) -> Option<u8> {
+    let mut threshold = None;
+    let mut project_threshold: Option<u8> = None;
+    for layer in
+        config_layer_stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+    {
+        let Some(layer_threshold) = layer
+            .config
+            .get("guardian")
+            .and_then(|guardian| guardian.get("approval_threshold"))
+            .and_then(TomlValue::as_integer)
+            .map(|value| u8::try_from(value.clamp(0, 100)).unwrap_or(100))
+        else {
+            continue;
+        };
+        if matches!(layer.name, ConfigLayerSource::Project { .. }) {
+            project_threshold = Some(
+                project_threshold.map_or(layer_threshold, |lowest| lowest.min(layer_threshold)),
+            );
+        } else {
+            threshold = Some(layer_threshold);
+        }
+    }
+    project_threshold.map(|project_threshold| {
+        threshold
+            .unwrap_or(DEFAULT_GUARDIAN_APPROVAL_THRESHOLD)
+            .min(project_threshold)
+    })
+}
+
 pub(crate) fn uses_deprecated_instructions_file(config_layer_stack: &ConfigLayerStack) -> bool {
     config_layer_stack
         .layers_high_to_low()
@@ -6,6 +6,8 @@ use std::path::PathBuf;
 
 use codex_network_proxy::NetworkMode;
 use codex_network_proxy::NetworkProxyConfig;
+use codex_network_proxy::NetworkRequestRule;
+use codex_network_proxy::PackageRegistry;
 use codex_protocol::permissions::FileSystemAccessMode;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry;
@@ -72,6 +74,29 @@ pub struct NetworkToml {
     pub denied_domains: Option<Vec<String>>,
     pub allow_unix_sockets: Option<Vec<String>>,
     pub allow_local_binding: Option<bool>,
+    /// Terminate HTTPS so limited mode and request rules can see inner methods and paths.
+    pub mitm: Option<bool>,
+    /// Per-domain method/path rules applied after the domain allowlist/denylist.
+    #[schemars(with = "Option<Vec<NetworkRequestRuleSchema>>")]
+    pub request_rules: Option<Vec<NetworkRequestRule>>,
+    /// Store full request/response pairs for MITM'd requests so `codex debug network --har` can
+    /// export them.
+    pub capture_har: Option<bool>,
+    /// Read-only caching mirror for npm, PyPI, and crates.io.
+    pub registry_mirror: Option<NetworkRegistryMirrorToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkRegistryMirrorToml {
+    pub enabled: Option<bool>,
+    /// Registries served by the mirror. Defaults to all of them.
+    #[schemars(with = "Option<Vec<PackageRegistrySchema>>")]
+    pub registries: Option<Vec<PackageRegistry>>,
+    /// Serve only from the cache and never contact upstream registries.
+    pub offline: Option<bool>,
+    /// Cache location. Defaults to `$CODEX_HOME/proxy/registry-cache`.
+    pub cache_dir: Option<AbsolutePathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -81,6 +106,33 @@ enum NetworkModeSchema {
     Full,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct NetworkRequestRuleSchema {
+    /// Domain pattern, using the same syntax as `allowed_domains`.
+    domain: String,
+    /// HTTP methods this rule applies to. Omit to match any method.
+    methods: Option<Vec<String>>,
+    /// URL path glob such as `/-/npm/v1/security/*`. Omit to match any path.
+    path: Option<String>,
+    action: NetworkRequestRuleActionSchema,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum NetworkRequestRuleActionSchema {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum PackageRegistrySchema {
+    Npm,
+    Pypi,
+    Crates,
+}
+
 impl NetworkToml {
     pub(crate) fn apply_to_network_proxy_config(&self, config: &mut NetworkProxyConfig) {
         if let Some(enabled) = self.enabled {
@@ -125,6 +177,30 @@ impl NetworkToml {
         if let Some(allow_local_binding) = self.allow_local_binding {
             config.network.allow_local_binding = allow_local_binding;
         }
+        if let Some(mitm) = self.mitm {
+            config.network.mitm = mitm;
+        }
+        if let Some(request_rules) = self.request_rules.as_ref() {
+            config.network.request_rules = request_rules.clone();
+        }
+        if let Some(capture_har) = self.capture_har {
+            config.network.capture_har = capture_har;
+        }
+        if let Some(registry_mirror) = self.registry_mirror.as_ref() {
+            let mirror = &mut config.network.registry_mirror;
+            if let Some(enabled) = registry_mirror.enabled {
+                mirror.enabled = enabled;
+            }
+            if let Some(registries) = registry_mirror.registries.as_ref() {
+                mirror.registries = registries.clone();
+            }
+            if let Some(offline) = registry_mirror.offline {
+                mirror.offline = offline;
+            }
+            if let Some(cache_dir) = registry_mirror.cache_dir.as_ref() {
+                mirror.cache_dir = Some(cache_dir.clone());
+            }
+        }
     }
 
     pub(crate) fn to_network_proxy_config(&self) -> NetworkProxyConfig {
@@ -4,11 +4,22 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use codex_apply_patch::HunkMatchOptions;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::HooksConfig;
 pub use codex_protocol::config_types::AltScreenMode;
+pub use codex_protocol::config_types::GuardianBackend;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::ServiceTier;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::permissions::SeccompProfile;
+use codex_protocol::protocol::CompactionTier;
+use codex_secrets::CustomSecretDetector;
+use codex_secrets::RedactionConfidence;
+use codex_secrets::SecretName;
+use codex_secrets::SecretRedactionConfig;
+use codex_secrets::SecretsBackendKind;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -29,6 +40,8 @@ pub const DEFAULT_MEMORIES_MAX_ROLLOUT_AGE_DAYS: i64 = 30;
 pub const DEFAULT_MEMORIES_MIN_ROLLOUT_IDLE_HOURS: i64 = 6;
 pub const DEFAULT_MEMORIES_MAX_RAW_MEMORIES_FOR_CONSOLIDATION: usize = 256;
 pub const DEFAULT_MEMORIES_MAX_UNUSED_DAYS: i64 = 30;
+pub const DEFAULT_GUARDIAN_APPROVAL_THRESHOLD: u8 = 80;
+pub const DEFAULT_COMPACTION_KEEP_RECENT_TOOL_OUTPUTS: usize = 4;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
@@ -368,6 +381,384 @@ pub struct FeedbackConfigToml {
     pub enabled: Option<bool>,
 }
 
+/// External hook commands loaded from the `[hooks]` table in config.toml.
+///
+/// Each command receives the JSON hook payload on stdin and may print a JSON
+/// decision (`allow`, `deny`, `modify`, or `ask`) on stdout.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksToml {
+    /// Commands run once when a session starts. A `deny` decision stops the session from starting.
+    #[serde(default)]
+    pub session_start: Vec<HookCommandToml>,
+    /// Commands run once when a session shuts down.
+    #[serde(default)]
+    pub session_end: Vec<HookCommandToml>,
+    /// Commands run before a user turn is sent to the model. A `deny` decision cancels the turn.
+    #[serde(default)]
+    pub before_turn: Vec<HookCommandToml>,
+    /// Commands run before each tool call. They may deny the call, rewrite its input, or
+    /// require an approval prompt.
+    #[serde(default)]
+    pub before_tool_use: Vec<HookCommandToml>,
+    /// Commands run after each tool call completes.
+    #[serde(default)]
+    pub after_tool_use: Vec<HookCommandToml>,
+    /// Commands run after the agent finishes a turn.
+    #[serde(default)]
+    pub after_agent: Vec<HookCommandToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookCommandToml {
+    /// Name used in logs and error messages. Defaults to the program name.
+    pub name: Option<String>,
+    /// Program and arguments to execute.
+    pub command: Vec<String>,
+    /// Maximum time to wait for the command before treating it as failed.
+    pub timeout_ms: Option<u64>,
+    /// When `true`, a spawn failure, timeout, non-zero exit, or malformed output blocks the
+    /// operation instead of letting it continue; a `session_start` hook then fails session
+    /// startup. Not supported for `session_end` hooks. Defaults to `false`.
+    pub fail_closed: Option<bool>,
+}
+
+impl From<HookCommandToml> for CommandHookConfig {
+    fn from(toml: HookCommandToml) -> Self {
+        Self {
+            name: toml.name,
+            argv: toml.command,
+            timeout: toml.timeout_ms.map(Duration::from_millis),
+            fail_closed: toml.fail_closed.unwrap_or(false),
+        }
+    }
+}
+
+impl From<HooksToml> for HooksConfig {
+    fn from(toml: HooksToml) -> Self {
+        fn commands(commands: Vec<HookCommandToml>) -> Vec<CommandHookConfig> {
+            commands.into_iter().map(Into::into).collect()
+        }
+
+        Self {
+            legacy_notify_argv: None,
+            session_start: commands(toml.session_start),
+            session_end: commands(toml.session_end),
+            before_turn: commands(toml.before_turn),
+            before_tool_use: commands(toml.before_tool_use),
+            after_tool_use: commands(toml.after_tool_use),
+            after_agent: commands(toml.after_agent),
+        }
+    }
+}
+
+/// Secret redaction settings loaded from the `[secret_redaction]` table in config.toml.
+///
+/// Detectors scrub tool outputs before they reach the model, recorded events in rollout
+/// files, and memory outputs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretRedactionToml {
+    /// When `false`, tool outputs and rollout files are not redacted. Memory outputs are
+    /// always redacted. Defaults to `true`.
+    pub enabled: Option<bool>,
+    /// Built-in detectors to turn off, by id (for example `bearer_token`).
+    #[serde(default)]
+    pub disable_builtin: Vec<String>,
+    /// Opt-in built-in detectors to turn on, by id: `high_entropy`, or a built-in below
+    /// `min_confidence` such as `secret_assignment`.
+    #[serde(default)]
+    pub enable_builtin: Vec<String>,
+    /// Built-in detectors below this confidence only run when listed in `enable_builtin`.
+    /// Defaults to `high`.
+    pub min_confidence: Option<RedactionConfidence>,
+    /// Additional regex detectors.
+    #[serde(default)]
+    pub detectors: Vec<SecretDetectorToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretDetectorToml {
+    /// Identifier reported when the detector fires.
+    pub id: String,
+    /// Regular expression to redact. When it has a capture group named `secret`, only that
+    /// group is replaced.
+    pub pattern: String,
+    /// Confidence reported for matches. Defaults to `medium`.
+    pub confidence: Option<RedactionConfidence>,
+}
+
+impl From<SecretRedactionToml> for SecretRedactionConfig {
+    fn from(toml: SecretRedactionToml) -> Self {
+        Self {
+            enabled: toml.enabled.unwrap_or(true),
+            disabled_builtins: toml.disable_builtin,
+            enabled_builtins: toml.enable_builtin,
+            min_confidence: toml.min_confidence.unwrap_or(RedactionConfidence::High),
+            custom_detectors: toml
+                .detectors
+                .into_iter()
+                .map(|detector| CustomSecretDetector {
+                    id: detector.id,
+                    pattern: detector.pattern,
+                    confidence: detector.confidence.unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Secrets settings loaded from the `[secrets]` table in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretsToml {
+    /// Where secrets managed by `codex secrets` are stored. Defaults to `local`.
+    pub backend: Option<SecretsBackendKind>,
+    /// Secrets exposed as environment variables to matching shell and unified exec commands.
+    #[serde(default)]
+    pub inject: Vec<SecretInjectionToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretInjectionToml {
+    /// Name of the stored secret (as passed to `codex secrets set`).
+    pub secret: String,
+    /// Environment variable that receives the secret. Defaults to the secret name.
+    pub env_var: Option<String>,
+    /// Command prefixes, such as `gh` or `git push`. The secret is injected when every
+    /// command in the invocation starts with one of them.
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Also inject the secret when an execpolicy rule explicitly allows the command.
+    #[serde(default)]
+    pub execpolicy_allowed: bool,
+}
+
+/// Effective `[secrets]` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SecretsConfig {
+    pub backend: SecretsBackendKind,
+    pub inject: Vec<SecretInjection>,
+}
+
+/// One secret-to-environment-variable mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretInjection {
+    pub secret: SecretName,
+    pub env_var: String,
+    /// Command prefixes, already split into words.
+    pub commands: Vec<Vec<String>>,
+    pub execpolicy_allowed: bool,
+}
+
+impl TryFrom<SecretsToml> for SecretsConfig {
+    type Error = String;
+
+    fn try_from(toml: SecretsToml) -> Result<Self, Self::Error> {
+        let mut inject = Vec::with_capacity(toml.inject.len());
+        for entry in toml.inject {
+            let secret = SecretName::new(&entry.secret)
+                .map_err(|err| format!("secrets.inject `{}`: {err}", entry.secret))?;
+            let env_var = entry.env_var.unwrap_or_else(|| secret.as_str().to_string());
+            if !is_env_var_name(&env_var) {
+                return Err(format!(
+                    "secrets.inject `{secret}`: `{env_var}` is not a valid environment variable name"
+                ));
+            }
+            let commands = entry
+                .commands
+                .iter()
+                .map(|prefix| {
+                    prefix
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            if commands.iter().any(Vec::is_empty) {
+                return Err(format!(
+                    "secrets.inject `{secret}`: command prefixes must not be empty"
+                ));
+            }
+            if commands.is_empty() && !entry.execpolicy_allowed {
+                return Err(format!(
+                    "secrets.inject `{secret}`: set `commands` or `execpolicy_allowed = true`"
+                ));
+            }
+            inject.push(SecretInjection {
+                secret,
+                env_var,
+                commands,
+                execpolicy_allowed: entry.execpolicy_allowed,
+            });
+        }
+        Ok(Self {
+            backend: toml.backend.unwrap_or_default(),
+            inject,
+        })
+    }
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|ch| ch == '_' || ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+/// Guardian settings loaded from the `[guardian]` table in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct GuardianToml {
+    /// `model` (default) asks a reviewer sub-agent; `rules` scores actions locally from
+    /// execpolicy rules and heuristics without a model call.
+    pub backend: Option<GuardianBackend>,
+    /// Actions whose risk score (0-100) is at or above this value are denied. Defaults to 80.
+    /// A project's `.codex/config.toml` can only lower it.
+    #[schemars(range(min = 1, max = 100))]
+    pub approval_threshold: Option<u8>,
+    /// Extra review policy appended to the guardian prompt. Set it in a project's
+    /// `.codex/config.toml` for repository-specific rules.
+    pub instructions: Option<String>,
+}
+
+/// Effective guardian settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardianConfig {
+    pub backend: GuardianBackend,
+    pub approval_threshold: u8,
+    pub instructions: Option<String>,
+}
+
+impl Default for GuardianConfig {
+    fn default() -> Self {
+        Self {
+            backend: GuardianBackend::default(),
+            approval_threshold: DEFAULT_GUARDIAN_APPROVAL_THRESHOLD,
+            instructions: None,
+        }
+    }
+}
+
+impl From<GuardianToml> for GuardianConfig {
+    fn from(toml: GuardianToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            backend: toml.backend.unwrap_or(defaults.backend),
+            approval_threshold: toml
+                .approval_threshold
+                .unwrap_or(defaults.approval_threshold)
+                .clamp(1, 100),
+            instructions: toml
+                .instructions
+                .map(|instructions| instructions.trim().to_string())
+                .filter(|instructions| !instructions.is_empty()),
+        }
+    }
+}
+
+/// Hunk matching settings for `apply_patch`, loaded from the `[apply_patch]`
+/// table in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApplyPatchToml {
+    /// Apply a chunk whose context lines are slightly stale to the closest
+    /// matching region of the file. Lines the chunk removes must still match.
+    /// Defaults to `true`.
+    pub fuzzy_match: Option<bool>,
+    /// Minimum similarity (50-100) a region needs to be fuzzily matched.
+    /// Defaults to 90.
+    #[schemars(range(min = 50, max = 100))]
+    pub fuzzy_threshold_percent: Option<u8>,
+    /// Match `@@` contexts as scope headers (e.g. `@@ fn parse` finds
+    /// `pub fn parse(input: &str)`) and look for the chunk inside that scope
+    /// first. Defaults to `true`.
+    pub anchor_to_scope: Option<bool>,
+}
+
+impl From<ApplyPatchToml> for HunkMatchOptions {
+    fn from(toml: ApplyPatchToml) -> Self {
+        let defaults = Self::default();
+        let fuzzy_threshold = if toml.fuzzy_match.unwrap_or(true) {
+            toml.fuzzy_threshold_percent
+                .map(|percent| f32::from(percent.clamp(50, 100)) / 100.0)
+                .or(defaults.fuzzy_threshold)
+        } else {
+            None
+        };
+        Self {
+            fuzzy_threshold,
+            anchor_to_scope: toml.anchor_to_scope.unwrap_or(defaults.anchor_to_scope),
+        }
+    }
+}
+
+/// Automatic compaction settings loaded from the `[compaction]` table in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CompactionToml {
+    /// Compact once the conversation uses this percentage (1-100) of the model's
+    /// context window. A lower limit set by the model still applies.
+    #[schemars(range(min = 1, max = 100))]
+    pub threshold_percent: Option<u8>,
+    /// Tiers applied in order until the history fits under the threshold. Defaults to
+    /// `["prune_tool_outputs", "drop_reasoning", "summarize"]`; an empty list disables
+    /// automatic compaction.
+    pub tiers: Option<Vec<CompactionTier>>,
+    /// Number of most recent tool outputs left untouched when pruning. Defaults to 4.
+    pub keep_recent_tool_outputs: Option<usize>,
+}
+
+/// Effective automatic compaction settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionConfig {
+    pub threshold_percent: Option<u8>,
+    pub tiers: Vec<CompactionTier>,
+    pub keep_recent_tool_outputs: usize,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            threshold_percent: None,
+            tiers: vec![
+                CompactionTier::PruneToolOutputs,
+                CompactionTier::DropReasoning,
+                CompactionTier::Summarize,
+            ],
+            keep_recent_tool_outputs: DEFAULT_COMPACTION_KEEP_RECENT_TOOL_OUTPUTS,
+        }
+    }
+}
+
+impl From<CompactionToml> for CompactionConfig {
+    fn from(toml: CompactionToml) -> Self {
+        let defaults = Self::default();
+        let tiers = toml.tiers.map_or(defaults.tiers, |tiers| {
+            let mut deduped = Vec::with_capacity(tiers.len());
+            for tier in tiers {
+                if !deduped.contains(&tier) {
+                    deduped.push(tier);
+                }
+            }
+            deduped
+        });
+        Self {
+            threshold_percent: toml
+                .threshold_percent
+                .map(|threshold_percent| threshold_percent.clamp(1, 100)),
+            tiers,
+            keep_recent_tool_outputs: toml
+                .keep_recent_tool_outputs
+                .unwrap_or(defaults.keep_recent_tool_outputs),
+        }
+    }
+}
+
 /// Memories settings loaded from config.toml.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -813,6 +1204,49 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
     }
 }
 
+/// Extra filesystem restrictions applied by the Linux sandbox on top of the
+/// writable roots of the active sandbox policy. Execpolicy `command_rule`s
+/// with a `write_scope` replace them for the commands they match.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxWriteScopeToml {
+    /// Globs that stay read-only inside writable roots, e.g. `.git/hooks`,
+    /// `**/.env*` or `Cargo.lock`. Relative globs are anchored at each
+    /// writable root. Globs are expanded when the command starts: files the
+    /// command creates afterwards are writable even if they match.
+    #[serde(default)]
+    pub read_only: Vec<String>,
+    /// Paths the sandboxed command may not read, e.g. `~/.ssh`.
+    #[serde(default)]
+    pub deny_read: Vec<AbsolutePathBuf>,
+}
+
+/// Named seccomp profiles the Linux sandbox installs for sandboxed commands.
+/// Execpolicy `command_rule`s with `seccomp_profiles` replace them for the
+/// commands they match.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxSeccompToml {
+    /// Profiles applied in every sandbox mode unless `modes` says otherwise.
+    #[serde(default)]
+    pub profiles: Vec<SeccompProfile>,
+    /// Per sandbox mode replacements for `profiles`.
+    pub modes: Option<SandboxSeccompModesToml>,
+    /// Programs a command may execute under
+    /// `no-process-spawn-outside-allowlist`, besides the command itself.
+    #[serde(default)]
+    pub spawn_allowlist: Vec<AbsolutePathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxSeccompModesToml {
+    /// Profiles for `read-only` sessions.
+    pub read_only: Option<Vec<SeccompProfile>>,
+    /// Profiles for `workspace-write` sessions.
+    pub workspace_write: Option<Vec<SeccompProfile>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShellEnvironmentPolicyInherit {
@@ -159,7 +159,7 @@ impl EnvironmentContext {
             lines.push(format!("  <cwd>{}</cwd>", cwd.to_string_lossy()));
         }
 
-        let shell_name = self.shell.name();
+        let shell_name = self.shell.command_shell().name();
         lines.push(format!("  <shell>{shell_name}</shell>"));
         if let Some(current_date) = self.current_date {
             lines.push(format!("  <current_date>{current_date}</current_date>"));
@@ -1,5 +1,6 @@
 use crate::exec::ExecToolCallOutput;
 use crate::network_policy_decision::NetworkPolicyDecisionPayload;
+use crate::sandboxing::seccomp::SeccompDenial;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use crate::truncate::TruncationPolicy;
@@ -35,6 +36,8 @@ pub enum SandboxErr {
     Denied {
         output: Box<ExecToolCallOutput>,
         network_policy_decision: Option<NetworkPolicyDecisionPayload>,
+        /// Set when the command was killed by a Linux seccomp profile.
+        seccomp_denial: Option<SeccompDenial>,
     },
 
     /// Error from linux seccomp filter setup
@@ -741,6 +744,7 @@ mod tests {
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
             network_policy_decision: None,
+            seccomp_denial: None,
         });
         assert_eq!(get_error_message_ui(&err), "aggregate detail");
     }
@@ -758,6 +762,7 @@ mod tests {
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
             network_policy_decision: None,
+            seccomp_denial: None,
         });
         assert_eq!(get_error_message_ui(&err), "stderr detail\nstdout detail");
     }
@@ -775,6 +780,7 @@ mod tests {
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
             network_policy_decision: None,
+            seccomp_denial: None,
         });
         assert_eq!(get_error_message_ui(&err), "stdout only");
     }
@@ -819,6 +825,7 @@ mod tests {
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
             network_policy_decision: None,
+            seccomp_denial: None,
         });
         assert_eq!(
             get_error_message_ui(&err),
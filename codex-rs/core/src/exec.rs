@@ -6,6 +6,7 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -28,6 +29,9 @@ use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::seccomp::SeccompDenial;
+use crate::secret_redaction::log_redactions;
+use crate::secret_redaction::redact_output_chunk;
 use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
@@ -37,6 +41,8 @@ use codex_network_proxy::NetworkProxy;
 use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::SeccompProfile;
+use codex_secrets::SecretRedactor;
 use codex_utils_pty::DEFAULT_OUTPUT_BYTES_CAP;
 use codex_utils_pty::process_group::kill_child_process_group;
 
@@ -46,7 +52,7 @@ pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 // for these.
 const SIGKILL_CODE: i32 = 9;
 const TIMEOUT_CODE: i32 = 64;
-const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
+pub(crate) const EXIT_CODE_SIGNAL_BASE: i32 = 128; // conventional shell: 128 + signal
 const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
 
 // I/O buffer sizing
@@ -175,6 +181,9 @@ pub struct StdoutStream {
     pub sub_id: String,
     pub call_id: String,
     pub tx_event: Sender<Event>,
+    /// Scrubs injected secret values from each streamed chunk. A value split
+    /// across two reads is only caught in the final output.
+    pub output_redactor: Option<Arc<SecretRedactor>>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -253,6 +262,7 @@ pub fn build_exec_request(
         sandbox_permissions,
         additional_permissions: None,
         justification,
+        exec_policy_command: None,
     };
 
     let manager = SandboxManager::new();
@@ -270,6 +280,9 @@ pub fn build_exec_request(
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
+            exec_policy: None,
+            write_scope: None,
+            seccomp: None,
             windows_sandbox_level,
         })
         .map_err(CodexErr::from)?;
@@ -296,6 +309,7 @@ pub(crate) async fn execute_exec_request(
         network_sandbox_policy,
         justification,
         arg0,
+        seccomp_profiles,
     } = exec_request;
     let _ = _sandbox_policy_from_env;
 
@@ -323,7 +337,7 @@ pub(crate) async fn execute_exec_request(
     )
     .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    finalize_exec_result(raw_output_result, sandbox, &seccomp_profiles, duration)
 }
 
 #[cfg(target_os = "windows")]
@@ -508,25 +522,35 @@ async fn exec_windows_sandbox(
 fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
+    seccomp_profiles: &[SeccompProfile],
     duration: Duration,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
 
             #[cfg(target_family = "unix")]
             {
                 if let Some(signal) = raw_output.exit_status.signal() {
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
+                    } else if signal == libc::SIGSYS
+                        && sandbox_type == SandboxType::LinuxSeccomp
+                        && seccomp_profiles
+                            .iter()
+                            .any(|profile| profile.kills_on_violation())
+                    {
+                        // Killed by a seccomp profile; report it the way a
+                        // shell would so it is classified as a denial below.
+                        exit_code = EXIT_CODE_SIGNAL_BASE + signal;
                     } else {
                         return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
                     }
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
@@ -534,7 +558,7 @@ fn finalize_exec_result(
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
             let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
-            let exec_output = ExecToolCallOutput {
+            let mut exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
                 stderr,
@@ -549,10 +573,22 @@ fn finalize_exec_result(
                 }));
             }
 
+            if let Some(seccomp_denial) =
+                SeccompDenial::from_output(sandbox_type, seccomp_profiles, &exec_output)
+            {
+                seccomp_denial.append_to_output(&mut exec_output);
+                return Err(CodexErr::Sandbox(SandboxErr::Denied {
+                    output: Box::new(exec_output),
+                    network_policy_decision: None,
+                    seccomp_denial: Some(seccomp_denial),
+                }));
+            }
+
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
                     network_policy_decision: None,
+                    seccomp_denial: None,
                 }));
             }
 
@@ -579,6 +615,10 @@ pub(crate) mod errors {
                 SandboxTransformError::SeatbeltUnavailable => CodexErr::UnsupportedOperation(
                     "seatbelt sandbox is only available on macOS".to_string(),
                 ),
+                err @ (SandboxTransformError::WriteScopeRequiresBwrap
+                | SandboxTransformError::UnknownSeccompProfile(_)) => {
+                    CodexErr::UnsupportedOperation(err.to_string())
+                }
             }
         }
     }
@@ -959,7 +999,11 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
         {
-            let chunk = tmp[..n].to_vec();
+            let mut chunk = tmp[..n].to_vec();
+            if let Some(redactor) = &stream.output_redactor {
+                let findings = redact_output_chunk(redactor, &mut chunk);
+                log_redactions("exec_output", &findings);
+            }
             let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
                 call_id: stream.call_id.clone(),
                 stream: if is_stderr {
@@ -1,12 +1,23 @@
+use crate::bash::parse_shell_lc_plain_commands;
 use crate::config::types::EnvironmentVariablePattern;
+use crate::config::types::SecretsConfig;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
 use codex_protocol::ThreadId;
+use codex_secrets::LiteralSecretDetector;
+use codex_secrets::SecretRedactor;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
 
 pub const CODEX_THREAD_ID_ENV_VAR: &str = "CODEX_THREAD_ID";
 
+/// Detector id reported when an injected secret value is scrubbed from command output.
+const INJECTED_SECRET_DETECTOR_ID: &str = "injected_secret";
+
 /// Construct an environment map based on the rules in the specified policy. The
 /// resulting map can be passed directly to `Command::envs()` after calling
 /// `env_clear()` to ensure no unintended variables are leaked to the spawned
@@ -93,6 +104,127 @@ where
     env_map
 }
 
+/// A stored secret resolved for injection into command environments. Deliberately not
+/// `Debug` so the value cannot end up in logs.
+#[derive(Clone)]
+struct InjectedSecret {
+    env_var: String,
+    value: String,
+    commands: Vec<Vec<String>>,
+    execpolicy_allowed: bool,
+}
+
+/// Injects the secrets configured under `[[secrets.inject]]` into the environment of
+/// matching commands at spawn time, and scrubs their values from captured output.
+///
+/// Secret values are resolved once per session so the keyring is not queried for every
+/// command.
+#[derive(Default)]
+pub(crate) struct SecretEnvInjector {
+    secrets: Vec<InjectedSecret>,
+    output_redactor: Option<Arc<SecretRedactor>>,
+}
+
+impl SecretEnvInjector {
+    /// Resolves every configured secret, preferring the environment scope derived from
+    /// `cwd` over the global scope. Missing or unreadable secrets are skipped with a warning.
+    pub(crate) fn load(config: &SecretsConfig, codex_home: &Path, cwd: &Path) -> Self {
+        if config.inject.is_empty() {
+            return Self::default();
+        }
+        let manager = SecretsManager::new(codex_home.to_path_buf(), config.backend);
+        let environment_id = environment_id_from_cwd(cwd);
+        let mut secrets = Vec::new();
+        for injection in &config.inject {
+            match manager.resolve(&environment_id, &injection.secret) {
+                Ok(Some(value)) => secrets.push(InjectedSecret {
+                    env_var: injection.env_var.clone(),
+                    value,
+                    commands: injection.commands.clone(),
+                    execpolicy_allowed: injection.execpolicy_allowed,
+                }),
+                Ok(None) => tracing::warn!(
+                    "secret {} is configured for injection but not set; run `codex secrets set {}`",
+                    injection.secret,
+                    injection.secret
+                ),
+                Err(err) => tracing::warn!("failed to read secret {}: {err}", injection.secret),
+            }
+        }
+        Self::new(secrets)
+    }
+
+    fn new(secrets: Vec<InjectedSecret>) -> Self {
+        let detector = LiteralSecretDetector::new(
+            INJECTED_SECRET_DETECTOR_ID,
+            secrets.iter().map(|secret| secret.value.clone()),
+        );
+        let output_redactor = (!detector.is_empty()).then(|| {
+            let mut redactor = SecretRedactor::empty();
+            redactor.register(detector);
+            Arc::new(redactor)
+        });
+        Self {
+            secrets,
+            output_redactor,
+        }
+    }
+
+    /// Adds the secrets that apply to `command` to `env` and returns the names of the
+    /// injected variables. A secret applies when every command in the invocation (after
+    /// unwrapping `bash -lc` scripts) starts with one of its prefixes, or when
+    /// `allowed_by_execpolicy` is set and the secret opted into execpolicy matching.
+    pub(crate) fn inject(
+        &self,
+        command: &[String],
+        allowed_by_execpolicy: bool,
+        env: &mut HashMap<String, String>,
+    ) -> Vec<String> {
+        if self.secrets.is_empty() {
+            return Vec::new();
+        }
+        let commands =
+            parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+        let mut injected = Vec::new();
+        for secret in &self.secrets {
+            let prefix_match = !commands.is_empty()
+                && commands.iter().all(|command| {
+                    secret
+                        .commands
+                        .iter()
+                        .any(|prefix| command.starts_with(prefix))
+                });
+            if prefix_match || (secret.execpolicy_allowed && allowed_by_execpolicy) {
+                env.insert(secret.env_var.clone(), secret.value.clone());
+                injected.push(secret.env_var.clone());
+            }
+        }
+        injected
+    }
+
+    /// Redactor that scrubs injected secret values, if any secret was resolved.
+    pub(crate) fn output_redactor(&self) -> Option<&Arc<SecretRedactor>> {
+        self.output_redactor.as_ref()
+    }
+
+    /// An injector whose secrets apply to no command, for tests that only need
+    /// the values scrubbed.
+    #[cfg(test)]
+    pub(crate) fn scrubbing_for_testing(values: &[&str]) -> Self {
+        Self::new(
+            values
+                .iter()
+                .map(|value| InjectedSecret {
+                    env_var: String::new(),
+                    value: (*value).to_string(),
+                    commands: Vec::new(),
+                    execpolicy_allowed: false,
+                })
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,4 +442,72 @@ mod tests {
         expected.insert(CODEX_THREAD_ID_ENV_VAR.to_string(), thread_id.to_string());
         assert_eq!(result, expected);
     }
+
+    fn gh_token_injector() -> SecretEnvInjector {
+        SecretEnvInjector::new(vec![
+            InjectedSecret {
+                env_var: "GH_TOKEN".to_string(),
+                value: "ghp_injected_value".to_string(),
+                commands: vec![
+                    vec!["gh".to_string()],
+                    vec!["git".to_string(), "push".to_string()],
+                ],
+                execpolicy_allowed: false,
+            },
+            InjectedSecret {
+                env_var: "NPM_TOKEN".to_string(),
+                value: "npm_injected_value".to_string(),
+                commands: Vec::new(),
+                execpolicy_allowed: true,
+            },
+        ])
+    }
+
+    fn vec_str(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn secrets_are_injected_only_for_matching_commands() {
+        let injector = gh_token_injector();
+
+        let mut env = HashMap::new();
+        let injected = injector.inject(
+            &vec_str(&["bash", "-lc", "gh pr list && git push origin main"]),
+            false,
+            &mut env,
+        );
+        assert_eq!(injected, vec!["GH_TOKEN".to_string()]);
+        assert_eq!(
+            env,
+            hashmap! { "GH_TOKEN".to_string() => "ghp_injected_value".to_string() }
+        );
+
+        let mut env = HashMap::new();
+        let injected = injector.inject(
+            &vec_str(&["bash", "-lc", "gh pr list && curl https://example.com"]),
+            false,
+            &mut env,
+        );
+        assert_eq!(injected, Vec::<String>::new());
+        assert_eq!(env, HashMap::new());
+    }
+
+    #[test]
+    fn execpolicy_allowed_secrets_follow_the_policy_decision() {
+        let injector = gh_token_injector();
+
+        let mut env = HashMap::new();
+        let injected = injector.inject(&vec_str(&["npm", "publish"]), true, &mut env);
+
+        assert_eq!(injected, vec!["NPM_TOKEN".to_string()]);
+        assert_eq!(
+            injector
+                .output_redactor()
+                .expect("secrets were resolved")
+                .redact("published with npm_injected_value")
+                .redacted,
+            "published with [REDACTED_SECRET]"
+        );
+    }
 }
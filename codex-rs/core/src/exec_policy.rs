@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
@@ -23,10 +24,13 @@ use codex_execpolicy::blocking_append_network_rule;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use thiserror::Error;
 use tokio::fs;
 use tokio::task::spawn_blocking;
 
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_commands_with_redirects;
 use crate::bash::parse_shell_lc_plain_commands;
 use crate::bash::parse_shell_lc_single_command_prefix;
 use crate::sandboxing::SandboxPermissions;
@@ -93,7 +97,7 @@ static BANNED_PREFIX_SUGGESTIONS: &[&[&str]] = &[
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
-        RuleMatch::PrefixRuleMatch { .. } => true,
+        RuleMatch::PrefixRuleMatch { .. } | RuleMatch::CommandRuleMatch { .. } => true,
         RuleMatch::HeuristicsRuleMatch { .. } => false,
     }
 }
@@ -175,6 +179,10 @@ pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
     pub(crate) prefix_rule: Option<Vec<String>>,
+    /// Working directory of the command, used by path-aware `command_rule`s.
+    pub(crate) cwd: Option<&'a Path>,
+    /// Environment the command will run with, used by env-aware `command_rule`s.
+    pub(crate) env: Option<&'a HashMap<String, String>>,
 }
 
 impl ExecPolicyManager {
@@ -206,9 +214,41 @@ impl ExecPolicyManager {
             sandbox_policy,
             sandbox_permissions,
             prefix_rule,
+            cwd,
+            env,
         } = req;
         let exec_policy = self.current();
-        let (commands, used_complex_parsing) = commands_for_exec_policy(command);
+        let (mut commands, redirects, mut used_complex_parsing) =
+            commands_and_redirects_for_exec_policy(command);
+        let match_options = MatchOptions {
+            resolve_host_executables: true,
+            cwd: cwd.and_then(|cwd| AbsolutePathBuf::from_absolute_path(cwd).ok()),
+            workspace_roots: cwd
+                .map(|cwd| exec_policy_workspace_roots(sandbox_policy, cwd))
+                .unwrap_or_default(),
+            redirects,
+            env: env.cloned(),
+        };
+        // Scripts with redirections are split into their commands only so
+        // `command_rule(redirects=...)` can judge the targets; unless such a
+        // rule matches, judge the script as a whole.
+        let redirect_rule_matches = if used_complex_parsing && match_options.redirects.is_some() {
+            redirect_rule_matches(exec_policy.as_ref(), &commands, &match_options)
+        } else {
+            Vec::new()
+        };
+        if used_complex_parsing
+            && match_options.redirects.is_some()
+            && redirect_rule_matches.is_empty()
+        {
+            commands = vec![command.to_vec()];
+            used_complex_parsing = false;
+        }
+        let has_split_redirects = used_complex_parsing
+            && match_options
+                .redirects
+                .as_ref()
+                .is_some_and(|redirects| !redirects.is_empty());
         // Keep heredoc prefix parsing for rule evaluation so existing
         // allow/prompt/forbidden rules still apply, but avoid auto-derived
         // amendments when only the heredoc fallback parser matched.
@@ -222,9 +262,6 @@ impl ExecPolicyManager {
                 used_complex_parsing,
             )
         };
-        let match_options = MatchOptions {
-            resolve_host_executables: true,
-        };
         let evaluation = exec_policy.check_multiple_with_options(
             commands.iter(),
             &exec_policy_fallback,
@@ -266,17 +303,37 @@ impl ExecPolicyManager {
                     },
                 }
             }
-            Decision::Allow => ExecApprovalRequirement::Skip {
-                // Bypass sandbox if execpolicy allows the command
-                bypass_sandbox: evaluation.matched_rules.iter().any(|rule_match| {
+            Decision::Allow => {
+                let mut allow_matches = evaluation.matched_rules.iter().filter(|rule_match| {
                     is_policy_match(rule_match) && rule_match.decision() == Decision::Allow
-                }),
-                proposed_execpolicy_amendment: if auto_amendment_allowed {
-                    try_derive_execpolicy_amendment_for_allow_rules(&evaluation.matched_rules)
+                });
+                // Bypass sandbox if execpolicy allows the command. A script that
+                // writes through redirections only leaves the sandbox when every
+                // allowing rule vetted those redirections, and a command that a
+                // rule gives its own sandbox settings never leaves it.
+                let has_sandbox_override = evaluation
+                    .matched_rules
+                    .iter()
+                    .any(|rule_match| rule_match.sandbox_override().is_some());
+                let bypass_sandbox = if has_sandbox_override {
+                    false
+                } else if has_split_redirects {
+                    let mut allow_matches = allow_matches.peekable();
+                    allow_matches.peek().is_some()
+                        && allow_matches
+                            .all(|rule_match| redirect_rule_matches.contains(rule_match))
                 } else {
-                    None
-                },
-            },
+                    allow_matches.next().is_some()
+                };
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox,
+                    proposed_execpolicy_amendment: if auto_amendment_allowed {
+                        try_derive_execpolicy_amendment_for_allow_rules(&evaluation.matched_rules)
+                    } else {
+                        None
+                    },
+                }
+            }
         }
     }
 
@@ -569,18 +626,79 @@ fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
 
+/// The cwd plus any writable roots granted by the sandbox policy count as the workspace for
+/// `command_rule(paths=..., redirects=...)` conditions.
+pub(crate) fn exec_policy_workspace_roots(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Vec<AbsolutePathBuf> {
+    let mut roots: Vec<AbsolutePathBuf> = AbsolutePathBuf::from_absolute_path(cwd)
+        .ok()
+        .into_iter()
+        .collect();
+    for writable_root in sandbox_policy.get_writable_roots_with_cwd(cwd) {
+        if !roots.contains(&writable_root.root) {
+            roots.push(writable_root.root);
+        }
+    }
+    roots
+}
+
+/// Rule matches on `commands` that only hold because of the script's
+/// redirection targets, i.e. `command_rule`s with a `redirects` condition.
+fn redirect_rule_matches(
+    policy: &Policy,
+    commands: &[Vec<String>],
+    match_options: &MatchOptions,
+) -> Vec<RuleMatch> {
+    let without_redirects = MatchOptions {
+        redirects: None,
+        ..match_options.clone()
+    };
+    let unconstrained = policy
+        .check_multiple_with_options(commands.iter(), &|_| Decision::Prompt, &without_redirects)
+        .matched_rules;
+    policy
+        .check_multiple_with_options(commands.iter(), &|_| Decision::Prompt, match_options)
+        .matched_rules
+        .into_iter()
+        .filter(|rule_match| is_policy_match(rule_match) && !unconstrained.contains(rule_match))
+        .collect()
+}
+
+#[cfg(test)]
 fn commands_for_exec_policy(command: &[String]) -> (Vec<Vec<String>>, bool) {
+    let (commands, _, used_complex_parsing) = commands_and_redirects_for_exec_policy(command);
+    (commands, used_complex_parsing)
+}
+
+/// Like [`commands_for_exec_policy`], but also returns the redirection targets
+/// for `command_rule(redirects=...)`. Targets are `None` when they are unknown,
+/// as for heredoc scripts and shell scripts that could not be parsed.
+pub(crate) fn commands_and_redirects_for_exec_policy(
+    command: &[String],
+) -> (Vec<Vec<String>>, Option<Vec<String>>, bool) {
     if let Some(commands) = parse_shell_lc_plain_commands(command)
         && !commands.is_empty()
     {
-        return (commands, false);
+        return (commands, Some(Vec::new()), false);
+    }
+
+    // Redirections keep a script from counting as plain, so it is never
+    // auto-approved as known-safe, but its commands and targets are still
+    // evaluated against the rules.
+    if let Some((commands, redirects)) = parse_shell_lc_commands_with_redirects(command)
+        && !commands.is_empty()
+    {
+        return (commands, Some(redirects), true);
     }
 
     if let Some(single_command) = parse_shell_lc_single_command_prefix(command) {
-        return (vec![single_command], true);
+        return (vec![single_command], None, true);
     }
 
-    (vec![command.to_vec()], false)
+    let redirects = extract_bash_command(command).is_none().then(Vec::new);
+    (vec![command.to_vec()], redirects, false)
 }
 
 /// Derive a proposed execpolicy amendment when a command requires user approval
@@ -712,6 +830,12 @@ fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Opt
                 decision: Decision::Prompt,
                 justification,
                 ..
+            }
+            | RuleMatch::CommandRuleMatch {
+                matched_prefix,
+                decision: Decision::Prompt,
+                justification,
+                ..
             } => Some((matched_prefix.len(), justification.as_deref())),
             _ => None,
         })
@@ -747,6 +871,12 @@ fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) ->
                 decision: Decision::Forbidden,
                 justification,
                 ..
+            }
+            | RuleMatch::CommandRuleMatch {
+                matched_prefix,
+                decision: Decision::Forbidden,
+                justification,
+                ..
             } => Some((matched_prefix, justification.as_deref())),
             _ => None,
         })
@@ -1236,6 +1366,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1265,6 +1397,251 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         assert_eq!(commands_for_exec_policy(&command), (vec![command], false));
     }
 
+    #[tokio::test]
+    async fn command_rule_paths_resolve_against_command_cwd() {
+        let policy_src = r#"
+command_rule(
+    pattern = ["rm"],
+    paths = "outside_workspace",
+    decision = "forbidden",
+    justification = "only delete files inside the workspace",
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let cwd = tempdir().expect("create temp dir");
+        let outside = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "rm -rf ../other".to_string(),
+        ];
+        let inside = vec!["rm".to_string(), "-rf".to_string(), "build".to_string()];
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &outside,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::DangerFullAccess,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+                cwd: Some(cwd.path()),
+                env: None,
+            })
+            .await;
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason:
+                    "`bash -lc 'rm -rf ../other'` rejected: only delete files inside the workspace"
+                        .to_string()
+            }
+        );
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &inside,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::DangerFullAccess,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+                cwd: Some(cwd.path()),
+                env: None,
+            })
+            .await;
+        assert!(
+            !matches!(requirement, ExecApprovalRequirement::Forbidden { .. }),
+            "{requirement:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn command_rule_redirects_see_shell_redirect_targets() {
+        let policy_src = r#"
+command_rule(
+    pattern = ["echo"],
+    redirects = "outside_workspace",
+    decision = "forbidden",
+    justification = "only write files inside the workspace",
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let cwd = tempdir().expect("create temp dir");
+        let request = |command| ExecApprovalRequest {
+            command,
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: &SandboxPolicy::DangerFullAccess,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+            cwd: Some(cwd.path()),
+            env: None,
+        };
+        let outside = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "echo x > ../out".to_string(),
+        ];
+        let inside = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "echo x > out 2>&1".to_string(),
+        ];
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(request(&outside))
+            .await;
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason:
+                    "`bash -lc 'echo x > ../out'` rejected: only write files inside the workspace"
+                        .to_string()
+            }
+        );
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(request(&inside))
+            .await;
+        assert!(
+            !matches!(requirement, ExecApprovalRequirement::Forbidden { .. }),
+            "{requirement:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn prefix_rule_allow_does_not_approve_redirected_writes() {
+        let policy_src = r#"prefix_rule(pattern=["git", "status"], decision="allow")"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let cwd = tempdir().expect("create temp dir");
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "git status > ~/.bashrc".to_string(),
+        ];
+
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &command,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::new_read_only_policy(),
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+                cwd: Some(cwd.path()),
+                env: None,
+            })
+            .await;
+        assert!(
+            !matches!(
+                requirement,
+                ExecApprovalRequirement::Skip {
+                    bypass_sandbox: true,
+                    ..
+                }
+            ),
+            "{requirement:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_redirect_rules_lift_the_sandbox_for_redirected_scripts() {
+        let policy_src = r#"
+prefix_rule(pattern=["git", "status"], decision="allow")
+command_rule(
+    pattern = ["echo"],
+    redirects = "inside_workspace",
+    decision = "allow",
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let cwd = tempdir().expect("create temp dir");
+        let request = |command| ExecApprovalRequest {
+            command,
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: &SandboxPolicy::DangerFullAccess,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+            cwd: Some(cwd.path()),
+            env: None,
+        };
+        let vetted = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "echo x > out".to_string(),
+        ];
+        let chained = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "echo x > out && git status".to_string(),
+        ];
+
+        assert_eq!(
+            manager
+                .create_exec_approval_requirement_for_command(request(&vetted))
+                .await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: true,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+        assert_eq!(
+            manager
+                .create_exec_approval_requirement_for_command(request(&chained))
+                .await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rules_with_sandbox_settings_keep_allowed_commands_sandboxed() {
+        let policy_src = r#"
+command_rule(
+    pattern = ["cargo", "update"],
+    write_scope = {"read_only": [".git/hooks"]},
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let command = vec!["cargo".to_string(), "update".to_string()];
+
+        assert_eq!(
+            manager
+                .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                    command: &command,
+                    approval_policy: AskForApproval::OnRequest,
+                    sandbox_policy: &SandboxPolicy::new_workspace_write_policy(),
+                    sandbox_permissions: SandboxPermissions::UseDefault,
+                    prefix_rule: None,
+                    cwd: None,
+                    env: None,
+                })
+                .await,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn evaluates_heredoc_script_against_prefix_rules() {
         let policy_src = r#"prefix_rule(pattern=["python3"], decision="allow")"#;
@@ -1286,6 +1663,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1313,6 +1692,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1341,6 +1722,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: Some(requested_prefix.clone()),
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1380,6 +1763,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1409,6 +1794,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1445,6 +1832,8 @@ prefix_rule(pattern=["git"], decision="allow")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1487,6 +1876,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1515,6 +1906,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1548,6 +1941,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1595,6 +1990,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1631,6 +2028,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1665,6 +2064,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1688,6 +2089,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1712,6 +2115,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1740,6 +2145,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1768,6 +2175,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1799,6 +2208,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1837,6 +2248,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd: None,
+                    env: None,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1911,6 +2324,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1941,6 +2356,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -1968,6 +2385,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -2006,6 +2425,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd: None,
+                    env: None,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -2029,6 +2450,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -2059,6 +2482,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -2232,6 +2657,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                env: None,
             })
             .await;
 
@@ -2298,6 +2725,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    env: None,
                 })
                 .await,
             "{pwsh_approval_reason}"
@@ -2321,6 +2750,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    env: None,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -2340,6 +2771,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    env: None,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
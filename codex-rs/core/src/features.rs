@@ -120,6 +120,11 @@ pub enum Feature {
     Sqlite,
     /// Enable startup memory extraction and file-backed memory consolidation.
     MemoryTool,
+    /// Expose the `code_search` tool backed by a tree-sitter symbol index.
+    CodeSearch,
+    /// Let `exec_command` start jobs that are logged to disk and survive
+    /// session resume.
+    PersistentJobs,
     /// Append additional AGENTS.md guidance to user instructions.
     ChildAgentsMd,
     /// Allow `detail: "original"` image outputs on supported models.
@@ -557,6 +562,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::CodeSearch,
+        key: "code_search",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PersistentJobs,
+        key: "persistent_jobs",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ChildAgentsMd,
         key: "child_agents_md",
@@ -8,16 +8,22 @@
 //!    return strict JSON.
 //!    The guardian clones the parent config, so it inherits any managed
 //!    network proxy / allowlist that the parent turn already had.
+//!    With `guardian.backend = "rules"`, score the action locally from
+//!    execpolicy rules and heuristics instead (see `guardian_rules`).
 //! 3. Fail closed on timeout, execution failure, or malformed output.
-//! 4. Approve only low- and medium-risk actions (`risk_score < 80`).
+//! 4. Approve only actions below `guardian.approval_threshold` (80 by default)
+//!    and record every decision in the rollout as a `GuardianAssessment` event.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use codex_protocol::config_types::GuardianBackend;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::GuardianAssessmentEvent;
+use codex_protocol::protocol::GuardianRiskLevel;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::WarningEvent;
 use codex_protocol::user_input::UserInput;
@@ -58,8 +64,6 @@ const GUARDIAN_MAX_MESSAGE_ENTRY_TOKENS: usize = 2_000;
 // payloads are often verbose and lower-signal than the human conversation.
 const GUARDIAN_MAX_TOOL_ENTRY_TOKENS: usize = 1_000;
 const GUARDIAN_MAX_ACTION_STRING_TOKENS: usize = 1_000;
-// Fail closed for scores at or above this threshold.
-const GUARDIAN_APPROVAL_RISK_THRESHOLD: u8 = 80;
 // Always keep some recent non-user context so the reviewer can see what the
 // agent was trying to do immediately before the escalation.
 const GUARDIAN_RECENT_ENTRY_LIMIT: usize = 40;
@@ -95,29 +99,20 @@ pub(crate) struct GuardianReviewRequest {
     pub(crate) action: Value,
 }
 
-/// Coarse risk label paired with the numeric `risk_score`.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum GuardianRiskLevel {
-    Low,
-    Medium,
-    High,
-}
-
 /// Evidence item returned by the guardian subagent.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct GuardianEvidence {
-    message: String,
-    why: String,
+    pub(crate) message: String,
+    pub(crate) why: String,
 }
 
 /// Structured output contract that the guardian subagent must satisfy.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct GuardianAssessment {
-    risk_level: GuardianRiskLevel,
-    risk_score: u8,
-    rationale: String,
-    evidence: Vec<GuardianEvidence>,
+    pub(crate) risk_level: GuardianRiskLevel,
+    pub(crate) risk_score: u8,
+    pub(crate) rationale: String,
+    pub(crate) evidence: Vec<GuardianEvidence>,
 }
 
 /// Transcript entry retained for guardian review after filtering.
@@ -174,6 +169,63 @@ async fn run_guardian_review(
         )
         .await;
 
+    let backend = turn.config.guardian.backend;
+    let action = request.action.clone();
+    let assessment = match backend {
+        GuardianBackend::Model => {
+            run_guardian_model_review(session.clone(), turn.clone(), request, retry_reason).await
+        }
+        GuardianBackend::Rules => {
+            let exec_policy = session.services.exec_policy.current();
+            crate::guardian_rules::assess_action(&request.action, exec_policy.as_ref())
+        }
+    };
+
+    let approval_threshold = turn.config.guardian.approval_threshold;
+    let approved = assessment.risk_score < approval_threshold;
+    let verdict = if approved { "approved" } else { "denied" };
+    // Emit a concise warning so the parent turn has an auditable summary of the
+    // guardian decision without needing the full subagent transcript.
+    let warning = format!(
+        "Guardian {verdict} approval request ({}/100, {}): {}",
+        assessment.risk_score, assessment.risk_level, assessment.rationale
+    );
+    session
+        .send_event(
+            turn.as_ref(),
+            EventMsg::Warning(WarningEvent { message: warning }),
+        )
+        .await;
+    session
+        .send_event(
+            turn.as_ref(),
+            EventMsg::GuardianAssessment(GuardianAssessmentEvent {
+                backend,
+                approved,
+                risk_level: assessment.risk_level,
+                risk_score: assessment.risk_score,
+                approval_threshold,
+                rationale: assessment.rationale,
+                action,
+            }),
+        )
+        .await;
+
+    if approved {
+        ReviewDecision::Approved
+    } else {
+        ReviewDecision::Denied
+    }
+}
+
+/// Runs the guardian subagent and converts timeouts and failures into a
+/// high-risk assessment.
+async fn run_guardian_model_review(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    request: GuardianReviewRequest,
+    retry_reason: Option<String>,
+) -> GuardianAssessment {
     let prompt_items = build_guardian_prompt_items(session.as_ref(), retry_reason, request).await;
     let schema = guardian_output_schema();
     let cancel_token = CancellationToken::new();
@@ -194,7 +246,7 @@ async fn run_guardian_review(
         }
     };
 
-    let assessment = match review {
+    match review {
         Some(Ok(assessment)) => assessment,
         Some(Err(err)) => GuardianAssessment {
             risk_level: GuardianRiskLevel::High,
@@ -209,29 +261,6 @@ async fn run_guardian_review(
                 .to_string(),
             evidence: vec![],
         },
-    };
-
-    let approved = assessment.risk_score < GUARDIAN_APPROVAL_RISK_THRESHOLD;
-    let verdict = if approved { "approved" } else { "denied" };
-    // Emit a concise warning so the parent turn has an auditable summary of the
-    // guardian decision without needing the full subagent transcript.
-    let warning = format!(
-        "Guardian {verdict} approval request ({}/100, {}): {}",
-        assessment.risk_score,
-        assessment.risk_level.as_str(),
-        assessment.rationale
-    );
-    session
-        .send_event(
-            turn.as_ref(),
-            EventMsg::Warning(WarningEvent { message: warning }),
-        )
-        .await;
-
-    if approved {
-        ReviewDecision::Approved
-    } else {
-        ReviewDecision::Denied
     }
 }
 
@@ -612,7 +641,9 @@ fn build_guardian_subagent_config(
     let mut guardian_config = parent_config.clone();
     guardian_config.model = Some(active_model.to_string());
     guardian_config.model_reasoning_effort = reasoning_effort;
-    guardian_config.developer_instructions = Some(guardian_policy_prompt());
+    guardian_config.developer_instructions = Some(guardian_policy_prompt(
+        parent_config.guardian.instructions.as_deref(),
+    ));
     guardian_config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
     guardian_config.permissions.sandbox_policy =
         Constrained::allow_only(SandboxPolicy::new_read_only_policy());
@@ -817,19 +848,17 @@ fn guardian_output_contract_prompt() -> &'static str {
 ///
 /// Keep the prompt in a dedicated markdown file so reviewers can audit prompt
 /// changes directly without diffing through code. The output contract is
-/// appended from code so it stays near `guardian_output_schema()`.
-fn guardian_policy_prompt() -> String {
+/// appended from code so it stays near `guardian_output_schema()`. Per-repo
+/// `guardian.instructions` go between the two so they cannot displace the
+/// output contract.
+fn guardian_policy_prompt(instructions: Option<&str>) -> String {
     let prompt = include_str!("guardian_prompt.md").trim_end();
-    format!("{prompt}\n\n{}\n", guardian_output_contract_prompt())
-}
-
-impl GuardianRiskLevel {
-    fn as_str(self) -> &'static str {
-        match self {
-            GuardianRiskLevel::Low => "low",
-            GuardianRiskLevel::Medium => "medium",
-            GuardianRiskLevel::High => "high",
-        }
+    let contract = guardian_output_contract_prompt();
+    match instructions {
+        Some(instructions) => format!(
+            "{prompt}\n\n# Additional instructions for this repository\n\n{instructions}\n\n{contract}\n"
+        ),
+        None => format!("{prompt}\n\n{contract}\n"),
     }
 }
 
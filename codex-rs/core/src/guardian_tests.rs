@@ -464,3 +464,32 @@ fn guardian_subagent_config_uses_parent_active_model_instead_of_hardcoded_slug()
 
     assert_eq!(guardian_config.model, Some("active-model".to_string()));
 }
+
+#[test]
+fn guardian_subagent_config_appends_repo_instructions_before_output_contract() {
+    let mut parent_config = test_config();
+    parent_config.guardian.instructions = Some("Never approve writes under deploy/.".to_string());
+
+    let guardian_config =
+        build_guardian_subagent_config(&parent_config, None, "active-model", None)
+            .expect("guardian config");
+    let prompt = guardian_config
+        .developer_instructions
+        .expect("guardian developer instructions");
+
+    let instructions_at = prompt
+        .find("Never approve writes under deploy/.")
+        .expect("repo instructions in prompt");
+    let contract_at = prompt
+        .find(guardian_output_contract_prompt())
+        .expect("output contract in prompt");
+    assert!(instructions_at < contract_at);
+    assert_eq!(
+        guardian_policy_prompt(None),
+        format!(
+            "{}\n\n{}\n",
+            include_str!("guardian_prompt.md").trim_end(),
+            guardian_output_contract_prompt()
+        )
+    );
+}
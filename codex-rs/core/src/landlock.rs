@@ -1,8 +1,11 @@
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::seccomp::SeccompSelection;
 use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::permissions::FileSystemWriteScope;
 use codex_protocol::permissions::NetworkSandboxPolicy;
 use std::collections::HashMap;
 use std::path::Path;
@@ -17,6 +20,12 @@ use tokio::process::Child;
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
 /// the equivalent CLI options.
+///
+/// When `print_plan` is set, the helper prints the mounts, namespaces and
+/// seccomp/landlock rules it would apply and exits without running `command`.
+///
+/// A non-empty `write_scope` is refused unless `use_bwrap_sandbox` is set,
+/// since only the bubblewrap pipeline can enforce it.
 #[allow(clippy::too_many_arguments)]
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
@@ -25,6 +34,9 @@ pub async fn spawn_command_under_linux_sandbox<P>(
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
+    write_scope: &FileSystemWriteScope,
+    seccomp: &SeccompSelection,
+    print_plan: bool,
     stdio_policy: StdioPolicy,
     network: Option<&NetworkProxy>,
     env: HashMap<String, String>,
@@ -32,13 +44,24 @@ pub async fn spawn_command_under_linux_sandbox<P>(
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(
+    if !use_bwrap_sandbox && !write_scope.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            SandboxTransformError::WriteScopeRequiresBwrap,
+        ));
+    }
+    let mut args = create_linux_sandbox_command_args(
         command,
         sandbox_policy,
         sandbox_policy_cwd,
         use_bwrap_sandbox,
         allow_network_for_proxy(false),
+        write_scope,
+        seccomp,
     );
+    if print_plan {
+        args.insert(0, "--print-plan".to_string());
+    }
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(SpawnChildRequest {
         program: codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -70,6 +93,8 @@ pub(crate) fn create_linux_sandbox_command_args(
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
+    write_scope: &FileSystemWriteScope,
+    seccomp: &SeccompSelection,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -93,6 +118,23 @@ pub(crate) fn create_linux_sandbox_command_args(
     if allow_network_for_proxy {
         linux_cmd.push("--allow-network-for-proxy".to_string());
     }
+    // Forwarded even without `--use-bwrap-sandbox` so the helper refuses to
+    // run instead of the scope being dropped silently.
+    if !write_scope.is_empty() {
+        #[expect(clippy::expect_used)]
+        let write_scope_json = serde_json::to_string(write_scope)
+            .expect("Failed to serialize FileSystemWriteScope to JSON");
+        linux_cmd.push("--write-scope".to_string());
+        linux_cmd.push(write_scope_json);
+    }
+    for profile in &seccomp.profiles {
+        linux_cmd.push("--seccomp-profile".to_string());
+        linux_cmd.push(profile.to_string());
+    }
+    for program in &seccomp.spawn_allowlist {
+        linux_cmd.push("--spawn-allowlist".to_string());
+        linux_cmd.push(program.to_string_lossy().to_string());
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
@@ -104,9 +146,19 @@ pub(crate) fn create_linux_sandbox_command_args(
     linux_cmd
 }
 
+/// Marks helper arguments built by [`create_linux_sandbox_command_args`] so
+/// bubblewrap does not kill the sandboxed command when Codex exits, which a
+/// persistent background job must survive.
+pub(crate) fn outlive_parent(mut linux_sandbox_args: Vec<String>) -> Vec<String> {
+    linux_sandbox_args.insert(0, "--outlive-parent".to_string());
+    linux_sandbox_args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::permissions::SeccompProfile;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -115,14 +167,31 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let with_bwrap =
-            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, false);
+        let write_scope = FileSystemWriteScope::default();
+
+        let with_bwrap = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            true,
+            false,
+            &write_scope,
+            &SeccompSelection::default(),
+        );
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false, false);
+        let without_bwrap = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            false,
+            false,
+            &write_scope,
+            &SeccompSelection::default(),
+        );
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
@@ -135,13 +204,144 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let args = create_linux_sandbox_command_args(command, &policy, cwd, true, true);
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            true,
+            true,
+            &FileSystemWriteScope::default(),
+            &SeccompSelection::default(),
+        );
         assert_eq!(
             args.contains(&"--allow-network-for-proxy".to_string()),
             true
         );
     }
 
+    #[test]
+    fn write_scope_is_forwarded_only_when_set() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        let args = create_linux_sandbox_command_args(
+            command.clone(),
+            &policy,
+            cwd,
+            true,
+            false,
+            &FileSystemWriteScope::default(),
+            &SeccompSelection::default(),
+        );
+        assert_eq!(args.contains(&"--write-scope".to_string()), false);
+
+        let write_scope = FileSystemWriteScope {
+            read_only: vec![".git/hooks".to_string()],
+            deny_read: Vec::new(),
+        };
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            true,
+            false,
+            &write_scope,
+            &SeccompSelection::default(),
+        );
+        let flag_index = args
+            .iter()
+            .position(|arg| arg == "--write-scope")
+            .expect("write scope flag");
+        assert_eq!(
+            args[flag_index + 1],
+            r#"{"read_only":[".git/hooks"]}"#.to_string()
+        );
+    }
+
+    #[test]
+    fn write_scope_is_forwarded_without_bwrap_so_the_helper_refuses_it() {
+        let write_scope = FileSystemWriteScope {
+            read_only: vec![".git/hooks".to_string()],
+            deny_read: Vec::new(),
+        };
+        let args = create_linux_sandbox_command_args(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/tmp"),
+            false,
+            false,
+            &write_scope,
+            &SeccompSelection::default(),
+        );
+        assert_eq!(args.contains(&"--write-scope".to_string()), true);
+    }
+
+    #[tokio::test]
+    async fn spawn_refuses_write_scope_without_bwrap() {
+        let write_scope = FileSystemWriteScope {
+            read_only: vec![".git/hooks".to_string()],
+            deny_read: Vec::new(),
+        };
+        let err = spawn_command_under_linux_sandbox(
+            "/nonexistent/codex-linux-sandbox",
+            vec!["/bin/true".to_string()],
+            PathBuf::from("/tmp"),
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/tmp"),
+            false,
+            &write_scope,
+            &SeccompSelection::default(),
+            false,
+            StdioPolicy::RedirectForShellTool,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .expect_err("write scope without bwrap must be refused");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn seccomp_profiles_and_spawn_allowlist_are_forwarded_in_order() {
+        let command = vec!["/bin/true".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        let args = create_linux_sandbox_command_args(
+            command,
+            &policy,
+            cwd,
+            false,
+            false,
+            &FileSystemWriteScope::default(),
+            &SeccompSelection {
+                profiles: vec![
+                    SeccompProfile::NoPtrace,
+                    SeccompProfile::NoProcessSpawnOutsideAllowlist,
+                ],
+                spawn_allowlist: vec![
+                    AbsolutePathBuf::from_absolute_path("/usr/bin/git").expect("path"),
+                ],
+            },
+        );
+        let separator = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        assert_eq!(
+            args[separator - 6..separator].to_vec(),
+            vec![
+                "--seccomp-profile".to_string(),
+                "no-ptrace".to_string(),
+                "--seccomp-profile".to_string(),
+                "no-process-spawn-outside-allowlist".to_string(),
+                "--spawn-allowlist".to_string(),
+                "/usr/bin/git".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn proxy_network_requires_managed_requirements() {
         assert_eq!(allow_network_for_proxy(false), false);
@@ -43,6 +43,7 @@ mod file_watcher;
 mod flags;
 pub mod git_info;
 mod guardian;
+mod guardian_rules;
 pub mod instructions;
 pub mod landlock;
 pub mod mcp;
@@ -64,6 +65,7 @@ pub mod personality_migration;
 pub mod plugins;
 mod sandbox_tags;
 pub mod sandboxing;
+mod secret_redaction;
 mod session_prefix;
 mod shell_detect;
 mod stream_events_utils;
@@ -11,6 +11,8 @@ use crate::memories::phase_one::PRUNE_BATCH_SIZE;
 use crate::memories::prompts::build_stage_one_input_message;
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
 use crate::rollout::policy::should_persist_response_item_for_memories;
+use crate::secret_redaction::log_redactions;
+use crate::secret_redaction::redact_text;
 use codex_api::ResponseEvent;
 use codex_otel::SessionTelemetry;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
@@ -22,7 +24,6 @@ use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::TokenUsage;
-use codex_secrets::redact_secrets;
 use futures::StreamExt;
 use serde::Deserialize;
 use serde_json::Value;
@@ -381,9 +382,14 @@ mod job {
         }
 
         let mut output: StageOneOutput = serde_json::from_str(&result)?;
-        output.raw_memory = redact_secrets(output.raw_memory);
-        output.rollout_summary = redact_secrets(output.rollout_summary);
-        output.rollout_slug = output.rollout_slug.map(redact_secrets);
+        // Memory outputs are always redacted, even when `secret_redaction.enabled` is off.
+        let redactor = &session.services.secret_redactor;
+        let mut findings = redact_text(redactor, &mut output.raw_memory);
+        findings.extend(redact_text(redactor, &mut output.rollout_summary));
+        if let Some(slug) = output.rollout_slug.as_mut() {
+            findings.extend(redact_text(redactor, slug));
+        }
+        log_redactions("memory_stage_one", &findings);
 
         Ok((output, token_usage))
     }
@@ -28,7 +28,6 @@ const MAX_REQUEST_MAX_RETRIES: u64 = 100;
 
 const COPILOT_PROVIDER_NAME: &str = "GitHub Copilot";
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
-const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
 pub(crate) const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub(crate) const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
 
@@ -39,6 +38,11 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// The Chat Completions API at `/v1/chat/completions`, as served by vLLM,
+    /// llama.cpp server and most OpenAI-compatible gateways.
+    Chat,
+    /// The Anthropic Messages API at `/v1/messages`.
+    Anthropic,
 }
 
 impl<'de> Deserialize<'de> for WireApi {
@@ -49,8 +53,12 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
-            "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            "chat" => Ok(Self::Chat),
+            "anthropic" => Ok(Self::Anthropic),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "chat", "anthropic"],
+            )),
         }
     }
 }
@@ -474,16 +482,35 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
     }
 
     #[test]
-    fn test_deserialize_chat_wire_api_shows_helpful_error() {
-        let provider_toml = r#"
-name = "OpenAI using Chat Completions"
-base_url = "https://api.openai.com/v1"
-env_key = "OPENAI_API_KEY"
+    fn test_deserialize_chat_and_anthropic_wire_apis() {
+        let chat_provider_toml = r#"
+name = "vLLM"
+base_url = "http://localhost:8000/v1"
 wire_api = "chat"
         "#;
+        let anthropic_provider_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "anthropic"
+        "#;
+
+        let chat_provider: ModelProviderInfo = toml::from_str(chat_provider_toml).unwrap();
+        let anthropic_provider: ModelProviderInfo =
+            toml::from_str(anthropic_provider_toml).unwrap();
+        assert_eq!(chat_provider.wire_api, WireApi::Chat);
+        assert_eq!(anthropic_provider.wire_api, WireApi::Anthropic);
+    }
+
+    #[test]
+    fn test_deserialize_unknown_wire_api_lists_supported_values() {
+        let provider_toml = r#"
+name = "Example"
+wire_api = "completions"
+        "#;
 
         let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
-        assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
+        assert!(err.to_string().contains("`anthropic`"));
     }
 
     #[test]
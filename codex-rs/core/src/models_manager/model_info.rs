@@ -33,6 +33,16 @@ pub(crate) fn with_config_overrides(mut model: ModelInfo, config: &Config) -> Mo
     if let Some(auto_compact_token_limit) = config.model_auto_compact_token_limit {
         model.auto_compact_token_limit = Some(auto_compact_token_limit);
     }
+    if let Some(threshold_percent) = config.compaction.threshold_percent
+        && let Some(context_window) = model.context_window
+    {
+        let threshold = context_window.saturating_mul(i64::from(threshold_percent)) / 100;
+        model.auto_compact_token_limit = Some(
+            model
+                .auto_compact_token_limit
+                .map_or(threshold, |limit| limit.min(threshold)),
+        );
+    }
     if let Some(token_limit) = config.tool_output_token_limit {
         model.truncation_policy = match model.truncation_policy.mode {
             TruncationMode::Bytes => {
@@ -81,6 +81,13 @@ pub(crate) fn denied_network_policy_message(blocked: &BlockedRequest) -> Option<
         "not_allowed" => "domain is not on the allowlist for the current sandbox mode",
         "not_allowed_local" => "local/private network addresses are blocked by policy",
         "method_not_allowed" => "request method is blocked by the current network mode",
+        "mitm_required" => {
+            "HTTPS interception is required to enforce method/path policy but is not enabled"
+        }
+        "request_rule_denied" => "request method/path is denied by a network request rule",
+        "request_rule_not_allowed" => {
+            "request method/path does not match any allowed request rule for this domain"
+        }
         "proxy_disabled" => "managed network proxy is disabled",
         _ => "request is blocked by network policy",
     };
@@ -287,6 +294,7 @@ mod tests {
             decision: Some("ask".to_string()),
             source: Some("decider".to_string()),
             port: Some(80),
+            rule: None,
             timestamp: 0,
         };
         assert_eq!(denied_network_policy_message(&blocked), None);
@@ -304,6 +312,7 @@ mod tests {
             decision: Some("deny".to_string()),
             source: Some("baseline_policy".to_string()),
             port: Some(80),
+            rule: None,
             timestamp: 0,
         };
         assert_eq!(
@@ -313,4 +322,27 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn denied_network_policy_message_for_request_rule_block_names_rule_reason() {
+        let blocked = BlockedRequest {
+            host: "github.com".to_string(),
+            reason: "request_rule_not_allowed".to_string(),
+            client: None,
+            method: Some("POST".to_string()),
+            mode: None,
+            protocol: "https".to_string(),
+            decision: Some("deny".to_string()),
+            source: Some("baseline_policy".to_string()),
+            port: Some(443),
+            rule: None,
+            timestamp: 0,
+        };
+        assert_eq!(
+            denied_network_policy_message(&blocked),
+            Some(
+                "Network access to \"github.com\" was blocked: request method/path does not match any allowed request rule for this domain.".to_string()
+            )
+        );
+    }
 }
@@ -14,6 +14,16 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the project root.
+//!
+//! A line of the form `@include <path>` splices the named file into the doc
+//! in its place. Paths are resolved relative to the including file and must
+//! stay inside the project root or CODEX_HOME. Includes nest, each file is
+//! spliced in at most once, and expansion stops at `project_doc_max_bytes`.
+//!
+//! Docs below the current working directory are not loaded at startup. When
+//! the agent edits a file elsewhere in the project, the nearest doc above that
+//! file is added to the conversation mid-session (see
+//! [`nearest_project_doc`]), within `project_doc_scoped_max_tokens`.
 
 use crate::config::Config;
 use crate::config_loader::ConfigLayerStackOrdering;
@@ -27,8 +37,12 @@ use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use codex_app_server_protocol::ConfigLayerSource;
 use dunce::canonicalize as normalize_path;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
-use tokio::io::AsyncReadExt;
 use toml::Value as TomlValue;
 use tracing::error;
 
@@ -44,6 +58,13 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
+/// Directive that splices another file into a project doc.
+const INCLUDE_DIRECTIVE: &str = "@include ";
+
+/// Bytes a single line may exceed the remaining budget by, so directive lines
+/// (which are replaced rather than copied) are read whole.
+const MAX_INCLUDE_LINE_BYTES: usize = 4096;
+
 fn render_js_repl_instructions(config: &Config) -> Option<String> {
     if !config.features.enabled(Feature::JsRepl) {
         return None;
@@ -158,7 +179,7 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         return Ok(None);
     }
 
-    let mut remaining: u64 = max_total as u64;
+    let mut remaining = max_total;
     let mut parts: Vec<String> = Vec::new();
 
     for p in paths {
@@ -166,29 +187,26 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
             break;
         }
 
-        let file = match tokio::fs::File::open(&p).await {
-            Ok(f) => f,
+        // One byte over the budget, so an oversize doc is still reported below.
+        let mut text = match load_project_doc(config, p.clone(), remaining.saturating_add(1)).await
+        {
+            Ok(doc) => doc.text,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
             Err(e) => return Err(e),
         };
 
-        let size = file.metadata().await?.len();
-        let mut reader = tokio::io::BufReader::new(file).take(remaining);
-        let mut data: Vec<u8> = Vec::new();
-        reader.read_to_end(&mut data).await?;
-
-        if size > remaining {
+        if text.len() > remaining {
             tracing::warn!(
                 "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
                 p.display(),
                 remaining,
             );
+            truncate_at_char_boundary(&mut text, remaining);
         }
 
-        let text = String::from_utf8_lossy(&data).to_string();
         if !text.trim().is_empty() {
+            remaining = remaining.saturating_sub(text.len());
             parts.push(text);
-            remaining = remaining.saturating_sub(data.len() as u64);
         }
     }
 
@@ -205,11 +223,54 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
 /// is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
-    let mut dir = config.cwd.clone();
+    discover_project_doc_paths_from(config, &config.cwd)
+}
+
+/// Like [`discover_project_doc_paths`], searching from `cwd` instead of
+/// `config.cwd`.
+pub(crate) fn discover_project_doc_paths_from(
+    config: &Config,
+    cwd: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut dir = cwd.to_path_buf();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
     }
 
+    let project_root = find_project_root(config, &dir)?;
+    let search_dirs: Vec<PathBuf> = if let Some(root) = project_root {
+        let mut dirs = Vec::new();
+        let mut cursor = dir.as_path();
+        loop {
+            dirs.push(cursor.to_path_buf());
+            if cursor == root {
+                break;
+            }
+            let Some(parent) = cursor.parent() else {
+                break;
+            };
+            cursor = parent;
+        }
+        dirs.reverse();
+        dirs
+    } else {
+        vec![dir]
+    };
+
+    let mut found: Vec<PathBuf> = Vec::new();
+    let candidate_filenames = candidate_filenames(config);
+    for d in search_dirs {
+        if let Some(doc) = project_doc_in_dir(&d, &candidate_filenames)? {
+            found.push(doc);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Walks upwards from `dir` looking for a configured `project_root_markers`
+/// entry. Returns `None` when no marker is found or the marker list is empty.
+fn find_project_root(config: &Config, dir: &Path) -> std::io::Result<Option<PathBuf>> {
     let mut merged = TomlValue::Table(toml::map::Map::new());
     for layer in config
         .config_layer_stack
@@ -249,46 +310,300 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
     }
 
-    let search_dirs: Vec<PathBuf> = if let Some(root) = project_root {
-        let mut dirs = Vec::new();
-        let mut cursor = dir.as_path();
-        loop {
-            dirs.push(cursor.to_path_buf());
-            if cursor == root {
-                break;
+    Ok(project_root)
+}
+
+/// The doc for a single directory: the first candidate filename that exists.
+fn project_doc_in_dir(
+    dir: &Path,
+    candidate_filenames: &[&str],
+) -> std::io::Result<Option<PathBuf>> {
+    for name in candidate_filenames {
+        let candidate = dir.join(name);
+        match std::fs::symlink_metadata(&candidate) {
+            Ok(md) => {
+                let ft = md.file_type();
+                // Allow regular files and symlinks; opening will later fail for dangling links.
+                if ft.is_file() || ft.is_symlink() {
+                    return Ok(Some(candidate));
+                }
             }
-            let Some(parent) = cursor.parent() else {
-                break;
-            };
-            cursor = parent;
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
         }
-        dirs.reverse();
-        dirs
-    } else {
-        vec![dir]
+    }
+    Ok(None)
+}
+
+/// Finds the project doc governing `path`: the first doc found walking up
+/// from the file's directory to the project root of `cwd`. Returns `None`
+/// when the file lies outside that project or no doc is found.
+pub(crate) fn nearest_project_doc(
+    config: &Config,
+    cwd: &Path,
+    path: &Path,
+) -> std::io::Result<Option<PathBuf>> {
+    let cwd = normalize_path(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+    let root = find_project_root(config, &cwd)?.unwrap_or(cwd);
+
+    // The edited file (or even its directory) may have just been deleted, so
+    // start from the closest ancestor that still exists.
+    let Some(dir) = path
+        .ancestors()
+        .skip(1)
+        .find_map(|ancestor| normalize_path(ancestor).ok())
+    else {
+        return Ok(None);
     };
+    if !dir.starts_with(&root) {
+        return Ok(None);
+    }
 
-    let mut found: Vec<PathBuf> = Vec::new();
     let candidate_filenames = candidate_filenames(config);
-    for d in search_dirs {
-        for name in &candidate_filenames {
-            let candidate = d.join(name);
-            match std::fs::symlink_metadata(&candidate) {
-                Ok(md) => {
-                    let ft = md.file_type();
-                    // Allow regular files and symlinks; opening will later fail for dangling links.
-                    if ft.is_file() || ft.is_symlink() {
-                        found.push(candidate);
-                        break;
+    for ancestor in dir.ancestors() {
+        if let Some(doc) = project_doc_in_dir(ancestor, &candidate_filenames)? {
+            return Ok(Some(doc));
+        }
+        if ancestor == root {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+/// A project doc with its `@include` directives expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedProjectDoc {
+    pub path: PathBuf,
+    pub text: String,
+    /// Files spliced in through `@include`, in the order they were expanded.
+    pub includes: Vec<PathBuf>,
+}
+
+/// Reads the doc at `path` and expands its `@include` directives, stopping
+/// once the text reaches `max_bytes`. Includes that are missing, resolve
+/// outside `include_roots`, would form a cycle, or were already spliced in are
+/// dropped with a warning.
+pub fn resolve_project_doc(
+    path: &Path,
+    include_roots: &[PathBuf],
+    max_bytes: usize,
+) -> std::io::Result<ResolvedProjectDoc> {
+    let mut expansion = IncludeExpansion {
+        include_roots,
+        stack: Vec::new(),
+        seen: HashSet::new(),
+        includes: Vec::new(),
+        text: String::new(),
+        remaining: max_bytes,
+    };
+    let canonical = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+    expansion.expand(path, canonical)?;
+    Ok(ResolvedProjectDoc {
+        path: path.to_path_buf(),
+        text: expansion.text,
+        includes: expansion.includes,
+    })
+}
+
+/// [`resolve_project_doc`] on the blocking pool, with the include roots for
+/// `path` under `config`.
+pub(crate) async fn load_project_doc(
+    config: &Config,
+    path: PathBuf,
+    max_bytes: usize,
+) -> std::io::Result<ResolvedProjectDoc> {
+    let include_roots = include_roots(config, &path)?;
+    tokio::task::spawn_blocking(move || resolve_project_doc(&path, &include_roots, max_bytes))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Directories `@include` targets may resolve into: the project root of
+/// `doc` (its own directory when there is none) and CODEX_HOME.
+fn include_roots(config: &Config, doc: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let dir = doc.parent().unwrap_or_else(|| Path::new(""));
+    let dir = normalize_path(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let project_root = find_project_root(config, &dir)?.unwrap_or(dir);
+    let codex_home =
+        normalize_path(&config.codex_home).unwrap_or_else(|_| config.codex_home.clone());
+    Ok(vec![project_root, codex_home])
+}
+
+struct IncludeExpansion<'a> {
+    include_roots: &'a [PathBuf],
+    /// Canonical paths of the files being expanded, outermost first.
+    stack: Vec<PathBuf>,
+    /// Canonical paths of every file read so far.
+    seen: HashSet<PathBuf>,
+    includes: Vec<PathBuf>,
+    text: String,
+    remaining: usize,
+}
+
+impl IncludeExpansion<'_> {
+    fn expand(&mut self, path: &Path, canonical: PathBuf) -> std::io::Result<()> {
+        let mut reader = BufReader::new(open_regular_file(path)?);
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        self.seen.insert(canonical.clone());
+        self.stack.push(canonical);
+
+        let mut buf = Vec::new();
+        while self.remaining > 0 {
+            buf.clear();
+            let limit = self.remaining + MAX_INCLUDE_LINE_BYTES;
+            if (&mut reader)
+                .take(limit as u64)
+                .read_until(b'\n', &mut buf)?
+                == 0
+            {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buf);
+            // A line cut off by the limit is plain text, never a directive.
+            let complete = buf.ends_with(b"\n") || buf.len() < limit;
+            let Some(target) = line
+                .trim()
+                .strip_prefix(INCLUDE_DIRECTIVE)
+                .map(str::trim)
+                .filter(|target| !target.is_empty() && complete)
+            else {
+                self.push(&line);
+                continue;
+            };
+
+            let include_path = base.join(target);
+            let canonical = match normalize_path(&include_path) {
+                Ok(canonical) => canonical,
+                Err(err) => {
+                    tracing::warn!(
+                        "skipping `@include {target}` in `{}`: {err}",
+                        path.display()
+                    );
+                    continue;
+                }
+            };
+            let skip_reason = if self.stack.contains(&canonical) {
+                Some("include cycle")
+            } else if self.seen.contains(&canonical) {
+                Some("already included")
+            } else if !self
+                .include_roots
+                .iter()
+                .any(|root| canonical.starts_with(root))
+            {
+                Some("outside the project root and CODEX_HOME")
+            } else {
+                None
+            };
+            if let Some(reason) = skip_reason {
+                tracing::warn!(
+                    "skipping `@include {target}` in `{}`: {reason}",
+                    path.display()
+                );
+                continue;
+            }
+
+            let start = self.text.len();
+            match self.expand(&include_path, canonical) {
+                Ok(()) => {
+                    self.includes.push(include_path);
+                    let included = &self.text[start..];
+                    if line.ends_with('\n') && !included.is_empty() && !included.ends_with('\n') {
+                        self.push("\n");
                     }
                 }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e),
+                Err(err) => tracing::warn!(
+                    "skipping `@include {target}` in `{}`: {err}",
+                    path.display()
+                ),
             }
         }
+
+        self.stack.pop();
+        Ok(())
     }
 
-    Ok(found)
+    fn push(&mut self, text: &str) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        truncate_at_char_boundary(&mut self.text, start + self.remaining);
+        self.remaining -= self.text.len() - start;
+    }
+}
+
+/// Opens `path`, refusing anything but a regular file (e.g. a FIFO or device).
+fn open_regular_file(path: &Path) -> std::io::Result<std::fs::File> {
+    let file = std::fs::File::open(path)?;
+    if !file.metadata()?.is_file() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "not a regular file",
+        ));
+    }
+    Ok(file)
+}
+
+/// Resolves the docs loaded at startup, for display (e.g. `/debug-config`).
+/// Like [`read_project_docs`], the docs share one `project_doc_max_bytes`
+/// budget, so each text is what the model actually sees.
+pub fn describe_project_docs(config: &Config) -> std::io::Result<Vec<ResolvedProjectDoc>> {
+    let mut remaining = config.project_doc_max_bytes;
+    let mut docs = Vec::new();
+    if remaining == 0 {
+        return Ok(docs);
+    }
+    for path in discover_project_doc_paths(config)? {
+        if remaining == 0 {
+            break;
+        }
+        let include_roots = include_roots(config, &path)?;
+        let doc = match resolve_project_doc(&path, &include_roots, remaining) {
+            Ok(doc) => doc,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if !doc.text.trim().is_empty() {
+            remaining = remaining.saturating_sub(doc.text.len());
+        }
+        docs.push(doc);
+    }
+    Ok(docs)
+}
+
+/// Shortens `text` to at most `max_bytes` without splitting a character.
+pub(crate) fn truncate_at_char_boundary(text: &mut String, max_bytes: usize) {
+    if text.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+}
+
+/// Directory-scoped docs loaded into the conversation mid-session because
+/// the agent edited files under their directory.
+#[derive(Debug, Default)]
+pub(crate) struct ScopedProjectDocs {
+    loaded: HashSet<PathBuf>,
+    tokens_used: usize,
+}
+
+impl ScopedProjectDocs {
+    pub(crate) fn contains(&self, path: &Path) -> bool {
+        self.loaded.contains(path)
+    }
+
+    pub(crate) fn remaining_tokens(&self, budget: usize) -> usize {
+        budget.saturating_sub(self.tokens_used)
+    }
+
+    pub(crate) fn record(&mut self, path: PathBuf, tokens: usize) {
+        self.loaded.insert(path);
+        self.tokens_used = self.tokens_used.saturating_add(tokens);
+    }
 }
 
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
@@ -602,6 +917,30 @@ mod tests {
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    #[tokio::test]
+    async fn described_docs_share_the_byte_budget() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        let nested = repo.path().join("workspace/crate_a");
+        std::fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("AGENTS.md"), "crate doc").unwrap();
+
+        let mut cfg = make_config(&repo, 12, None).await;
+        cfg.cwd = nested;
+
+        let texts: Vec<String> = describe_project_docs(&cfg)
+            .expect("describe docs")
+            .into_iter()
+            .map(|doc| doc.text)
+            .collect();
+        assert_eq!(texts, vec!["root doc".to_string(), "crat".to_string()]);
+    }
+
     #[tokio::test]
     async fn project_root_markers_are_honored_for_agents_discovery() {
         let root = tempfile::tempdir().expect("tempdir");
@@ -782,6 +1121,132 @@ mod tests {
         assert_eq!(res, "base doc");
     }
 
+    /// `@include` splices files in place, resolving paths relative to the
+    /// file that contains the directive.
+    #[tokio::test]
+    async fn includes_are_expanded_relative_to_including_file() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(tmp.path().join("docs")).unwrap();
+        fs::write(
+            tmp.path().join("AGENTS.md"),
+            "intro\n@include docs/style.md\noutro",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("docs/style.md"),
+            "@include ../shared.md\nstyle rules\n",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("shared.md"), "shared").unwrap();
+
+        let res = get_user_instructions(&make_config(&tmp, 4096, None).await, None, None)
+            .await
+            .expect("doc expected");
+
+        assert_eq!(res, "intro\nshared\nstyle rules\noutro");
+    }
+
+    #[test]
+    fn include_cycles_are_skipped() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let agents = tmp.path().join("AGENTS.md");
+        let other = tmp.path().join("other.md");
+        fs::write(&agents, "a\n@include other.md\n").unwrap();
+        fs::write(&other, "b\n@include AGENTS.md\n@include missing.md\n").unwrap();
+
+        let doc = resolve_project_doc(&agents, &[normalize_path(tmp.path()).unwrap()], 4096)
+            .expect("resolve doc");
+
+        assert_eq!(doc.text, "a\nb\n");
+        assert_eq!(doc.includes, vec![other]);
+    }
+
+    #[test]
+    fn includes_outside_the_include_roots_are_skipped() {
+        let project = tempfile::tempdir().expect("tempdir");
+        let outside = tempfile::tempdir().expect("tempdir");
+        let secret = outside.path().join("secret.md");
+        fs::write(&secret, "secret").unwrap();
+        fs::write(project.path().join("inside.md"), "inside\n").unwrap();
+        let agents = project.path().join("AGENTS.md");
+        fs::write(
+            &agents,
+            format!(
+                "@include inside.md\n@include {}\n@include /dev/zero\nend\n",
+                secret.display()
+            ),
+        )
+        .unwrap();
+
+        let doc = resolve_project_doc(&agents, &[normalize_path(project.path()).unwrap()], 4096)
+            .expect("resolve doc");
+
+        assert_eq!(doc.text, "inside\nend\n");
+        assert_eq!(doc.includes, vec![project.path().join("inside.md")]);
+    }
+
+    #[test]
+    fn each_file_is_included_once_and_expansion_stops_at_the_budget() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let roots = [normalize_path(tmp.path()).unwrap()];
+        // Every level includes the next one twice; expanding them all would
+        // take 2^20 reads.
+        for level in 0..20 {
+            fs::write(
+                tmp.path().join(format!("level{level}.md")),
+                format!(
+                    "{level}\n@include level{next}.md\n@include level{next}.md\n",
+                    next = level + 1
+                ),
+            )
+            .unwrap();
+        }
+        fs::write(tmp.path().join("level20.md"), "x".repeat(100)).unwrap();
+
+        let doc =
+            resolve_project_doc(&tmp.path().join("level0.md"), &roots, 4096).expect("resolve doc");
+        let expected_levels: String = (0..20).map(|level| format!("{level}\n")).collect();
+        assert_eq!(doc.text, format!("{expected_levels}{}\n", "x".repeat(100)));
+        assert_eq!(doc.includes.len(), 20);
+
+        let doc =
+            resolve_project_doc(&tmp.path().join("level0.md"), &roots, 16).expect("resolve doc");
+        assert_eq!(doc.text, "0\n1\n2\n3\n4\n5\n6\n7\n");
+    }
+
+    #[tokio::test]
+    async fn nearest_project_doc_is_scoped_to_edited_directory() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let root = normalize_path(repo.path()).unwrap();
+        fs::write(root.join(".git"), "gitdir: /path/to/actual/git/dir\n").unwrap();
+        fs::write(root.join("AGENTS.md"), "root doc").unwrap();
+        fs::create_dir_all(root.join("crates/foo/src")).unwrap();
+        fs::create_dir_all(root.join("crates/bar/src")).unwrap();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(root.join("crates/foo/AGENTS.md"), "foo doc").unwrap();
+        let outside = tempfile::tempdir().expect("tempdir");
+
+        let cfg = make_config(&repo, 4096, None).await;
+        let cwd = root.join("app");
+
+        assert_eq!(
+            nearest_project_doc(&cfg, &cwd, &root.join("crates/foo/src/lib.rs")).unwrap(),
+            Some(root.join("crates/foo/AGENTS.md"))
+        );
+        assert_eq!(
+            nearest_project_doc(&cfg, &cwd, &root.join("crates/bar/src/new/mod.rs")).unwrap(),
+            Some(root.join("AGENTS.md"))
+        );
+        assert_eq!(
+            nearest_project_doc(&cfg, &cwd, &outside.path().join("lib.rs")).unwrap(),
+            None
+        );
+        assert_eq!(
+            discover_project_doc_paths_from(&cfg, &cwd).unwrap(),
+            vec![root.join("AGENTS.md")]
+        );
+    }
+
     fn create_skill(codex_home: PathBuf, name: &str, description: &str) {
         let skill_dir = codex_home.join(format!("skills/{name}"));
         fs::create_dir_all(&skill_dir).unwrap();
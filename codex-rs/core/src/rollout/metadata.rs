@@ -583,6 +583,7 @@ mod tests {
         let items = vec![RolloutItem::Compacted(CompactedItem {
             message: "noop".to_string(),
             replacement_history: None,
+            report: None,
         })];
 
         let builder = builder_from_items(items.as_slice(), path.as_path()).expect("builder");
@@ -5,6 +5,7 @@ use std::fs::{self};
 use std::io::Error as IoError;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -43,6 +44,10 @@ use crate::config::Config;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
 use crate::path_utils;
+use crate::secret_redaction::build_redactor;
+use crate::secret_redaction::log_redactions;
+use crate::secret_redaction::redact_rollout_item;
+use crate::secret_redaction::redaction_event;
 use crate::state_db;
 use crate::state_db::StateDbHandle;
 use crate::truncate::TruncationPolicy;
@@ -55,6 +60,7 @@ use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
+use codex_secrets::SecretRedactor;
 use codex_state::StateRuntime;
 use codex_state::ThreadMetadataBuilder;
 
@@ -73,6 +79,8 @@ pub struct RolloutRecorder {
     pub(crate) rollout_path: PathBuf,
     state_db: Option<StateDbHandle>,
     event_persistence_mode: EventPersistenceMode,
+    /// Scrubs secrets from items before they are written; `None` when redaction is disabled.
+    secret_redactor: Option<Arc<SecretRedactor>>,
 }
 
 #[derive(Clone)]
@@ -470,6 +478,10 @@ impl RolloutRecorder {
             rollout_path,
             state_db: state_db_ctx,
             event_persistence_mode,
+            secret_redactor: config
+                .secret_redaction
+                .enabled
+                .then(|| build_redactor(&config.secret_redaction)),
         })
     }
 
@@ -488,10 +500,21 @@ impl RolloutRecorder {
             // "fully qualified MCP tool calls," so we could consider
             // reformatting them in that case.
             if is_persisted_response_item(item, self.event_persistence_mode) {
-                filtered.push(sanitize_rollout_item_for_persistence(
+                let item = sanitize_rollout_item_for_persistence(
                     item.clone(),
                     self.event_persistence_mode,
-                ));
+                );
+                let Some(redactor) = &self.secret_redactor else {
+                    filtered.push(item);
+                    continue;
+                };
+                let (item, findings) = redact_rollout_item(redactor, item);
+                log_redactions("rollout", &findings);
+                filtered.push(item);
+                // Recorded next to the item so the rollout says what was scrubbed from it.
+                if let Some(event) = redaction_event("rollout", &findings) {
+                    filtered.push(RolloutItem::EventMsg(event));
+                }
             }
         }
         if filtered.is_empty() {
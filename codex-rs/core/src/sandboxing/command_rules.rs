@@ -0,0 +1,154 @@
+use crate::exec_policy::commands_and_redirects_for_exec_policy;
+use crate::exec_policy::exec_policy_workspace_roots;
+use crate::protocol::SandboxPolicy;
+use codex_execpolicy::MatchOptions;
+use codex_execpolicy::Policy;
+use codex_execpolicy::SandboxOverride;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Sandbox overrides that execpolicy `command_rule`s attach to a command.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandRuleSandbox {
+    /// Overrides of the rules matching each sub-command, in policy order.
+    /// Empty when the command is not a script Codex can fully parse.
+    per_command: Vec<Vec<SandboxOverride>>,
+}
+
+impl CommandRuleSandbox {
+    /// Matches `command` against `policy` the way approval does: shell scripts
+    /// are split into their sub-commands and path conditions resolve against
+    /// `cwd` and the writable roots of `sandbox_policy`.
+    pub fn for_command(
+        policy: &Policy,
+        command: &[String],
+        cwd: &Path,
+        sandbox_policy: &SandboxPolicy,
+        env: &HashMap<String, String>,
+    ) -> Self {
+        let (commands, redirects, _) = commands_and_redirects_for_exec_policy(command);
+        // Unknown redirections mean the script was only partially parsed.
+        if redirects.is_none() {
+            return Self::default();
+        }
+        let options = MatchOptions {
+            resolve_host_executables: true,
+            cwd: AbsolutePathBuf::from_absolute_path(cwd).ok(),
+            workspace_roots: exec_policy_workspace_roots(sandbox_policy, cwd),
+            redirects,
+            env: Some(env.clone()),
+        };
+        let per_command = commands
+            .iter()
+            .map(|command| {
+                policy
+                    .matches_for_command_with_options(command, None, &options)
+                    .iter()
+                    .filter_map(|rule_match| rule_match.sandbox_override().cloned())
+                    .collect()
+            })
+            .collect();
+        Self { per_command }
+    }
+
+    /// Returns the setting `select` reads from the overrides when every
+    /// sub-command matches a rule that sets it, taking the first such rule per
+    /// sub-command, and they all agree. Chaining an unrelated command onto a
+    /// matching one therefore cannot borrow its sandbox.
+    pub(crate) fn agreed<T: Clone + PartialEq>(
+        &self,
+        select: impl Fn(&SandboxOverride) -> Option<&T>,
+    ) -> Option<T> {
+        let mut agreed = None;
+        for overrides in &self.per_command {
+            let setting = overrides.iter().find_map(&select)?;
+            match agreed {
+                Some(previous) if previous != setting => return None,
+                _ => agreed = Some(setting),
+            }
+        }
+        agreed.cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_execpolicy::PolicyParser;
+    use codex_execpolicy::WriteScopeOverride;
+    use pretty_assertions::assert_eq;
+
+    fn command(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn write_scope_rules() -> Policy {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "test.rules",
+                r#"
+command_rule(pattern = ["cargo", "update"], write_scope = {"read_only": [".git/hooks"]})
+command_rule(pattern = ["cargo", "fetch"], write_scope = {"read_only": [".git/hooks"]})
+command_rule(pattern = ["npm", "install"], write_scope = {"read_only": []})
+"#,
+            )
+            .expect("parse rules");
+        parser.build()
+    }
+
+    fn read_only_for(policy: &Policy, command: &[String]) -> Option<Vec<String>> {
+        let cwd = std::env::temp_dir();
+        CommandRuleSandbox::for_command(
+            policy,
+            command,
+            &cwd,
+            &SandboxPolicy::new_workspace_write_policy(),
+            &HashMap::new(),
+        )
+        .agreed(|sandbox| sandbox.write_scope.as_ref())
+        .and_then(|write_scope: WriteScopeOverride| write_scope.read_only)
+    }
+
+    #[test]
+    fn matching_rule_supplies_the_override() {
+        let policy = write_scope_rules();
+
+        assert_eq!(
+            read_only_for(
+                &policy,
+                &command(&["/usr/bin/cargo", "update", "-p", "serde"])
+            ),
+            Some(vec![".git/hooks".to_string()])
+        );
+        assert_eq!(read_only_for(&policy, &command(&["cargo", "build"])), None);
+    }
+
+    #[test]
+    fn every_sub_command_must_match_an_agreeing_rule() {
+        let policy = write_scope_rules();
+
+        assert_eq!(
+            read_only_for(
+                &policy,
+                &command(&["bash", "-lc", "cargo fetch && cargo update"])
+            ),
+            Some(vec![".git/hooks".to_string()])
+        );
+        assert_eq!(
+            read_only_for(
+                &policy,
+                &command(&["bash", "-lc", "cargo update && rm Cargo.lock"])
+            ),
+            None
+        );
+        assert_eq!(
+            read_only_for(
+                &policy,
+                &command(&["bash", "-lc", "cargo update; npm install"])
+            ),
+            None
+        );
+    }
+}
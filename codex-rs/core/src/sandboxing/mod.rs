@@ -6,7 +6,10 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+pub mod command_rules;
 pub(crate) mod macos_permissions;
+pub mod seccomp;
+pub mod write_scope;
 
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
@@ -24,6 +27,7 @@ use crate::seatbelt::create_seatbelt_command_args_with_extensions;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
+use codex_execpolicy::Policy;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::FileSystemPermissions;
@@ -37,15 +41,20 @@ use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::SeccompProfile;
 use codex_protocol::protocol::NetworkAccess;
 use codex_protocol::protocol::ReadOnlyAccess;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use command_rules::CommandRuleSandbox;
 use dunce::canonicalize;
 use macos_permissions::merge_macos_seatbelt_profile_extensions;
+use seccomp::SandboxSeccomp;
+use seccomp::UnknownSeccompProfile;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use write_scope::SandboxWriteScope;
 
 #[derive(Debug)]
 pub struct CommandSpec {
@@ -57,6 +66,10 @@ pub struct CommandSpec {
     pub sandbox_permissions: SandboxPermissions,
     pub additional_permissions: Option<PermissionProfile>,
     pub justification: Option<String>,
+    /// The command as execpolicy judged it, when `program`/`args` wrap it
+    /// (e.g. to source a shell snapshot). `command_rule` sandbox overrides
+    /// are matched against it; `None` means `program`/`args` themselves.
+    pub exec_policy_command: Option<Vec<String>>,
 }
 
 #[derive(Debug)]
@@ -74,6 +87,9 @@ pub struct ExecRequest {
     pub network_sandbox_policy: NetworkSandboxPolicy,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Seccomp profiles the Linux sandbox installs for this command, used to
+    /// report commands they stop as seccomp denials.
+    pub seccomp_profiles: Vec<SeccompProfile>,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -94,6 +110,12 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub macos_seatbelt_profile_extensions: Option<&'a MacOsSeatbeltProfileExtensions>,
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
+    /// Rules whose `command_rule` sandbox overrides apply to the command.
+    pub exec_policy: Option<&'a Policy>,
+    /// Read-only globs and deny-read paths for the Linux sandbox.
+    pub write_scope: Option<&'a SandboxWriteScope>,
+    /// Named seccomp profiles for the Linux sandbox.
+    pub seccomp: Option<&'a SandboxSeccomp>,
     pub windows_sandbox_level: WindowsSandboxLevel,
 }
 
@@ -110,6 +132,12 @@ pub(crate) enum SandboxTransformError {
     #[cfg(not(target_os = "macos"))]
     #[error("seatbelt sandbox is only available on macOS")]
     SeatbeltUnavailable,
+    #[error(
+        "`sandbox_write_scope` applies to this command but is only enforced by the bubblewrap Linux sandbox; enable `features.use_linux_sandbox_bwrap` or remove the scope"
+    )]
+    WriteScopeRequiresBwrap,
+    #[error(transparent)]
+    UnknownSeccompProfile(#[from] UnknownSeccompProfile),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -441,6 +469,9 @@ impl SandboxManager {
             macos_seatbelt_profile_extensions,
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
+            exec_policy,
+            write_scope,
+            seccomp,
             windows_sandbox_level,
         } = request;
         #[cfg(not(target_os = "macos"))]
@@ -489,7 +520,9 @@ impl SandboxManager {
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
+        let exec_policy_command = spec.exec_policy_command.take();
 
+        let mut seccomp_profiles = Vec::new();
         let (command, sandbox_env, arg0_override) = match sandbox {
             SandboxType::None => (command, HashMap::new(), None),
             #[cfg(target_os = "macos")]
@@ -515,12 +548,38 @@ impl SandboxManager {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
                 let allow_proxy_network = allow_network_for_proxy(enforce_managed_network);
+                let command_rules = exec_policy
+                    .map(|exec_policy| {
+                        CommandRuleSandbox::for_command(
+                            exec_policy,
+                            exec_policy_command.as_deref().unwrap_or(&command),
+                            &spec.cwd,
+                            &effective_policy,
+                            &env,
+                        )
+                    })
+                    .unwrap_or_default();
+                let write_scope = write_scope
+                    .map(|write_scope| write_scope.for_command(&command_rules))
+                    .unwrap_or_default();
+                // The legacy Landlock pipeline cannot express these mounts, so
+                // refuse to run rather than drop a deny-read path silently.
+                if !use_linux_sandbox_bwrap && !write_scope.is_empty() {
+                    return Err(SandboxTransformError::WriteScopeRequiresBwrap);
+                }
+                let seccomp = seccomp
+                    .map(|seccomp| seccomp.for_command(&effective_policy, &command_rules))
+                    .transpose()?
+                    .unwrap_or_default();
+                seccomp_profiles = seccomp.profiles.clone();
                 let mut args = create_linux_sandbox_command_args(
                     command.clone(),
                     &effective_policy,
                     sandbox_policy_cwd,
                     use_linux_sandbox_bwrap,
                     allow_proxy_network,
+                    &write_scope,
+                    &seccomp,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
@@ -557,6 +616,7 @@ impl SandboxManager {
             network_sandbox_policy: effective_network_policy,
             justification: spec.justification,
             arg0: arg0_override,
+            seccomp_profiles,
         })
     }
 
@@ -587,10 +647,13 @@ mod tests {
     #[cfg(target_os = "macos")]
     use super::EffectiveSandboxPermissions;
     use super::SandboxManager;
+    use super::SandboxTransformError;
+    use super::SandboxWriteScope;
     use super::merge_file_system_policy_with_additional_permissions;
     use super::normalize_additional_permissions;
     use super::sandbox_policy_with_additional_permissions;
     use super::should_require_platform_sandbox;
+    use crate::config::types::SandboxWriteScopeToml;
     use crate::exec::SandboxType;
     use crate::protocol::NetworkAccess;
     use crate::protocol::ReadOnlyAccess;
@@ -709,6 +772,7 @@ mod tests {
                     sandbox_permissions: super::SandboxPermissions::UseDefault,
                     additional_permissions: None,
                     justification: None,
+                    exec_policy_command: None,
                 },
                 policy: &SandboxPolicy::ExternalSandbox {
                     network_access: crate::protocol::NetworkAccess::Restricted,
@@ -723,6 +787,9 @@ mod tests {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: None,
                 use_linux_sandbox_bwrap: false,
+                exec_policy: None,
+                write_scope: None,
+                seccomp: None,
                 windows_sandbox_level: WindowsSandboxLevel::Disabled,
             })
             .expect("transform");
@@ -953,6 +1020,7 @@ mod tests {
                         ..Default::default()
                     }),
                     justification: None,
+                    exec_policy_command: None,
                 },
                 policy: &SandboxPolicy::ExternalSandbox {
                     network_access: NetworkAccess::Restricted,
@@ -967,6 +1035,9 @@ mod tests {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: None,
                 use_linux_sandbox_bwrap: false,
+                exec_policy: None,
+                write_scope: None,
+                seccomp: None,
                 windows_sandbox_level: WindowsSandboxLevel::Disabled,
             })
             .expect("transform");
@@ -1011,6 +1082,7 @@ mod tests {
                         ..Default::default()
                     }),
                     justification: None,
+                    exec_policy_command: None,
                 },
                 policy: &SandboxPolicy::ReadOnly {
                     access: ReadOnlyAccess::FullAccess,
@@ -1039,6 +1111,9 @@ mod tests {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: None,
                 use_linux_sandbox_bwrap: false,
+                exec_policy: None,
+                write_scope: None,
+                seccomp: None,
                 windows_sandbox_level: WindowsSandboxLevel::Disabled,
             })
             .expect("transform");
@@ -1068,6 +1143,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_scope_without_bwrap_is_refused() {
+        let manager = SandboxManager::new();
+        let cwd = std::env::current_dir().expect("current dir");
+        let policy = SandboxPolicy::new_workspace_write_policy();
+        let file_system_policy = FileSystemSandboxPolicy::from(&policy);
+        let write_scope = SandboxWriteScope::from_toml(SandboxWriteScopeToml {
+            deny_read: vec![
+                AbsolutePathBuf::from_absolute_path("/home/user/.ssh").expect("absolute path"),
+            ],
+            ..Default::default()
+        });
+        let linux_sandbox_exe = std::path::PathBuf::from("/usr/bin/codex-linux-sandbox");
+        let request = |use_linux_sandbox_bwrap| super::SandboxTransformRequest {
+            spec: super::CommandSpec {
+                program: "true".to_string(),
+                args: Vec::new(),
+                cwd: cwd.clone(),
+                env: HashMap::new(),
+                expiration: crate::exec::ExecExpiration::DefaultTimeout,
+                sandbox_permissions: super::SandboxPermissions::UseDefault,
+                additional_permissions: None,
+                justification: None,
+                exec_policy_command: None,
+            },
+            policy: &policy,
+            file_system_policy: &file_system_policy,
+            network_policy: NetworkSandboxPolicy::Restricted,
+            sandbox: SandboxType::LinuxSeccomp,
+            enforce_managed_network: false,
+            network: None,
+            sandbox_policy_cwd: cwd.as_path(),
+            #[cfg(target_os = "macos")]
+            macos_seatbelt_profile_extensions: None,
+            codex_linux_sandbox_exe: Some(&linux_sandbox_exe),
+            use_linux_sandbox_bwrap,
+            exec_policy: None,
+            write_scope: Some(&write_scope),
+            seccomp: None,
+            windows_sandbox_level: WindowsSandboxLevel::Disabled,
+        };
+
+        assert!(matches!(
+            manager.transform(request(false)),
+            Err(SandboxTransformError::WriteScopeRequiresBwrap)
+        ));
+        assert!(manager.transform(request(true)).is_ok());
+    }
+
     #[test]
     fn merge_file_system_policy_with_additional_permissions_preserves_unreadable_roots() {
         let temp_dir = TempDir::new().expect("create temp dir");
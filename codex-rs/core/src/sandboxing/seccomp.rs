@@ -0,0 +1,338 @@
+use crate::config::types::SandboxSeccompToml;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::SandboxType;
+use crate::protocol::SandboxPolicy;
+use crate::sandboxing::command_rules::CommandRuleSandbox;
+use codex_protocol::permissions::SeccompProfile;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+/// Resolved `[sandbox_seccomp]` configuration.
+///
+/// Execpolicy `command_rule(seccomp_profiles = ...)` entries replace the
+/// profiles for the commands they match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SandboxSeccomp {
+    profiles: Vec<SeccompProfile>,
+    read_only: Option<Vec<SeccompProfile>>,
+    workspace_write: Option<Vec<SeccompProfile>>,
+    spawn_allowlist: Vec<AbsolutePathBuf>,
+}
+
+/// Seccomp profiles selected for one command, plus the programs
+/// `no-process-spawn-outside-allowlist` lets it execute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeccompSelection {
+    pub profiles: Vec<SeccompProfile>,
+    /// Empty unless `profiles` contains `no-process-spawn-outside-allowlist`.
+    pub spawn_allowlist: Vec<AbsolutePathBuf>,
+}
+
+/// An execpolicy `command_rule` named a seccomp profile Codex does not know.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("execpolicy command_rule sets unknown seccomp profile `{0}`")]
+pub struct UnknownSeccompProfile(pub String);
+
+impl SandboxSeccomp {
+    pub fn from_toml(toml: SandboxSeccompToml) -> Self {
+        let modes = toml.modes.unwrap_or_default();
+        Self {
+            profiles: toml.profiles,
+            read_only: modes.read_only,
+            workspace_write: modes.workspace_write,
+            spawn_allowlist: toml.spawn_allowlist,
+        }
+    }
+
+    /// Returns the profiles to install for a command under `policy`, given
+    /// the `command_rule` overrides that match it.
+    ///
+    /// Profiles set by the matching rules win; otherwise the profiles
+    /// configured for the sandbox mode apply, falling back to the base
+    /// `profiles`. Policies that do not run under the Linux sandbox get none.
+    pub fn for_command(
+        &self,
+        policy: &SandboxPolicy,
+        command_rules: &CommandRuleSandbox,
+    ) -> Result<SeccompSelection, UnknownSeccompProfile> {
+        let mode_profiles = match policy {
+            SandboxPolicy::ReadOnly { .. } => self.read_only.as_ref(),
+            SandboxPolicy::WorkspaceWrite { .. } => self.workspace_write.as_ref(),
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                return Ok(SeccompSelection::default());
+            }
+        };
+        let mut profiles = match command_rules.agreed(|sandbox| sandbox.seccomp_profiles.as_ref()) {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    name.parse::<SeccompProfile>()
+                        .map_err(|_| UnknownSeccompProfile(name.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => mode_profiles.unwrap_or(&self.profiles).clone(),
+        };
+        profiles.sort();
+        profiles.dedup();
+        let spawn_allowlist = if profiles.contains(&SeccompProfile::NoProcessSpawnOutsideAllowlist)
+        {
+            self.spawn_allowlist.clone()
+        } else {
+            Vec::new()
+        };
+        Ok(SeccompSelection {
+            profiles,
+            spawn_allowlist,
+        })
+    }
+}
+
+/// A sandboxed command that was stopped by one of the seccomp profiles it ran
+/// under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeccompDenial {
+    pub profiles: Vec<SeccompProfile>,
+}
+
+impl SeccompDenial {
+    /// Detects a command killed with `SIGSYS` while seccomp profiles were
+    /// installed. The Linux sandbox only kills on profile violations; its
+    /// network filter fails with `EPERM` instead.
+    ///
+    /// `no-process-spawn-outside-allowlist` does not kill: a program outside
+    /// the allowlist fails to execute with `EACCES`, so a failed command that
+    /// reports `Permission denied` under it is attributed to the profile.
+    pub(crate) fn from_output(
+        sandbox_type: SandboxType,
+        profiles: &[SeccompProfile],
+        output: &ExecToolCallOutput,
+    ) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            if sandbox_type == SandboxType::LinuxSeccomp
+                && output.exit_code == crate::exec::EXIT_CODE_SIGNAL_BASE + libc::SIGSYS
+            {
+                let profiles = profiles
+                    .iter()
+                    .copied()
+                    .filter(|profile| profile.kills_on_violation())
+                    .collect::<Vec<_>>();
+                if !profiles.is_empty() {
+                    return Some(Self { profiles });
+                }
+            }
+        }
+        let spawn_denied = sandbox_type == SandboxType::LinuxSeccomp
+            && output.exit_code != 0
+            && profiles.contains(&SeccompProfile::NoProcessSpawnOutsideAllowlist)
+            && output
+                .aggregated_output
+                .text
+                .to_lowercase()
+                .contains("permission denied");
+        spawn_denied.then(|| Self {
+            profiles: vec![SeccompProfile::NoProcessSpawnOutsideAllowlist],
+        })
+    }
+
+    /// Human-readable reason shown to the model and in approval prompts.
+    pub fn message(&self) -> String {
+        if self.profiles == [SeccompProfile::NoProcessSpawnOutsideAllowlist] {
+            return "Command was denied by the Linux sandbox: seccomp profile `no-process-spawn-outside-allowlist` only lets it execute itself and the programs in `[sandbox_seccomp] spawn_allowlist`.".to_string();
+        }
+        let profiles = self
+            .profiles
+            .iter()
+            .map(|profile| {
+                format!(
+                    "`{profile}` (blocks {})",
+                    profile.blocked_syscalls().join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "Command was killed by the Linux sandbox for making a syscall blocked by seccomp profile {profiles}."
+        )
+    }
+
+    /// Appends [`Self::message`] to the command's stderr so the model sees why
+    /// it died instead of a bare exit code.
+    pub(crate) fn append_to_output(&self, output: &mut ExecToolCallOutput) {
+        let message = self.message();
+        for stream in [&mut output.stderr.text, &mut output.aggregated_output.text] {
+            if !stream.is_empty() && !stream.ends_with('\n') {
+                stream.push('\n');
+            }
+            stream.push_str(&message);
+            stream.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::SandboxSeccompModesToml;
+    use codex_execpolicy::PolicyParser;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn seccomp() -> SandboxSeccomp {
+        SandboxSeccomp::from_toml(SandboxSeccompToml {
+            profiles: vec![SeccompProfile::NoPtrace],
+            modes: Some(SandboxSeccompModesToml {
+                read_only: Some(vec![
+                    SeccompProfile::NoProcessSpawnOutsideAllowlist,
+                    SeccompProfile::NoPtrace,
+                    SeccompProfile::NoMount,
+                ]),
+                workspace_write: None,
+            }),
+            spawn_allowlist: vec![
+                AbsolutePathBuf::from_absolute_path("/usr/bin/git").expect("path"),
+            ],
+        })
+    }
+
+    fn command_rules(command: &[&str]) -> CommandRuleSandbox {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "test.rules",
+                r#"
+command_rule(pattern = ["cargo", "test"], seccomp_profiles = ["no-mount"])
+command_rule(pattern = ["gdb"], seccomp_profiles = ["no-ptrce"])
+"#,
+            )
+            .expect("parse rules");
+        let command = command.iter().map(ToString::to_string).collect::<Vec<_>>();
+        CommandRuleSandbox::for_command(
+            &parser.build(),
+            &command,
+            &std::env::temp_dir(),
+            &SandboxPolicy::new_read_only_policy(),
+            &HashMap::new(),
+        )
+    }
+
+    fn profiles(policy: &SandboxPolicy, command: &[&str]) -> Vec<SeccompProfile> {
+        seccomp()
+            .for_command(policy, &command_rules(command))
+            .expect("known profiles")
+            .profiles
+    }
+
+    #[test]
+    fn mode_profiles_replace_base_profiles() {
+        let ls = ["ls"];
+
+        assert_eq!(
+            seccomp()
+                .for_command(&SandboxPolicy::new_read_only_policy(), &command_rules(&ls))
+                .expect("known profiles"),
+            SeccompSelection {
+                profiles: vec![
+                    SeccompProfile::NoPtrace,
+                    SeccompProfile::NoMount,
+                    SeccompProfile::NoProcessSpawnOutsideAllowlist,
+                ],
+                spawn_allowlist: vec![
+                    AbsolutePathBuf::from_absolute_path("/usr/bin/git").expect("path")
+                ],
+            }
+        );
+        assert_eq!(
+            seccomp()
+                .for_command(
+                    &SandboxPolicy::new_workspace_write_policy(),
+                    &command_rules(&ls)
+                )
+                .expect("known profiles"),
+            SeccompSelection {
+                profiles: vec![SeccompProfile::NoPtrace],
+                spawn_allowlist: Vec::new(),
+            }
+        );
+        assert_eq!(profiles(&SandboxPolicy::DangerFullAccess, &ls), Vec::new());
+    }
+
+    #[test]
+    fn command_rule_requires_every_shell_sub_command_to_match() {
+        let policy = SandboxPolicy::new_read_only_policy();
+
+        assert_eq!(
+            profiles(&policy, &["bash", "-lc", "cargo test -p core"]),
+            vec![SeccompProfile::NoMount]
+        );
+        assert_eq!(
+            profiles(&policy, &["bash", "-lc", "cargo test && ls"]).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn unknown_rule_profile_is_rejected() {
+        let err = seccomp()
+            .for_command(
+                &SandboxPolicy::new_read_only_policy(),
+                &command_rules(&["gdb", "-p", "1"]),
+            )
+            .expect_err("unknown profile should be rejected");
+
+        assert_eq!(err, UnknownSeccompProfile("no-ptrce".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sigsys_under_profiles_is_a_seccomp_denial() {
+        let output = ExecToolCallOutput {
+            exit_code: 128 + libc::SIGSYS,
+            ..Default::default()
+        };
+        let profiles = [SeccompProfile::NoPtrace];
+
+        let denial = SeccompDenial::from_output(SandboxType::LinuxSeccomp, &profiles, &output)
+            .expect("seccomp denial");
+        assert_eq!(
+            denial.message(),
+            "Command was killed by the Linux sandbox for making a syscall blocked by seccomp profile `no-ptrace` (blocks ptrace, process_vm_readv, process_vm_writev, pidfd_getfd)."
+        );
+        assert_eq!(
+            SeccompDenial::from_output(SandboxType::LinuxSeccomp, &[], &output),
+            None
+        );
+        assert_eq!(
+            SeccompDenial::from_output(SandboxType::None, &profiles, &output),
+            None
+        );
+    }
+
+    #[test]
+    fn permission_denied_under_spawn_allowlist_is_a_seccomp_denial() {
+        let mut output = ExecToolCallOutput {
+            exit_code: 126,
+            ..Default::default()
+        };
+        output.aggregated_output.text =
+            "bash: line 1: /usr/bin/curl: Permission denied\n".to_string();
+        let profiles = [
+            SeccompProfile::NoPtrace,
+            SeccompProfile::NoProcessSpawnOutsideAllowlist,
+        ];
+
+        assert_eq!(
+            SeccompDenial::from_output(SandboxType::LinuxSeccomp, &profiles, &output),
+            Some(SeccompDenial {
+                profiles: vec![SeccompProfile::NoProcessSpawnOutsideAllowlist],
+            })
+        );
+        assert_eq!(
+            SeccompDenial::from_output(
+                SandboxType::LinuxSeccomp,
+                &[SeccompProfile::NoPtrace],
+                &output
+            ),
+            None
+        );
+    }
+}
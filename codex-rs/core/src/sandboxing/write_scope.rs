@@ -0,0 +1,101 @@
+use crate::config::types::SandboxWriteScopeToml;
+use crate::sandboxing::command_rules::CommandRuleSandbox;
+use codex_protocol::permissions::FileSystemWriteScope;
+
+/// Resolved `[sandbox_write_scope]` configuration.
+///
+/// Execpolicy `command_rule(write_scope = ...)` entries replace it for the
+/// commands they match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SandboxWriteScope {
+    base: FileSystemWriteScope,
+}
+
+impl SandboxWriteScope {
+    pub fn from_toml(toml: SandboxWriteScopeToml) -> Self {
+        Self {
+            base: FileSystemWriteScope {
+                read_only: toml.read_only,
+                deny_read: toml.deny_read,
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
+
+    /// Returns the write scope to enforce for a command, given the
+    /// `command_rule` overrides that match it.
+    pub fn for_command(&self, command_rules: &CommandRuleSandbox) -> FileSystemWriteScope {
+        let Some(write_scope) = command_rules.agreed(|sandbox| sandbox.write_scope.as_ref()) else {
+            return self.base.clone();
+        };
+        FileSystemWriteScope {
+            read_only: write_scope
+                .read_only
+                .unwrap_or_else(|| self.base.read_only.clone()),
+            deny_read: write_scope
+                .deny_read
+                .unwrap_or_else(|| self.base.deny_read.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::SandboxPolicy;
+    use codex_execpolicy::PolicyParser;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn write_scope() -> SandboxWriteScope {
+        SandboxWriteScope::from_toml(SandboxWriteScopeToml {
+            read_only: vec![".git/hooks".to_string(), "Cargo.lock".to_string()],
+            deny_read: vec![AbsolutePathBuf::from_absolute_path("/home/user/.ssh").expect("path")],
+        })
+    }
+
+    fn command_rules(command: &[&str]) -> CommandRuleSandbox {
+        let mut parser = PolicyParser::new();
+        parser
+            .parse(
+                "test.rules",
+                r#"command_rule(pattern = ["cargo", "update"], write_scope = {"read_only": [".git/hooks"]})"#,
+            )
+            .expect("parse rules");
+        let command = command.iter().map(ToString::to_string).collect::<Vec<_>>();
+        CommandRuleSandbox::for_command(
+            &parser.build(),
+            &command,
+            &std::env::temp_dir(),
+            &SandboxPolicy::new_workspace_write_policy(),
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn unmatched_command_uses_base_scope() {
+        let scope = write_scope();
+
+        assert_eq!(
+            scope.for_command(&command_rules(&["cargo", "build"])),
+            scope.base
+        );
+    }
+
+    #[test]
+    fn matching_rule_replaces_only_the_fields_it_sets() {
+        let scope = write_scope();
+
+        assert_eq!(
+            scope.for_command(&command_rules(&["cargo", "update", "-p", "serde"])),
+            FileSystemWriteScope {
+                read_only: vec![".git/hooks".to_string()],
+                deny_read: scope.base.deny_read,
+            }
+        );
+    }
+}
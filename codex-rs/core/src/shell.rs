@@ -4,6 +4,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::LazyLock;
 use tokio::sync::watch;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -13,6 +14,8 @@ pub enum ShellType {
     PowerShell,
     Sh,
     Cmd,
+    Fish,
+    Nushell,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,14 +38,34 @@ impl Shell {
             ShellType::PowerShell => "powershell",
             ShellType::Sh => "sh",
             ShellType::Cmd => "cmd",
+            ShellType::Fish => "fish",
+            ShellType::Nushell => "nushell",
+        }
+    }
+
+    /// The shell that runs commands for this session. Fish and Nushell
+    /// sessions run commands in a POSIX shell, since command parsing, safe
+    /// command detection and execpolicy only understand POSIX syntax; their
+    /// snapshot still carries the user's environment into it.
+    pub fn command_shell(&self) -> &Shell {
+        match self.shell_type {
+            ShellType::Fish | ShellType::Nushell => &POSIX_COMMAND_SHELL,
+            _ => self,
         }
     }
 
     /// Takes a string of shell and returns the full list of command args to
-    /// use with `exec()` to run the shell command.
+    /// use with `exec()` to run the shell command in [`Self::command_shell`].
     pub fn derive_exec_args(&self, command: &str, use_login_shell: bool) -> Vec<String> {
+        self.command_shell()
+            .native_exec_args(command, use_login_shell)
+    }
+
+    /// Like [`Self::derive_exec_args`], but always runs `command` in this
+    /// shell itself, e.g. to capture or validate its snapshot.
+    pub(crate) fn native_exec_args(&self, command: &str, use_login_shell: bool) -> Vec<String> {
         match self.shell_type {
-            ShellType::Zsh | ShellType::Bash | ShellType::Sh => {
+            ShellType::Zsh | ShellType::Bash | ShellType::Sh | ShellType::Fish => {
                 let arg = if use_login_shell { "-lc" } else { "-c" };
                 vec![
                     self.shell_path.to_string_lossy().to_string(),
@@ -66,6 +89,16 @@ impl Shell {
                 args.push(command.to_string());
                 args
             }
+            ShellType::Nushell => {
+                // Nushell does not accept combined short flags.
+                let mut args = vec![self.shell_path.to_string_lossy().to_string()];
+                if use_login_shell {
+                    args.push("--login".to_string());
+                }
+                args.push("-c".to_string());
+                args.push(command.to_string());
+                args
+            }
         }
     }
 
@@ -75,6 +108,32 @@ impl Shell {
     }
 }
 
+/// Quotes `input` as a fish single-quoted string, escaping backslashes and `'`.
+pub(crate) fn fish_single_quote(input: &str) -> String {
+    let escaped = input.replace('\\', "\\\\").replace('\'', "\\'");
+    format!("'{escaped}'")
+}
+
+/// Quotes `input` as a Nushell raw string (`r#'...'#`), adding `#`s until the
+/// delimiter cannot occur inside the input.
+pub(crate) fn nushell_raw_string(input: &str) -> String {
+    let mut hashes = String::from("#");
+    while input.contains(&format!("'{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}'{input}'{hashes}")
+}
+
+/// Runs commands for fish and Nushell sessions; see [`Shell::command_shell`].
+static POSIX_COMMAND_SHELL: LazyLock<Shell> = LazyLock::new(|| {
+    let posix_shell = if cfg!(target_os = "macos") {
+        get_shell(ShellType::Zsh, None).or_else(|| get_shell(ShellType::Bash, None))
+    } else {
+        get_shell(ShellType::Bash, None).or_else(|| get_shell(ShellType::Zsh, None))
+    };
+    posix_shell.unwrap_or_else(ultimate_fallback_shell)
+});
+
 pub(crate) fn empty_shell_snapshot_receiver() -> watch::Receiver<Option<Arc<ShellSnapshot>>> {
     let (_tx, rx) = watch::channel(None);
     rx
@@ -187,6 +246,40 @@ fn get_sh_shell(path: Option<&PathBuf>) -> Option<Shell> {
     })
 }
 
+fn get_fish_shell(path: Option<&PathBuf>) -> Option<Shell> {
+    let shell_path = get_shell_path(
+        ShellType::Fish,
+        path,
+        "fish",
+        vec![
+            "/usr/bin/fish",
+            "/usr/local/bin/fish",
+            "/opt/homebrew/bin/fish",
+        ],
+    );
+
+    shell_path.map(|shell_path| Shell {
+        shell_type: ShellType::Fish,
+        shell_path,
+        shell_snapshot: empty_shell_snapshot_receiver(),
+    })
+}
+
+fn get_nushell_shell(path: Option<&PathBuf>) -> Option<Shell> {
+    let shell_path = get_shell_path(
+        ShellType::Nushell,
+        path,
+        "nu",
+        vec!["/usr/bin/nu", "/usr/local/bin/nu", "/opt/homebrew/bin/nu"],
+    );
+
+    shell_path.map(|shell_path| Shell {
+        shell_type: ShellType::Nushell,
+        shell_path,
+        shell_snapshot: empty_shell_snapshot_receiver(),
+    })
+}
+
 fn get_powershell_shell(path: Option<&PathBuf>) -> Option<Shell> {
     let shell_path = get_shell_path(
         ShellType::PowerShell,
@@ -242,6 +335,8 @@ pub fn get_shell(shell_type: ShellType, path: Option<&PathBuf>) -> Option<Shell>
         ShellType::PowerShell => get_powershell_shell(path),
         ShellType::Sh => get_sh_shell(path),
         ShellType::Cmd => get_cmd_shell(path),
+        ShellType::Fish => get_fish_shell(path),
+        ShellType::Nushell => get_nushell_shell(path),
     }
 }
 
@@ -293,7 +388,18 @@ mod detect_shell_type_tests {
             detect_shell_type(&PathBuf::from("powershell")),
             Some(ShellType::PowerShell)
         );
-        assert_eq!(detect_shell_type(&PathBuf::from("fish")), None);
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("fish")),
+            Some(ShellType::Fish)
+        );
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("/opt/homebrew/bin/fish")),
+            Some(ShellType::Fish)
+        );
+        assert_eq!(
+            detect_shell_type(&PathBuf::from("/usr/bin/nu")),
+            Some(ShellType::Nushell)
+        );
         assert_eq!(detect_shell_type(&PathBuf::from("other")), None);
         assert_eq!(
             detect_shell_type(&PathBuf::from("/bin/zsh")),
@@ -358,7 +464,10 @@ mod tests {
 
     #[test]
     #[cfg(target_os = "macos")]
-    fn fish_fallback_to_zsh() {
+    fn missing_fish_falls_back_to_zsh() {
+        if get_shell(ShellType::Fish, None).is_some() {
+            return;
+        }
         let zsh_shell = default_user_shell_from_path(Some(PathBuf::from("/bin/fish")));
 
         let shell_path = zsh_shell.shell_path;
@@ -403,6 +512,8 @@ mod tests {
             assert!(shell_works(get_shell(ShellType::Zsh, None), cmd, false));
             assert!(shell_works(get_shell(ShellType::Bash, None), cmd, true));
             assert!(shell_works(get_shell(ShellType::Sh, None), cmd, true));
+            assert!(shell_works(get_shell(ShellType::Fish, None), cmd, false));
+            assert!(shell_works(get_shell(ShellType::Nushell, None), cmd, false));
         }
     }
 
@@ -464,6 +575,59 @@ mod tests {
             test_powershell_shell.derive_exec_args("echo hello", true),
             vec!["pwsh.exe", "-Command", "echo hello"]
         );
+
+        let test_fish_shell = Shell {
+            shell_type: ShellType::Fish,
+            shell_path: PathBuf::from("/usr/bin/fish"),
+            shell_snapshot: empty_shell_snapshot_receiver(),
+        };
+        assert_eq!(
+            test_fish_shell.native_exec_args("echo hello", true),
+            vec!["/usr/bin/fish", "-lc", "echo hello"]
+        );
+
+        let test_nushell_shell = Shell {
+            shell_type: ShellType::Nushell,
+            shell_path: PathBuf::from("/usr/bin/nu"),
+            shell_snapshot: empty_shell_snapshot_receiver(),
+        };
+        assert_eq!(
+            test_nushell_shell.native_exec_args("echo hello", false),
+            vec!["/usr/bin/nu", "-c", "echo hello"]
+        );
+        assert_eq!(
+            test_nushell_shell.native_exec_args("echo hello", true),
+            vec!["/usr/bin/nu", "--login", "-c", "echo hello"]
+        );
+    }
+
+    #[test]
+    fn fish_and_nushell_sessions_run_commands_in_a_posix_shell() {
+        for (shell_type, shell_path) in [
+            (ShellType::Fish, "/usr/bin/fish"),
+            (ShellType::Nushell, "/usr/bin/nu"),
+        ] {
+            let shell = Shell {
+                shell_type,
+                shell_path: PathBuf::from(shell_path),
+                shell_snapshot: empty_shell_snapshot_receiver(),
+            };
+            let command_shell = shell.command_shell();
+            assert!(matches!(
+                command_shell.shell_type,
+                ShellType::Bash | ShellType::Zsh | ShellType::Sh
+            ));
+
+            let command_shell_path = command_shell.shell_path.to_string_lossy().to_string();
+            assert_eq!(
+                shell.derive_exec_args("echo hello", true),
+                vec![
+                    command_shell_path,
+                    "-lc".to_string(),
+                    "echo hello".to_string()
+                ]
+            );
+        }
     }
 
     #[tokio::test]
@@ -9,7 +9,9 @@ use std::time::SystemTime;
 use crate::rollout::list::find_thread_path_by_id_str;
 use crate::shell::Shell;
 use crate::shell::ShellType;
+use crate::shell::fish_single_quote;
 use crate::shell::get_shell;
+use crate::shell::nushell_raw_string;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -118,6 +120,8 @@ impl ShellSnapshot {
         // File to store the snapshot
         let extension = match shell.shell_type {
             ShellType::PowerShell => "ps1",
+            ShellType::Fish => "fish",
+            ShellType::Nushell => "nu",
             _ => "sh",
         };
         let path = codex_home
@@ -227,6 +231,8 @@ async fn capture_snapshot(shell: &Shell, cwd: &Path) -> Result<String> {
         ShellType::Bash => run_shell_script(shell, &bash_snapshot_script(), cwd).await,
         ShellType::Sh => run_shell_script(shell, &sh_snapshot_script(), cwd).await,
         ShellType::PowerShell => run_shell_script(shell, powershell_snapshot_script(), cwd).await,
+        ShellType::Fish => run_shell_script(shell, &fish_snapshot_script(), cwd).await,
+        ShellType::Nushell => run_shell_script(shell, &nushell_snapshot_script(), cwd).await,
         ShellType::Cmd => bail!("Shell snapshotting is not yet supported for {shell_type:?}"),
     }
 }
@@ -242,7 +248,17 @@ fn strip_snapshot_preamble(snapshot: &str) -> Result<String> {
 
 async fn validate_snapshot(shell: &Shell, snapshot_path: &Path, cwd: &Path) -> Result<()> {
     let snapshot_path_display = snapshot_path.display();
-    let script = format!("set -e; . \"{snapshot_path_display}\"");
+    let script = match shell.shell_type {
+        ShellType::Fish => format!(
+            "source {}",
+            fish_single_quote(&snapshot_path.to_string_lossy())
+        ),
+        ShellType::Nushell => format!(
+            "source {}",
+            nushell_raw_string(&snapshot_path.to_string_lossy())
+        ),
+        _ => format!("set -e; . \"{snapshot_path_display}\""),
+    };
     run_script_with_timeout(shell, &script, SNAPSHOT_TIMEOUT, false, cwd)
         .await
         .map(|_| ())
@@ -259,7 +275,7 @@ async fn run_script_with_timeout(
     use_login_shell: bool,
     cwd: &Path,
 ) -> Result<String> {
-    let args = shell.derive_exec_args(script, use_login_shell);
+    let args = shell.native_exec_args(script, use_login_shell);
     let shell_name = shell.name();
 
     // Handler is kept as guard to control the drop. The `mut` pattern is required because .args()
@@ -448,6 +464,74 @@ fi
     script.replace("EXCLUDED_EXPORTS", &excluded)
 }
 
+fn fish_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS.join(" ");
+    // Aliases are functions in fish. Functions shipped with fish itself are
+    // skipped: every fish process autoloads them anyway.
+    let script = r##"echo '# Snapshot file'
+echo '# Functions'
+for name in (functions --names)
+    if string match -q -- "$__fish_data_dir/*" (functions --details $name)
+        continue
+    end
+    functions $name
+    echo ''
+end
+set -l export_names
+for name in (set --names --export)
+    if contains -- $name EXCLUDED_EXPORTS
+        continue
+    end
+    if string match -qr '^[A-Za-z_][A-Za-z0-9_]*$' -- $name
+        set -a export_names $name
+    end
+end
+echo '# exports' (count $export_names)
+for name in $export_names
+    echo set -gx $name (string escape -- $$name)
+end
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
+fn nushell_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS
+        .iter()
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    // Only string and list-of-string variables round-trip through `to nuon`;
+    // closures such as ENV_CONVERSIONS are re-created by the user's config.
+    // Custom commands are replayed from their source; one whose source cannot
+    // be shown is skipped rather than failing the whole snapshot.
+    let script = r##"let excluded = [EXCLUDED_EXPORTS, 'FILE_PWD', 'CURRENT_FILE', 'PROCESS_PATH', 'NU_VERSION', 'LAST_EXIT_CODE', 'CMD_DURATION_MS']
+let exports = ($env
+    | transpose name value
+    | where name not-in $excluded
+    | where name =~ '^[A-Za-z_][A-Za-z0-9_]*$'
+    | where ($it.value | describe) in ['string', 'list<string>']
+    | reduce --fold {} {|row, acc| $acc | upsert $row.name $row.value })
+let commands = (scope commands | where type == 'custom')
+let aliases = (scope aliases)
+print '# Snapshot file'
+print '# Functions'
+for command in $commands {
+    try {
+        print (view source $command.name)
+        print ''
+    }
+}
+print $"# aliases ($aliases | length)"
+for alias in $aliases {
+    print $"alias ($alias.name) = ($alias.expansion)"
+}
+print ''
+print $"# exports ($exports | columns | length)"
+print $"load-env ($exports | to nuon)"
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
 fn powershell_snapshot_script() -> &'static str {
     r##"$ErrorActionPreference = 'Stop'
 Write-Output '# Snapshot file'
@@ -843,6 +927,41 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn fish_snapshot_round_trips_exports_and_functions() -> Result<()> {
+        let Some(shell) = get_shell(ShellType::Fish, None) else {
+            return Ok(());
+        };
+        let dir = tempdir()?;
+        let path = dir.path().join("snapshot.fish");
+        write_shell_snapshot(ShellType::Fish, &path, dir.path()).await?;
+        let snapshot = fs::read_to_string(&path).await?;
+        assert!(snapshot.contains("# Snapshot file"));
+        assert!(snapshot.contains("# exports "));
+        assert!(snapshot.contains("set -gx PATH "));
+
+        validate_snapshot(&shell, &path, dir.path()).await
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn nushell_snapshot_round_trips_exports() -> Result<()> {
+        let Some(shell) = get_shell(ShellType::Nushell, None) else {
+            return Ok(());
+        };
+        let dir = tempdir()?;
+        let path = dir.path().join("snapshot.nu");
+        write_shell_snapshot(ShellType::Nushell, &path, dir.path()).await?;
+        let snapshot = fs::read_to_string(&path).await?;
+        assert!(snapshot.contains("# Snapshot file"));
+        assert!(snapshot.contains("# Functions"));
+        assert!(snapshot.contains("# aliases "));
+        assert!(snapshot.contains("load-env {"));
+
+        validate_snapshot(&shell, &path, dir.path()).await
+    }
+
     #[cfg(target_os = "windows")]
     #[ignore]
     #[tokio::test]
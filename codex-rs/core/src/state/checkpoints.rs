@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use codex_git::GhostCommit;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::Checkpoint;
+
+/// Undo/redo bookkeeping for a session.
+///
+/// The checkpoints themselves are the `GhostSnapshot` items in history (one per
+/// turn, newest last), each naming the turn that captured it so the mapping is
+/// persisted with the rollout. This holds what history does not: the diff each
+/// turn produced and the redo stack.
+#[derive(Debug, Default)]
+pub(crate) struct CheckpointTimeline {
+    diff_by_turn: HashMap<String, String>,
+    redo: Vec<RedoEntry>,
+}
+
+/// State needed to reverse one undo.
+#[derive(Debug, Clone)]
+pub(crate) struct RedoEntry {
+    /// Working tree as it was right before the undo.
+    pub(crate) snapshot: GhostCommit,
+    /// Snapshot items the undo removed from history, with their original
+    /// indices in ascending order.
+    pub(crate) removed: Vec<(usize, ResponseItem)>,
+}
+
+impl CheckpointTimeline {
+    /// Records that a new checkpoint was captured. It starts a new branch of
+    /// the timeline, so pending redos are dropped.
+    pub(crate) fn record_snapshot(&mut self) {
+        self.redo.clear();
+    }
+
+    /// Stores the latest cumulative diff for `turn_id`.
+    pub(crate) fn record_turn_diff(&mut self, turn_id: &str, unified_diff: String) {
+        self.diff_by_turn.insert(turn_id.to_string(), unified_diff);
+    }
+
+    pub(crate) fn push_redo(&mut self, entry: RedoEntry) {
+        self.redo.push(entry);
+    }
+
+    pub(crate) fn pop_redo(&mut self) -> Option<RedoEntry> {
+        self.redo.pop()
+    }
+
+    pub(crate) fn redo_depth(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Lists the checkpoints in `history`, newest first.
+    pub(crate) fn checkpoints(&self, history: &[ResponseItem]) -> Vec<Checkpoint> {
+        history
+            .iter()
+            .rev()
+            .filter_map(|item| match item {
+                ResponseItem::GhostSnapshot { ghost_commit } => Some(ghost_commit),
+                _ => None,
+            })
+            .zip(1u32..)
+            .map(|(ghost_commit, turns_back)| {
+                let turn_id = ghost_commit.turn_id().map(ToString::to_string);
+                let diff = turn_id
+                    .as_ref()
+                    .and_then(|turn_id| self.diff_by_turn.get(turn_id))
+                    .cloned();
+                Checkpoint {
+                    turns_back,
+                    snapshot_id: ghost_commit.id().to_string(),
+                    turn_id,
+                    diff,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Removes the `num_turns` newest snapshot items from `items`. Returns the
+/// removed items with their original indices (ascending), or `None` when there
+/// are fewer than `num_turns` snapshots.
+pub(crate) fn take_newest_snapshots(
+    items: &mut Vec<ResponseItem>,
+    num_turns: usize,
+) -> Option<Vec<(usize, ResponseItem)>> {
+    // Newest first, so removing in this order keeps the remaining indices valid.
+    let indices: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, item)| matches!(item, ResponseItem::GhostSnapshot { .. }))
+        .map(|(idx, _)| idx)
+        .take(num_turns)
+        .collect();
+    if num_turns == 0 || indices.len() < num_turns {
+        return None;
+    }
+    let mut removed: Vec<(usize, ResponseItem)> = indices
+        .into_iter()
+        .map(|idx| (idx, items.remove(idx)))
+        .collect();
+    removed.reverse();
+    Some(removed)
+}
+
+/// Puts items removed by [`take_newest_snapshots`] back where they were.
+pub(crate) fn restore_snapshots(
+    items: &mut Vec<ResponseItem>,
+    removed: Vec<(usize, ResponseItem)>,
+) {
+    for (idx, item) in removed {
+        items.insert(idx.min(items.len()), item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    fn snapshot(id: &str) -> ResponseItem {
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new()),
+        }
+    }
+
+    fn turn_snapshot(id: &str, turn_id: &str) -> ResponseItem {
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new())
+                .with_turn_id(turn_id.to_string()),
+        }
+    }
+
+    fn message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn take_and_restore_round_trip() {
+        let original = vec![
+            message("one"),
+            snapshot("a"),
+            message("two"),
+            snapshot("b"),
+            message("three"),
+            snapshot("c"),
+            message("four"),
+        ];
+        let mut items = original.clone();
+
+        let removed = take_newest_snapshots(&mut items, 2).expect("two snapshots available");
+        assert_eq!(
+            removed.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+        assert_eq!(
+            items,
+            vec![
+                message("one"),
+                snapshot("a"),
+                message("two"),
+                message("three"),
+                message("four"),
+            ]
+        );
+
+        restore_snapshots(&mut items, removed);
+        assert_eq!(items, original);
+    }
+
+    #[test]
+    fn take_fails_without_enough_snapshots() {
+        let mut items = vec![snapshot("a"), message("one")];
+        assert_eq!(take_newest_snapshots(&mut items, 2), None);
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn checkpoints_are_newest_first_with_turn_diffs() {
+        let mut timeline = CheckpointTimeline::default();
+        timeline.record_turn_diff("turn-2", "diff for turn 2".to_string());
+
+        let history = vec![
+            turn_snapshot("a", "turn-1"),
+            message("one"),
+            turn_snapshot("b", "turn-2"),
+        ];
+        assert_eq!(
+            timeline.checkpoints(&history),
+            vec![
+                Checkpoint {
+                    turns_back: 1,
+                    snapshot_id: "b".to_string(),
+                    turn_id: Some("turn-2".to_string()),
+                    diff: Some("diff for turn 2".to_string()),
+                },
+                Checkpoint {
+                    turns_back: 2,
+                    snapshot_id: "a".to_string(),
+                    turn_id: Some("turn-1".to_string()),
+                    diff: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn new_snapshot_clears_redo() {
+        let mut timeline = CheckpointTimeline::default();
+        timeline.push_redo(RedoEntry {
+            snapshot: GhostCommit::new("current".to_string(), None, Vec::new(), Vec::new()),
+            removed: Vec::new(),
+        });
+        assert_eq!(timeline.redo_depth(), 1);
+
+        timeline.record_snapshot();
+        assert_eq!(timeline.redo_depth(), 0);
+    }
+}
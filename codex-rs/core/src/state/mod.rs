@@ -1,7 +1,12 @@
+mod checkpoints;
 mod service;
 mod session;
 mod turn;
 
+pub(crate) use checkpoints::CheckpointTimeline;
+pub(crate) use checkpoints::RedoEntry;
+pub(crate) use checkpoints::restore_snapshots;
+pub(crate) use checkpoints::take_newest_snapshots;
 pub(crate) use service::SessionServices;
 pub(crate) use session::SessionState;
 pub(crate) use turn::ActiveTurn;
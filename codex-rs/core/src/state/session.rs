@@ -9,9 +9,11 @@ use crate::codex::PreviousTurnSettings;
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
 use crate::error::Result as CodexResult;
+use crate::project_doc::ScopedProjectDocs;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::state::CheckpointTimeline;
 use crate::tasks::RegularTask;
 use crate::truncate::TruncationPolicy;
 use codex_protocol::protocol::TurnContextItem;
@@ -32,6 +34,9 @@ pub(crate) struct SessionState {
     pub(crate) startup_regular_task: Option<JoinHandle<CodexResult<RegularTask>>>,
     pub(crate) active_mcp_tool_selection: Option<Vec<String>>,
     pub(crate) active_connector_selection: HashSet<String>,
+    pub(crate) checkpoints: CheckpointTimeline,
+    /// Directory-scoped project docs already added to the history.
+    pub(crate) scoped_project_docs: ScopedProjectDocs,
 }
 
 impl SessionState {
@@ -49,6 +54,8 @@ impl SessionState {
             startup_regular_task: None,
             active_mcp_tool_selection: None,
             active_connector_selection: HashSet::new(),
+            checkpoints: CheckpointTimeline::default(),
+            scoped_project_docs: ScopedProjectDocs::default(),
         }
     }
 
@@ -83,6 +90,8 @@ impl SessionState {
         self.history.replace(items);
         self.history
             .set_reference_context_item(reference_context_item);
+        // Compaction drops injected docs, so let them be loaded again.
+        self.scoped_project_docs = ScopedProjectDocs::default();
     }
 
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
@@ -93,6 +93,7 @@ impl SessionTask for GhostSnapshotTask {
                     {
                         Ok(Ok((ghost_commit, report))) => {
                             info!("ghost snapshot blocking task finished");
+                            let ghost_commit = ghost_commit.with_turn_id(ctx.sub_id.clone());
                             if warnings_enabled {
                                 for message in format_snapshot_warnings(
                                     ghost_snapshot.ignore_large_untracked_files,
@@ -114,6 +115,10 @@ impl SessionTask for GhostSnapshotTask {
                                     ghost_commit: ghost_commit.clone(),
                                 }])
                                 .await;
+                            session
+                                .session
+                                .record_checkpoint()
+                                .await;
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
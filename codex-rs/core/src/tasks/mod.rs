@@ -45,6 +45,7 @@ pub(crate) use compact::CompactTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
+pub(crate) use undo::UndoAction;
 pub(crate) use undo::UndoTask;
 pub(crate) use user_shell::UserShellCommandMode;
 pub(crate) use user_shell::UserShellCommandTask;
@@ -205,7 +206,11 @@ impl Session {
             self.handle_task_abort(task, reason.clone()).await;
         }
         if reason == TurnAbortReason::Interrupted {
-            self.close_unified_exec_processes().await;
+            // Persistent jobs outlive interrupts; `/clean` and shutdown handle them.
+            self.services
+                .unified_exec_manager
+                .terminate_transient_processes()
+                .await;
         }
     }
 
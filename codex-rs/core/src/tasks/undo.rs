@@ -4,11 +4,18 @@ use crate::codex::TurnContext;
 use crate::protocol::EventMsg;
 use crate::protocol::UndoCompletedEvent;
 use crate::protocol::UndoStartedEvent;
+use crate::state::RedoEntry;
 use crate::state::TaskKind;
+use crate::state::restore_snapshots;
+use crate::state::take_newest_snapshots;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use async_trait::async_trait;
+use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
+use codex_git::GitToolingError;
 use codex_git::RestoreGhostCommitOptions;
+use codex_git::create_ghost_commit;
 use codex_git::restore_ghost_commit_with_options;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
@@ -17,11 +24,22 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-pub(crate) struct UndoTask;
+/// Which way to move through the checkpoint timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UndoAction {
+    /// Restore the checkpoint taken `num_turns` turns ago.
+    Undo { num_turns: u32 },
+    /// Reapply the most recent undo.
+    Redo,
+}
+
+pub(crate) struct UndoTask {
+    action: UndoAction,
+}
 
 impl UndoTask {
-    pub(crate) fn new() -> Self {
-        Self
+    pub(crate) fn new(action: UndoAction) -> Self {
+        Self { action }
     }
 }
 
@@ -42,16 +60,20 @@ impl SessionTask for UndoTask {
         _input: Vec<UserInput>,
         cancellation_token: CancellationToken,
     ) -> Option<String> {
+        let (counter, verb) = match self.action {
+            UndoAction::Undo { .. } => ("codex.task.undo", "Undo"),
+            UndoAction::Redo => ("codex.task.redo", "Redo"),
+        };
         let _ = session
             .session
             .services
             .session_telemetry
-            .counter("codex.task.undo", 1, &[]);
+            .counter(counter, 1, &[]);
         let sess = session.clone_session();
         sess.send_event(
             ctx.as_ref(),
             EventMsg::UndoStarted(UndoStartedEvent {
-                message: Some("Undo in progress...".to_string()),
+                message: Some(format!("{verb} in progress...")),
             }),
         )
         .await;
@@ -61,71 +83,156 @@ impl SessionTask for UndoTask {
                 ctx.as_ref(),
                 EventMsg::UndoCompleted(UndoCompletedEvent {
                     success: false,
-                    message: Some("Undo cancelled.".to_string()),
+                    message: Some(format!("{verb} cancelled.")),
                 }),
             )
             .await;
             return None;
         }
 
-        let history = sess.clone_history().await;
-        let mut items = history.raw_items().to_vec();
-        let mut completed = UndoCompletedEvent {
-            success: false,
-            message: None,
+        let completed = match self.action {
+            UndoAction::Undo { num_turns } => undo(&session, &ctx, num_turns).await,
+            UndoAction::Redo => redo(&session, &ctx).await,
         };
+        sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+            .await;
+        None
+    }
+}
 
-        let Some((idx, ghost_commit)) =
-            items
-                .iter()
-                .enumerate()
-                .rev()
-                .find_map(|(idx, item)| match item {
-                    ResponseItem::GhostSnapshot { ghost_commit } => {
-                        Some((idx, ghost_commit.clone()))
-                    }
-                    _ => None,
-                })
-        else {
-            completed.message = Some("No ghost snapshot available to undo.".to_string());
-            sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
-                .await;
-            return None;
+async fn undo(
+    session: &SessionTaskContext,
+    ctx: &Arc<TurnContext>,
+    num_turns: u32,
+) -> UndoCompletedEvent {
+    let sess = session.clone_session();
+    let history = sess.clone_history().await;
+    let mut items = history.raw_items().to_vec();
+    let available = items
+        .iter()
+        .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
+        .count();
+    let Some(removed) = take_newest_snapshots(&mut items, num_turns as usize) else {
+        let message = if available == 0 {
+            "No ghost snapshot available to undo.".to_string()
+        } else if num_turns == 0 {
+            "Undo needs at least one turn.".to_string()
+        } else {
+            format!("Cannot undo {num_turns} turns; only {available} snapshots are available.")
         };
+        return failed(message);
+    };
+    let Some(ResponseItem::GhostSnapshot { ghost_commit }) =
+        removed.first().map(|(_, item)| item.clone())
+    else {
+        return failed("No ghost snapshot available to undo.".to_string());
+    };
 
-        let commit_id = ghost_commit.id().to_string();
-        let repo_path = ctx.cwd.clone();
-        let ghost_snapshot = ctx.ghost_snapshot.clone();
-        let restore_result = tokio::task::spawn_blocking(move || {
-            let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
-            restore_ghost_commit_with_options(&options, &ghost_commit)
-        })
-        .await;
+    // Capture where we are now so the undo can be redone. Failing to do so
+    // should not block the undo itself.
+    let current = match capture(ctx).await {
+        Ok(current) => Some(current),
+        Err(message) => {
+            warn!("undo will not be redoable: {message}");
+            None
+        }
+    };
+
+    let commit_id = ghost_commit.id().to_string();
+    if let Err(message) = restore(ctx, ghost_commit).await {
+        return failed(format!("Failed to restore snapshot {commit_id}: {message}"));
+    }
+
+    let reference_context_item = sess.reference_context_item().await;
+    sess.replace_history(items, reference_context_item).await;
+    if let Some(snapshot) = current {
+        sess.push_redo(RedoEntry { snapshot, removed }).await;
+    }
+    info!(
+        commit_id = commit_id,
+        num_turns, "Undo restored ghost snapshot"
+    );
+    let short_id = short_id(&commit_id);
+    let message = if num_turns == 1 {
+        format!("Undo restored snapshot {short_id}.")
+    } else {
+        format!("Undo restored snapshot {short_id} ({num_turns} turns back).")
+    };
+    UndoCompletedEvent {
+        success: true,
+        message: Some(message),
+    }
+}
 
-        match restore_result {
-            Ok(Ok(())) => {
-                items.remove(idx);
-                let reference_context_item = sess.reference_context_item().await;
-                sess.replace_history(items, reference_context_item).await;
-                let short_id: String = commit_id.chars().take(7).collect();
-                info!(commit_id = commit_id, "Undo restored ghost snapshot");
-                completed.success = true;
-                completed.message = Some(format!("Undo restored snapshot {short_id}."));
-            }
-            Ok(Err(err)) => {
-                let message = format!("Failed to restore snapshot {commit_id}: {err}");
-                warn!("{message}");
-                completed.message = Some(message);
-            }
-            Err(err) => {
-                let message = format!("Failed to restore snapshot {commit_id}: {err}");
-                error!("{message}");
-                completed.message = Some(message);
-            }
+async fn redo(session: &SessionTaskContext, ctx: &Arc<TurnContext>) -> UndoCompletedEvent {
+    let sess = session.clone_session();
+    let Some(entry) = sess.pop_redo().await else {
+        return failed("Nothing to redo.".to_string());
+    };
+
+    let commit_id = entry.snapshot.id().to_string();
+    if let Err(message) = restore(ctx, entry.snapshot.clone()).await {
+        sess.push_redo(entry).await;
+        return failed(format!("Failed to restore snapshot {commit_id}: {message}"));
+    }
+
+    let history = sess.clone_history().await;
+    let mut items = history.raw_items().to_vec();
+    restore_snapshots(&mut items, entry.removed);
+    let reference_context_item = sess.reference_context_item().await;
+    sess.replace_history(items, reference_context_item).await;
+    info!(commit_id = commit_id, "Redo restored ghost snapshot");
+    UndoCompletedEvent {
+        success: true,
+        message: Some(format!("Redo restored snapshot {}.", short_id(&commit_id))),
+    }
+}
+
+async fn capture(ctx: &Arc<TurnContext>) -> Result<GhostCommit, String> {
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let options = CreateGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+        create_ghost_commit(&options)
+    })
+    .await;
+    flatten(result)
+}
+
+async fn restore(ctx: &Arc<TurnContext>, ghost_commit: GhostCommit) -> Result<(), String> {
+    let repo_path = ctx.cwd.clone();
+    let ghost_snapshot = ctx.ghost_snapshot.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let options = RestoreGhostCommitOptions::new(&repo_path).ghost_snapshot(ghost_snapshot);
+        restore_ghost_commit_with_options(&options, &ghost_commit)
+    })
+    .await;
+    flatten(result)
+}
+
+fn flatten<T>(
+    result: Result<Result<T, GitToolingError>, tokio::task::JoinError>,
+) -> Result<T, String> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => {
+            warn!("{err}");
+            Err(err.to_string())
+        }
+        Err(err) => {
+            error!("{err}");
+            Err(err.to_string())
         }
+    }
+}
 
-        sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
-            .await;
-        None
+fn failed(message: String) -> UndoCompletedEvent {
+    UndoCompletedEvent {
+        success: false,
+        message: Some(message),
     }
 }
+
+fn short_id(commit_id: &str) -> String {
+    commit_id.chars().take(7).collect()
+}
@@ -173,12 +173,14 @@ pub(crate) async fn execute_user_shell_command(
         network_sandbox_policy: NetworkSandboxPolicy::from(&sandbox_policy),
         justification: None,
         arg0: None,
+        seccomp_profiles: Vec::new(),
     };
 
     let stdout_stream = Some(StdoutStream {
         sub_id: turn_context.sub_id.clone(),
         call_id: call_id.clone(),
         tx_event: session.get_tx_event(),
+        output_redactor: session.services.secret_env.output_redactor().cloned(),
     });
 
     let exec_result = execute_exec_request(exec_env, &sandbox_policy, stdout_stream, None)
@@ -223,6 +225,7 @@ pub(crate) async fn execute_user_shell_command(
                         duration: Duration::ZERO,
                         formatted_output: aborted_message,
                         status: ExecCommandStatus::Failed,
+                        seccomp_denial: None,
                     }),
                 )
                 .await;
@@ -254,6 +257,7 @@ pub(crate) async fn execute_user_shell_command(
                         } else {
                             ExecCommandStatus::Failed
                         },
+                        seccomp_denial: None,
                     }),
                 )
                 .await;
@@ -294,6 +298,7 @@ pub(crate) async fn execute_user_shell_command(
                             turn_context.truncation_policy,
                         ),
                         status: ExecCommandStatus::Failed,
+                        seccomp_denial: None,
                     }),
                 )
                 .await;
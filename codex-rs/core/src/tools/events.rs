@@ -14,7 +14,7 @@ use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::PatchApplyStatus;
-use crate::protocol::TurnDiffEvent;
+use crate::sandboxing::seccomp::SeccompDenial;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
@@ -57,6 +57,7 @@ pub(crate) enum ToolEventStage {
 
 pub(crate) enum ToolEventFailure {
     Output(ExecToolCallOutput),
+    SeccompDenied(ExecToolCallOutput, SeccompDenial),
     Message(String),
     Rejected(String),
 }
@@ -97,6 +98,7 @@ pub(crate) enum ToolEmitter {
     },
     ApplyPatch {
         changes: HashMap<PathBuf, FileChange>,
+        skipped: Vec<PathBuf>,
         auto_approved: bool,
     },
     UnifiedExec {
@@ -125,9 +127,14 @@ impl ToolEmitter {
         }
     }
 
-    pub fn apply_patch(changes: HashMap<PathBuf, FileChange>, auto_approved: bool) -> Self {
+    pub fn apply_patch(
+        changes: HashMap<PathBuf, FileChange>,
+        skipped: Vec<PathBuf>,
+        auto_approved: bool,
+    ) -> Self {
         Self::ApplyPatch {
             changes,
+            skipped,
             auto_approved,
         }
     }
@@ -171,6 +178,7 @@ impl ToolEmitter {
             (
                 Self::ApplyPatch {
                     changes,
+                    skipped,
                     auto_approved,
                 },
                 ToolEventStage::Begin,
@@ -187,6 +195,7 @@ impl ToolEmitter {
                             turn_id: ctx.turn.sub_id.clone(),
                             auto_approved: *auto_approved,
                             changes: changes.clone(),
+                            skipped: skipped.clone(),
                         }),
                     )
                     .await;
@@ -208,7 +217,9 @@ impl ToolEmitter {
             }
             (
                 Self::ApplyPatch { changes, .. },
-                ToolEventStage::Failure(ToolEventFailure::Output(output)),
+                ToolEventStage::Failure(
+                    ToolEventFailure::Output(output) | ToolEventFailure::SeccompDenied(output, _),
+                ),
             ) => {
                 emit_patch_end(
                     ctx,
@@ -313,6 +324,19 @@ impl ToolEmitter {
                 };
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                output,
+                seccomp_denial: Some(seccomp_denial),
+                ..
+            }))) => {
+                let response = self.format_exec_output_for_model(&output, ctx);
+                let event = ToolEventStage::Failure(ToolEventFailure::SeccompDenied(
+                    *output,
+                    seccomp_denial,
+                ));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
                 let response = self.format_exec_output_for_model(&output, ctx);
@@ -393,6 +417,30 @@ struct ExecCommandResult {
     duration: Duration,
     formatted_output: String,
     status: ExecCommandStatus,
+    seccomp_denial: Option<SeccompDenial>,
+}
+
+impl ExecCommandResult {
+    fn from_output(
+        output: &ExecToolCallOutput,
+        seccomp_denial: Option<SeccompDenial>,
+        ctx: ToolEventCtx<'_>,
+    ) -> Self {
+        Self {
+            stdout: output.stdout.text.clone(),
+            stderr: output.stderr.text.clone(),
+            aggregated_output: output.aggregated_output.text.clone(),
+            exit_code: output.exit_code,
+            duration: output.duration,
+            formatted_output: format_exec_output_str(output, ctx.turn.truncation_policy),
+            status: if output.exit_code == 0 {
+                ExecCommandStatus::Completed
+            } else {
+                ExecCommandStatus::Failed
+            },
+            seccomp_denial,
+        }
+    }
 }
 
 async fn emit_exec_stage(
@@ -415,19 +463,11 @@ async fn emit_exec_stage(
         }
         ToolEventStage::Success(output)
         | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
-            let exec_result = ExecCommandResult {
-                stdout: output.stdout.text.clone(),
-                stderr: output.stderr.text.clone(),
-                aggregated_output: output.aggregated_output.text.clone(),
-                exit_code: output.exit_code,
-                duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
-                status: if output.exit_code == 0 {
-                    ExecCommandStatus::Completed
-                } else {
-                    ExecCommandStatus::Failed
-                },
-            };
+            let exec_result = ExecCommandResult::from_output(&output, None, ctx);
+            emit_exec_end(ctx, exec_input, exec_result).await;
+        }
+        ToolEventStage::Failure(ToolEventFailure::SeccompDenied(output, seccomp_denial)) => {
+            let exec_result = ExecCommandResult::from_output(&output, Some(seccomp_denial), ctx);
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
         ToolEventStage::Failure(ToolEventFailure::Message(message)) => {
@@ -440,6 +480,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Failed,
+                seccomp_denial: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -453,6 +494,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Declined,
+                seccomp_denial: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -483,6 +525,9 @@ async fn emit_exec_end(
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
                 status: exec_result.status,
+                seccomp_denial: exec_result
+                    .seccomp_denial
+                    .map(|seccomp_denial| seccomp_denial.profiles),
             }),
         )
         .await;
@@ -496,6 +541,11 @@ async fn emit_patch_end(
     success: bool,
     status: PatchApplyStatus,
 ) {
+    let edited_paths: Vec<PathBuf> = if success {
+        changes.keys().map(|path| ctx.turn.cwd.join(path)).collect()
+    } else {
+        Vec::new()
+    };
     ctx.session
         .send_event(
             ctx.turn,
@@ -517,9 +567,11 @@ async fn emit_patch_end(
             guard.get_unified_diff()
         };
         if let Ok(Some(unified_diff)) = unified_diff {
-            ctx.session
-                .send_event(ctx.turn, EventMsg::TurnDiff(TurnDiffEvent { unified_diff }))
-                .await;
+            ctx.session.send_turn_diff(ctx.turn, unified_diff).await;
         }
     }
+
+    ctx.session
+        .load_scoped_project_docs(ctx.turn, &edited_paths)
+        .await;
 }
@@ -106,7 +106,11 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
+        match codex_apply_patch::maybe_parse_apply_patch_verified_with_options(
+            &command,
+            &cwd,
+            &turn.config.apply_patch,
+        ) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
                 match apply_patch::apply_patch(turn.as_ref(), changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
@@ -119,8 +123,11 @@ impl ToolHandler for ApplyPatchHandler {
                     InternalApplyPatchInvocation::DelegateToExec(apply) => {
                         let changes = convert_apply_patch_to_protocol(&apply.action);
                         let file_paths = file_paths_for_action(&apply.action);
-                        let emitter =
-                            ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
+                        let emitter = ToolEmitter::apply_patch(
+                            changes.clone(),
+                            apply.action.skipped.clone(),
+                            apply.auto_approved,
+                        );
                         let event_ctx = ToolEventCtx::new(
                             session.as_ref(),
                             turn.as_ref(),
@@ -136,6 +143,7 @@ impl ToolHandler for ApplyPatchHandler {
                             exec_approval_requirement: apply.exec_approval_requirement,
                             timeout_ms: None,
                             codex_exe: turn.codex_linux_sandbox_exe.clone(),
+                            match_options: turn.config.apply_patch,
                         };
 
                         let mut orchestrator = ToolOrchestrator::new();
@@ -201,7 +209,11 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    match codex_apply_patch::maybe_parse_apply_patch_verified_with_options(
+        command,
+        cwd,
+        &turn.config.apply_patch,
+    ) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
@@ -222,7 +234,11 @@ pub(crate) async fn intercept_apply_patch(
                 InternalApplyPatchInvocation::DelegateToExec(apply) => {
                     let changes = convert_apply_patch_to_protocol(&apply.action);
                     let approval_keys = file_paths_for_action(&apply.action);
-                    let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
+                    let emitter = ToolEmitter::apply_patch(
+                        changes.clone(),
+                        apply.action.skipped.clone(),
+                        apply.auto_approved,
+                    );
                     let event_ctx = ToolEventCtx::new(
                         session.as_ref(),
                         turn.as_ref(),
@@ -238,6 +254,7 @@ pub(crate) async fn intercept_apply_patch(
                         exec_approval_requirement: apply.exec_approval_requirement,
                         timeout_ms,
                         codex_exe: turn.codex_linux_sandbox_exe.clone(),
+                        match_options: turn.config.apply_patch,
                     };
 
                     let mut orchestrator = ToolOrchestrator::new();
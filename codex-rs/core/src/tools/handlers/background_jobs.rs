@@ -0,0 +1,182 @@
+use std::fmt::Write as _;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::BackgroundJob;
+use codex_protocol::protocol::BackgroundJobStatus;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::unified_exec::search_log;
+
+pub struct BackgroundJobsHandler;
+
+const DEFAULT_MAX_LINES: usize = 200;
+const MAX_LINES: usize = 2_000;
+/// How much of the end of a job's log is searched.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+fn default_max_lines() -> usize {
+    DEFAULT_MAX_LINES
+}
+
+#[derive(Deserialize)]
+struct BackgroundJobsArgs {
+    // Matches the `session_id` naming of `exec_command` and `write_stdin`.
+    #[serde(default)]
+    session_id: Option<i32>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default = "default_max_lines")]
+    max_lines: usize,
+}
+
+#[async_trait]
+impl ToolHandler for BackgroundJobsHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "background_jobs handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: BackgroundJobsArgs = parse_arguments(&arguments)?;
+
+        let Some(session_id) = args.session_id else {
+            let jobs = session.list_background_jobs().await.jobs;
+            let body = if jobs.is_empty() {
+                "No background jobs.".to_string()
+            } else {
+                format_jobs(&jobs)
+            };
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(body),
+                success: Some(true),
+            });
+        };
+
+        if args.max_lines == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "max_lines must be greater than zero".to_string(),
+            ));
+        }
+        let pattern = args
+            .pattern
+            .as_deref()
+            .filter(|pattern| !pattern.is_empty())
+            .map(regex_lite::Regex::new)
+            .transpose()
+            .map_err(|err| FunctionCallError::RespondToModel(format!("invalid pattern: {err}")))?;
+
+        let (log, truncated) = session
+            .job_store()
+            .await
+            .read_log(&session_id.to_string(), MAX_LOG_BYTES)
+            .await
+            .map_err(|_| {
+                FunctionCallError::RespondToModel(format!(
+                    "no log found for background job {session_id}"
+                ))
+            })?;
+        let log = String::from_utf8_lossy(&log);
+        let lines = search_log(&log, pattern.as_ref(), args.max_lines.min(MAX_LINES));
+        if lines.is_empty() {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text("No matching lines.".to_string()),
+                success: Some(false),
+            });
+        }
+
+        let mut body = String::new();
+        if truncated {
+            let _ = writeln!(
+                body,
+                "(only the last {} KiB of the log were searched; line numbers count from there)",
+                MAX_LOG_BYTES / 1024
+            );
+        }
+        for (line_number, line) in lines {
+            let _ = writeln!(body, "{line_number}: {line}");
+        }
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(body),
+            success: Some(true),
+        })
+    }
+}
+
+/// Renders one line per job:
+///
+/// ```text
+/// 1234 running npm run dev (cwd /repo, log /home/me/.codex/background_jobs/…/1234.log)
+/// ```
+fn format_jobs(jobs: &[BackgroundJob]) -> String {
+    let mut output = String::new();
+    for job in jobs {
+        let status = match (job.status, job.exit_code) {
+            (BackgroundJobStatus::Running, _) => "running".to_string(),
+            (BackgroundJobStatus::Exited, Some(code)) => format!("exited({code})"),
+            (BackgroundJobStatus::Exited, None) => "killed".to_string(),
+            (BackgroundJobStatus::Lost, _) => "lost".to_string(),
+        };
+        let _ = writeln!(
+            output,
+            "{} {status} {} (cwd {}, log {})",
+            job.process_id,
+            job.command.join(" "),
+            job.cwd.display(),
+            job.log_path.display()
+        );
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_jobs;
+    use codex_protocol::protocol::BackgroundJob;
+    use codex_protocol::protocol::BackgroundJobStatus;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn formats_jobs_with_status_and_log_path() {
+        let job = |process_id: &str, status, exit_code| BackgroundJob {
+            process_id: process_id.to_string(),
+            command: vec!["npm".to_string(), "run".to_string(), "dev".to_string()],
+            cwd: PathBuf::from("/repo"),
+            started_at: 0,
+            status,
+            exit_code,
+            log_path: PathBuf::from(format!("/logs/{process_id}.log")),
+        };
+        let jobs = vec![
+            job("1000", BackgroundJobStatus::Running, None),
+            job("1001", BackgroundJobStatus::Exited, Some(1)),
+            job("1002", BackgroundJobStatus::Lost, None),
+        ];
+
+        assert_eq!(
+            format_jobs(&jobs),
+            "1000 running npm run dev (cwd /repo, log /logs/1000.log)\n\
+             1001 exited(1) npm run dev (cwd /repo, log /logs/1001.log)\n\
+             1002 lost npm run dev (cwd /repo, log /logs/1002.log)\n"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_code_search::CodeIndex;
+use codex_code_search::Language;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_state::CodeSymbol;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct CodeSearchHandler;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+/// How long a query waits for the index to catch up before answering from
+/// what is already indexed. The refresh keeps running in the background.
+const REFRESH_DEADLINE: Duration = Duration::from_secs(10);
+
+const STALE_INDEX_NOTE: &str =
+    "Note: the code index is still being updated; results may be incomplete.";
+
+type SharedRefresh = Shared<BoxFuture<'static, Result<(), String>>>;
+
+/// In-flight index refreshes keyed by root, so concurrent queries against the
+/// same workspace share one refresh instead of racing each other.
+static REFRESHES: LazyLock<StdMutex<HashMap<PathBuf, SharedRefresh>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum CodeSearchQuery {
+    Definition,
+    References,
+    Outline,
+}
+
+#[derive(Deserialize)]
+struct CodeSearchArgs {
+    query: CodeSearchQuery,
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for CodeSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: CodeSearchArgs = parse_arguments(&arguments)?;
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let limit = args.limit.min(MAX_LIMIT);
+
+        let Some(db) = session.state_db() else {
+            return Err(FunctionCallError::RespondToModel(
+                "code_search is unavailable because the state database is disabled".to_string(),
+            ));
+        };
+        let index = CodeIndex::new(db, turn.cwd.clone());
+        let index_is_stale =
+            match tokio::time::timeout(REFRESH_DEADLINE, refresh_in_background(&index)).await {
+                Ok(Ok(())) => false,
+                Ok(Err(err)) => {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "failed to index: {err}"
+                    )));
+                }
+                Err(_) => true,
+            };
+
+        let symbols = match args.query {
+            CodeSearchQuery::Definition | CodeSearchQuery::References => {
+                let symbol = args
+                    .symbol
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|symbol| !symbol.is_empty())
+                    .ok_or_else(|| {
+                        FunctionCallError::RespondToModel(
+                            "symbol is required for definition and references queries".to_string(),
+                        )
+                    })?;
+                let result = if args.query == CodeSearchQuery::Definition {
+                    index.definitions(symbol, limit).await
+                } else {
+                    index.references(symbol, limit).await
+                };
+                result.map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("code_search failed: {err}"))
+                })?
+            }
+            CodeSearchQuery::Outline => {
+                let path = args
+                    .path
+                    .as_deref()
+                    .filter(|path| !path.trim().is_empty())
+                    .ok_or_else(|| {
+                        FunctionCallError::RespondToModel(
+                            "path is required for outline queries".to_string(),
+                        )
+                    })?;
+                let path = turn.resolve_path(Some(path.to_string()));
+                if !path.starts_with(index.root()) {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "`{}` is outside the indexed directory `{}`",
+                        path.display(),
+                        index.root().display()
+                    )));
+                }
+                if Language::from_path(&path).is_none() {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "`{}` is not a supported source file",
+                        path.display()
+                    )));
+                }
+                let mut symbols = index.outline(&path).await.map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("code_search failed: {err}"))
+                })?;
+                symbols.truncate(limit);
+                symbols
+            }
+        };
+
+        let (mut output, success) = if symbols.is_empty() {
+            ("No matches found.".to_string(), false)
+        } else {
+            (format_symbols(&symbols), true)
+        };
+        if index_is_stale {
+            output.push_str("\n\n");
+            output.push_str(STALE_INDEX_NOTE);
+        }
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success: Some(success),
+        })
+    }
+}
+
+/// Returns the refresh in flight for `index`'s root, starting one if there is
+/// none. The refresh runs on its own task, so it completes even when every
+/// caller stops waiting for it.
+fn refresh_in_background(index: &CodeIndex) -> SharedRefresh {
+    let mut refreshes = REFRESHES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(refresh) = refreshes.get(index.root()) {
+        return refresh.clone();
+    }
+    let task_index = index.clone();
+    let handle = tokio::spawn(async move {
+        let result = task_index
+            .refresh()
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string());
+        REFRESHES
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(task_index.root());
+        result
+    });
+    let refresh = async move { handle.await.map_err(|err| err.to_string())? }
+        .boxed()
+        .shared();
+    refreshes.insert(index.root().to_path_buf(), refresh.clone());
+    refresh
+}
+
+/// Renders one entry per symbol:
+///
+/// ```text
+/// src/lib.rs:4-6 method Parser::parse
+///     fn parse(&self) -> u32 {
+/// ```
+fn format_symbols(symbols: &[CodeSymbol]) -> String {
+    let mut output = String::new();
+    for symbol in symbols {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        let _ = write!(output, "{}:{}", symbol.path, symbol.start_line);
+        if symbol.end_line > symbol.start_line {
+            let _ = write!(output, "-{}", symbol.end_line);
+        }
+        let _ = write!(output, " {} ", symbol.kind);
+        if let Some(container) = &symbol.container {
+            let _ = write!(output, "{container}::");
+        }
+        let _ = write!(output, "{}\n    {}", symbol.name, symbol.snippet);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_symbols;
+    use codex_state::CodeSymbol;
+    use codex_state::CodeSymbolRole;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_symbols_with_ranges_and_snippets() {
+        let symbols = vec![
+            CodeSymbol {
+                path: "src/lib.rs".to_string(),
+                name: "parse".to_string(),
+                kind: "method".to_string(),
+                role: CodeSymbolRole::Definition,
+                container: Some("Parser".to_string()),
+                start_line: 4,
+                end_line: 6,
+                snippet: "fn parse(&self) -> u32 {".to_string(),
+            },
+            CodeSymbol {
+                path: "main.py".to_string(),
+                name: "parse".to_string(),
+                kind: "call".to_string(),
+                role: CodeSymbolRole::Reference,
+                container: None,
+                start_line: 2,
+                end_line: 2,
+                snippet: "parse()".to_string(),
+            },
+        ];
+        assert_eq!(
+            format_symbols(&symbols),
+            "src/lib.rs:4-6 method Parser::parse\n    fn parse(&self) -> u32 {\nmain.py:2 call parse\n    parse()"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub(crate) mod agent_jobs;
 pub mod apply_patch;
 mod artifacts;
+mod background_jobs;
+mod code_search;
 mod dynamic;
 mod grep_files;
 mod js_repl;
@@ -29,6 +31,8 @@ use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::normalize_additional_permissions;
 pub use apply_patch::ApplyPatchHandler;
 pub use artifacts::ArtifactsHandler;
+pub use background_jobs::BackgroundJobsHandler;
+pub use code_search::CodeSearchHandler;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::protocol::AskForApproval;
 pub use dynamic::DynamicToolHandler;
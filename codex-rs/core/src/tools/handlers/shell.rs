@@ -389,8 +389,19 @@ impl ShellHandler {
                 sandbox_policy: turn.sandbox_policy.get(),
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
+                cwd: Some(exec_params.cwd.as_path()),
+                env: Some(&exec_params.env),
             })
             .await;
+        for env_var in session.services.secret_env.inject(
+            &exec_params.command,
+            exec_approval_requirement.allowed_by_policy_rule(),
+            &mut exec_params.env,
+        ) {
+            if let Some(value) = exec_params.env.get(&env_var) {
+                explicit_env_overrides.insert(env_var, value.clone());
+            }
+        }
 
         let req = ShellRequest {
             command: exec_params.command.clone(),
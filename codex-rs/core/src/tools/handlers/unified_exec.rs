@@ -54,6 +54,8 @@ pub(crate) struct ExecCommandArgs {
     justification: Option<String>,
     #[serde(default)]
     prefix_rule: Option<Vec<String>>,
+    #[serde(default)]
+    persist: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -165,9 +167,17 @@ impl ToolHandler for UnifiedExecHandler {
                     additional_permissions,
                     justification,
                     prefix_rule,
+                    persist,
                     ..
                 } = args;
 
+                if persist && !session.features().enabled(Feature::PersistentJobs) {
+                    manager.release_process_id(&process_id).await;
+                    return Err(FunctionCallError::RespondToModel(
+                        "persist requires the persistent_jobs feature".to_string(),
+                    ));
+                }
+
                 let request_permission_enabled =
                     session.features().enabled(Feature::RequestPermissions);
 
@@ -233,6 +243,7 @@ impl ToolHandler for UnifiedExecHandler {
                             additional_permissions: normalized_additional_permissions,
                             justification,
                             prefix_rule,
+                            persist,
                         },
                         &context,
                     )
@@ -842,6 +842,7 @@ impl JsReplManager {
             sandbox_permissions: SandboxPermissions::UseDefault,
             additional_permissions: None,
             justification: None,
+            exec_policy_command: None,
         };
 
         let sandbox = SandboxManager::new();
@@ -874,6 +875,9 @@ impl JsReplManager {
                 use_linux_sandbox_bwrap: turn
                     .features
                     .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
+                exec_policy: None,
+                write_scope: Some(&turn.config.permissions.sandbox_write_scope),
+                seccomp: Some(&turn.config.permissions.sandbox_seccomp),
                 windows_sandbox_level: turn.windows_sandbox_level,
             })
             .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
@@ -4,14 +4,20 @@ use crate::guardian::GuardianReviewRequest;
 use crate::guardian::review_approval_request;
 use crate::guardian::routes_approval_to_guardian;
 use crate::network_policy_decision::denied_network_policy_message;
+use crate::state_db::StateDbHandle;
 use crate::tools::sandboxing::ToolError;
+use async_trait::async_trait;
 use codex_network_proxy::BlockedRequest;
 use codex_network_proxy::BlockedRequestObserver;
+use codex_network_proxy::NetworkConnectionDecision;
+use codex_network_proxy::NetworkConnectionObserver;
+use codex_network_proxy::NetworkConnectionRecord;
 use codex_network_proxy::NetworkDecision;
 use codex_network_proxy::NetworkPolicyDecider;
 use codex_network_proxy::NetworkPolicyRequest;
 use codex_network_proxy::NetworkProtocol;
 use codex_network_proxy::NetworkProxy;
+use codex_protocol::ThreadId;
 use codex_protocol::approvals::NetworkApprovalContext;
 use codex_protocol::approvals::NetworkApprovalProtocol;
 use codex_protocol::approvals::NetworkPolicyRuleAction;
@@ -162,6 +168,7 @@ impl PendingHostApproval {
 
 struct ActiveNetworkApprovalCall {
     registration_id: String,
+    call_id: String,
 }
 
 pub(crate) struct NetworkApprovalService {
@@ -191,10 +198,16 @@ impl NetworkApprovalService {
         other_approved_hosts.extend(approved_hosts.iter().cloned());
     }
 
-    async fn register_call(&self, registration_id: String) {
+    async fn register_call(&self, registration_id: String, call_id: String) {
         let mut active_calls = self.active_calls.lock().await;
         let key = registration_id.clone();
-        active_calls.insert(key, Arc::new(ActiveNetworkApprovalCall { registration_id }));
+        active_calls.insert(
+            key,
+            Arc::new(ActiveNetworkApprovalCall {
+                registration_id,
+                call_id,
+            }),
+        );
     }
 
     pub(crate) async fn unregister_call(&self, registration_id: &str) {
@@ -213,6 +226,13 @@ impl NetworkApprovalService {
         None
     }
 
+    /// Tool call id to attribute proxied traffic to, when exactly one call is using the network.
+    async fn active_call_id(&self) -> Option<String> {
+        self.resolve_single_active_call()
+            .await
+            .map(|call| call.call_id.clone())
+    }
+
     async fn get_or_create_pending_approval(
         &self,
         key: HostApprovalKey,
@@ -506,6 +526,72 @@ pub(crate) fn build_blocked_request_observer(
     })
 }
 
+/// Stores proxied connections in the state DB, attributed to the tool call
+/// that was using the network when each connection opened.
+struct NetworkConnectionRecorder {
+    state_db: StateDbHandle,
+    thread_id: ThreadId,
+    network_approval: Arc<NetworkApprovalService>,
+}
+
+#[async_trait]
+impl NetworkConnectionObserver for NetworkConnectionRecorder {
+    async fn on_connection_open(&self) -> Option<String> {
+        self.network_approval.active_call_id().await
+    }
+
+    async fn on_connection(&self, record: NetworkConnectionRecord) {
+        let connection = network_connection_from_record(self.thread_id, record);
+        if let Err(err) = self.state_db.insert_network_connection(&connection).await {
+            warn!("failed to record network connection: {err}");
+        }
+    }
+}
+
+pub(crate) fn build_network_connection_observer(
+    state_db: StateDbHandle,
+    thread_id: ThreadId,
+    network_approval: Arc<NetworkApprovalService>,
+) -> Arc<dyn NetworkConnectionObserver> {
+    Arc::new(NetworkConnectionRecorder {
+        state_db,
+        thread_id,
+        network_approval,
+    })
+}
+
+fn network_connection_from_record(
+    thread_id: ThreadId,
+    record: NetworkConnectionRecord,
+) -> codex_state::NetworkConnection {
+    let decision = match record.decision {
+        NetworkConnectionDecision::Allow => codex_state::NetworkConnectionDecision::Allow,
+        NetworkConnectionDecision::Deny => codex_state::NetworkConnectionDecision::Deny,
+    };
+    let har_entry = record
+        .har_entry
+        .as_ref()
+        .and_then(|entry| serde_json::to_string(entry).ok());
+    codex_state::NetworkConnection {
+        thread_id,
+        process: record.opened_by,
+        client_addr: record.client,
+        protocol: record.protocol,
+        host: record.host,
+        port: record.port,
+        method: record.method,
+        path: record.path,
+        decision,
+        reason: record.reason,
+        rule: record.rule,
+        status: record.status,
+        bytes_sent: record.bytes_sent,
+        bytes_received: record.bytes_received,
+        har_entry,
+        created_at: record.timestamp,
+    }
+}
+
 pub(crate) fn build_network_policy_decider(
     network_approval: Arc<NetworkApprovalService>,
     network_policy_decider_session: Arc<RwLock<std::sync::Weak<Session>>>,
@@ -527,7 +613,7 @@ pub(crate) fn build_network_policy_decider(
 pub(crate) async fn begin_network_approval(
     session: &Session,
     _turn_id: &str,
-    _call_id: &str,
+    call_id: &str,
     has_managed_network_requirements: bool,
     spec: Option<NetworkApprovalSpec>,
 ) -> Option<ActiveNetworkApproval> {
@@ -540,7 +626,7 @@ pub(crate) async fn begin_network_approval(
     session
         .services
         .network_approval
-        .register_call(registration_id.clone())
+        .register_call(registration_id.clone(), call_id.to_string())
         .await;
 
     Some(ActiveNetworkApproval {
@@ -750,7 +836,9 @@ mod tests {
     #[tokio::test]
     async fn record_blocked_request_sets_policy_outcome_for_owner_call() {
         let service = NetworkApprovalService::default();
-        service.register_call("registration-1".to_string()).await;
+        service
+            .register_call("registration-1".to_string(), "call-1".to_string())
+            .await;
 
         service
             .record_blocked_request(denied_blocked_request("example.com"))
@@ -767,7 +855,9 @@ mod tests {
     #[tokio::test]
     async fn blocked_request_policy_does_not_override_user_denial_outcome() {
         let service = NetworkApprovalService::default();
-        service.register_call("registration-1".to_string()).await;
+        service
+            .register_call("registration-1".to_string(), "call-1".to_string())
+            .await;
 
         service
             .record_call_outcome("registration-1", NetworkApprovalOutcome::DeniedByUser)
@@ -782,11 +872,75 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn network_connection_from_record_keeps_the_call_that_opened_it() {
+        let service = NetworkApprovalService::default();
+        let thread_id = ThreadId::new();
+        let record = NetworkConnectionRecord {
+            protocol: "https".to_string(),
+            host: "registry.npmjs.org".to_string(),
+            port: Some(443),
+            client: Some("127.0.0.1:50000".to_string()),
+            opened_by: service.active_call_id().await,
+            method: Some("GET".to_string()),
+            path: Some("/left-pad".to_string()),
+            decision: NetworkConnectionDecision::Allow,
+            reason: None,
+            rule: Some("allow GET registry.npmjs.org/**".to_string()),
+            status: Some(200),
+            bytes_sent: Some(0),
+            bytes_received: Some(512),
+            har_entry: None,
+            timestamp: 1_735_689_600,
+        };
+
+        // Without a single call using the network, the connection stays unattributed.
+        assert_eq!(
+            network_connection_from_record(thread_id, record.clone()).process,
+            None
+        );
+
+        service
+            .register_call("registration-1".to_string(), "call-1".to_string())
+            .await;
+        let record = NetworkConnectionRecord {
+            opened_by: service.active_call_id().await,
+            ..record
+        };
+        // A tunnel is recorded when it closes, possibly after its call ended.
+        service.unregister_call("registration-1").await;
+        assert_eq!(
+            network_connection_from_record(thread_id, record),
+            codex_state::NetworkConnection {
+                thread_id,
+                process: Some("call-1".to_string()),
+                client_addr: Some("127.0.0.1:50000".to_string()),
+                protocol: "https".to_string(),
+                host: "registry.npmjs.org".to_string(),
+                port: Some(443),
+                method: Some("GET".to_string()),
+                path: Some("/left-pad".to_string()),
+                decision: codex_state::NetworkConnectionDecision::Allow,
+                reason: None,
+                rule: Some("allow GET registry.npmjs.org/**".to_string()),
+                status: Some(200),
+                bytes_sent: Some(0),
+                bytes_received: Some(512),
+                har_entry: None,
+                created_at: 1_735_689_600,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn record_blocked_request_ignores_ambiguous_unattributed_blocked_requests() {
         let service = NetworkApprovalService::default();
-        service.register_call("registration-1".to_string()).await;
-        service.register_call("registration-2".to_string()).await;
+        service
+            .register_call("registration-1".to_string(), "call-1".to_string())
+            .await;
+        service
+            .register_call("registration-2".to_string(), "call-2".to_string())
+            .await;
 
         service
             .record_blocked_request(denied_blocked_request("example.com"))
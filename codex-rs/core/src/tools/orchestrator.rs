@@ -187,6 +187,7 @@ impl ToolOrchestrator {
         // Platform-specific flag gating is handled by SandboxManager::select_initial
         // via crate::safety::get_platform_sandbox(..).
         let use_linux_sandbox_bwrap = turn_ctx.features.enabled(Feature::UseLinuxSandboxBwrap);
+        let exec_policy = tool_ctx.session.services.exec_policy.current();
         let initial_attempt = SandboxAttempt {
             sandbox: initial_sandbox,
             policy: &turn_ctx.sandbox_policy,
@@ -197,6 +198,9 @@ impl ToolOrchestrator {
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
+            exec_policy: Some(&exec_policy),
+            write_scope: Some(&turn_ctx.config.permissions.sandbox_write_scope),
+            seccomp: Some(&turn_ctx.config.permissions.sandbox_seccomp),
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
         };
 
@@ -219,6 +223,7 @@ impl ToolOrchestrator {
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                 output,
                 network_policy_decision,
+                seccomp_denial,
             }))) => {
                 let network_approval_context = if has_managed_network_requirements {
                     network_policy_decision
@@ -231,12 +236,14 @@ impl ToolOrchestrator {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
                         network_policy_decision,
+                        seccomp_denial,
                     })));
                 }
                 if !tool.escalate_on_failure() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
                         network_policy_decision,
+                        seccomp_denial,
                     })));
                 }
                 // Under `Never` or `OnRequest`, do not retry without sandbox;
@@ -257,6 +264,7 @@ impl ToolOrchestrator {
                         return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                             output,
                             network_policy_decision,
+                            seccomp_denial,
                         })));
                     }
                 }
@@ -266,6 +274,8 @@ impl ToolOrchestrator {
                             "Network access to \"{}\" is blocked by policy.",
                             network_approval_context.host
                         )
+                    } else if let Some(seccomp_denial) = seccomp_denial.as_ref() {
+                        seccomp_denial.message()
                     } else {
                         build_denial_reason_from_output(output.as_ref())
                     };
@@ -319,6 +329,9 @@ impl ToolOrchestrator {
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
                     use_linux_sandbox_bwrap,
+                    exec_policy: None,
+                    write_scope: None,
+                    seccomp: None,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
                 };
 
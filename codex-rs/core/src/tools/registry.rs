@@ -15,12 +15,14 @@ use crate::tools::context::ToolPayload;
 use async_trait::async_trait;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterToolUse;
+use codex_hooks::HookEventBeforeToolUse;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
 use codex_hooks::HookToolInput;
 use codex_hooks::HookToolInputLocalShell;
 use codex_hooks::HookToolKind;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::protocol::ReviewDecision;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
 
@@ -78,7 +80,7 @@ impl ToolRegistry {
 
     pub async fn dispatch(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<ResponseInputItem, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let call_id_owned = invocation.call_id.clone();
@@ -162,7 +164,44 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
-        let is_mutating = handler.is_mutating(&invocation).await;
+        let mut is_mutating = handler.is_mutating(&invocation).await;
+        let before_tool_use = match dispatch_before_tool_use_hook(&invocation, is_mutating).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
+                    invocation: &invocation,
+                    output_preview: err.to_string(),
+                    success: false,
+                    executed: false,
+                    duration: Duration::ZERO,
+                    mutating: is_mutating,
+                })
+                .await;
+                return Err(err);
+            }
+        };
+        if let Some(tool_input) = before_tool_use.tool_input {
+            invocation.payload = match tool_payload_from_hook_input(&invocation.payload, tool_input)
+            {
+                Ok(payload) => payload,
+                Err(err) => {
+                    dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
+                        invocation: &invocation,
+                        output_preview: err.to_string(),
+                        success: false,
+                        executed: false,
+                        duration: Duration::ZERO,
+                        mutating: is_mutating,
+                    })
+                    .await;
+                    return Err(err);
+                }
+            };
+            is_mutating = handler.is_mutating(&invocation).await;
+        }
+        if before_tool_use.require_approval {
+            request_hook_approval(&invocation, before_tool_use.approval_reason).await?;
+        }
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
 
@@ -283,6 +322,12 @@ impl ToolRegistryBuilder {
         }
     }
 
+    /// Drops every spec and handler whose tool name does not satisfy `keep`.
+    pub fn retain_tools(&mut self, keep: impl Fn(&str) -> bool) {
+        self.specs.retain(|spec| keep(spec.spec.name()));
+        self.handlers.retain(|name, _| keep(name));
+    }
+
     // TODO(jif) for dynamic tools.
     // pub fn register_many<I>(&mut self, names: I, handler: Arc<dyn ToolHandler>)
     // where
@@ -323,6 +368,57 @@ fn sandbox_policy_tag(policy: &SandboxPolicy) -> &'static str {
     }
 }
 
+/// Converts a hook-rewritten tool input back into a [ToolPayload]. Hooks may only
+/// rewrite the arguments of a call, not change its kind or MCP target.
+fn tool_payload_from_hook_input(
+    original: &ToolPayload,
+    tool_input: HookToolInput,
+) -> Result<ToolPayload, FunctionCallError> {
+    match (original, tool_input) {
+        (ToolPayload::Function { .. }, HookToolInput::Function { arguments }) => {
+            Ok(ToolPayload::Function { arguments })
+        }
+        (ToolPayload::Custom { .. }, HookToolInput::Custom { input }) => {
+            Ok(ToolPayload::Custom { input })
+        }
+        (ToolPayload::LocalShell { params }, HookToolInput::LocalShell { params: rewritten }) => {
+            let mut params = params.clone();
+            params.command = rewritten.command;
+            params.workdir = rewritten.workdir;
+            params.timeout_ms = rewritten.timeout_ms;
+            params.sandbox_permissions = rewritten.sandbox_permissions;
+            params.prefix_rule = rewritten.prefix_rule;
+            params.justification = rewritten.justification;
+            Ok(ToolPayload::LocalShell { params })
+        }
+        (
+            ToolPayload::Mcp { server, tool, .. },
+            HookToolInput::Mcp {
+                server: rewritten_server,
+                tool: rewritten_tool,
+                arguments,
+            },
+        ) if *server == rewritten_server && *tool == rewritten_tool => Ok(ToolPayload::Mcp {
+            server: rewritten_server,
+            tool: rewritten_tool,
+            raw_arguments: arguments,
+        }),
+        (_, tool_input) => Err(FunctionCallError::Fatal(format!(
+            "before_tool_use hook returned incompatible tool input: {}",
+            hook_tool_kind_label(&hook_tool_kind(&tool_input))
+        ))),
+    }
+}
+
+fn hook_tool_kind_label(kind: &HookToolKind) -> &'static str {
+    match kind {
+        HookToolKind::Function => "function",
+        HookToolKind::Custom => "custom",
+        HookToolKind::LocalShell => "local_shell",
+        HookToolKind::Mcp => "mcp",
+    }
+}
+
 // Hooks use a separate wire-facing input type so hook payload JSON stays stable
 // and decoupled from core's internal tool runtime representation.
 impl From<&ToolPayload> for HookToolInput {
@@ -366,6 +462,145 @@ fn hook_tool_kind(tool_input: &HookToolInput) -> HookToolKind {
     }
 }
 
+#[derive(Default)]
+struct BeforeToolUseOutcome {
+    /// Tool input as rewritten by hooks, if any hook modified it.
+    tool_input: Option<HookToolInput>,
+    /// Set when a hook requires an explicit approval prompt before execution.
+    require_approval: bool,
+    approval_reason: Option<String>,
+}
+
+async fn dispatch_before_tool_use_hook(
+    invocation: &ToolInvocation,
+    mutating: bool,
+) -> Result<BeforeToolUseOutcome, FunctionCallError> {
+    let session = invocation.session.as_ref();
+    let turn = invocation.turn.as_ref();
+    let tool_input = HookToolInput::from(&invocation.payload);
+    let hook_outcomes = session
+        .hooks()
+        .dispatch(HookPayload {
+            session_id: session.conversation_id,
+            cwd: turn.cwd.clone(),
+            client: turn.app_server_client_name.clone(),
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: turn.sub_id.clone(),
+                    call_id: invocation.call_id.clone(),
+                    tool_name: invocation.tool_name.clone(),
+                    tool_kind: hook_tool_kind(&tool_input),
+                    tool_input,
+                    mutating,
+                    sandbox: sandbox_tag(
+                        &turn.sandbox_policy,
+                        turn.windows_sandbox_level,
+                        turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+                    )
+                    .to_string(),
+                    sandbox_policy: sandbox_policy_tag(&turn.sandbox_policy).to_string(),
+                },
+            },
+        })
+        .await;
+
+    let mut outcome = BeforeToolUseOutcome::default();
+    for hook_outcome in hook_outcomes {
+        let hook_name = hook_outcome.hook_name;
+        match hook_outcome.result {
+            HookResult::Success => {}
+            HookResult::FailedContinue(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; continuing"
+                );
+            }
+            HookResult::FailedAbort(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; aborting operation"
+                );
+                return Err(FunctionCallError::Fatal(format!(
+                    "before_tool_use hook '{hook_name}' failed and aborted operation: {error}"
+                )));
+            }
+            HookResult::Deny { reason } => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "tool call denied by hook '{hook_name}': {reason}"
+                )));
+            }
+            HookResult::ModifyToolInput(tool_input) => {
+                outcome.tool_input = Some(tool_input);
+            }
+            HookResult::RequireApproval { reason } => {
+                outcome.require_approval = true;
+                outcome.approval_reason = outcome.approval_reason.or(reason);
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Prompts the user for a tool call that a `before_tool_use` hook flagged, even
+/// when the approval policy would otherwise run it without asking.
+async fn request_hook_approval(
+    invocation: &ToolInvocation,
+    reason: Option<String>,
+) -> Result<(), FunctionCallError> {
+    let command = match &invocation.payload {
+        ToolPayload::LocalShell { params } => params.command.clone(),
+        payload => vec![
+            invocation.tool_name.clone(),
+            payload.log_payload().into_owned(),
+        ],
+    };
+    let decision = invocation
+        .session
+        .request_command_approval(
+            invocation.turn.as_ref(),
+            invocation.call_id.clone(),
+            None,
+            command,
+            invocation.turn.cwd.clone(),
+            reason,
+            None,
+            None,
+            None,
+            Some(vec![
+                ReviewDecision::Approved,
+                ReviewDecision::Denied,
+                ReviewDecision::Abort,
+            ]),
+        )
+        .await;
+    match decision {
+        ReviewDecision::Denied => Err(FunctionCallError::RespondToModel(
+            "rejected by user".to_string(),
+        )),
+        ReviewDecision::Abort => {
+            // Like an aborted exec approval, this ends the turn. The interrupt
+            // waits for this task to finish, so it cannot run inline.
+            let session = Arc::clone(&invocation.session);
+            tokio::spawn(async move { session.interrupt_task().await });
+            Err(FunctionCallError::RespondToModel(
+                "aborted by user".to_string(),
+            ))
+        }
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession
+        | ReviewDecision::NetworkPolicyAmendment { .. } => Ok(()),
+    }
+}
+
 struct AfterToolUseHookDispatch<'a> {
     invocation: &'a ToolInvocation,
     output_preview: String,
@@ -438,6 +673,19 @@ async fn dispatch_after_tool_use_hook(
                     "after_tool_use hook '{hook_name}' failed and aborted operation: {error}"
                 )));
             }
+            HookResult::Deny { reason } => {
+                return Some(FunctionCallError::RespondToModel(format!(
+                    "after_tool_use hook '{hook_name}' withheld the tool output: {reason}"
+                )));
+            }
+            HookResult::ModifyToolInput(_) | HookResult::RequireApproval { .. } => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    "after_tool_use hook returned a pre-execution decision; ignoring"
+                );
+            }
         }
     }
 
@@ -4,6 +4,9 @@ use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::mcp_connection_manager::ToolInfo;
 use crate::sandboxing::SandboxPermissions;
+use crate::secret_redaction::log_redactions;
+use crate::secret_redaction::redact_tool_output;
+use crate::secret_redaction::redaction_event;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
@@ -168,6 +171,9 @@ impl ToolRouter {
             ));
         }
 
+        let redact_outputs = turn.config.secret_redaction.enabled;
+        let redaction_session = Arc::clone(&session);
+        let redaction_turn = Arc::clone(&turn);
         let invocation = ToolInvocation {
             session,
             turn,
@@ -177,15 +183,29 @@ impl ToolRouter {
             payload,
         };
 
-        match self.registry.dispatch(invocation).await {
-            Ok(response) => Ok(response),
-            Err(FunctionCallError::Fatal(message)) => Err(FunctionCallError::Fatal(message)),
-            Err(err) => Ok(Self::failure_response(
-                failure_call_id,
-                payload_outputs_custom,
-                err,
-            )),
+        let mut response = match self.registry.dispatch(invocation).await {
+            Ok(response) => response,
+            Err(FunctionCallError::Fatal(message)) => {
+                return Err(FunctionCallError::Fatal(message));
+            }
+            Err(err) => Self::failure_response(failure_call_id, payload_outputs_custom, err),
+        };
+        let services = &redaction_session.services;
+        let mut findings = Vec::new();
+        if redact_outputs {
+            findings = redact_tool_output(&services.secret_redactor, &mut response);
+        }
+        // Injected secret values are always scrubbed, even with `secret_redaction` disabled.
+        if let Some(redactor) = services.secret_env.output_redactor() {
+            findings.extend(redact_tool_output(redactor, &mut response));
+        }
+        log_redactions("tool_output", &findings);
+        if let Some(event) = redaction_event("tool_output", &findings) {
+            redaction_session
+                .send_event(redaction_turn.as_ref(), event)
+                .await;
         }
+        Ok(response)
     }
 
     fn failure_response(
@@ -23,6 +23,7 @@ use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::CODEX_CORE_APPLY_PATCH_ARG1;
+use codex_apply_patch::HunkMatchOptions;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::ReviewDecision;
@@ -40,6 +41,9 @@ pub struct ApplyPatchRequest {
     pub exec_approval_requirement: ExecApprovalRequirement,
     pub timeout_ms: Option<u64>,
     pub codex_exe: Option<PathBuf>,
+    /// Forwarded to the self-invocation so chunks land where the approved diff
+    /// placed them.
+    pub match_options: HunkMatchOptions,
 }
 
 #[derive(Default)]
@@ -81,12 +85,14 @@ impl ApplyPatchRuntime {
             }
         };
         let program = exe.to_string_lossy().to_string();
+        let mut args = vec![
+            CODEX_CORE_APPLY_PATCH_ARG1.to_string(),
+            req.action.patch.clone(),
+        ];
+        args.extend(req.match_options.to_args());
         Ok(CommandSpec {
             program,
-            args: vec![
-                CODEX_CORE_APPLY_PATCH_ARG1.to_string(),
-                req.action.patch.clone(),
-            ],
+            args,
             cwd: req.action.cwd.clone(),
             expiration: req.timeout_ms.into(),
             // Run apply_patch with a minimal environment for determinism and to avoid leaks.
@@ -94,6 +100,7 @@ impl ApplyPatchRuntime {
             sandbox_permissions: SandboxPermissions::UseDefault,
             additional_permissions: None,
             justification: None,
+            exec_policy_command: None,
         })
     }
 
@@ -102,6 +109,8 @@ impl ApplyPatchRuntime {
             sub_id: ctx.turn.sub_id.clone(),
             call_id: ctx.call_id.clone(),
             tx_event: ctx.session.get_tx_event(),
+            // apply_patch runs with an empty environment, so no secret is injected.
+            output_redactor: None,
         })
     }
 }
@@ -133,6 +142,7 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let retry_reason = ctx.retry_reason.clone();
         let approval_keys = self.approval_keys(req);
         let changes = req.changes.clone();
+        let skipped = req.action.skipped.clone();
         Box::pin(async move {
             if routes_approval_to_guardian(turn) {
                 let request = ApplyPatchRuntime::build_guardian_review_request(req);
@@ -140,7 +150,14 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
             }
             if let Some(reason) = retry_reason {
                 let rx_approve = session
-                    .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
+                    .request_patch_approval(
+                        turn,
+                        call_id,
+                        changes.clone(),
+                        skipped.clone(),
+                        Some(reason),
+                        None,
+                    )
                     .await;
                 return rx_approve.await.unwrap_or_default();
             }
@@ -151,7 +168,7 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
                 approval_keys,
                 || async move {
                     let rx_approve = session
-                        .request_patch_approval(turn, call_id, changes, None, None)
+                        .request_patch_approval(turn, call_id, changes, skipped, None, None)
                         .await;
                     rx_approve.await.unwrap_or_default()
                 },
@@ -250,6 +267,7 @@ mod tests {
             },
             timeout_ms: None,
             codex_exe: None,
+            match_options: HunkMatchOptions::default(),
         };
 
         let guardian_request = ApplyPatchRuntime::build_guardian_review_request(&request);
@@ -267,4 +285,44 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn command_spec_forwards_hunk_match_options() {
+        let path = std::env::temp_dir().join("apply-patch-options-test.txt");
+        let action = ApplyPatchAction::new_add_for_test(&path, "hello".to_string());
+        let patch = action.patch.clone();
+        let match_options = HunkMatchOptions {
+            fuzzy_threshold: None,
+            anchor_to_scope: false,
+        };
+        let request = ApplyPatchRequest {
+            action,
+            file_paths: Vec::new(),
+            changes: HashMap::new(),
+            exec_approval_requirement: ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            },
+            timeout_ms: None,
+            codex_exe: Some(PathBuf::from("/usr/local/bin/codex")),
+            match_options,
+        };
+
+        let spec = ApplyPatchRuntime::build_command_spec(&request, std::path::Path::new("/"))
+            .expect("command spec");
+
+        assert_eq!(
+            spec.args,
+            vec![
+                CODEX_CORE_APPLY_PATCH_ARG1.to_string(),
+                patch,
+                "--no-fuzzy".to_string(),
+                "--no-scope-anchor".to_string(),
+            ]
+        );
+        assert_eq!(
+            HunkMatchOptions::from_args(&spec.args[2..]),
+            Ok(match_options)
+        );
+    }
 }
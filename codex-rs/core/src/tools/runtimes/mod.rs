@@ -9,6 +9,9 @@ use crate::path_utils;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
+use crate::shell::ShellType;
+use crate::shell::fish_single_quote;
+use crate::shell::nushell_raw_string;
 use crate::skills::SkillMetadata;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::models::PermissionProfile;
@@ -50,6 +53,7 @@ pub(crate) fn build_command_spec(
         sandbox_permissions,
         additional_permissions,
         justification,
+        exec_policy_command: None,
     })
 }
 
@@ -63,8 +67,11 @@ pub(crate) fn build_command_spec(
 ///   => user_shell -c ". SNAPSHOT (best effort); exec shell -c <script>"
 ///
 /// This wrapper script uses POSIX constructs (`if`, `.`, `exec`) so it can
-/// be run by Bash/Zsh/sh. On non-matching commands, or when command cwd does
-/// not match the snapshot cwd, this is a no-op.
+/// be run by Bash/Zsh/sh. Fish and Nushell sessions still run the script in
+/// their POSIX [`Shell::command_shell`]; the wrapper is written in the
+/// session shell's syntax and only sources its snapshot so the environment
+/// is inherited by the `exec`ed shell. On non-matching commands, or when
+/// command cwd does not match the snapshot cwd, this is a no-op.
 pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
     command: &[String],
     session_shell: &Shell,
@@ -94,26 +101,58 @@ pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
         return command.to_vec();
     }
 
-    if command.len() < 3 {
+    let [original_shell, flag, original_script, trailing_args @ ..] = command else {
         return command.to_vec();
-    }
-
-    let flag = command[1].as_str();
+    };
     if flag != "-lc" {
         return command.to_vec();
     }
 
     let snapshot_path = snapshot.path.to_string_lossy();
     let shell_path = session_shell.shell_path.to_string_lossy();
-    let original_shell = shell_single_quote(&command[0]);
-    let original_script = shell_single_quote(&command[2]);
-    let snapshot_path = shell_single_quote(snapshot_path.as_ref());
-    let trailing_args = command[3..]
+    let rewritten_script = match session_shell.shell_type {
+        ShellType::Fish => fish_snapshot_wrapper(
+            &snapshot_path,
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+        ShellType::Nushell => nushell_snapshot_wrapper(
+            &snapshot_path,
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+        _ => posix_snapshot_wrapper(
+            &snapshot_path,
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+    };
+
+    vec![shell_path.to_string(), "-c".to_string(), rewritten_script]
+}
+
+fn posix_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let original_shell = shell_single_quote(original_shell);
+    let original_script = shell_single_quote(original_script);
+    let snapshot_path = shell_single_quote(snapshot_path);
+    let trailing_args = trailing_args
         .iter()
         .map(|arg| format!(" '{}'", shell_single_quote(arg)))
         .collect::<String>();
     let (override_captures, override_exports) = build_override_exports(explicit_env_overrides);
-    let rewritten_script = if override_exports.is_empty() {
+    if override_exports.is_empty() {
         format!(
             "if . '{snapshot_path}' >/dev/null 2>&1; then :; fi\n\nexec '{original_shell}' -c '{original_script}'{trailing_args}"
         )
@@ -121,17 +160,95 @@ pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
         format!(
             "{override_captures}\n\nif . '{snapshot_path}' >/dev/null 2>&1; then :; fi\n\n{override_exports}\n\nexec '{original_shell}' -c '{original_script}'{trailing_args}"
         )
-    };
+    }
+}
 
-    vec![shell_path.to_string(), "-c".to_string(), rewritten_script]
+fn fish_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let keys = override_keys(explicit_env_overrides);
+    let mut lines = Vec::new();
+    for (idx, key) in keys.iter().enumerate() {
+        lines.push(format!(
+            "if set -q {key}; set -g __CODEX_SNAPSHOT_OVERRIDE_SET_{idx} 1; set -g __CODEX_SNAPSHOT_OVERRIDE_{idx} ${key}; end"
+        ));
+    }
+    lines.push(format!(
+        "source {} >/dev/null 2>&1",
+        fish_single_quote(snapshot_path)
+    ));
+    for (idx, key) in keys.iter().enumerate() {
+        lines.push(format!(
+            "if set -q __CODEX_SNAPSHOT_OVERRIDE_SET_{idx}; set -gx {key} $__CODEX_SNAPSHOT_OVERRIDE_{idx}; else; set -e {key}; end"
+        ));
+    }
+    let mut exec = format!(
+        "exec {} -c {}",
+        fish_single_quote(original_shell),
+        fish_single_quote(original_script)
+    );
+    for arg in trailing_args {
+        exec.push(' ');
+        exec.push_str(&fish_single_quote(arg));
+    }
+    lines.push(exec);
+    lines.join("\n")
 }
 
-fn build_override_exports(explicit_env_overrides: &HashMap<String, String>) -> (String, String) {
+/// Nushell resolves `source` while parsing, so unlike the POSIX and fish
+/// wrappers a broken snapshot fails the command; snapshots are validated
+/// before they are published, which keeps this from happening in practice.
+fn nushell_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let keys = override_keys(explicit_env_overrides);
+    let mut lines = Vec::new();
+    for (idx, key) in keys.iter().enumerate() {
+        lines.push(format!(
+            "let __codex_snapshot_override_set_{idx} = ('{key}' in ($env | columns))"
+        ));
+        lines.push(format!(
+            "let __codex_snapshot_override_{idx} = ($env.{key}? | default '')"
+        ));
+    }
+    lines.push(format!("source {}", nushell_raw_string(snapshot_path)));
+    for (idx, key) in keys.iter().enumerate() {
+        lines.push(format!(
+            "if $__codex_snapshot_override_set_{idx} {{ $env.{key} = $__codex_snapshot_override_{idx} }} else {{ hide-env --ignore-errors {key} }}"
+        ));
+    }
+    let mut exec = format!(
+        "exec {} -c {}",
+        nushell_raw_string(original_shell),
+        nushell_raw_string(original_script)
+    );
+    for arg in trailing_args {
+        exec.push(' ');
+        exec.push_str(&nushell_raw_string(arg));
+    }
+    lines.push(exec);
+    lines.join("\n")
+}
+
+fn override_keys(explicit_env_overrides: &HashMap<String, String>) -> Vec<&String> {
     let mut keys = explicit_env_overrides
         .keys()
         .filter(|key| is_valid_shell_variable_name(key))
         .collect::<Vec<_>>();
     keys.sort_unstable();
+    keys
+}
+
+fn build_override_exports(explicit_env_overrides: &HashMap<String, String>) -> (String, String) {
+    let keys = override_keys(explicit_env_overrides);
 
     if keys.is_empty() {
         return (String::new(), String::new());
@@ -610,4 +727,80 @@ mod tests {
         assert!(output.status.success(), "command failed: {output:?}");
         assert_eq!(String::from_utf8_lossy(&output.stdout), "unset");
     }
+
+    #[test]
+    fn maybe_wrap_shell_lc_with_snapshot_uses_fish_syntax_for_fish_sessions() {
+        let dir = tempdir().expect("create temp dir");
+        let snapshot_path = dir.path().join("snapshot.fish");
+        std::fs::write(&snapshot_path, "# Snapshot file\n").expect("write snapshot");
+        let session_shell = shell_with_snapshot(
+            ShellType::Fish,
+            "/usr/bin/fish",
+            snapshot_path.clone(),
+            dir.path().to_path_buf(),
+        );
+        let command = vec![
+            "/bin/bash".to_string(),
+            "-lc".to_string(),
+            "echo 'hi'".to_string(),
+        ];
+        let explicit_env_overrides = HashMap::from([("FOO".to_string(), "bar".to_string())]);
+
+        let rewritten = maybe_wrap_shell_lc_with_snapshot(
+            &command,
+            &session_shell,
+            dir.path(),
+            &explicit_env_overrides,
+        );
+
+        let snapshot_path = snapshot_path.to_string_lossy();
+        assert_eq!(
+            rewritten,
+            vec![
+                "/usr/bin/fish".to_string(),
+                "-c".to_string(),
+                format!(
+                    "if set -q FOO; set -g __CODEX_SNAPSHOT_OVERRIDE_SET_0 1; set -g __CODEX_SNAPSHOT_OVERRIDE_0 $FOO; end\n\
+                     source '{snapshot_path}' >/dev/null 2>&1\n\
+                     if set -q __CODEX_SNAPSHOT_OVERRIDE_SET_0; set -gx FOO $__CODEX_SNAPSHOT_OVERRIDE_0; else; set -e FOO; end\n\
+                     exec '/bin/bash' -c 'echo \\'hi\\''"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn maybe_wrap_shell_lc_with_snapshot_uses_nushell_syntax_for_nu_sessions() {
+        let dir = tempdir().expect("create temp dir");
+        let snapshot_path = dir.path().join("snapshot.nu");
+        std::fs::write(&snapshot_path, "# Snapshot file\n").expect("write snapshot");
+        let session_shell = shell_with_snapshot(
+            ShellType::Nushell,
+            "/usr/bin/nu",
+            snapshot_path.clone(),
+            dir.path().to_path_buf(),
+        );
+        let command = vec![
+            "/bin/bash".to_string(),
+            "-lc".to_string(),
+            "echo 'hi'".to_string(),
+        ];
+
+        let rewritten = maybe_wrap_shell_lc_with_snapshot(
+            &command,
+            &session_shell,
+            dir.path(),
+            &HashMap::new(),
+        );
+
+        let snapshot_path = snapshot_path.to_string_lossy();
+        assert_eq!(
+            rewritten,
+            vec![
+                "/usr/bin/nu".to_string(),
+                "-c".to_string(),
+                format!("source r#'{snapshot_path}'#\nexec r#'/bin/bash'# -c r#'echo 'hi''#"),
+            ]
+        );
+    }
 }
@@ -112,6 +112,7 @@ impl ShellRuntime {
             sub_id: ctx.turn.sub_id.clone(),
             call_id: ctx.call_id.clone(),
             tx_event: ctx.session.get_tx_event(),
+            output_redactor: ctx.session.services.secret_env.output_redactor().cloned(),
         })
     }
 }
@@ -250,7 +251,7 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             }
         }
 
-        let spec = build_command_spec(
+        let mut spec = build_command_spec(
             &command,
             &req.cwd,
             &req.env,
@@ -259,6 +260,7 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.additional_permissions.clone(),
             req.justification.clone(),
         )?;
+        spec.exec_policy_command = Some(req.command.clone());
         let env = attempt
             .env_for(spec, req.network.as_ref())
             .map_err(|err| ToolError::Codex(err.into()))?;
@@ -12,6 +12,8 @@ use crate::guardian::review_approval_request;
 use crate::guardian::routes_approval_to_guardian;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::seccomp::SandboxSeccomp;
+use crate::sandboxing::write_scope::SandboxWriteScope;
 use crate::shell::ShellType;
 use crate::skills::SkillMetadata;
 use crate::tools::runtimes::ExecveSessionApproval;
@@ -30,6 +32,7 @@ use codex_protocol::models::MacOsSeatbeltProfileExtensions;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::SeccompProfile;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
 use codex_protocol::protocol::ReviewDecision;
@@ -108,6 +111,7 @@ pub(super) async fn try_run_zsh_fork(
         network_sandbox_policy,
         justification,
         arg0,
+        seccomp_profiles,
     } = sandbox_exec_request;
     let ParsedShellCommand { script, login, .. } = extract_shell_script(&command)?;
     let effective_timeout = Duration::from_millis(
@@ -130,6 +134,7 @@ pub(super) async fn try_run_zsh_fork(
         sandbox_permissions,
         justification,
         arg0,
+        seccomp_profiles,
         sandbox_policy_cwd: ctx.turn.cwd.clone(),
         macos_seatbelt_profile_extensions: ctx
             .turn
@@ -139,6 +144,9 @@ pub(super) async fn try_run_zsh_fork(
             .clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_linux_sandbox_bwrap: ctx.turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+        exec_policy: ctx.session.services.exec_policy.current(),
+        sandbox_write_scope: ctx.turn.config.permissions.sandbox_write_scope.clone(),
+        sandbox_seccomp: ctx.turn.config.permissions.sandbox_seccomp.clone(),
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -242,6 +250,7 @@ pub(crate) async fn prepare_unified_exec_zsh_fork(
         sandbox_permissions: exec_request.sandbox_permissions,
         justification: exec_request.justification.clone(),
         arg0: exec_request.arg0.clone(),
+        seccomp_profiles: exec_request.seccomp_profiles.clone(),
         sandbox_policy_cwd: ctx.turn.cwd.clone(),
         macos_seatbelt_profile_extensions: ctx
             .turn
@@ -251,6 +260,9 @@ pub(crate) async fn prepare_unified_exec_zsh_fork(
             .clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_linux_sandbox_bwrap: ctx.turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+        exec_policy: ctx.session.services.exec_policy.current(),
+        sandbox_write_scope: ctx.turn.config.permissions.sandbox_write_scope.clone(),
+        sandbox_seccomp: ctx.turn.config.permissions.sandbox_seccomp.clone(),
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -739,6 +751,7 @@ fn evaluate_intercepted_exec_policy(
         &fallback,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     )
 }
@@ -791,11 +804,15 @@ struct CoreShellCommandExecutor {
     sandbox_permissions: SandboxPermissions,
     justification: Option<String>,
     arg0: Option<String>,
+    seccomp_profiles: Vec<SeccompProfile>,
     sandbox_policy_cwd: PathBuf,
     #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
     macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
     codex_linux_sandbox_exe: Option<PathBuf>,
     use_linux_sandbox_bwrap: bool,
+    exec_policy: Arc<Policy>,
+    sandbox_write_scope: SandboxWriteScope,
+    sandbox_seccomp: SandboxSeccomp,
 }
 
 struct PrepareSandboxedExecParams<'a> {
@@ -844,6 +861,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                 network_sandbox_policy: self.network_sandbox_policy,
                 justification: self.justification.clone(),
                 arg0: self.arg0.clone(),
+                seccomp_profiles: self.seccomp_profiles.clone(),
             },
             None,
             after_spawn,
@@ -981,6 +999,7 @@ impl CoreShellCommandExecutor {
                     },
                     additional_permissions,
                     justification: self.justification.clone(),
+                    exec_policy_command: None,
                 },
                 policy: sandbox_policy,
                 file_system_policy: file_system_sandbox_policy,
@@ -993,6 +1012,9 @@ impl CoreShellCommandExecutor {
                 macos_seatbelt_profile_extensions,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.as_ref(),
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
+                exec_policy: Some(&self.exec_policy),
+                write_scope: Some(&self.sandbox_write_scope),
+                seccomp: Some(&self.sandbox_seccomp),
                 windows_sandbox_level: self.windows_sandbox_level,
             })?;
         if let Some(network) = exec_request.network.as_ref() {
@@ -1063,6 +1085,7 @@ fn map_exec_result(
         return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
             network_policy_decision: None,
+            seccomp_denial: None,
         })));
     }
 
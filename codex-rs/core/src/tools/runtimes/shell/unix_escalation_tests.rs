@@ -19,10 +19,16 @@ use crate::protocol::ReadOnlyAccess;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::SandboxPermissions;
 #[cfg(target_os = "macos")]
+use crate::sandboxing::seccomp::SandboxSeccomp;
+#[cfg(target_os = "macos")]
+use crate::sandboxing::write_scope::SandboxWriteScope;
+#[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 use crate::skills::SkillMetadata;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Evaluation;
+#[cfg(target_os = "macos")]
+use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 #[cfg(target_os = "macos")]
@@ -48,6 +54,8 @@ use pretty_assertions::assert_eq;
 #[cfg(target_os = "macos")]
 use std::collections::HashMap;
 use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::sync::Arc;
 use std::time::Duration;
 
 fn host_absolute_path(segments: &[&str]) -> String {
@@ -510,6 +518,7 @@ async fn prepare_escalated_exec_turn_default_preserves_macos_seatbelt_extensions
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        seccomp_profiles: Vec::new(),
         sandbox_policy_cwd: cwd.to_path_buf(),
         macos_seatbelt_profile_extensions: Some(MacOsSeatbeltProfileExtensions {
             macos_preferences: MacOsPreferencesPermission::ReadWrite,
@@ -517,6 +526,9 @@ async fn prepare_escalated_exec_turn_default_preserves_macos_seatbelt_extensions
         }),
         codex_linux_sandbox_exe: None,
         use_linux_sandbox_bwrap: false,
+        exec_policy: Arc::new(Policy::empty()),
+        sandbox_write_scope: SandboxWriteScope::default(),
+        sandbox_seccomp: SandboxSeccomp::default(),
     };
 
     let prepared = executor
@@ -562,10 +574,14 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        seccomp_profiles: Vec::new(),
         sandbox_policy_cwd: cwd.to_path_buf(),
         macos_seatbelt_profile_extensions: None,
         codex_linux_sandbox_exe: None,
         use_linux_sandbox_bwrap: false,
+        exec_policy: Arc::new(Policy::empty()),
+        sandbox_write_scope: SandboxWriteScope::default(),
+        sandbox_seccomp: SandboxSeccomp::default(),
     };
 
     let permissions = Permissions {
@@ -583,6 +599,8 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
             macos_preferences: MacOsPreferencesPermission::ReadWrite,
             ..Default::default()
         }),
+        sandbox_write_scope: Default::default(),
+        sandbox_seccomp: Default::default(),
     };
 
     let prepared = executor
@@ -638,6 +656,7 @@ async fn prepare_escalated_exec_permission_profile_unions_turn_and_requested_mac
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        seccomp_profiles: Vec::new(),
         sandbox_policy_cwd: cwd.to_path_buf(),
         macos_seatbelt_profile_extensions: Some(MacOsSeatbeltProfileExtensions {
             macos_preferences: MacOsPreferencesPermission::ReadOnly,
@@ -645,6 +664,9 @@ async fn prepare_escalated_exec_permission_profile_unions_turn_and_requested_mac
         }),
         codex_linux_sandbox_exe: None,
         use_linux_sandbox_bwrap: false,
+        exec_policy: Arc::new(Policy::empty()),
+        sandbox_write_scope: SandboxWriteScope::default(),
+        sandbox_seccomp: SandboxSeccomp::default(),
     };
 
     let prepared = executor
@@ -53,6 +53,9 @@ pub struct UnifiedExecRequest {
     pub explicit_env_overrides: HashMap<String, String>,
     pub network: Option<NetworkProxy>,
     pub tty: bool,
+    /// Log file of a persistent job; when set the process is spawned detached
+    /// and writes its output there (see `unified_exec/jobs.rs`).
+    pub job_log: Option<PathBuf>,
     pub sandbox_permissions: SandboxPermissions,
     pub additional_permissions: Option<PermissionProfile>,
     pub justification: Option<String>,
@@ -214,7 +217,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             network.apply_to_env(&mut env);
         }
         if self.backend == UnifiedExecBackendConfig::ZshFork {
-            let spec = build_command_spec(
+            let mut spec = build_command_spec(
                 &command,
                 &req.cwd,
                 &env,
@@ -224,6 +227,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                 req.justification.clone(),
             )
             .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
+            spec.exec_policy_command = Some(base_command.clone());
             let exec_env = attempt
                 .env_for(spec, req.network.as_ref())
                 .map_err(|err| ToolError::Codex(err.into()))?;
@@ -234,16 +238,20 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                         .open_session_with_exec_env(
                             &prepared.exec_request,
                             req.tty,
+                            req.job_log.as_deref(),
                             prepared.spawn_lifecycle,
                         )
                         .await
                         .map_err(|err| match err {
-                            UnifiedExecError::SandboxDenied { output, .. } => {
-                                ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                                    output: Box::new(output),
-                                    network_policy_decision: None,
-                                }))
-                            }
+                            UnifiedExecError::SandboxDenied {
+                                output,
+                                seccomp_denial,
+                                ..
+                            } => ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                                output: Box::new(output),
+                                network_policy_decision: None,
+                                seccomp_denial,
+                            })),
                             other => ToolError::Rejected(other.to_string()),
                         });
                 }
@@ -254,7 +262,7 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                 }
             }
         }
-        let spec = build_command_spec(
+        let mut spec = build_command_spec(
             &command,
             &req.cwd,
             &env,
@@ -264,19 +272,28 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             req.justification.clone(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
+        spec.exec_policy_command = Some(base_command.clone());
         let exec_env = attempt
             .env_for(spec, req.network.as_ref())
             .map_err(|err| ToolError::Codex(err.into()))?;
         self.manager
-            .open_session_with_exec_env(&exec_env, req.tty, Box::new(NoopSpawnLifecycle))
+            .open_session_with_exec_env(
+                &exec_env,
+                req.tty,
+                req.job_log.as_deref(),
+                Box::new(NoopSpawnLifecycle),
+            )
             .await
             .map_err(|err| match err {
-                UnifiedExecError::SandboxDenied { output, .. } => {
-                    ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                        output: Box::new(output),
-                        network_policy_decision: None,
-                    }))
-                }
+                UnifiedExecError::SandboxDenied {
+                    output,
+                    seccomp_denial,
+                    ..
+                } => ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                    output: Box::new(output),
+                    network_policy_decision: None,
+                    seccomp_denial,
+                })),
                 other => ToolError::Rejected(other.to_string()),
             })
     }
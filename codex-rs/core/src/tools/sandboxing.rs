@@ -12,8 +12,11 @@ use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::seccomp::SandboxSeccomp;
+use crate::sandboxing::write_scope::SandboxWriteScope;
 use crate::state::SessionServices;
 use crate::tools::network_approval::NetworkApprovalSpec;
+use codex_execpolicy::Policy;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::approvals::NetworkApprovalContext;
@@ -142,6 +145,17 @@ pub(crate) enum ExecApprovalRequirement {
 }
 
 impl ExecApprovalRequirement {
+    /// Whether an execpolicy rule explicitly allowed the command.
+    pub(crate) fn allowed_by_policy_rule(&self) -> bool {
+        matches!(
+            self,
+            Self::Skip {
+                bypass_sandbox: true,
+                ..
+            }
+        )
+    }
+
     pub fn proposed_execpolicy_amendment(&self) -> Option<&ExecPolicyAmendment> {
         match self {
             Self::NeedsApproval {
@@ -327,6 +341,9 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
+    pub exec_policy: Option<&'a Policy>,
+    pub write_scope: Option<&'a SandboxWriteScope>,
+    pub seccomp: Option<&'a SandboxSeccomp>,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
 }
 
@@ -350,6 +367,9 @@ impl<'a> SandboxAttempt<'a> {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
+                exec_policy: self.exec_policy,
+                write_scope: self.write_scope,
+                seccomp: self.seccomp,
                 windows_sandbox_level: self.windows_sandbox_level,
             })
     }
@@ -64,6 +64,8 @@ pub(crate) struct ToolsConfig {
     pub image_gen_tool: bool,
     pub agent_roles: BTreeMap<String, AgentRoleConfig>,
     pub search_tool: bool,
+    pub code_search: bool,
+    pub persistent_jobs: bool,
     pub request_permission_enabled: bool,
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
@@ -74,6 +76,7 @@ pub(crate) struct ToolsConfig {
     pub experimental_supported_tools: Vec<String>,
     pub agent_jobs_tools: bool,
     pub agent_jobs_worker_tools: bool,
+    pub tool_allowlist: Option<Vec<String>>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -100,6 +103,8 @@ impl ToolsConfig {
         let include_default_mode_request_user_input =
             include_request_user_input && features.enabled(Feature::DefaultModeRequestUserInput);
         let include_search_tool = features.enabled(Feature::Apps);
+        let include_code_search = features.enabled(Feature::CodeSearch);
+        let include_persistent_jobs = features.enabled(Feature::PersistentJobs);
         let include_artifact_tools =
             features.enabled(Feature::Artifact) && codex_artifacts::can_manage_artifact_runtime();
         let include_image_gen_tool =
@@ -165,6 +170,8 @@ impl ToolsConfig {
             image_gen_tool: include_image_gen_tool,
             agent_roles: BTreeMap::new(),
             search_tool: include_search_tool,
+            code_search: include_code_search,
+            persistent_jobs: include_persistent_jobs,
             request_permission_enabled,
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
@@ -175,6 +182,7 @@ impl ToolsConfig {
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
             agent_jobs_tools: include_agent_jobs,
             agent_jobs_worker_tools,
+            tool_allowlist: None,
         }
     }
 
@@ -192,6 +200,22 @@ impl ToolsConfig {
         self.web_search_config = web_search_config;
         self
     }
+
+    pub fn with_tool_allowlist(mut self, tool_allowlist: Option<Vec<String>>) -> Self {
+        self.tool_allowlist = tool_allowlist;
+        self
+    }
+
+    /// Whether `tool_name` passes `[tools] allowed`. Entries ending in `*` match by prefix.
+    pub(crate) fn is_tool_allowed(&self, tool_name: &str) -> bool {
+        let Some(allowlist) = &self.tool_allowlist else {
+            return true;
+        };
+        allowlist.iter().any(|entry| match entry.strip_suffix('*') {
+            Some(prefix) => tool_name.starts_with(prefix),
+            None => tool_name == entry,
+        })
+    }
 }
 
 fn supports_image_generation(model_info: &ModelInfo) -> bool {
@@ -401,7 +425,11 @@ fn create_approval_parameters(request_permission_enabled: bool) -> BTreeMap<Stri
     properties
 }
 
-fn create_exec_command_tool(allow_login_shell: bool, request_permission_enabled: bool) -> ToolSpec {
+fn create_exec_command_tool(
+    allow_login_shell: bool,
+    request_permission_enabled: bool,
+    persistent_jobs: bool,
+) -> ToolSpec {
     let mut properties = BTreeMap::from([
         (
             "cmd".to_string(),
@@ -461,6 +489,19 @@ fn create_exec_command_tool(allow_login_shell: bool, request_permission_enabled:
             },
         );
     }
+    if persistent_jobs {
+        properties.insert(
+            "persist".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Run the command as a background job that survives interrupts and session \
+                     resume, with its output logged to disk. Inspect it later with \
+                     `background_jobs`. On macOS and Linux jobs have no stdin. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        );
+    }
     properties.extend(create_approval_parameters(request_permission_enabled));
 
     ToolSpec::Function(ResponsesApiTool {
@@ -1189,6 +1230,110 @@ fn create_test_sync_tool() -> ToolSpec {
     })
 }
 
+fn create_background_jobs_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "session_id".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Job to read the log of. Omit to list all background jobs of this session."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Regular expression; only log lines matching it are returned.".to_string(),
+                ),
+            },
+        ),
+        (
+            "max_lines".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of log lines to return, counted from the end (defaults to 200)."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "background_jobs".to_string(),
+        description: "Lists background jobs started with `exec_command` and `persist: true`, \
+                      including jobs from before the session was resumed, or reads and \
+                      searches the saved output log of one job."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_code_search_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "One of \"definition\" (where a symbol is defined), \"references\" (where \
+                     it is called or used), or \"outline\" (the symbols defined in one file)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "symbol".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Symbol name for definition/references queries. Qualify it as `Type::name` \
+                     or `Type.name` to restrict matches to one type, trait, class, or module."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File to outline (required for outline queries), absolute or relative to \
+                     the session's working directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of results to return (defaults to 50).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_search".to_string(),
+        description: "Looks up symbol definitions, references, or a file outline using an \
+                      index of Rust, TypeScript/JavaScript, Python, and Go sources in the \
+                      working directory. Results include file paths, line numbers, and the \
+                      matching source line. Prefer this over text search when you know the \
+                      symbol name."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_grep_files_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1809,6 +1954,8 @@ pub(crate) fn build_specs(
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::ArtifactsHandler;
+    use crate::tools::handlers::BackgroundJobsHandler;
+    use crate::tools::handlers::CodeSearchHandler;
     use crate::tools::handlers::DynamicToolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
@@ -1860,12 +2007,20 @@ pub(crate) fn build_specs(
         }
         ConfigShellToolType::UnifiedExec => {
             builder.push_spec_with_parallel_support(
-                create_exec_command_tool(config.allow_login_shell, request_permission_enabled),
+                create_exec_command_tool(
+                    config.allow_login_shell,
+                    request_permission_enabled,
+                    config.persistent_jobs,
+                ),
                 true,
             );
             builder.push_spec(create_write_stdin_tool());
             builder.register_handler("exec_command", unified_exec_handler.clone());
             builder.register_handler("write_stdin", unified_exec_handler);
+            if config.persistent_jobs {
+                builder.push_spec_with_parallel_support(create_background_jobs_tool(), true);
+                builder.register_handler("background_jobs", Arc::new(BackgroundJobsHandler));
+            }
         }
         ConfigShellToolType::Disabled => {
             // Do nothing.
@@ -1940,6 +2095,11 @@ pub(crate) fn build_specs(
         builder.register_handler("grep_files", grep_files_handler);
     }
 
+    if config.code_search {
+        builder.push_spec_with_parallel_support(create_code_search_tool(), true);
+        builder.register_handler("code_search", Arc::new(CodeSearchHandler));
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"read_file".to_string())
@@ -2075,6 +2235,10 @@ pub(crate) fn build_specs(
         }
     }
 
+    if config.tool_allowlist.is_some() {
+        builder.retain_tools(|name| config.is_tool_allowed(name));
+    }
+
     builder
 }
 
@@ -2279,7 +2443,7 @@ mod tests {
         // Build expected from the same helpers used by the builder.
         let mut expected: BTreeMap<String, ToolSpec> = BTreeMap::from([]);
         for spec in [
-            create_exec_command_tool(true, false),
+            create_exec_command_tool(true, false, false),
             create_write_stdin_tool(),
             PLAN_TOOL.clone(),
             create_request_user_input_tool(CollaborationModesConfig::default()),
@@ -2337,6 +2501,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tool_allowlist_filters_specs_and_handlers() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        })
+        .with_tool_allowlist(Some(vec![
+            "view_image".to_string(),
+            "mcp__docs__*".to_string(),
+        ]));
+
+        let (tools, registry) = build_specs(
+            &tools_config,
+            Some(HashMap::from([
+                (
+                    "mcp__docs__search".to_string(),
+                    mcp_tool("search", "Search", serde_json::json!({"type": "object"})),
+                ),
+                (
+                    "mcp__rmcp__echo".to_string(),
+                    mcp_tool("echo", "Echo", serde_json::json!({"type": "object"})),
+                ),
+            ])),
+            None,
+            &[],
+        )
+        .build();
+
+        let mut names = tools
+            .iter()
+            .map(|tool| tool_name(&tool.spec))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec!["mcp__docs__search", "view_image"]);
+        assert!(registry.handler("view_image").is_some());
+        assert!(registry.handler("mcp__rmcp__echo").is_none());
+        assert!(registry.handler("update_plan").is_none());
+    }
+
     #[test]
     fn test_build_specs_artifact_tool_enabled() {
         let mut config = test_config();
@@ -2487,6 +2696,77 @@ mod tests {
         assert_contains_tool_names(&tools, &["js_repl", "js_repl_reset"]);
     }
 
+    #[test]
+    fn code_search_tool_requires_feature() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "code_search")
+        );
+
+        let mut features = Features::with_defaults();
+        features.enable(Feature::CodeSearch);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["code_search"]);
+        assert!(find_tool(&tools, "code_search").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn background_jobs_tool_requires_feature() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::UnifiedExec);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "background_jobs")
+        );
+
+        features.enable(Feature::PersistentJobs);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+            session_source: SessionSource::Cli,
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["exec_command", "background_jobs"]);
+        let ToolSpec::Function(ResponsesApiTool {
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = &find_tool(&tools, "exec_command").spec
+        else {
+            panic!("exec_command should be a function tool");
+        };
+        assert!(properties.contains_key("persist"));
+    }
+
     #[test]
     fn image_generation_tools_require_feature_and_supported_model() {
         let config = test_config();
@@ -1,4 +1,5 @@
 use crate::exec::ExecToolCallOutput;
+use crate::sandboxing::seccomp::SeccompDenial;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -20,6 +21,7 @@ pub(crate) enum UnifiedExecError {
     SandboxDenied {
         message: String,
         output: ExecToolCallOutput,
+        seccomp_denial: Option<SeccompDenial>,
     },
 }
 
@@ -29,6 +31,10 @@ impl UnifiedExecError {
     }
 
     pub(crate) fn sandbox_denied(message: String, output: ExecToolCallOutput) -> Self {
-        Self::SandboxDenied { message, output }
+        Self::SandboxDenied {
+            message,
+            output,
+            seccomp_denial: None,
+        }
     }
 }
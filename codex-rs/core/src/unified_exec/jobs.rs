@@ -0,0 +1,611 @@
+//! Persistent background jobs.
+//!
+//! A job is a unified exec process started with `persist: true`. Its output is
+//! mirrored to `$CODEX_HOME/background_jobs/<thread_id>/<process_id>.log` and
+//! its metadata to a sibling `<process_id>.json`, so the log stays readable
+//! after the job exits or the session ends.
+//!
+//! Jobs are not killed by an interrupt. On Unix a job is spawned detached (see
+//! `codex_utils_pty::detached`): it writes straight to its log, its PID is
+//! kept in the record, and when the owning session shuts down it is left
+//! running for the next session that resumes the same thread, in this Codex
+//! process or a later one, to attach to again. Elsewhere the output is
+//! mirrored to the log here and still-running jobs are parked in a
+//! process-wide registry instead, so they cannot outlive the Codex process.
+//! Jobs that are neither running nor recorded as exited are reported as
+//! [`BackgroundJobStatus::Lost`].
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex as StdMutex;
+#[cfg(unix)]
+use std::sync::Weak;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::BackgroundJob;
+use codex_protocol::protocol::BackgroundJobStatus;
+#[cfg(unix)]
+use codex_utils_pty::detached;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Duration;
+#[cfg(unix)]
+use tokio::time::Instant;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+use super::ProcessEntry;
+use super::UNIFIED_EXEC_OUTPUT_MAX_BYTES;
+use super::async_watcher::TRAILING_OUTPUT_GRACE;
+use super::head_tail_buffer::HeadTailBuffer;
+#[cfg(unix)]
+use super::process::NoopSpawnLifecycle;
+use super::process::UnifiedExecProcess;
+#[cfg(unix)]
+use crate::exec::SandboxType;
+
+const JOBS_DIR: &str = "background_jobs";
+
+/// How often a running job's log is rewritten when new output arrived.
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Persistent processes whose session shut down, keyed by thread, waiting for
+/// a session that resumes the thread to take them back.
+static PARKED_JOBS: LazyLock<StdMutex<HashMap<ThreadId, Vec<ProcessEntry>>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JobRecord {
+    pub(crate) process_id: String,
+    pub(crate) command: Vec<String>,
+    pub(crate) cwd: PathBuf,
+    /// Unix timestamp (seconds) at which the job was started.
+    pub(crate) started_at: i64,
+    #[serde(default)]
+    pub(crate) exited: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) exit_code: Option<i32>,
+    /// PID of a detached job, used to attach to it again after resume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pid: Option<u32>,
+    /// Start time of the detached process (see
+    /// `codex_utils_pty::detached::process_start_time`), so a reused PID is not
+    /// mistaken for the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) start_time: Option<u64>,
+}
+
+impl JobRecord {
+    pub(crate) fn new(process_id: String, command: Vec<String>, cwd: PathBuf) -> Self {
+        Self {
+            process_id,
+            command,
+            cwd,
+            started_at: chrono::Utc::now().timestamp(),
+            exited: false,
+            exit_code: None,
+            pid: None,
+            start_time: None,
+        }
+    }
+
+    fn to_background_job(&self, store: &JobStore, running: bool) -> BackgroundJob {
+        let status = if running {
+            BackgroundJobStatus::Running
+        } else if self.exited {
+            BackgroundJobStatus::Exited
+        } else {
+            BackgroundJobStatus::Lost
+        };
+        BackgroundJob {
+            process_id: self.process_id.clone(),
+            command: self.command.clone(),
+            cwd: self.cwd.clone(),
+            started_at: self.started_at,
+            status,
+            exit_code: self.exit_code,
+            log_path: store.log_path(&self.process_id),
+        }
+    }
+}
+
+/// On-disk records and logs for the jobs of a single thread.
+#[derive(Debug, Clone)]
+pub(crate) struct JobStore {
+    dir: PathBuf,
+}
+
+impl JobStore {
+    pub(crate) fn new(codex_home: &Path, thread_id: ThreadId) -> Self {
+        Self {
+            dir: codex_home.join(JOBS_DIR).join(thread_id.to_string()),
+        }
+    }
+
+    pub(crate) fn log_path(&self, process_id: &str) -> PathBuf {
+        self.dir.join(format!("{process_id}.log"))
+    }
+
+    /// Creates the job directory and returns the log path a detached job
+    /// writes to.
+    pub(crate) async fn prepare_log(&self, process_id: &str) -> io::Result<PathBuf> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        Ok(self.log_path(process_id))
+    }
+
+    fn record_path(&self, process_id: &str) -> PathBuf {
+        self.dir.join(format!("{process_id}.json"))
+    }
+
+    pub(crate) async fn save_record(&self, record: &JobRecord) -> io::Result<()> {
+        let json = serde_json::to_vec_pretty(record).map_err(io::Error::other)?;
+        self.write_atomically(&self.record_path(&record.process_id), &json)
+            .await
+    }
+
+    async fn write_log(&self, process_id: &str, output: &[u8]) -> io::Result<()> {
+        self.write_atomically(&self.log_path(process_id), output)
+            .await
+    }
+
+    /// Reads the last `max_bytes` of a job's output, reaching back into the
+    /// rotated log when the current one is shorter. Returns whether earlier
+    /// output was left out, in which case the partial first line is dropped.
+    pub(crate) async fn read_log(
+        &self,
+        process_id: &str,
+        max_bytes: u64,
+    ) -> io::Result<(Vec<u8>, bool)> {
+        let log_path = self.log_path(process_id);
+        let (mut output, mut truncated) = read_tail(&log_path, max_bytes).await?;
+        if !truncated {
+            let rotated_path = detached::rotated_log_path(&log_path);
+            match read_tail(&rotated_path, max_bytes - output.len() as u64).await {
+                Ok((mut earlier, earlier_truncated)) => {
+                    earlier.extend_from_slice(&output);
+                    output = earlier;
+                    truncated = earlier_truncated;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if truncated {
+            let first_line_end = output
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map_or(output.len(), |index| index + 1);
+            output.drain(..first_line_end);
+        }
+        Ok((output, truncated))
+    }
+
+    /// All readable job records, oldest first.
+    pub(crate) async fn load_records(&self) -> Vec<JobRecord> {
+        let mut records = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return records;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Ok(bytes) = tokio::fs::read(&path).await else {
+                continue;
+            };
+            match serde_json::from_slice::<JobRecord>(&bytes) {
+                Ok(record) => records.push(record),
+                Err(err) => warn!("ignoring unreadable job record {}: {err}", path.display()),
+            }
+        }
+        records.sort_by(|a, b| (a.started_at, &a.process_id).cmp(&(b.started_at, &b.process_id)));
+        records
+    }
+
+    /// Lists the thread's jobs; `running` holds the ids of live processes.
+    pub(crate) async fn list_jobs(&self, running: &HashSet<String>) -> Vec<BackgroundJob> {
+        self.load_records()
+            .await
+            .iter()
+            .map(|record| {
+                let is_running = !record.exited && running.contains(&record.process_id);
+                record.to_background_job(self, is_running)
+            })
+            .collect()
+    }
+
+    /// Writes through a temporary file so readers never observe a partial log.
+    async fn write_atomically(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let tmp_path = self.dir.join(format!(".{file_name}.tmp"));
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+}
+
+/// Mirror a persistent job's output to disk until it exits, then mark its
+/// record as exited. Detached jobs write their log themselves, and are not
+/// marked when they were only detached from this session.
+pub(crate) fn spawn_job_logger(
+    process: Arc<UnifiedExecProcess>,
+    store: JobStore,
+    mut record: JobRecord,
+) {
+    let exit_token = process.cancellation_token();
+
+    if process.pid().is_some() {
+        tokio::spawn(async move {
+            exit_token.cancelled().await;
+            if process.is_detached() {
+                return;
+            }
+            record.exited = true;
+            record.exit_code = process.exit_code();
+            if let Err(err) = store.save_record(&record).await {
+                warn!("failed to record exit of job {}: {err}", record.process_id);
+            }
+        });
+        return;
+    }
+
+    let mut receiver = process.output_receiver();
+    tokio::spawn(async move {
+        let mut buffer = HeadTailBuffer::new(UNIFIED_EXEC_OUTPUT_MAX_BYTES);
+        let mut dirty = false;
+        let mut flush = tokio::time::interval(LOG_FLUSH_INTERVAL);
+        flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = exit_token.cancelled() => break,
+
+                _ = flush.tick(), if dirty => {
+                    if let Err(err) = store.write_log(&record.process_id, &buffer.to_bytes()).await {
+                        warn!("failed to write log for job {}: {err}", record.process_id);
+                    }
+                    dirty = false;
+                }
+
+                received = receiver.recv() => match received {
+                    Ok(chunk) => {
+                        buffer.push_chunk(chunk);
+                        dirty = true;
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+
+        tokio::time::sleep(TRAILING_OUTPUT_GRACE).await;
+        while let Ok(chunk) = receiver.try_recv() {
+            buffer.push_chunk(chunk);
+        }
+        if let Err(err) = store
+            .write_log(&record.process_id, &buffer.to_bytes())
+            .await
+        {
+            warn!("failed to write log for job {}: {err}", record.process_id);
+        }
+
+        record.exited = true;
+        record.exit_code = process.exit_code();
+        if let Err(err) = store.save_record(&record).await {
+            warn!("failed to record exit of job {}: {err}", record.process_id);
+        }
+    });
+}
+
+pub(super) fn park_jobs(thread_id: ThreadId, entries: Vec<ProcessEntry>) {
+    if entries.is_empty() {
+        return;
+    }
+    let mut parked = PARKED_JOBS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    parked.entry(thread_id).or_default().extend(entries);
+}
+
+/// Takes the thread's parked jobs, dropping any that exited in the meantime.
+pub(super) fn take_parked_jobs(thread_id: ThreadId) -> Vec<ProcessEntry> {
+    let entries = {
+        let mut parked = PARKED_JOBS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        parked.remove(&thread_id).unwrap_or_default()
+    };
+    entries
+        .into_iter()
+        .filter(|entry| !entry.process.has_exited())
+        .collect()
+}
+
+/// Attaches to a detached job recorded by an earlier session, if its process
+/// is still running, and starts logging its exit.
+#[cfg(unix)]
+pub(super) async fn attach_job(store: &JobStore, record: &JobRecord) -> Option<ProcessEntry> {
+    let pid = record
+        .pid
+        .filter(|pid| detached::is_process_alive(*pid, record.start_time))?;
+    let spawned =
+        match detached::attach_process(pid, record.start_time, &store.log_path(&record.process_id))
+        {
+            Ok(spawned) => spawned,
+            Err(err) => {
+                warn!("failed to attach to job {}: {err}", record.process_id);
+                return None;
+            }
+        };
+    // The sandbox was checked when the job started; there is nothing left to
+    // report about it here.
+    let process = match UnifiedExecProcess::from_spawned(
+        spawned,
+        SandboxType::None,
+        Vec::new(),
+        Box::new(NoopSpawnLifecycle),
+    )
+    .await
+    {
+        Ok(process) => Arc::new(process),
+        Err(err) => {
+            warn!("failed to attach to job {}: {err}", record.process_id);
+            return None;
+        }
+    };
+    spawn_job_logger(Arc::clone(&process), store.clone(), record.clone());
+    Some(ProcessEntry {
+        process,
+        call_id: String::new(),
+        process_id: record.process_id.clone(),
+        command: record.command.clone(),
+        tty: false,
+        network_approval_id: None,
+        session: Weak::new(),
+        last_used: Instant::now(),
+        persistent: true,
+    })
+}
+
+/// Reads at most the last `max_bytes` of `path`, and whether anything before
+/// them was skipped.
+async fn read_tail(path: &Path, max_bytes: u64) -> io::Result<(Vec<u8>, bool)> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let start = file.metadata().await?.len().saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut data = Vec::new();
+    file.take(max_bytes).read_to_end(&mut data).await?;
+    Ok((data, start > 0))
+}
+
+/// Lines of `log` matching `pattern` (all lines when `None`), keeping only the
+/// last `max_lines`. Line numbers are 1-based.
+pub(crate) fn search_log<'a>(
+    log: &'a str,
+    pattern: Option<&regex_lite::Regex>,
+    max_lines: usize,
+) -> Vec<(usize, &'a str)> {
+    let matches: Vec<(usize, &str)> = log
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| pattern.is_none_or(|pattern| pattern.is_match(line)))
+        .map(|(index, line)| (index + 1, line))
+        .collect();
+    let skip = matches.len().saturating_sub(max_lines);
+    matches.into_iter().skip(skip).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn job_records_round_trip_and_report_status() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let store = JobStore::new(codex_home.path(), ThreadId::new());
+
+        let running = JobRecord::new(
+            "1000".to_string(),
+            vec!["sleep".to_string(), "60".to_string()],
+            PathBuf::from("/tmp"),
+        );
+        let mut exited = JobRecord::new(
+            "1001".to_string(),
+            vec!["true".to_string()],
+            PathBuf::from("/tmp"),
+        );
+        exited.exited = true;
+        exited.exit_code = Some(0);
+        let lost = JobRecord::new(
+            "1002".to_string(),
+            vec!["make".to_string()],
+            PathBuf::from("/tmp"),
+        );
+        for record in [&running, &exited, &lost] {
+            store.save_record(record).await.expect("save record");
+        }
+
+        let live = HashSet::from(["1000".to_string()]);
+        let statuses: Vec<(String, BackgroundJobStatus, Option<i32>)> = store
+            .list_jobs(&live)
+            .await
+            .into_iter()
+            .map(|job| (job.process_id, job.status, job.exit_code))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("1000".to_string(), BackgroundJobStatus::Running, None),
+                ("1001".to_string(), BackgroundJobStatus::Exited, Some(0)),
+                ("1002".to_string(), BackgroundJobStatus::Lost, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn logs_are_readable_after_write() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let store = JobStore::new(codex_home.path(), ThreadId::new());
+
+        store
+            .write_log("1000", b"first\nsecond\n")
+            .await
+            .expect("write log");
+        store
+            .write_log("1000", b"replaced\n")
+            .await
+            .expect("rewrite");
+
+        assert_eq!(
+            store.read_log("1000", 1024).await.expect("read"),
+            (b"replaced\n".to_vec(), false)
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn read_log_returns_a_bounded_tail_across_the_rotated_log() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let store = JobStore::new(codex_home.path(), ThreadId::new());
+        let log_path = store.prepare_log("1000").await.expect("prepare log");
+        std::fs::write(detached::rotated_log_path(&log_path), "one\ntwo\nthree\n")
+            .expect("write rotated log");
+        std::fs::write(&log_path, "four\n").expect("write log");
+
+        assert_eq!(
+            store.read_log("1000", 1024).await.expect("read"),
+            (b"one\ntwo\nthree\nfour\n".to_vec(), false)
+        );
+        // The cut falls inside `two`, which is dropped.
+        assert_eq!(
+            store.read_log("1000", 13).await.expect("read"),
+            (b"three\nfour\n".to_vec(), true)
+        );
+        assert_eq!(
+            store.read_log("1000", 3).await.expect("read"),
+            (Vec::new(), true)
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn detached_jobs_are_attached_again_and_record_their_exit() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let store = JobStore::new(codex_home.path(), ThreadId::new());
+        let log_path = store.prepare_log("1000").await.expect("prepare log");
+        let env: HashMap<String, String> = std::env::vars().collect();
+        let spawned = detached::spawn_process(
+            "/bin/sh",
+            &["-c".to_string(), "sleep 60".to_string()],
+            codex_home.path(),
+            &env,
+            &None,
+            &log_path,
+        )
+        .await
+        .expect("spawn");
+        let mut record = JobRecord::new(
+            "1000".to_string(),
+            vec!["sleep".to_string(), "60".to_string()],
+            codex_home.path().to_path_buf(),
+        );
+        record.pid = spawned.session.pid();
+        record.start_time = record.pid.and_then(detached::process_start_time);
+        store.save_record(&record).await.expect("save record");
+        // What an earlier session leaves behind when it shuts down.
+        spawned.session.disown();
+        drop(spawned);
+
+        let entry = attach_job(&store, &record).await.expect("attach job");
+        assert!(!entry.process.has_exited());
+        entry.process.terminate();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let exited = loop {
+            let records = store.load_records().await;
+            if records.iter().all(|record| record.exited) || Instant::now() >= deadline {
+                break records;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        };
+        assert_eq!(
+            exited
+                .iter()
+                .map(|record| (record.exited, record.exit_code))
+                .collect::<Vec<_>>(),
+            vec![(true, None)]
+        );
+        assert!(attach_job(&store, &record).await.is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn reused_pid_with_a_different_start_time_is_lost() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let store = JobStore::new(codex_home.path(), ThreadId::new());
+        let log_path = store.prepare_log("1000").await.expect("prepare log");
+        let env: HashMap<String, String> = std::env::vars().collect();
+        let spawned = detached::spawn_process(
+            "/bin/sh",
+            &["-c".to_string(), "sleep 60".to_string()],
+            codex_home.path(),
+            &env,
+            &None,
+            &log_path,
+        )
+        .await
+        .expect("spawn");
+        let mut record = JobRecord::new(
+            "1000".to_string(),
+            vec!["sleep".to_string(), "60".to_string()],
+            codex_home.path().to_path_buf(),
+        );
+        record.pid = spawned.session.pid();
+        let start_time = record
+            .pid
+            .and_then(detached::process_start_time)
+            .expect("start time");
+        // The live process stands in for an unrelated one that took over the
+        // job's PID after the job exited.
+        record.start_time = Some(start_time + 1);
+        store.save_record(&record).await.expect("save record");
+
+        assert!(attach_job(&store, &record).await.is_none());
+        let statuses: Vec<BackgroundJobStatus> = store
+            .list_jobs(&HashSet::new())
+            .await
+            .into_iter()
+            .map(|job| job.status)
+            .collect();
+        assert_eq!(statuses, vec![BackgroundJobStatus::Lost]);
+
+        spawned.session.terminate();
+    }
+
+    #[test]
+    fn search_log_filters_and_keeps_the_tail() {
+        let log = "compiling a\nerror: one\ncompiling b\nerror: two\nerror: three\n";
+        let pattern = regex_lite::Regex::new("^error").expect("regex");
+
+        assert_eq!(
+            search_log(log, Some(&pattern), 2),
+            vec![(4, "error: two"), (5, "error: three")]
+        );
+        assert_eq!(search_log(log, None, 1), vec![(5, "error: three")]);
+    }
+}
@@ -41,6 +41,7 @@ use crate::sandboxing::SandboxPermissions;
 mod async_watcher;
 mod errors;
 mod head_tail_buffer;
+mod jobs;
 mod process;
 mod process_manager;
 
@@ -49,6 +50,8 @@ pub(crate) fn set_deterministic_process_ids_for_tests(enabled: bool) {
 }
 
 pub(crate) use errors::UnifiedExecError;
+pub(crate) use jobs::JobStore;
+pub(crate) use jobs::search_log;
 pub(crate) use process::NoopSpawnLifecycle;
 #[cfg(unix)]
 pub(crate) use process::SpawnLifecycle;
@@ -97,6 +100,9 @@ pub(crate) struct ExecCommandRequest {
     pub additional_permissions: Option<PermissionProfile>,
     pub justification: Option<String>,
     pub prefix_rule: Option<Vec<String>>,
+    /// Keep the process alive across interrupts and session resume, logging
+    /// its output to disk (see `jobs.rs`).
+    pub persist: bool,
 }
 
 #[derive(Debug)]
@@ -164,6 +170,7 @@ struct ProcessEntry {
     network_approval_id: Option<String>,
     session: Weak<Session>,
     last_used: tokio::time::Instant,
+    persistent: bool,
 }
 
 pub(crate) fn clamp_yield_time(yield_time_ms: u64) -> u64 {
@@ -242,6 +249,7 @@ mod tests {
                     additional_permissions: None,
                     justification: None,
                     prefix_rule: None,
+                    persist: false,
                 },
                 &context,
             )
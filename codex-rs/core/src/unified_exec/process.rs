@@ -16,8 +16,10 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::sandboxing::seccomp::SeccompDenial;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
+use codex_protocol::permissions::SeccompProfile;
 use codex_utils_pty::ExecCommandSession;
 use codex_utils_pty::SpawnedPty;
 
@@ -57,6 +59,8 @@ pub(crate) struct UnifiedExecProcess {
     output_drained: Arc<Notify>,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    seccomp_profiles: Vec<SeccompProfile>,
+    detached: AtomicBool,
     _spawn_lifecycle: SpawnLifecycleHandle,
 }
 
@@ -65,6 +69,7 @@ impl UnifiedExecProcess {
         process_handle: ExecCommandSession,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
         sandbox_type: SandboxType,
+        seccomp_profiles: Vec<SeccompProfile>,
         spawn_lifecycle: SpawnLifecycleHandle,
     ) -> Self {
         let output_buffer = Arc::new(Mutex::new(HeadTailBuffer::default()));
@@ -109,6 +114,8 @@ impl UnifiedExecProcess {
             output_drained,
             output_task,
             sandbox_type,
+            seccomp_profiles,
+            detached: AtomicBool::new(false),
             _spawn_lifecycle: spawn_lifecycle,
         }
     }
@@ -147,6 +154,23 @@ impl UnifiedExecProcess {
         self.process_handle.exit_code()
     }
 
+    /// PID of a process spawned detached (see `jobs.rs`).
+    pub(super) fn pid(&self) -> Option<u32> {
+        self.process_handle.pid()
+    }
+
+    /// Stops watching the process without killing it, leaving it running
+    /// for a later session to attach to.
+    pub(super) fn detach(&self) {
+        self.detached.store(true, Ordering::Release);
+        self.process_handle.disown();
+        self.terminate();
+    }
+
+    pub(super) fn is_detached(&self) -> bool {
+        self.detached.load(Ordering::Acquire)
+    }
+
     pub(super) fn terminate(&self) {
         self.output_closed.store(true, Ordering::Release);
         self.output_closed_notify.notify_waiters();
@@ -190,12 +214,22 @@ impl UnifiedExecProcess {
         }
 
         let exit_code = self.exit_code().unwrap_or(-1);
-        let exec_output = ExecToolCallOutput {
+        let mut exec_output = ExecToolCallOutput {
             exit_code,
             stderr: StreamOutput::new(text.to_string()),
             aggregated_output: StreamOutput::new(text.to_string()),
             ..Default::default()
         };
+        if let Some(seccomp_denial) =
+            SeccompDenial::from_output(sandbox_type, &self.seccomp_profiles, &exec_output)
+        {
+            seccomp_denial.append_to_output(&mut exec_output);
+            return Err(UnifiedExecError::SandboxDenied {
+                message: seccomp_denial.message(),
+                output: exec_output,
+                seccomp_denial: Some(seccomp_denial),
+            });
+        }
         if is_likely_sandbox_denied(sandbox_type, &exec_output) {
             let snippet = formatted_truncate_text(
                 text,
@@ -214,6 +248,7 @@ impl UnifiedExecProcess {
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
+        seccomp_profiles: Vec<SeccompProfile>,
         spawn_lifecycle: SpawnLifecycleHandle,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
@@ -223,7 +258,13 @@ impl UnifiedExecProcess {
             mut exit_rx,
         } = spawned;
         let output_rx = codex_utils_pty::combine_output_receivers(stdout_rx, stderr_rx);
-        let managed = Self::new(process_handle, output_rx, sandbox_type, spawn_lifecycle);
+        let managed = Self::new(
+            process_handle,
+            output_rx,
+            sandbox_type,
+            seccomp_profiles,
+            spawn_lifecycle,
+        );
 
         let exit_ready = matches!(exit_rx.try_recv(), Ok(_) | Err(TryRecvError::Closed));
 
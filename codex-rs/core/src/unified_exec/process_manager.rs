@@ -1,9 +1,12 @@
+use codex_protocol::ThreadId;
 use rand::Rng;
 use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Weak;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use tokio::sync::Notify;
@@ -12,12 +15,15 @@ use tokio::time::Duration;
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
 use crate::sandboxing::ExecRequest;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
+use crate::tools::events::ToolEventFailure;
 use crate::tools::events::ToolEventStage;
 use crate::tools::network_approval::DeferredNetworkApproval;
 use crate::tools::network_approval::finish_deferred_network_approval;
@@ -25,6 +31,7 @@ use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolRequest;
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
@@ -47,6 +54,13 @@ use crate::unified_exec::async_watcher::start_streaming_output;
 use crate::unified_exec::clamp_yield_time;
 use crate::unified_exec::generate_chunk_id;
 use crate::unified_exec::head_tail_buffer::HeadTailBuffer;
+use crate::unified_exec::jobs::JobRecord;
+use crate::unified_exec::jobs::JobStore;
+#[cfg(unix)]
+use crate::unified_exec::jobs::attach_job;
+use crate::unified_exec::jobs::park_jobs;
+use crate::unified_exec::jobs::spawn_job_logger;
+use crate::unified_exec::jobs::take_parked_jobs;
 use crate::unified_exec::process::OutputBuffer;
 use crate::unified_exec::process::OutputHandles;
 use crate::unified_exec::process::SpawnLifecycleHandle;
@@ -164,8 +178,28 @@ impl UnifiedExecProcessManager {
             .workdir
             .clone()
             .unwrap_or_else(|| context.turn.cwd.clone());
+        let job_store = if request.persist {
+            Some(JobStore::new(
+                &context.session.codex_home().await,
+                context.session.conversation_id,
+            ))
+        } else {
+            None
+        };
+        let job_log = match &job_store {
+            Some(store) => match store.prepare_log(&request.process_id).await {
+                Ok(log_path) => Some(log_path),
+                Err(err) => {
+                    self.release_process_id(&request.process_id).await;
+                    return Err(UnifiedExecError::create_process(format!(
+                        "failed to create job log: {err}"
+                    )));
+                }
+            },
+            None => None,
+        };
         let process = self
-            .open_session_with_sandbox(&request, cwd.clone(), context)
+            .open_session_with_sandbox(&request, cwd.clone(), job_log, context)
             .await;
 
         let (process, mut deferred_network_approval) = match process {
@@ -178,6 +212,25 @@ impl UnifiedExecProcessManager {
             }
         };
 
+        if let Some(store) = job_store {
+            let mut record = JobRecord::new(
+                request.process_id.clone(),
+                request.command.clone(),
+                cwd.clone(),
+            );
+            record.pid = process.pid();
+            #[cfg(unix)]
+            {
+                record.start_time = record
+                    .pid
+                    .and_then(codex_utils_pty::detached::process_start_time);
+            }
+            if let Err(err) = store.save_record(&record).await {
+                tracing::warn!("failed to record job {}: {err}", request.process_id);
+            }
+            spawn_job_logger(Arc::clone(&process), store, record);
+        }
+
         let transcript = Arc::new(tokio::sync::Mutex::new(HeadTailBuffer::default()));
         let event_ctx = ToolEventCtx::new(
             context.session.as_ref(),
@@ -268,7 +321,9 @@ impl UnifiedExecProcessManager {
                 cwd.clone(),
                 start,
                 process_id,
-                request.tty,
+                // Detached jobs have no stdin.
+                request.tty && process.pid().is_none(),
+                request.persist,
                 network_approval_id,
                 Arc::clone(&transcript),
             )
@@ -476,6 +531,7 @@ impl UnifiedExecProcessManager {
         started_at: Instant,
         process_id: String,
         tty: bool,
+        persistent: bool,
         network_approval_id: Option<String>,
         transcript: Arc<tokio::sync::Mutex<HeadTailBuffer>>,
     ) {
@@ -488,6 +544,7 @@ impl UnifiedExecProcessManager {
             network_approval_id,
             session: Arc::downgrade(&context.session),
             last_used: started_at,
+            persistent,
         };
         let (number_processes, pruned_entry) = {
             let mut store = self.process_store.lock().await;
@@ -529,6 +586,7 @@ impl UnifiedExecProcessManager {
         &self,
         env: &ExecRequest,
         tty: bool,
+        job_log: Option<&Path>,
         mut spawn_lifecycle: SpawnLifecycleHandle,
     ) -> Result<UnifiedExecProcess, UnifiedExecError> {
         let (program, args) = env
@@ -536,6 +594,38 @@ impl UnifiedExecProcessManager {
             .split_first()
             .ok_or(UnifiedExecError::MissingCommandLine)?;
 
+        #[cfg(unix)]
+        if let Some(log_path) = job_log {
+            let args = if env.sandbox == crate::exec::SandboxType::LinuxSeccomp
+                && env.arg0.as_deref() == Some("codex-linux-sandbox")
+            {
+                crate::landlock::outlive_parent(args.to_vec())
+            } else {
+                args.to_vec()
+            };
+            let spawned = codex_utils_pty::detached::spawn_process(
+                program,
+                &args,
+                env.cwd.as_path(),
+                &env.env,
+                &env.arg0,
+                log_path,
+            )
+            .await
+            .map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
+            spawn_lifecycle.after_spawn();
+            return UnifiedExecProcess::from_spawned(
+                spawned,
+                env.sandbox,
+                env.seccomp_profiles.clone(),
+                spawn_lifecycle,
+            )
+            .await;
+        }
+        // Elsewhere jobs are mirrored to their log by `spawn_job_logger`.
+        #[cfg(not(unix))]
+        let _ = job_log;
+
         let spawn_result = if tty {
             codex_utils_pty::pty::spawn_process(
                 program,
@@ -559,16 +649,23 @@ impl UnifiedExecProcessManager {
         let spawned =
             spawn_result.map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
         spawn_lifecycle.after_spawn();
-        UnifiedExecProcess::from_spawned(spawned, env.sandbox, spawn_lifecycle).await
+        UnifiedExecProcess::from_spawned(
+            spawned,
+            env.sandbox,
+            env.seccomp_profiles.clone(),
+            spawn_lifecycle,
+        )
+        .await
     }
 
     pub(super) async fn open_session_with_sandbox(
         &self,
         request: &ExecCommandRequest,
         cwd: PathBuf,
+        job_log: Option<PathBuf>,
         context: &UnifiedExecContext,
     ) -> Result<(UnifiedExecProcess, Option<DeferredNetworkApproval>), UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(
+        let mut env = apply_unified_exec_env(create_env(
             &context.turn.shell_environment_policy,
             Some(context.session.conversation_id),
         ));
@@ -585,15 +682,28 @@ impl UnifiedExecProcessManager {
                 sandbox_policy: context.turn.sandbox_policy.get(),
                 sandbox_permissions: request.sandbox_permissions,
                 prefix_rule: request.prefix_rule.clone(),
+                cwd: Some(cwd.as_path()),
+                env: Some(&env),
             })
             .await;
+        let mut explicit_env_overrides = context.turn.shell_environment_policy.r#set.clone();
+        for env_var in context.session.services.secret_env.inject(
+            &request.command,
+            exec_approval_requirement.allowed_by_policy_rule(),
+            &mut env,
+        ) {
+            if let Some(value) = env.get(&env_var) {
+                explicit_env_overrides.insert(env_var, value.clone());
+            }
+        }
         let req = UnifiedExecToolRequest {
             command: request.command.clone(),
             cwd,
             env,
-            explicit_env_overrides: context.turn.shell_environment_policy.r#set.clone(),
+            explicit_env_overrides,
             network: request.network.clone(),
             tty: request.tty,
+            job_log,
             sandbox_permissions: request.sandbox_permissions,
             additional_permissions: request.additional_permissions.clone(),
             justification: request.justification.clone(),
@@ -605,7 +715,7 @@ impl UnifiedExecProcessManager {
             call_id: context.call_id.clone(),
             tool_name: "exec_command".to_string(),
         };
-        orchestrator
+        let result = orchestrator
             .run(
                 &mut runtime,
                 &req,
@@ -613,7 +723,39 @@ impl UnifiedExecProcessManager {
                 &context.turn,
                 context.turn.approval_policy.value(),
             )
-            .await
+            .await;
+        if let Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+            output,
+            seccomp_denial: Some(seccomp_denial),
+            ..
+        }))) = &result
+        {
+            // The process never started streaming, so report the denial as a
+            // complete begin/end pair that carries the offending profiles.
+            let event_ctx = ToolEventCtx::new(
+                context.session.as_ref(),
+                context.turn.as_ref(),
+                &context.call_id,
+                None,
+            );
+            let emitter = ToolEmitter::unified_exec(
+                &request.command,
+                req.cwd.clone(),
+                ExecCommandSource::UnifiedExecStartup,
+                Some(request.process_id.clone()),
+            );
+            emitter.emit(event_ctx, ToolEventStage::Begin).await;
+            emitter
+                .emit(
+                    event_ctx,
+                    ToolEventStage::Failure(ToolEventFailure::SeccompDenied(
+                        output.as_ref().clone(),
+                        seccomp_denial.clone(),
+                    )),
+                )
+                .await;
+        }
+        result
             .map(|result| (result.output, result.deferred_network_approval))
             .map_err(|e| UnifiedExecError::create_process(format!("{e:?}")))
     }
@@ -762,6 +904,116 @@ impl UnifiedExecProcessManager {
             entry.process.terminate();
         }
     }
+
+    /// Terminates every process except persistent jobs.
+    pub(crate) async fn terminate_transient_processes(&self) {
+        let entries = self.take_processes(|entry| !entry.persistent).await;
+        for entry in entries {
+            Self::unregister_network_approval_for_entry(&entry).await;
+            entry.process.terminate();
+        }
+    }
+
+    /// Moves live persistent jobs out of this manager so they survive the
+    /// session shutting down; see [`Self::reattach_jobs`]. Detached jobs are
+    /// left running on their own, the others are parked in this process.
+    pub(crate) async fn park_persistent_processes(&self, thread_id: ThreadId) {
+        let entries = self
+            .take_processes(|entry| entry.persistent && !entry.process.has_exited())
+            .await;
+        let mut parked = Vec::with_capacity(entries.len());
+        for mut entry in entries {
+            // Network approvals belong to the session that is going away.
+            Self::unregister_network_approval_for_entry(&entry).await;
+            if entry.process.pid().is_some() {
+                entry.process.detach();
+                continue;
+            }
+            entry.network_approval_id = None;
+            entry.session = Weak::new();
+            parked.push(entry);
+        }
+        park_jobs(thread_id, parked);
+    }
+
+    /// Takes back the jobs left running by an earlier session of `thread_id`:
+    /// those parked in this process and detached jobs in `records` that are
+    /// still alive. Reserves the ids of all `records` so new processes do not
+    /// reuse the id of a job recorded on disk. Returns the number of
+    /// reattached jobs.
+    pub(crate) async fn reattach_jobs(
+        &self,
+        thread_id: ThreadId,
+        job_store: &JobStore,
+        records: &[JobRecord],
+    ) -> usize {
+        #[cfg_attr(not(unix), allow(unused_mut))]
+        let mut entries = take_parked_jobs(thread_id);
+        #[cfg(unix)]
+        for record in records {
+            if record.exited
+                || entries
+                    .iter()
+                    .any(|entry| entry.process_id == record.process_id)
+            {
+                continue;
+            }
+            if let Some(entry) = attach_job(job_store, record).await {
+                entries.push(entry);
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = job_store;
+        let count = entries.len();
+        let mut store = self.process_store.lock().await;
+        store
+            .reserved_process_ids
+            .extend(records.iter().map(|record| record.process_id.clone()));
+        for entry in entries {
+            store.reserved_process_ids.insert(entry.process_id.clone());
+            store.processes.insert(entry.process_id.clone(), entry);
+        }
+        count
+    }
+
+    /// Terminates a single process. Returns `false` when it is unknown.
+    pub(crate) async fn kill_process(&self, process_id: &str) -> bool {
+        let entry = {
+            let mut store = self.process_store.lock().await;
+            store.remove(process_id)
+        };
+        let Some(entry) = entry else {
+            return false;
+        };
+        Self::unregister_network_approval_for_entry(&entry).await;
+        entry.process.terminate();
+        true
+    }
+
+    /// Ids of the persistent jobs that are still running.
+    pub(crate) async fn running_job_ids(&self) -> HashSet<String> {
+        let store = self.process_store.lock().await;
+        store
+            .processes
+            .values()
+            .filter(|entry| entry.persistent && !entry.process.has_exited())
+            .map(|entry| entry.process_id.clone())
+            .collect()
+    }
+
+    async fn take_processes(&self, filter: impl Fn(&ProcessEntry) -> bool) -> Vec<ProcessEntry> {
+        let mut store = self.process_store.lock().await;
+        let process_ids: Vec<String> = store
+            .processes
+            .iter()
+            .filter(|(_, entry)| filter(entry))
+            .map(|(process_id, _)| process_id.clone())
+            .collect();
+        process_ids
+            .iter()
+            .filter_map(|process_id| store.remove(process_id))
+            .collect()
+    }
 }
 
 enum ProcessStatus {
@@ -1,7 +1,10 @@
 use codex_core::features::Feature;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
 use core_test_support::test_codex::test_codex;
@@ -81,3 +84,51 @@ async fn hierarchical_agents_emits_when_no_project_doc() {
         "expected hierarchical agents message appended: {instructions}"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn editing_files_loads_nearest_scoped_agents_md() {
+    let server = start_mock_server().await;
+    let patch =
+        "*** Begin Patch\n*** Add File: crates/foo/src/lib.rs\n+pub fn foo() {}\n*** End Patch";
+    let resp_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_function_call("call-1", patch),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.include_apply_patch_tool = true;
+        let crate_dir = config.cwd.join("crates/foo");
+        std::fs::create_dir_all(&crate_dir).expect("create crate dir");
+        std::fs::write(crate_dir.join("AGENTS.md"), "foo rules").expect("write AGENTS.md");
+    });
+    let test = builder.build(&server).await.expect("build test codex");
+
+    test.submit_turn("add foo").await.expect("submit turn");
+
+    let requests = resp_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let has_scoped_doc = |index: usize| {
+        requests[index]
+            .message_input_texts("user")
+            .iter()
+            .any(|text| {
+                text.starts_with("# AGENTS.md instructions for ") && text.contains("foo rules")
+            })
+    };
+    assert!(
+        !has_scoped_doc(0),
+        "scoped doc must not load before the edit"
+    );
+    assert!(has_scoped_doc(1), "scoped doc should load after the edit");
+}
@@ -11,6 +11,7 @@ use anyhow::bail;
 use codex_core::CodexThread;
 use codex_core::features::Feature;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ListCheckpointsResponseEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::UndoCompletedEvent;
 use core_test_support::responses::ev_apply_patch_function_call;
@@ -112,7 +113,11 @@ async fn run_apply_patch_turn(
 }
 
 async fn invoke_undo(codex: &Arc<CodexThread>) -> Result<UndoCompletedEvent> {
-    codex.submit(Op::Undo).await?;
+    submit_undo_op(codex, Op::Undo).await
+}
+
+async fn submit_undo_op(codex: &Arc<CodexThread>, op: Op) -> Result<UndoCompletedEvent> {
+    codex.submit(op).await?;
     let event = wait_for_event_match(codex, |msg| match msg {
         EventMsg::UndoCompleted(done) => Some(done.clone()),
         _ => None,
@@ -551,3 +556,140 @@ async fn undo_preserves_unrelated_staged_changes() -> Result<()> {
 
     Ok(())
 }
+
+async fn list_checkpoints(codex: &Arc<CodexThread>) -> Result<ListCheckpointsResponseEvent> {
+    codex.submit(Op::ListCheckpoints).await?;
+    let event = wait_for_event_match(codex, |msg| match msg {
+        EventMsg::ListCheckpointsResponse(listing) => Some(listing.clone()),
+        _ => None,
+    })
+    .await;
+    Ok(event)
+}
+
+async fn run_story_turns(harness: &TestCodexHarness, story: &Path) -> Result<()> {
+    fs::write(story, "initial\n")?;
+    let versions = ["initial", "turn one", "turn two", "turn three"];
+    for (idx, (before, after)) in versions.iter().zip(versions.iter().skip(1)).enumerate() {
+        run_apply_patch_turn(
+            harness,
+            &format!("change {idx}"),
+            &format!("story-turn-{idx}"),
+            &format!("*** Begin Patch\n*** Update File: story.txt\n@@\n-{before}\n+{after}\n*** End Patch"),
+            "ok",
+        )
+        .await?;
+        assert_eq!(fs::read_to_string(story)?, format!("{after}\n"));
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_multiple_turns_then_redo() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    let story = harness.path("story.txt");
+    run_story_turns(&harness, &story).await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    let completed = submit_undo_op(&codex, Op::UndoTurns { num_turns: 2 }).await?;
+    assert!(completed.success, "undo failed: {:?}", completed.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn one\n");
+
+    let listing = list_checkpoints(&codex).await?;
+    assert_eq!(listing.checkpoints.len(), 1);
+    assert_eq!(listing.redo_depth, 1);
+
+    let completed = submit_undo_op(&codex, Op::Redo).await?;
+    assert!(completed.success, "redo failed: {:?}", completed.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn three\n");
+
+    let listing = list_checkpoints(&codex).await?;
+    assert_eq!(listing.checkpoints.len(), 3);
+    assert_eq!(listing.redo_depth, 0);
+
+    let completed = submit_undo_op(&codex, Op::Redo).await?;
+    assert!(!completed.success);
+    assert_eq!(completed.message.as_deref(), Some("Nothing to redo."));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_more_turns_than_available_fails() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    let story = harness.path("story.txt");
+    run_story_turns(&harness, &story).await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    let completed = submit_undo_op(&codex, Op::UndoTurns { num_turns: 5 }).await?;
+    assert!(!completed.success);
+    assert_eq!(
+        completed.message.as_deref(),
+        Some("Cannot undo 5 turns; only 3 snapshots are available.")
+    );
+    assert_eq!(fs::read_to_string(&story)?, "turn three\n");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn checkpoints_include_turn_diffs() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+    let story = harness.path("story.txt");
+    run_story_turns(&harness, &story).await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    let listing = list_checkpoints(&codex).await?;
+    assert_eq!(
+        listing
+            .checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.turns_back)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    let newest_diff = listing.checkpoints[0]
+        .diff
+        .as_deref()
+        .context("newest checkpoint should carry its turn diff")?;
+    assert!(newest_diff.contains("-turn two"), "{newest_diff}");
+    assert!(newest_diff.contains("+turn three"), "{newest_diff}");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn undo_outside_git_repo_uses_shadow_store() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let builder = test_codex().with_model("gpt-5.1").with_config(|config| {
+        config.include_apply_patch_tool = true;
+        config.ghost_snapshot.shadow_store = Some(config.codex_home.join("snapshots"));
+        config
+            .features
+            .enable(Feature::GhostCommit)
+            .expect("test config should allow feature update");
+    });
+    let harness = TestCodexHarness::with_builder(builder).await?;
+    let story = harness.path("story.txt");
+    run_story_turns(&harness, &story).await?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    expect_successful_undo(&codex).await?;
+    assert_eq!(fs::read_to_string(&story)?, "turn two\n");
+
+    let completed = submit_undo_op(&codex, Op::Redo).await?;
+    assert!(completed.success, "redo failed: {:?}", completed.message);
+    assert_eq!(fs::read_to_string(&story)?, "turn three\n");
+
+    Ok(())
+}
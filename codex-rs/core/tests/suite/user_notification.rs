@@ -2,6 +2,8 @@
 
 use std::os::unix::fs::PermissionsExt;
 
+use codex_core::config::types::HookCommandToml;
+use codex_core::config::types::HooksToml;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
 use codex_protocol::user_input::UserInput;
@@ -77,3 +79,40 @@ mv "${tmp_path}" "${payload_path}""#,
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_start_hook_deny_stops_the_session() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let hooks = HooksToml {
+        session_start: vec![HookCommandToml {
+            name: Some("business-hours".to_string()),
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"echo '{"decision": "deny", "reason": "outside business hours"}'"#.to_string(),
+            ],
+            timeout_ms: None,
+            fail_closed: None,
+        }],
+        ..HooksToml::default()
+    };
+
+    let Err(err) = test_codex()
+        .with_config(move |cfg| cfg.hooks = hooks.into())
+        .build(&server)
+        .await
+    else {
+        panic!("session_start deny should stop the session");
+    };
+
+    assert!(
+        err.to_string().contains(
+            "session_start hook 'business-hours' denied the session: outside business hours"
+        ),
+        "unexpected error: {err:#}"
+    );
+
+    Ok(())
+}
@@ -99,6 +99,18 @@ pub struct Cli {
     )]
     pub json: bool,
 
+    /// How approval and user-input requests are answered. `stdio` implies `--json`: requests
+    /// are emitted as JSONL events and decisions are read as JSONL from stdin, so the prompt
+    /// must be passed as an argument.
+    #[arg(
+        long = "approvals",
+        value_enum,
+        value_name = "MODE",
+        default_value_t = ApprovalsMode::Never,
+        conflicts_with = "dangerously_bypass_approvals_and_sandbox"
+    )]
+    pub approvals: ApprovalsMode,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(
         long = "output-last-message",
@@ -258,6 +270,16 @@ pub enum Color {
     Auto,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ApprovalsMode {
+    /// Never ask; requests that need approval are rejected.
+    #[default]
+    Never,
+    /// Emit requests on stdout and read decisions from stdin.
+    Stdio,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,4 +337,24 @@ mod tests {
         assert_eq!(args.session_id.as_deref(), Some("session-123"));
         assert_eq!(args.prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn approvals_stdio_parses_and_conflicts_with_bypass() {
+        let cli = Cli::parse_from(["codex-exec", "--approvals=stdio", "fix the build"]);
+        assert_eq!(cli.approvals, ApprovalsMode::Stdio);
+        assert_eq!(cli.prompt.as_deref(), Some("fix the build"));
+
+        let default = Cli::parse_from(["codex-exec", "fix the build"]);
+        assert_eq!(default.approvals, ApprovalsMode::Never);
+
+        let err = Cli::try_parse_from([
+            "codex-exec",
+            "--approvals",
+            "stdio",
+            "--dangerously-bypass-approvals-and-sandbox",
+            "fix the build",
+        ])
+        .expect_err("stdio approvals conflict with bypassing approvals");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
 }
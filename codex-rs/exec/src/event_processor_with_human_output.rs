@@ -231,7 +231,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
-            EventMsg::ModelReroute(_) => {}
+            EventMsg::ModelReroute(_)
+            | EventMsg::GuardianAssessment(_)
+            | EventMsg::SecretsRedacted(_) => {}
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -859,6 +861,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::ListCheckpointsResponse(_)
+            | EventMsg::ListBackgroundJobsResponse(_)
+            | EventMsg::ProjectDocLoaded(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
             | EventMsg::RemoteSkillDownloaded(_)
@@ -940,6 +945,9 @@ impl EventProcessorWithHumanOutput {
                 | EventMsg::GetHistoryEntryResponse(_)
                 | EventMsg::McpListToolsResponse(_)
                 | EventMsg::ListCustomPromptsResponse(_)
+                | EventMsg::ListCheckpointsResponse(_)
+                | EventMsg::ListBackgroundJobsResponse(_)
+                | EventMsg::ProjectDocLoaded(_)
                 | EventMsg::ListSkillsResponse(_)
                 | EventMsg::ListRemoteSkillsResponse(_)
                 | EventMsg::RemoteSkillDownloaded(_)
@@ -843,18 +843,10 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         });
     }
 
-    #[allow(clippy::print_stdout)]
     fn process_event(&mut self, event: protocol::Event) -> CodexStatus {
         let aggregated = self.collect_thread_events(&event);
         for conv_event in aggregated {
-            match serde_json::to_string(&conv_event) {
-                Ok(line) => {
-                    println!("{line}");
-                }
-                Err(e) => {
-                    error!("Failed to serialize event: {e:?}");
-                }
-            }
+            print_thread_event(&conv_event);
         }
 
         let protocol::Event { msg, .. } = event;
@@ -878,3 +870,16 @@ impl EventProcessor for EventProcessorWithJsonOutput {
         }
     }
 }
+
+/// Writes one event as a JSONL line on stdout.
+#[allow(clippy::print_stdout)]
+pub(crate) fn print_thread_event(event: &ThreadEvent) {
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            println!("{line}");
+        }
+        Err(e) => {
+            error!("Failed to serialize event: {e:?}");
+        }
+    }
+}
@@ -34,6 +34,14 @@ pub enum ThreadEvent {
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
+    /// Emitted with `--approvals stdio` when the agent needs a decision before running a
+    /// command or applying a patch. Answer it with an `approval.response` line on stdin.
+    #[serde(rename = "approval.requested")]
+    ApprovalRequested(ApprovalRequestedEvent),
+    /// Emitted with `--approvals stdio` when the agent asks the user questions. Answer it
+    /// with a `user_input.response` line on stdin.
+    #[serde(rename = "user_input.requested")]
+    UserInputRequested(UserInputRequestedEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -88,6 +96,97 @@ pub struct ThreadErrorEvent {
     pub message: String,
 }
 
+/// A pending approval. `id` is the handle to use in the matching `approval.response`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct ApprovalRequestedEvent {
+    pub id: String,
+    pub thread_id: String,
+    #[serde(flatten)]
+    pub request: ApprovalRequest,
+    /// Decisions accepted for this request.
+    pub available_decisions: Vec<ApprovalDecision>,
+}
+
+/// What the agent wants to do.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ApprovalRequest {
+    /// Run a command, possibly outside the sandbox.
+    CommandExecution {
+        command: String,
+        cwd: String,
+        reason: Option<String>,
+    },
+    /// Apply a set of file changes.
+    FileChange {
+        changes: Vec<FileUpdateChange>,
+        reason: Option<String>,
+        grant_root: Option<String>,
+    },
+}
+
+/// Decision sent back for an `approval.requested` event.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// Run this command or apply this patch.
+    Approved,
+    /// Approve this request and matching requests for the rest of the session.
+    ApprovedForSession,
+    /// Skip this request and let the agent try something else.
+    Denied,
+    /// Skip this request and stop the turn.
+    Abort,
+}
+
+/// Questions the agent wants answered. `id` is the handle to use in the matching
+/// `user_input.response`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct UserInputRequestedEvent {
+    pub id: String,
+    pub thread_id: String,
+    pub questions: Vec<UserInputQuestion>,
+}
+
+/// A single question in a `user_input.requested` event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct UserInputQuestion {
+    pub id: String,
+    pub header: String,
+    pub question: String,
+    /// Whether a free-form answer is accepted in addition to `options`.
+    pub is_other: bool,
+    /// Whether the answer should be treated as a secret by the responder.
+    pub is_secret: bool,
+    pub options: Vec<UserInputOption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct UserInputOption {
+    pub label: String,
+    pub description: String,
+}
+
+/// JSONL messages read from stdin with `--approvals stdio`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "type")]
+pub enum StdioResponse {
+    /// Resolves an `approval.requested` event.
+    #[serde(rename = "approval.response")]
+    Approval {
+        id: String,
+        decision: ApprovalDecision,
+    },
+    /// Resolves a `user_input.requested` event. `answers` maps question ids to the
+    /// selected option labels or free-form text.
+    #[serde(rename = "user_input.response")]
+    UserInput {
+        id: String,
+        #[serde(default)]
+        answers: HashMap<String, Vec<String>>,
+    },
+}
+
 /// Canonical representation of a thread item and its domain-specific payload.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadItem {
@@ -9,7 +9,9 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod stdio_approvals;
 
+pub use cli::ApprovalsMode;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
@@ -52,6 +54,7 @@ use codex_utils_oss::ensure_oss_provider_ready;
 use codex_utils_oss::get_default_model_for_oss_provider;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use event_processor_with_jsonl_output::print_thread_event;
 use serde_json::Value;
 use std::collections::HashSet;
 use std::io::IsTerminal;
@@ -74,6 +77,9 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::stdio_approvals::StdinMessage;
+use crate::stdio_approvals::StdioApprovals;
+use crate::stdio_approvals::spawn_stdin_reader;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
@@ -91,6 +97,14 @@ enum InitialOperation {
     },
 }
 
+/// Whatever woke up the main loop: the next thread event, or a message from the stdin
+/// reader when `--approvals stdio` is on.
+#[allow(clippy::large_enum_variant)]
+enum LoopInput {
+    Event(Option<ThreadEventEnvelope>),
+    Stdin(StdinMessage),
+}
+
 #[derive(Clone)]
 struct ThreadEventEnvelope {
     thread_id: codex_protocol::ThreadId,
@@ -100,6 +114,7 @@ struct ThreadEventEnvelope {
 }
 
 struct ExecRunArgs {
+    approvals: ApprovalsMode,
     command: Option<ExecCommand>,
     config: Config,
     cursor_ansi: bool,
@@ -145,7 +160,8 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         ephemeral,
         color,
         last_message_file,
-        json: json_mode,
+        json,
+        approvals,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
@@ -153,6 +169,9 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         progress_cursor,
     } = cli;
 
+    // Approval requests are written as JSONL, so stdio approvals always use JSON output.
+    let json_mode = json || approvals == ApprovalsMode::Stdio;
+
     let (_stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
         cli::Color::Never => (false, false),
@@ -302,7 +321,11 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         review_model: None,
         config_profile,
         // Default to never ask for approvals in headless mode. Feature flags can override.
-        approval_policy: Some(AskForApproval::Never),
+        // With stdio approvals the configured policy applies, since requests can be answered.
+        approval_policy: match approvals {
+            ApprovalsMode::Never => Some(AskForApproval::Never),
+            ApprovalsMode::Stdio => None,
+        },
         sandbox_mode,
         cwd: resolved_cwd,
         model_provider: model_provider.clone(),
@@ -383,6 +406,7 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         set_parent_from_context(&exec_span, context);
     }
     run_exec_session(ExecRunArgs {
+        approvals,
         command,
         config,
         cursor_ansi,
@@ -404,6 +428,7 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
 
 async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     let ExecRunArgs {
+        approvals,
         command,
         config,
         cursor_ansi,
@@ -512,8 +537,12 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     let primary_thread_id_for_span = primary_thread_id.to_string();
     exec_span.record("thread.id", primary_thread_id_for_span.as_str());
 
+    let stdin_reserved = approvals == ApprovalsMode::Stdio;
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
+            if stdin_reserved && review_cli.prompt.as_deref() == Some("-") {
+                exit_prompt_on_reserved_stdin();
+            }
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
@@ -530,6 +559,9 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
                     }
                 })
                 .or(root_prompt);
+            if stdin_reserved && reads_prompt_from_stdin(prompt_arg.as_deref()) {
+                exit_prompt_on_reserved_stdin();
+            }
             let prompt_text = resolve_prompt(prompt_arg);
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
@@ -551,6 +583,9 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
             )
         }
         (None, root_prompt, imgs) => {
+            if stdin_reserved && reads_prompt_from_stdin(root_prompt.as_deref()) {
+                exit_prompt_on_reserved_stdin();
+            }
             let prompt_text = resolve_prompt(root_prompt);
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
@@ -667,7 +702,38 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
-    while let Some(envelope) = rx.recv().await {
+    let mut stdio_approvals: Option<StdioApprovals> = stdin_reserved.then(StdioApprovals::new);
+    let mut stdin_rx = stdin_reserved.then(spawn_stdin_reader);
+    loop {
+        let input = tokio::select! {
+            envelope = rx.recv() => LoopInput::Event(envelope),
+            Some(message) = next_stdin_message(&mut stdin_rx) => LoopInput::Stdin(message),
+        };
+        let envelope = match input {
+            LoopInput::Event(Some(envelope)) => envelope,
+            LoopInput::Event(None) => break,
+            LoopInput::Stdin(message) => {
+                let Some(approvals) = stdio_approvals.as_mut() else {
+                    continue;
+                };
+                match message {
+                    StdinMessage::Response(response) => match approvals.resolve(response) {
+                        Ok((thread, op)) => {
+                            thread.submit(op).await?;
+                        }
+                        #[allow(clippy::print_stderr)]
+                        Err(err) => eprintln!("Ignoring approval response: {err}"),
+                    },
+                    StdinMessage::Closed => {
+                        stdin_rx = None;
+                        for (thread, op) in approvals.close() {
+                            thread.submit(op).await?;
+                        }
+                    }
+                }
+                continue;
+            }
+        };
         let ThreadEventEnvelope {
             thread_id,
             thread,
@@ -697,6 +763,15 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
                 })
                 .await?;
         }
+        if let Some(approvals) = stdio_approvals.as_mut()
+            && let Some((request, resolved)) =
+                approvals.register(thread_id, Arc::clone(&thread), &event.msg)
+        {
+            print_thread_event(&request);
+            if let Some((thread, op)) = resolved {
+                thread.submit(op).await?;
+            }
+        }
         if let EventMsg::McpStartupUpdate(update) = &event.msg
             && required_mcp_servers.contains(&update.server)
             && let codex_protocol::protocol::McpStartupStatus::Failed { error } = &update.status
@@ -738,6 +813,15 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn next_stdin_message(
+    rx: &mut Option<tokio::sync::mpsc::UnboundedReceiver<StdinMessage>>,
+) -> Option<StdinMessage> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 fn spawn_thread_listener(
     thread_id: codex_protocol::ThreadId,
     thread: Arc<codex_core::CodexThread>,
@@ -941,6 +1025,18 @@ fn decode_utf16(
     String::from_utf16(&units).map_err(|_| PromptDecodeError::InvalidUtf16 { encoding })
 }
 
+fn reads_prompt_from_stdin(prompt_arg: Option<&str>) -> bool {
+    matches!(prompt_arg, None | Some("-"))
+}
+
+#[allow(clippy::print_stderr)]
+fn exit_prompt_on_reserved_stdin() -> ! {
+    eprintln!(
+        "--approvals stdio reads approval decisions from stdin; pass the prompt as an argument."
+    );
+    std::process::exit(1);
+}
+
 fn resolve_prompt(prompt_arg: Option<String>) -> String {
     match prompt_arg {
         Some(p) if p != "-" => p,
@@ -0,0 +1,545 @@
+//! `--approvals stdio`: approval and `request_user_input` requests are written to stdout
+//! as JSONL events and resolved by `approval.response` / `user_input.response` lines read
+//! from stdin.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_core::CodexThread;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecApprovalRequestEvent;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputEvent;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::exec_events::ApprovalDecision;
+use crate::exec_events::ApprovalRequest;
+use crate::exec_events::ApprovalRequestedEvent;
+use crate::exec_events::FileUpdateChange;
+use crate::exec_events::PatchChangeKind;
+use crate::exec_events::StdioResponse;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::UserInputOption;
+use crate::exec_events::UserInputQuestion;
+use crate::exec_events::UserInputRequestedEvent;
+
+/// Messages produced by the stdin reader task.
+#[derive(Debug)]
+pub(crate) enum StdinMessage {
+    Response(StdioResponse),
+    /// stdin reached EOF or failed; no further decisions will arrive.
+    Closed,
+}
+
+/// Spawns a task that parses JSONL responses from stdin. Malformed lines are reported on
+/// stderr and skipped so a single bad write does not wedge the session.
+pub(crate) fn spawn_stdin_reader() -> UnboundedReceiver<StdinMessage> {
+    let (tx, rx) = unbounded_channel();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str::<StdioResponse>(&line) {
+                        Ok(response) => {
+                            if tx.send(StdinMessage::Response(response)).is_err() {
+                                break;
+                            }
+                        }
+                        #[allow(clippy::print_stderr)]
+                        Err(err) => eprintln!("Ignoring invalid approval response on stdin: {err}"),
+                    }
+                }
+                Ok(None) => {
+                    let _ = tx.send(StdinMessage::Closed);
+                    break;
+                }
+                #[allow(clippy::print_stderr)]
+                Err(err) => {
+                    eprintln!("Failed to read approval responses from stdin: {err}");
+                    let _ = tx.send(StdinMessage::Closed);
+                    break;
+                }
+            }
+        }
+    });
+    rx
+}
+
+enum PendingKind {
+    Exec {
+        approval_id: String,
+        turn_id: String,
+    },
+    Patch {
+        call_id: String,
+    },
+    UserInput {
+        turn_id: String,
+    },
+}
+
+struct PendingRequest<T> {
+    /// Registration order, so requests are settled in the order they were emitted.
+    seq: u64,
+    thread: T,
+    kind: PendingKind,
+}
+
+/// Tracks the requests that were written to stdout and turns responses into ops for the
+/// thread that asked. `T` is the thread handle (`Arc<CodexThread>` outside of tests).
+pub(crate) struct StdioApprovals<T = Arc<CodexThread>> {
+    next_id: u64,
+    pending: HashMap<String, PendingRequest<T>>,
+    stdin_closed: bool,
+}
+
+impl<T> StdioApprovals<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 0,
+            pending: HashMap::new(),
+            stdin_closed: false,
+        }
+    }
+
+    /// Records a request that needs an answer and returns the event to emit, or `None`
+    /// when `msg` is not a request. Once stdin is closed the request is answered right
+    /// away with the default response, returned as the second element.
+    pub(crate) fn register(
+        &mut self,
+        thread_id: ThreadId,
+        thread: T,
+        msg: &EventMsg,
+    ) -> Option<(ThreadEvent, Option<(T, Op)>)> {
+        let seq = self.next_id;
+        let id = format!("request_{seq}");
+        let (kind, event) = match msg {
+            EventMsg::ExecApprovalRequest(ev) => (
+                PendingKind::Exec {
+                    approval_id: ev.effective_approval_id(),
+                    turn_id: ev.turn_id.clone(),
+                },
+                exec_approval_event(id.clone(), thread_id, ev),
+            ),
+            EventMsg::ApplyPatchApprovalRequest(ev) => (
+                PendingKind::Patch {
+                    call_id: ev.call_id.clone(),
+                },
+                patch_approval_event(id.clone(), thread_id, ev),
+            ),
+            EventMsg::RequestUserInput(ev) => (
+                PendingKind::UserInput {
+                    turn_id: ev.turn_id.clone(),
+                },
+                user_input_event(id.clone(), thread_id, ev),
+            ),
+            _ => return None,
+        };
+        self.next_id += 1;
+        let default = self
+            .stdin_closed
+            .then(|| default_response(&kind, id.clone()));
+        self.pending
+            .insert(id, PendingRequest { seq, thread, kind });
+        let resolved = default.and_then(|response| self.resolve(response).ok());
+        Some((event, resolved))
+    }
+
+    /// Resolves a pending request. Errors describe responses that could not be matched.
+    pub(crate) fn resolve(&mut self, response: StdioResponse) -> Result<(T, Op), String> {
+        let id = match &response {
+            StdioResponse::Approval { id, .. } | StdioResponse::UserInput { id, .. } => id.clone(),
+        };
+        let Some(PendingRequest { seq, thread, kind }) = self.pending.remove(&id) else {
+            return Err(format!("no pending request with id {id}"));
+        };
+        let op = match (kind, response) {
+            (
+                PendingKind::Exec {
+                    approval_id,
+                    turn_id,
+                },
+                StdioResponse::Approval { decision, .. },
+            ) => Op::ExecApproval {
+                id: approval_id,
+                turn_id: Some(turn_id),
+                decision: review_decision(decision),
+            },
+            (PendingKind::Patch { call_id }, StdioResponse::Approval { decision, .. }) => {
+                Op::PatchApproval {
+                    id: call_id,
+                    decision: review_decision(decision),
+                }
+            }
+            (PendingKind::UserInput { turn_id }, StdioResponse::UserInput { answers, .. }) => {
+                Op::UserInputAnswer {
+                    id: turn_id,
+                    response: user_input_response(answers),
+                }
+            }
+            (kind, _) => {
+                let expected = match kind {
+                    PendingKind::UserInput { .. } => "user_input.response",
+                    PendingKind::Exec { .. } | PendingKind::Patch { .. } => "approval.response",
+                };
+                self.pending
+                    .insert(id.clone(), PendingRequest { seq, thread, kind });
+                return Err(format!("request {id} expects a {expected}"));
+            }
+        };
+        Ok((thread, op))
+    }
+
+    /// Called once stdin is closed: every pending request, and every request registered
+    /// afterwards, is denied (or answered with no answers) so the session can finish.
+    pub(crate) fn close(&mut self) -> Vec<(T, Op)> {
+        self.stdin_closed = true;
+        let mut pending = self.pending.drain().collect::<Vec<_>>();
+        pending.sort_by_key(|(_, request)| request.seq);
+        let mut resolved = Vec::with_capacity(pending.len());
+        for (id, request) in pending {
+            let response = default_response(&request.kind, id.clone());
+            self.pending.insert(id, request);
+            if let Ok(op) = self.resolve(response) {
+                resolved.push(op);
+            }
+        }
+        resolved
+    }
+}
+
+fn default_response(kind: &PendingKind, id: String) -> StdioResponse {
+    match kind {
+        PendingKind::UserInput { .. } => StdioResponse::UserInput {
+            id,
+            answers: HashMap::new(),
+        },
+        PendingKind::Exec { .. } | PendingKind::Patch { .. } => StdioResponse::Approval {
+            id,
+            decision: ApprovalDecision::Denied,
+        },
+    }
+}
+
+fn exec_approval_event(
+    id: String,
+    thread_id: ThreadId,
+    ev: &ExecApprovalRequestEvent,
+) -> ThreadEvent {
+    let available_decisions = ev
+        .effective_available_decisions()
+        .iter()
+        .filter_map(approval_decision)
+        .collect();
+    ThreadEvent::ApprovalRequested(ApprovalRequestedEvent {
+        id,
+        thread_id: thread_id.to_string(),
+        request: ApprovalRequest::CommandExecution {
+            command: shlex::try_join(ev.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| ev.command.join(" ")),
+            cwd: ev.cwd.display().to_string(),
+            reason: ev.reason.clone(),
+        },
+        available_decisions,
+    })
+}
+
+fn patch_approval_event(
+    id: String,
+    thread_id: ThreadId,
+    ev: &ApplyPatchApprovalRequestEvent,
+) -> ThreadEvent {
+    let mut changes = ev
+        .changes
+        .iter()
+        .map(|(path, change)| FileUpdateChange {
+            path: path.display().to_string(),
+            kind: match change {
+                FileChange::Add { .. } => PatchChangeKind::Add,
+                FileChange::Delete { .. } => PatchChangeKind::Delete,
+                FileChange::Update { .. } => PatchChangeKind::Update,
+            },
+        })
+        .collect::<Vec<_>>();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    ThreadEvent::ApprovalRequested(ApprovalRequestedEvent {
+        id,
+        thread_id: thread_id.to_string(),
+        request: ApprovalRequest::FileChange {
+            changes,
+            reason: ev.reason.clone(),
+            grant_root: ev
+                .grant_root
+                .as_ref()
+                .map(|root| root.display().to_string()),
+        },
+        available_decisions: vec![
+            ApprovalDecision::Approved,
+            ApprovalDecision::ApprovedForSession,
+            ApprovalDecision::Denied,
+            ApprovalDecision::Abort,
+        ],
+    })
+}
+
+fn user_input_event(id: String, thread_id: ThreadId, ev: &RequestUserInputEvent) -> ThreadEvent {
+    ThreadEvent::UserInputRequested(UserInputRequestedEvent {
+        id,
+        thread_id: thread_id.to_string(),
+        questions: ev
+            .questions
+            .iter()
+            .map(|question| UserInputQuestion {
+                id: question.id.clone(),
+                header: question.header.clone(),
+                question: question.question.clone(),
+                is_other: question.is_other,
+                is_secret: question.is_secret,
+                options: question
+                    .options
+                    .iter()
+                    .flatten()
+                    .map(|option| UserInputOption {
+                        label: option.label.clone(),
+                        description: option.description.clone(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Amendment decisions need a payload the JSONL protocol does not expose, so they are
+/// not offered over stdio.
+fn approval_decision(decision: &ReviewDecision) -> Option<ApprovalDecision> {
+    match decision {
+        ReviewDecision::Approved => Some(ApprovalDecision::Approved),
+        ReviewDecision::ApprovedForSession => Some(ApprovalDecision::ApprovedForSession),
+        ReviewDecision::Denied => Some(ApprovalDecision::Denied),
+        ReviewDecision::Abort => Some(ApprovalDecision::Abort),
+        ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::NetworkPolicyAmendment { .. } => None,
+    }
+}
+
+fn review_decision(decision: ApprovalDecision) -> ReviewDecision {
+    match decision {
+        ApprovalDecision::Approved => ReviewDecision::Approved,
+        ApprovalDecision::ApprovedForSession => ReviewDecision::ApprovedForSession,
+        ApprovalDecision::Denied => ReviewDecision::Denied,
+        ApprovalDecision::Abort => ReviewDecision::Abort,
+    }
+}
+
+fn user_input_response(answers: HashMap<String, Vec<String>>) -> RequestUserInputResponse {
+    RequestUserInputResponse {
+        answers: answers
+            .into_iter()
+            .map(|(question_id, answers)| (question_id, RequestUserInputAnswer { answers }))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::request_user_input::RequestUserInputQuestion;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    const THREAD_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    fn thread_id() -> ThreadId {
+        ThreadId::from_string(THREAD_ID).expect("thread id")
+    }
+
+    fn exec_request() -> EventMsg {
+        EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+            call_id: "call-1".to_string(),
+            approval_id: None,
+            turn_id: "turn-1".to_string(),
+            command: vec!["git".to_string(), "push".to_string(), "a b".to_string()],
+            cwd: PathBuf::from("/repo"),
+            reason: Some("needs network".to_string()),
+            network_approval_context: None,
+            proposed_execpolicy_amendment: None,
+            proposed_network_policy_amendments: None,
+            additional_permissions: None,
+            available_decisions: Some(vec![ReviewDecision::Approved, ReviewDecision::Abort]),
+            parsed_cmd: Vec::new(),
+        })
+    }
+
+    fn user_input_request() -> EventMsg {
+        EventMsg::RequestUserInput(RequestUserInputEvent {
+            call_id: "call-2".to_string(),
+            turn_id: "turn-2".to_string(),
+            questions: vec![RequestUserInputQuestion {
+                id: "target".to_string(),
+                header: "Target".to_string(),
+                question: "Which environment?".to_string(),
+                is_other: true,
+                is_secret: false,
+                options: None,
+            }],
+        })
+    }
+
+    #[test]
+    fn exec_request_round_trips_to_exec_approval_op() {
+        let mut approvals = StdioApprovals::new();
+
+        let (event, resolved) = approvals
+            .register(thread_id(), "thread", &exec_request())
+            .expect("exec approval is a request");
+
+        assert!(resolved.is_none());
+        assert_eq!(
+            serde_json::to_value(&event).expect("serialize"),
+            serde_json::json!({
+                "type": "approval.requested",
+                "id": "request_0",
+                "thread_id": THREAD_ID,
+                "kind": "command_execution",
+                "command": "git push 'a b'",
+                "cwd": "/repo",
+                "reason": "needs network",
+                "available_decisions": ["approved", "abort"],
+            })
+        );
+
+        let response: StdioResponse = serde_json::from_str(
+            r#"{"type":"approval.response","id":"request_0","decision":"approved"}"#,
+        )
+        .expect("parse response");
+        let (thread, op) = approvals.resolve(response).expect("pending request");
+
+        assert_eq!(thread, "thread");
+        assert_eq!(
+            op,
+            Op::ExecApproval {
+                id: "call-1".to_string(),
+                turn_id: Some("turn-1".to_string()),
+                decision: ReviewDecision::Approved,
+            }
+        );
+    }
+
+    #[test]
+    fn user_input_response_maps_answers_and_rejects_wrong_response_type() {
+        let mut approvals = StdioApprovals::new();
+        approvals
+            .register(thread_id(), (), &user_input_request())
+            .expect("user input is a request");
+
+        let err = approvals
+            .resolve(StdioResponse::Approval {
+                id: "request_0".to_string(),
+                decision: ApprovalDecision::Approved,
+            })
+            .expect_err("approval does not answer questions");
+        assert_eq!(err, "request request_0 expects a user_input.response");
+
+        let (_, op) = approvals
+            .resolve(StdioResponse::UserInput {
+                id: "request_0".to_string(),
+                answers: HashMap::from([("target".to_string(), vec!["staging".to_string()])]),
+            })
+            .expect("request still pending");
+        assert_eq!(
+            op,
+            Op::UserInputAnswer {
+                id: "turn-2".to_string(),
+                response: RequestUserInputResponse {
+                    answers: HashMap::from([(
+                        "target".to_string(),
+                        RequestUserInputAnswer {
+                            answers: vec!["staging".to_string()],
+                        },
+                    )]),
+                },
+            }
+        );
+        assert_eq!(
+            approvals.resolve(StdioResponse::Approval {
+                id: "request_0".to_string(),
+                decision: ApprovalDecision::Denied,
+            }),
+            Err("no pending request with id request_0".to_string())
+        );
+    }
+
+    #[test]
+    fn closing_stdin_denies_pending_and_later_requests() {
+        let mut approvals = StdioApprovals::new();
+        approvals.register(thread_id(), 1, &exec_request());
+        approvals.register(thread_id(), 2, &user_input_request());
+
+        let resolved = approvals.close();
+        assert_eq!(
+            resolved,
+            vec![
+                (
+                    1,
+                    Op::ExecApproval {
+                        id: "call-1".to_string(),
+                        turn_id: Some("turn-1".to_string()),
+                        decision: ReviewDecision::Denied,
+                    }
+                ),
+                (
+                    2,
+                    Op::UserInputAnswer {
+                        id: "turn-2".to_string(),
+                        response: RequestUserInputResponse {
+                            answers: HashMap::new(),
+                        },
+                    }
+                ),
+            ]
+        );
+
+        let (_, resolved) = approvals
+            .register(thread_id(), 3, &exec_request())
+            .expect("exec approval is a request");
+        assert_eq!(
+            resolved,
+            Some((
+                3,
+                Op::ExecApproval {
+                    id: "call-1".to_string(),
+                    turn_id: Some("turn-1".to_string()),
+                    decision: ReviewDecision::Denied,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn closing_stdin_settles_requests_in_registration_order() {
+        let mut approvals = StdioApprovals::new();
+        for thread in 0..12 {
+            approvals.register(thread_id(), thread, &exec_request());
+        }
+
+        let threads = approvals
+            .close()
+            .into_iter()
+            .map(|(thread, _)| thread)
+            .collect::<Vec<_>>();
+        assert_eq!(threads, (0..12).collect::<Vec<_>>());
+    }
+}
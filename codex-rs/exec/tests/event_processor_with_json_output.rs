@@ -909,6 +909,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            seccomp_denial: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -997,6 +998,7 @@ fn command_execution_output_delta_updates_item_progress() {
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            seccomp_denial: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1071,6 +1073,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Failed,
+            seccomp_denial: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1113,6 +1116,7 @@ fn exec_command_end_without_begin_is_ignored() {
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            seccomp_denial: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1153,6 +1157,7 @@ fn patch_apply_success_produces_item_completed_patchapply() {
             turn_id: "turn-1".to_string(),
             auto_approved: true,
             changes: changes.clone(),
+            skipped: Vec::new(),
         }),
     );
     let out_begin = ep.collect_thread_events(&begin);
@@ -1226,6 +1231,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
             turn_id: "turn-2".to_string(),
             auto_approved: false,
             changes: changes.clone(),
+            skipped: Vec::new(),
         }),
     );
     assert!(ep.collect_thread_events(&begin).is_empty());
@@ -43,6 +43,8 @@ async fn spawn_command_under_sandbox(
     env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
     use codex_core::landlock::spawn_command_under_linux_sandbox;
+    use codex_core::sandboxing::seccomp::SeccompSelection;
+    use codex_protocol::permissions::FileSystemWriteScope;
     let codex_linux_sandbox_exe = codex_utils_cargo_bin::cargo_bin("codex-exec")
         .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
     spawn_command_under_linux_sandbox(
@@ -52,6 +54,9 @@ async fn spawn_command_under_sandbox(
         sandbox_policy,
         sandbox_cwd,
         false,
+        &FileSystemWriteScope::default(),
+        &SeccompSelection::default(),
+        false,
         stdio_policy,
         None,
         env,
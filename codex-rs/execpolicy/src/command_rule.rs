@@ -0,0 +1,397 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use wildmatch::WildMatch;
+
+use crate::decision::Decision;
+use crate::error::Error;
+use crate::error::Result;
+use crate::policy::MatchOptions;
+use crate::rule::PrefixPattern;
+use crate::rule::Rule;
+use crate::rule::RuleMatch;
+
+/// Prefix used in `command_rule` matchers to request a regular expression instead of a glob.
+const REGEX_MATCHER_PREFIX: &str = "re:";
+
+/// Matches a single token (an argument or an env value) by glob or regular expression.
+#[derive(Clone, Debug)]
+pub struct ArgMatcher {
+    source: String,
+    kind: ArgMatcherKind,
+}
+
+#[derive(Clone, Debug)]
+enum ArgMatcherKind {
+    Glob(WildMatch),
+    Regex(Regex),
+}
+
+impl ArgMatcher {
+    /// Parses `raw` as a glob (`*` and `?` wildcards) or, when prefixed with `re:`, as an
+    /// unanchored regular expression.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let kind = match raw.strip_prefix(REGEX_MATCHER_PREFIX) {
+            Some(pattern) => Regex::new(pattern)
+                .map(ArgMatcherKind::Regex)
+                .map_err(|err| {
+                    Error::InvalidPattern(format!("invalid regex `{pattern}`: {err}"))
+                })?,
+            None if raw.is_empty() => {
+                return Err(Error::InvalidPattern(
+                    "argument matcher cannot be empty".to_string(),
+                ));
+            }
+            None => ArgMatcherKind::Glob(WildMatch::new(raw)),
+        };
+        Ok(Self {
+            source: raw.to_string(),
+            kind,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        match &self.kind {
+            ArgMatcherKind::Glob(glob) => glob.matches(value),
+            ArgMatcherKind::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+impl PartialEq for ArgMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for ArgMatcher {}
+
+/// One `args` entry of a `command_rule`: at least one argument after the matched prefix must
+/// satisfy one of the alternatives.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgCondition {
+    pub alternatives: Vec<ArgMatcher>,
+}
+
+impl ArgCondition {
+    fn explain_match(&self, args: &[String]) -> Option<String> {
+        args.iter().find_map(|arg| {
+            self.alternatives
+                .iter()
+                .find(|matcher| matcher.matches(arg))
+                .map(|matcher| format!("argument `{arg}` matches `{}`", matcher.source()))
+        })
+    }
+}
+
+/// Where path-like tokens must resolve relative to the workspace roots.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathScope {
+    /// Every path resolves inside a workspace root (and there is at least one path).
+    InsideWorkspace,
+    /// At least one path resolves outside every workspace root or cannot be resolved.
+    OutsideWorkspace,
+}
+
+impl PathScope {
+    pub fn parse(raw: &str, field: &str) -> Result<Self> {
+        match raw {
+            "inside_workspace" => Ok(Self::InsideWorkspace),
+            "outside_workspace" => Ok(Self::OutsideWorkspace),
+            other => Err(Error::InvalidRule(format!(
+                "command_rule {field} must be one of inside_workspace, outside_workspace (got {other})"
+            ))),
+        }
+    }
+}
+
+/// Constraint on the redirections attached to the command.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedirectCondition {
+    /// The command has no redirections.
+    None,
+    /// The redirection targets satisfy the given scope.
+    Scope(PathScope),
+}
+
+impl RedirectCondition {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "none" => Ok(Self::None),
+            other => PathScope::parse(other, "redirects")
+                .map(Self::Scope)
+                .map_err(|_| {
+                    Error::InvalidRule(format!(
+                        "command_rule redirects must be one of none, inside_workspace, outside_workspace (got {other})"
+                    ))
+                }),
+        }
+    }
+}
+
+/// Requires the environment variable `name` to be set to a value matching `value`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvCondition {
+    pub name: String,
+    pub value: ArgMatcher,
+}
+
+/// Linux sandbox settings a `command_rule` applies to the commands it matches in place of the
+/// ones configured in `config.toml`. Commands matched by a rule that sets any of them stay
+/// sandboxed even when the rule allows them.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_scope: Option<WriteScopeOverride>,
+    /// Names of the seccomp profiles to install instead of the `[sandbox_seccomp]` ones, e.g.
+    /// `no-ptrace`. Codex rejects names it does not know when it runs the command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seccomp_profiles: Option<Vec<String>>,
+}
+
+impl SandboxOverride {
+    pub fn is_empty(&self) -> bool {
+        self.write_scope.is_none() && self.seccomp_profiles.is_none()
+    }
+}
+
+/// Replacement for the `[sandbox_write_scope]` settings; unset fields keep the configured
+/// value.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteScopeOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_read: Option<Vec<AbsolutePathBuf>>,
+}
+
+/// A prefix rule with extra conditions on the remaining arguments, path arguments,
+/// redirections, and environment. Every condition must hold for the rule to match.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommandRule {
+    pub pattern: PrefixPattern,
+    pub args: Vec<ArgCondition>,
+    pub paths: Option<PathScope>,
+    pub redirects: Option<RedirectCondition>,
+    pub env: Vec<EnvCondition>,
+    pub sandbox: SandboxOverride,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
+impl CommandRule {
+    fn explain_match(&self, cmd: &[String], options: &MatchOptions) -> Option<Vec<String>> {
+        let matched_prefix_len = self.pattern.rest.len() + 1;
+        let args = &cmd[matched_prefix_len..];
+        let mut conditions = Vec::new();
+
+        for condition in &self.args {
+            conditions.push(condition.explain_match(args)?);
+        }
+
+        if let Some(scope) = self.paths {
+            let paths = path_arguments(args);
+            conditions.push(explain_scope(scope, &paths, options, "path argument")?);
+        }
+
+        match self.redirects {
+            Some(RedirectCondition::None) => {
+                if !options.redirects.as_ref()?.is_empty() {
+                    return None;
+                }
+                conditions.push("command has no redirections".to_string());
+            }
+            Some(RedirectCondition::Scope(scope)) => {
+                let targets = options.redirects.as_ref()?;
+                let targets = targets.iter().map(String::as_str).collect::<Vec<_>>();
+                conditions.push(explain_scope(scope, &targets, options, "redirect target")?);
+            }
+            None => {}
+        }
+
+        for condition in &self.env {
+            let value = options.env.as_ref()?.get(&condition.name)?;
+            if !condition.value.matches(value) {
+                return None;
+            }
+            conditions.push(format!(
+                "env `{}={value}` matches `{}`",
+                condition.name,
+                condition.value.source()
+            ));
+        }
+
+        Some(conditions)
+    }
+}
+
+impl Rule for CommandRule {
+    fn program(&self) -> &str {
+        self.pattern.first.as_ref()
+    }
+
+    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+        self.matches_with_options(cmd, &MatchOptions::default())
+    }
+
+    fn matches_with_options(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch> {
+        let matched_prefix = self.pattern.matches_prefix(cmd)?;
+        let conditions = self.explain_match(cmd, options)?;
+        Some(RuleMatch::CommandRuleMatch {
+            matched_prefix,
+            decision: self.decision,
+            resolved_program: None,
+            justification: self.justification.clone(),
+            conditions,
+            sandbox: self.sandbox.clone(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Operands that may name files: every argument that is not a flag, plus everything after `--`.
+fn path_arguments(args: &[String]) -> Vec<&str> {
+    let mut paths = Vec::new();
+    let mut after_separator = false;
+    for arg in args {
+        if after_separator {
+            paths.push(arg.as_str());
+        } else if arg == "--" {
+            after_separator = true;
+        } else if !arg.starts_with('-') {
+            paths.push(arg.as_str());
+        }
+    }
+    paths
+}
+
+fn explain_scope(
+    scope: PathScope,
+    paths: &[&str],
+    options: &MatchOptions,
+    noun: &str,
+) -> Option<String> {
+    let outside = paths
+        .iter()
+        .find(|path| !resolves_inside_workspace(path, options));
+    match (scope, outside) {
+        (PathScope::InsideWorkspace, None) if !paths.is_empty() => Some(format!(
+            "every {noun} resolves inside the workspace roots: {}",
+            paths
+                .iter()
+                .map(|path| format!("`{path}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+        (PathScope::OutsideWorkspace, Some(path)) => Some(format!(
+            "{noun} `{path}` resolves outside the workspace roots"
+        )),
+        _ => None,
+    }
+}
+
+/// Resolves `raw` lexically against the match cwd and reports whether it falls under one of
+/// the workspace roots (the cwd when no roots are given). Tokens that still contain shell
+/// expansions, or relative paths without a cwd, are never considered inside.
+fn resolves_inside_workspace(raw: &str, options: &MatchOptions) -> bool {
+    if raw.contains('$') || raw.contains('`') {
+        return false;
+    }
+    let resolved = match &options.cwd {
+        Some(cwd) => cwd.join(raw).ok(),
+        None if Path::new(raw).is_absolute() => AbsolutePathBuf::from_absolute_path(raw).ok(),
+        None => None,
+    };
+    let Some(resolved) = resolved else {
+        return false;
+    };
+
+    if options.workspace_roots.is_empty() {
+        options
+            .cwd
+            .as_ref()
+            .is_some_and(|cwd| resolved.as_path().starts_with(cwd.as_path()))
+    } else {
+        options
+            .workspace_roots
+            .iter()
+            .any(|root| resolved.as_path().starts_with(root.as_path()))
+    }
+}
+
+/// Splits a `command_rule` example into the command and the context it implies: leading
+/// `NAME=value` tokens become the environment and shell redirections (`> out`, `2>>log`,
+/// `<in`) become redirect targets. Examples run with cwd and sole workspace root
+/// [`example_workspace_root`].
+pub(crate) fn example_command_and_options(example: &[String]) -> (Vec<String>, MatchOptions) {
+    let mut env = HashMap::new();
+    let mut tokens = example.iter().peekable();
+    while let Some((name, value)) = tokens.peek().and_then(|token| parse_env_assignment(token)) {
+        env.insert(name.to_string(), value.to_string());
+        tokens.next();
+    }
+
+    let mut command = Vec::new();
+    let mut redirects = Vec::new();
+    while let Some(token) = tokens.next() {
+        match redirect_target(token) {
+            Some("") => redirects.extend(tokens.next().cloned()),
+            // `2>&1` and friends duplicate descriptors rather than naming a file.
+            Some(target) if target.starts_with('&') => {}
+            Some(target) => redirects.push(target.to_string()),
+            None => command.push(token.clone()),
+        }
+    }
+
+    let workspace_root = example_workspace_root();
+    let options = MatchOptions {
+        resolve_host_executables: true,
+        cwd: Some(workspace_root.clone()),
+        workspace_roots: vec![workspace_root],
+        redirects: Some(redirects),
+        env: Some(env),
+    };
+    (command, options)
+}
+
+/// The directory `command_rule` examples are evaluated from.
+pub(crate) fn example_workspace_root() -> AbsolutePathBuf {
+    let root = if cfg!(windows) {
+        r"C:\workspace"
+    } else {
+        "/workspace"
+    };
+    #[expect(clippy::expect_used)]
+    AbsolutePathBuf::from_absolute_path(root).expect("example workspace root is absolute")
+}
+
+fn parse_env_assignment(token: &str) -> Option<(&str, &str)> {
+    let (name, value) = token.split_once('=')?;
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|ch| ch == '_' || ch.is_ascii_alphabetic());
+    (valid_start && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric()))
+        .then_some((name, value))
+}
+
+fn redirect_target(token: &str) -> Option<&str> {
+    let rest = token.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '&');
+    [">>", ">", "<"]
+        .into_iter()
+        .find_map(|operator| rest.strip_prefix(operator))
+}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Serialize;
 
 use crate::Decision;
@@ -28,6 +30,23 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub resolve_host_executables: bool,
 
+    /// Working directory used to resolve relative path arguments for `command_rule`
+    /// conditions. Defaults to the current directory.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Workspace root for `paths`/`redirects` conditions (repeatable). Defaults to the cwd.
+    #[arg(long = "workspace-root", value_name = "DIR")]
+    pub workspace_roots: Vec<PathBuf>,
+
+    /// Environment variable visible to the command, as NAME=VALUE (repeatable).
+    #[arg(long = "env", value_name = "NAME=VALUE", value_parser = parse_env_var)]
+    pub env: Vec<(String, String)>,
+
+    /// Redirection target attached to the command (repeatable).
+    #[arg(long = "redirect", value_name = "TARGET")]
+    pub redirects: Vec<String>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -42,19 +61,47 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
-        let matched_rules = policy.matches_for_command_with_options(
-            &self.command,
-            None,
-            &MatchOptions {
-                resolve_host_executables: self.resolve_host_executables,
-            },
-        );
+        let matched_rules =
+            policy.matches_for_command_with_options(&self.command, None, &self.match_options()?);
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
         println!("{json}");
 
         Ok(())
     }
+
+    fn match_options(&self) -> Result<MatchOptions> {
+        let cwd = match &self.cwd {
+            Some(cwd) => AbsolutePathBuf::from_absolute_path(cwd)
+                .with_context(|| format!("invalid --cwd {}", cwd.display()))?,
+            None => AbsolutePathBuf::current_dir().context("failed to read current directory")?,
+        };
+        let workspace_roots = self
+            .workspace_roots
+            .iter()
+            .map(|root| {
+                cwd.join(root)
+                    .with_context(|| format!("invalid --workspace-root {}", root.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // The checked command is a plain argv, so it only has the redirections and
+        // environment given on the command line.
+        Ok(MatchOptions {
+            resolve_host_executables: self.resolve_host_executables,
+            cwd: Some(cwd),
+            workspace_roots,
+            redirects: Some(self.redirects.clone()),
+            env: Some(self.env.iter().cloned().collect::<HashMap<_, _>>()),
+        })
+    }
+}
+
+fn parse_env_var(raw: &str) -> std::result::Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE (got {raw})")),
+    }
 }
 
 pub fn format_matches_json(matched_rules: &[RuleMatch], pretty: bool) -> Result<String> {
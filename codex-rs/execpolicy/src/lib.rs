@@ -1,4 +1,5 @@
 pub mod amend;
+pub mod command_rule;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
@@ -10,6 +11,9 @@ pub mod rule;
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
 pub use amend::blocking_append_network_rule;
+pub use command_rule::CommandRule;
+pub use command_rule::SandboxOverride;
+pub use command_rule::WriteScopeOverride;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
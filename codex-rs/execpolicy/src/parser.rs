@@ -10,6 +10,7 @@ use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
 use starlark::values::Value;
+use starlark::values::dict::DictRef;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
@@ -19,6 +20,14 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::command_rule::ArgCondition;
+use crate::command_rule::ArgMatcher;
+use crate::command_rule::CommandRule;
+use crate::command_rule::EnvCondition;
+use crate::command_rule::PathScope;
+use crate::command_rule::RedirectCondition;
+use crate::command_rule::SandboxOverride;
+use crate::command_rule::WriteScopeOverride;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::ErrorLocation;
@@ -119,6 +128,7 @@ impl PolicyBuilder {
         rules: Vec<RuleRef>,
         matches: Vec<Vec<String>>,
         not_matches: Vec<Vec<String>>,
+        examples_include_context: bool,
         location: Option<ErrorLocation>,
     ) {
         self.pending_example_validations
@@ -126,6 +136,7 @@ impl PolicyBuilder {
                 rules,
                 matches,
                 not_matches,
+                examples_include_context,
                 location,
             });
     }
@@ -142,10 +153,20 @@ impl PolicyBuilder {
                 Vec::new(),
                 self.host_executables_by_name.clone(),
             );
-            validate_not_match_examples(&policy, &validation.rules, &validation.not_matches)
-                .map_err(|error| attach_validation_location(error, validation.location.clone()))?;
-            validate_match_examples(&policy, &validation.rules, &validation.matches)
-                .map_err(|error| attach_validation_location(error, validation.location.clone()))?;
+            validate_not_match_examples(
+                &policy,
+                &validation.rules,
+                &validation.not_matches,
+                validation.examples_include_context,
+            )
+            .map_err(|error| attach_validation_location(error, validation.location.clone()))?;
+            validate_match_examples(
+                &policy,
+                &validation.rules,
+                &validation.matches,
+                validation.examples_include_context,
+            )
+            .map_err(|error| attach_validation_location(error, validation.location.clone()))?;
         }
 
         Ok(())
@@ -165,6 +186,8 @@ struct PendingExampleValidation {
     rules: Vec<RuleRef>,
     matches: Vec<Vec<String>>,
     not_matches: Vec<Vec<String>>,
+    /// Whether examples may carry leading env assignments and redirections (`command_rule`).
+    examples_include_context: bool,
     location: Option<ErrorLocation>,
 }
 
@@ -216,6 +239,101 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     }
 }
 
+fn parse_arg_conditions<'v>(args: UnpackList<Value<'v>>) -> Result<Vec<ArgCondition>> {
+    args.items
+        .into_iter()
+        .map(|value| {
+            let alternatives = match parse_pattern_token(value)? {
+                PatternToken::Single(raw) => vec![ArgMatcher::parse(&raw)?],
+                PatternToken::Alts(alternatives) => alternatives
+                    .iter()
+                    .map(|raw| ArgMatcher::parse(raw))
+                    .collect::<Result<_>>()?,
+            };
+            Ok(ArgCondition { alternatives })
+        })
+        .collect()
+}
+
+fn parse_env_conditions<'v>(env: Value<'v>) -> Result<Vec<EnvCondition>> {
+    let dict = DictRef::from_value(env).ok_or_else(|| {
+        Error::InvalidRule(format!(
+            "command_rule env must be a dict of strings (got {})",
+            env.get_type()
+        ))
+    })?;
+    dict.iter()
+        .map(|(name, value)| {
+            let (Some(name), Some(value)) = (name.unpack_str(), value.unpack_str()) else {
+                return Err(Error::InvalidRule(
+                    "command_rule env keys and values must be strings".to_string(),
+                ));
+            };
+            if name.is_empty() {
+                return Err(Error::InvalidRule(
+                    "command_rule env names cannot be empty".to_string(),
+                ));
+            }
+            Ok(EnvCondition {
+                name: name.to_string(),
+                value: ArgMatcher::parse(value)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_write_scope_override<'v>(write_scope: Value<'v>) -> Result<WriteScopeOverride> {
+    let dict = DictRef::from_value(write_scope).ok_or_else(|| {
+        Error::InvalidRule(format!(
+            "command_rule write_scope must be a dict (got {})",
+            write_scope.get_type()
+        ))
+    })?;
+    let mut write_scope = WriteScopeOverride::default();
+    for (key, value) in dict.iter() {
+        match key.unpack_str() {
+            Some("read_only") => {
+                write_scope.read_only = Some(parse_string_list(value, "write_scope.read_only")?);
+            }
+            Some("deny_read") => {
+                write_scope.deny_read = Some(
+                    parse_string_list(value, "write_scope.deny_read")?
+                        .iter()
+                        .map(|raw| parse_deny_read_path(raw))
+                        .collect::<Result<_>>()?,
+                );
+            }
+            _ => {
+                return Err(Error::InvalidRule(format!(
+                    "command_rule write_scope keys must be read_only or deny_read (got {key})"
+                )));
+            }
+        }
+    }
+    Ok(write_scope)
+}
+
+fn parse_string_list<'v>(value: Value<'v>, field: &str) -> Result<Vec<String>> {
+    let invalid = || Error::InvalidRule(format!("command_rule {field} must be a list of strings"));
+    ListRef::from_value(value)
+        .ok_or_else(invalid)?
+        .content()
+        .iter()
+        .map(|item| item.unpack_str().map(str::to_string).ok_or_else(invalid))
+        .collect()
+}
+
+/// `deny_read` entries are absolute or start with `~/`, matching `[sandbox_write_scope]`.
+fn parse_deny_read_path(raw: &str) -> Result<AbsolutePathBuf> {
+    if !(Path::new(raw).is_absolute() || raw == "~" || raw.starts_with("~/")) {
+        return Err(Error::InvalidRule(format!(
+            "command_rule write_scope.deny_read paths must be absolute (got {raw})"
+        )));
+    }
+    AbsolutePathBuf::from_absolute_path(raw)
+        .map_err(|error| Error::InvalidRule(format!("invalid path `{raw}`: {error}")))
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -402,7 +520,116 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             })
             .collect();
 
-        builder.add_pending_example_validation(rules.clone(), matches, not_matches, location);
+        builder.add_pending_example_validation(
+            rules.clone(),
+            matches,
+            not_matches,
+            false,
+            location,
+        );
+        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+        Ok(NoneType)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn command_rule<'v>(
+        pattern: UnpackList<Value<'v>>,
+        args: Option<UnpackList<Value<'v>>>,
+        paths: Option<&'v str>,
+        redirects: Option<&'v str>,
+        env: Option<Value<'v>>,
+        write_scope: Option<Value<'v>>,
+        seccomp_profiles: Option<Value<'v>>,
+        decision: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = match decision {
+            Some(raw) => Decision::parse(raw)?,
+            None => Decision::Allow,
+        };
+
+        let justification = match justification {
+            Some(raw) if raw.trim().is_empty() => {
+                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
+            }
+            Some(raw) => Some(raw.to_string()),
+            None => None,
+        };
+
+        let pattern_tokens = parse_pattern(pattern)?;
+        let args = args
+            .map(parse_arg_conditions)
+            .transpose()?
+            .unwrap_or_default();
+        let paths = paths
+            .map(|raw| PathScope::parse(raw, "paths"))
+            .transpose()?;
+        let redirects = redirects.map(RedirectCondition::parse).transpose()?;
+        let env = env
+            .map(parse_env_conditions)
+            .transpose()?
+            .unwrap_or_default();
+        let sandbox = SandboxOverride {
+            write_scope: write_scope.map(parse_write_scope_override).transpose()?,
+            seccomp_profiles: seccomp_profiles
+                .map(|value| parse_string_list(value, "seccomp_profiles"))
+                .transpose()?,
+        };
+        if args.is_empty()
+            && paths.is_none()
+            && redirects.is_none()
+            && env.is_empty()
+            && sandbox.is_empty()
+        {
+            return Err(Error::InvalidRule(
+                "command_rule requires at least one of args, paths, redirects, env, write_scope, or seccomp_profiles; use prefix_rule otherwise"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let matches: Vec<Vec<String>> =
+            r#match.map(parse_examples).transpose()?.unwrap_or_default();
+        let not_matches: Vec<Vec<String>> = not_match
+            .map(parse_examples)
+            .transpose()?
+            .unwrap_or_default();
+        let location = eval
+            .call_stack_top_location()
+            .map(error_location_from_file_span);
+
+        let mut builder = policy_builder(eval);
+
+        let (first_token, remaining_tokens) = pattern_tokens
+            .split_first()
+            .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+
+        let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
+
+        let rules: Vec<RuleRef> = first_token
+            .alternatives()
+            .iter()
+            .map(|head| {
+                Arc::new(CommandRule {
+                    pattern: PrefixPattern {
+                        first: Arc::from(head.as_str()),
+                        rest: rest.clone(),
+                    },
+                    args: args.clone(),
+                    paths,
+                    redirects,
+                    env: env.clone(),
+                    sandbox: sandbox.clone(),
+                    decision,
+                    justification: justification.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        builder.add_pending_example_validation(rules.clone(), matches, not_matches, true, location);
         rules.into_iter().for_each(|rule| builder.add_rule(rule));
         Ok(NoneType)
     }
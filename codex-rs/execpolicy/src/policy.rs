@@ -22,6 +22,16 @@ type HeuristicsFallback<'a> = Option<&'a dyn Fn(&[String]) -> Decision>;
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MatchOptions {
     pub resolve_host_executables: bool,
+    /// Directory relative path arguments and redirect targets are resolved against.
+    pub cwd: Option<AbsolutePathBuf>,
+    /// Roots that count as "inside the workspace"; defaults to `cwd` when empty.
+    pub workspace_roots: Vec<AbsolutePathBuf>,
+    /// Redirection targets attached to the command. `None` means unknown, in which case
+    /// rules that constrain redirections never match.
+    pub redirects: Option<Vec<String>>,
+    /// Environment the command runs with. `None` means unknown, in which case rules that
+    /// constrain env vars never match.
+    pub env: Option<HashMap<String, String>>,
 }
 
 #[derive(Clone, Debug)]
@@ -272,12 +282,12 @@ impl Policy {
         options: &MatchOptions,
     ) -> Vec<RuleMatch> {
         let matched_rules = self
-            .match_exact_rules(cmd)
+            .match_exact_rules(cmd, options)
             .filter(|matched_rules| !matched_rules.is_empty())
             .or_else(|| {
                 options
                     .resolve_host_executables
-                    .then(|| self.match_host_executable_rules(cmd))
+                    .then(|| self.match_host_executable_rules(cmd, options))
                     .filter(|matched_rules| !matched_rules.is_empty())
             })
             .unwrap_or_default();
@@ -294,17 +304,26 @@ impl Policy {
         }
     }
 
-    fn match_exact_rules(&self, cmd: &[String]) -> Option<Vec<RuleMatch>> {
+    fn match_exact_rules(&self, cmd: &[String], options: &MatchOptions) -> Option<Vec<RuleMatch>> {
         let first = cmd.first()?;
         Some(
             self.rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches_with_options(cmd, options))
+                        .collect()
+                })
                 .unwrap_or_default(),
         )
     }
 
-    fn match_host_executable_rules(&self, cmd: &[String]) -> Vec<RuleMatch> {
+    fn match_host_executable_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<RuleMatch> {
         let Some(first) = cmd.first() else {
             return Vec::new();
         };
@@ -328,7 +347,7 @@ impl Policy {
             .collect::<Vec<_>>();
         rules
             .iter()
-            .filter_map(|rule| rule.matches(&basename_command))
+            .filter_map(|rule| rule.matches_with_options(&basename_command, options))
             .map(|rule_match| rule_match.with_resolved_program(&program))
             .collect()
     }
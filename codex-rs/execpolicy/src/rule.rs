@@ -1,3 +1,4 @@
+use crate::command_rule::SandboxOverride;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
@@ -75,6 +76,19 @@ pub enum RuleMatch {
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
     },
+    CommandRuleMatch {
+        #[serde(rename = "matchedPrefix")]
+        matched_prefix: Vec<String>,
+        decision: Decision,
+        #[serde(rename = "resolvedProgram", skip_serializing_if = "Option::is_none")]
+        resolved_program: Option<AbsolutePathBuf>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+        /// Human-readable explanation of each `command_rule` condition that held.
+        conditions: Vec<String>,
+        #[serde(default, skip_serializing_if = "SandboxOverride::is_empty")]
+        sandbox: SandboxOverride,
+    },
     HeuristicsRuleMatch {
         command: Vec<String>,
         decision: Decision,
@@ -85,10 +99,19 @@ impl RuleMatch {
     pub fn decision(&self) -> Decision {
         match self {
             Self::PrefixRuleMatch { decision, .. } => *decision,
+            Self::CommandRuleMatch { decision, .. } => *decision,
             Self::HeuristicsRuleMatch { decision, .. } => *decision,
         }
     }
 
+    /// Sandbox settings attached by a `command_rule`, if it sets any.
+    pub fn sandbox_override(&self) -> Option<&SandboxOverride> {
+        match self {
+            Self::CommandRuleMatch { sandbox, .. } if !sandbox.is_empty() => Some(sandbox),
+            _ => None,
+        }
+    }
+
     pub fn with_resolved_program(self, resolved_program: &AbsolutePathBuf) -> Self {
         match self {
             Self::PrefixRuleMatch {
@@ -102,6 +125,21 @@ impl RuleMatch {
                 resolved_program: Some(resolved_program.clone()),
                 justification,
             },
+            Self::CommandRuleMatch {
+                matched_prefix,
+                decision,
+                justification,
+                conditions,
+                sandbox,
+                ..
+            } => Self::CommandRuleMatch {
+                matched_prefix,
+                decision,
+                resolved_program: Some(resolved_program.clone()),
+                justification,
+                conditions,
+                sandbox,
+            },
             other => other,
         }
     }
@@ -216,6 +254,12 @@ pub trait Rule: Any + Debug + Send + Sync {
 
     fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
 
+    /// Like [`Rule::matches`], but with the cwd, workspace roots, redirections, and
+    /// environment from `options` available to context-aware rules.
+    fn matches_with_options(&self, cmd: &[String], _options: &MatchOptions) -> Option<RuleMatch> {
+        self.matches(cmd)
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -247,15 +291,14 @@ pub(crate) fn validate_match_examples(
     policy: &Policy,
     rules: &[RuleRef],
     matches: &[Vec<String>],
+    examples_include_context: bool,
 ) -> Result<()> {
     let mut unmatched_examples = Vec::new();
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
 
     for example in matches {
+        let (command, options) = example_command_and_options(example, examples_include_context);
         if !policy
-            .matches_for_command_with_options(example, None, &options)
+            .matches_for_command_with_options(&command, None, &options)
             .is_empty()
         {
             continue;
@@ -283,14 +326,12 @@ pub(crate) fn validate_not_match_examples(
     policy: &Policy,
     _rules: &[RuleRef],
     not_matches: &[Vec<String>],
+    examples_include_context: bool,
 ) -> Result<()> {
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
-
     for example in not_matches {
+        let (command, options) = example_command_and_options(example, examples_include_context);
         if let Some(rule) = policy
-            .matches_for_command_with_options(example, None, &options)
+            .matches_for_command_with_options(&command, None, &options)
             .first()
         {
             return Err(Error::ExampleDidMatch {
@@ -304,3 +345,22 @@ pub(crate) fn validate_not_match_examples(
 
     Ok(())
 }
+
+/// `command_rule` examples carry their own env assignments and redirections; every other
+/// example is matched as a plain argv.
+fn example_command_and_options(
+    example: &[String],
+    examples_include_context: bool,
+) -> (Vec<String>, MatchOptions) {
+    if examples_include_context {
+        crate::command_rule::example_command_and_options(example)
+    } else {
+        (
+            example.to_vec(),
+            MatchOptions {
+                resolve_host_executables: true,
+                ..Default::default()
+            },
+        )
+    }
+}
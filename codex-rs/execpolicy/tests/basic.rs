@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -14,6 +15,8 @@ use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::SandboxOverride;
+use codex_execpolicy::WriteScopeOverride;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
@@ -786,6 +789,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -843,6 +847,7 @@ host_executable(name = "git", paths = [])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -878,6 +883,7 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -908,6 +914,7 @@ prefix_rule(pattern = ["git"], decision = "prompt")
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -945,6 +952,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -961,3 +969,255 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
     );
     Ok(())
 }
+
+fn workspace_match_options(cwd: &str) -> MatchOptions {
+    MatchOptions {
+        cwd: Some(absolute_path(cwd)),
+        redirects: Some(Vec::new()),
+        env: Some(HashMap::new()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn command_rule_matches_paths_inside_workspace() -> Result<()> {
+    let policy_src = r#"
+command_rule(
+    pattern = ["rm"],
+    paths = "inside_workspace",
+    match = ["rm -rf build", ["rm", "./a", "b/c"]],
+    not_match = ["rm /etc/passwd", "rm -rf ../sibling", "rm -rf", "rm $HOME"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+    let cwd = host_absolute_path(&["repo"]);
+    let options = workspace_match_options(&cwd);
+
+    let evaluation =
+        policy.check_with_options(&tokens(&["rm", "-rf", "target"]), &prompt_all, &options);
+    assert_eq!(
+        evaluation,
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::CommandRuleMatch {
+                matched_prefix: tokens(&["rm"]),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+                conditions: vec![
+                    "every path argument resolves inside the workspace roots: `target`".to_string()
+                ],
+                sandbox: SandboxOverride::default(),
+            }],
+        }
+    );
+
+    let outside = policy.check_with_options(&tokens(&["rm", "../x"]), &prompt_all, &options);
+    assert_eq!(outside.decision, Decision::Prompt);
+    assert!(!outside.is_match());
+
+    // Without a cwd, relative paths cannot be resolved and never count as inside.
+    let no_cwd = policy.check(&tokens(&["rm", "target"]), &prompt_all);
+    assert!(!no_cwd.is_match());
+    Ok(())
+}
+
+#[test]
+fn command_rule_forbids_force_push_to_main() -> Result<()> {
+    let policy_src = r#"
+command_rule(
+    pattern = ["git", "push"],
+    args = [["--force", "-f", "--force-with-lease*"], ["main", "*:main", "re:^refs/heads/main$"]],
+    decision = "forbidden",
+    justification = "never rewrite main",
+    match = ["git push --force origin main", "git push -f origin HEAD:main"],
+    not_match = ["git push --force origin feature", "git push origin main"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&[
+            "git",
+            "push",
+            "--force-with-lease",
+            "origin",
+            "refs/heads/main",
+        ]),
+        &allow_all,
+    );
+    assert_eq!(
+        evaluation,
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::CommandRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Forbidden,
+                resolved_program: None,
+                justification: Some("never rewrite main".to_string()),
+                conditions: vec![
+                    "argument `--force-with-lease` matches `--force-with-lease*`".to_string(),
+                    "argument `refs/heads/main` matches `re:^refs/heads/main$`".to_string(),
+                ],
+                sandbox: SandboxOverride::default(),
+            }],
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn command_rule_matches_env_and_redirects() -> Result<()> {
+    let policy_src = r#"
+command_rule(
+    pattern = ["make"],
+    env = {"CI": "true"},
+    decision = "prompt",
+    match = ["CI=true make test"],
+    not_match = ["make test", "CI=false make test"],
+)
+command_rule(
+    pattern = ["echo"],
+    redirects = "inside_workspace",
+    match = ["echo hi > out.txt", "echo hi 2>>logs/err.log"],
+    not_match = ["echo hi > /etc/motd", "echo hi", "echo hi 2>&1"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let mut options = workspace_match_options(&host_absolute_path(&["repo"]));
+    options.env = Some(HashMap::from([("CI".to_string(), "true".to_string())]));
+    assert_eq!(
+        policy.matches_for_command_with_options(&tokens(&["make", "test"]), None, &options),
+        vec![RuleMatch::CommandRuleMatch {
+            matched_prefix: tokens(&["make"]),
+            decision: Decision::Prompt,
+            resolved_program: None,
+            justification: None,
+            conditions: vec!["env `CI=true` matches `true`".to_string()],
+            sandbox: SandboxOverride::default(),
+        }]
+    );
+
+    // Rules that constrain the environment never match when it is unknown.
+    assert_eq!(
+        policy.matches_for_command(&tokens(&["make", "test"]), None),
+        Vec::<RuleMatch>::new()
+    );
+    Ok(())
+}
+
+#[test]
+fn command_rule_examples_are_validated() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"command_rule(pattern=["rm"], paths="inside_workspace", match=["rm /tmp/x"])"#,
+        )
+        .expect_err("example outside the workspace should fail validation");
+    assert!(
+        matches!(err, Error::ExampleDidNotMatch { ref examples, .. } if examples == &vec!["rm /tmp/x".to_string()]),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn command_rule_requires_a_condition() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", r#"command_rule(pattern=["rm"])"#)
+        .expect_err("command_rule without conditions should fail");
+    assert!(
+        err.to_string().contains(
+            "requires at least one of args, paths, redirects, env, write_scope, or seccomp_profiles"
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn command_rule_carries_write_scope_override() -> Result<()> {
+    let policy_src = r#"
+command_rule(
+    pattern = ["cargo", "update"],
+    write_scope = {"read_only": [".git/hooks"], "deny_read": ["/etc/secrets"]},
+    match = ["cargo update -p serde"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let matches = policy.matches_for_command(&tokens(&["cargo", "update"]), None);
+    assert_eq!(
+        matches,
+        vec![RuleMatch::CommandRuleMatch {
+            matched_prefix: tokens(&["cargo", "update"]),
+            decision: Decision::Allow,
+            resolved_program: None,
+            justification: None,
+            conditions: Vec::new(),
+            sandbox: SandboxOverride {
+                write_scope: Some(WriteScopeOverride {
+                    read_only: Some(vec![".git/hooks".to_string()]),
+                    deny_read: Some(vec![absolute_path("/etc/secrets")]),
+                }),
+                seccomp_profiles: None,
+            },
+        }]
+    );
+    assert_eq!(
+        serde_json::to_value(&matches[0])?["commandRuleMatch"]["sandbox"],
+        serde_json::json!({
+            "writeScope": {"readOnly": [".git/hooks"], "denyRead": ["/etc/secrets"]}
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn command_rule_carries_seccomp_profiles() -> Result<()> {
+    let mut parser = PolicyParser::new();
+    parser.parse(
+        "test.rules",
+        r#"command_rule(pattern = ["cargo", "test"], seccomp_profiles = ["no-ptrace", "no-mount"])"#,
+    )?;
+    let policy = parser.build();
+
+    let matches = policy.matches_for_command(&tokens(&["cargo", "test", "-p", "core"]), None);
+    assert_eq!(
+        matches[0].sandbox_override(),
+        Some(&SandboxOverride {
+            write_scope: None,
+            seccomp_profiles: Some(vec!["no-ptrace".to_string(), "no-mount".to_string()]),
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&matches[0])?["commandRuleMatch"]["sandbox"],
+        serde_json::json!({"seccompProfiles": ["no-ptrace", "no-mount"]})
+    );
+    Ok(())
+}
+
+#[test]
+fn command_rule_rejects_relative_deny_read_paths() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"command_rule(pattern=["cargo"], write_scope={"deny_read": [".ssh"]})"#,
+        )
+        .expect_err("relative deny_read path should be rejected");
+    assert!(
+        err.to_string()
+            .contains("write_scope.deny_read paths must be absolute"),
+        "{err}"
+    );
+}
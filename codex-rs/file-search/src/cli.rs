@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use clap::ArgAction;
 use clap::Parser;
 
-/// Fuzzy matches filenames under a directory.
+/// Fuzzy matches filenames (or, with `--content`, searches file contents)
+/// under a directory.
 #[derive(Parser)]
 #[command(version)]
 pub struct Cli {
@@ -37,6 +38,14 @@ pub struct Cli {
     #[arg(short, long, action = ArgAction::Append)]
     pub exclude: Vec<String>,
 
+    /// Search file contents instead of fuzzy-matching paths.
+    #[arg(long, default_value = "false")]
+    pub content: bool,
+
+    /// Treat the pattern as a regular expression (requires `--content`).
+    #[arg(long, default_value = "false", requires = "content")]
+    pub regex: bool,
+
     /// Search pattern.
     pub pattern: Option<String>,
 }
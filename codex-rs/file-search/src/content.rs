@@ -0,0 +1,493 @@
+//! Content search: walks the same files as the path matcher and greps them
+//! line by line, streaming ranked hits through the session's reporter.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::RecvTimeoutError;
+use regex::Regex;
+use regex::RegexBuilder;
+
+use crate::ContentMatch;
+use crate::FileMatch;
+use crate::FileSearchMode;
+use crate::FileSearchSnapshot;
+use crate::SessionInner;
+use crate::WorkSignal;
+use crate::build_walker;
+use crate::get_file_path;
+
+/// Longest line kept in a [`ContentMatch`]; longer lines are cut at a char
+/// boundary.
+pub const MAX_CONTENT_LINE_BYTES: usize = 512;
+
+/// Files larger than this are not searched.
+const MAX_CONTENT_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Leading bytes inspected for a NUL to decide a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Minimum delay between streamed snapshots while a scan is running.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+const BASE_SCORE: u32 = 100;
+const WHOLE_WORD_BONUS: u32 = 50;
+const EXACT_CASE_BONUS: u32 = 20;
+const MAX_DEPTH_PENALTY: u32 = 20;
+
+/// Drives a content-search session: every query update starts a fresh scan
+/// and supersedes the previous one.
+pub(crate) fn content_worker(
+    inner: Arc<SessionInner>,
+    work_rx: Receiver<WorkSignal>,
+    override_matcher: Option<ignore::overrides::Override>,
+    mode: FileSearchMode,
+) {
+    let generation = Arc::new(AtomicUsize::new(0));
+    loop {
+        match work_rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(WorkSignal::QueryUpdated(query)) => {
+                let scan = Scan {
+                    inner: inner.clone(),
+                    override_matcher: override_matcher.clone(),
+                    generation: generation.clone(),
+                    id: generation.fetch_add(1, Ordering::SeqCst) + 1,
+                    query,
+                    mode,
+                };
+                thread::spawn(move || scan.run());
+            }
+            Ok(WorkSignal::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+            Ok(WorkSignal::NucleoNotify | WorkSignal::WalkComplete)
+            | Err(RecvTimeoutError::Timeout) => {}
+        }
+        if inner.cancelled.load(Ordering::Relaxed) || inner.shutdown.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+
+    // Stop any in-flight scan and make sure the reporter hears about it.
+    generation.fetch_add(1, Ordering::SeqCst);
+    inner.reporter.on_complete();
+}
+
+struct Scan {
+    inner: Arc<SessionInner>,
+    override_matcher: Option<ignore::overrides::Override>,
+    generation: Arc<AtomicUsize>,
+    id: usize,
+    query: String,
+    mode: FileSearchMode,
+}
+
+struct ScanState {
+    matches: Vec<FileMatch>,
+    total_match_count: usize,
+    scanned_file_count: usize,
+    dirty: bool,
+    last_update: Instant,
+}
+
+impl Scan {
+    fn is_stale(&self) -> bool {
+        self.generation.load(Ordering::SeqCst) != self.id
+            || self.inner.cancelled.load(Ordering::Relaxed)
+            || self.inner.shutdown.load(Ordering::Relaxed)
+    }
+
+    fn run(self) {
+        // An empty or invalid pattern completes immediately with no matches.
+        let matcher = Some(self.query.as_str())
+            .filter(|query| !query.is_empty())
+            .and_then(|query| build_matcher(query, self.mode).ok());
+        let state = Mutex::new(ScanState {
+            matches: Vec::new(),
+            total_match_count: 0,
+            scanned_file_count: 0,
+            dirty: false,
+            last_update: Instant::now(),
+        });
+
+        if let Some(matcher) = &matcher
+            && let Some(walk_builder) = build_walker(&self.inner, self.override_matcher.clone())
+        {
+            let scan = &self;
+            let state = &state;
+            walk_builder.build_parallel().run(|| {
+                Box::new(move |entry| {
+                    if scan.is_stale() {
+                        return ignore::WalkState::Quit;
+                    }
+                    let Ok(entry) = entry else {
+                        return ignore::WalkState::Continue;
+                    };
+                    if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                        return ignore::WalkState::Continue;
+                    }
+                    let Some((root_idx, relative_path)) =
+                        get_file_path(entry.path(), &scan.inner.search_directories)
+                    else {
+                        return ignore::WalkState::Continue;
+                    };
+                    let file_matches = search_file(
+                        entry.path(),
+                        relative_path,
+                        &scan.inner.search_directories[root_idx],
+                        matcher,
+                        scan.inner.compute_indices,
+                    );
+
+                    #[expect(clippy::unwrap_used)]
+                    let mut state = state.lock().unwrap();
+                    state.scanned_file_count += 1;
+                    if !file_matches.is_empty() {
+                        state.total_match_count += file_matches.len();
+                        state.matches.extend(file_matches);
+                        // Keep memory bounded without losing anything that
+                        // could still make the top `limit`.
+                        if state.matches.len() > scan.inner.limit * 2 {
+                            sort_and_truncate(&mut state.matches, scan.inner.limit);
+                        }
+                        state.dirty = true;
+                    }
+                    if state.dirty && state.last_update.elapsed() >= UPDATE_INTERVAL {
+                        scan.report(&mut state, false);
+                    }
+                    ignore::WalkState::Continue
+                })
+            });
+        }
+
+        if self.is_stale() {
+            return;
+        }
+        #[expect(clippy::unwrap_used)]
+        let mut state = state.lock().unwrap();
+        self.report(&mut state, true);
+        drop(state);
+        self.inner.reporter.on_complete();
+    }
+
+    fn report(&self, state: &mut ScanState, walk_complete: bool) {
+        sort_and_truncate(&mut state.matches, self.inner.limit);
+        state.dirty = false;
+        state.last_update = Instant::now();
+        self.inner.reporter.on_update(&FileSearchSnapshot {
+            query: self.query.clone(),
+            matches: state.matches.clone(),
+            total_match_count: state.total_match_count,
+            scanned_file_count: state.scanned_file_count,
+            walk_complete,
+        });
+    }
+}
+
+struct ContentMatcher {
+    regex: Regex,
+    /// The literal query when it should earn the exact-case bonus.
+    exact: Option<String>,
+}
+
+/// Compiles the query with smart case: case-insensitive unless the query
+/// contains an uppercase character.
+fn build_matcher(query: &str, mode: FileSearchMode) -> Result<ContentMatcher, regex::Error> {
+    let pattern = match mode {
+        FileSearchMode::ContentRegex => query.to_string(),
+        FileSearchMode::ContentLiteral | FileSearchMode::Path => regex::escape(query),
+    };
+    let case_insensitive = !query.chars().any(char::is_uppercase);
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(case_insensitive)
+        .build()?;
+    let exact = (mode == FileSearchMode::ContentLiteral).then(|| query.to_string());
+    Ok(ContentMatcher { regex, exact })
+}
+
+/// Returns one [`FileMatch`] per matching line of the file at `path`.
+/// Unreadable, oversized, binary, and non-UTF-8 files yield no matches.
+fn search_file(
+    path: &Path,
+    relative_path: &str,
+    root: &Path,
+    matcher: &ContentMatcher,
+    compute_indices: bool,
+) -> Vec<FileMatch> {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Vec::new();
+    };
+    if metadata.len() > MAX_CONTENT_FILE_BYTES {
+        return Vec::new();
+    }
+    let Ok(bytes) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return Vec::new();
+    }
+    let Ok(text) = std::str::from_utf8(&bytes) else {
+        return Vec::new();
+    };
+
+    let depth_penalty = u32::try_from(Path::new(relative_path).components().count())
+        .unwrap_or(u32::MAX)
+        .min(MAX_DEPTH_PENALTY);
+    let mut file_matches = Vec::new();
+    for (line_idx, line) in text.lines().enumerate() {
+        let mut found = matcher
+            .regex
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .peekable();
+        let Some(first) = found.peek().copied() else {
+            continue;
+        };
+
+        // Columns and word boundaries come from the full line; only the text
+        // returned to the caller is truncated.
+        let shown = truncate_line(line);
+        let mut score = BASE_SCORE - depth_penalty;
+        let mut indices = Vec::new();
+        let mut whole_word = false;
+        let mut exact_case = false;
+        for m in found {
+            whole_word |= is_whole_word(line, m.start(), m.end());
+            exact_case |= matcher
+                .exact
+                .as_deref()
+                .is_some_and(|exact| m.as_str() == exact);
+            if compute_indices && m.end() <= shown.len() {
+                let start = shown[..m.start()].chars().count();
+                let len = m.as_str().chars().count();
+                indices.extend((start..start + len).filter_map(|idx| u32::try_from(idx).ok()));
+            }
+        }
+        if whole_word {
+            score += WHOLE_WORD_BONUS;
+        }
+        if exact_case {
+            score += EXACT_CASE_BONUS;
+        }
+
+        file_matches.push(FileMatch {
+            score,
+            path: PathBuf::from(relative_path),
+            root: root.to_path_buf(),
+            indices: compute_indices.then_some(indices),
+            content: Some(ContentMatch {
+                line_number: line_idx + 1,
+                column: line[..first.start()].chars().count() + 1,
+                line: shown.to_string(),
+            }),
+        });
+    }
+    file_matches
+}
+
+fn truncate_line(line: &str) -> &str {
+    if line.len() <= MAX_CONTENT_LINE_BYTES {
+        return line;
+    }
+    let mut end = MAX_CONTENT_LINE_BYTES;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+fn is_whole_word(line: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let before = line.get(..start).and_then(|s| s.chars().next_back());
+    let after = line.get(end..).and_then(|s| s.chars().next());
+    !before.is_some_and(is_word) && !after.is_some_and(is_word)
+}
+
+/// Orders by descending score, then path, then line number, and keeps the
+/// first `limit` entries.
+fn sort_and_truncate(matches: &mut Vec<FileMatch>, limit: usize) {
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| line_number(a).cmp(&line_number(b)))
+    });
+    matches.truncate(limit);
+}
+
+fn line_number(file_match: &FileMatch) -> usize {
+    file_match
+        .content
+        .as_ref()
+        .map_or(0, |content| content.line_number)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::FileSearchOptions;
+    use crate::run;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::num::NonZero;
+    use tempfile::TempDir;
+
+    fn options(mode: FileSearchMode) -> FileSearchOptions {
+        FileSearchOptions {
+            limit: NonZero::new(20).unwrap(),
+            compute_indices: true,
+            mode,
+            ..Default::default()
+        }
+    }
+
+    fn hits(results: &[FileMatch]) -> Vec<(String, usize, usize)> {
+        results
+            .iter()
+            .map(|m| {
+                let content = m.content.as_ref().unwrap();
+                (
+                    m.path.to_string_lossy().to_string(),
+                    content.line_number,
+                    content.column,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn literal_search_ranks_whole_word_matches_first() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("a.rs"),
+            "fn parser_init() {}\nlet p = parse();\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("b.txt"), "nothing here\n").unwrap();
+
+        let results = run(
+            "parse",
+            vec![dir.path().to_path_buf()],
+            options(FileSearchMode::ContentLiteral),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hits(&results.matches),
+            vec![("a.rs".to_string(), 2, 9), ("a.rs".to_string(), 1, 4)]
+        );
+        assert_eq!(results.total_match_count, 2);
+        assert_eq!(results.matches[0].indices, Some(vec![8, 9, 10, 11, 12]));
+        assert_eq!(
+            results.matches[0].content.as_ref().unwrap().line,
+            "let p = parse();"
+        );
+    }
+
+    #[test]
+    fn matches_past_the_truncated_line_keep_their_column_and_word_bonus() {
+        let dir = TempDir::new().unwrap();
+        let padding = "x".repeat(MAX_CONTENT_LINE_BYTES + 100);
+        fs::write(
+            dir.path().join("long.txt"),
+            format!("{padding} needle\nneedles\n"),
+        )
+        .unwrap();
+
+        let results = run(
+            "needle",
+            vec![dir.path().to_path_buf()],
+            options(FileSearchMode::ContentLiteral),
+            None,
+        )
+        .unwrap();
+
+        // The match on the long line is a whole word past the truncation
+        // point, so it still outranks the partial match on line 2.
+        assert_eq!(
+            hits(&results.matches),
+            vec![
+                ("long.txt".to_string(), 1, MAX_CONTENT_LINE_BYTES + 102),
+                ("long.txt".to_string(), 2, 1),
+            ]
+        );
+        let long_line = &results.matches[0];
+        assert_eq!(
+            long_line.content.as_ref().unwrap().line,
+            &padding[..MAX_CONTENT_LINE_BYTES]
+        );
+        assert_eq!(long_line.indices, Some(Vec::new()));
+    }
+
+    #[test]
+    fn regex_search_uses_smart_case() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("notes.md"), "TODO: one\ntodo: two\n").unwrap();
+
+        let insensitive = run(
+            r"todo:\s+\w+",
+            vec![dir.path().to_path_buf()],
+            options(FileSearchMode::ContentRegex),
+            None,
+        )
+        .unwrap();
+        assert_eq!(insensitive.total_match_count, 2);
+
+        let sensitive = run(
+            "TODO",
+            vec![dir.path().to_path_buf()],
+            options(FileSearchMode::ContentRegex),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            hits(&sensitive.matches),
+            vec![("notes.md".to_string(), 1, 1)]
+        );
+    }
+
+    #[test]
+    fn content_search_respects_exclude_and_skips_binary_files() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("vendor")).unwrap();
+        fs::write(dir.path().join("main.go"), "needle\n").unwrap();
+        fs::write(dir.path().join("vendor/dep.go"), "needle\n").unwrap();
+        fs::write(dir.path().join("blob.bin"), b"needle\0\x01\x02").unwrap();
+
+        let results = run(
+            "needle",
+            vec![dir.path().to_path_buf()],
+            FileSearchOptions {
+                exclude: vec!["vendor/**".to_string()],
+                ..options(FileSearchMode::ContentLiteral)
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(hits(&results.matches), vec![("main.go".to_string(), 1, 1)]);
+    }
+
+    #[test]
+    fn invalid_regex_completes_with_no_matches() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "(\n").unwrap();
+
+        let results = run(
+            "(",
+            vec![dir.path().to_path_buf()],
+            options(FileSearchMode::ContentRegex),
+            None,
+        )
+        .unwrap();
+        assert_eq!(results.matches, Vec::new());
+    }
+}
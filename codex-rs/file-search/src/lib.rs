@@ -35,8 +35,10 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod content;
 
 pub use cli::Cli;
+pub use content::MAX_CONTENT_LINE_BYTES;
 
 /// A single match result returned from the search.
 ///
@@ -55,6 +57,11 @@ pub struct FileMatch {
     pub root: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indices: Option<Vec<u32>>, // Sorted & deduplicated when present
+    /// Matching line for content searches; `None` for path searches. When
+    /// present, `indices` refer to characters of `content.line` rather than
+    /// of `path`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<ContentMatch>,
 }
 
 impl FileMatch {
@@ -71,6 +78,36 @@ pub fn file_name_from_path(path: &str) -> String {
         .unwrap_or_else(|| path.to_string())
 }
 
+/// Location of a content-search hit within a file.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ContentMatch {
+    /// 1-based line number.
+    pub line_number: usize,
+    /// 1-based column, in characters, of the first match on the line.
+    pub column: usize,
+    /// The matching line without its line terminator, truncated to
+    /// [`MAX_CONTENT_LINE_BYTES`].
+    pub line: String,
+}
+
+/// What a session matches the query against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileSearchMode {
+    /// Fuzzy-match file paths.
+    #[default]
+    Path,
+    /// Search file contents for the query as a literal string.
+    ContentLiteral,
+    /// Search file contents with the query as a regular expression.
+    ContentRegex,
+}
+
+impl FileSearchMode {
+    pub fn is_content(self) -> bool {
+        matches!(self, Self::ContentLiteral | Self::ContentRegex)
+    }
+}
+
 #[derive(Debug)]
 pub struct FileSearchResults {
     pub matches: Vec<FileMatch>,
@@ -100,6 +137,7 @@ pub struct FileSearchOptions {
     /// turns off `.gitignore`, git-global/exclude rules, `.ignore`, and
     /// parent-directory ignore scanning.
     pub respect_gitignore: bool,
+    pub mode: FileSearchMode,
 }
 
 impl Default for FileSearchOptions {
@@ -112,6 +150,7 @@ impl Default for FileSearchOptions {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            mode: FileSearchMode::Path,
         }
     }
 }
@@ -157,6 +196,7 @@ pub fn create_session(
         threads,
         compute_indices,
         respect_gitignore,
+        mode,
     } = options;
 
     let Some(primary_search_directory) = search_directories.first() else {
@@ -164,6 +204,26 @@ pub fn create_session(
     };
     let override_matcher = build_override_matcher(primary_search_directory, &exclude)?;
     let (work_tx, work_rx) = unbounded();
+    let cancelled = cancel_flag.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+
+    if mode.is_content() {
+        let inner = Arc::new(SessionInner {
+            search_directories,
+            limit: limit.get(),
+            threads: threads.get(),
+            compute_indices,
+            respect_gitignore,
+            cancelled,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            reporter,
+            work_tx,
+        });
+        let worker_inner = inner.clone();
+        thread::spawn(move || {
+            content::content_worker(worker_inner, work_rx, override_matcher, mode)
+        });
+        return Ok(FileSearchSession { inner });
+    }
 
     let notify_tx = work_tx.clone();
     let notify = Arc::new(move || {
@@ -177,8 +237,6 @@ pub fn create_session(
     );
     let injector = nucleo.injector();
 
-    let cancelled = cancel_flag.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
-
     let inner = Arc::new(SessionInner {
         search_directories,
         limit: limit.get(),
@@ -215,6 +273,8 @@ pub async fn run_main<T: Reporter>(
         json: _,
         exclude,
         threads,
+        content,
+        regex,
     }: Cli,
     reporter: T,
 ) -> anyhow::Result<()> {
@@ -260,6 +320,11 @@ pub async fn run_main<T: Reporter>(
             threads,
             compute_indices,
             respect_gitignore: true,
+            mode: match (content, regex) {
+                (false, _) => FileSearchMode::Path,
+                (true, false) => FileSearchMode::ContentLiteral,
+                (true, true) => FileSearchMode::ContentRegex,
+            },
         },
         None,
     )?;
@@ -403,36 +468,10 @@ fn walker_worker(
     override_matcher: Option<ignore::overrides::Override>,
     injector: Injector<Arc<str>>,
 ) {
-    let Some(first_root) = inner.search_directories.first() else {
+    let Some(walk_builder) = build_walker(&inner, override_matcher) else {
         let _ = inner.work_tx.send(WorkSignal::WalkComplete);
         return;
     };
-
-    let mut walk_builder = WalkBuilder::new(first_root);
-    for root in inner.search_directories.iter().skip(1) {
-        walk_builder.add(root);
-    }
-    walk_builder
-        .threads(inner.threads)
-        // Allow hidden entries.
-        .hidden(false)
-        // Follow symlinks to search their contents.
-        .follow_links(true)
-        // Keep ignore behavior aligned with git repositories: only apply
-        // gitignore rules when a git context exists.
-        .require_git(true);
-    if !inner.respect_gitignore {
-        walk_builder
-            .git_ignore(false)
-            .git_global(false)
-            .git_exclude(false)
-            .ignore(false)
-            .parents(false);
-    }
-    if let Some(override_matcher) = override_matcher {
-        walk_builder.overrides(override_matcher);
-    }
-
     let walker = walk_builder.build_parallel();
 
     walker.run(|| {
@@ -473,6 +512,42 @@ fn walker_worker(
     let _ = inner.work_tx.send(WorkSignal::WalkComplete);
 }
 
+/// Builds the walker shared by path and content searches; see
+/// [`walker_worker`] for the ignore semantics.
+fn build_walker(
+    inner: &SessionInner,
+    override_matcher: Option<ignore::overrides::Override>,
+) -> Option<WalkBuilder> {
+    let first_root = inner.search_directories.first()?;
+
+    let mut walk_builder = WalkBuilder::new(first_root);
+    for root in inner.search_directories.iter().skip(1) {
+        walk_builder.add(root);
+    }
+    walk_builder
+        .threads(inner.threads)
+        // Allow hidden entries.
+        .hidden(false)
+        // Follow symlinks to search their contents.
+        .follow_links(true)
+        // Keep ignore behavior aligned with git repositories: only apply
+        // gitignore rules when a git context exists.
+        .require_git(true);
+    if !inner.respect_gitignore {
+        walk_builder
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .ignore(false)
+            .parents(false);
+    }
+    if let Some(override_matcher) = override_matcher {
+        walk_builder.overrides(override_matcher);
+    }
+
+    Some(walk_builder)
+}
+
 fn matcher_worker(
     inner: Arc<SessionInner>,
     work_rx: Receiver<WorkSignal>,
@@ -557,6 +632,7 @@ fn matcher_worker(
                                 path: PathBuf::from(relative_path),
                                 root: inner.search_directories[root_idx].clone(),
                                 indices,
+                                content: None,
                             })
                         })
                         .collect();
@@ -947,6 +1023,7 @@ mod tests {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            mode: FileSearchMode::Path,
         };
         let results =
             run("file-000", vec![dir.path().to_path_buf()], options, None).expect("run ok");
@@ -1018,6 +1095,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                mode: FileSearchMode::Path,
             },
             None,
         )
@@ -1038,6 +1116,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                mode: FileSearchMode::Path,
             },
             None,
         )
@@ -1082,6 +1161,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                mode: FileSearchMode::Path,
             },
             None,
         )
@@ -1102,6 +1182,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                mode: FileSearchMode::Path,
             },
             None,
         )
@@ -1122,6 +1203,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                mode: FileSearchMode::Path,
             },
             None,
         )
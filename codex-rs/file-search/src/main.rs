@@ -28,6 +28,14 @@ impl Reporter for StdioReporter {
     fn report_match(&self, file_match: &FileMatch) {
         if self.write_output_as_json {
             println!("{}", serde_json::to_string(&file_match).unwrap());
+        } else if let Some(content) = &file_match.content {
+            println!(
+                "{}:{}:{}: {}",
+                file_match.path.to_string_lossy(),
+                content.line_number,
+                content.column,
+                content.line
+            );
         } else if self.show_indices {
             let indices = file_match
                 .indices
@@ -0,0 +1,289 @@
+use std::io;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::Hook;
+use crate::HookPayload;
+use crate::HookResult;
+use crate::HookToolInput;
+use crate::command_from_argv;
+
+pub const DEFAULT_COMMAND_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Decision printed by an external hook command on stdout. Empty stdout is treated as
+/// `allow` so observe-only hooks do not need to print anything.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+enum CommandHookOutput {
+    Allow,
+    Deny {
+        reason: String,
+    },
+    Modify {
+        tool_input: HookToolInput,
+    },
+    Ask {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+impl From<CommandHookOutput> for HookResult {
+    fn from(output: CommandHookOutput) -> Self {
+        match output {
+            CommandHookOutput::Allow => HookResult::Success,
+            CommandHookOutput::Deny { reason } => HookResult::Deny { reason },
+            CommandHookOutput::Modify { tool_input } => HookResult::ModifyToolInput(tool_input),
+            CommandHookOutput::Ask { reason } => HookResult::RequireApproval { reason },
+        }
+    }
+}
+
+/// Builds a hook that runs `argv` with the JSON-encoded [`HookPayload`] on stdin and reads
+/// an optional JSON decision from stdout. Spawn failures, non-zero exits, timeouts, and
+/// malformed output are reported as `FailedContinue`, or as `FailedAbort` when
+/// `fail_closed` is set so a broken policy hook blocks the operation instead of allowing it.
+pub fn command_hook(name: String, argv: Vec<String>, timeout: Duration, fail_closed: bool) -> Hook {
+    let argv = Arc::new(argv);
+    Hook {
+        name,
+        func: Arc::new(move |payload: &HookPayload| {
+            let argv = Arc::clone(&argv);
+            Box::pin(async move {
+                match run_command_hook(&argv, payload, timeout).await {
+                    Ok(result) => result,
+                    Err(err) if fail_closed => HookResult::FailedAbort(err.into()),
+                    Err(err) => HookResult::FailedContinue(err.into()),
+                }
+            })
+        }),
+    }
+}
+
+async fn run_command_hook(
+    argv: &[String],
+    payload: &HookPayload,
+    timeout: Duration,
+) -> io::Result<HookResult> {
+    let mut command =
+        command_from_argv(argv).ok_or_else(|| io::Error::other("hook command is empty"))?;
+    let input = serde_json::to_vec(payload)?;
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn()?;
+
+    let run = async move {
+        if let Some(mut stdin) = child.stdin.take() {
+            // Hooks are free to ignore their input; a closed pipe is not an error.
+            match stdin.write_all(&input).await {
+                Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err),
+                _ => {}
+            }
+        }
+        child.wait_with_output().await
+    };
+    let output = tokio::time::timeout(timeout, run).await.map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("hook command timed out after {}ms", timeout.as_millis()),
+        )
+    })??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!(
+            "hook command exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+
+    parse_command_hook_output(&output.stdout)
+}
+
+fn parse_command_hook_output(stdout: &[u8]) -> io::Result<HookResult> {
+    let stdout = String::from_utf8_lossy(stdout);
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return Ok(HookResult::Success);
+    }
+    let output: CommandHookOutput = serde_json::from_str(stdout).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid hook command output: {err}"),
+        )
+    })?;
+    Ok(output.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::Utc;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::HookEvent;
+    use crate::HookEventBeforeToolUse;
+    use crate::HookToolInputLocalShell;
+    use crate::HookToolKind;
+
+    fn before_tool_use_payload() -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from("/tmp"),
+            client: None,
+            triggered_at: Utc::now(),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-1".to_string(),
+                    call_id: "call-1".to_string(),
+                    tool_name: "shell".to_string(),
+                    tool_kind: HookToolKind::Function,
+                    tool_input: HookToolInput::Function {
+                        arguments: "{\"command\":[\"rm\",\"-rf\",\"/\"]}".to_string(),
+                    },
+                    mutating: true,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "danger-full-access".to_string(),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn parse_output_treats_empty_stdout_as_allow() {
+        assert!(matches!(
+            parse_command_hook_output(b" \n").expect("parse"),
+            HookResult::Success
+        ));
+    }
+
+    #[test]
+    fn parse_output_maps_decisions() {
+        assert!(matches!(
+            parse_command_hook_output(br#"{"decision":"deny","reason":"no rm"}"#).expect("parse"),
+            HookResult::Deny { reason } if reason == "no rm"
+        ));
+        assert!(matches!(
+            parse_command_hook_output(br#"{"decision":"ask"}"#).expect("parse"),
+            HookResult::RequireApproval { reason: None }
+        ));
+        let modified = parse_command_hook_output(
+            br#"{"decision":"modify","tool_input":{"input_type":"local_shell","params":{"command":["ls"]}}}"#,
+        )
+        .expect("parse");
+        let HookResult::ModifyToolInput(tool_input) = modified else {
+            panic!("expected modified tool input, got {modified:?}");
+        };
+        assert_eq!(
+            tool_input,
+            HookToolInput::LocalShell {
+                params: HookToolInputLocalShell {
+                    command: vec!["ls".to_string()],
+                    workdir: None,
+                    timeout_ms: None,
+                    sandbox_permissions: None,
+                    prefix_rule: None,
+                    justification: None,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_output_rejects_unknown_decision() {
+        let err = parse_command_hook_output(br#"{"decision":"maybe"}"#).expect_err("invalid");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_reads_payload_from_stdin_and_returns_decision() {
+        let hook = command_hook(
+            "org-policy".to_string(),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                r#"if grep -q -e '-rf'; then printf '{"decision":"deny","reason":"rm is not allowed"}'; fi"#
+                    .to_string(),
+            ],
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+            false,
+        );
+
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        assert_eq!(outcome.hook_name, "org-policy");
+        assert!(matches!(
+            outcome.result,
+            HookResult::Deny { reason } if reason == "rm is not allowed"
+        ));
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_reports_non_zero_exit_as_continuable_failure() {
+        let hook = command_hook(
+            "failing".to_string(),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "echo boom >&2; exit 3".to_string(),
+            ],
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+            false,
+        );
+
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        let HookResult::FailedContinue(err) = outcome.result else {
+            panic!("expected FailedContinue, got {:?}", outcome.result);
+        };
+        assert!(err.to_string().contains("boom"), "{err}");
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn command_hook_times_out() {
+        let hook = command_hook(
+            "slow".to_string(),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "sleep 5".to_string(),
+            ],
+            Duration::from_millis(100),
+            false,
+        );
+
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        assert!(matches!(outcome.result, HookResult::FailedContinue(_)));
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn fail_closed_command_hook_aborts_on_failure() {
+        let hook = command_hook(
+            "policy".to_string(),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "echo not-json".to_string(),
+            ],
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+            true,
+        );
+
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        assert!(outcome.result.should_abort_operation());
+        assert!(matches!(outcome.result, HookResult::FailedAbort(_)));
+    }
+}
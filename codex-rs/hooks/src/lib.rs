@@ -1,7 +1,11 @@
+mod command_hook;
 mod registry;
 mod types;
 mod user_notification;
 
+pub use command_hook::DEFAULT_COMMAND_HOOK_TIMEOUT;
+pub use command_hook::command_hook;
+pub use registry::CommandHookConfig;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
 pub use registry::command_from_argv;
@@ -9,6 +13,10 @@ pub use types::Hook;
 pub use types::HookEvent;
 pub use types::HookEventAfterAgent;
 pub use types::HookEventAfterToolUse;
+pub use types::HookEventBeforeToolUse;
+pub use types::HookEventBeforeTurn;
+pub use types::HookEventSessionEnd;
+pub use types::HookEventSessionStart;
 pub use types::HookPayload;
 pub use types::HookResponse;
 pub use types::HookResult;
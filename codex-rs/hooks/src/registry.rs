@@ -1,17 +1,61 @@
+use std::time::Duration;
+
 use tokio::process::Command;
 
+use crate::command_hook::DEFAULT_COMMAND_HOOK_TIMEOUT;
+use crate::command_hook::command_hook;
 use crate::types::Hook;
 use crate::types::HookEvent;
 use crate::types::HookPayload;
 use crate::types::HookResponse;
+use crate::types::HookResult;
 
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct HooksConfig {
     pub legacy_notify_argv: Option<Vec<String>>,
+    pub session_start: Vec<CommandHookConfig>,
+    pub session_end: Vec<CommandHookConfig>,
+    pub before_turn: Vec<CommandHookConfig>,
+    pub before_tool_use: Vec<CommandHookConfig>,
+    pub after_tool_use: Vec<CommandHookConfig>,
+    pub after_agent: Vec<CommandHookConfig>,
+}
+
+/// External command registered for a hook event. See [`crate::command_hook`] for the
+/// stdin/stdout contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandHookConfig {
+    pub name: Option<String>,
+    pub argv: Vec<String>,
+    pub timeout: Option<Duration>,
+    /// Block the operation when the command fails instead of continuing.
+    pub fail_closed: bool,
+}
+
+impl CommandHookConfig {
+    fn into_hook(self) -> Option<Hook> {
+        if self.argv.first().is_none_or(String::is_empty) {
+            return None;
+        }
+        let name = self.name.unwrap_or_else(|| self.argv[0].clone());
+        let timeout = self.timeout.unwrap_or(DEFAULT_COMMAND_HOOK_TIMEOUT);
+        Some(command_hook(name, self.argv, timeout, self.fail_closed))
+    }
+}
+
+fn command_hooks(configs: Vec<CommandHookConfig>) -> Vec<Hook> {
+    configs
+        .into_iter()
+        .filter_map(CommandHookConfig::into_hook)
+        .collect()
 }
 
 #[derive(Clone)]
 pub struct Hooks {
+    session_start: Vec<Hook>,
+    session_end: Vec<Hook>,
+    before_turn: Vec<Hook>,
+    before_tool_use: Vec<Hook>,
     after_agent: Vec<Hook>,
     after_tool_use: Vec<Hook>,
 }
@@ -23,33 +67,49 @@ impl Default for Hooks {
 }
 
 // Hooks are arbitrary, user-specified functions that are deterministically
-// executed after specific events in the Codex lifecycle.
+// executed around specific events in the Codex lifecycle. `Before*` hooks may
+// intervene in the operation through their [`HookResult`].
 impl Hooks {
     pub fn new(config: HooksConfig) -> Self {
-        let after_agent = config
+        let mut after_agent: Vec<Hook> = config
             .legacy_notify_argv
             .filter(|argv| !argv.is_empty() && !argv[0].is_empty())
             .map(crate::notify_hook)
             .into_iter()
             .collect();
+        after_agent.extend(command_hooks(config.after_agent));
         Self {
+            session_start: command_hooks(config.session_start),
+            session_end: command_hooks(config.session_end),
+            before_turn: command_hooks(config.before_turn),
+            before_tool_use: command_hooks(config.before_tool_use),
             after_agent,
-            after_tool_use: Vec::new(),
+            after_tool_use: command_hooks(config.after_tool_use),
         }
     }
 
     fn hooks_for_event(&self, hook_event: &HookEvent) -> &[Hook] {
         match hook_event {
+            HookEvent::SessionStart { .. } => &self.session_start,
+            HookEvent::SessionEnd { .. } => &self.session_end,
+            HookEvent::BeforeTurn { .. } => &self.before_turn,
+            HookEvent::BeforeToolUse { .. } => &self.before_tool_use,
             HookEvent::AfterAgent { .. } => &self.after_agent,
             HookEvent::AfterToolUse { .. } => &self.after_tool_use,
         }
     }
 
-    pub async fn dispatch(&self, hook_payload: HookPayload) -> Vec<HookResponse> {
+    pub async fn dispatch(&self, mut hook_payload: HookPayload) -> Vec<HookResponse> {
         let hooks = self.hooks_for_event(&hook_payload.hook_event);
         let mut outcomes = Vec::with_capacity(hooks.len());
         for hook in hooks {
             let outcome = hook.execute(&hook_payload).await;
+            // Later hooks see the input as rewritten by earlier ones.
+            if let (HookResult::ModifyToolInput(tool_input), HookEvent::BeforeToolUse { event }) =
+                (&outcome.result, &mut hook_payload.hook_event)
+            {
+                event.tool_input = tool_input.clone();
+            }
             let should_abort_operation = outcome.result.should_abort_operation();
             outcomes.push(outcome);
             if should_abort_operation {
@@ -93,7 +153,7 @@ mod tests {
     use super::*;
     use crate::types::HookEventAfterAgent;
     use crate::types::HookEventAfterToolUse;
-    use crate::types::HookResult;
+    use crate::types::HookEventBeforeToolUse;
     use crate::types::HookToolInput;
     use crate::types::HookToolKind;
 
@@ -231,6 +291,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec![]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -238,6 +299,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -245,6 +307,7 @@ mod tests {
         assert_eq!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .len(),
@@ -367,6 +430,141 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    fn before_tool_use_payload(arguments: &str) -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from(CWD),
+            client: None,
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-1".to_string(),
+                    call_id: "call-1".to_string(),
+                    tool_name: "shell".to_string(),
+                    tool_kind: HookToolKind::Function,
+                    tool_input: HookToolInput::Function {
+                        arguments: arguments.to_string(),
+                    },
+                    mutating: true,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "danger-full-access".to_string(),
+                },
+            },
+        }
+    }
+
+    fn static_result_hook(name: &str, result: fn() -> HookResult) -> Hook {
+        Hook {
+            name: name.to_string(),
+            func: Arc::new(move |_| Box::pin(async move { result() })),
+        }
+    }
+
+    #[test]
+    fn hooks_new_registers_command_hooks_with_program_name() {
+        let hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+            before_tool_use: vec![
+                CommandHookConfig {
+                    name: None,
+                    argv: vec!["org-policy".to_string(), "--strict".to_string()],
+                    timeout: None,
+                    fail_closed: false,
+                },
+                CommandHookConfig {
+                    name: Some("empty".to_string()),
+                    argv: Vec::new(),
+                    timeout: None,
+                    fail_closed: false,
+                },
+            ],
+            after_agent: vec![CommandHookConfig {
+                name: Some("audit".to_string()),
+                argv: vec!["audit-log".to_string()],
+                timeout: Some(Duration::from_secs(1)),
+                fail_closed: false,
+            }],
+            ..HooksConfig::default()
+        });
+
+        let names = |hooks: &[Hook]| {
+            hooks
+                .iter()
+                .map(|hook| hook.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&hooks.before_tool_use), vec!["org-policy"]);
+        assert_eq!(names(&hooks.after_agent), vec!["legacy_notify", "audit"]);
+        assert!(hooks.before_turn.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dispatch_before_tool_use_passes_modified_input_to_later_hooks() {
+        let seen = Arc::new(std::sync::Mutex::new(None));
+        let seen_by_hook = Arc::clone(&seen);
+        let hooks = Hooks {
+            before_tool_use: vec![
+                static_result_hook("rewrite", || {
+                    HookResult::ModifyToolInput(HookToolInput::Function {
+                        arguments: "{\"command\":[\"ls\"]}".to_string(),
+                    })
+                }),
+                Hook {
+                    name: "observe".to_string(),
+                    func: Arc::new(move |payload: &HookPayload| {
+                        let seen = Arc::clone(&seen_by_hook);
+                        let tool_input = match &payload.hook_event {
+                            HookEvent::BeforeToolUse { event } => Some(event.tool_input.clone()),
+                            _ => None,
+                        };
+                        Box::pin(async move {
+                            *seen.lock().expect("lock") = tool_input;
+                            HookResult::Success
+                        })
+                    }),
+                },
+            ],
+            ..Hooks::default()
+        };
+
+        let outcomes = hooks
+            .dispatch(before_tool_use_payload("{\"command\":[\"rm\"]}"))
+            .await;
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes[0].result, HookResult::ModifyToolInput(_)));
+        assert_eq!(
+            *seen.lock().expect("lock"),
+            Some(HookToolInput::Function {
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_stops_when_hook_denies() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![
+                static_result_hook("deny", || HookResult::Deny {
+                    reason: "blocked".to_string(),
+                }),
+                counting_success_hook(&calls, "counting"),
+            ],
+            ..Hooks::default()
+        };
+
+        let outcomes = hooks.dispatch(before_tool_use_payload("{}")).await;
+        assert_eq!(outcomes.len(), 1);
+        assert!(matches!(
+            &outcomes[0].result,
+            HookResult::Deny { reason } if reason == "blocked"
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
     #[cfg(not(windows))]
     #[tokio::test]
     async fn hook_executes_program_with_payload_argument_unix() -> Result<()> {
@@ -7,6 +7,7 @@ use chrono::Utc;
 use codex_protocol::ThreadId;
 use codex_protocol::models::SandboxPermissions;
 use futures::future::BoxFuture;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
 
@@ -22,11 +23,20 @@ pub enum HookResult {
     /// FailedAbort: hook failed, other subsequent hooks should not execute, and the operation
     /// should be aborted.
     FailedAbort(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// Deny: hook blocked the operation. Subsequent hooks do not execute and the reason is
+    /// surfaced to the model (for tool calls) or the user (for turns).
+    Deny { reason: String },
+    /// ModifyToolInput: hook rewrote the tool input. Subsequent hooks observe the rewritten
+    /// input, and the tool executes with it. Only meaningful for `BeforeToolUse`.
+    ModifyToolInput(HookToolInput),
+    /// RequireApproval: hook requests an explicit user approval before the tool executes,
+    /// regardless of the session approval policy. Only meaningful for `BeforeToolUse`.
+    RequireApproval { reason: Option<String> },
 }
 
 impl HookResult {
     pub fn should_abort_operation(&self) -> bool {
-        matches!(self, Self::FailedAbort(_))
+        matches!(self, Self::FailedAbort(_) | Self::Deny { .. })
     }
 }
 
@@ -81,6 +91,27 @@ pub struct HookEventAfterAgent {
     pub last_assistant_message: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionStart {
+    pub thread_id: ThreadId,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionEnd {
+    pub thread_id: ThreadId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeTurn {
+    pub thread_id: ThreadId,
+    pub turn_id: String,
+    pub input_messages: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum HookToolKind {
@@ -90,7 +121,7 @@ pub enum HookToolKind {
     Mcp,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookToolInputLocalShell {
     pub command: Vec<String>,
@@ -101,7 +132,7 @@ pub struct HookToolInputLocalShell {
     pub justification: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "input_type", rename_all = "snake_case")]
 pub enum HookToolInput {
     Function {
@@ -120,6 +151,19 @@ pub enum HookToolInput {
     },
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeToolUse {
+    pub turn_id: String,
+    pub call_id: String,
+    pub tool_name: String,
+    pub tool_kind: HookToolKind,
+    pub tool_input: HookToolInput,
+    pub mutating: bool,
+    pub sandbox: String,
+    pub sandbox_policy: String,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterToolUse {
@@ -147,6 +191,22 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum HookEvent {
+    SessionStart {
+        #[serde(flatten)]
+        event: HookEventSessionStart,
+    },
+    SessionEnd {
+        #[serde(flatten)]
+        event: HookEventSessionEnd,
+    },
+    BeforeTurn {
+        #[serde(flatten)]
+        event: HookEventBeforeTurn,
+    },
+    BeforeToolUse {
+        #[serde(flatten)]
+        event: HookEventBeforeToolUse,
+    },
     AfterAgent {
         #[serde(flatten)]
         event: HookEventAfterAgent,
@@ -171,6 +231,7 @@ mod tests {
     use super::HookEvent;
     use super::HookEventAfterAgent;
     use super::HookEventAfterToolUse;
+    use super::HookEventBeforeToolUse;
     use super::HookPayload;
     use super::HookToolInput;
     use super::HookToolInputLocalShell;
@@ -287,4 +348,83 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn before_tool_use_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            client: Some("codex-tui".to_string()),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-3".to_string(),
+                    call_id: "call-2".to_string(),
+                    tool_name: "mcp__docs__search".to_string(),
+                    tool_kind: HookToolKind::Mcp,
+                    tool_input: HookToolInput::Mcp {
+                        server: "docs".to_string(),
+                        tool: "search".to_string(),
+                        arguments: "{\"q\":\"hooks\"}".to_string(),
+                    },
+                    mutating: false,
+                    sandbox: "none".to_string(),
+                    sandbox_policy: "read-only".to_string(),
+                },
+            },
+        };
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "client": "codex-tui",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "before_tool_use",
+                "turn_id": "turn-3",
+                "call_id": "call-2",
+                "tool_name": "mcp__docs__search",
+                "tool_kind": "mcp",
+                "tool_input": {
+                    "input_type": "mcp",
+                    "server": "docs",
+                    "tool": "search",
+                    "arguments": "{\"q\":\"hooks\"}",
+                },
+                "mutating": false,
+                "sandbox": "none",
+                "sandbox_policy": "read-only",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn tool_input_deserializes_with_optional_shell_fields_omitted() {
+        let actual: HookToolInput = serde_json::from_value(json!({
+            "input_type": "local_shell",
+            "params": { "command": ["git", "status"] },
+        }))
+        .expect("deserialize tool input");
+
+        assert_eq!(
+            actual,
+            HookToolInput::LocalShell {
+                params: HookToolInputLocalShell {
+                    command: vec!["git".to_string(), "status".to_string()],
+                    workdir: None,
+                    timeout_ms: None,
+                    sandbox_permissions: None,
+                    prefix_rule: None,
+                    justification: None,
+                },
+            }
+        );
+    }
 }
@@ -2,9 +2,11 @@
 //!
 //! This module mirrors the semantics used by the macOS Seatbelt sandbox:
 //! - the filesystem is read-only by default,
-//! - explicit writable roots are layered on top, and
+//! - explicit writable roots are layered on top,
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
-//!   their parent root is writable.
+//!   their parent root is writable, and
+//! - an optional [`FileSystemWriteScope`] adds read-only globs and deny-read
+//!   paths on top of the policy.
 //!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
@@ -15,8 +17,10 @@ use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
+use codex_protocol::permissions::FileSystemWriteScope;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::WritableRoot;
+use globset::GlobBuilder;
 
 /// Linux "platform defaults" that keep common system binaries and dynamic
 /// libraries readable when `ReadOnlyAccess::Restricted` requests them.
@@ -34,6 +38,12 @@ const LINUX_PLATFORM_DEFAULT_READ_ROOTS: &[&str] = &[
     "/run/current-system/sw",
 ];
 
+/// Upper bound on directory entries visited while expanding write-scope globs.
+///
+/// Globs are expanded every time the sandbox starts, so a broad `**` pattern
+/// over a large tree fails closed instead of stalling each command.
+const MAX_WRITE_SCOPE_GLOB_ENTRIES: usize = 100_000;
+
 /// Options that control how bubblewrap is invoked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct BwrapOptions {
@@ -44,6 +54,11 @@ pub(crate) struct BwrapOptions {
     pub mount_proc: bool,
     /// How networking should be configured inside the bubblewrap sandbox.
     pub network_mode: BwrapNetworkMode,
+    /// Whether the sandboxed command is killed when bubblewrap's parent exits.
+    ///
+    /// Persistent background jobs clear this so they keep running after Codex
+    /// exits.
+    pub die_with_parent: bool,
 }
 
 impl Default for BwrapOptions {
@@ -51,6 +66,7 @@ impl Default for BwrapOptions {
         Self {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            die_with_parent: true,
         }
     }
 }
@@ -82,11 +98,13 @@ impl BwrapNetworkMode {
 /// When the policy grants full disk write access and full network access, this
 /// returns `command` unchanged so we avoid unnecessary sandboxing overhead.
 /// If network isolation is requested, we still wrap with bubblewrap so network
-/// namespace restrictions apply while preserving full filesystem access.
+/// namespace restrictions apply while preserving full filesystem access. The
+/// write scope only applies to policies with explicit writable roots.
 pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    write_scope: &FileSystemWriteScope,
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
@@ -97,13 +115,15 @@ pub(crate) fn create_bwrap_command_args(
         };
     }
 
-    create_bwrap_flags(command, sandbox_policy, cwd, options)
+    create_bwrap_flags(command, sandbox_policy, cwd, write_scope, options)
 }
 
 fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOptions) -> Vec<String> {
-    let mut args = vec![
-        "--new-session".to_string(),
-        "--die-with-parent".to_string(),
+    let mut args = vec!["--new-session".to_string()];
+    if options.die_with_parent {
+        args.push("--die-with-parent".to_string());
+    }
+    args.extend([
         "--bind".to_string(),
         "/".to_string(),
         "/".to_string(),
@@ -111,7 +131,7 @@ fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOption
         // not need ambient CAP_SYS_ADMIN to create the remaining namespaces.
         "--unshare-user".to_string(),
         "--unshare-pid".to_string(),
-    ];
+    ]);
     if options.network_mode.should_unshare_network() {
         args.push("--unshare-net".to_string());
     }
@@ -129,12 +149,15 @@ fn create_bwrap_flags(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    write_scope: &FileSystemWriteScope,
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
-    args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    if options.die_with_parent {
+        args.push("--die-with-parent".to_string());
+    }
+    args.extend(create_filesystem_args(sandbox_policy, cwd, write_scope)?);
     // Request a user namespace explicitly rather than relying on bubblewrap's
     // auto-enable behavior, which is skipped when the caller runs as uid 0.
     args.push("--unshare-user".to_string());
@@ -164,7 +187,14 @@ fn create_bwrap_flags(
 ///    writable subpaths under `/dev` (for example, `/dev/shm`).
 /// 4. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+/// 5. Write-scope read-only globs are expanded and mounted the same way, then
+///    deny-read paths are hidden behind an empty tmpfs (directories) or
+///    `/dev/null` (files).
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    write_scope: &FileSystemWriteScope,
+) -> Result<Vec<String>> {
     let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
     ensure_mount_targets_exist(&writable_roots)?;
 
@@ -243,35 +273,173 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         .collect();
 
     for subpath in collect_read_only_subpaths(&writable_roots) {
-        if let Some(symlink_path) = find_symlink_in_path(&subpath, &allowed_write_paths) {
+        push_read_only_subpath_args(&mut args, &subpath, &allowed_write_paths);
+    }
+
+    for subpath in expand_read_only_globs(&write_scope.read_only, &allowed_write_paths)? {
+        push_read_only_subpath_args(&mut args, &subpath, &allowed_write_paths);
+    }
+
+    for path in &write_scope.deny_read {
+        push_deny_read_args(&mut args, path.as_path());
+    }
+
+    Ok(args)
+}
+
+/// Mount `subpath` read-only when it lives under a writable root.
+fn push_read_only_subpath_args(
+    args: &mut Vec<String>,
+    subpath: &Path,
+    allowed_write_paths: &[PathBuf],
+) {
+    if let Some(symlink_path) = find_symlink_in_path(subpath, allowed_write_paths) {
+        args.push("--ro-bind".to_string());
+        args.push("/dev/null".to_string());
+        args.push(path_to_string(&symlink_path));
+        return;
+    }
+
+    if !subpath.exists() {
+        // Each protected subpath can have a different first missing component
+        // that must be blocked independently (for example, `/repo/.git` vs
+        // `/repo/.codex`).
+        if let Some(first_missing_component) = find_first_non_existent_component(subpath)
+            && is_within_allowed_write_paths(&first_missing_component, allowed_write_paths)
+        {
             args.push("--ro-bind".to_string());
             args.push("/dev/null".to_string());
-            args.push(path_to_string(&symlink_path));
-            continue;
+            args.push(path_to_string(&first_missing_component));
         }
+        return;
+    }
 
-        if !subpath.exists() {
-            // Keep this in the per-subpath loop: each protected subpath can have
-            // a different first missing component that must be blocked
-            // independently (for example, `/repo/.git` vs `/repo/.codex`).
-            if let Some(first_missing_component) = find_first_non_existent_component(&subpath)
-                && is_within_allowed_write_paths(&first_missing_component, &allowed_write_paths)
-            {
-                args.push("--ro-bind".to_string());
-                args.push("/dev/null".to_string());
-                args.push(path_to_string(&first_missing_component));
+    if is_within_allowed_write_paths(subpath, allowed_write_paths) {
+        args.push("--ro-bind".to_string());
+        args.push(path_to_string(subpath));
+        args.push(path_to_string(subpath));
+    }
+}
+
+/// Hide `path` from the sandboxed process. Directories are replaced by an
+/// empty read-only tmpfs and files by `/dev/null`; missing paths are skipped.
+fn push_deny_read_args(args: &mut Vec<String>, path: &Path) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    let path = path_to_string(path);
+    if metadata.is_dir() {
+        args.push("--tmpfs".to_string());
+        args.push(path.clone());
+        args.push("--remount-ro".to_string());
+        args.push(path);
+    } else {
+        args.push("--ro-bind".to_string());
+        args.push("/dev/null".to_string());
+        args.push(path);
+    }
+}
+
+/// Expand write-scope read-only patterns into concrete paths.
+///
+/// Relative patterns are anchored at every writable root. Patterns without
+/// glob metacharacters are returned as-is so missing paths stay protected;
+/// glob patterns only match paths that exist when the sandbox starts.
+fn expand_read_only_globs(
+    patterns: &[String],
+    allowed_write_paths: &[PathBuf],
+) -> Result<BTreeSet<PathBuf>> {
+    let mut paths = BTreeSet::new();
+    for pattern in patterns {
+        let anchored_patterns = if Path::new(pattern).is_absolute() {
+            vec![split_literal_prefix(pattern)]
+        } else {
+            allowed_write_paths
+                .iter()
+                .map(|root| (root.clone(), pattern.clone()))
+                .collect()
+        };
+        for (base, relative_pattern) in anchored_patterns {
+            if contains_glob_meta(&relative_pattern) {
+                paths.extend(expand_glob(&base, &relative_pattern)?);
+            } else {
+                paths.insert(base.join(relative_pattern));
             }
-            continue;
         }
+    }
+    Ok(paths)
+}
 
-        if is_within_allowed_write_paths(&subpath, &allowed_write_paths) {
-            args.push("--ro-bind".to_string());
-            args.push(path_to_string(&subpath));
-            args.push(path_to_string(&subpath));
+fn contains_glob_meta(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Split an absolute pattern into its longest glob-free directory prefix and
+/// the remaining pattern relative to it.
+fn split_literal_prefix(pattern: &str) -> (PathBuf, String) {
+    let mut base = PathBuf::new();
+    let mut rest = Vec::new();
+    for component in Path::new(pattern).components() {
+        let component_str = component.as_os_str().to_string_lossy();
+        if rest.is_empty() && !contains_glob_meta(&component_str) {
+            base.push(component);
+        } else {
+            rest.push(component_str.into_owned());
         }
     }
+    (base, rest.join("/"))
+}
 
-    Ok(args)
+/// Walk the filesystem below `base` and return the entries matching
+/// `relative_pattern`. `*` does not cross `/`, and symlinks are not followed.
+/// Matched directories are not descended into because the whole subtree is
+/// already covered by the read-only mount.
+fn expand_glob(base: &Path, relative_pattern: &str) -> Result<Vec<PathBuf>> {
+    let matcher = GlobBuilder::new(relative_pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|err| {
+            CodexErr::UnsupportedOperation(format!(
+                "Invalid sandbox write-scope glob {relative_pattern}: {err}"
+            ))
+        })?
+        .compile_matcher();
+    let max_depth = if relative_pattern.contains("**") {
+        usize::MAX
+    } else {
+        relative_pattern.split('/').count()
+    };
+
+    let mut matches = Vec::new();
+    let mut visited = 0;
+    let mut pending = vec![(base.to_path_buf(), 1)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > MAX_WRITE_SCOPE_GLOB_ENTRIES {
+                return Err(CodexErr::UnsupportedOperation(format!(
+                    "Sandbox write-scope glob {relative_pattern} under {base} visited more than {MAX_WRITE_SCOPE_GLOB_ENTRIES} entries; use a narrower pattern.",
+                    base = base.display()
+                )));
+            }
+            let path = entry.path();
+            if path
+                .strip_prefix(base)
+                .is_ok_and(|relative_path| matcher.is_match(relative_path))
+            {
+                matches.push(path);
+                continue;
+            }
+            let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+            if is_dir && depth < max_depth {
+                pending.push((path, depth + 1));
+            }
+        }
+    }
+    Ok(matches)
 }
 
 /// Collect unique read-only subpaths across all writable roots.
@@ -399,9 +567,11 @@ mod tests {
             command.clone(),
             &SandboxPolicy::DangerFullAccess,
             Path::new("/"),
+            &FileSystemWriteScope::default(),
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
+                die_with_parent: true,
             },
         )
         .expect("create bwrap args");
@@ -416,9 +586,11 @@ mod tests {
             command,
             &SandboxPolicy::DangerFullAccess,
             Path::new("/"),
+            &FileSystemWriteScope::default(),
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
+                die_with_parent: true,
             },
         )
         .expect("create bwrap args");
@@ -442,6 +614,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn die_with_parent_is_omitted_for_commands_that_outlive_codex() {
+        let options = BwrapOptions {
+            die_with_parent: false,
+            ..Default::default()
+        };
+        let restricted = create_bwrap_command_args(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/"),
+            &FileSystemWriteScope::default(),
+            options,
+        )
+        .expect("create bwrap args");
+        let full_filesystem = create_bwrap_command_args(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::DangerFullAccess,
+            Path::new("/"),
+            &FileSystemWriteScope::default(),
+            BwrapOptions {
+                network_mode: BwrapNetworkMode::Isolated,
+                ..options
+            },
+        )
+        .expect("create bwrap args");
+
+        for args in [restricted, full_filesystem] {
+            assert_eq!(args.first().map(String::as_str), Some("--new-session"));
+            assert!(!args.contains(&"--die-with-parent".to_string()));
+        }
+    }
+
     #[test]
     fn mounts_dev_before_writable_dev_binds() {
         let sandbox_policy = SandboxPolicy::WorkspaceWrite {
@@ -452,7 +656,12 @@ mod tests {
             exclude_slash_tmp: true,
         };
 
-        let args = create_filesystem_args(&sandbox_policy, Path::new("/")).expect("bwrap fs args");
+        let args = create_filesystem_args(
+            &sandbox_policy,
+            Path::new("/"),
+            &FileSystemWriteScope::default(),
+        )
+        .expect("bwrap fs args");
         assert_eq!(
             args,
             vec![
@@ -488,7 +697,9 @@ mod tests {
             network_access: false,
         };
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), &FileSystemWriteScope::default())
+                .expect("filesystem args");
 
         assert_eq!(args[0..4], ["--tmpfs", "/", "--dev", "/dev"]);
 
@@ -503,6 +714,104 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn write_scope_globs_stay_read_only_inside_writable_roots() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path().canonicalize().expect("canonical temp dir");
+        std::fs::create_dir_all(root.join(".git/hooks")).expect("create hooks dir");
+        std::fs::create_dir_all(root.join("app")).expect("create app dir");
+        std::fs::write(root.join(".env"), "").expect("write .env");
+        std::fs::write(root.join("app/.env.local"), "").expect("write nested .env");
+        std::fs::write(root.join("app/main.rs"), "").expect("write main.rs");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        let write_scope = FileSystemWriteScope {
+            read_only: vec![
+                ".git/hooks".to_string(),
+                "**/.env*".to_string(),
+                "Cargo.lock".to_string(),
+            ],
+            deny_read: Vec::new(),
+        };
+
+        let args = create_filesystem_args(&policy, &root, &write_scope).expect("filesystem args");
+
+        let ro_bind = |path: &Path| {
+            let path = path_to_string(path);
+            args.windows(3)
+                .any(|window| window == ["--ro-bind", path.as_str(), path.as_str()])
+        };
+        assert!(ro_bind(&root.join(".git/hooks")));
+        assert!(ro_bind(&root.join(".env")));
+        assert!(ro_bind(&root.join("app/.env.local")));
+        assert!(!ro_bind(&root.join("app/main.rs")));
+        // Missing literal paths are blocked so they cannot be created.
+        let cargo_lock = path_to_string(&root.join("Cargo.lock"));
+        assert!(
+            args.windows(3)
+                .any(|window| window == ["--ro-bind", "/dev/null", cargo_lock.as_str()])
+        );
+    }
+
+    #[test]
+    fn single_star_glob_does_not_cross_directories() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path().canonicalize().expect("canonical temp dir");
+        std::fs::create_dir_all(root.join("nested")).expect("create nested dir");
+        std::fs::write(root.join("top.lock"), "").expect("write top.lock");
+        std::fs::write(root.join("nested/inner.lock"), "").expect("write inner.lock");
+
+        let paths = expand_read_only_globs(&["*.lock".to_string()], std::slice::from_ref(&root))
+            .expect("expand globs");
+
+        assert_eq!(paths, BTreeSet::from([root.join("top.lock")]));
+    }
+
+    #[test]
+    fn deny_read_hides_directories_and_files() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path().canonicalize().expect("canonical temp dir");
+        let ssh_dir = root.join(".ssh");
+        let credentials = root.join("credentials");
+        std::fs::create_dir(&ssh_dir).expect("create .ssh");
+        std::fs::write(&credentials, "secret").expect("write credentials");
+        let write_scope = FileSystemWriteScope {
+            read_only: Vec::new(),
+            deny_read: vec![
+                AbsolutePathBuf::try_from(ssh_dir.as_path()).expect("absolute .ssh"),
+                AbsolutePathBuf::try_from(credentials.as_path()).expect("absolute credentials"),
+                AbsolutePathBuf::try_from(root.join("missing").as_path()).expect("absolute path"),
+            ],
+        };
+
+        let args = create_filesystem_args(
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/"),
+            &write_scope,
+        )
+        .expect("filesystem args");
+
+        let ssh_dir = path_to_string(&ssh_dir);
+        let credentials = path_to_string(&credentials);
+        assert_eq!(
+            args[args.len() - 7..],
+            [
+                "--tmpfs",
+                ssh_dir.as_str(),
+                "--remount-ro",
+                ssh_dir.as_str(),
+                "--ro-bind",
+                "/dev/null",
+                credentials.as_str(),
+            ]
+        );
+    }
+
     #[test]
     fn restricted_read_only_with_platform_defaults_includes_usr_when_present() {
         let temp_dir = TempDir::new().expect("temp dir");
@@ -517,7 +826,9 @@ mod tests {
         // `ReadOnlyAccess::Restricted` always includes `cwd` as a readable
         // root. Using `"/"` here would intentionally collapse to broad read
         // access, so use a non-root cwd to exercise the restricted path.
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), &FileSystemWriteScope::default())
+                .expect("filesystem args");
 
         assert!(args.starts_with(&["--tmpfs".to_string(), "/".to_string()]));
 
@@ -1,13 +1,20 @@
 //! In-process Linux sandbox primitives: `no_new_privs` and seccomp.
 //!
 //! Filesystem restrictions are enforced by bubblewrap in `linux_run_main`.
-//! Landlock helpers remain available here as legacy/backup utilities.
+//! Landlock helpers remain available here as legacy/backup utilities, and
+//! Landlock also enforces the `no-process-spawn-outside-allowlist` profile.
 use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
 use codex_core::error::SandboxErr;
+use codex_protocol::permissions::SeccompProfile;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
@@ -34,8 +41,11 @@ use seccompiler::apply_filter;
 /// them, not the entire CLI process.
 ///
 /// This function is responsible for:
-/// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply, and
-/// - installing the network seccomp filter when network access is disabled.
+/// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply,
+/// - installing the network seccomp filter when network access is disabled, and
+/// - installing the filters for the requested named seccomp profiles, and
+/// - restricting `execve` to `spawn_allowlist` for
+///   `no-process-spawn-outside-allowlist`.
 ///
 /// Filesystem restrictions are intentionally handled by bubblewrap.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
@@ -44,6 +54,8 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     apply_landlock_fs: bool,
     allow_network_for_proxy: bool,
     proxy_routed_network: bool,
+    seccomp_profiles: &[SeccompProfile],
+    spawn_allowlist: &[PathBuf],
 ) -> Result<()> {
     let network_seccomp_mode = network_seccomp_mode(
         sandbox_policy,
@@ -56,6 +68,7 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     // we avoid this unless we need seccomp or we are explicitly using the
     // legacy Landlock filesystem pipeline.
     if network_seccomp_mode.is_some()
+        || !seccomp_profiles.is_empty()
         || (apply_landlock_fs && !sandbox_policy.has_full_disk_write_access())
     {
         set_no_new_privs()?;
@@ -65,6 +78,12 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
         install_network_seccomp_filter_on_current_thread(mode)?;
     }
 
+    install_seccomp_profiles_on_current_thread(seccomp_profiles)?;
+
+    if seccomp_profiles.contains(&SeccompProfile::NoProcessSpawnOutsideAllowlist) {
+        install_spawn_allowlist_on_current_thread(spawn_allowlist)?;
+    }
+
     if apply_landlock_fs && !sandbox_policy.has_full_disk_write_access() {
         if !sandbox_policy.has_full_disk_read_access() {
             return Err(CodexErr::UnsupportedOperation(
@@ -113,6 +132,24 @@ fn network_seccomp_mode(
     }
 }
 
+/// Human-readable summary of the network seccomp filter that
+/// [`apply_sandbox_policy_to_current_thread`] would install.
+pub(crate) fn describe_network_seccomp(
+    sandbox_policy: &SandboxPolicy,
+    allow_network_for_proxy: bool,
+    proxy_routed_network: bool,
+) -> &'static str {
+    match network_seccomp_mode(
+        sandbox_policy,
+        allow_network_for_proxy,
+        proxy_routed_network,
+    ) {
+        None => "none (full network access)",
+        Some(NetworkSeccompMode::Restricted) => "restricted network",
+        Some(NetworkSeccompMode::ProxyRouted) => "proxy-routed network",
+    }
+}
+
 /// Enable `PR_SET_NO_NEW_PRIVS` so seccomp can be applied safely.
 fn set_no_new_privs() -> Result<()> {
     let result = unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) };
@@ -159,6 +196,170 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Restricts `execve` to `allowlist` with a Landlock ruleset that handles only
+/// the execute right, for `no-process-spawn-outside-allowlist`.
+///
+/// The kernel also checks the execute right on the ELF and `#!` interpreters
+/// it loads, so those of every allowlisted program are allowed too. Missing
+/// entries are skipped.
+fn install_spawn_allowlist_on_current_thread(allowlist: &[PathBuf]) -> Result<()> {
+    let programs = allowlist
+        .iter()
+        .flat_map(|program| {
+            let mut programs = vec![program.clone()];
+            programs.extend(exec_interpreters(program));
+            programs
+        })
+        .filter(|program| program.exists())
+        .collect::<Vec<_>>();
+
+    let status = Ruleset::default()
+        .set_compatibility(CompatLevel::BestEffort)
+        .handle_access(AccessFs::Execute)?
+        .create()?
+        .add_rules(landlock::path_beneath_rules(&programs, AccessFs::Execute))?
+        .set_no_new_privs(true)
+        .restrict_self()?;
+
+    if status.ruleset == landlock::RulesetStatus::NotEnforced {
+        return Err(CodexErr::Sandbox(SandboxErr::LandlockRestrict));
+    }
+
+    Ok(())
+}
+
+/// Adds the program `command` runs to `allowlist`, resolved through `PATH`
+/// the way `execvp` does.
+pub(crate) fn spawn_allowlist_for_command(
+    mut allowlist: Vec<PathBuf>,
+    command: &[String],
+) -> Vec<PathBuf> {
+    let Some(program) = command.first() else {
+        return allowlist;
+    };
+    let resolved = if program.contains('/') {
+        Some(PathBuf::from(program))
+    } else {
+        std::env::var_os("PATH").and_then(|path| {
+            std::env::split_paths(&path)
+                .map(|dir| dir.join(program))
+                .find(|candidate| candidate.is_file())
+        })
+    };
+    allowlist.extend(resolved);
+    allowlist
+}
+
+/// Returns the interpreters the kernel opens to execute `program`: the
+/// `PT_INTERP` loader of an ELF file, or the `#!` interpreter of a script
+/// along with its loader.
+fn exec_interpreters(program: &Path) -> Vec<PathBuf> {
+    let Ok(mut file) = File::open(program) else {
+        return Vec::new();
+    };
+    let mut header = [0u8; 256];
+    let Ok(len) = file.read(&mut header) else {
+        return Vec::new();
+    };
+    let header = &header[..len];
+    if let Some(script) = header.strip_prefix(b"#!") {
+        let line = script
+            .split(|byte| *byte == b'\n')
+            .next()
+            .unwrap_or_default();
+        let Some(interpreter) = String::from_utf8_lossy(line)
+            .split_whitespace()
+            .next()
+            .map(PathBuf::from)
+        else {
+            return Vec::new();
+        };
+        let mut interpreters = elf_interpreter(&interpreter)
+            .into_iter()
+            .collect::<Vec<_>>();
+        interpreters.insert(0, interpreter);
+        return interpreters;
+    }
+    elf_interpreter(program).into_iter().collect()
+}
+
+/// Reads the `PT_INTERP` program header of the ELF file at `path`.
+fn elf_interpreter(path: &Path) -> Option<PathBuf> {
+    const PT_INTERP: u32 = 3;
+    let mut file = File::open(path).ok()?;
+    // ELF32 headers are 52 bytes long, ELF64 headers 64.
+    let mut elf_header = [0u8; 64];
+    file.read_exact(&mut elf_header[..52]).ok()?;
+    if &elf_header[..4] != b"\x7fELF" {
+        return None;
+    }
+    let is_64 = match elf_header[4] {
+        1 => false,
+        2 => {
+            file.read_exact(&mut elf_header[52..]).ok()?;
+            true
+        }
+        _ => return None,
+    };
+    let little_endian = match elf_header[5] {
+        1 => true,
+        2 => false,
+        _ => return None,
+    };
+    let read = |bytes: &[u8]| -> u64 {
+        let push = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+        if little_endian {
+            bytes.iter().rev().fold(0, push)
+        } else {
+            bytes.iter().fold(0, push)
+        }
+    };
+    let (phoff, phentsize, phnum) = if is_64 {
+        (
+            read(&elf_header[32..40]),
+            read(&elf_header[54..56]),
+            read(&elf_header[56..58]),
+        )
+    } else {
+        (
+            read(&elf_header[28..32]),
+            read(&elf_header[42..44]),
+            read(&elf_header[44..46]),
+        )
+    };
+    let mut program_header = vec![0u8; usize::try_from(phentsize).ok()?];
+    if program_header.len() < if is_64 { 40 } else { 20 } {
+        return None;
+    }
+    for index in 0..phnum {
+        file.seek(SeekFrom::Start(phoff.checked_add(index * phentsize)?))
+            .ok()?;
+        file.read_exact(&mut program_header).ok()?;
+        if read(&program_header[..4]) != u64::from(PT_INTERP) {
+            continue;
+        }
+        let (offset, size) = if is_64 {
+            (read(&program_header[8..16]), read(&program_header[32..40]))
+        } else {
+            (read(&program_header[4..8]), read(&program_header[16..20]))
+        };
+        if size > 4096 {
+            return None;
+        }
+        let mut interpreter = vec![0u8; usize::try_from(size).ok()?];
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        file.read_exact(&mut interpreter).ok()?;
+        let end = interpreter
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(interpreter.len());
+        return Some(PathBuf::from(
+            String::from_utf8_lossy(&interpreter[..end]).as_ref(),
+        ));
+    }
+    None
+}
+
 /// Installs a seccomp filter for Linux network sandboxing.
 ///
 /// The filter is applied to the current thread so only the sandboxed child
@@ -241,10 +442,95 @@ fn install_network_seccomp_filter_on_current_thread(
         }
     }
 
+    // When a rule matches, return EPERM.
+    apply_seccomp_rules(rules, SeccompAction::Errno(libc::EPERM as u32))
+}
+
+/// Installs the filters for the named seccomp profiles.
+///
+/// A blocked syscall kills the whole process with `SIGSYS` rather than
+/// failing with `EPERM`, so codex can report which profile stopped the
+/// command instead of surfacing an opaque error from deep inside it.
+fn install_seccomp_profiles_on_current_thread(
+    profiles: &[SeccompProfile],
+) -> std::result::Result<(), SandboxErr> {
+    let rules = seccomp_profile_rules(profiles)?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+    apply_seccomp_rules(rules, SeccompAction::KillProcess)?;
+
+    if profiles.contains(&SeccompProfile::NoProcessSpawn) {
+        // `clone3` passes its flags in a struct that seccomp cannot inspect.
+        // Fail it with ENOSYS so libc falls back to `clone`, where threads can
+        // be told apart from new processes.
+        let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
+        rules.insert(libc::SYS_clone3, vec![]);
+        apply_seccomp_rules(rules, SeccompAction::Errno(libc::ENOSYS as u32))?;
+    }
+    Ok(())
+}
+
+fn seccomp_profile_rules(
+    profiles: &[SeccompProfile],
+) -> std::result::Result<BTreeMap<i64, Vec<SeccompRule>>, SandboxErr> {
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
+    for profile in profiles {
+        let syscalls: &[i64] = match profile {
+            SeccompProfile::NoPtrace => &[
+                libc::SYS_ptrace,
+                libc::SYS_process_vm_readv,
+                libc::SYS_process_vm_writev,
+                libc::SYS_pidfd_getfd,
+            ],
+            SeccompProfile::NoMount => &[
+                libc::SYS_mount,
+                libc::SYS_umount2,
+                libc::SYS_pivot_root,
+                libc::SYS_open_tree,
+                libc::SYS_move_mount,
+                libc::SYS_fsopen,
+                libc::SYS_fsconfig,
+                libc::SYS_fsmount,
+                libc::SYS_fspick,
+                libc::SYS_mount_setattr,
+            ],
+            SeccompProfile::NoProcessSpawn => {
+                // Threads are created with `CLONE_THREAD`; any other `clone`
+                // starts a new process.
+                let new_process = SeccompRule::new(vec![SeccompCondition::new(
+                    0, // first argument (flags)
+                    SeccompCmpArgLen::Qword,
+                    SeccompCmpOp::MaskedEq(libc::CLONE_THREAD as u64),
+                    0,
+                )?])?;
+                rules.insert(libc::SYS_clone, vec![new_process]);
+                #[cfg(target_arch = "x86_64")]
+                {
+                    rules.insert(libc::SYS_fork, vec![]);
+                    rules.insert(libc::SYS_vfork, vec![]);
+                }
+                &[]
+            }
+            // Enforced with Landlock, since seccomp cannot read the path
+            // passed to `execve`.
+            SeccompProfile::NoProcessSpawnOutsideAllowlist => &[],
+        };
+        for syscall in syscalls {
+            rules.insert(*syscall, vec![]); // empty rule vec = unconditional match
+        }
+    }
+    Ok(rules)
+}
+
+fn apply_seccomp_rules(
+    rules: BTreeMap<i64, Vec<SeccompRule>>,
+    match_action: SeccompAction,
+) -> std::result::Result<(), SandboxErr> {
     let filter = SeccompFilter::new(
         rules,
-        SeccompAction::Allow,                     // default – allow
-        SeccompAction::Errno(libc::EPERM as u32), // when rule matches – return EPERM
+        SeccompAction::Allow, // default – allow
+        match_action,
         if cfg!(target_arch = "x86_64") {
             TargetArch::x86_64
         } else if cfg!(target_arch = "aarch64") {
@@ -264,10 +550,16 @@ fn install_network_seccomp_filter_on_current_thread(
 #[cfg(test)]
 mod tests {
     use super::NetworkSeccompMode;
+    use super::elf_interpreter;
+    use super::exec_interpreters;
     use super::network_seccomp_mode;
+    use super::seccomp_profile_rules;
     use super::should_install_network_seccomp;
+    use super::spawn_allowlist_for_command;
+    use codex_protocol::permissions::SeccompProfile;
     use codex_protocol::protocol::SandboxPolicy;
     use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
 
     #[test]
     fn managed_network_enforces_seccomp_even_for_full_network_policy() {
@@ -320,4 +612,97 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn seccomp_profiles_block_only_their_syscalls() {
+        let rules = seccomp_profile_rules(&[SeccompProfile::NoPtrace, SeccompProfile::NoMount])
+            .expect("profile rules");
+
+        assert_eq!(rules.contains_key(&libc::SYS_ptrace), true);
+        assert_eq!(rules.contains_key(&libc::SYS_mount), true);
+        assert_eq!(rules.contains_key(&libc::SYS_clone), false);
+    }
+
+    #[test]
+    fn no_process_spawn_only_restricts_clone_without_clone_thread() {
+        let rules =
+            seccomp_profile_rules(&[SeccompProfile::NoProcessSpawn]).expect("profile rules");
+
+        assert_eq!(rules.get(&libc::SYS_clone).map(Vec::len), Some(1));
+        assert_eq!(rules.contains_key(&libc::SYS_ptrace), false);
+    }
+
+    #[test]
+    fn spawn_allowlist_is_enforced_by_landlock_not_seccomp() {
+        let rules = seccomp_profile_rules(&[SeccompProfile::NoProcessSpawnOutsideAllowlist])
+            .expect("profile rules");
+
+        assert_eq!(rules.is_empty(), true);
+    }
+
+    /// A little-endian ELF64 file with a `PT_LOAD` header followed by a
+    /// `PT_INTERP` header naming `interpreter`.
+    fn elf64_with_interpreter(interpreter: &str) -> Vec<u8> {
+        let interpreter = format!("{interpreter}\0");
+        let interp_offset = 64 + 2 * 56;
+        let mut elf = vec![0u8; interp_offset];
+        elf[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        elf[32..40].copy_from_slice(&64u64.to_le_bytes());
+        elf[54..56].copy_from_slice(&56u16.to_le_bytes());
+        elf[56..58].copy_from_slice(&2u16.to_le_bytes());
+        elf[64..68].copy_from_slice(&1u32.to_le_bytes());
+        let interp_header = &mut elf[120..176];
+        interp_header[..4].copy_from_slice(&3u32.to_le_bytes());
+        interp_header[8..16].copy_from_slice(&(interp_offset as u64).to_le_bytes());
+        interp_header[32..40].copy_from_slice(&(interpreter.len() as u64).to_le_bytes());
+        elf.extend_from_slice(interpreter.as_bytes());
+        elf
+    }
+
+    #[test]
+    fn elf_interpreter_reads_pt_interp() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let program = dir.path().join("program");
+        std::fs::write(&program, elf64_with_interpreter("/lib/ld-test.so.2")).expect("write elf");
+        let script = dir.path().join("script");
+        std::fs::write(&script, "echo not an elf\n").expect("write script");
+
+        assert_eq!(
+            elf_interpreter(&program),
+            Some(PathBuf::from("/lib/ld-test.so.2"))
+        );
+        assert_eq!(elf_interpreter(&script), None);
+    }
+
+    #[test]
+    fn exec_interpreters_include_the_shebang_interpreter_and_its_loader() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let interpreter = dir.path().join("interpreter");
+        std::fs::write(&interpreter, elf64_with_interpreter("/lib/ld-test.so.2"))
+            .expect("write elf");
+        let script = dir.path().join("script");
+        std::fs::write(
+            &script,
+            format!("#!{} -e\necho hi\n", interpreter.display()),
+        )
+        .expect("write script");
+
+        assert_eq!(
+            exec_interpreters(&script),
+            vec![interpreter, PathBuf::from("/lib/ld-test.so.2")]
+        );
+    }
+
+    #[test]
+    fn spawn_allowlist_adds_the_command_program() {
+        let allowlist = vec![PathBuf::from("/usr/bin/git")];
+
+        assert_eq!(
+            spawn_allowlist_for_command(allowlist.clone(), &["./build.sh".to_string()]),
+            vec![PathBuf::from("/usr/bin/git"), PathBuf::from("./build.sh")]
+        );
+        let with_sh = spawn_allowlist_for_command(allowlist, &["sh".to_string()]);
+        assert_eq!(with_sh.len(), 2);
+        assert_eq!(with_sh[1].file_name(), Some("sh".as_ref()));
+    }
 }
@@ -10,10 +10,14 @@ use crate::bwrap::BwrapNetworkMode;
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::landlock::describe_network_seccomp;
+use crate::landlock::spawn_allowlist_for_command;
 use crate::proxy_routing::activate_proxy_routes_in_netns;
 use crate::proxy_routing::prepare_host_proxy_route_spec;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
+use codex_protocol::permissions::FileSystemWriteScope;
+use codex_protocol::permissions::SeccompProfile;
 
 #[derive(Debug, Parser)]
 /// CLI surface for the Linux sandbox helper.
@@ -61,6 +65,33 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Internal: keep the sandboxed command running after the process that
+    /// started this helper exits. Used for persistent background jobs.
+    #[arg(long = "outlive-parent", hide = true, default_value_t = false)]
+    pub outlive_parent: bool,
+
+    /// Read-only globs and deny-read paths layered on top of the sandbox
+    /// policy. Requires `--use-bwrap-sandbox`.
+    #[arg(long = "write-scope", hide = true)]
+    pub write_scope: Option<FileSystemWriteScope>,
+
+    /// Named seccomp profile to install on top of the network filter, e.g.
+    /// `no-ptrace`. May be repeated. Blocked syscalls kill the command with
+    /// `SIGSYS`.
+    #[arg(long = "seccomp-profile")]
+    pub seccomp_profiles: Vec<SeccompProfile>,
+
+    /// Program the command may execute under
+    /// `no-process-spawn-outside-allowlist`, besides the command itself. May
+    /// be repeated.
+    #[arg(long = "spawn-allowlist")]
+    pub spawn_allowlist: Vec<PathBuf>,
+
+    /// Print the mounts, namespaces, and seccomp/landlock rules that would be
+    /// applied, then exit without running the command.
+    #[arg(long = "print-plan", default_value_t = false)]
+    pub print_plan: bool,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -82,13 +113,44 @@ pub fn run_main() -> ! {
         allow_network_for_proxy,
         proxy_route_spec,
         no_proc,
+        outlive_parent,
+        write_scope,
+        seccomp_profiles,
+        spawn_allowlist,
+        print_plan,
         command,
     } = LandlockCommand::parse();
+    let write_scope = write_scope.unwrap_or_default();
+    let spawn_allowlist =
+        if seccomp_profiles.contains(&SeccompProfile::NoProcessSpawnOutsideAllowlist) {
+            spawn_allowlist_for_command(spawn_allowlist, &command)
+        } else {
+            Vec::new()
+        };
 
     if command.is_empty() {
         panic!("No command specified to execute.");
     }
     ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec, use_bwrap_sandbox);
+    ensure_write_scope_is_supported(&write_scope, use_bwrap_sandbox);
+
+    if print_plan {
+        print!(
+            "{}",
+            format_sandbox_plan(
+                &sandbox_policy_cwd,
+                &sandbox_policy,
+                &write_scope,
+                &seccomp_profiles,
+                &spawn_allowlist,
+                use_bwrap_sandbox,
+                allow_network_for_proxy,
+                !no_proc,
+                command,
+            )
+        );
+        std::process::exit(0);
+    }
 
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
     // established the filesystem view.
@@ -108,6 +170,8 @@ pub fn run_main() -> ! {
             false,
             allow_network_for_proxy,
             proxy_routing_active,
+            &seccomp_profiles,
+            &spawn_allowlist,
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -121,6 +185,8 @@ pub fn run_main() -> ! {
             false,
             allow_network_for_proxy,
             false,
+            &seccomp_profiles,
+            &spawn_allowlist,
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -145,14 +211,20 @@ pub fn run_main() -> ! {
             use_bwrap_sandbox,
             allow_network_for_proxy,
             proxy_route_spec,
+            &seccomp_profiles,
+            &spawn_allowlist,
             command,
         );
         run_bwrap_with_proc_fallback(
             &sandbox_policy_cwd,
             &sandbox_policy,
+            &write_scope,
             inner,
-            !no_proc,
-            allow_network_for_proxy,
+            BwrapOptions {
+                mount_proc: !no_proc,
+                network_mode: bwrap_network_mode(&sandbox_policy, allow_network_for_proxy),
+                die_with_parent: !outlive_parent,
+            },
         );
     }
 
@@ -163,6 +235,8 @@ pub fn run_main() -> ! {
         true,
         allow_network_for_proxy,
         false,
+        &seccomp_profiles,
+        &spawn_allowlist,
     ) {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
@@ -175,27 +249,183 @@ fn ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec: bool, use_bwrap_san
     }
 }
 
+fn ensure_write_scope_is_supported(write_scope: &FileSystemWriteScope, use_bwrap_sandbox: bool) {
+    if !write_scope.is_empty() && !use_bwrap_sandbox {
+        panic!("--write-scope requires --use-bwrap-sandbox");
+    }
+}
+
+/// Describe what the helper would do for `command` without running it.
+///
+/// This mirrors the branches in [`run_main`] but skips the `/proc` preflight,
+/// so `mount_proc` reflects `--no-proc` only.
+#[allow(clippy::too_many_arguments)]
+fn format_sandbox_plan(
+    sandbox_policy_cwd: &Path,
+    sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    write_scope: &FileSystemWriteScope,
+    seccomp_profiles: &[SeccompProfile],
+    spawn_allowlist: &[PathBuf],
+    use_bwrap_sandbox: bool,
+    allow_network_for_proxy: bool,
+    mount_proc: bool,
+    command: Vec<String>,
+) -> String {
+    let mut plan = String::new();
+    let full_disk_write = sandbox_policy.has_full_disk_write_access() && !allow_network_for_proxy;
+    let mut push_line = |line: String| {
+        plan.push_str(&line);
+        plan.push('\n');
+    };
+    push_line(format!("command: {}", command.join(" ")));
+
+    let (pipeline, proxy_routed_network) = if full_disk_write {
+        ("seccomp only (full disk write access)", false)
+    } else if use_bwrap_sandbox {
+        ("bubblewrap + seccomp", allow_network_for_proxy)
+    } else {
+        ("legacy landlock + seccomp", false)
+    };
+    push_line(format!("pipeline: {pipeline}"));
+
+    if !full_disk_write && use_bwrap_sandbox {
+        let network_mode = bwrap_network_mode(sandbox_policy, allow_network_for_proxy);
+        let options = BwrapOptions {
+            mount_proc,
+            network_mode,
+            die_with_parent: true,
+        };
+        let argv = build_bwrap_argv(
+            command,
+            sandbox_policy,
+            sandbox_policy_cwd,
+            write_scope,
+            options,
+        );
+        let BwrapPlan {
+            mounts,
+            namespaces,
+            proc_mounted,
+        } = describe_bwrap_argv(&argv);
+        push_line("mounts:".to_string());
+        for mount in mounts {
+            push_line(format!("  {mount}"));
+        }
+        push_line(format!("namespaces: {}", namespaces.join(", ")));
+        push_line(format!(
+            "proc: {}",
+            if proc_mounted {
+                "mounted"
+            } else {
+                "not mounted"
+            }
+        ));
+    }
+
+    push_line(format!(
+        "seccomp: {}",
+        describe_network_seccomp(
+            sandbox_policy,
+            allow_network_for_proxy,
+            proxy_routed_network
+        )
+    ));
+    if !seccomp_profiles.is_empty() {
+        let profiles = seccomp_profiles
+            .iter()
+            .map(|profile| {
+                if profile.kills_on_violation() {
+                    format!(
+                        "{profile} (kills on {})",
+                        profile.blocked_syscalls().join(", ")
+                    )
+                } else {
+                    let programs = spawn_allowlist
+                        .iter()
+                        .map(|program| program.to_string_lossy())
+                        .collect::<Vec<_>>();
+                    format!("{profile} (landlock: execute only {})", programs.join(", "))
+                }
+            })
+            .collect::<Vec<_>>();
+        push_line(format!("seccomp profiles: {}", profiles.join("; ")));
+    }
+
+    let landlock = if full_disk_write || use_bwrap_sandbox {
+        "not used".to_string()
+    } else {
+        let writable_roots = sandbox_policy
+            .get_writable_roots_with_cwd(sandbox_policy_cwd)
+            .into_iter()
+            .map(|writable_root| writable_root.root.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        format!("read everywhere, write to {}", writable_roots.join(", "))
+    };
+    push_line(format!("landlock: {landlock}"));
+    plan
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct BwrapPlan {
+    mounts: Vec<String>,
+    namespaces: Vec<String>,
+    proc_mounted: bool,
+}
+
+/// Group bubblewrap flags (everything before `--`) into mounts and namespaces.
+fn describe_bwrap_argv(argv: &[String]) -> BwrapPlan {
+    let mut plan = BwrapPlan::default();
+    let mut args = argv.iter().skip(1).take_while(|arg| *arg != "--");
+    while let Some(flag) = args.next() {
+        let operand_count = match flag.as_str() {
+            "--bind" | "--ro-bind" | "--dev-bind" => 2,
+            "--tmpfs" | "--dev" | "--remount-ro" | "--proc" | "--argv0" => 1,
+            _ => 0,
+        };
+        let operands: Vec<&str> = args
+            .by_ref()
+            .take(operand_count)
+            .map(String::as_str)
+            .collect();
+        match flag.as_str() {
+            "--proc" => plan.proc_mounted = true,
+            "--argv0" | "--new-session" | "--die-with-parent" => {}
+            namespace if namespace.starts_with("--unshare-") => {
+                plan.namespaces
+                    .push(namespace.trim_start_matches("--unshare-").to_string());
+            }
+            _ => plan.mounts.push(format!("{flag} {}", operands.join(" "))),
+        }
+    }
+    plan
+}
+
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    write_scope: &FileSystemWriteScope,
     inner: Vec<String>,
-    mount_proc: bool,
-    allow_network_for_proxy: bool,
+    mut options: BwrapOptions,
 ) -> ! {
-    let network_mode = bwrap_network_mode(sandbox_policy, allow_network_for_proxy);
-    let mut mount_proc = mount_proc;
-
-    if mount_proc && !preflight_proc_mount_support(sandbox_policy_cwd, sandbox_policy, network_mode)
+    if options.mount_proc
+        && !preflight_proc_mount_support(
+            sandbox_policy_cwd,
+            sandbox_policy,
+            write_scope,
+            options.network_mode,
+        )
     {
         eprintln!("codex-linux-sandbox: bwrap could not mount /proc; retrying with --no-proc");
-        mount_proc = false;
+        options.mount_proc = false;
     }
 
-    let options = BwrapOptions {
-        mount_proc,
-        network_mode,
-    };
-    let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
+    let argv = build_bwrap_argv(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        write_scope,
+        options,
+    );
     exec_vendored_bwrap(argv);
 }
 
@@ -216,10 +446,17 @@ fn build_bwrap_argv(
     inner: Vec<String>,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    write_scope: &FileSystemWriteScope,
     options: BwrapOptions,
 ) -> Vec<String> {
-    let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
-        .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
+    let mut args = create_bwrap_command_args(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        write_scope,
+        options,
+    )
+    .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
 
     let command_separator_index = args
         .iter()
@@ -238,10 +475,15 @@ fn build_bwrap_argv(
 fn preflight_proc_mount_support(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    write_scope: &FileSystemWriteScope,
     network_mode: BwrapNetworkMode,
 ) -> bool {
-    let preflight_argv =
-        build_preflight_bwrap_argv(sandbox_policy_cwd, sandbox_policy, network_mode);
+    let preflight_argv = build_preflight_bwrap_argv(
+        sandbox_policy_cwd,
+        sandbox_policy,
+        write_scope,
+        network_mode,
+    );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
 }
@@ -249,6 +491,7 @@ fn preflight_proc_mount_support(
 fn build_preflight_bwrap_argv(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    write_scope: &FileSystemWriteScope,
     network_mode: BwrapNetworkMode,
 ) -> Vec<String> {
     let preflight_command = vec![resolve_true_command()];
@@ -256,9 +499,11 @@ fn build_preflight_bwrap_argv(
         preflight_command,
         sandbox_policy,
         sandbox_policy_cwd,
+        write_scope,
         BwrapOptions {
             mount_proc: true,
             network_mode,
+            die_with_parent: true,
         },
     )
 }
@@ -359,12 +604,15 @@ fn is_proc_mount_failure(stderr: &str) -> bool {
 }
 
 /// Build the inner command that applies seccomp after bubblewrap.
+#[allow(clippy::too_many_arguments)]
 fn build_inner_seccomp_command(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
     proxy_route_spec: Option<String>,
+    seccomp_profiles: &[SeccompProfile],
+    spawn_allowlist: &[PathBuf],
     command: Vec<String>,
 ) -> Vec<String> {
     let current_exe = match std::env::current_exe() {
@@ -394,6 +642,14 @@ fn build_inner_seccomp_command(
         inner.push("--proxy-route-spec".to_string());
         inner.push(proxy_route_spec);
     }
+    for profile in seccomp_profiles {
+        inner.push("--seccomp-profile".to_string());
+        inner.push(profile.to_string());
+    }
+    for program in spawn_allowlist {
+        inner.push("--spawn-allowlist".to_string());
+        inner.push(program.to_string_lossy().to_string());
+    }
     inner.push("--".to_string());
     inner.extend(command);
     inner
@@ -2,6 +2,8 @@
 use super::*;
 #[cfg(test)]
 use codex_protocol::protocol::SandboxPolicy;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
 
 #[test]
 fn detects_proc_mount_invalid_argument_failure() {
@@ -33,9 +35,11 @@ fn inserts_bwrap_argv0_before_command_separator() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &FileSystemWriteScope::default(),
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            die_with_parent: true,
         },
     );
     assert_eq!(
@@ -67,9 +71,11 @@ fn inserts_unshare_net_when_network_isolation_requested() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &FileSystemWriteScope::default(),
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::Isolated,
+            die_with_parent: true,
         },
     );
     assert!(argv.contains(&"--unshare-net".to_string()));
@@ -81,9 +87,11 @@ fn inserts_unshare_net_when_proxy_only_network_mode_requested() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &FileSystemWriteScope::default(),
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::ProxyOnly,
+            die_with_parent: true,
         },
     );
     assert!(argv.contains(&"--unshare-net".to_string()));
@@ -98,7 +106,12 @@ fn proxy_only_mode_takes_precedence_over_full_network_policy() {
 #[test]
 fn managed_proxy_preflight_argv_is_wrapped_for_full_access_policy() {
     let mode = bwrap_network_mode(&SandboxPolicy::DangerFullAccess, true);
-    let argv = build_preflight_bwrap_argv(Path::new("/"), &SandboxPolicy::DangerFullAccess, mode);
+    let argv = build_preflight_bwrap_argv(
+        Path::new("/"),
+        &SandboxPolicy::DangerFullAccess,
+        &FileSystemWriteScope::default(),
+        mode,
+    );
     assert!(argv.iter().any(|arg| arg == "--"));
 }
 
@@ -110,6 +123,8 @@ fn managed_proxy_inner_command_includes_route_spec() {
         true,
         true,
         Some("{\"routes\":[]}".to_string()),
+        &[],
+        &[],
         vec!["/bin/true".to_string()],
     );
 
@@ -125,6 +140,8 @@ fn non_managed_inner_command_omits_route_spec() {
         true,
         false,
         None,
+        &[],
+        &[],
         vec!["/bin/true".to_string()],
     );
 
@@ -140,6 +157,8 @@ fn managed_proxy_inner_command_requires_route_spec() {
             true,
             true,
             None,
+            &[],
+            &[],
             vec!["/bin/true".to_string()],
         )
     });
@@ -158,3 +177,138 @@ fn valid_inner_stage_modes_do_not_panic() {
     ensure_inner_stage_mode_is_valid(false, true);
     ensure_inner_stage_mode_is_valid(true, true);
 }
+
+#[test]
+fn write_scope_without_bwrap_panics() {
+    let write_scope = FileSystemWriteScope {
+        read_only: vec![".env".to_string()],
+        deny_read: Vec::new(),
+    };
+    let result = std::panic::catch_unwind(|| ensure_write_scope_is_supported(&write_scope, false));
+    assert!(result.is_err());
+
+    ensure_write_scope_is_supported(&write_scope, true);
+    ensure_write_scope_is_supported(&FileSystemWriteScope::default(), false);
+}
+
+#[test]
+fn describe_bwrap_argv_groups_mounts_and_namespaces() {
+    let argv = build_bwrap_argv(
+        vec!["/bin/true".to_string()],
+        &SandboxPolicy::new_read_only_policy(),
+        Path::new("/"),
+        &FileSystemWriteScope::default(),
+        BwrapOptions {
+            mount_proc: false,
+            network_mode: BwrapNetworkMode::Isolated,
+            die_with_parent: true,
+        },
+    );
+
+    assert_eq!(
+        describe_bwrap_argv(&argv),
+        BwrapPlan {
+            mounts: vec!["--ro-bind / /".to_string(), "--dev /dev".to_string()],
+            namespaces: vec!["user".to_string(), "pid".to_string(), "net".to_string()],
+            proc_mounted: false,
+        }
+    );
+}
+
+#[test]
+fn sandbox_plan_reports_legacy_landlock_writable_roots() {
+    let cwd = std::env::temp_dir();
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: Vec::new(),
+        read_only_access: Default::default(),
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+
+    let plan = format_sandbox_plan(
+        &cwd,
+        &sandbox_policy,
+        &FileSystemWriteScope::default(),
+        &[],
+        &[],
+        false,
+        false,
+        true,
+        vec!["/bin/true".to_string()],
+    );
+
+    assert_eq!(
+        plan,
+        format!(
+            "command: /bin/true\npipeline: legacy landlock + seccomp\nseccomp: restricted network\nlandlock: read everywhere, write to {}\n",
+            cwd.display()
+        )
+    );
+}
+
+#[test]
+fn inner_command_forwards_seccomp_profiles() {
+    let args = build_inner_seccomp_command(
+        Path::new("/tmp"),
+        &SandboxPolicy::new_read_only_policy(),
+        true,
+        false,
+        None,
+        &[SeccompProfile::NoMount],
+        &[PathBuf::from("/usr/bin/git")],
+        vec!["/bin/true".to_string()],
+    );
+
+    let separator = args
+        .iter()
+        .position(|arg| arg == "--")
+        .expect("command separator");
+    assert_eq!(
+        args[separator - 4..separator].to_vec(),
+        vec![
+            "--seccomp-profile".to_string(),
+            "no-mount".to_string(),
+            "--spawn-allowlist".to_string(),
+            "/usr/bin/git".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn sandbox_plan_lists_seccomp_profiles() {
+    let plan = format_sandbox_plan(
+        Path::new("/tmp"),
+        &SandboxPolicy::new_read_only_policy(),
+        &FileSystemWriteScope::default(),
+        &[SeccompProfile::NoPtrace],
+        &[],
+        false,
+        false,
+        true,
+        vec!["/bin/true".to_string()],
+    );
+
+    assert!(plan.contains(
+        "seccomp profiles: no-ptrace (kills on ptrace, process_vm_readv, process_vm_writev, pidfd_getfd)\n"
+    ));
+}
+
+#[test]
+fn sandbox_plan_lists_the_spawn_allowlist() {
+    let plan = format_sandbox_plan(
+        Path::new("/tmp"),
+        &SandboxPolicy::new_read_only_policy(),
+        &FileSystemWriteScope::default(),
+        &[SeccompProfile::NoProcessSpawnOutsideAllowlist],
+        &[PathBuf::from("/usr/bin/git"), PathBuf::from("/bin/true")],
+        false,
+        false,
+        true,
+        vec!["/bin/true".to_string()],
+    );
+
+    assert!(plan.contains(
+        "seccomp profiles: no-process-spawn-outside-allowlist (landlock: execute only /usr/bin/git, /bin/true)\n"
+    ));
+}
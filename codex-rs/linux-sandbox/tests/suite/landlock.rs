@@ -266,6 +266,114 @@ async fn bwrap_preserves_writable_dev_shm_bind_mount() {
     );
 }
 
+/// Starts the helper from a shell that exits at once, the way Codex exits while
+/// a persistent job keeps running, and reports whether the sandboxed command
+/// still got to write `marker` a second later.
+async fn sandboxed_command_outlives_its_parent(outlive_parent: bool) -> bool {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let marker = tmpdir.path().join("marker");
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![AbsolutePathBuf::try_from(tmpdir.path()).unwrap()],
+        read_only_access: Default::default(),
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let cwd = std::env::current_dir().unwrap();
+    let mut helper_args = vec![
+        "--sandbox-policy-cwd".to_string(),
+        cwd.to_string_lossy().to_string(),
+        "--sandbox-policy".to_string(),
+        serde_json::to_string(&sandbox_policy).unwrap(),
+        "--use-bwrap-sandbox".to_string(),
+    ];
+    if outlive_parent {
+        helper_args.push("--outlive-parent".to_string());
+    }
+    helper_args.extend([
+        "--".to_string(),
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        format!("sleep 1 && touch {}", marker.to_string_lossy()),
+    ]);
+
+    let status = tokio::process::Command::new("/bin/sh")
+        .arg("-c")
+        .arg("\"$@\" >/dev/null 2>&1 &")
+        .arg("sh")
+        .arg(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .args(helper_args)
+        .status()
+        .await
+        .unwrap();
+    assert!(status.success());
+
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+    while tokio::time::Instant::now() < deadline {
+        if marker.exists() {
+            return true;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn bwrap_outlive_parent_keeps_persistent_jobs_running() {
+    if should_skip_bwrap_tests().await {
+        eprintln!("skipping bwrap test: bwrap sandbox prerequisites are unavailable");
+        return;
+    }
+
+    assert!(sandboxed_command_outlives_its_parent(true).await);
+    assert!(!sandboxed_command_outlives_its_parent(false).await);
+}
+
+async fn run_with_spawn_allowlist(spawn_allowlist: &[&str]) -> std::process::Output {
+    let cwd = std::env::current_dir().unwrap();
+    let mut helper_args = vec![
+        "--sandbox-policy-cwd".to_string(),
+        cwd.to_string_lossy().to_string(),
+        "--sandbox-policy".to_string(),
+        serde_json::to_string(&SandboxPolicy::DangerFullAccess).unwrap(),
+        "--seccomp-profile".to_string(),
+        "no-process-spawn-outside-allowlist".to_string(),
+    ];
+    for program in spawn_allowlist {
+        helper_args.push("--spawn-allowlist".to_string());
+        helper_args.push(program.to_string());
+    }
+    helper_args.extend([
+        "--".to_string(),
+        "/bin/sh".to_string(),
+        "-c".to_string(),
+        "/bin/true".to_string(),
+    ]);
+
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .args(helper_args)
+        .output()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn spawn_allowlist_blocks_programs_outside_it() {
+    let allowed = run_with_spawn_allowlist(&["/bin/true"]).await;
+    if String::from_utf8_lossy(&allowed.stderr).contains("LandlockRestrict") {
+        eprintln!("skipping spawn allowlist test: Landlock is not enforced on this kernel");
+        return;
+    }
+    assert!(allowed.status.success(), "{allowed:?}");
+
+    let blocked = run_with_spawn_allowlist(&[]).await;
+    assert!(!blocked.status.success(), "{blocked:?}");
+    assert!(
+        String::from_utf8_lossy(&blocked.stderr).contains("Permission denied"),
+        "{blocked:?}"
+    );
+}
+
 #[tokio::test]
 async fn test_writable_root() {
     let tmpdir = tempfile::tempdir().unwrap();
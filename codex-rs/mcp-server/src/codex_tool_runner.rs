@@ -272,6 +272,7 @@ async fn run_codex_tool_session_inner(
                         reason,
                         grant_root,
                         changes,
+                        skipped: _,
                     }) => {
                         handle_patch_approval_request(
                             call_id,
@@ -332,6 +333,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::ListCheckpointsResponse(_)
+                    | EventMsg::ListBackgroundJobsResponse(_)
+                    | EventMsg::ProjectDocLoaded(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ListRemoteSkillsResponse(_)
                     | EventMsg::RemoteSkillDownloaded(_)
@@ -370,6 +374,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::DynamicToolCallResponse(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ModelReroute(_)
+                    | EventMsg::GuardianAssessment(_)
+                    | EventMsg::SecretsRedacted(_)
                     | EventMsg::ThreadRolledBack(_)
                     | EventMsg::CollabAgentSpawnBegin(_)
                     | EventMsg::CollabAgentSpawnEnd(_)
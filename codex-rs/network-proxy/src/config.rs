@@ -43,6 +43,15 @@ pub struct NetworkProxySettings {
     pub allow_local_binding: bool,
     #[serde(default)]
     pub mitm: bool,
+    /// Per-domain method/path rules evaluated after the domain allowlist/denylist.
+    #[serde(default)]
+    pub request_rules: Vec<NetworkRequestRule>,
+    /// Capture request/response pairs for MITM'd requests so they can be exported as HAR.
+    #[serde(default)]
+    pub capture_har: bool,
+    /// Read-only caching mirror for public package registries.
+    #[serde(default)]
+    pub registry_mirror: RegistryMirrorConfig,
 }
 
 impl Default for NetworkProxySettings {
@@ -62,6 +71,9 @@ impl Default for NetworkProxySettings {
             allow_unix_sockets: Vec::new(),
             allow_local_binding: false,
             mitm: false,
+            request_rules: Vec::new(),
+            capture_har: false,
+            registry_mirror: RegistryMirrorConfig::default(),
         }
     }
 }
@@ -88,6 +100,87 @@ impl NetworkMode {
     }
 }
 
+/// A rule that refines what the proxy permits for hosts matching `domain`.
+///
+/// Rules only apply to hosts that already pass the domain allowlist/denylist. When any allow rule
+/// matches a host, requests to that host must match an allow rule; deny rules always win.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRequestRule {
+    /// Domain pattern, using the same syntax as `allowed_domains`.
+    pub domain: String,
+    /// HTTP methods this rule applies to. Empty means any method.
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// URL path glob (for example `/-/npm/v1/security/*`). Unset means any path.
+    #[serde(default)]
+    pub path: Option<String>,
+    pub action: NetworkRequestRuleAction,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRequestRuleAction {
+    Allow,
+    Deny,
+}
+
+/// Serves `GET`/`HEAD` requests to known package registries from a local content-addressed cache.
+///
+/// Mirrored registry hosts are reachable without being listed in `allowed_domains`, but only
+/// read-only. HTTPS registries additionally require `mitm = true`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RegistryMirrorConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Registries to mirror.
+    #[serde(default = "default_mirrored_registries")]
+    pub registries: Vec<PackageRegistry>,
+    /// Serve only from the cache: misses fail instead of being fetched upstream.
+    #[serde(default)]
+    pub offline: bool,
+    /// Cache location. Defaults to `$CODEX_HOME/proxy/registry-cache`.
+    #[serde(default)]
+    pub cache_dir: Option<AbsolutePathBuf>,
+}
+
+impl Default for RegistryMirrorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            registries: default_mirrored_registries(),
+            offline: false,
+            cache_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageRegistry {
+    /// `registry.npmjs.org`
+    Npm,
+    /// `pypi.org` and `files.pythonhosted.org`
+    Pypi,
+    /// `index.crates.io` and `static.crates.io`
+    Crates,
+}
+
+impl PackageRegistry {
+    pub const ALL: [PackageRegistry; 3] = [Self::Npm, Self::Pypi, Self::Crates];
+
+    pub const fn hosts(self) -> &'static [&'static str] {
+        match self {
+            Self::Npm => &["registry.npmjs.org"],
+            Self::Pypi => &["pypi.org", "files.pythonhosted.org"],
+            Self::Crates => &["index.crates.io", "static.crates.io"],
+        }
+    }
+}
+
+fn default_mirrored_registries() -> Vec<PackageRegistry> {
+    PackageRegistry::ALL.to_vec()
+}
+
 fn default_proxy_url() -> String {
     "http://127.0.0.1:3128".to_string()
 }
@@ -376,6 +469,18 @@ mod tests {
                 allow_unix_sockets: Vec::new(),
                 allow_local_binding: false,
                 mitm: false,
+                request_rules: Vec::new(),
+                capture_har: false,
+                registry_mirror: RegistryMirrorConfig {
+                    enabled: false,
+                    registries: vec![
+                        PackageRegistry::Npm,
+                        PackageRegistry::Pypi,
+                        PackageRegistry::Crates,
+                    ],
+                    offline: false,
+                    cache_dir: None,
+                },
             }
         );
     }
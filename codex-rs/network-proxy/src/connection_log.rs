@@ -0,0 +1,629 @@
+//! Per-connection records for traffic that passes through (or is refused by) the proxy.
+//!
+//! Blocked requests are recorded from [`NetworkProxyState::record_blocked`]. Allowed HTTP
+//! exchanges are recorded once the response body finishes streaming so byte counts are final;
+//! CONNECT tunnels are recorded when the tunnel closes.
+
+use crate::har::HarContent;
+use crate::har::HarEntry;
+use crate::har::HarHeader;
+use crate::har::HarPostData;
+use crate::har::HarRequest;
+use crate::har::HarResponse;
+use crate::har::HarTimings;
+use crate::har::har_header;
+use crate::har::har_query_string;
+use crate::runtime::BlockedRequest;
+use crate::runtime::NetworkProxyState;
+use crate::runtime::unix_timestamp;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::HeaderMap;
+use rama_http::Request;
+use rama_http::Response;
+use rama_http::header::CONTENT_LENGTH;
+use rama_http::header::CONTENT_TYPE;
+use rama_http::header::TRANSFER_ENCODING;
+use serde::Serialize;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Instant;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+/// Bodies larger than this are truncated in HAR captures (byte counts stay exact).
+const HAR_MAX_BODY_BYTES: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkConnectionDecision {
+    Allow,
+    Deny,
+}
+
+impl NetworkConnectionDecision {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// One proxied connection or HTTP exchange.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NetworkConnectionRecord {
+    /// `http`, `https` (MITM), `http-connect`, or `socks5`.
+    pub protocol: String,
+    pub host: String,
+    pub port: Option<u16>,
+    /// Peer address of the client that opened the connection.
+    pub client: Option<String>,
+    /// What the connection observer attributed the connection to as it opened
+    /// (see [`NetworkConnectionObserver::on_connection_open`]). Tunnels are
+    /// only recorded once they close, by which time that may have changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opened_by: Option<String>,
+    pub method: Option<String>,
+    /// Request path without the query string, when the proxy could see it.
+    pub path: Option<String>,
+    pub decision: NetworkConnectionDecision,
+    pub reason: Option<String>,
+    /// Label of the request rule that decided the request, if any.
+    pub rule: Option<String>,
+    pub status: Option<u16>,
+    /// Bytes sent by the client. `None` when the proxy did not observe the payload.
+    pub bytes_sent: Option<u64>,
+    /// Bytes returned to the client. `None` when the proxy did not observe the payload.
+    pub bytes_received: Option<u64>,
+    /// Full exchange, captured for MITM'd requests when `capture_har` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub har_entry: Option<HarEntry>,
+    pub timestamp: i64,
+}
+
+impl NetworkConnectionRecord {
+    /// An allowed connection whose payload the proxy does not inspect.
+    pub(crate) fn allowed(protocol: &str, host: String, port: u16, client: Option<String>) -> Self {
+        Self {
+            protocol: protocol.to_string(),
+            host,
+            port: Some(port),
+            client,
+            opened_by: None,
+            method: None,
+            path: None,
+            decision: NetworkConnectionDecision::Allow,
+            reason: None,
+            rule: None,
+            status: None,
+            bytes_sent: None,
+            bytes_received: None,
+            har_entry: None,
+            timestamp: unix_timestamp(),
+        }
+    }
+
+    pub(crate) fn from_blocked(blocked: &BlockedRequest) -> Self {
+        Self {
+            protocol: blocked.protocol.clone(),
+            host: blocked.host.clone(),
+            port: blocked.port,
+            client: blocked.client.clone(),
+            opened_by: None,
+            method: blocked.method.clone(),
+            path: None,
+            decision: NetworkConnectionDecision::Deny,
+            reason: Some(blocked.reason.clone()),
+            rule: blocked.rule.clone(),
+            status: None,
+            bytes_sent: None,
+            bytes_received: None,
+            har_entry: None,
+            timestamp: blocked.timestamp,
+        }
+    }
+}
+
+#[async_trait]
+pub trait NetworkConnectionObserver: Send + Sync + 'static {
+    /// Called as a connection opens; the result is carried on its record as
+    /// [`NetworkConnectionRecord::opened_by`].
+    async fn on_connection_open(&self) -> Option<String> {
+        None
+    }
+
+    async fn on_connection(&self, record: NetworkConnectionRecord);
+}
+
+#[async_trait]
+impl<O: NetworkConnectionObserver + ?Sized> NetworkConnectionObserver for Arc<O> {
+    async fn on_connection_open(&self) -> Option<String> {
+        (**self).on_connection_open().await
+    }
+
+    async fn on_connection(&self, record: NetworkConnectionRecord) {
+        (**self).on_connection(record).await
+    }
+}
+
+#[async_trait]
+impl<F, Fut> NetworkConnectionObserver for F
+where
+    F: Fn(NetworkConnectionRecord) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn on_connection(&self, record: NetworkConnectionRecord) {
+        (self)(record).await
+    }
+}
+
+pub(crate) struct HttpExchangeArgs {
+    pub protocol: &'static str,
+    pub host: String,
+    pub port: u16,
+    pub client: Option<String>,
+    /// See [`NetworkConnectionRecord::opened_by`].
+    pub opened_by: Option<String>,
+    pub rule: Option<String>,
+    /// Capture a HAR entry for this exchange.
+    pub capture_har: bool,
+}
+
+/// Tracks one allowed HTTP exchange from request to the end of the response body.
+pub(crate) struct HttpExchange {
+    app_state: Arc<NetworkProxyState>,
+    record: NetworkConnectionRecord,
+    har_request: Option<HarRequest>,
+    started_at: String,
+    started: Instant,
+    request_tap: BodyTap,
+    request_has_body: bool,
+}
+
+impl HttpExchange {
+    pub(crate) fn start(
+        app_state: Arc<NetworkProxyState>,
+        args: HttpExchangeArgs,
+        req: &Request,
+        url: &str,
+    ) -> Self {
+        let HttpExchangeArgs {
+            protocol,
+            host,
+            port,
+            client,
+            opened_by,
+            rule,
+            capture_har,
+        } = args;
+        let har_request = capture_har.then(|| HarRequest {
+            method: req.method().as_str().to_string(),
+            url: url.to_string(),
+            http_version: format!("{:?}", req.version()),
+            cookies: Vec::new(),
+            headers: har_headers(req.headers()),
+            query_string: har_query_string(req.uri().query()),
+            post_data: None,
+            headers_size: -1,
+            body_size: -1,
+        });
+        Self {
+            app_state,
+            record: NetworkConnectionRecord {
+                method: Some(req.method().as_str().to_string()),
+                path: Some(req.uri().path().to_string()),
+                opened_by,
+                rule,
+                ..NetworkConnectionRecord::allowed(protocol, host, port, client)
+            },
+            started_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            started: Instant::now(),
+            request_tap: BodyTap::new(capture_har),
+            request_has_body: request_has_body(req.headers()),
+            har_request,
+        }
+    }
+
+    pub(crate) fn wrap_request_body(&self, body: Body) -> Body {
+        // Re-wrapping an empty body would turn it into a stream of unknown length and make the
+        // upstream client add chunked framing to bodiless requests.
+        if !self.request_has_body {
+            return body;
+        }
+        Body::from_stream(TapStream {
+            inner: Box::pin(body.into_data_stream()),
+            tap: self.request_tap.clone(),
+            on_end: None,
+        })
+    }
+
+    /// Wraps the response body so the exchange is recorded once it has been fully streamed (or
+    /// dropped by the client).
+    pub(crate) fn finish_with_response(self, resp: Response) -> Response {
+        let (parts, body) = resp.into_parts();
+        let response_tap = BodyTap::new(self.har_request.is_some());
+        let finisher = ExchangeFinisher {
+            exchange: self,
+            status: parts.status.as_u16(),
+            status_text: parts
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            http_version: format!("{:?}", parts.version),
+            headers: parts.headers.clone(),
+            response_tap: response_tap.clone(),
+        };
+        let body = Body::from_stream(TapStream {
+            inner: Box::pin(body.into_data_stream()),
+            tap: response_tap,
+            on_end: Some(finisher),
+        });
+        Response::from_parts(parts, body)
+    }
+
+    /// Records an exchange that never produced an upstream response.
+    pub(crate) fn finish_without_response(self) {
+        let mut record = self.record;
+        record.bytes_sent = Some(self.request_tap.len());
+        spawn_record(self.app_state, record);
+    }
+}
+
+struct ExchangeFinisher {
+    exchange: HttpExchange,
+    status: u16,
+    status_text: String,
+    http_version: String,
+    headers: HeaderMap,
+    response_tap: BodyTap,
+}
+
+impl ExchangeFinisher {
+    fn finish(self) {
+        let Self {
+            exchange,
+            status,
+            status_text,
+            http_version,
+            headers,
+            response_tap,
+        } = self;
+        let HttpExchange {
+            app_state,
+            mut record,
+            har_request,
+            started_at,
+            started,
+            request_tap,
+            request_has_body: _,
+        } = exchange;
+        let bytes_sent = request_tap.len();
+        let bytes_received = response_tap.len();
+        record.status = Some(status);
+        record.bytes_sent = Some(bytes_sent);
+        record.bytes_received = Some(bytes_received);
+        record.har_entry = har_request.map(|mut request| {
+            request.body_size = i64::try_from(bytes_sent).unwrap_or(i64::MAX);
+            if bytes_sent > 0 {
+                let (text, _) = request_tap.captured_text(bytes_sent);
+                request.post_data = Some(HarPostData {
+                    mime_type: header_value(&request.headers, CONTENT_TYPE.as_str()),
+                    text: text.unwrap_or_default(),
+                });
+            }
+            let (text, comment) = response_tap.captured_text(bytes_received);
+            let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+            let response_headers = har_headers(&headers);
+            HarEntry {
+                started_date_time: started_at,
+                time: elapsed_ms,
+                request,
+                response: HarResponse {
+                    status,
+                    status_text,
+                    http_version,
+                    cookies: Vec::new(),
+                    content: HarContent {
+                        size: i64::try_from(bytes_received).unwrap_or(i64::MAX),
+                        mime_type: header_value(&response_headers, CONTENT_TYPE.as_str()),
+                        text,
+                        comment,
+                    },
+                    headers: response_headers,
+                    redirect_url: String::new(),
+                    headers_size: -1,
+                    body_size: i64::try_from(bytes_received).unwrap_or(i64::MAX),
+                },
+                cache: serde_json::Map::new(),
+                timings: HarTimings {
+                    send: 0.0,
+                    wait: elapsed_ms,
+                    receive: 0.0,
+                },
+            }
+        });
+        spawn_record(app_state, record);
+    }
+}
+
+/// Counts (and optionally captures a prefix of) body bytes as they stream through.
+#[derive(Clone)]
+struct BodyTap {
+    len: Arc<AtomicU64>,
+    captured: Option<Arc<Mutex<Vec<u8>>>>,
+}
+
+impl BodyTap {
+    fn new(capture: bool) -> Self {
+        Self {
+            len: Arc::new(AtomicU64::new(0)),
+            captured: capture.then(|| Arc::new(Mutex::new(Vec::new()))),
+        }
+    }
+
+    fn observe(&self, bytes: &[u8]) {
+        self.len.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        if let Some(captured) = self.captured.as_ref()
+            && let Ok(mut captured) = captured.lock()
+        {
+            let remaining = HAR_MAX_BODY_BYTES.saturating_sub(captured.len());
+            captured.extend_from_slice(&bytes[..bytes.len().min(remaining)]);
+        }
+    }
+
+    fn len(&self) -> u64 {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns the captured body as text plus a HAR comment describing anything left out.
+    fn captured_text(&self, total_len: u64) -> (Option<String>, Option<String>) {
+        let Some(captured) = self
+            .captured
+            .as_ref()
+            .and_then(|captured| captured.lock().ok().map(|captured| captured.clone()))
+        else {
+            return (None, None);
+        };
+        let truncated = (captured.len() as u64) < total_len;
+        match String::from_utf8(captured) {
+            Ok(text) if truncated => (
+                Some(text),
+                Some(format!("body truncated to {HAR_MAX_BODY_BYTES} bytes")),
+            ),
+            Ok(text) => (Some(text), None),
+            Err(_) => (None, Some("binary body omitted".to_string())),
+        }
+    }
+}
+
+struct TapStream {
+    inner: Pin<Box<BodyDataStream>>,
+    tap: BodyTap,
+    on_end: Option<ExchangeFinisher>,
+}
+
+impl Stream for TapStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(bytes))) => {
+                this.tap.observe(&bytes);
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Poll::Ready(None) => {
+                if let Some(finisher) = this.on_end.take() {
+                    finisher.finish();
+                }
+                Poll::Ready(None)
+            }
+            other => other,
+        }
+    }
+}
+
+impl Drop for TapStream {
+    fn drop(&mut self) {
+        // Clients may hang up mid-body; still record what was transferred.
+        if let Some(finisher) = self.on_end.take() {
+            finisher.finish();
+        }
+    }
+}
+
+/// Counts bytes flowing through a CONNECT tunnel, from the client's point of view.
+pub(crate) struct CountingStream<S> {
+    inner: S,
+    read: Arc<AtomicU64>,
+    written: Arc<AtomicU64>,
+}
+
+#[derive(Clone)]
+pub(crate) struct TunnelByteCounts {
+    read: Arc<AtomicU64>,
+    written: Arc<AtomicU64>,
+}
+
+impl TunnelByteCounts {
+    /// Bytes the client sent through the tunnel.
+    pub(crate) fn sent(&self) -> u64 {
+        self.read.load(Ordering::Relaxed)
+    }
+
+    /// Bytes the tunnel delivered back to the client.
+    pub(crate) fn received(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> CountingStream<S> {
+    pub(crate) fn new(inner: S) -> (Self, TunnelByteCounts) {
+        let read = Arc::new(AtomicU64::new(0));
+        let written = Arc::new(AtomicU64::new(0));
+        let counts = TunnelByteCounts {
+            read: Arc::clone(&read),
+            written: Arc::clone(&written),
+        };
+        (
+            Self {
+                inner,
+                read,
+                written,
+            },
+            counts,
+        )
+    }
+
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for CountingStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        let read = buf.filled().len().saturating_sub(before);
+        this.read.fetch_add(read as u64, Ordering::Relaxed);
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for CountingStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let result = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            this.written.fetch_add(written as u64, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+pub(crate) fn spawn_record(app_state: Arc<NetworkProxyState>, record: NetworkConnectionRecord) {
+    // Called from `poll_next`/`Drop`, so hand the async observer call to the runtime.
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(async move {
+            app_state.record_connection(record).await;
+        });
+    }
+}
+
+fn request_has_body(headers: &HeaderMap) -> bool {
+    headers.contains_key(TRANSFER_ENCODING)
+        || headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim() != "0")
+}
+
+fn har_headers(headers: &HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| har_header(name.as_str(), &String::from_utf8_lossy(value.as_bytes())))
+        .collect()
+}
+
+fn header_value(headers: &[HarHeader], name: &str) -> String {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn body_tap_counts_everything_but_captures_a_bounded_prefix() {
+        let tap = BodyTap::new(true);
+        tap.observe(&vec![b'a'; HAR_MAX_BODY_BYTES]);
+        tap.observe(b"overflow");
+
+        assert_eq!(tap.len(), HAR_MAX_BODY_BYTES as u64 + 8);
+        let (text, comment) = tap.captured_text(tap.len());
+        assert_eq!(text.map(|text| text.len()), Some(HAR_MAX_BODY_BYTES));
+        assert_eq!(
+            comment,
+            Some(format!("body truncated to {HAR_MAX_BODY_BYTES} bytes"))
+        );
+    }
+
+    #[test]
+    fn body_tap_without_capture_only_counts() {
+        let tap = BodyTap::new(false);
+        tap.observe(b"hello");
+
+        assert_eq!(tap.len(), 5);
+        assert_eq!(tap.captured_text(5), (None, None));
+    }
+
+    #[test]
+    fn request_has_body_uses_framing_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(!request_has_body(&headers));
+
+        headers.insert(CONTENT_LENGTH, "0".parse().unwrap());
+        assert!(!request_has_body(&headers));
+
+        headers.insert(CONTENT_LENGTH, "12".parse().unwrap());
+        assert!(request_has_body(&headers));
+
+        headers.remove(CONTENT_LENGTH);
+        headers.insert(TRANSFER_ENCODING, "chunked".parse().unwrap());
+        assert!(request_has_body(&headers));
+    }
+
+    #[tokio::test]
+    async fn counting_stream_tracks_both_directions() {
+        use tokio::io::AsyncReadExt;
+        use tokio::io::AsyncWriteExt;
+
+        let (client, mut server) = tokio::io::duplex(64);
+        let (mut counted, counts) = CountingStream::new(client);
+        server.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        counted.read_exact(&mut buf).await.unwrap();
+        counted.write_all(b"pong!").await.unwrap();
+
+        assert_eq!((counts.sent(), counts.received()), (4, 5));
+    }
+}
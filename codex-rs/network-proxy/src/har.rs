@@ -0,0 +1,206 @@
+//! Minimal HAR 1.2 model for exporting MITM'd exchanges.
+//!
+//! Only the fields needed to replay a request/response pair are populated; timings are coarse.
+//! See <http://www.softwareishard.com/blog/har-12-spec/>.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Header values replaced with [`REDACTED_HEADER_VALUE`] before an entry is captured.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+];
+pub const REDACTED_HEADER_VALUE: &str = "[REDACTED]";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+impl Har {
+    pub fn new(entries: Vec<HarEntry>) -> Self {
+        Self {
+            log: HarLog {
+                version: "1.2".to_string(),
+                creator: HarCreator {
+                    name: "codex-network-proxy".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    /// RFC 3339 timestamp of when the request started.
+    pub started_date_time: String,
+    /// Total elapsed time in milliseconds.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: serde_json::Map<String, serde_json::Value>,
+    pub timings: HarTimings,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<serde_json::Value>,
+    pub headers: Vec<HarHeader>,
+    #[serde(default)]
+    pub query_string: Vec<HarQueryParam>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<serde_json::Value>,
+    pub headers: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL", default)]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarQueryParam {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    /// Omitted when the body was not valid UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Set when the captured body was truncated or omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+pub(crate) fn har_header(name: &str, value: &str) -> HarHeader {
+    let value = if REDACTED_HEADERS
+        .iter()
+        .any(|redacted| name.eq_ignore_ascii_case(redacted))
+    {
+        REDACTED_HEADER_VALUE.to_string()
+    } else {
+        value.to_string()
+    };
+    HarHeader {
+        name: name.to_string(),
+        value,
+    }
+}
+
+pub(crate) fn har_query_string(query: Option<&str>) -> Vec<HarQueryParam> {
+    query
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .map(|(name, value)| HarQueryParam {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn har_header_redacts_credentials() {
+        assert_eq!(
+            har_header("Authorization", "Bearer secret"),
+            HarHeader {
+                name: "Authorization".to_string(),
+                value: REDACTED_HEADER_VALUE.to_string(),
+            }
+        );
+        assert_eq!(
+            har_header("accept", "application/json"),
+            HarHeader {
+                name: "accept".to_string(),
+                value: "application/json".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn har_query_string_decodes_pairs() {
+        assert_eq!(
+            har_query_string(Some("q=a%20b&page=2")),
+            vec![
+                HarQueryParam {
+                    name: "q".to_string(),
+                    value: "a b".to_string(),
+                },
+                HarQueryParam {
+                    name: "page".to_string(),
+                    value: "2".to_string(),
+                },
+            ]
+        );
+        assert_eq!(har_query_string(None), Vec::new());
+    }
+}
@@ -1,4 +1,8 @@
 use crate::config::NetworkMode;
+use crate::connection_log::CountingStream;
+use crate::connection_log::HttpExchange;
+use crate::connection_log::HttpExchangeArgs;
+use crate::connection_log::NetworkConnectionRecord;
 use crate::mitm;
 use crate::network_policy::BlockDecisionAuditEventArgs;
 use crate::network_policy::NetworkDecision;
@@ -16,10 +20,13 @@ use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_REGISTRY_MIRROR_READ_ONLY;
 use crate::reasons::REASON_UNIX_SOCKET_UNSUPPORTED;
+use crate::registry_mirror::RegistryMirrorRoute;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_header_value;
 use crate::responses::blocked_message_with_policy;
+use crate::responses::blocked_request_rule_message;
 use crate::responses::blocked_text_response_with_policy;
 use crate::responses::json_response;
 use crate::runtime::unix_socket_permissions_supported;
@@ -250,9 +257,20 @@ async fn http_connect_accept(
         }
     };
 
-    if mode == NetworkMode::Limited && mitm_state.is_none() {
-        // Limited mode is designed to be read-only. Without MITM, a CONNECT tunnel would hide the
-        // inner HTTP method/headers from the proxy, effectively bypassing method policy.
+    let has_request_rules = app_state
+        .has_request_rules(&host)
+        .await
+        .map_err(|err| internal_error("failed to evaluate request rules", err))?;
+    let registry_mirror = app_state
+        .registry_mirror_applies(&host)
+        .await
+        .map_err(|err| internal_error("failed to evaluate registry mirror", err))?;
+    let mitm_required = mode == NetworkMode::Limited || has_request_rules || registry_mirror;
+
+    if mitm_required && mitm_state.is_none() {
+        // Limited mode is designed to be read-only, request rules match on method/path, and the
+        // registry mirror answers requests itself. Without MITM, a CONNECT tunnel would hide the
+        // inner HTTP method/headers from the proxy, effectively bypassing all three.
         emit_http_block_decision_audit_event(
             &app_state,
             BlockDecisionAuditEventArgs {
@@ -279,7 +297,7 @@ async fn http_connect_accept(
                 reason: REASON_MITM_REQUIRED.to_string(),
                 client: client.clone(),
                 method: Some("CONNECT".to_string()),
-                mode: Some(NetworkMode::Limited),
+                mode: Some(mode),
                 protocol: "http-connect".to_string(),
                 decision: Some(details.decision.as_str().to_string()),
                 source: Some(details.source.as_str().to_string()),
@@ -288,14 +306,14 @@ async fn http_connect_accept(
             .await;
         let client = client.as_deref().unwrap_or_default();
         warn!(
-            "CONNECT blocked; MITM required for read-only HTTPS in limited mode (client={client}, host={host}, mode=limited, allowed_methods=GET, HEAD, OPTIONS)"
+            "CONNECT blocked; MITM required to enforce method policy on HTTPS (client={client}, host={host}, mode={mode:?}, request_rules={has_request_rules}, registry_mirror={registry_mirror})"
         );
         return Err(blocked_text_with_details(REASON_MITM_REQUIRED, &details));
     }
 
     req.extensions_mut().insert(ProxyTarget(authority));
     req.extensions_mut().insert(mode);
-    if let Some(mitm_state) = mitm_state {
+    if mitm_required && let Some(mitm_state) = mitm_state {
         req.extensions_mut().insert(mitm_state);
     }
 
@@ -324,11 +342,12 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         return Ok(());
     };
 
-    if mode == NetworkMode::Limited
-        && upgraded
-            .extensions()
-            .get::<Arc<mitm::MitmState>>()
-            .is_some()
+    // MITM state is only attached when the CONNECT handler decided inner requests need policy
+    // checks (limited mode or per-domain request rules).
+    if upgraded
+        .extensions()
+        .get::<Arc<mitm::MitmState>>()
+        .is_some()
     {
         let host = normalize_host(&target.host.to_string());
         let port = target.port;
@@ -363,23 +382,50 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         None
     };
 
-    if let Err(err) = forward_connect_tunnel(upgraded, proxy).await {
+    let app_state = upgraded
+        .extensions()
+        .get::<Arc<NetworkProxyState>>()
+        .cloned();
+    let client = client_addr(&upgraded);
+    let opened_by = match &app_state {
+        Some(app_state) => app_state.connection_opened().await,
+        None => None,
+    };
+    let (source, byte_counts) = CountingStream::new(upgraded);
+    if let Err(err) = forward_connect_tunnel(source, proxy).await {
         warn!("tunnel error: {err}");
     }
+    if let Some(app_state) = app_state {
+        app_state
+            .record_connection(NetworkConnectionRecord {
+                method: Some("CONNECT".to_string()),
+                opened_by,
+                bytes_sent: Some(byte_counts.sent()),
+                bytes_received: Some(byte_counts.received()),
+                ..NetworkConnectionRecord::allowed(
+                    "http-connect",
+                    normalize_host(&target.host.to_string()),
+                    target.port,
+                    client,
+                )
+            })
+            .await;
+    }
     Ok(())
 }
 
 async fn forward_connect_tunnel(
-    upgraded: Upgraded,
+    source: CountingStream<Upgraded>,
     proxy: Option<ProxyAddress>,
 ) -> Result<(), BoxError> {
-    let authority = upgraded
+    let authority = source
+        .get_ref()
         .extensions()
         .get::<ProxyTarget>()
         .map(|target| target.0.clone())
         .ok_or_else(|| OpaqueError::from_display("missing forward authority").into_boxed())?;
 
-    let mut extensions = upgraded.extensions().clone();
+    let mut extensions = source.get_ref().extensions().clone();
     if let Some(proxy) = proxy {
         extensions.insert(proxy);
     }
@@ -400,10 +446,7 @@ async fn forward_connect_tunnel(
                 .into_boxed()
         })?;
 
-    let proxy_req = ProxyRequest {
-        source: upgraded,
-        target,
-    };
+    let proxy_req = ProxyRequest { source, target };
     StreamForwardService::default()
         .serve(proxy_req)
         .await
@@ -657,7 +700,73 @@ async fn http_plain_proxy(
         }
     }
 
-    if !method_allowed {
+    let request_path = req.uri().path().to_string();
+    let rule_decision = match app_state
+        .request_rule_decision(&host, req.method().as_str(), &request_path)
+        .await
+        .map_err(|err| internal_error("failed to evaluate request rules", err))
+    {
+        Ok(decision) => decision,
+        Err(resp) => return Ok(resp),
+    };
+    if let Some(reason) = rule_decision.block_reason() {
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source: NetworkDecisionSource::BaselinePolicy,
+                reason,
+                protocol: NetworkProtocol::Http,
+                server_address: host.as_str(),
+                server_port: port,
+                method: Some(req.method().as_str()),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::Http,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(
+                BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: reason.to_string(),
+                    client: client.clone(),
+                    method: Some(req.method().as_str().to_string()),
+                    mode: None,
+                    protocol: "http".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                })
+                .with_rule(rule_decision.rule().map(str::to_string)),
+            )
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let method = req.method();
+        let rule = rule_decision.rule().unwrap_or("<none>");
+        warn!(
+            "request blocked by request rule (client={client}, host={host}, method={method}, path={request_path}, reason={reason}, rule={rule})"
+        );
+        let message = blocked_request_rule_message(
+            reason,
+            method.as_str(),
+            &request_path,
+            rule_decision.rule(),
+        );
+        return Ok(json_blocked_with_message(
+            &host,
+            reason,
+            Some(&details),
+            Some(message),
+        ));
+    }
+
+    if !method_allowed && !rule_decision.overrides_mode() {
         emit_http_block_decision_audit_event(
             &app_state,
             BlockDecisionAuditEventArgs {
@@ -703,10 +812,81 @@ async fn http_plain_proxy(
         ));
     }
 
+    let mirror_route = match app_state
+        .registry_mirror_route(&host, req.method().as_str(), req.headers())
+        .await
+        .map_err(|err| internal_error("failed to evaluate registry mirror", err))
+    {
+        Ok(route) => route,
+        Err(resp) => return Ok(resp),
+    };
+    if let Some((_, RegistryMirrorRoute::ReadOnly)) = mirror_route {
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source: NetworkDecisionSource::BaselinePolicy,
+                reason: REASON_REGISTRY_MIRROR_READ_ONLY,
+                protocol: NetworkProtocol::Http,
+                server_address: host.as_str(),
+                server_port: port,
+                method: Some(req.method().as_str()),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_REGISTRY_MIRROR_READ_ONLY,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::Http,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: REASON_REGISTRY_MIRROR_READ_ONLY.to_string(),
+                client: client.clone(),
+                method: Some(req.method().as_str().to_string()),
+                mode: None,
+                protocol: "http".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let method = req.method();
+        warn!("request blocked by registry mirror (client={client}, host={host}, method={method})");
+        return Ok(json_blocked(
+            &host,
+            REASON_REGISTRY_MIRROR_READ_ONLY,
+            Some(&details),
+        ));
+    }
+
+    let exchange = HttpExchange::start(
+        Arc::clone(&app_state),
+        HttpExchangeArgs {
+            protocol: "http",
+            host: host.clone(),
+            port,
+            client: client.clone(),
+            opened_by: app_state.connection_opened().await,
+            rule: rule_decision.rule().map(str::to_string),
+            capture_har: false,
+        },
+        &req,
+        &req.uri().to_string(),
+    );
     let client = client.as_deref().unwrap_or_default();
     let method = req.method();
     info!("request allowed (client={client}, host={host}, method={method})");
 
+    if let Some((mirror, RegistryMirrorRoute::Serve(registry))) = mirror_route {
+        let resp = mirror.serve(registry, &host, &req).await;
+        return Ok(exchange.finish_with_response(resp));
+    }
+
     let allow_upstream_proxy = match app_state
         .allow_upstream_proxy()
         .await
@@ -723,10 +903,12 @@ async fn http_plain_proxy(
 
     // Strip hop-by-hop headers only after extracting metadata used for policy correlation.
     remove_hop_by_hop_request_headers(req.headers_mut());
+    let req = req.map(|body| exchange.wrap_request_body(body));
     match client.serve(req).await {
-        Ok(resp) => Ok(resp),
+        Ok(resp) => Ok(exchange.finish_with_response(resp)),
         Err(err) => {
             warn!("upstream request failed: {err}");
+            exchange.finish_without_response();
             Ok(text_response(StatusCode::BAD_GATEWAY, "upstream failure"))
         }
     }
@@ -836,17 +1018,26 @@ fn remove_hop_by_hop_request_headers(headers: &mut HeaderMap) {
 }
 
 fn json_blocked(host: &str, reason: &str, details: Option<&PolicyDecisionDetails<'_>>) -> Response {
-    let (message, decision, source, protocol, port) = details
+    let message = details.map(|details| blocked_message_with_policy(reason, details));
+    json_blocked_with_message(host, reason, details, message)
+}
+
+fn json_blocked_with_message(
+    host: &str,
+    reason: &str,
+    details: Option<&PolicyDecisionDetails<'_>>,
+    message: Option<String>,
+) -> Response {
+    let (decision, source, protocol, port) = details
         .map(|details| {
             (
-                Some(blocked_message_with_policy(reason, details)),
                 Some(details.decision.as_str()),
                 Some(details.source.as_str()),
                 Some(details.protocol.as_policy_protocol()),
                 Some(details.port),
             )
         })
-        .unwrap_or((None, None, None, None, None));
+        .unwrap_or((None, None, None, None));
     let response = BlockedResponse {
         status: "blocked",
         host,
@@ -2,12 +2,16 @@
 
 mod certs;
 mod config;
+mod connection_log;
+pub mod har;
 mod http_proxy;
 mod mitm;
 mod network_policy;
 mod policy;
 mod proxy;
 mod reasons;
+mod registry_mirror;
+mod request_rules;
 mod responses;
 mod runtime;
 mod socks5;
@@ -16,7 +20,14 @@ mod upstream;
 
 pub use config::NetworkMode;
 pub use config::NetworkProxyConfig;
+pub use config::NetworkRequestRule;
+pub use config::NetworkRequestRuleAction;
+pub use config::PackageRegistry;
+pub use config::RegistryMirrorConfig;
 pub use config::host_and_port_from_network_addr;
+pub use connection_log::NetworkConnectionDecision;
+pub use connection_log::NetworkConnectionObserver;
+pub use connection_log::NetworkConnectionRecord;
 pub use network_policy::NetworkDecision;
 pub use network_policy::NetworkDecisionSource;
 pub use network_policy::NetworkPolicyDecider;
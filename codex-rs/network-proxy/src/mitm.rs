@@ -1,7 +1,14 @@
 use crate::certs::ManagedMitmCa;
 use crate::config::NetworkMode;
+use crate::connection_log::HttpExchange;
+use crate::connection_log::HttpExchangeArgs;
+use crate::network_policy::NetworkDecisionSource;
+use crate::network_policy::NetworkPolicyDecision;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::reasons::REASON_REGISTRY_MIRROR_READ_ONLY;
+use crate::registry_mirror::RegistryMirrorRoute;
+use crate::responses::blocked_request_rule_response;
 use crate::responses::blocked_text_response;
 use crate::responses::text_response;
 use crate::runtime::HostBlockDecision;
@@ -206,9 +213,40 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     let method = req.method().as_str().to_string();
     let path = path_and_query(req.uri());
     let log_path = path_for_log(req.uri());
+    let authority = authority_header_value(&target_host, target_port);
+
+    let app_state = Arc::clone(&request_ctx.policy.app_state);
+    let rule_decision = app_state
+        .request_rule_decision(&target_host, &method, &log_path)
+        .await?;
+    let exchange = HttpExchange::start(
+        Arc::clone(&app_state),
+        HttpExchangeArgs {
+            protocol: "https",
+            host: target_host.clone(),
+            port: target_port,
+            client: req
+                .extensions()
+                .get::<SocketInfo>()
+                .map(|info| info.peer_addr().to_string()),
+            opened_by: app_state.connection_opened().await,
+            rule: rule_decision.rule().map(str::to_string),
+            capture_har: app_state.capture_har().await?,
+        },
+        &req,
+        &format!("https://{authority}{path}"),
+    );
+
+    if let Some((mirror, RegistryMirrorRoute::Serve(registry))) = app_state
+        .registry_mirror_route(&target_host, &method, req.headers())
+        .await?
+    {
+        let resp = mirror.serve(registry, &target_host, &req).await;
+        return Ok(exchange.finish_with_response(resp));
+    }
 
     let (mut parts, body) = req.into_parts();
-    let authority = authority_header_value(&target_host, target_port);
+    let body = exchange.wrap_request_body(body);
     parts.uri = build_https_uri(&authority, &path)?;
     parts
         .headers
@@ -231,9 +269,15 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     };
 
     let upstream_req = Request::from_parts(parts, body);
-    let upstream_resp = mitm.upstream.serve(upstream_req).await?;
+    let upstream_resp = match mitm.upstream.serve(upstream_req).await {
+        Ok(resp) => resp,
+        Err(err) => {
+            exchange.finish_without_response();
+            return Err(err.into());
+        }
+    };
     respond_with_inspection(
-        upstream_resp,
+        exchange.finish_with_response(upstream_resp),
         inspect,
         max_body_bytes,
         &method,
@@ -305,7 +349,40 @@ async fn mitm_blocking_response(
         return Ok(Some(blocked_text_response(reason)));
     }
 
-    if !policy.mode.allows_method(&method) {
+    let rule_decision = policy
+        .app_state
+        .request_rule_decision(&policy.target_host, &method, &log_path)
+        .await?;
+    if let Some(reason) = rule_decision.block_reason() {
+        let _ = policy
+            .app_state
+            .record_blocked(
+                BlockedRequest::new(BlockedRequestArgs {
+                    host: policy.target_host.clone(),
+                    reason: reason.to_string(),
+                    client: client.clone(),
+                    method: Some(method.clone()),
+                    mode: Some(policy.mode),
+                    protocol: "https".to_string(),
+                    decision: Some(NetworkPolicyDecision::Deny.as_str().to_string()),
+                    source: Some(NetworkDecisionSource::BaselinePolicy.as_str().to_string()),
+                    port: Some(policy.target_port),
+                })
+                .with_rule(rule_decision.rule().map(str::to_string)),
+            )
+            .await;
+        let rule = rule_decision.rule();
+        warn!(
+            "MITM blocked by request rule (host={}, method={method}, path={log_path}, reason={reason}, rule={})",
+            policy.target_host,
+            rule.unwrap_or("<none>")
+        );
+        return Ok(Some(blocked_request_rule_response(
+            reason, &method, &log_path, rule,
+        )));
+    }
+
+    if !rule_decision.overrides_mode() && !policy.mode.allows_method(&method) {
         let _ = policy
             .app_state
             .record_blocked(BlockedRequest::new(BlockedRequestArgs {
@@ -327,6 +404,34 @@ async fn mitm_blocking_response(
         return Ok(Some(blocked_text_response(REASON_METHOD_NOT_ALLOWED)));
     }
 
+    if let Some((_, RegistryMirrorRoute::ReadOnly)) = policy
+        .app_state
+        .registry_mirror_route(&policy.target_host, &method, req.headers())
+        .await?
+    {
+        let _ = policy
+            .app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: policy.target_host.clone(),
+                reason: REASON_REGISTRY_MIRROR_READ_ONLY.to_string(),
+                client: client.clone(),
+                method: Some(method.clone()),
+                mode: Some(policy.mode),
+                protocol: "https".to_string(),
+                decision: Some(NetworkPolicyDecision::Deny.as_str().to_string()),
+                source: Some(NetworkDecisionSource::BaselinePolicy.as_str().to_string()),
+                port: Some(policy.target_port),
+            }))
+            .await;
+        warn!(
+            "MITM blocked by registry mirror (host={}, method={method}, path={log_path})",
+            policy.target_host
+        );
+        return Ok(Some(blocked_text_response(
+            REASON_REGISTRY_MIRROR_READ_ONLY,
+        )));
+    }
+
     Ok(None)
 }
 
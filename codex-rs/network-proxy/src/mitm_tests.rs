@@ -1,9 +1,15 @@
 use super::*;
 
 use crate::config::NetworkProxySettings;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleAction;
+use crate::config::RegistryMirrorConfig;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_REGISTRY_MIRROR_READ_ONLY;
+use crate::reasons::REASON_REQUEST_RULE_NOT_ALLOWED;
 use crate::runtime::network_proxy_state_for_policy;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 use rama_http::Body;
 use rama_http::Method;
@@ -57,6 +63,75 @@ async fn mitm_policy_blocks_disallowed_method_and_records_telemetry() {
     assert_eq!(blocked[0].port, Some(443));
 }
 
+#[tokio::test]
+async fn mitm_policy_allows_request_rule_match_in_limited_mode() {
+    let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
+        allowed_domains: vec!["registry.npmjs.org".to_string()],
+        request_rules: vec![NetworkRequestRule {
+            domain: "registry.npmjs.org".to_string(),
+            methods: vec!["POST".to_string()],
+            path: Some("/-/npm/v1/security/*".to_string()),
+            action: NetworkRequestRuleAction::Allow,
+        }],
+        ..NetworkProxySettings::default()
+    }));
+    let ctx = policy_ctx(
+        app_state.clone(),
+        NetworkMode::Limited,
+        "registry.npmjs.org",
+        443,
+    );
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/-/npm/v1/security/advisories/bulk")
+        .header(HOST, "registry.npmjs.org")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = mitm_blocking_response(&req, &ctx).await.unwrap();
+
+    assert!(response.is_none());
+    assert_eq!(app_state.blocked_snapshot().await.unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn mitm_policy_blocks_request_outside_allow_rules_with_reason() {
+    let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
+        allowed_domains: vec!["github.com".to_string()],
+        request_rules: vec![NetworkRequestRule {
+            domain: "github.com".to_string(),
+            methods: vec!["GET".to_string()],
+            path: None,
+            action: NetworkRequestRuleAction::Allow,
+        }],
+        ..NetworkProxySettings::default()
+    }));
+    let ctx = policy_ctx(app_state.clone(), NetworkMode::Full, "github.com", 443);
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/login/oauth?token=secret")
+        .header(HOST, "github.com")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = mitm_blocking_response(&req, &ctx)
+        .await
+        .unwrap()
+        .expect("POST should be blocked by the GET-only rule");
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        response.headers().get("x-proxy-error").unwrap(),
+        "blocked-by-request-rule"
+    );
+
+    let blocked = app_state.drain_blocked().await.unwrap();
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].reason, REASON_REQUEST_RULE_NOT_ALLOWED);
+    assert_eq!(blocked[0].method.as_deref(), Some("POST"));
+    assert_eq!(blocked[0].decision.as_deref(), Some("deny"));
+}
+
 #[tokio::test]
 async fn mitm_policy_rejects_host_mismatch() {
     let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
@@ -108,3 +183,51 @@ async fn mitm_policy_rechecks_local_private_target_after_connect() {
     assert_eq!(blocked[0].host, "10.0.0.1");
     assert_eq!(blocked[0].port, Some(443));
 }
+
+#[tokio::test]
+async fn mitm_policy_keeps_mirrored_registries_read_only() {
+    let cache_dir = tempfile::TempDir::new().unwrap();
+    let app_state = Arc::new(network_proxy_state_for_policy(NetworkProxySettings {
+        allow_local_binding: true,
+        registry_mirror: RegistryMirrorConfig {
+            enabled: true,
+            cache_dir: Some(AbsolutePathBuf::from_absolute_path(cache_dir.path()).unwrap()),
+            ..RegistryMirrorConfig::default()
+        },
+        ..NetworkProxySettings::default()
+    }));
+    let ctx = policy_ctx(
+        app_state.clone(),
+        NetworkMode::Full,
+        "registry.npmjs.org",
+        443,
+    );
+    let read = Request::builder()
+        .method(Method::GET)
+        .uri("/left-pad")
+        .header(HOST, "registry.npmjs.org")
+        .body(Body::empty())
+        .unwrap();
+    let publish = Request::builder()
+        .method(Method::PUT)
+        .uri("/left-pad")
+        .header(HOST, "registry.npmjs.org")
+        .body(Body::empty())
+        .unwrap();
+
+    assert!(mitm_blocking_response(&read, &ctx).await.unwrap().is_none());
+    let response = mitm_blocking_response(&publish, &ctx)
+        .await
+        .unwrap()
+        .expect("writes to a mirrored registry should be blocked");
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        response.headers().get("x-proxy-error").unwrap(),
+        "blocked-by-registry-mirror"
+    );
+    let blocked = app_state.drain_blocked().await.unwrap();
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].reason, REASON_REGISTRY_MIRROR_READ_ONLY);
+    assert_eq!(blocked[0].method.as_deref(), Some("PUT"));
+}
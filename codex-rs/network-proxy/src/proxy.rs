@@ -1,4 +1,5 @@
 use crate::config;
+use crate::connection_log::NetworkConnectionObserver;
 use crate::http_proxy;
 use crate::network_policy::NetworkPolicyDecider;
 use crate::runtime::BlockedRequestObserver;
@@ -60,6 +61,7 @@ pub struct NetworkProxyBuilder {
     managed_by_codex: bool,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
+    connection_observer: Option<Arc<dyn NetworkConnectionObserver>>,
 }
 
 impl Default for NetworkProxyBuilder {
@@ -71,6 +73,7 @@ impl Default for NetworkProxyBuilder {
             managed_by_codex: true,
             policy_decider: None,
             blocked_request_observer: None,
+            connection_observer: None,
         }
     }
 }
@@ -125,6 +128,19 @@ impl NetworkProxyBuilder {
         self
     }
 
+    pub fn connection_observer<O>(mut self, observer: O) -> Self
+    where
+        O: NetworkConnectionObserver,
+    {
+        self.connection_observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub fn connection_observer_arc(mut self, observer: Arc<dyn NetworkConnectionObserver>) -> Self {
+        self.connection_observer = Some(observer);
+        self
+    }
+
     pub async fn build(self) -> Result<NetworkProxy> {
         let state = self.state.ok_or_else(|| {
             anyhow::anyhow!(
@@ -134,6 +150,9 @@ impl NetworkProxyBuilder {
         state
             .set_blocked_request_observer(self.blocked_request_observer.clone())
             .await;
+        state
+            .set_connection_observer(self.connection_observer.clone())
+            .await;
         let current_cfg = state.current_cfg().await?;
         let (requested_http_addr, requested_socks_addr, reserved_listeners) =
             if self.managed_by_codex {
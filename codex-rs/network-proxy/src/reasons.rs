@@ -5,4 +5,7 @@ pub(crate) const REASON_NOT_ALLOWED: &str = "not_allowed";
 pub(crate) const REASON_NOT_ALLOWED_LOCAL: &str = "not_allowed_local";
 pub(crate) const REASON_POLICY_DENIED: &str = "policy_denied";
 pub(crate) const REASON_PROXY_DISABLED: &str = "proxy_disabled";
+pub(crate) const REASON_REGISTRY_MIRROR_READ_ONLY: &str = "registry_mirror_read_only";
+pub(crate) const REASON_REQUEST_RULE_DENIED: &str = "request_rule_denied";
+pub(crate) const REASON_REQUEST_RULE_NOT_ALLOWED: &str = "request_rule_not_allowed";
 pub(crate) const REASON_UNIX_SOCKET_UNSUPPORTED: &str = "unix_socket_unsupported";
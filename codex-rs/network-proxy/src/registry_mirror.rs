@@ -0,0 +1,578 @@
+//! Read-only caching mirror for public package registries (`network.registry_mirror`).
+//!
+//! `GET`/`HEAD` requests to mirrored registry hosts are answered from a content-addressed cache:
+//! response bodies live under `blobs/sha256/<digest>` and `entries/<key digest>.json` maps a request
+//! (URL plus `Accept` header) to its blob. Immutable artifacts (tarballs, wheels, `.crate` files)
+//! are served from the cache whenever present. Mutable metadata (packuments, simple indexes, sparse
+//! index files) is refreshed upstream and the cached copy is only used when upstream fails or the
+//! mirror is offline. Cached bodies are streamed from their blob and checked against the digest
+//! as they are read.
+
+use crate::config::PackageRegistry;
+use crate::config::RegistryMirrorConfig;
+use crate::upstream::UpstreamClient;
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_utils_home_dir::find_codex_home;
+use codex_utils_rustls_provider::ensure_rustls_crypto_provider;
+use rama_core::Service;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::Stream;
+use rama_core::futures::stream;
+use rama_core::futures::stream::StreamExt;
+use rama_http::Body;
+use rama_http::HeaderMap;
+use rama_http::HeaderValue;
+use rama_http::Method;
+use rama_http::Request;
+use rama_http::Response;
+use rama_http::StatusCode;
+use rama_http::header;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest as _;
+use sha2::Sha256;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::io::AsyncReadExt as _;
+use tracing::info;
+use tracing::warn;
+
+/// Header added to every mirrored response: `hit`, `miss`, `stale`, or `offline-miss`.
+pub(crate) const REGISTRY_MIRROR_HEADER: &str = "x-codex-registry-mirror";
+
+/// Larger upstream bodies are streamed through without being cached.
+const MAX_CACHED_BODY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Chunk size used when streaming a cached blob back to the client.
+const BLOB_READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Default cache location, relative to `CODEX_HOME`; sits next to the managed MITM CA.
+const REGISTRY_CACHE_DIR: [&str; 2] = ["proxy", "registry-cache"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RegistryMirrorRoute {
+    /// Not a mirrored request; the regular proxy flow applies.
+    Bypass,
+    /// Answer the request from the mirror.
+    Serve(PackageRegistry),
+    /// A write to a mirrored host that is not explicitly allowlisted.
+    ReadOnly,
+}
+
+pub struct RegistryMirror {
+    registries: Vec<PackageRegistry>,
+    offline: bool,
+    cache: RegistryCache,
+    upstream: UpstreamClient,
+}
+
+impl std::fmt::Debug for RegistryMirror {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegistryMirror")
+            .field("registries", &self.registries)
+            .field("offline", &self.offline)
+            .field("cache_dir", &self.cache.root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RegistryMirror {
+    pub(crate) fn new(config: &RegistryMirrorConfig, allow_upstream_proxy: bool) -> Result<Self> {
+        let cache_dir = match config.cache_dir.as_ref() {
+            Some(cache_dir) => cache_dir.to_path_buf(),
+            None => find_codex_home()
+                .context("failed to resolve CODEX_HOME for the registry mirror cache")?
+                .join(REGISTRY_CACHE_DIR.iter().collect::<PathBuf>()),
+        };
+        // The mirror is built with the proxy state, before `NetworkProxy::run`
+        // installs the provider, and the upstream client needs one already.
+        ensure_rustls_crypto_provider();
+        let upstream = if allow_upstream_proxy {
+            UpstreamClient::from_env_proxy()
+        } else {
+            UpstreamClient::direct()
+        };
+        Ok(Self {
+            registries: config.registries.clone(),
+            offline: config.offline,
+            cache: RegistryCache::new(cache_dir),
+            upstream,
+        })
+    }
+
+    pub(crate) fn registry_for_host(&self, host: &str) -> Option<PackageRegistry> {
+        self.registries
+            .iter()
+            .copied()
+            .find(|registry| registry.hosts().contains(&host))
+    }
+
+    /// Decides whether a request is served by the mirror.
+    ///
+    /// Authenticated requests are left to the regular proxy flow (and never cached) unless the
+    /// mirror is offline, in which case nothing may reach the network.
+    pub(crate) fn route(
+        &self,
+        host: &str,
+        method: &str,
+        headers: &HeaderMap,
+        explicitly_allowed: bool,
+    ) -> RegistryMirrorRoute {
+        let Some(registry) = self.registry_for_host(host) else {
+            return RegistryMirrorRoute::Bypass;
+        };
+        if !matches!(method, "GET" | "HEAD") {
+            return if explicitly_allowed {
+                RegistryMirrorRoute::Bypass
+            } else {
+                RegistryMirrorRoute::ReadOnly
+            };
+        }
+        if headers.contains_key(header::AUTHORIZATION) && !self.offline {
+            return RegistryMirrorRoute::Bypass;
+        }
+        RegistryMirrorRoute::Serve(registry)
+    }
+
+    pub(crate) async fn serve(
+        &self,
+        registry: PackageRegistry,
+        host: &str,
+        req: &Request,
+    ) -> Response {
+        let path_and_query = req
+            .uri()
+            .path_and_query()
+            .map(rama_http::uri::PathAndQuery::as_str)
+            .unwrap_or("/");
+        let url = format!("https://{host}{path_and_query}");
+        let accept = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let key = cache_key(&url, accept.as_deref());
+        let head = req.method() == Method::HEAD;
+        let immutable = is_immutable_artifact(registry, host, req.uri().path());
+
+        let cached = match self.cache.lookup(&key).await {
+            Ok(cached) => cached,
+            Err(err) => {
+                warn!("registry mirror cache read failed for {url}: {err}");
+                None
+            }
+        };
+        if let Some(cached) = cached.as_ref()
+            && (immutable || self.offline)
+        {
+            info!("registry mirror hit (url={url})");
+            return cached.response(CacheStatus::Hit, head);
+        }
+        if self.offline {
+            warn!("registry mirror offline miss (url={url})");
+            return text_response(
+                StatusCode::GATEWAY_TIMEOUT,
+                CacheStatus::OfflineMiss,
+                "Codex registry mirror is offline and this request is not cached.",
+            );
+        }
+
+        match self.fetch(&url, accept.as_deref()).await {
+            Ok(Fetched::Cacheable(entry)) => {
+                if let Err(err) = self.cache.store(&key, &entry).await {
+                    warn!("registry mirror cache write failed for {url}: {err}");
+                }
+                info!(
+                    "registry mirror miss (url={url}, bytes={})",
+                    entry.meta.size
+                );
+                entry.response(CacheStatus::Miss, head)
+            }
+            Ok(Fetched::Uncacheable(resp))
+                if resp.status().is_server_error() && cached.is_some() =>
+            {
+                warn!(
+                    "registry mirror serving stale copy (url={url}, upstream_status={})",
+                    resp.status()
+                );
+                stale_response(cached, head)
+            }
+            Ok(Fetched::Uncacheable(mut resp)) => {
+                resp.headers_mut().insert(
+                    REGISTRY_MIRROR_HEADER,
+                    HeaderValue::from_static(CacheStatus::Miss.as_str()),
+                );
+                resp
+            }
+            Err(err) if cached.is_some() => {
+                warn!("registry mirror serving stale copy (url={url}): {err}");
+                stale_response(cached, head)
+            }
+            Err(err) => {
+                warn!("registry mirror upstream fetch failed (url={url}): {err}");
+                text_response(
+                    StatusCode::BAD_GATEWAY,
+                    CacheStatus::Miss,
+                    "Codex registry mirror could not reach the upstream registry.",
+                )
+            }
+        }
+    }
+
+    async fn fetch(&self, url: &str, accept: Option<&str>) -> Result<Fetched> {
+        // Only forward headers that select a representation; anything else (cookies, conditional
+        // headers, compression) would make the shared cache entry depend on the requester.
+        let mut builder = Request::builder()
+            .method(Method::GET)
+            .uri(url)
+            .header(header::ACCEPT_ENCODING, "identity")
+            .header(
+                header::USER_AGENT,
+                concat!("codex-network-proxy/", env!("CARGO_PKG_VERSION")),
+            );
+        if let Some(accept) = accept {
+            builder = builder.header(header::ACCEPT, accept);
+        }
+        let req = builder
+            .body(Body::empty())
+            .with_context(|| format!("invalid registry URL {url}"))?;
+        let resp = self.upstream.serve(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Ok(Fetched::Uncacheable(resp));
+        }
+        let content_length = resp
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if content_length.is_some_and(|len| len > MAX_CACHED_BODY_BYTES as u64) {
+            return Ok(Fetched::Uncacheable(resp));
+        }
+
+        // Chunked and length-less bodies are only known to be too large once the cap is hit.
+        let (parts, body) = resp.into_parts();
+        let body = match read_body(body, MAX_CACHED_BODY_BYTES).await? {
+            ReadBody::Complete(body) => body,
+            ReadBody::TooLarge(body) => {
+                return Ok(Fetched::Uncacheable(Response::from_parts(parts, body)));
+            }
+        };
+        Ok(Fetched::Cacheable(CachedResponse {
+            meta: CachedResponseMeta::new(url, &parts.headers, &body),
+            body: CachedBody::Bytes(body),
+        }))
+    }
+}
+
+enum Fetched {
+    Cacheable(CachedResponse),
+    /// Returned to the client unchanged.
+    Uncacheable(Response),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CacheStatus {
+    Hit,
+    Miss,
+    Stale,
+    OfflineMiss,
+}
+
+impl CacheStatus {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+            Self::Stale => "stale",
+            Self::OfflineMiss => "offline-miss",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedResponseMeta {
+    url: String,
+    /// Hex SHA-256 of the body; names the blob.
+    sha256: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl CachedResponseMeta {
+    fn new(url: &str, headers: &HeaderMap, body: &[u8]) -> Self {
+        let header = |name: header::HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            url: url.to_string(),
+            sha256: sha256_hex(body),
+            size: body.len() as u64,
+            content_type: header(header::CONTENT_TYPE),
+            content_encoding: header(header::CONTENT_ENCODING),
+            etag: header(header::ETAG),
+            last_modified: header(header::LAST_MODIFIED),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedResponse {
+    meta: CachedResponseMeta,
+    body: CachedBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CachedBody {
+    /// Just fetched from upstream.
+    Bytes(Bytes),
+    /// The cached blob, read when the response body is polled.
+    Blob(PathBuf),
+}
+
+impl CachedResponse {
+    fn response(&self, status: CacheStatus, head: bool) -> Response {
+        let mut builder = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, self.meta.size)
+            .header(REGISTRY_MIRROR_HEADER, status.as_str());
+        for (name, value) in [
+            (header::CONTENT_TYPE, self.meta.content_type.as_deref()),
+            (
+                header::CONTENT_ENCODING,
+                self.meta.content_encoding.as_deref(),
+            ),
+            (header::ETAG, self.meta.etag.as_deref()),
+            (header::LAST_MODIFIED, self.meta.last_modified.as_deref()),
+        ] {
+            if let Some(value) = value {
+                builder = builder.header(name, value);
+            }
+        }
+        let body = match &self.body {
+            _ if head => Body::empty(),
+            CachedBody::Bytes(body) => Body::from(body.clone()),
+            CachedBody::Blob(path) => {
+                Body::from_stream(verified_blob_stream(path.clone(), self.meta.sha256.clone()))
+            }
+        };
+        builder.body(body).unwrap_or_else(|_| {
+            text_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                status,
+                "Codex registry mirror could not build the response.",
+            )
+        })
+    }
+}
+
+/// Streams the blob at `path`, failing the stream at the end when its contents do not match
+/// `sha256`, so a corrupted blob is never passed off as a complete response.
+fn verified_blob_stream(
+    path: PathBuf,
+    sha256: String,
+) -> impl Stream<Item = std::result::Result<Bytes, BoxError>> + Send + 'static {
+    enum State {
+        Unopened(PathBuf, String),
+        Reading(tokio::fs::File, Sha256, PathBuf, String),
+        Done,
+    }
+
+    stream::unfold(State::Unopened(path, sha256), |state| async move {
+        let (mut file, mut hasher, path, sha256) = match state {
+            State::Unopened(path, sha256) => match tokio::fs::File::open(&path).await {
+                Ok(file) => (file, Sha256::new(), path, sha256),
+                Err(err) => return Some((Err(err.into()), State::Done)),
+            },
+            State::Reading(file, hasher, path, sha256) => (file, hasher, path, sha256),
+            State::Done => return None,
+        };
+        let mut chunk = vec![0; BLOB_READ_CHUNK_BYTES];
+        match file.read(&mut chunk).await {
+            Ok(0) => {
+                if format!("{:x}", hasher.finalize()) == sha256 {
+                    return None;
+                }
+                warn!(
+                    "registry mirror blob does not match its digest; aborting response from {}",
+                    path.display()
+                );
+                let err: BoxError = format!("corrupted cache blob {}", path.display()).into();
+                Some((Err(err), State::Done))
+            }
+            Ok(read) => {
+                chunk.truncate(read);
+                hasher.update(&chunk);
+                Some((
+                    Ok(Bytes::from(chunk)),
+                    State::Reading(file, hasher, path, sha256),
+                ))
+            }
+            Err(err) => Some((Err(err.into()), State::Done)),
+        }
+    })
+}
+
+fn stale_response(cached: Option<CachedResponse>, head: bool) -> Response {
+    match cached {
+        Some(cached) => cached.response(CacheStatus::Stale, head),
+        None => text_response(
+            StatusCode::BAD_GATEWAY,
+            CacheStatus::Miss,
+            "Codex registry mirror could not reach the upstream registry.",
+        ),
+    }
+}
+
+struct RegistryCache {
+    root: PathBuf,
+}
+
+impl RegistryCache {
+    fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.root.join("entries").join(format!("{key}.json"))
+    }
+
+    fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.root.join("blobs").join("sha256").join(sha256)
+    }
+
+    async fn lookup(&self, key: &str) -> Result<Option<CachedResponse>> {
+        let entry_path = self.entry_path(key);
+        let meta = match tokio::fs::read(&entry_path).await {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("failed to read {}", entry_path.display()));
+            }
+        };
+        let meta: CachedResponseMeta = serde_json::from_slice(&meta)
+            .with_context(|| format!("invalid cache entry {}", entry_path.display()))?;
+        let blob_path = self.blob_path(&meta.sha256);
+        let size = match tokio::fs::metadata(&blob_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", blob_path.display()));
+            }
+        };
+        // The digest is checked while the body streams; a size mismatch is caught up front.
+        if size != meta.size {
+            warn!(
+                "registry mirror blob does not match its recorded size; ignoring {}",
+                blob_path.display()
+            );
+            return Ok(None);
+        }
+        Ok(Some(CachedResponse {
+            meta,
+            body: CachedBody::Blob(blob_path),
+        }))
+    }
+
+    async fn store(&self, key: &str, entry: &CachedResponse) -> Result<()> {
+        let blob_path = self.blob_path(&entry.meta.sha256);
+        if let CachedBody::Bytes(body) = &entry.body
+            && !tokio::fs::try_exists(&blob_path).await?
+        {
+            write_atomically(&blob_path, body).await?;
+        }
+        let meta = serde_json::to_vec_pretty(&entry.meta)?;
+        write_atomically(&self.entry_path(key), &meta).await
+    }
+}
+
+/// Writes via a sibling temp file and rename so concurrent readers never see partial files.
+async fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow!("cache path has no parent: {}", path.display()))?;
+    tokio::fs::create_dir_all(parent)
+        .await
+        .with_context(|| format!("failed to create {}", parent.display()))?;
+    let temp_path = parent.join(format!(
+        ".tmp-{}-{}",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&temp_path, contents)
+        .await
+        .with_context(|| format!("failed to write {}", temp_path.display()))?;
+    if let Err(err) = tokio::fs::rename(&temp_path, path).await {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(err).with_context(|| format!("failed to write {}", path.display()));
+    }
+    Ok(())
+}
+
+enum ReadBody {
+    Complete(Bytes),
+    /// The body outgrew the cap: what was read so far followed by the rest of the stream.
+    TooLarge(Body),
+}
+
+/// Buffers `body` unless it is larger than `max_bytes`, in which case reading stops at the cap.
+async fn read_body(body: Body, max_bytes: usize) -> Result<ReadBody> {
+    let mut stream = body.into_data_stream();
+    let mut buf = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|err| anyhow!("failed to read upstream body: {err}"))?;
+        buf.extend_from_slice(&chunk);
+        if buf.len() > max_bytes {
+            let read = stream::once(std::future::ready(Ok(Bytes::from(buf))));
+            return Ok(ReadBody::TooLarge(Body::from_stream(read.chain(stream))));
+        }
+    }
+    Ok(ReadBody::Complete(Bytes::from(buf)))
+}
+
+fn cache_key(url: &str, accept: Option<&str>) -> String {
+    sha256_hex(format!("{url}\n{}", accept.unwrap_or_default()).as_bytes())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Whether the URL names a published artifact whose contents never change.
+fn is_immutable_artifact(registry: PackageRegistry, host: &str, path: &str) -> bool {
+    match registry {
+        PackageRegistry::Npm => path.contains("/-/") && path.ends_with(".tgz"),
+        PackageRegistry::Pypi => host == "files.pythonhosted.org",
+        PackageRegistry::Crates => host == "static.crates.io",
+    }
+}
+
+fn text_response(status: StatusCode, cache_status: CacheStatus, body: &'static str) -> Response {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .header(REGISTRY_MIRROR_HEADER, cache_status.as_str())
+        .body(Body::from(body))
+        .unwrap_or_else(|_| Response::new(Body::from(body)))
+}
+
+#[cfg(test)]
+#[path = "registry_mirror_tests.rs"]
+mod tests;
@@ -0,0 +1,312 @@
+use super::*;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn mirror(cache_dir: &TempDir, offline: bool) -> RegistryMirror {
+    RegistryMirror::new(
+        &RegistryMirrorConfig {
+            enabled: true,
+            offline,
+            cache_dir: Some(AbsolutePathBuf::from_absolute_path(cache_dir.path()).unwrap()),
+            ..RegistryMirrorConfig::default()
+        },
+        false,
+    )
+    .unwrap()
+}
+
+fn cached_response(url: &str, body: &'static [u8]) -> CachedResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+    );
+    CachedResponse {
+        meta: CachedResponseMeta::new(url, &headers, body),
+        body: CachedBody::Bytes(Bytes::from_static(body)),
+    }
+}
+
+fn get(uri: &str) -> Request {
+    Request::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn body_bytes(resp: Response) -> Bytes {
+    match read_body(resp.into_body(), MAX_CACHED_BODY_BYTES)
+        .await
+        .unwrap()
+    {
+        ReadBody::Complete(body) => body,
+        ReadBody::TooLarge(_) => panic!("test body exceeded the cache cap"),
+    }
+}
+
+#[test]
+fn route_serves_reads_and_keeps_writes_read_only() {
+    let cache_dir = TempDir::new().unwrap();
+    let mirror = mirror(&cache_dir, false);
+    let headers = HeaderMap::new();
+
+    assert_eq!(
+        mirror.route("registry.npmjs.org", "GET", &headers, false),
+        RegistryMirrorRoute::Serve(PackageRegistry::Npm)
+    );
+    assert_eq!(
+        mirror.route("files.pythonhosted.org", "HEAD", &headers, false),
+        RegistryMirrorRoute::Serve(PackageRegistry::Pypi)
+    );
+    assert_eq!(
+        mirror.route("registry.npmjs.org", "PUT", &headers, false),
+        RegistryMirrorRoute::ReadOnly
+    );
+    assert_eq!(
+        mirror.route("registry.npmjs.org", "PUT", &headers, true),
+        RegistryMirrorRoute::Bypass
+    );
+    assert_eq!(
+        mirror.route("example.com", "GET", &headers, false),
+        RegistryMirrorRoute::Bypass
+    );
+}
+
+#[test]
+fn route_leaves_authenticated_reads_upstream_unless_offline() {
+    let cache_dir = TempDir::new().unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer t"));
+
+    assert_eq!(
+        mirror(&cache_dir, false).route("registry.npmjs.org", "GET", &headers, false),
+        RegistryMirrorRoute::Bypass
+    );
+    assert_eq!(
+        mirror(&cache_dir, true).route("registry.npmjs.org", "GET", &headers, false),
+        RegistryMirrorRoute::Serve(PackageRegistry::Npm)
+    );
+}
+
+#[test]
+fn route_ignores_registries_that_are_not_mirrored() {
+    let cache_dir = TempDir::new().unwrap();
+    let mirror = RegistryMirror::new(
+        &RegistryMirrorConfig {
+            enabled: true,
+            registries: vec![PackageRegistry::Crates],
+            cache_dir: Some(AbsolutePathBuf::from_absolute_path(cache_dir.path()).unwrap()),
+            ..RegistryMirrorConfig::default()
+        },
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        mirror.route("registry.npmjs.org", "GET", &HeaderMap::new(), false),
+        RegistryMirrorRoute::Bypass
+    );
+    assert_eq!(
+        mirror.registry_for_host("static.crates.io"),
+        Some(PackageRegistry::Crates)
+    );
+}
+
+#[test]
+fn immutable_artifacts_are_classified_per_registry() {
+    assert!(is_immutable_artifact(
+        PackageRegistry::Npm,
+        "registry.npmjs.org",
+        "/left-pad/-/left-pad-1.3.0.tgz"
+    ));
+    assert!(!is_immutable_artifact(
+        PackageRegistry::Npm,
+        "registry.npmjs.org",
+        "/left-pad"
+    ));
+    assert!(is_immutable_artifact(
+        PackageRegistry::Pypi,
+        "files.pythonhosted.org",
+        "/packages/ab/cd/requests-2.32.0-py3-none-any.whl"
+    ));
+    assert!(!is_immutable_artifact(
+        PackageRegistry::Pypi,
+        "pypi.org",
+        "/simple/requests/"
+    ));
+    assert!(is_immutable_artifact(
+        PackageRegistry::Crates,
+        "static.crates.io",
+        "/crates/serde/serde-1.0.0.crate"
+    ));
+    assert!(!is_immutable_artifact(
+        PackageRegistry::Crates,
+        "index.crates.io",
+        "/se/rd/serde"
+    ));
+}
+
+#[tokio::test]
+async fn cache_round_trips_entries_by_content_digest() {
+    let cache_dir = TempDir::new().unwrap();
+    let cache = RegistryCache::new(cache_dir.path().to_path_buf());
+    let entry = cached_response("https://registry.npmjs.org/left-pad", b"{}");
+    let key = cache_key("https://registry.npmjs.org/left-pad", None);
+
+    assert_eq!(cache.lookup(&key).await.unwrap(), None);
+    cache.store(&key, &entry).await.unwrap();
+
+    assert_eq!(
+        cache.lookup(&key).await.unwrap(),
+        Some(CachedResponse {
+            meta: entry.meta.clone(),
+            body: CachedBody::Blob(cache.blob_path(&entry.meta.sha256)),
+        })
+    );
+    assert!(cache.blob_path(&entry.meta.sha256).exists());
+    assert_eq!(
+        cache
+            .lookup(&cache_key(
+                "https://registry.npmjs.org/left-pad",
+                Some("application/vnd.npm.install-v1+json"),
+            ))
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn cache_ignores_blobs_that_fail_integrity_check() {
+    let cache_dir = TempDir::new().unwrap();
+    let cache = RegistryCache::new(cache_dir.path().to_path_buf());
+    let entry = cached_response("https://static.crates.io/crates/a/a-1.0.0.crate", b"crate");
+    let key = cache_key(&entry.meta.url, None);
+    cache.store(&key, &entry).await.unwrap();
+
+    std::fs::write(cache.blob_path(&entry.meta.sha256), b"tampered").unwrap();
+
+    assert_eq!(cache.lookup(&key).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn cached_blob_that_fails_integrity_check_aborts_the_response() {
+    let cache_dir = TempDir::new().unwrap();
+    let cache = RegistryCache::new(cache_dir.path().to_path_buf());
+    let entry = cached_response("https://static.crates.io/crates/a/a-1.0.0.crate", b"crate");
+    let key = cache_key(&entry.meta.url, None);
+    cache.store(&key, &entry).await.unwrap();
+
+    // Same size, so only the digest check can catch it.
+    std::fs::write(cache.blob_path(&entry.meta.sha256), b"CRATE").unwrap();
+
+    let resp = cache
+        .lookup(&key)
+        .await
+        .unwrap()
+        .expect("size still matches")
+        .response(CacheStatus::Hit, false);
+    assert!(
+        read_body(resp.into_body(), MAX_CACHED_BODY_BYTES)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn read_body_stops_at_the_cap_and_keeps_the_rest_streamable() {
+    let chunks =
+        ["abc", "def", "ghij"].map(|chunk| Ok::<_, BoxError>(Bytes::from_static(chunk.as_bytes())));
+    let body = Body::from_stream(stream::iter(chunks));
+
+    let ReadBody::TooLarge(body) = read_body(body, 4).await.unwrap() else {
+        panic!("expected the body to exceed the cap");
+    };
+    assert_eq!(
+        body_bytes(Response::new(body)).await,
+        Bytes::from_static(b"abcdefghij")
+    );
+
+    let body = Body::from_stream(stream::iter(
+        ["abc", "def"].map(|chunk| Ok::<_, BoxError>(Bytes::from_static(chunk.as_bytes()))),
+    ));
+    let ReadBody::Complete(body) = read_body(body, 6).await.unwrap() else {
+        panic!("expected the body to fit under the cap");
+    };
+    assert_eq!(body, Bytes::from_static(b"abcdef"));
+}
+
+#[tokio::test]
+async fn offline_mirror_serves_seeded_cache_without_network() {
+    let cache_dir = TempDir::new().unwrap();
+    let mirror = mirror(&cache_dir, true);
+    let url = "https://registry.npmjs.org/left-pad";
+    mirror
+        .cache
+        .store(
+            &cache_key(url, None),
+            &cached_response(url, b"{\"name\":\"left-pad\"}"),
+        )
+        .await
+        .unwrap();
+
+    let resp = mirror
+        .serve(
+            PackageRegistry::Npm,
+            "registry.npmjs.org",
+            &get("/left-pad"),
+        )
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(REGISTRY_MIRROR_HEADER).unwrap(), "hit");
+    assert_eq!(
+        body_bytes(resp).await,
+        Bytes::from_static(b"{\"name\":\"left-pad\"}")
+    );
+}
+
+#[tokio::test]
+async fn offline_mirror_rejects_cache_misses() {
+    let cache_dir = TempDir::new().unwrap();
+    let mirror = mirror(&cache_dir, true);
+
+    let resp = mirror
+        .serve(PackageRegistry::Npm, "registry.npmjs.org", &get("/is-odd"))
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(
+        resp.headers().get(REGISTRY_MIRROR_HEADER).unwrap(),
+        "offline-miss"
+    );
+}
+
+#[tokio::test]
+async fn head_requests_get_headers_without_body() {
+    let cache_dir = TempDir::new().unwrap();
+    let mirror = mirror(&cache_dir, false);
+    let url = "https://static.crates.io/crates/a/a-1.0.0.crate";
+    mirror
+        .cache
+        .store(&cache_key(url, None), &cached_response(url, b"crate"))
+        .await
+        .unwrap();
+    let req = Request::builder()
+        .method(Method::HEAD)
+        .uri("/crates/a/a-1.0.0.crate")
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = mirror
+        .serve(PackageRegistry::Crates, "static.crates.io", &req)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(header::CONTENT_LENGTH).unwrap(), "5");
+    assert_eq!(resp.headers().get(REGISTRY_MIRROR_HEADER).unwrap(), "hit");
+    assert_eq!(body_bytes(resp).await, Bytes::new());
+}
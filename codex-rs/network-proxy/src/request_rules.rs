@@ -0,0 +1,412 @@
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleAction;
+use crate::policy::compile_globset;
+use crate::reasons::REASON_REQUEST_RULE_DENIED;
+use crate::reasons::REASON_REQUEST_RULE_NOT_ALLOWED;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::ensure;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use globset::GlobSet;
+
+/// Compiled form of `network.request_rules`.
+#[derive(Debug, Clone, Default)]
+pub struct RequestRules {
+    rules: Vec<CompiledRequestRule>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRequestRule {
+    domains: GlobSet,
+    methods: Vec<String>,
+    path: Option<GlobMatcher>,
+    action: NetworkRequestRuleAction,
+    label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RequestRuleDecision {
+    /// No rule targets the host; the network mode decides.
+    NoRules,
+    /// An allow rule matched the request.
+    Allowed { rule: String },
+    /// A deny rule matched the request.
+    Denied { rule: String },
+    /// The host has allow rules, but none of them matched the request.
+    NotAllowed,
+    /// The host has rules, and the path could not be normalized unambiguously
+    /// (see [`normalize_path`]).
+    AmbiguousPath,
+}
+
+impl RequestRuleDecision {
+    pub(crate) fn block_reason(&self) -> Option<&'static str> {
+        match self {
+            Self::Denied { .. } | Self::AmbiguousPath => Some(REASON_REQUEST_RULE_DENIED),
+            Self::NotAllowed => Some(REASON_REQUEST_RULE_NOT_ALLOWED),
+            Self::NoRules | Self::Allowed { .. } => None,
+        }
+    }
+
+    pub(crate) fn rule(&self) -> Option<&str> {
+        match self {
+            Self::Allowed { rule } | Self::Denied { rule } => Some(rule),
+            Self::NoRules | Self::NotAllowed | Self::AmbiguousPath => None,
+        }
+    }
+
+    /// Whether the request may skip the network mode's method restriction.
+    pub(crate) fn overrides_mode(&self) -> bool {
+        matches!(self, Self::Allowed { .. })
+    }
+}
+
+impl RequestRules {
+    pub(crate) fn compile(rules: &[NetworkRequestRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                CompiledRequestRule::compile(rule)
+                    .with_context(|| format!("invalid network.request_rules[{index}]"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Returns true when any rule targets `host`, meaning HTTPS traffic to it must be terminated
+    /// so the inner requests can be checked.
+    pub(crate) fn applies_to_host(&self, host: &str) -> bool {
+        self.rules.iter().any(|rule| rule.domains.is_match(host))
+    }
+
+    /// Evaluates rules for a request. Deny rules win over allow rules regardless of order.
+    /// Paths are matched in their [normalized](normalize_path) form.
+    pub(crate) fn evaluate(&self, host: &str, method: &str, path: &str) -> RequestRuleDecision {
+        let mut host_rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.domains.is_match(host))
+            .peekable();
+        if host_rules.peek().is_none() {
+            return RequestRuleDecision::NoRules;
+        }
+        let Some(path) = normalize_path(path) else {
+            return RequestRuleDecision::AmbiguousPath;
+        };
+        let path = path.as_str();
+
+        let mut host_has_allow_rules = false;
+        let mut allowed_by = None;
+        for rule in host_rules {
+            match rule.action {
+                NetworkRequestRuleAction::Deny => {
+                    if rule.matches_request(method, path) {
+                        return RequestRuleDecision::Denied {
+                            rule: rule.label.clone(),
+                        };
+                    }
+                }
+                NetworkRequestRuleAction::Allow => {
+                    host_has_allow_rules = true;
+                    if allowed_by.is_none() && rule.matches_request(method, path) {
+                        allowed_by = Some(rule.label.clone());
+                    }
+                }
+            }
+        }
+
+        match allowed_by {
+            Some(rule) => RequestRuleDecision::Allowed { rule },
+            None if host_has_allow_rules => RequestRuleDecision::NotAllowed,
+            None => RequestRuleDecision::NoRules,
+        }
+    }
+}
+
+impl CompiledRequestRule {
+    fn compile(rule: &NetworkRequestRule) -> Result<Self> {
+        let domains = compile_globset(std::slice::from_ref(&rule.domain))?;
+        let methods = rule
+            .methods
+            .iter()
+            .map(|method| method.trim().to_ascii_uppercase())
+            .collect::<Vec<_>>();
+        ensure!(
+            methods.iter().all(|method| !method.is_empty()),
+            "request rule methods must not be empty strings"
+        );
+        let path = rule
+            .path
+            .as_deref()
+            .map(|path| {
+                ensure!(
+                    path.starts_with('/'),
+                    "request rule path must start with '/': {path}"
+                );
+                // `*` intentionally matches across `/` so `/api/*` covers the whole subtree.
+                Ok(GlobBuilder::new(path)
+                    .build()
+                    .with_context(|| format!("invalid request rule path glob: {path}"))?
+                    .compile_matcher())
+            })
+            .transpose()?;
+
+        Ok(Self {
+            domains,
+            label: rule_label(rule, &methods),
+            methods,
+            path,
+            action: rule.action,
+        })
+    }
+
+    fn matches_request(&self, method: &str, path: &str) -> bool {
+        let method_matches = self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(method));
+        let path_matches = self
+            .path
+            .as_ref()
+            .is_none_or(|matcher| matcher.is_match(path));
+        method_matches && path_matches
+    }
+}
+
+/// The path a server most likely routes `path` to: unreserved characters are
+/// percent-decoded, repeated slashes collapsed and dot segments resolved, so
+/// `//admin/x`, `/%61dmin/x` and `/x/../admin/x` all become `/admin/x`.
+/// Returns `None` where servers disagree: an encoded `/` or `\`, a backslash,
+/// or a malformed escape.
+fn normalize_path(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => return None,
+            b'%' => {
+                let hex = bytes.get(index + 1..index + 3)?;
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return None;
+                }
+                let byte = u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?;
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                    decoded.push(byte);
+                } else if matches!(byte, b'/' | b'\\') {
+                    return None;
+                } else {
+                    decoded.push(b'%');
+                    decoded.extend(hex.to_ascii_uppercase());
+                }
+                index += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    let decoded = String::from_utf8(decoded).ok()?;
+
+    let mut segments = Vec::new();
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    let ends_in_directory =
+        decoded.ends_with('/') || decoded.ends_with("/.") || decoded.ends_with("/..");
+    if ends_in_directory && !segments.is_empty() {
+        normalized.push('/');
+    }
+    Some(normalized)
+}
+
+fn rule_label(rule: &NetworkRequestRule, methods: &[String]) -> String {
+    let action = match rule.action {
+        NetworkRequestRuleAction::Allow => "allow",
+        NetworkRequestRuleAction::Deny => "deny",
+    };
+    let methods = if methods.is_empty() {
+        "*".to_string()
+    } else {
+        methods.join(",")
+    };
+    let path = rule.path.as_deref().unwrap_or("/**");
+    format!("{action} {methods} {}{path}", rule.domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn rule(
+        domain: &str,
+        methods: &[&str],
+        path: Option<&str>,
+        action: NetworkRequestRuleAction,
+    ) -> NetworkRequestRule {
+        NetworkRequestRule {
+            domain: domain.to_string(),
+            methods: methods.iter().map(ToString::to_string).collect(),
+            path: path.map(str::to_string),
+            action,
+        }
+    }
+
+    #[test]
+    fn evaluate_matches_method_and_path_globs() {
+        let rules = RequestRules::compile(&[
+            rule(
+                "registry.npmjs.org",
+                &["post"],
+                Some("/-/npm/v1/security/*"),
+                NetworkRequestRuleAction::Allow,
+            ),
+            rule(
+                "registry.npmjs.org",
+                &["GET", "HEAD"],
+                None,
+                NetworkRequestRuleAction::Allow,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate(
+                "registry.npmjs.org",
+                "POST",
+                "/-/npm/v1/security/advisories/bulk"
+            ),
+            RequestRuleDecision::Allowed {
+                rule: "allow POST registry.npmjs.org/-/npm/v1/security/*".to_string(),
+            }
+        );
+        assert_eq!(
+            rules.evaluate("registry.npmjs.org", "GET", "/left-pad"),
+            RequestRuleDecision::Allowed {
+                rule: "allow GET,HEAD registry.npmjs.org/**".to_string(),
+            }
+        );
+        assert_eq!(
+            rules.evaluate("registry.npmjs.org", "PUT", "/left-pad"),
+            RequestRuleDecision::NotAllowed
+        );
+        assert_eq!(
+            rules.evaluate("github.com", "POST", "/"),
+            RequestRuleDecision::NoRules
+        );
+    }
+
+    #[test]
+    fn evaluate_deny_wins_over_allow() {
+        let rules = RequestRules::compile(&[
+            rule("**.github.com", &[], None, NetworkRequestRuleAction::Allow),
+            rule(
+                "api.github.com",
+                &["DELETE"],
+                None,
+                NetworkRequestRuleAction::Deny,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate("api.github.com", "DELETE", "/repos/o/r"),
+            RequestRuleDecision::Denied {
+                rule: "deny DELETE api.github.com/**".to_string(),
+            }
+        );
+        assert!(rules.applies_to_host("github.com"));
+        assert!(!rules.applies_to_host("example.com"));
+    }
+
+    #[test]
+    fn evaluate_matches_normalized_paths() {
+        let rules = RequestRules::compile(&[
+            rule("example.com", &[], None, NetworkRequestRuleAction::Allow),
+            rule(
+                "example.com",
+                &[],
+                Some("/admin/*"),
+                NetworkRequestRuleAction::Deny,
+            ),
+        ])
+        .unwrap();
+        let denied = RequestRuleDecision::Denied {
+            rule: "deny * example.com/admin/*".to_string(),
+        };
+
+        for path in [
+            "/admin/x",
+            "//admin/x",
+            "/%61dmin/x",
+            "/%61%64min/x",
+            "/x/../admin/y",
+            "/./admin/./y",
+            "/../admin/y",
+        ] {
+            assert_eq!(rules.evaluate("example.com", "GET", path), denied, "{path}");
+        }
+        for path in [
+            "/admin%2Fx",
+            "/admin%5cx",
+            "/admin\\x",
+            "/admin/%zz",
+            "/admin/%+1",
+        ] {
+            assert_eq!(
+                rules.evaluate("example.com", "GET", path),
+                RequestRuleDecision::AmbiguousPath,
+                "{path}"
+            );
+        }
+        assert_eq!(
+            rules.evaluate("example.com", "GET", "/administrator/../public/%20x"),
+            RequestRuleDecision::Allowed {
+                rule: "allow * example.com/**".to_string(),
+            }
+        );
+        // Hosts without rules are not affected.
+        assert_eq!(
+            rules.evaluate("other.com", "GET", "/admin%2Fx"),
+            RequestRuleDecision::NoRules
+        );
+    }
+
+    #[test]
+    fn normalize_path_keeps_trailing_slashes_and_other_escapes() {
+        assert_eq!(normalize_path("/a//b/./c/../"), Some("/a/b/".to_string()));
+        assert_eq!(normalize_path("/a/b/.."), Some("/a/".to_string()));
+        assert_eq!(normalize_path("/.."), Some("/".to_string()));
+        assert_eq!(normalize_path(""), Some("/".to_string()));
+        assert_eq!(normalize_path("/a%3fb%7E"), Some("/a%3Fb~".to_string()));
+    }
+
+    #[test]
+    fn compile_rejects_relative_paths() {
+        let err = RequestRules::compile(&[rule(
+            "github.com",
+            &["GET"],
+            Some("api/*"),
+            NetworkRequestRuleAction::Allow,
+        )])
+        .unwrap_err();
+
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid network.request_rules[0]: request rule path must start with '/': api/*"
+        );
+    }
+}
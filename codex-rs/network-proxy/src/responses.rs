@@ -6,6 +6,9 @@ use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_REGISTRY_MIRROR_READ_ONLY;
+use crate::reasons::REASON_REQUEST_RULE_DENIED;
+use crate::reasons::REASON_REQUEST_RULE_NOT_ALLOWED;
 use rama_http::Body;
 use rama_http::Response;
 use rama_http::StatusCode;
@@ -53,6 +56,8 @@ pub fn blocked_header_value(reason: &str) -> &'static str {
         REASON_DENIED => "blocked-by-denylist",
         REASON_METHOD_NOT_ALLOWED => "blocked-by-method-policy",
         REASON_MITM_REQUIRED => "blocked-by-mitm-required",
+        REASON_REGISTRY_MIRROR_READ_ONLY => "blocked-by-registry-mirror",
+        REASON_REQUEST_RULE_DENIED | REASON_REQUEST_RULE_NOT_ALLOWED => "blocked-by-request-rule",
         _ => "blocked-by-policy",
     }
 }
@@ -69,7 +74,18 @@ pub fn blocked_message(reason: &str) -> &'static str {
         REASON_METHOD_NOT_ALLOWED => {
             "Codex blocked this request: method not allowed in limited mode."
         }
-        REASON_MITM_REQUIRED => "Codex blocked this request: MITM required for limited HTTPS.",
+        REASON_MITM_REQUIRED => {
+            "Codex blocked this request: MITM required to enforce HTTPS method policy."
+        }
+        REASON_REGISTRY_MIRROR_READ_ONLY => {
+            "Codex blocked this request: the package registry mirror is read-only."
+        }
+        REASON_REQUEST_RULE_DENIED => {
+            "Codex blocked this request: method/path denied by a network request rule."
+        }
+        REASON_REQUEST_RULE_NOT_ALLOWED => {
+            "Codex blocked this request: method/path not allowed by the request rules for this domain."
+        }
         _ => "Codex blocked this request by network policy.",
     }
 }
@@ -82,6 +98,37 @@ pub fn blocked_text_response(reason: &str) -> Response {
         .body(Body::from(blocked_message(reason)))
         .unwrap_or_else(|_| Response::new(Body::from("blocked")))
 }
+
+/// Like [`blocked_message`], but names the request and, when one matched, the rule that blocked it.
+pub fn blocked_request_rule_message(
+    reason: &str,
+    method: &str,
+    path: &str,
+    rule: Option<&str>,
+) -> String {
+    let message = blocked_message(reason);
+    match rule {
+        Some(rule) => format!("{message} (request: {method} {path}; rule: {rule})"),
+        None => format!("{message} (request: {method} {path})"),
+    }
+}
+
+pub fn blocked_request_rule_response(
+    reason: &str,
+    method: &str,
+    path: &str,
+    rule: Option<&str>,
+) -> Response {
+    Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .header("content-type", "text/plain")
+        .header("x-proxy-error", blocked_header_value(reason))
+        .body(Body::from(blocked_request_rule_message(
+            reason, method, path, rule,
+        )))
+        .unwrap_or_else(|_| Response::new(Body::from("blocked")))
+}
+
 pub fn blocked_message_with_policy(reason: &str, details: &PolicyDecisionDetails<'_>) -> String {
     let _ = (details.reason, details.host);
     blocked_message(reason).to_string()
@@ -122,4 +169,21 @@ mod tests {
             "Codex blocked this request: domain not in allowlist (this is not a denylist block)."
         );
     }
+
+    #[test]
+    fn blocked_request_rule_message_names_request_and_rule() {
+        assert_eq!(
+            blocked_request_rule_message(
+                REASON_REQUEST_RULE_DENIED,
+                "DELETE",
+                "/repos/o/r",
+                Some("deny DELETE api.github.com/**"),
+            ),
+            "Codex blocked this request: method/path denied by a network request rule. (request: DELETE /repos/o/r; rule: deny DELETE api.github.com/**)"
+        );
+        assert_eq!(
+            blocked_header_value(REASON_REQUEST_RULE_NOT_ALLOWED),
+            "blocked-by-request-rule"
+        );
+    }
 }
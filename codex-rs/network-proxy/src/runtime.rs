@@ -1,6 +1,8 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::config::ValidatedUnixSocketPath;
+use crate::connection_log::NetworkConnectionObserver;
+use crate::connection_log::NetworkConnectionRecord;
 use crate::mitm::MitmState;
 use crate::policy::Host;
 use crate::policy::is_loopback_host;
@@ -9,6 +11,10 @@ use crate::policy::normalize_host;
 use crate::reasons::REASON_DENIED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::registry_mirror::RegistryMirror;
+use crate::registry_mirror::RegistryMirrorRoute;
+use crate::request_rules::RequestRuleDecision;
+use crate::request_rules::RequestRules;
 use crate::state::NetworkProxyConstraintError;
 use crate::state::NetworkProxyConstraints;
 use crate::state::build_config_state;
@@ -18,6 +24,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use globset::GlobSet;
+use rama_http::HeaderMap;
 use serde::Serialize;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -94,6 +101,9 @@ pub struct BlockedRequest {
     pub source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
+    /// Label of the request rule that blocked the request, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
     pub timestamp: i64,
 }
 
@@ -132,9 +142,15 @@ impl BlockedRequest {
             decision,
             source,
             port,
+            rule: None,
             timestamp: unix_timestamp(),
         }
     }
+
+    pub fn with_rule(mut self, rule: Option<String>) -> Self {
+        self.rule = rule;
+        self
+    }
 }
 
 fn blocked_request_violation_log_line(entry: &BlockedRequest) -> String {
@@ -155,7 +171,9 @@ pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
     pub deny_set: GlobSet,
+    pub request_rules: RequestRules,
     pub mitm: Option<Arc<MitmState>>,
+    pub registry_mirror: Option<Arc<RegistryMirror>>,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
     pub blocked_total: u64,
@@ -200,6 +218,7 @@ pub struct NetworkProxyState {
     state: Arc<RwLock<ConfigState>>,
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
+    connection_observer: Arc<RwLock<Option<Arc<dyn NetworkConnectionObserver>>>>,
     audit_metadata: NetworkProxyAuditMetadata,
 }
 
@@ -217,6 +236,7 @@ impl Clone for NetworkProxyState {
             state: self.state.clone(),
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
+            connection_observer: self.connection_observer.clone(),
             audit_metadata: self.audit_metadata.clone(),
         }
     }
@@ -267,6 +287,7 @@ impl NetworkProxyState {
             state: Arc::new(RwLock::new(state)),
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
+            connection_observer: Arc::new(RwLock::new(None)),
             audit_metadata,
         }
     }
@@ -279,6 +300,32 @@ impl NetworkProxyState {
         *observer = blocked_request_observer;
     }
 
+    pub async fn set_connection_observer(
+        &self,
+        connection_observer: Option<Arc<dyn NetworkConnectionObserver>>,
+    ) {
+        let mut observer = self.connection_observer.write().await;
+        *observer = connection_observer;
+    }
+
+    /// Asks the observer, if one is registered, what a connection opening now
+    /// should be attributed to.
+    pub async fn connection_opened(&self) -> Option<String> {
+        let observer = self.connection_observer.read().await.clone();
+        match observer {
+            Some(observer) => observer.on_connection_open().await,
+            None => None,
+        }
+    }
+
+    /// Forwards a connection record to the observer, if one is registered.
+    pub async fn record_connection(&self, record: NetworkConnectionRecord) {
+        let observer = self.connection_observer.read().await.clone();
+        if let Some(observer) = observer {
+            observer.on_connection(record).await;
+        }
+    }
+
     pub fn audit_metadata(&self) -> &NetworkProxyAuditMetadata {
         &self.audit_metadata
     }
@@ -340,7 +387,14 @@ impl NetworkProxyState {
             Ok(host) => host,
             Err(_) => return Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed)),
         };
-        let (deny_set, allow_set, allow_local_binding, allowed_domains_empty, allowed_domains) = {
+        let (
+            deny_set,
+            allow_set,
+            allow_local_binding,
+            allowed_domains_empty,
+            allowed_domains,
+            registry_mirror,
+        ) = {
             let guard = self.state.read().await;
             (
                 guard.deny_set.clone(),
@@ -348,6 +402,7 @@ impl NetworkProxyState {
                 guard.config.network.allow_local_binding,
                 guard.config.network.allowed_domains.is_empty(),
                 guard.config.network.allowed_domains.clone(),
+                guard.registry_mirror.clone(),
             )
         };
 
@@ -356,12 +411,16 @@ impl NetworkProxyState {
         // Decision order matters:
         //  1) explicit deny always wins
         //  2) local/private networking is opt-in (defense-in-depth)
-        //  3) allowlist is enforced when configured
+        //  3) allowlist is enforced when configured (mirrored registries count as allowlisted;
+        //     the mirror itself keeps them read-only)
         if deny_set.is_match(host_str) {
             return Ok(HostBlockDecision::Blocked(HostBlockReason::Denied));
         }
 
-        let is_allowlisted = allow_set.is_match(host_str);
+        let is_mirrored = registry_mirror
+            .as_ref()
+            .is_some_and(|mirror| mirror.registry_for_host(host_str).is_some());
+        let is_allowlisted = allow_set.is_match(host_str) || is_mirrored;
         if !allow_local_binding {
             // If the intent is "prevent access to local/internal networks", we must not rely solely
             // on string checks like `localhost` / `127.0.0.1`. Attackers can use DNS rebinding or
@@ -394,7 +453,7 @@ impl NetworkProxyState {
             }
         }
 
-        if allowed_domains_empty || !is_allowlisted {
+        if (allowed_domains_empty && !is_mirrored) || !is_allowlisted {
             Ok(HostBlockDecision::Blocked(HostBlockReason::NotAllowed))
         } else {
             Ok(HostBlockDecision::Allowed)
@@ -433,6 +492,11 @@ impl NetworkProxyState {
         debug!("{violation_line}");
         drop(guard);
 
+        self.record_connection(NetworkConnectionRecord {
+            opened_by: self.connection_opened().await,
+            ..NetworkConnectionRecord::from_blocked(&blocked_for_observer)
+        })
+        .await;
         if let Some(observer) = blocked_request_observer {
             observer.on_blocked_request(blocked_for_observer).await;
         }
@@ -515,12 +579,35 @@ impl NetworkProxyState {
         Ok(guard.config.network.mode.allows_method(method))
     }
 
+    pub(crate) async fn request_rule_decision(
+        &self,
+        host: &str,
+        method: &str,
+        path: &str,
+    ) -> Result<RequestRuleDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.evaluate(host, method, path))
+    }
+
+    pub async fn has_request_rules(&self, host: &str) -> Result<bool> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.applies_to_host(host))
+    }
+
     pub async fn allow_upstream_proxy(&self) -> Result<bool> {
         self.reload_if_needed().await?;
         let guard = self.state.read().await;
         Ok(guard.config.network.allow_upstream_proxy)
     }
 
+    pub async fn capture_har(&self) -> Result<bool> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.config.network.capture_har)
+    }
+
     pub async fn network_mode(&self) -> Result<NetworkMode> {
         self.reload_if_needed().await?;
         let guard = self.state.read().await;
@@ -558,6 +645,46 @@ impl NetworkProxyState {
         Ok(guard.mitm.clone())
     }
 
+    /// Returns the mirror when `host` is one of its registries, together with how the request
+    /// should be routed.
+    pub(crate) async fn registry_mirror_route(
+        &self,
+        host: &str,
+        method: &str,
+        headers: &HeaderMap,
+    ) -> Result<Option<(Arc<RegistryMirror>, RegistryMirrorRoute)>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        let Some(mirror) = guard.registry_mirror.clone() else {
+            return Ok(None);
+        };
+        let explicitly_allowed = guard.allow_set.is_match(host);
+        match mirror.route(host, method, headers, explicitly_allowed) {
+            RegistryMirrorRoute::Bypass => Ok(None),
+            route => Ok(Some((mirror, route))),
+        }
+    }
+
+    pub async fn registry_mirror_applies(&self, host: &str) -> Result<bool> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard
+            .registry_mirror
+            .as_ref()
+            .is_some_and(|mirror| mirror.registry_for_host(host).is_some()))
+    }
+
+    /// Whether `host` is mirrored but not explicitly allowlisted, so only mirrored reads may reach it.
+    pub async fn registry_mirror_read_only(&self, host: &str) -> Result<bool> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard
+            .registry_mirror
+            .as_ref()
+            .is_some_and(|mirror| mirror.registry_for_host(host).is_some())
+            && !guard.allow_set.is_match(host))
+    }
+
     pub async fn add_allowed_domain(&self, host: &str) -> Result<()> {
         self.update_domain_list(host, DomainListKind::Allow).await
     }
@@ -778,7 +905,7 @@ fn is_explicit_local_allowlisted(allowed_domains: &[String], host: &Host) -> boo
     })
 }
 
-fn unix_timestamp() -> i64 {
+pub(crate) fn unix_timestamp() -> i64 {
     OffsetDateTime::now_utc().unix_timestamp()
 }
 
@@ -819,6 +946,9 @@ mod tests {
 
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
+    use crate::config::NetworkRequestRuleAction;
+    use crate::config::RegistryMirrorConfig;
     use crate::policy::compile_globset;
     use crate::state::NetworkProxyConstraints;
     use crate::state::build_config_state;
@@ -839,6 +969,40 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn host_blocked_allows_mirrored_registries_without_allowlist() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let state = network_proxy_state_for_policy(NetworkProxySettings {
+            allow_local_binding: true,
+            denied_domains: vec!["pypi.org".to_string()],
+            registry_mirror: RegistryMirrorConfig {
+                enabled: true,
+                cache_dir: Some(AbsolutePathBuf::from_absolute_path(cache_dir.path()).unwrap()),
+                ..RegistryMirrorConfig::default()
+            },
+            ..NetworkProxySettings::default()
+        });
+
+        assert_eq!(
+            state.host_blocked("registry.npmjs.org", 443).await.unwrap(),
+            HostBlockDecision::Allowed
+        );
+        assert_eq!(
+            state.host_blocked("pypi.org", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::Denied)
+        );
+        assert_eq!(
+            state.host_blocked("example.com", 443).await.unwrap(),
+            HostBlockDecision::Blocked(HostBlockReason::NotAllowed)
+        );
+        assert!(
+            state
+                .registry_mirror_read_only("registry.npmjs.org")
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn host_blocked_requires_allowlist_match() {
         let state = network_proxy_state_for_policy(NetworkProxySettings {
@@ -1063,6 +1227,7 @@ mod tests {
             decision: Some("ask".to_string()),
             source: Some("decider".to_string()),
             port: Some(80),
+            rule: None,
             timestamp: 1_735_689_600,
         };
 
@@ -1246,6 +1411,40 @@ mod tests {
         assert!(validate_policy_against_constraints(&config, &constraints).is_err());
     }
 
+    #[test]
+    fn validate_policy_against_constraints_disallows_request_rules_widening_mode() {
+        let constraints = NetworkProxyConstraints {
+            mode: Some(NetworkMode::Limited),
+            ..NetworkProxyConstraints::default()
+        };
+        let rule = |methods: Vec<String>| NetworkRequestRule {
+            domain: "registry.npmjs.org".to_string(),
+            methods,
+            path: None,
+            action: NetworkRequestRuleAction::Allow,
+        };
+
+        let config = NetworkProxyConfig {
+            network: NetworkProxySettings {
+                enabled: true,
+                mode: NetworkMode::Limited,
+                request_rules: vec![rule(vec!["POST".to_string()])],
+                ..NetworkProxySettings::default()
+            },
+        };
+        assert!(validate_policy_against_constraints(&config, &constraints).is_err());
+
+        let config = NetworkProxyConfig {
+            network: NetworkProxySettings {
+                enabled: true,
+                mode: NetworkMode::Limited,
+                request_rules: vec![rule(vec!["get".to_string()])],
+                ..NetworkProxySettings::default()
+            },
+        };
+        assert!(validate_policy_against_constraints(&config, &constraints).is_ok());
+    }
+
     #[test]
     fn validate_policy_against_constraints_allows_narrowing_wildcard_allowlist() {
         let constraints = NetworkProxyConstraints {
@@ -1,4 +1,5 @@
 use crate::config::NetworkMode;
+use crate::connection_log::NetworkConnectionRecord;
 use crate::network_policy::BlockDecisionAuditEventArgs;
 use crate::network_policy::NetworkDecision;
 use crate::network_policy::NetworkDecisionSource;
@@ -12,6 +13,8 @@ use crate::network_policy::evaluate_host_policy;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_REGISTRY_MIRROR_READ_ONLY;
+use crate::reasons::REASON_REQUEST_RULE_NOT_ALLOWED;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_message_with_policy;
 use crate::state::BlockedRequest;
@@ -239,6 +242,96 @@ async fn handle_socks5_tcp(
         }
     }
 
+    // SOCKS tunnels hide the HTTP method/path, so hosts with request rules are HTTP(S)-only.
+    match app_state.has_request_rules(&host).await {
+        Ok(true) => {
+            emit_socks_block_decision_audit_event(
+                &app_state,
+                NetworkDecisionSource::BaselinePolicy,
+                REASON_REQUEST_RULE_NOT_ALLOWED,
+                NetworkProtocol::Socks5Tcp,
+                host.as_str(),
+                port,
+                client.as_deref(),
+            );
+            let details = PolicyDecisionDetails {
+                decision: NetworkPolicyDecision::Deny,
+                reason: REASON_REQUEST_RULE_NOT_ALLOWED,
+                source: NetworkDecisionSource::BaselinePolicy,
+                protocol: NetworkProtocol::Socks5Tcp,
+                host: &host,
+                port,
+            };
+            let _ = app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: REASON_REQUEST_RULE_NOT_ALLOWED.to_string(),
+                    client: client.clone(),
+                    method: None,
+                    mode: None,
+                    protocol: "socks5".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            warn!("SOCKS blocked; host has request rules (client={client}, host={host})");
+            return Err(policy_denied_error(REASON_REQUEST_RULE_NOT_ALLOWED, &details).into());
+        }
+        Ok(false) => {}
+        Err(err) => {
+            error!("failed to evaluate request rules: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    }
+
+    // The registry mirror only sees HTTP(S) traffic; raw tunnels to mirrored hosts would bypass it.
+    match app_state.registry_mirror_read_only(&host).await {
+        Ok(true) => {
+            emit_socks_block_decision_audit_event(
+                &app_state,
+                NetworkDecisionSource::BaselinePolicy,
+                REASON_REGISTRY_MIRROR_READ_ONLY,
+                NetworkProtocol::Socks5Tcp,
+                host.as_str(),
+                port,
+                client.as_deref(),
+            );
+            let details = PolicyDecisionDetails {
+                decision: NetworkPolicyDecision::Deny,
+                reason: REASON_REGISTRY_MIRROR_READ_ONLY,
+                source: NetworkDecisionSource::BaselinePolicy,
+                protocol: NetworkProtocol::Socks5Tcp,
+                host: &host,
+                port,
+            };
+            let _ = app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: REASON_REGISTRY_MIRROR_READ_ONLY.to_string(),
+                    client: client.clone(),
+                    method: None,
+                    mode: None,
+                    protocol: "socks5".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            warn!(
+                "SOCKS blocked; host is served by the registry mirror (client={client}, host={host})"
+            );
+            return Err(policy_denied_error(REASON_REGISTRY_MIRROR_READ_ONLY, &details).into());
+        }
+        Ok(false) => {}
+        Err(err) => {
+            error!("failed to evaluate registry mirror: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    }
+
     let request = NetworkPolicyRequest::new(NetworkPolicyRequestArgs {
         protocol: NetworkProtocol::Socks5Tcp,
         host: host.clone(),
@@ -281,6 +374,12 @@ async fn handle_socks5_tcp(
             return Err(policy_denied_error(&reason, &details).into());
         }
         Ok(NetworkDecision::Allow) => {
+            app_state
+                .record_connection(NetworkConnectionRecord {
+                    opened_by: app_state.connection_opened().await,
+                    ..NetworkConnectionRecord::allowed("socks5", host.clone(), port, client.clone())
+                })
+                .await;
             let client = client.as_deref().unwrap_or_default();
             info!("SOCKS allowed (client={client}, host={host}, port={port})");
         }
@@ -487,6 +586,8 @@ mod tests {
     use crate::config::NetworkMode;
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
+    use crate::config::NetworkRequestRuleAction;
     use crate::network_policy::test_support::POLICY_DECISION_EVENT_NAME;
     use crate::network_policy::test_support::capture_events;
     use crate::network_policy::test_support::find_event_by_name;
@@ -570,6 +671,49 @@ mod tests {
         assert_eq!(event.field("client.address"), Some("unknown"));
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn handle_socks5_tcp_blocks_hosts_with_request_rules() {
+        let state = state_for_settings(NetworkProxySettings {
+            enabled: true,
+            mode: NetworkMode::Full,
+            allowed_domains: vec!["github.com".to_string()],
+            request_rules: vec![NetworkRequestRule {
+                domain: "github.com".to_string(),
+                methods: vec!["GET".to_string()],
+                path: None,
+                action: NetworkRequestRuleAction::Allow,
+            }],
+            ..NetworkProxySettings::default()
+        });
+        let mut request =
+            TcpRequest::new(HostWithPort::try_from("github.com:443").expect("valid authority"));
+        request.extensions_mut().insert(state.clone());
+
+        let (result, events) = capture_events(|| async {
+            handle_socks5_tcp(request, TcpConnector::default(), None).await
+        })
+        .await;
+        assert!(
+            result.is_err(),
+            "SOCKS tunnel to a ruled host should be denied"
+        );
+
+        let event = find_event_by_name(&events, POLICY_DECISION_EVENT_NAME)
+            .expect("expected policy decision event");
+        assert_eq!(event.field("network.policy.decision"), Some("deny"));
+        assert_eq!(
+            event.field("network.policy.source"),
+            Some("baseline_policy")
+        );
+        assert_eq!(
+            event.field("network.policy.reason"),
+            Some(REASON_REQUEST_RULE_NOT_ALLOWED)
+        );
+        let blocked = state.drain_blocked().await.unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].reason, REASON_REQUEST_RULE_NOT_ALLOWED);
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn inspect_socks5_udp_emits_block_decision_for_mode_guard_deny() {
         let state = state_for_settings(NetworkProxySettings {
@@ -1,9 +1,12 @@
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
+use crate::config::NetworkRequestRuleAction;
 use crate::mitm::MitmState;
 use crate::policy::DomainPattern;
 use crate::policy::compile_globset;
 use crate::policy::is_global_wildcard_domain_pattern;
+use crate::registry_mirror::RegistryMirror;
+use crate::request_rules::RequestRules;
 use crate::runtime::ConfigState;
 use serde::Deserialize;
 use std::collections::HashSet;
@@ -65,6 +68,7 @@ pub fn build_config_state(
         .map_err(NetworkProxyConstraintError::into_anyhow)?;
     let deny_set = compile_globset(&config.network.denied_domains)?;
     let allow_set = compile_globset(&config.network.allowed_domains)?;
+    let request_rules = RequestRules::compile(&config.network.request_rules)?;
     let mitm = if config.network.mitm {
         Some(Arc::new(MitmState::new(
             config.network.allow_upstream_proxy,
@@ -72,11 +76,21 @@ pub fn build_config_state(
     } else {
         None
     };
+    let registry_mirror = if config.network.registry_mirror.enabled {
+        Some(Arc::new(RegistryMirror::new(
+            &config.network.registry_mirror,
+            config.network.allow_upstream_proxy,
+        )?))
+    } else {
+        None
+    };
     Ok(ConfigState {
         config,
         allow_set,
         deny_set,
+        request_rules,
         mitm,
+        registry_mirror,
         constraints,
         blocked: std::collections::VecDeque::new(),
         blocked_total: 0,
@@ -137,6 +151,30 @@ pub fn validate_policy_against_constraints(
         })?;
     }
 
+    if let Some(max_mode) = constraints.mode {
+        // Allow rules override the mode's method restriction, so they must not grant methods
+        // beyond what the managed mode permits.
+        for rule in &config.network.request_rules {
+            if rule.action != NetworkRequestRuleAction::Allow {
+                continue;
+            }
+            let widens_mode = if rule.methods.is_empty() {
+                max_mode != NetworkMode::Full
+            } else {
+                rule.methods
+                    .iter()
+                    .any(|method| !max_mode.allows_method(&method.trim().to_ascii_uppercase()))
+            };
+            if widens_mode {
+                return Err(invalid_value(
+                    "network.request_rules",
+                    format!("allow {} {:?}", rule.domain, rule.methods),
+                    format!("methods permitted by {max_mode:?} mode"),
+                ));
+            }
+        }
+    }
+
     let allow_upstream_proxy = constraints.allow_upstream_proxy;
     validate(
         config.network.allow_upstream_proxy,
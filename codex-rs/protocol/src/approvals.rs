@@ -253,6 +253,9 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(default)]
     pub turn_id: String,
     pub changes: HashMap<PathBuf, FileChange>,
+    /// Files whose binary diffs the patch leaves untouched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<PathBuf>,
     /// Optional explanatory reason (e.g. request for extra write access).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
@@ -101,6 +101,18 @@ pub enum Personality {
     Pragmatic,
 }
 
+/// How guardian scores `on-request` approvals: with a reviewer model or with local rules.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS, Default,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GuardianBackend {
+    #[default]
+    Model,
+    Rules,
+}
+
 #[derive(
     Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS, Default,
 )]
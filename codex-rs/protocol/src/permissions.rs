@@ -3,6 +3,7 @@ use std::ffi::OsStr;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
@@ -408,6 +409,116 @@ impl FileSystemSandboxPolicy {
     }
 }
 
+/// Extra filesystem restrictions layered on top of a sandbox policy's writable roots.
+///
+/// Currently enforced by the Linux bubblewrap sandbox only.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct FileSystemWriteScope {
+    /// Globs that stay read-only inside writable roots. Relative globs are anchored at each
+    /// writable root; `*` does not cross `/` while `**` does.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub read_only: Vec<String>,
+    /// Paths the sandboxed process may not read at all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_read: Vec<AbsolutePathBuf>,
+}
+
+impl FileSystemWriteScope {
+    pub fn is_empty(&self) -> bool {
+        self.read_only.is_empty() && self.deny_read.is_empty()
+    }
+}
+
+impl FromStr for FileSystemWriteScope {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Named seccomp profiles the Linux sandbox can install on top of its network
+/// filter. A command that makes a blocked syscall is killed with `SIGSYS`,
+/// except under `NoProcessSpawnOutsideAllowlist`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    JsonSchema,
+    TS,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum SeccompProfile {
+    /// Block tracing and reading/writing other processes' memory.
+    NoPtrace,
+    /// Block mounting, unmounting, and switching filesystems.
+    NoMount,
+    /// Block creating child processes. Threads are still allowed.
+    NoProcessSpawn,
+    /// Only execute the command itself and the programs in the spawn
+    /// allowlist. Enforced with Landlock's execute right because seccomp
+    /// cannot read the `execve` path, so other programs fail with `EACCES`
+    /// instead of killing the command.
+    NoProcessSpawnOutsideAllowlist,
+}
+
+impl SeccompProfile {
+    /// Syscalls rejected by this profile, for reporting.
+    pub fn blocked_syscalls(self) -> &'static [&'static str] {
+        match self {
+            Self::NoPtrace => &[
+                "ptrace",
+                "process_vm_readv",
+                "process_vm_writev",
+                "pidfd_getfd",
+            ],
+            Self::NoMount => &[
+                "mount",
+                "umount2",
+                "pivot_root",
+                "open_tree",
+                "move_mount",
+                "fsopen",
+                "fsconfig",
+                "fsmount",
+                "fspick",
+                "mount_setattr",
+            ],
+            Self::NoProcessSpawn => &["fork", "vfork", "clone (without CLONE_THREAD)"],
+            Self::NoProcessSpawnOutsideAllowlist => &["execve", "execveat"],
+        }
+    }
+
+    /// Whether a violation kills the command with `SIGSYS` rather than
+    /// failing the syscall.
+    pub fn kills_on_violation(self) -> bool {
+        !matches!(self, Self::NoProcessSpawnOutsideAllowlist)
+    }
+}
+
+impl FromStr for SeccompProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-ptrace" => Ok(Self::NoPtrace),
+            "no-mount" => Ok(Self::NoMount),
+            "no-process-spawn" => Ok(Self::NoProcessSpawn),
+            "no-process-spawn-outside-allowlist" => Ok(Self::NoProcessSpawnOutsideAllowlist),
+            _ => Err(format!("unknown seccomp profile: {s}")),
+        }
+    }
+}
+
 impl From<&SandboxPolicy> for NetworkSandboxPolicy {
     fn from(value: &SandboxPolicy) -> Self {
         if value.has_full_network_access() {
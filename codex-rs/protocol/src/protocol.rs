@@ -15,6 +15,7 @@ use std::time::Duration;
 use crate::ThreadId;
 use crate::approvals::ElicitationRequestEvent;
 use crate::config_types::CollaborationMode;
+use crate::config_types::GuardianBackend;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
@@ -68,6 +69,7 @@ pub use crate::permissions::FileSystemSandboxKind;
 pub use crate::permissions::FileSystemSandboxPolicy;
 pub use crate::permissions::FileSystemSpecialPath;
 pub use crate::permissions::NetworkSandboxPolicy;
+pub use crate::permissions::SeccompProfile;
 pub use crate::request_user_input::RequestUserInputEvent;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
@@ -179,6 +181,14 @@ pub enum Op {
     /// Terminate all running background terminal processes for this thread.
     CleanBackgroundTerminals,
 
+    /// Request the thread's persistent background jobs. Reply is delivered
+    /// via `EventMsg::ListBackgroundJobsResponse`.
+    ListBackgroundJobs,
+
+    /// Terminate one background job by its unified exec process id. The
+    /// updated job list is delivered via `EventMsg::ListBackgroundJobsResponse`.
+    KillBackgroundJob { process_id: String },
+
     /// Start a realtime conversation stream.
     RealtimeConversationStart(ConversationStartParams),
 
@@ -433,6 +443,17 @@ pub enum Op {
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Restore the working tree to the checkpoint taken `num_turns` turns
+    /// ago. `UndoTurns { num_turns: 1 }` is equivalent to `Undo`.
+    UndoTurns { num_turns: u32 },
+
+    /// Reapply the most recent undo. Any new checkpoint clears the redo stack.
+    Redo,
+
+    /// Request the undo timeline. Reply is delivered via
+    /// `EventMsg::ListCheckpointsResponse`.
+    ListCheckpoints,
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
@@ -1044,6 +1065,12 @@ pub enum EventMsg {
     /// Model routing changed from the requested model to a different model.
     ModelReroute(ModelRerouteEvent),
 
+    /// Guardian decided an approval request on the user's behalf.
+    GuardianAssessment(GuardianAssessmentEvent),
+
+    /// Secrets were scrubbed from a tool output or a recorded rollout item.
+    SecretsRedacted(SecretsRedactedEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
@@ -1172,6 +1199,15 @@ pub enum EventMsg {
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
+    /// Undo timeline for the session.
+    ListCheckpointsResponse(ListCheckpointsResponseEvent),
+
+    /// Persistent background jobs for the thread.
+    ListBackgroundJobsResponse(ListBackgroundJobsResponseEvent),
+
+    /// A directory-scoped project doc was added to the conversation.
+    ProjectDocLoaded(ProjectDocLoadedEvent),
+
     /// List of remote skills available to the agent.
     ListRemoteSkillsResponse(ListRemoteSkillsResponseEvent),
 
@@ -1544,6 +1580,54 @@ pub struct ModelRerouteEvent {
     pub reason: ModelRerouteReason,
 }
 
+/// Coarse risk label paired with a guardian `risk_score`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GuardianRiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct GuardianAssessmentEvent {
+    /// Backend that scored the action.
+    pub backend: GuardianBackend,
+    /// Whether the action was approved, i.e. `risk_score < approval_threshold`.
+    pub approved: bool,
+    pub risk_level: GuardianRiskLevel,
+    pub risk_score: u8,
+    /// Scores at or above this value are denied.
+    pub approval_threshold: u8,
+    pub rationale: String,
+    /// The planned action that was reviewed.
+    pub action: Value,
+}
+
+/// Audit record for one redacted value. The secrets themselves are never included.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct SecretsRedactedEvent {
+    /// What was redacted: `tool_output` or `rollout`.
+    pub source: String,
+    pub redactions: Vec<SecretRedaction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct SecretRedaction {
+    /// Id of the detector that matched.
+    pub detector: String,
+    /// `low`, `medium` or `high`.
+    pub confidence: String,
+    /// JSON pointer of the redacted string within the value, when it is structured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub field: Option<String>,
+    /// 1-based position of the match in the original string.
+    pub line: u32,
+    pub column: u32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -2211,6 +2295,37 @@ pub struct CompactedItem {
     pub message: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub replacement_history: Option<Vec<ResponseItem>>,
+    /// What automatic compaction removed; absent for manual `/compact`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<CompactionReport>,
+}
+
+/// Strategies automatic compaction applies in order, stopping once the history
+/// fits under the compaction threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionTier {
+    /// Truncate the output of older tool calls.
+    PruneToolOutputs,
+    /// Drop reasoning items from earlier turns.
+    DropReasoning,
+    /// Replace the history with a model-written summary.
+    Summarize,
+}
+
+/// Outcome of an automatic compaction.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CompactionReport {
+    /// The last tier that ran; the tiers configured before it ran too.
+    pub tier: CompactionTier,
+    pub tool_outputs_pruned: u32,
+    pub reasoning_items_dropped: u32,
+    /// Estimated tokens in the history before compaction.
+    #[ts(type = "number")]
+    pub tokens_before: i64,
+    /// Estimated tokens in the history after compaction.
+    #[ts(type = "number")]
+    pub tokens_after: i64,
 }
 
 impl From<CompactedItem> for ResponseItem {
@@ -2469,6 +2584,12 @@ pub struct ExecCommandEndEvent {
     pub formatted_output: String,
     /// Completion status for this command execution.
     pub status: ExecCommandStatus,
+    /// Seccomp profiles that stopped the command under the Linux sandbox,
+    /// either by killing it for a blocked syscall or by refusing to execute a
+    /// program outside the spawn allowlist; absent for any other outcome.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub seccomp_denial: Option<Vec<SeccompProfile>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2573,6 +2694,9 @@ pub struct PatchApplyBeginEvent {
     pub auto_approved: bool,
     /// The changes to be applied.
     pub changes: HashMap<PathBuf, FileChange>,
+    /// Files whose binary diffs the patch leaves untouched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2695,6 +2819,76 @@ pub struct ListSkillsResponseEvent {
     pub skills: Vec<SkillsListEntry>,
 }
 
+/// Response payload for `Op::ListCheckpoints`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListCheckpointsResponseEvent {
+    /// Checkpoints that can be undone to, newest first.
+    pub checkpoints: Vec<Checkpoint>,
+    /// Number of undos that `Op::Redo` can reapply.
+    pub redo_depth: u32,
+}
+
+/// A working-tree snapshot taken at the start of a turn.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct Checkpoint {
+    /// Value of `num_turns` that restores this checkpoint via `Op::UndoTurns`.
+    pub turns_back: u32,
+    pub snapshot_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    /// Unified diff of the edits the turn made after this checkpoint, when
+    /// the turn changed files through tracked tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Response payload for `Op::ListBackgroundJobs`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListBackgroundJobsResponseEvent {
+    /// Jobs started with `persist: true`, oldest first.
+    pub jobs: Vec<BackgroundJob>,
+}
+
+/// A unified exec process whose output is logged under `CODEX_HOME` so it
+/// outlives the session that started it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BackgroundJob {
+    pub process_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    /// Unix timestamp (seconds) when the job started.
+    #[ts(type = "number")]
+    pub started_at: i64,
+    pub status: BackgroundJobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Head and tail of the job's output, as last written to disk.
+    pub log_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundJobStatus {
+    /// The job's process is alive in this Codex process.
+    Running,
+    /// The job exited or was killed; see `exit_code`.
+    Exited,
+    /// The Codex process that owned the job went away before the job was
+    /// seen to exit; only its log remains.
+    Lost,
+}
+
+/// Emitted when an AGENTS.md below the session's working directory is loaded
+/// because the agent edited a file under its directory.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProjectDocLoadedEvent {
+    pub path: PathBuf,
+    /// Edited file whose directory the doc governs.
+    pub trigger_path: PathBuf,
+    /// Whether the doc was cut short to fit `project_doc_scoped_max_tokens`.
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct RemoteSkillSummary {
     pub id: String,
@@ -3813,6 +4007,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn exec_command_end_event_carries_seccomp_denial_profiles() -> Result<()> {
+        let event: EventMsg = serde_json::from_value(json!({
+            "type": "exec_command_end",
+            "call_id": "call-1",
+            "turn_id": "turn-1",
+            "command": ["ls"],
+            "cwd": "/tmp",
+            "parsed_cmd": [],
+            "stdout": "",
+            "stderr": "",
+            "exit_code": 159,
+            "duration": { "secs": 0, "nanos": 0 },
+            "formatted_output": "",
+            "status": "failed",
+            "seccomp_denial": ["no-ptrace", "no-process-spawn"],
+        }))?;
+        let EventMsg::ExecCommandEnd(mut end) = event else {
+            panic!("expected exec_command_end event");
+        };
+        assert_eq!(
+            end.seccomp_denial,
+            Some(vec![
+                SeccompProfile::NoPtrace,
+                SeccompProfile::NoProcessSpawn
+            ])
+        );
+
+        end.seccomp_denial = None;
+        let value = serde_json::to_value(EventMsg::ExecCommandEnd(end))?;
+        assert_eq!(value.get("seccomp_denial"), None);
+        Ok(())
+    }
+
     /// Serialize Event to verify that its JSON representation has the expected
     /// amount of nesting.
     #[test]
@@ -16,7 +16,21 @@ mod local;
 mod sanitizer;
 
 pub use local::LocalSecretsBackend;
+pub use sanitizer::CustomSecretDetector;
+pub use sanitizer::HIGH_ENTROPY_DETECTOR_ID;
+pub use sanitizer::HighEntropySecretDetector;
+pub use sanitizer::LiteralSecretDetector;
+pub use sanitizer::RedactionConfidence;
+pub use sanitizer::RedactionFinding;
+pub use sanitizer::RedactionReport;
+pub use sanitizer::RegexSecretDetector;
+pub use sanitizer::SecretDetector;
+pub use sanitizer::SecretMatch;
+pub use sanitizer::SecretRedactionConfig;
+pub use sanitizer::SecretRedactor;
+pub use sanitizer::builtin_detector_ids;
 pub use sanitizer::redact_secrets;
+pub use sanitizer::summarize_findings;
 
 const KEYRING_SERVICE: &str = "codex";
 
@@ -136,6 +150,15 @@ impl SecretsManager {
     pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         self.backend.list(scope_filter)
     }
+
+    /// Looks `name` up in the environment scope first and falls back to the global scope.
+    pub fn resolve(&self, environment_id: &str, name: &SecretName) -> Result<Option<String>> {
+        let scope = SecretScope::environment(environment_id)?;
+        if let Some(value) = self.get(&scope, name)? {
+            return Ok(Some(value));
+        }
+        self.get(&SecretScope::Global, name)
+    }
 }
 
 pub fn environment_id_from_cwd(cwd: &Path) -> String {
@@ -242,4 +265,32 @@ mod tests {
         assert_eq!(manager.get(&scope, &name)?, None);
         Ok(())
     }
+
+    #[test]
+    fn resolve_prefers_environment_scope_over_global() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let keyring = Arc::new(MockKeyringStore::default());
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            keyring,
+        );
+        let name = SecretName::new("NPM_TOKEN")?;
+        manager.set(&SecretScope::Global, &name, "global-token")?;
+        assert_eq!(
+            manager.resolve("repo", &name)?,
+            Some("global-token".to_string())
+        );
+
+        manager.set(&SecretScope::environment("repo")?, &name, "repo-token")?;
+        assert_eq!(
+            manager.resolve("repo", &name)?,
+            Some("repo-token".to_string())
+        );
+        assert_eq!(
+            manager.resolve("other", &name)?,
+            Some("global-token".to_string())
+        );
+        Ok(())
+    }
 }
@@ -27,6 +27,26 @@ pub fn try_parse_shell(shell_lc_arg: &str) -> Option<Tree> {
 /// (parentheses, redirections, substitutions, control flow, etc.). Otherwise
 /// returns `None`.
 pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<Vec<Vec<String>>> {
+    parse_word_only_commands(tree, src, false).map(|(commands, _)| commands)
+}
+
+/// Like [`try_parse_word_only_commands_sequence`], but also accepts file
+/// redirections (`> out`, `2>>log`, `< in`) attached to the commands and
+/// returns their targets in source order. File-descriptor duplications such as
+/// `2>&1` contribute no target. Returns `None` when a redirection target is not
+/// a literal word.
+pub fn try_parse_word_only_commands_with_redirects(
+    tree: &Tree,
+    src: &str,
+) -> Option<(Vec<Vec<String>>, Vec<String>)> {
+    parse_word_only_commands(tree, src, true)
+}
+
+fn parse_word_only_commands(
+    tree: &Tree,
+    src: &str,
+    allow_redirects: bool,
+) -> Option<(Vec<Vec<String>>, Vec<String>)> {
     if tree.root_node().has_error() {
         return None;
     }
@@ -55,10 +75,17 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
     let mut cursor = root.walk();
     let mut stack = vec![root];
     let mut command_nodes = Vec::new();
+    let mut redirect_nodes = Vec::new();
     while let Some(node) = stack.pop() {
         let kind = node.kind();
         if node.is_named() {
-            if !ALLOWED_KINDS.contains(&kind) {
+            if allow_redirects && kind == "file_redirect" {
+                redirect_nodes.push(node);
+                continue;
+            }
+            if !(ALLOWED_KINDS.contains(&kind)
+                || (allow_redirects && kind == "redirected_statement"))
+            {
                 return None;
             }
             if kind == "command" {
@@ -91,7 +118,42 @@ pub fn try_parse_word_only_commands_sequence(tree: &Tree, src: &str) -> Option<V
             return None;
         }
     }
-    Some(commands)
+
+    redirect_nodes.sort_by_key(Node::start_byte);
+    let mut redirects = Vec::new();
+    for node in redirect_nodes {
+        redirects.extend(parse_file_redirect_target(node, src)?);
+    }
+    Some((commands, redirects))
+}
+
+/// Returns the file a `file_redirect` node reads or writes, `Some(None)` for
+/// file-descriptor duplications and closes (`2>&1`, `>&-`), or `None` when the
+/// target is not a literal word.
+fn parse_file_redirect_target(node: Node<'_>, src: &str) -> Option<Option<String>> {
+    let mut cursor = node.walk();
+    let operator = node
+        .children(&mut cursor)
+        .find(|child| !child.is_named())
+        .map(|child| child.kind())?;
+    if matches!(operator, ">&-" | "<&-") {
+        return Some(None);
+    }
+    let destination = node.child_by_field_name("destination")?;
+    if matches!(operator, ">&" | "<&")
+        && (destination.kind() == "number" || destination.utf8_text(src.as_bytes()).ok()? == "-")
+    {
+        return Some(None);
+    }
+    let target = match destination.kind() {
+        "word" | "number" if is_literal_word_or_number(destination) => {
+            destination.utf8_text(src.as_bytes()).ok()?.to_owned()
+        }
+        "string" => parse_double_quoted_string(destination, src)?,
+        "raw_string" => parse_raw_string(destination, src)?,
+        _ => return None,
+    };
+    Some(Some(target))
 }
 
 pub fn extract_bash_command(command: &[String]) -> Option<(&str, &str)> {
@@ -119,6 +181,18 @@ pub fn parse_shell_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<Strin
     try_parse_word_only_commands_sequence(&tree, script)
 }
 
+/// Returns the plain commands within a `bash -lc "..."` invocation together
+/// with the targets of their file redirections. See
+/// [`try_parse_word_only_commands_with_redirects`].
+pub fn parse_shell_lc_commands_with_redirects(
+    command: &[String],
+) -> Option<(Vec<Vec<String>>, Vec<String>)> {
+    let (_, script) = extract_bash_command(command)?;
+
+    let tree = try_parse_shell(script)?;
+    try_parse_word_only_commands_with_redirects(&tree, script)
+}
+
 /// Returns the parsed argv for a single shell command in a here-doc style
 /// script (`<<`), as long as the script contains exactly one command node.
 pub fn parse_shell_lc_single_command_prefix(command: &[String]) -> Option<Vec<String>> {
@@ -413,6 +487,43 @@ mod tests {
         assert!(parse_seq("echo hi & echo bye").is_none());
     }
 
+    #[test]
+    fn extracts_file_redirect_targets() {
+        let src = "echo x > ../out && cat < in.txt 2>&1 | tee -a log 2>> 'err log'";
+        let tree = try_parse_shell(src).expect("parse");
+        assert_eq!(
+            try_parse_word_only_commands_with_redirects(&tree, src),
+            Some((
+                vec![
+                    vec!["echo".to_string(), "x".to_string()],
+                    vec!["cat".to_string()],
+                    vec!["tee".to_string(), "-a".to_string(), "log".to_string()],
+                ],
+                vec![
+                    "../out".to_string(),
+                    "in.txt".to_string(),
+                    "err log".to_string(),
+                ],
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_non_literal_redirect_targets() {
+        for src in [
+            "echo x > $HOME/out",
+            "echo x > \"$OUT\"",
+            "cat <<EOF\nhi\nEOF",
+        ] {
+            let tree = try_parse_shell(src).expect("parse");
+            assert_eq!(
+                try_parse_word_only_commands_with_redirects(&tree, src),
+                None,
+                "{src}"
+            );
+        }
+    }
+
     #[test]
     fn rejects_command_and_process_substitutions_and_expansions() {
         assert!(parse_seq("echo $(pwd)").is_none());
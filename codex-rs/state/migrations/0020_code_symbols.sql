@@ -0,0 +1,25 @@
+CREATE TABLE code_index_files (
+    root TEXT NOT NULL,
+    path TEXT NOT NULL,
+    language TEXT NOT NULL,
+    modified_at_ms INTEGER NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    indexed_at INTEGER NOT NULL,
+    PRIMARY KEY (root, path)
+);
+
+CREATE TABLE code_symbols (
+    root TEXT NOT NULL,
+    path TEXT NOT NULL,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    role TEXT NOT NULL,
+    container TEXT,
+    start_line INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    snippet TEXT NOT NULL,
+    FOREIGN KEY(root, path) REFERENCES code_index_files(root, path) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_code_symbols_name ON code_symbols(root, name, role);
+CREATE INDEX idx_code_symbols_path ON code_symbols(root, path, start_line);
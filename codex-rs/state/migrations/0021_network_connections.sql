@@ -0,0 +1,21 @@
+CREATE TABLE network_connections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    thread_id TEXT NOT NULL,
+    process TEXT,
+    client_addr TEXT,
+    protocol TEXT NOT NULL,
+    host TEXT NOT NULL,
+    port INTEGER,
+    method TEXT,
+    path TEXT,
+    decision TEXT NOT NULL,
+    reason TEXT,
+    rule TEXT,
+    status INTEGER,
+    bytes_sent INTEGER,
+    bytes_received INTEGER,
+    har_entry TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_network_connections_thread ON network_connections(thread_id, created_at, id);
@@ -0,0 +1,6 @@
+CREATE TABLE thread_client_identities (
+    thread_id TEXT NOT NULL,
+    identity TEXT NOT NULL,
+    first_seen_at INTEGER NOT NULL,
+    PRIMARY KEY(thread_id, identity)
+);
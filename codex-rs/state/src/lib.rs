@@ -35,7 +35,12 @@ pub use model::Anchor;
 pub use model::BackfillState;
 pub use model::BackfillStats;
 pub use model::BackfillStatus;
+pub use model::CodeIndexFile;
+pub use model::CodeSymbol;
+pub use model::CodeSymbolRole;
 pub use model::ExtractionOutcome;
+pub use model::NetworkConnection;
+pub use model::NetworkConnectionDecision;
 pub use model::SortKey;
 pub use model::Stage1JobClaim;
 pub use model::Stage1JobClaimOutcome;
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Whether an indexed symbol occurrence declares the name or uses it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeSymbolRole {
+    Definition,
+    Reference,
+}
+
+impl CodeSymbolRole {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CodeSymbolRole::Definition => "definition",
+            CodeSymbolRole::Reference => "reference",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "definition" => Ok(Self::Definition),
+            "reference" => Ok(Self::Reference),
+            _ => Err(anyhow::anyhow!("invalid code symbol role: {value}")),
+        }
+    }
+}
+
+/// A source file tracked by the code index, with the fingerprint used to
+/// decide whether it needs to be re-parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeIndexFile {
+    /// `/`-separated path relative to the index root.
+    pub path: String,
+    pub language: String,
+    pub modified_at_ms: i64,
+    pub size_bytes: i64,
+}
+
+/// One symbol occurrence extracted from a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSymbol {
+    /// `/`-separated path relative to the index root.
+    pub path: String,
+    pub name: String,
+    /// Tag kind such as `function`, `struct`, or `call`.
+    pub kind: String,
+    pub role: CodeSymbolRole,
+    /// Name of the enclosing type, trait, class, or module, if any.
+    pub container: Option<String>,
+    /// 1-based, inclusive.
+    pub start_line: u32,
+    /// 1-based, inclusive.
+    pub end_line: u32,
+    /// First line of the tagged node, trimmed.
+    pub snippet: String,
+}
+
+impl CodeSymbol {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        let role: String = row.try_get("role")?;
+        let start_line: i64 = row.try_get("start_line")?;
+        let end_line: i64 = row.try_get("end_line")?;
+        Ok(Self {
+            path: row.try_get("path")?,
+            name: row.try_get("name")?,
+            kind: row.try_get("kind")?,
+            role: CodeSymbolRole::parse(role.as_str())?,
+            container: row.try_get("container")?,
+            start_line: u32::try_from(start_line)?,
+            end_line: u32::try_from(end_line)?,
+            snippet: row.try_get("snippet")?,
+        })
+    }
+}
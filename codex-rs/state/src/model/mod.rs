@@ -1,7 +1,9 @@
 mod agent_job;
 mod backfill_state;
+mod code_symbol;
 mod log;
 mod memories;
+mod network_connection;
 mod thread_metadata;
 
 pub use agent_job::AgentJob;
@@ -13,6 +15,9 @@ pub use agent_job::AgentJobProgress;
 pub use agent_job::AgentJobStatus;
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
+pub use code_symbol::CodeIndexFile;
+pub use code_symbol::CodeSymbol;
+pub use code_symbol::CodeSymbolRole;
 pub use log::LogEntry;
 pub use log::LogQuery;
 pub use log::LogRow;
@@ -23,6 +28,8 @@ pub use memories::Stage1JobClaimOutcome;
 pub use memories::Stage1Output;
 pub use memories::Stage1OutputRef;
 pub use memories::Stage1StartupClaimParams;
+pub use network_connection::NetworkConnection;
+pub use network_connection::NetworkConnectionDecision;
 pub use thread_metadata::Anchor;
 pub use thread_metadata::BackfillStats;
 pub use thread_metadata::ExtractionOutcome;
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use codex_protocol::ThreadId;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Whether the network proxy let a connection through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkConnectionDecision {
+    Allow,
+    Deny,
+}
+
+impl NetworkConnectionDecision {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            NetworkConnectionDecision::Allow => "allow",
+            NetworkConnectionDecision::Deny => "deny",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            _ => Err(anyhow::anyhow!(
+                "invalid network connection decision: {value}"
+            )),
+        }
+    }
+}
+
+/// One connection (or HTTP exchange) observed by a thread's network proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkConnection {
+    pub thread_id: ThreadId,
+    /// Tool call that was using the network when the connection opened, if exactly one was.
+    pub process: Option<String>,
+    pub client_addr: Option<String>,
+    /// `http`, `https`, `http-connect`, or `socks5`.
+    pub protocol: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub decision: NetworkConnectionDecision,
+    pub reason: Option<String>,
+    /// Label of the request rule that decided the request, if any.
+    pub rule: Option<String>,
+    pub status: Option<u16>,
+    pub bytes_sent: Option<u64>,
+    pub bytes_received: Option<u64>,
+    /// Serialized HAR 1.2 entry, present when HAR capture was enabled.
+    pub har_entry: Option<String>,
+    /// Unix timestamp (seconds).
+    pub created_at: i64,
+}
+
+impl NetworkConnection {
+    pub(crate) fn try_from_row(row: &SqliteRow) -> Result<Self> {
+        let thread_id: String = row.try_get("thread_id")?;
+        let decision: String = row.try_get("decision")?;
+        let port: Option<i64> = row.try_get("port")?;
+        let status: Option<i64> = row.try_get("status")?;
+        let bytes_sent: Option<i64> = row.try_get("bytes_sent")?;
+        let bytes_received: Option<i64> = row.try_get("bytes_received")?;
+        Ok(Self {
+            thread_id: ThreadId::try_from(thread_id)?,
+            process: row.try_get("process")?,
+            client_addr: row.try_get("client_addr")?,
+            protocol: row.try_get("protocol")?,
+            host: row.try_get("host")?,
+            port: port.map(u16::try_from).transpose()?,
+            method: row.try_get("method")?,
+            path: row.try_get("path")?,
+            decision: NetworkConnectionDecision::parse(decision.as_str())?,
+            reason: row.try_get("reason")?,
+            rule: row.try_get("rule")?,
+            status: status.map(u16::try_from).transpose()?,
+            bytes_sent: bytes_sent.map(u64::try_from).transpose()?,
+            bytes_received: bytes_received.map(u64::try_from).transpose()?,
+            har_entry: row.try_get("har_entry")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
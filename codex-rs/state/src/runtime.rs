@@ -51,8 +51,10 @@ use tracing::warn;
 
 mod agent_jobs;
 mod backfill;
+mod code_symbols;
 mod logs;
 mod memories;
+mod network_connections;
 #[cfg(test)]
 mod test_support;
 mod threads;
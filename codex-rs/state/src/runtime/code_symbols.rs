@@ -0,0 +1,295 @@
+use super::*;
+use crate::CodeIndexFile;
+use crate::CodeSymbol;
+use crate::CodeSymbolRole;
+use std::collections::HashMap;
+
+impl StateRuntime {
+    /// Returns every file indexed under `root`, keyed by relative path.
+    pub async fn code_index_files(
+        &self,
+        root: &Path,
+    ) -> anyhow::Result<HashMap<String, CodeIndexFile>> {
+        let rows = sqlx::query(
+            r#"
+SELECT path, language, modified_at_ms, size_bytes
+FROM code_index_files
+WHERE root = ?
+            "#,
+        )
+        .bind(root_key(root))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        let mut files = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let file = CodeIndexFile {
+                path: row.try_get("path")?,
+                language: row.try_get("language")?,
+                modified_at_ms: row.try_get("modified_at_ms")?,
+                size_bytes: row.try_get("size_bytes")?,
+            };
+            files.insert(file.path.clone(), file);
+        }
+        Ok(files)
+    }
+
+    /// Records `file` and replaces all symbols previously stored for it.
+    pub async fn replace_code_index_file(
+        &self,
+        root: &Path,
+        file: &CodeIndexFile,
+        symbols: &[CodeSymbol],
+    ) -> anyhow::Result<()> {
+        let root = root_key(root);
+        let now = Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+DELETE FROM code_symbols
+WHERE root = ? AND path = ?
+            "#,
+        )
+        .bind(root.as_str())
+        .bind(file.path.as_str())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+INSERT INTO code_index_files (root, path, language, modified_at_ms, size_bytes, indexed_at)
+VALUES (?, ?, ?, ?, ?, ?)
+ON CONFLICT(root, path) DO UPDATE SET
+    language = excluded.language,
+    modified_at_ms = excluded.modified_at_ms,
+    size_bytes = excluded.size_bytes,
+    indexed_at = excluded.indexed_at
+            "#,
+        )
+        .bind(root.as_str())
+        .bind(file.path.as_str())
+        .bind(file.language.as_str())
+        .bind(file.modified_at_ms)
+        .bind(file.size_bytes)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        for symbol in symbols {
+            sqlx::query(
+                r#"
+INSERT INTO code_symbols (
+    root,
+    path,
+    name,
+    kind,
+    role,
+    container,
+    start_line,
+    end_line,
+    snippet
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(root.as_str())
+            .bind(file.path.as_str())
+            .bind(symbol.name.as_str())
+            .bind(symbol.kind.as_str())
+            .bind(symbol.role.as_str())
+            .bind(symbol.container.as_deref())
+            .bind(i64::from(symbol.start_line))
+            .bind(i64::from(symbol.end_line))
+            .bind(symbol.snippet.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Forgets the given files (and their symbols) under `root`.
+    pub async fn remove_code_index_files(
+        &self,
+        root: &Path,
+        paths: &[String],
+    ) -> anyhow::Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let root = root_key(root);
+        let mut tx = self.pool.begin().await?;
+        for path in paths {
+            sqlx::query(
+                r#"
+DELETE FROM code_symbols
+WHERE root = ? AND path = ?
+                "#,
+            )
+            .bind(root.as_str())
+            .bind(path.as_str())
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                r#"
+DELETE FROM code_index_files
+WHERE root = ? AND path = ?
+                "#,
+            )
+            .bind(root.as_str())
+            .bind(path.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Finds occurrences of `name` with the given role under `root`.
+    ///
+    /// When `container` is set, only symbols declared inside (or, for
+    /// references, qualified by) that container are returned.
+    pub async fn find_code_symbols(
+        &self,
+        root: &Path,
+        name: &str,
+        container: Option<&str>,
+        role: CodeSymbolRole,
+        limit: usize,
+    ) -> anyhow::Result<Vec<CodeSymbol>> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT path, name, kind, role, container, start_line, end_line, snippet FROM code_symbols WHERE root = ",
+        );
+        builder.push_bind(root_key(root));
+        builder.push(" AND name = ");
+        builder.push_bind(name);
+        builder.push(" AND role = ");
+        builder.push_bind(role.as_str());
+        if let Some(container) = container {
+            builder.push(" AND container = ");
+            builder.push_bind(container);
+        }
+        builder.push(" ORDER BY path ASC, start_line ASC LIMIT ");
+        builder.push_bind(i64::try_from(limit).unwrap_or(i64::MAX));
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.iter().map(CodeSymbol::try_from_row).collect()
+    }
+
+    /// Lists the definitions in one indexed file, in source order.
+    pub async fn code_symbols_in_file(
+        &self,
+        root: &Path,
+        path: &str,
+    ) -> anyhow::Result<Vec<CodeSymbol>> {
+        let rows = sqlx::query(
+            r#"
+SELECT path, name, kind, role, container, start_line, end_line, snippet
+FROM code_symbols
+WHERE root = ? AND path = ? AND role = ?
+ORDER BY start_line ASC, end_line DESC
+            "#,
+        )
+        .bind(root_key(root))
+        .bind(path)
+        .bind(CodeSymbolRole::Definition.as_str())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(CodeSymbol::try_from_row).collect()
+    }
+}
+
+fn root_key(root: &Path) -> String {
+    root.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateRuntime;
+    use super::test_support::unique_temp_dir;
+    use crate::CodeIndexFile;
+    use crate::CodeSymbol;
+    use crate::CodeSymbolRole;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn symbol(name: &str, role: CodeSymbolRole, line: u32) -> CodeSymbol {
+        CodeSymbol {
+            path: "src/lib.rs".to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            role,
+            container: None,
+            start_line: line,
+            end_line: line,
+            snippet: format!("fn {name}()"),
+        }
+    }
+
+    #[tokio::test]
+    async fn replace_and_query_code_symbols() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string())
+            .await
+            .expect("initialize runtime");
+        let root = Path::new("/repo");
+        let file = CodeIndexFile {
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            modified_at_ms: 1,
+            size_bytes: 10,
+        };
+
+        runtime
+            .replace_code_index_file(
+                root,
+                &file,
+                &[
+                    symbol("parse", CodeSymbolRole::Definition, 3),
+                    symbol("parse", CodeSymbolRole::Reference, 9),
+                ],
+            )
+            .await
+            .expect("index file");
+        // Re-indexing replaces rather than appends.
+        runtime
+            .replace_code_index_file(
+                root,
+                &file,
+                &[symbol("parse", CodeSymbolRole::Definition, 4)],
+            )
+            .await
+            .expect("reindex file");
+
+        let definitions = runtime
+            .find_code_symbols(root, "parse", None, CodeSymbolRole::Definition, 10)
+            .await
+            .expect("find definitions");
+        assert_eq!(
+            definitions,
+            vec![symbol("parse", CodeSymbolRole::Definition, 4)]
+        );
+        let references = runtime
+            .find_code_symbols(root, "parse", None, CodeSymbolRole::Reference, 10)
+            .await
+            .expect("find references");
+        assert_eq!(references, Vec::new());
+
+        let files = runtime.code_index_files(root).await.expect("list files");
+        assert_eq!(files.get("src/lib.rs"), Some(&file));
+
+        runtime
+            .remove_code_index_files(root, &["src/lib.rs".to_string()])
+            .await
+            .expect("remove file");
+        assert_eq!(
+            runtime
+                .code_symbols_in_file(root, "src/lib.rs")
+                .await
+                .expect("outline"),
+            Vec::new()
+        );
+        assert!(
+            runtime
+                .code_index_files(root)
+                .await
+                .expect("list files")
+                .is_empty()
+        );
+    }
+}
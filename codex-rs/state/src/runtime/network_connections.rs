@@ -0,0 +1,160 @@
+use super::*;
+use crate::NetworkConnection;
+
+impl StateRuntime {
+    /// Appends one network proxy connection record.
+    pub async fn insert_network_connection(
+        &self,
+        connection: &NetworkConnection,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO network_connections (
+    thread_id,
+    process,
+    client_addr,
+    protocol,
+    host,
+    port,
+    method,
+    path,
+    decision,
+    reason,
+    rule,
+    status,
+    bytes_sent,
+    bytes_received,
+    har_entry,
+    created_at
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(connection.thread_id.to_string())
+        .bind(connection.process.as_deref())
+        .bind(connection.client_addr.as_deref())
+        .bind(connection.protocol.as_str())
+        .bind(connection.host.as_str())
+        .bind(connection.port.map(i64::from))
+        .bind(connection.method.as_deref())
+        .bind(connection.path.as_deref())
+        .bind(connection.decision.as_str())
+        .bind(connection.reason.as_deref())
+        .bind(connection.rule.as_deref())
+        .bind(connection.status.map(i64::from))
+        .bind(connection.bytes_sent.map(i64::try_from).transpose()?)
+        .bind(connection.bytes_received.map(i64::try_from).transpose()?)
+        .bind(connection.har_entry.as_deref())
+        .bind(connection.created_at)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Lists the connections recorded for `thread_id`, oldest first.
+    pub async fn list_network_connections(
+        &self,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Vec<NetworkConnection>> {
+        let rows = sqlx::query(
+            r#"
+SELECT
+    thread_id,
+    process,
+    client_addr,
+    protocol,
+    host,
+    port,
+    method,
+    path,
+    decision,
+    reason,
+    rule,
+    status,
+    bytes_sent,
+    bytes_received,
+    har_entry,
+    created_at
+FROM network_connections
+WHERE thread_id = ?
+ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(thread_id.to_string())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.iter().map(NetworkConnection::try_from_row).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateRuntime;
+    use super::test_support::unique_temp_dir;
+    use crate::NetworkConnection;
+    use crate::NetworkConnectionDecision;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    fn connection(
+        thread_id: ThreadId,
+        host: &str,
+        decision: NetworkConnectionDecision,
+        created_at: i64,
+    ) -> NetworkConnection {
+        NetworkConnection {
+            thread_id,
+            process: Some("call-1".to_string()),
+            client_addr: Some("127.0.0.1:50000".to_string()),
+            protocol: "https".to_string(),
+            host: host.to_string(),
+            port: Some(443),
+            method: Some("GET".to_string()),
+            path: Some("/".to_string()),
+            decision,
+            reason: (decision == NetworkConnectionDecision::Deny)
+                .then(|| "not_allowed".to_string()),
+            rule: None,
+            status: (decision == NetworkConnectionDecision::Allow).then_some(200),
+            bytes_sent: Some(12),
+            bytes_received: Some(u64::from(u32::MAX) + 1),
+            har_entry: None,
+            created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn network_connections_round_trip_per_thread() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string())
+            .await
+            .expect("initialize runtime");
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let other_thread_id =
+            ThreadId::from_string(&Uuid::new_v4().to_string()).expect("other thread id");
+
+        let allowed = connection(thread_id, "github.com", NetworkConnectionDecision::Allow, 2);
+        let denied = connection(thread_id, "example.com", NetworkConnectionDecision::Deny, 1);
+        for record in [
+            &allowed,
+            &denied,
+            &connection(
+                other_thread_id,
+                "github.com",
+                NetworkConnectionDecision::Allow,
+                1,
+            ),
+        ] {
+            runtime
+                .insert_network_connection(record)
+                .await
+                .expect("insert connection");
+        }
+
+        let connections = runtime
+            .list_network_connections(thread_id)
+            .await
+            .expect("list connections");
+        assert_eq!(connections, vec![denied, allowed]);
+    }
+}
@@ -442,6 +442,45 @@ ON CONFLICT(thread_id, position) DO NOTHING
         Ok(())
     }
 
+    /// Record that an authenticated client identity (for example an app-server connection's
+    /// bearer-token name or Unix peer) started or drove a thread. Repeated records are ignored.
+    pub async fn record_thread_client_identity(
+        &self,
+        thread_id: ThreadId,
+        identity: &str,
+        seen_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO thread_client_identities (thread_id, identity, first_seen_at)
+VALUES (?, ?, ?)
+ON CONFLICT(thread_id, identity) DO NOTHING
+            "#,
+        )
+        .bind(thread_id.to_string())
+        .bind(identity)
+        .bind(datetime_to_epoch_seconds(seen_at))
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// List the client identities recorded for a thread, oldest first.
+    pub async fn list_thread_client_identities(
+        &self,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Vec<String>> {
+        let rows = sqlx::query(
+            "SELECT identity FROM thread_client_identities WHERE thread_id = ? ORDER BY first_seen_at ASC, identity ASC",
+        )
+        .bind(thread_id.to_string())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|row| row.try_get("identity").map_err(anyhow::Error::from))
+            .collect()
+    }
+
     /// Apply rollout items incrementally using the underlying database.
     pub async fn apply_rollout_items(
         &self,
@@ -547,6 +586,10 @@ ON CONFLICT(thread_id, position) DO NOTHING
 
     /// Delete a thread metadata row by id.
     pub async fn delete_thread(&self, thread_id: ThreadId) -> anyhow::Result<u64> {
+        sqlx::query("DELETE FROM thread_client_identities WHERE thread_id = ?")
+            .bind(thread_id.to_string())
+            .execute(self.pool.as_ref())
+            .await?;
         let result = sqlx::query("DELETE FROM threads WHERE id = ?")
             .bind(thread_id.to_string())
             .execute(self.pool.as_ref())
@@ -876,6 +919,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn thread_client_identities_are_recorded_once_and_deleted_with_thread() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize");
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000790").expect("valid thread id");
+        let first_seen = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).expect("timestamp");
+        let later = DateTime::<Utc>::from_timestamp(1_700_000_100, 0).expect("timestamp");
+
+        for (identity, seen_at) in [
+            ("token:ci-bot", first_seen),
+            ("unix:uid=1000", later),
+            ("token:ci-bot", later),
+        ] {
+            runtime
+                .record_thread_client_identity(thread_id, identity, seen_at)
+                .await
+                .expect("record identity");
+        }
+
+        assert_eq!(
+            runtime
+                .list_thread_client_identities(thread_id)
+                .await
+                .expect("list identities"),
+            vec!["token:ci-bot".to_string(), "unix:uid=1000".to_string()]
+        );
+
+        runtime
+            .delete_thread(thread_id)
+            .await
+            .expect("delete thread");
+        assert_eq!(
+            runtime
+                .list_thread_client_identities(thread_id)
+                .await
+                .expect("list identities"),
+            Vec::<String>::new()
+        );
+    }
+
     #[tokio::test]
     async fn insert_thread_if_absent_preserves_existing_metadata() {
         let codex_home = unique_temp_dir();
@@ -512,6 +512,7 @@ mod tests {
                     changes: HashMap::new(),
                     reason: None,
                     grant_root: None,
+                    skipped: Vec::new(),
                 },
             ),
         });
@@ -559,6 +560,7 @@ mod tests {
                     changes: HashMap::new(),
                     reason: None,
                     grant_root: None,
+                    skipped: Vec::new(),
                 },
             ),
         });
@@ -208,6 +208,7 @@ use crate::bottom_pane::textarea::TextArea;
 use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
+use crate::file_search::CONTENT_QUERY_PREFIX;
 use crate::history_cell;
 use crate::tui::FrameRequester;
 use crate::ui_consts::LIVE_PREFIX_COLS;
@@ -3509,9 +3510,11 @@ impl ChatComposer {
                 .send(AppEvent::StartFileSearch(query.clone()));
         }
 
+        // A bare `@#` has nothing to search for yet; show the hint instead.
+        let show_empty_prompt = query.is_empty() || query == CONTENT_QUERY_PREFIX;
         match &mut self.active_popup {
             ActivePopup::File(popup) => {
-                if query.is_empty() {
+                if show_empty_prompt {
                     popup.set_empty_prompt();
                 } else {
                     popup.set_query(&query);
@@ -3519,7 +3522,7 @@ impl ChatComposer {
             }
             _ => {
                 let mut popup = FileSearchPopup::new();
-                if query.is_empty() {
+                if show_empty_prompt {
                     popup.set_empty_prompt();
                 } else {
                     popup.set_query(&query);
@@ -7149,6 +7152,7 @@ mod tests {
                 path: PathBuf::from("src/main.rs"),
                 root: PathBuf::from("/tmp"),
                 indices: None,
+                content: None,
             }],
         );
 
@@ -109,6 +109,33 @@ impl FileSearchPopup {
     }
 }
 
+/// Row text for a match: the path for path matches, or `path:line  text`
+/// for content matches. Indices are shifted to point into the row text.
+fn row_name_and_indices(m: &FileMatch) -> (String, Option<Vec<usize>>) {
+    let path = m.path.to_string_lossy();
+    let indices = m
+        .indices
+        .as_ref()
+        .map(|v| v.iter().map(|&i| i as usize).collect::<Vec<_>>());
+    let Some(content) = &m.content else {
+        return (path.to_string(), indices);
+    };
+    let text = content.line.trim_start();
+    let trimmed = content.line[..content.line.len() - text.len()]
+        .chars()
+        .count();
+    let prefix = format!("{path}:{}  ", content.line_number);
+    let offset = prefix.chars().count();
+    let indices = indices.map(|indices| {
+        indices
+            .into_iter()
+            .filter(|&i| i >= trimmed)
+            .map(|i| i - trimmed + offset)
+            .collect()
+    });
+    (format!("{prefix}{}", text.trim_end()), indices)
+}
+
 impl WidgetRef for &FileSearchPopup {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         // Convert matches to GenericDisplayRow, translating indices to usize at the UI boundary.
@@ -117,19 +144,19 @@ impl WidgetRef for &FileSearchPopup {
         } else {
             self.matches
                 .iter()
-                .map(|m| GenericDisplayRow {
-                    name: m.path.to_string_lossy().to_string(),
-                    name_prefix_spans: Vec::new(),
-                    match_indices: m
-                        .indices
-                        .as_ref()
-                        .map(|v| v.iter().map(|&i| i as usize).collect()),
-                    display_shortcut: None,
-                    description: None,
-                    category_tag: None,
-                    wrap_indent: None,
-                    is_disabled: false,
-                    disabled_reason: None,
+                .map(|m| {
+                    let (name, match_indices) = row_name_and_indices(m);
+                    GenericDisplayRow {
+                        name,
+                        name_prefix_spans: Vec::new(),
+                        match_indices,
+                        display_shortcut: None,
+                        description: None,
+                        category_tag: None,
+                        wrap_indent: None,
+                        is_disabled: false,
+                        disabled_reason: None,
+                    }
                 })
                 .collect()
         };
@@ -150,3 +177,34 @@ impl WidgetRef for &FileSearchPopup {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::row_name_and_indices;
+    use codex_file_search::ContentMatch;
+    use codex_file_search::FileMatch;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn content_rows_show_line_and_shift_indices() {
+        let file_match = FileMatch {
+            score: 100,
+            path: PathBuf::from("src/lib.rs"),
+            root: PathBuf::from("/repo"),
+            indices: Some(vec![8, 9, 10]),
+            content: Some(ContentMatch {
+                line_number: 12,
+                column: 9,
+                line: "    let foo = 1;".to_string(),
+            }),
+        };
+        assert_eq!(
+            row_name_and_indices(&file_match),
+            (
+                "src/lib.rs:12  let foo = 1;".to_string(),
+                Some(vec![19, 20, 21])
+            )
+        );
+    }
+}
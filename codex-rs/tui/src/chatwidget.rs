@@ -114,6 +114,8 @@ use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ImageGenerationBeginEvent;
 use codex_protocol::protocol::ImageGenerationEndEvent;
+use codex_protocol::protocol::ListBackgroundJobsResponseEvent;
+use codex_protocol::protocol::ListCheckpointsResponseEvent;
 use codex_protocol::protocol::ListCustomPromptsResponseEvent;
 use codex_protocol::protocol::ListSkillsResponseEvent;
 use codex_protocol::protocol::McpListToolsResponseEvent;
@@ -124,6 +126,7 @@ use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::PatchApplyBeginEvent;
+use codex_protocol::protocol::ProjectDocLoadedEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
@@ -687,6 +690,8 @@ pub(crate) struct ChatWidget {
     current_cwd: Option<PathBuf>,
     // Runtime network proxy bind addresses from SessionConfigured.
     session_network_proxy: Option<codex_protocol::protocol::SessionNetworkProxyRuntime>,
+    // Directory-scoped project docs the session loaded after startup.
+    scoped_project_docs: Vec<ProjectDocLoadedEvent>,
     // Shared latch so we only warn once about invalid status-line item IDs.
     status_line_invalid_items_warned: Arc<AtomicBool>,
     // Cached git branch name for the status line (None if unknown).
@@ -1182,6 +1187,7 @@ impl ChatWidget {
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.set_skills(None);
         self.session_network_proxy = event.network_proxy.clone();
+        self.scoped_project_docs.clear();
         self.thread_id = Some(event.session_id);
         self.thread_name = event.thread_name.clone();
         self.forked_from = event.forked_from_id;
@@ -2522,6 +2528,16 @@ impl ChatWidget {
         }
     }
 
+    fn on_list_checkpoints(&mut self, event: ListCheckpointsResponseEvent) {
+        self.add_to_history(history_cell::new_checkpoints_output(event));
+        self.request_redraw();
+    }
+
+    fn on_list_background_jobs(&mut self, event: ListBackgroundJobsResponseEvent) {
+        self.add_to_history(history_cell::new_background_jobs_output(event.jobs));
+        self.request_redraw();
+    }
+
     fn on_stream_error(&mut self, message: String, additional_details: Option<String>) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -3163,6 +3179,7 @@ impl ChatWidget {
             current_rollout_path: None,
             current_cwd,
             session_network_proxy: None,
+            scoped_project_docs: Vec::new(),
             status_line_invalid_items_warned,
             status_line_branch: None,
             status_line_branch_cwd: None,
@@ -3345,6 +3362,7 @@ impl ChatWidget {
             current_rollout_path: None,
             current_cwd,
             session_network_proxy: None,
+            scoped_project_docs: Vec::new(),
             status_line_invalid_items_warned,
             status_line_branch: None,
             status_line_branch_cwd: None,
@@ -3519,6 +3537,7 @@ impl ChatWidget {
             current_rollout_path: None,
             current_cwd,
             session_network_proxy: None,
+            scoped_project_docs: Vec::new(),
             status_line_invalid_items_warned,
             status_line_branch: None,
             status_line_branch_cwd: None,
@@ -3977,9 +3996,15 @@ impl ChatWidget {
                 }
                 self.request_quit_without_confirmation();
             }
-            // SlashCommand::Undo => {
-            //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
-            // }
+            SlashCommand::Undo => {
+                self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
+            }
+            SlashCommand::Redo => {
+                self.app_event_tx.send(AppEvent::CodexOp(Op::Redo));
+            }
+            SlashCommand::Checkpoints => {
+                self.submit_op(Op::ListCheckpoints);
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -4045,6 +4070,9 @@ impl ChatWidget {
             }
             SlashCommand::Ps => {
                 self.add_ps_output();
+                if self.config.features.enabled(Feature::PersistentJobs) {
+                    self.submit_op(Op::ListBackgroundJobs);
+                }
             }
             SlashCommand::Clean => {
                 self.clean_background_terminals();
@@ -4106,6 +4134,7 @@ impl ChatWidget {
                         ]),
                         reason: None,
                         grant_root: Some(PathBuf::from("/tmp")),
+                        skipped: Vec::new(),
                     }),
                 }));
             }
@@ -4221,6 +4250,41 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Undo if !trimmed.is_empty() => {
+                let Ok(num_turns) = trimmed.parse::<u32>() else {
+                    self.add_error_message("Usage: /undo [turns]".to_string());
+                    return;
+                };
+                if self
+                    .bottom_pane
+                    .prepare_inline_args_submission(false)
+                    .is_none()
+                {
+                    return;
+                }
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::UndoTurns { num_turns }));
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Ps if !trimmed.is_empty() => {
+                let process_id = match trimmed.split_whitespace().collect::<Vec<_>>()[..] {
+                    ["kill", process_id] => process_id.to_string(),
+                    _ => {
+                        self.add_error_message("Usage: /ps [kill <job id>]".to_string());
+                        return;
+                    }
+                };
+                if self
+                    .bottom_pane
+                    .prepare_inline_args_submission(false)
+                    .is_none()
+                {
+                    return;
+                }
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::KillBackgroundJob { process_id }));
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -4749,7 +4813,9 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
-            EventMsg::ModelReroute(_) => {}
+            EventMsg::ModelReroute(_)
+            | EventMsg::GuardianAssessment(_)
+            | EventMsg::SecretsRedacted(_) => {}
             EventMsg::Error(ErrorEvent {
                 message,
                 codex_error_info,
@@ -4815,6 +4881,9 @@ impl ChatWidget {
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
+            EventMsg::ListCheckpointsResponse(ev) => self.on_list_checkpoints(ev),
+            EventMsg::ListBackgroundJobsResponse(ev) => self.on_list_background_jobs(ev),
+            EventMsg::ProjectDocLoaded(ev) => self.scoped_project_docs.push(ev),
             EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
@@ -5157,6 +5226,7 @@ impl ChatWidget {
         self.add_to_history(crate::debug_config::new_debug_config_output(
             &self.config,
             self.session_network_proxy.as_ref(),
+            &self.scoped_project_docs,
         ));
     }
 
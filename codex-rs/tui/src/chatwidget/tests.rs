@@ -1840,6 +1840,7 @@ async fn make_chatwidget_manual(
         current_rollout_path: None,
         current_cwd: None,
         session_network_proxy: None,
+        scoped_project_docs: Vec::new(),
         status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
         status_line_branch: None,
         status_line_branch_cwd: None,
@@ -3481,6 +3482,7 @@ fn end_exec(
             } else {
                 CoreExecCommandStatus::Failed
             },
+            seccomp_denial: None,
         }),
     });
 }
@@ -4630,6 +4632,7 @@ async fn exec_end_without_begin_uses_event_command() {
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
             status: CoreExecCommandStatus::Completed,
+            seccomp_denial: None,
         }),
     });
 
@@ -8073,6 +8076,7 @@ async fn approval_modal_patch_snapshot() -> anyhow::Result<()> {
         changes,
         reason: Some("The model wants to apply changes".into()),
         grant_root: Some(PathBuf::from("/tmp")),
+        skipped: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-patch".into(),
@@ -8526,6 +8530,7 @@ async fn apply_patch_events_emit_history_cells() {
         changes,
         reason: None,
         grant_root: None,
+        skipped: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -8566,6 +8571,7 @@ async fn apply_patch_events_emit_history_cells() {
         turn_id: "turn-c1".into(),
         auto_approved: true,
         changes: changes2,
+        skipped: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -8626,6 +8632,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             changes: proposed_changes,
             reason: None,
             grant_root: None,
+            skipped: Vec::new(),
         }),
     });
     drain_insert_history(&mut rx);
@@ -8644,6 +8651,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             turn_id: "turn-c1".into(),
             auto_approved: false,
             changes: apply_changes,
+            skipped: Vec::new(),
         }),
     });
 
@@ -8675,6 +8683,7 @@ async fn apply_patch_manual_flow_snapshot() {
             changes: proposed_changes,
             reason: Some("Manual review required".into()),
             grant_root: None,
+            skipped: Vec::new(),
         }),
     });
     let history_before_apply = drain_insert_history(&mut rx);
@@ -8697,6 +8706,7 @@ async fn apply_patch_manual_flow_snapshot() {
             turn_id: "turn-c1".into(),
             auto_approved: false,
             changes: apply_changes,
+            skipped: Vec::new(),
         }),
     });
     let approved_lines = drain_insert_history(&mut rx)
@@ -8726,6 +8736,7 @@ async fn apply_patch_approval_sends_op_with_call_id() {
         changes,
         reason: None,
         grant_root: None,
+        skipped: Vec::new(),
     };
     chat.handle_codex_event(Event {
         id: "sub-123".into(),
@@ -8770,6 +8781,7 @@ async fn apply_patch_full_flow_integration_like() {
             changes,
             reason: None,
             grant_root: None,
+            skipped: Vec::new(),
         }),
     });
 
@@ -8810,6 +8822,7 @@ async fn apply_patch_full_flow_integration_like() {
             turn_id: "turn-call-1".into(),
             auto_approved: false,
             changes: changes2,
+            skipped: Vec::new(),
         }),
     });
     let mut end_changes = HashMap::new();
@@ -8854,6 +8867,7 @@ async fn apply_patch_untrusted_shows_approval_modal() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            skipped: Vec::new(),
         }),
     });
 
@@ -8908,6 +8922,7 @@ async fn apply_patch_request_shows_diff_summary() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            skipped: Vec::new(),
         }),
     });
 
@@ -9537,6 +9552,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            seccomp_denial: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -9,6 +9,9 @@ use codex_core::config_loader::RequirementSource;
 use codex_core::config_loader::ResidencyRequirement;
 use codex_core::config_loader::SandboxModeRequirement;
 use codex_core::config_loader::WebSearchModeRequirement;
+use codex_core::project_doc::ResolvedProjectDoc;
+use codex_core::project_doc::describe_project_docs;
+use codex_protocol::protocol::ProjectDocLoadedEvent;
 use codex_protocol::protocol::SessionNetworkProxyRuntime;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -17,9 +20,21 @@ use toml::Value as TomlValue;
 pub(crate) fn new_debug_config_output(
     config: &Config,
     session_network_proxy: Option<&SessionNetworkProxyRuntime>,
+    scoped_project_docs: &[ProjectDocLoadedEvent],
 ) -> PlainHistoryCell {
     let mut lines = render_debug_config_lines(&config.config_layer_stack);
 
+    lines.push("".into());
+    match describe_project_docs(config) {
+        Ok(startup_docs) => {
+            lines.extend(render_project_doc_lines(&startup_docs, scoped_project_docs));
+        }
+        Err(err) => {
+            lines.extend(render_project_doc_lines(&[], scoped_project_docs));
+            lines.push(format!("  error reading project docs: {err}").red().into());
+        }
+    }
+
     if let Some(proxy) = session_network_proxy {
         lines.push("".into());
         lines.push("Session runtime:".bold().into());
@@ -44,6 +59,38 @@ pub(crate) fn new_debug_config_output(
     PlainHistoryCell::new(lines)
 }
 
+/// Lists the AGENTS.md files in context: those loaded at startup (with the
+/// files they `@include`) followed by those loaded mid-session.
+fn render_project_doc_lines(
+    startup_docs: &[ResolvedProjectDoc],
+    scoped_docs: &[ProjectDocLoadedEvent],
+) -> Vec<Line<'static>> {
+    let mut lines = vec!["Project docs:".bold().into()];
+    if startup_docs.is_empty() && scoped_docs.is_empty() {
+        lines.push("  <none>".dim().into());
+        return lines;
+    }
+
+    for doc in startup_docs {
+        lines.push(format!("  - {} (startup)", doc.path.display()).into());
+        for include in &doc.includes {
+            lines.push(format!("     include: {}", include.display()).dim().into());
+        }
+    }
+    for doc in scoped_docs {
+        let truncated = if doc.truncated { ", truncated" } else { "" };
+        lines.push(
+            format!(
+                "  - {} (scoped, via {}{truncated})",
+                doc.path.display(),
+                doc.trigger_path.display()
+            )
+            .into(),
+        );
+    }
+    lines
+}
+
 fn session_all_proxy_url(http_addr: &str, socks_addr: &str, socks_enabled: bool) -> String {
     if socks_enabled {
         format!("socks5h://{socks_addr}")
@@ -386,6 +433,7 @@ fn format_network_constraints(network: &NetworkConstraints) -> String {
 #[cfg(test)]
 mod tests {
     use super::render_debug_config_lines;
+    use super::render_project_doc_lines;
     use super::session_all_proxy_url;
     use codex_app_server_protocol::ConfigLayerSource;
     use codex_core::config::Constrained;
@@ -402,12 +450,15 @@ mod tests {
     use codex_core::config_loader::SandboxModeRequirement;
     use codex_core::config_loader::Sourced;
     use codex_core::config_loader::WebSearchModeRequirement;
+    use codex_core::project_doc::ResolvedProjectDoc;
     use codex_protocol::config_types::WebSearchMode;
     use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::ProjectDocLoadedEvent;
     use codex_protocol::protocol::SandboxPolicy;
     use codex_utils_absolute_path::AbsolutePathBuf;
     use ratatui::text::Line;
     use std::collections::BTreeMap;
+    use std::path::PathBuf;
     use toml::Value as TomlValue;
 
     fn empty_toml_table() -> TomlValue {
@@ -682,4 +733,34 @@ approval_policy = "never"
             "http://127.0.0.1:3128".to_string()
         );
     }
+
+    #[test]
+    fn debug_config_output_lists_startup_and_scoped_project_docs() {
+        let startup = vec![ResolvedProjectDoc {
+            path: PathBuf::from("/repo/AGENTS.md"),
+            text: "root doc".to_string(),
+            includes: vec![PathBuf::from("/repo/docs/style.md")],
+        }];
+        let scoped = vec![ProjectDocLoadedEvent {
+            path: PathBuf::from("/repo/crates/foo/AGENTS.md"),
+            trigger_path: PathBuf::from("/repo/crates/foo/src/lib.rs"),
+            truncated: true,
+        }];
+
+        let rendered = render_to_text(&render_project_doc_lines(&startup, &scoped));
+        assert_eq!(
+            rendered,
+            [
+                "Project docs:",
+                "  - /repo/AGENTS.md (startup)",
+                "     include: /repo/docs/style.md",
+                "  - /repo/crates/foo/AGENTS.md (scoped, via /repo/crates/foo/src/lib.rs, truncated)",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            render_to_text(&render_project_doc_lines(&[], &[])),
+            "Project docs:\n  <none>"
+        );
+    }
 }
@@ -4,6 +4,11 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//!
+//! A token that starts with [`CONTENT_QUERY_PREFIX`] (`@#needle`) searches
+//! file contents for `needle` instead of fuzzy-matching paths. Results are
+//! reported under the full query, prefix included, so the popup can tell
+//! them apart from stale path results.
 
 use codex_file_search as file_search;
 use std::path::PathBuf;
@@ -13,6 +18,9 @@ use std::sync::Mutex;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 
+/// Prefix of an `@` token that switches the search to file contents.
+pub(crate) const CONTENT_QUERY_PREFIX: &str = "#";
+
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
@@ -22,6 +30,7 @@ pub(crate) struct FileSearchManager {
 struct SearchState {
     latest_query: String,
     session: Option<file_search::FileSearchSession>,
+    session_mode: file_search::FileSearchMode,
     session_token: usize,
 }
 
@@ -31,6 +40,7 @@ impl FileSearchManager {
             state: Arc::new(Mutex::new(SearchState {
                 latest_query: String::new(),
                 session: None,
+                session_mode: file_search::FileSearchMode::Path,
                 session_token: 0,
             })),
             search_dir,
@@ -59,31 +69,39 @@ impl FileSearchManager {
         st.latest_query.clear();
         st.latest_query.push_str(&query);
 
-        if query.is_empty() {
+        let (mode, needle) = split_query(&query);
+        if needle.is_empty() {
             st.session.take();
             return;
         }
 
-        if st.session.is_none() {
-            self.start_session_locked(&mut st);
+        if st.session.is_none() || st.session_mode != mode {
+            self.start_session_locked(&mut st, mode);
         }
         if let Some(session) = st.session.as_ref() {
-            session.update_query(&query);
+            session.update_query(needle);
         }
     }
 
-    fn start_session_locked(&self, st: &mut SearchState) {
+    fn start_session_locked(&self, st: &mut SearchState, mode: file_search::FileSearchMode) {
         st.session_token = st.session_token.wrapping_add(1);
         let session_token = st.session_token;
         let reporter = Arc::new(TuiSessionReporter {
             state: self.state.clone(),
             app_tx: self.app_tx.clone(),
             session_token,
+            query_prefix: if mode.is_content() {
+                CONTENT_QUERY_PREFIX
+            } else {
+                ""
+            },
         });
+        st.session_mode = mode;
         let session = file_search::create_session(
             vec![self.search_dir.clone()],
             file_search::FileSearchOptions {
                 compute_indices: true,
+                mode,
                 ..Default::default()
             },
             reporter,
@@ -99,10 +117,20 @@ impl FileSearchManager {
     }
 }
 
+/// Splits an `@` token into the search mode and the text to search for.
+fn split_query(query: &str) -> (file_search::FileSearchMode, &str) {
+    match query.strip_prefix(CONTENT_QUERY_PREFIX) {
+        Some(needle) => (file_search::FileSearchMode::ContentLiteral, needle),
+        None => (file_search::FileSearchMode::Path, query),
+    }
+}
+
 struct TuiSessionReporter {
     state: Arc<Mutex<SearchState>>,
     app_tx: AppEventSender,
     session_token: usize,
+    /// Re-added to reported queries so they match the token the user typed.
+    query_prefix: &'static str,
 }
 
 impl TuiSessionReporter {
@@ -115,7 +143,7 @@ impl TuiSessionReporter {
         {
             return;
         }
-        let query = snapshot.query.clone();
+        let query = format!("{}{}", self.query_prefix, snapshot.query);
         drop(st);
         self.app_tx.send(AppEvent::FileSearchResult {
             query,
@@ -131,3 +159,20 @@ impl file_search::SessionReporter for TuiSessionReporter {
 
     fn on_complete(&self) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::split_query;
+    use codex_file_search::FileSearchMode;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hash_prefix_selects_content_search() {
+        assert_eq!(split_query("src/ma"), (FileSearchMode::Path, "src/ma"));
+        assert_eq!(
+            split_query("#fn main"),
+            (FileSearchMode::ContentLiteral, "fn main")
+        );
+        assert_eq!(split_query("#"), (FileSearchMode::ContentLiteral, ""));
+    }
+}
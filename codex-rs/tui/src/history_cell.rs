@@ -53,7 +53,10 @@ use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::BackgroundJob;
+use codex_protocol::protocol::BackgroundJobStatus;
 use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::ListCheckpointsResponseEvent;
 use codex_protocol::protocol::McpAuthStatus;
 use codex_protocol::protocol::McpInvocation;
 use codex_protocol::protocol::SessionConfiguredEvent;
@@ -1714,6 +1717,123 @@ pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
 }
 
 /// Render MCP tools grouped by connection using the fully-qualified tool names.
+pub(crate) fn new_checkpoints_output(event: ListCheckpointsResponseEvent) -> PlainHistoryCell {
+    let ListCheckpointsResponseEvent {
+        checkpoints,
+        redo_depth,
+    } = event;
+    let mut lines: Vec<Line<'static>> = vec!["/checkpoints".magenta().into(), "".into()];
+
+    if checkpoints.is_empty() {
+        lines.push("  • No checkpoints recorded yet.".italic().into());
+    }
+
+    for checkpoint in checkpoints {
+        let turns = if checkpoint.turns_back == 1 {
+            "1 turn back".to_string()
+        } else {
+            format!("{} turns back", checkpoint.turns_back)
+        };
+        let short_id: String = checkpoint.snapshot_id.chars().take(7).collect();
+        lines.push(vec!["  • ".into(), turns.bold(), "  ".into(), short_id.dim()].into());
+
+        let stats = checkpoint
+            .diff
+            .as_deref()
+            .map(unified_diff_file_stats)
+            .unwrap_or_default();
+        if stats.is_empty() {
+            lines.push("    no file changes recorded".dim().into());
+        }
+        for (path, added, removed) in stats {
+            lines.push(
+                vec![
+                    "    ".into(),
+                    path.into(),
+                    " ".into(),
+                    format!("+{added}").green(),
+                    " ".into(),
+                    format!("-{removed}").red(),
+                ]
+                .into(),
+            );
+        }
+    }
+
+    if redo_depth > 0 {
+        lines.push("".into());
+        lines.push(
+            format!("  {redo_depth} undo(s) can be reapplied with /redo")
+                .dim()
+                .into(),
+        );
+    }
+
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_background_jobs_output(jobs: Vec<BackgroundJob>) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec!["Background jobs".bold().into(), "".into()];
+
+    if jobs.is_empty() {
+        lines.push("  • No background jobs recorded.".italic().into());
+    }
+
+    for job in jobs {
+        let status = match (job.status, job.exit_code) {
+            (BackgroundJobStatus::Running, _) => "running".green(),
+            (BackgroundJobStatus::Exited, Some(0)) => "exited".dim(),
+            (BackgroundJobStatus::Exited, Some(code)) => format!("exited {code}").red(),
+            (BackgroundJobStatus::Exited, None) => "killed".dim(),
+            (BackgroundJobStatus::Lost, _) => "lost".magenta(),
+        };
+        lines.push(
+            vec![
+                "  • ".into(),
+                job.process_id.bold(),
+                "  ".into(),
+                status,
+                "  ".into(),
+                exec_snippet(&job.command).into(),
+            ]
+            .into(),
+        );
+        lines.push(format!("    log {}", job.log_path.display()).dim().into());
+    }
+
+    lines.push("".into());
+    lines.push("  Stop a running job with /ps kill <id>".dim().into());
+
+    PlainHistoryCell { lines }
+}
+
+/// Per-file added/removed line counts for a unified diff, in diff order.
+fn unified_diff_file_stats(diff: &str) -> Vec<(String, usize, usize)> {
+    let mut stats: Vec<(String, usize, usize)> = Vec::new();
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let path = rest
+                .rsplit_once(" b/")
+                .map_or(rest, |(_, path)| path)
+                .to_string();
+            stats.push((path, 0, 0));
+            continue;
+        }
+        let Some((_, added, removed)) = stats.last_mut() else {
+            continue;
+        };
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if line.starts_with('+') {
+            *added += 1;
+        } else if line.starts_with('-') {
+            *removed += 1;
+        }
+    }
+    stats
+}
+
 pub(crate) fn new_mcp_tools_output(
     config: &Config,
     tools: HashMap<String, codex_protocol::mcp::Tool>,
@@ -3022,6 +3142,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checkpoints_output_lists_turns_with_diff_stats() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n-old\n+new\n+more\n";
+        let cell = new_checkpoints_output(ListCheckpointsResponseEvent {
+            checkpoints: vec![
+                codex_protocol::protocol::Checkpoint {
+                    turns_back: 1,
+                    snapshot_id: "0123456789abcdef".to_string(),
+                    turn_id: Some("turn-2".to_string()),
+                    diff: Some(diff.to_string()),
+                },
+                codex_protocol::protocol::Checkpoint {
+                    turns_back: 2,
+                    snapshot_id: "fedcba9876543210".to_string(),
+                    turn_id: None,
+                    diff: None,
+                },
+            ],
+            redo_depth: 1,
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "/checkpoints",
+                "",
+                "  • 1 turn back  0123456",
+                "    src/lib.rs +2 -1",
+                "  • 2 turns back  fedcba9",
+                "    no file changes recorded",
+                "",
+                "  1 undo(s) can be reapplied with /redo",
+            ]
+        );
+    }
+
+    #[test]
+    fn background_jobs_output_lists_status_and_log() {
+        let job = |process_id: &str, status, exit_code| BackgroundJob {
+            process_id: process_id.to_string(),
+            command: vec!["bash".into(), "-lc".into(), "npm run dev".into()],
+            cwd: PathBuf::from("/repo"),
+            started_at: 0,
+            status,
+            exit_code,
+            log_path: PathBuf::from(format!("/logs/{process_id}.log")),
+        };
+        let cell = new_background_jobs_output(vec![
+            job("4821", BackgroundJobStatus::Running, None),
+            job("4822", BackgroundJobStatus::Exited, Some(2)),
+            job("4823", BackgroundJobStatus::Lost, None),
+        ]);
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "Background jobs",
+                "",
+                "  • 4821  running  npm run dev",
+                "    log /logs/4821.log",
+                "  • 4822  exited 2  npm run dev",
+                "    log /logs/4822.log",
+                "  • 4823  lost  npm run dev",
+                "    log /logs/4823.log",
+                "",
+                "  Stop a running job with /ps kill <id>",
+            ]
+        );
+    }
+
     #[test]
     fn web_search_history_cell_short_query_does_not_wrap() {
         let query = "short query".to_string();
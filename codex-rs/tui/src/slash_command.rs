@@ -32,7 +32,9 @@ pub enum SlashCommand {
     Plan,
     Collab,
     Agent,
-    // Undo,
+    Undo,
+    Redo,
+    Checkpoints,
     Diff,
     Copy,
     Mention,
@@ -75,7 +77,9 @@ impl SlashCommand {
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Clear => "clear the terminal and start a new chat",
             SlashCommand::Fork => "fork the current chat",
-            // SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::Undo => "restore the workspace to before the last turn: /undo [turns]",
+            SlashCommand::Redo => "reapply the changes removed by the last /undo",
+            SlashCommand::Checkpoints => "list turn checkpoints available to /undo",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
             SlashCommand::Copy => "copy the latest Codex output to your clipboard",
@@ -124,6 +128,8 @@ impl SlashCommand {
             self,
             SlashCommand::Review
                 | SlashCommand::Rename
+                | SlashCommand::Undo
+                | SlashCommand::Ps
                 | SlashCommand::Plan
                 | SlashCommand::Fast
                 | SlashCommand::SandboxReadRoot
@@ -138,7 +144,8 @@ impl SlashCommand {
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
-            // | SlashCommand::Undo
+            | SlashCommand::Undo
+            | SlashCommand::Redo
             | SlashCommand::Model
             | SlashCommand::Fast
             | SlashCommand::Personality
@@ -156,6 +163,7 @@ impl SlashCommand {
             SlashCommand::Diff
             | SlashCommand::Copy
             | SlashCommand::Rename
+            | SlashCommand::Checkpoints
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
@@ -28,6 +28,8 @@ pub enum GitToolingError {
     PathEscapesRepository { path: PathBuf },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
+    #[error("snapshot store: {0}")]
+    ShadowStore(String),
     #[error(transparent)]
     Walkdir(#[from] WalkdirError),
     #[error(transparent)]
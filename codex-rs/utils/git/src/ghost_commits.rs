@@ -20,6 +20,7 @@ use crate::operations::resolve_repository_root;
 use crate::operations::run_git_for_status;
 use crate::operations::run_git_for_stdout;
 use crate::operations::run_git_for_stdout_all;
+use crate::shadow_store::ShadowStore;
 
 /// Default commit message used for ghost commits when none is provided.
 const DEFAULT_COMMIT_MESSAGE: &str = "codex snapshot";
@@ -32,7 +33,7 @@ const DEFAULT_IGNORE_LARGE_UNTRACKED_FILES: i64 = 10 * 1024 * 1024;
 ///
 /// These are typically large dependency or build trees that are not useful
 /// for undo and can cause snapshots to grow without bound.
-const DEFAULT_IGNORED_DIR_NAMES: &[&str] = &[
+pub(crate) const DEFAULT_IGNORED_DIR_NAMES: &[&str] = &[
     "node_modules",
     ".venv",
     "venv",
@@ -66,6 +67,9 @@ pub struct GhostSnapshotConfig {
     pub ignore_large_untracked_files: Option<i64>,
     pub ignore_large_untracked_dirs: Option<i64>,
     pub disable_warnings: bool,
+    /// Where to keep content-addressed snapshots of directories that are not
+    /// inside a Git repository. `None` skips snapshots outside Git.
+    pub shadow_store: Option<PathBuf>,
 }
 
 impl Default for GhostSnapshotConfig {
@@ -74,6 +78,7 @@ impl Default for GhostSnapshotConfig {
             ignore_large_untracked_files: Some(DEFAULT_IGNORE_LARGE_UNTRACKED_FILES),
             ignore_large_untracked_dirs: Some(DEFAULT_IGNORE_LARGE_UNTRACKED_DIRS),
             disable_warnings: false,
+            shadow_store: None,
         }
     }
 }
@@ -299,10 +304,20 @@ pub fn capture_ghost_snapshot_report(
 }
 
 /// Create a ghost commit capturing the current state of the repository's working tree along with a report.
+///
+/// Outside a Git repository the snapshot goes to the configured shadow store
+/// instead, when there is one.
 pub fn create_ghost_commit_with_report(
     options: &CreateGhostCommitOptions<'_>,
 ) -> Result<(GhostCommit, GhostSnapshotReport), GitToolingError> {
-    ensure_git_repository(options.repo_path)?;
+    if let Err(err) = ensure_git_repository(options.repo_path) {
+        return match (err, options.ghost_snapshot.shadow_store.as_deref()) {
+            (GitToolingError::NotAGitRepository { .. }, Some(store_root)) => {
+                ShadowStore::new(store_root).capture(options.repo_path, &options.ghost_snapshot)
+            }
+            (err, _) => Err(err),
+        };
+    }
 
     let repo_root = resolve_repository_root(options.repo_path)?;
     let repo_prefix = repo_subdir(repo_root.as_path(), options.repo_path);
@@ -433,6 +448,12 @@ pub fn restore_ghost_commit_with_options(
     options: &RestoreGhostCommitOptions<'_>,
     commit: &GhostCommit,
 ) -> Result<(), GitToolingError> {
+    if let Some(store_root) = options.ghost_snapshot.shadow_store.as_deref() {
+        let store = ShadowStore::new(store_root);
+        if store.contains(commit.id()) {
+            return store.restore(options.repo_path, commit.id(), &options.ghost_snapshot);
+        }
+    }
     ensure_git_repository(options.repo_path)?;
 
     let repo_root = resolve_repository_root(options.repo_path)?;
@@ -1170,6 +1191,7 @@ mod tests {
             ignore_large_untracked_files: Some(DEFAULT_IGNORE_LARGE_UNTRACKED_FILES),
             ignore_large_untracked_dirs: Some(threshold),
             disable_warnings: false,
+            shadow_store: None,
         };
         let (ghost, _report) = create_ghost_commit_with_report(
             &CreateGhostCommitOptions::new(repo).ghost_snapshot(snapshot_config),
@@ -1776,4 +1798,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn non_git_directory_uses_shadow_store_when_configured() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let store = tempfile::tempdir()?;
+        let dir = temp.path();
+        std::fs::write(dir.join("notes.txt"), "original\n")?;
+
+        assert_matches!(
+            create_ghost_commit(&CreateGhostCommitOptions::new(dir)),
+            Err(GitToolingError::NotAGitRepository { .. })
+        );
+
+        let snapshot_config = GhostSnapshotConfig {
+            shadow_store: Some(store.path().to_path_buf()),
+            ..GhostSnapshotConfig::default()
+        };
+        let ghost = create_ghost_commit(
+            &CreateGhostCommitOptions::new(dir).ghost_snapshot(snapshot_config.clone()),
+        )?;
+        assert_eq!(ghost.parent(), None);
+
+        std::fs::write(dir.join("notes.txt"), "edited\n")?;
+        std::fs::write(dir.join("scratch.txt"), "new\n")?;
+        restore_ghost_commit_with_options(
+            &RestoreGhostCommitOptions::new(dir).ghost_snapshot(snapshot_config),
+            &ghost,
+        )?;
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.txt"))?,
+            "original\n"
+        );
+        assert!(!dir.join("scratch.txt").exists());
+        Ok(())
+    }
 }
@@ -7,6 +7,7 @@ mod errors;
 mod ghost_commits;
 mod operations;
 mod platform;
+mod shadow_store;
 
 pub use apply::ApplyGitRequest;
 pub use apply::ApplyGitResult;
@@ -43,6 +44,9 @@ pub struct GhostCommit {
     parent: Option<CommitID>,
     preexisting_untracked_files: Vec<PathBuf>,
     preexisting_untracked_dirs: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    turn_id: Option<String>,
 }
 
 impl GhostCommit {
@@ -58,9 +62,17 @@ impl GhostCommit {
             parent,
             preexisting_untracked_files,
             preexisting_untracked_dirs,
+            turn_id: None,
         }
     }
 
+    /// Records the turn that captured this snapshot, so the association
+    /// survives in the rollout.
+    pub fn with_turn_id(mut self, turn_id: String) -> Self {
+        self.turn_id = Some(turn_id);
+        self
+    }
+
     /// Commit ID for the snapshot.
     pub fn id(&self) -> &str {
         &self.id
@@ -80,6 +92,11 @@ impl GhostCommit {
     pub fn preexisting_untracked_dirs(&self) -> &[PathBuf] {
         &self.preexisting_untracked_dirs
     }
+
+    /// Turn that captured the snapshot, when recorded.
+    pub fn turn_id(&self) -> Option<&str> {
+        self.turn_id.as_deref()
+    }
 }
 
 impl fmt::Display for GhostCommit {
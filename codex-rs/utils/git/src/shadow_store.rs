@@ -0,0 +1,663 @@
+//! Content-addressed snapshots for directories that are not inside a Git
+//! repository.
+//!
+//! Layout under the store root:
+//!
+//! ```text
+//! objects/<aa>/<rest-of-sha256>   file bodies, shared between snapshots
+//! manifests/<sha256>.json         one manifest per snapshot
+//! ```
+//!
+//! A snapshot id is the SHA-256 of its manifest, so capturing an unchanged
+//! directory twice yields the same id. Ids are 64 hex characters, which keeps
+//! them distinct from the 40-character commit ids of Git-backed snapshots.
+//!
+//! Capturing occasionally collects garbage: manifests not captured again for
+//! [`SNAPSHOT_RETENTION`] are removed along with the objects only they used.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+use walkdir::WalkDir;
+
+use crate::GhostCommit;
+use crate::GitToolingError;
+use crate::ghost_commits::DEFAULT_IGNORED_DIR_NAMES;
+use crate::ghost_commits::GhostSnapshotConfig;
+use crate::ghost_commits::GhostSnapshotReport;
+use crate::ghost_commits::IgnoredUntrackedFile;
+
+/// Refuse to snapshot directories with more files than this (for example a
+/// home directory); copying them on every turn would be far too slow.
+const MAX_SNAPSHOT_FILES: usize = 20_000;
+
+/// Snapshots older than this are dropped when the store is collected.
+const SNAPSHOT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Minimum time between two collections of the same store.
+const GC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Marker file whose mtime records the last collection.
+const GC_MARKER: &str = "last_gc";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// Canonical path of the directory the snapshot was taken from.
+    root: PathBuf,
+    /// Captured files keyed by `/`-separated path relative to `root`.
+    files: BTreeMap<String, ManifestEntry>,
+    /// Directories that existed at capture time, relative to `root`.
+    dirs: BTreeSet<String>,
+    /// Files left out because of their size. Restore never deletes them.
+    skipped: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    digest: String,
+    #[serde(default)]
+    executable: bool,
+}
+
+/// Files and directories under a snapshot root, after applying ignore rules.
+#[derive(Default)]
+struct DirListing {
+    files: Vec<ListedFile>,
+    dirs: BTreeSet<String>,
+}
+
+struct ListedFile {
+    relative: String,
+    path: PathBuf,
+    byte_size: u64,
+}
+
+pub(crate) struct ShadowStore {
+    root: PathBuf,
+}
+
+impl ShadowStore {
+    pub(crate) fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Whether `id` names a snapshot held by this store.
+    pub(crate) fn contains(&self, id: &str) -> bool {
+        is_digest(id) && self.manifest_path(id).is_file()
+    }
+
+    /// Captures every file under `dir` and returns the snapshot as a
+    /// parentless [`GhostCommit`].
+    pub(crate) fn capture(
+        &self,
+        dir: &Path,
+        config: &GhostSnapshotConfig,
+    ) -> Result<(GhostCommit, GhostSnapshotReport), GitToolingError> {
+        let root = fs::canonicalize(dir)?;
+        let listing = self.list(&root, Some(MAX_SNAPSHOT_FILES))?;
+
+        let mut files = BTreeMap::new();
+        let mut skipped = BTreeSet::new();
+        let mut ignored_untracked_files = Vec::new();
+        for file in listing.files {
+            if exceeds_threshold(file.byte_size, config.ignore_large_untracked_files) {
+                ignored_untracked_files.push(IgnoredUntrackedFile {
+                    path: PathBuf::from(&file.relative),
+                    byte_size: i64::try_from(file.byte_size).unwrap_or(i64::MAX),
+                });
+                skipped.insert(file.relative);
+                continue;
+            }
+            let contents = fs::read(&file.path)?;
+            let digest = self.write_object(&contents)?;
+            let executable = is_executable(&file.path)?;
+            files.insert(file.relative, ManifestEntry { digest, executable });
+        }
+
+        let manifest = Manifest {
+            root,
+            files,
+            dirs: listing.dirs,
+            skipped,
+        };
+        let encoded = serde_json::to_vec_pretty(&manifest)
+            .map_err(|err| GitToolingError::ShadowStore(err.to_string()))?;
+        let id = digest_hex(&encoded);
+        let manifest_path = self.manifest_path(&id);
+        if manifest_path.is_file() {
+            // Retention counts from the last capture, not the first.
+            touch(&manifest_path)?;
+        } else {
+            write_atomically(&manifest_path, &encoded)?;
+        }
+        // Collection is best effort; a failure must not lose this snapshot.
+        let _ = self.collect_garbage_if_due();
+
+        Ok((
+            GhostCommit::new(id, None, Vec::new(), Vec::new()),
+            GhostSnapshotReport {
+                large_untracked_dirs: Vec::new(),
+                ignored_untracked_files,
+            },
+        ))
+    }
+
+    /// Makes `dir` match snapshot `id`: rewrites changed files, recreates
+    /// deleted ones, and removes files and directories created since. Files
+    /// that were too large to capture, or are too large now, are left alone.
+    pub(crate) fn restore(
+        &self,
+        dir: &Path,
+        id: &str,
+        config: &GhostSnapshotConfig,
+    ) -> Result<(), GitToolingError> {
+        let manifest = self.read_manifest(id)?;
+        let root = fs::canonicalize(dir)?;
+        if root != manifest.root {
+            return Err(GitToolingError::ShadowStore(format!(
+                "snapshot {id} was taken in {}, not {}",
+                manifest.root.display(),
+                root.display()
+            )));
+        }
+
+        let listing = self.list(&root, None)?;
+        for file in &listing.files {
+            if manifest.files.contains_key(&file.relative)
+                || manifest.skipped.contains(&file.relative)
+                || exceeds_threshold(file.byte_size, config.ignore_large_untracked_files)
+            {
+                continue;
+            }
+            fs::remove_file(&file.path)?;
+        }
+
+        for (relative, entry) in &manifest.files {
+            let path = root.join(relative);
+            let unchanged = match fs::read(&path) {
+                Ok(current) => digest_hex(&current) == entry.digest,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+                Err(err) => return Err(err.into()),
+            };
+            if !unchanged {
+                let contents = fs::read(self.object_path(&entry.digest))?;
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                // A plain write keeps the mode of an existing file and gives
+                // a recreated one the usual umask-derived mode.
+                fs::write(&path, contents)?;
+            }
+            if is_executable(&path)? != entry.executable {
+                set_executable(&path, entry.executable)?;
+            }
+        }
+
+        // Deepest first, so parents empty out before they are visited.
+        for relative in listing.dirs.iter().rev() {
+            if manifest.dirs.contains(relative) {
+                continue;
+            }
+            let path = root.join(relative);
+            if fs::read_dir(&path)?.next().is_none() {
+                fs::remove_dir(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists `root`, giving up as soon as it holds more than `max_files`
+    /// files so huge trees are not walked in full.
+    fn list(&self, root: &Path, max_files: Option<usize>) -> Result<DirListing, GitToolingError> {
+        let mut listing = DirListing::default();
+        // The store lives in CODEX_HOME next to session state that must
+        // survive an undo, so the parent directory is left alone as well.
+        let excluded = self.excluded_dirs();
+        let walker = WalkDir::new(root)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                !(entry.depth() > 0
+                    && entry.file_type().is_dir()
+                    && (is_ignored_dir(entry.file_name().to_str())
+                        || excluded.iter().any(|dir| entry.path() == dir.as_path())))
+            });
+        for entry in walker {
+            let entry = entry?;
+            if entry.depth() == 0 {
+                continue;
+            }
+            // Paths that cannot be recorded in the manifest are neither
+            // captured nor cleaned up.
+            let Some(relative) = relative_key(root, entry.path()) else {
+                continue;
+            };
+            let file_type = entry.file_type();
+            if file_type.is_dir() {
+                listing.dirs.insert(relative);
+            } else if file_type.is_file() {
+                if max_files.is_some_and(|max| listing.files.len() >= max) {
+                    return Err(GitToolingError::ShadowStore(format!(
+                        "{} has more than {MAX_SNAPSHOT_FILES} files; not snapshotting it",
+                        root.display()
+                    )));
+                }
+                listing.files.push(ListedFile {
+                    relative,
+                    path: entry.path().to_path_buf(),
+                    byte_size: entry.metadata()?.len(),
+                });
+            }
+        }
+        Ok(listing)
+    }
+
+    fn excluded_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(self.root.as_path())
+            .chain(self.root.parent())
+            .map(|dir| fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()))
+            .collect()
+    }
+
+    /// Runs [`Self::collect_garbage`] unless the store was collected within
+    /// the last [`GC_INTERVAL`].
+    fn collect_garbage_if_due(&self) -> io::Result<()> {
+        let marker = self.root.join(GC_MARKER);
+        let now = SystemTime::now();
+        let due = match fs::metadata(&marker).and_then(|metadata| metadata.modified()) {
+            Ok(last) => now.duration_since(last).unwrap_or_default() >= GC_INTERVAL,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => true,
+            Err(err) => return Err(err),
+        };
+        if !due {
+            return Ok(());
+        }
+        write_atomically(&marker, &[])?;
+        self.collect_garbage(now)
+    }
+
+    /// Removes manifests older than [`SNAPSHOT_RETENTION`], then objects no
+    /// remaining manifest refers to. Objects written within the last
+    /// [`GC_INTERVAL`] are kept, since a capture running concurrently may not
+    /// have written the manifest that refers to them yet.
+    fn collect_garbage(&self, now: SystemTime) -> io::Result<()> {
+        let is_older_than = |path: &Path, age: Duration| -> io::Result<bool> {
+            let modified = fs::metadata(path)?.modified()?;
+            Ok(now.duration_since(modified).unwrap_or_default() >= age)
+        };
+
+        let mut referenced = BTreeSet::new();
+        for entry in read_dir_if_exists(&self.root.join("manifests"))? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            if is_older_than(&path, SNAPSHOT_RETENTION)? {
+                fs::remove_file(&path)?;
+                continue;
+            }
+            // If a manifest cannot be read, its objects are unknown, so no
+            // object is safe to delete this time.
+            let Some(manifest) = fs::read(&path)
+                .ok()
+                .and_then(|encoded| serde_json::from_slice::<Manifest>(&encoded).ok())
+            else {
+                return Ok(());
+            };
+            referenced.extend(manifest.files.into_values().map(|entry| entry.digest));
+        }
+
+        for prefix in read_dir_if_exists(&self.root.join("objects"))? {
+            let prefix = prefix?.path();
+            for object in fs::read_dir(&prefix)? {
+                let path = object?.path();
+                let Some(digest) =
+                    relative_key(&self.root.join("objects"), &path).map(|key| key.replace('/', ""))
+                else {
+                    continue;
+                };
+                if !referenced.contains(&digest) && is_older_than(&path, GC_INTERVAL)? {
+                    fs::remove_file(&path)?;
+                }
+            }
+            if fs::read_dir(&prefix)?.next().is_none() {
+                fs::remove_dir(&prefix)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_object(&self, contents: &[u8]) -> Result<String, GitToolingError> {
+        let digest = digest_hex(contents);
+        let path = self.object_path(&digest);
+        if !path.is_file() {
+            write_atomically(&path, contents)?;
+        }
+        Ok(digest)
+    }
+
+    fn read_manifest(&self, id: &str) -> Result<Manifest, GitToolingError> {
+        if !is_digest(id) {
+            return Err(GitToolingError::ShadowStore(format!(
+                "{id} is not a snapshot id"
+            )));
+        }
+        let encoded = fs::read(self.manifest_path(id))?;
+        serde_json::from_slice(&encoded)
+            .map_err(|err| GitToolingError::ShadowStore(format!("snapshot {id}: {err}")))
+    }
+
+    fn object_path(&self, digest: &str) -> PathBuf {
+        let (prefix, rest) = digest.split_at(2.min(digest.len()));
+        self.root.join("objects").join(prefix).join(rest)
+    }
+
+    fn manifest_path(&self, id: &str) -> PathBuf {
+        self.root.join("manifests").join(format!("{id}.json"))
+    }
+}
+
+fn digest_hex(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn is_digest(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn is_ignored_dir(name: Option<&str>) -> bool {
+    name.is_some_and(|name| name == ".git" || DEFAULT_IGNORED_DIR_NAMES.contains(&name))
+}
+
+fn exceeds_threshold(byte_size: u64, threshold: Option<i64>) -> bool {
+    threshold
+        .and_then(|threshold| u64::try_from(threshold).ok())
+        .is_some_and(|threshold| byte_size > threshold)
+}
+
+fn relative_key(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts = relative
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("/"))
+}
+
+fn read_dir_if_exists(dir: &Path) -> io::Result<Vec<io::Result<fs::DirEntry>>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => Ok(entries.collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn touch(path: &Path) -> io::Result<()> {
+    fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())
+}
+
+/// Writes `contents` to a temp file beside `path` and renames it into place,
+/// so readers never observe a partially written file.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    file.write_all(contents)?;
+    file.flush()?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(fs::metadata(path)?.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> io::Result<bool> {
+    Ok(false)
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path, executable: bool) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    let mode = permissions.mode();
+    permissions.set_mode(if executable {
+        mode | ((mode & 0o444) >> 2)
+    } else {
+        mode & !0o111
+    });
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path, _executable: bool) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn config() -> GhostSnapshotConfig {
+        GhostSnapshotConfig::default()
+    }
+
+    #[test]
+    fn restore_undoes_edits_creations_and_deletions() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        let dir = work.path();
+        fs::write(dir.join("edited.txt"), "before\n")?;
+        fs::write(dir.join("deleted.txt"), "keep me\n")?;
+        fs::create_dir(dir.join("src"))?;
+        fs::write(dir.join("src/lib.rs"), "fn main() {}\n")?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (snapshot, _) = store.capture(dir, &config())?;
+        assert!(store.contains(snapshot.id()));
+        assert_eq!(snapshot.parent(), None);
+
+        fs::write(dir.join("edited.txt"), "after\n")?;
+        fs::remove_file(dir.join("deleted.txt"))?;
+        fs::create_dir_all(dir.join("new/nested"))?;
+        fs::write(dir.join("new/nested/file.txt"), "created\n")?;
+
+        store.restore(dir, snapshot.id(), &config())?;
+
+        assert_eq!(fs::read_to_string(dir.join("edited.txt"))?, "before\n");
+        assert_eq!(fs::read_to_string(dir.join("deleted.txt"))?, "keep me\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("src/lib.rs"))?,
+            "fn main() {}\n"
+        );
+        assert!(!dir.join("new").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn identical_trees_share_an_id() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        fs::write(work.path().join("a.txt"), "same\n")?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (first, _) = store.capture(work.path(), &config())?;
+        let (second, _) = store.capture(work.path(), &config())?;
+        assert_eq!(first.id(), second.id());
+
+        fs::write(work.path().join("a.txt"), "different\n")?;
+        let (third, _) = store.capture(work.path(), &config())?;
+        assert_ne!(first.id(), third.id());
+        Ok(())
+    }
+
+    #[test]
+    fn large_and_ignored_files_are_preserved() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        let dir = work.path();
+        fs::write(dir.join("big.bin"), vec![0u8; 64])?;
+        fs::create_dir(dir.join("node_modules"))?;
+        fs::write(dir.join("node_modules/dep.js"), "module\n")?;
+
+        let config = GhostSnapshotConfig {
+            ignore_large_untracked_files: Some(16),
+            ..GhostSnapshotConfig::default()
+        };
+        let store = ShadowStore::new(store_dir.path());
+        let (snapshot, report) = store.capture(dir, &config)?;
+        assert_eq!(
+            report.ignored_untracked_files,
+            vec![IgnoredUntrackedFile {
+                path: PathBuf::from("big.bin"),
+                byte_size: 64,
+            }]
+        );
+
+        fs::write(dir.join("later-big.bin"), vec![1u8; 64])?;
+        fs::write(dir.join("node_modules/other.js"), "module\n")?;
+        store.restore(dir, snapshot.id(), &config)?;
+
+        assert!(dir.join("big.bin").exists());
+        assert!(dir.join("later-big.bin").exists());
+        assert!(dir.join("node_modules/other.js").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn restore_refuses_a_different_directory() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        fs::write(second.path().join("precious.txt"), "data\n")?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (snapshot, _) = store.capture(first.path(), &config())?;
+        let result = store.restore(second.path(), snapshot.id(), &config());
+
+        assert!(matches!(result, Err(GitToolingError::ShadowStore(_))));
+        assert!(second.path().join("precious.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn store_parent_directory_is_not_captured() -> Result<(), GitToolingError> {
+        let work = tempfile::tempdir()?;
+        let dir = work.path();
+        let codex_home = dir.join(".codex");
+        fs::create_dir(&codex_home)?;
+        fs::write(dir.join("notes.txt"), "before\n")?;
+
+        let store = ShadowStore::new(&codex_home.join("snapshots"));
+        let (snapshot, _) = store.capture(dir, &config())?;
+
+        fs::write(dir.join("notes.txt"), "after\n")?;
+        fs::write(codex_home.join("session.jsonl"), "{}\n")?;
+        store.restore(dir, snapshot.id(), &config())?;
+
+        assert_eq!(fs::read_to_string(dir.join("notes.txt"))?, "before\n");
+        assert_eq!(
+            fs::read_to_string(codex_home.join("session.jsonl"))?,
+            "{}\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn listing_stops_once_the_file_limit_is_exceeded() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(work.path().join(name), "x\n")?;
+        }
+
+        let store = ShadowStore::new(store_dir.path());
+        let root = fs::canonicalize(work.path())?;
+        assert_eq!(store.list(&root, Some(3))?.files.len(), 3);
+        assert!(matches!(
+            store.list(&root, Some(2)),
+            Err(GitToolingError::ShadowStore(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn garbage_collection_drops_expired_snapshots_and_their_objects() -> Result<(), GitToolingError>
+    {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        fs::write(work.path().join("a.txt"), "old\n")?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (old, _) = store.capture(work.path(), &config())?;
+        let old_object = store.object_path(&digest_hex(b"old\n"));
+        assert!(old_object.is_file());
+
+        store.collect_garbage(SystemTime::now() + SNAPSHOT_RETENTION + GC_INTERVAL)?;
+
+        assert!(!store.contains(old.id()));
+        assert!(!old_object.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn garbage_collection_keeps_recent_snapshots() -> Result<(), GitToolingError> {
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        fs::write(work.path().join("a.txt"), "kept\n")?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (snapshot, _) = store.capture(work.path(), &config())?;
+        store.collect_garbage(SystemTime::now() + GC_INTERVAL)?;
+
+        fs::write(work.path().join("a.txt"), "changed\n")?;
+        store.restore(work.path(), snapshot.id(), &config())?;
+        assert_eq!(fs::read_to_string(work.path().join("a.txt"))?, "kept\n");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restore_brings_back_executable_bit() -> Result<(), GitToolingError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store_dir = tempfile::tempdir()?;
+        let work = tempfile::tempdir()?;
+        let script = work.path().join("run.sh");
+        fs::write(&script, "#!/bin/sh\n")?;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+
+        let store = ShadowStore::new(store_dir.path());
+        let (snapshot, _) = store.capture(work.path(), &config())?;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644))?;
+        store.restore(work.path(), snapshot.id(), &config())?;
+
+        assert!(is_executable(&script)?);
+        Ok(())
+    }
+}
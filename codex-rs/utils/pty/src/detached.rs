@@ -0,0 +1,335 @@
+//! Processes that are meant to outlive the Codex process that started them.
+//!
+//! A detached process runs in its own session with stdin closed and stdout and
+//! stderr appended straight to a log file, so it keeps running (and logging)
+//! after Codex exits. Output is reported to the caller by tailing that file,
+//! and a later Codex process can attach to the job again by PID. While a
+//! process is tailed its log is rotated at [`MAX_LOG_BYTES`].
+
+use std::collections::HashMap;
+use std::io;
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::process::ChildTerminator;
+use crate::process::ProcessHandle;
+use crate::process::SpawnedProcess;
+
+/// How often the log is checked for new output and an attached process for
+/// liveness.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Size past which a tailed log is rotated: its contents are copied to
+/// [`rotated_log_path`], replacing an earlier rotation, and the log is
+/// truncated. A process nobody is attached to keeps appending until a Codex
+/// process attaches to it again.
+pub const MAX_LOG_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Where rotation keeps the previous contents of `log_path`.
+pub fn rotated_log_path(log_path: &Path) -> PathBuf {
+    let mut path = log_path.as_os_str().to_owned();
+    path.push(".1");
+    PathBuf::from(path)
+}
+
+struct DetachedChildTerminator {
+    process_group_id: u32,
+}
+
+impl ChildTerminator for DetachedChildTerminator {
+    fn kill(&mut self) -> io::Result<()> {
+        crate::process_group::kill_process_group(self.process_group_id)
+    }
+}
+
+/// Spawns `program` in a new session with stdin closed and its output
+/// appended to `log_path`.
+///
+/// Unlike the pipe and PTY spawners, the child gets no parent-death signal:
+/// it is only stopped by [`ProcessHandle::terminate`], and not at all once
+/// the handle has been [disowned](ProcessHandle::disown).
+pub async fn spawn_process(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+    env: &HashMap<String, String>,
+    arg0: &Option<String>,
+    log_path: &Path,
+) -> Result<SpawnedProcess> {
+    if program.is_empty() {
+        anyhow::bail!("missing program for detached spawn");
+    }
+
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+    let offset = log.metadata()?.len();
+
+    let mut command = Command::new(program);
+    if let Some(arg0) = arg0 {
+        command.arg0(arg0);
+    }
+    unsafe {
+        command.pre_exec(crate::process_group::detach_from_tty);
+    }
+    command.current_dir(cwd);
+    command.env_clear();
+    command.envs(env);
+    command.args(args);
+    command.stdin(Stdio::null());
+    command.stdout(log.try_clone()?);
+    command.stderr(log);
+
+    let mut child = command.spawn()?;
+    let pid = child
+        .id()
+        .ok_or_else(|| io::Error::other("missing child pid"))?;
+
+    let exit_status = Arc::new(AtomicBool::new(false));
+    let exit_code = Arc::new(StdMutex::new(None));
+    let (exit_tx, exit_rx) = oneshot::channel::<i32>();
+    let wait_exit_status = Arc::clone(&exit_status);
+    let wait_exit_code = Arc::clone(&exit_code);
+    let wait_handle = tokio::spawn(async move {
+        let code = match child.wait().await {
+            Ok(status) => status.code().unwrap_or(-1),
+            Err(_) => -1,
+        };
+        if let Ok(mut guard) = wait_exit_code.lock() {
+            *guard = Some(code);
+        }
+        wait_exit_status.store(true, Ordering::SeqCst);
+        let _ = exit_tx.send(code);
+    });
+
+    Ok(tail_process(
+        pid,
+        log_path.to_path_buf(),
+        offset,
+        wait_handle,
+        exit_status,
+        exit_code,
+        exit_rx,
+    ))
+}
+
+/// Attaches to a process started by [`spawn_process`], possibly in another
+/// Codex process, reporting output appended to `log_path` from now on.
+///
+/// `start_time` is the [`process_start_time`] recorded when the process was
+/// spawned, if any. The exit code of an attached process cannot be observed;
+/// once it is gone the handle reports it as exited with no code.
+pub fn attach_process(
+    pid: u32,
+    start_time: Option<u64>,
+    log_path: &Path,
+) -> Result<SpawnedProcess> {
+    if !is_process_alive(pid, start_time) {
+        anyhow::bail!("process {pid} is not running");
+    }
+    let offset = std::fs::metadata(log_path)?.len();
+
+    let exit_status = Arc::new(AtomicBool::new(false));
+    let exit_code = Arc::new(StdMutex::new(None));
+    let (exit_tx, exit_rx) = oneshot::channel::<i32>();
+    let wait_exit_status = Arc::clone(&exit_status);
+    let wait_handle = tokio::spawn(async move {
+        while is_process_alive(pid, start_time) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        wait_exit_status.store(true, Ordering::SeqCst);
+        let _ = exit_tx.send(-1);
+    });
+
+    Ok(tail_process(
+        pid,
+        log_path.to_path_buf(),
+        offset,
+        wait_handle,
+        exit_status,
+        exit_code,
+        exit_rx,
+    ))
+}
+
+/// Whether `pid` is still a running session leader, as a process started by
+/// [`spawn_process`] is, and started at `start_time` when one was recorded.
+/// Both checks guard against the PID having been reused by an unrelated
+/// process.
+pub fn is_process_alive(pid: u32, start_time: Option<u64>) -> bool {
+    let started_as_recorded = |expected: u64| process_start_time(pid) == Some(expected);
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    let signalable = unsafe { libc::kill(pid, 0) } == 0
+        || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM);
+    signalable
+        && unsafe { libc::getsid(pid) } == pid
+        && !is_zombie(pid)
+        && start_time.is_none_or(started_as_recorded)
+}
+
+/// When `pid` started, in clock ticks since boot (field 22 of
+/// `/proc/<pid>/stat`). A reused PID gets a new start time, so recording it
+/// next to the PID identifies the process that was spawned.
+#[cfg(target_os = "linux")]
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // Skip the parenthesized command name; the rest starts at field 3.
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(22 - 3)?.parse().ok()
+}
+
+/// Not available off Linux; callers then rely on the session check alone.
+#[cfg(not(target_os = "linux"))]
+pub fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// A zombie still answers `kill(pid, 0)` until it is reaped, which for a job
+/// whose parent went away may take a while.
+#[cfg(target_os = "linux")]
+fn is_zombie(pid: libc::pid_t) -> bool {
+    // The state follows the parenthesized command name, which may itself
+    // contain spaces or parentheses.
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|stat| {
+            let (_, rest) = stat.rsplit_once(')')?;
+            rest.trim_start().chars().next()
+        })
+        .is_some_and(|state| state == 'Z')
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_zombie(_pid: libc::pid_t) -> bool {
+    false
+}
+
+fn tail_process(
+    pid: u32,
+    log_path: PathBuf,
+    offset: u64,
+    wait_handle: JoinHandle<()>,
+    exit_status: Arc<AtomicBool>,
+    exit_code: Arc<StdMutex<Option<i32>>>,
+    exit_rx: oneshot::Receiver<i32>,
+) -> SpawnedProcess {
+    let (stdout_tx, stdout_rx) = mpsc::channel::<Vec<u8>>(128);
+    let (_stderr_tx, stderr_rx) = mpsc::channel::<Vec<u8>>(1);
+    let reader_handle = tokio::spawn(tail_log(
+        log_path,
+        offset,
+        Arc::clone(&exit_status),
+        stdout_tx,
+    ));
+    let reader_abort_handles = vec![reader_handle.abort_handle()];
+
+    // The child never reads stdin; writes are discarded.
+    let (writer_tx, writer_rx) = mpsc::channel::<Vec<u8>>(1);
+    drop(writer_rx);
+
+    let handle = ProcessHandle::new(
+        writer_tx,
+        Box::new(DetachedChildTerminator {
+            process_group_id: pid,
+        }),
+        reader_handle,
+        reader_abort_handles,
+        tokio::spawn(async {}),
+        wait_handle,
+        exit_status,
+        exit_code,
+        None,
+    )
+    .with_pid(pid);
+
+    SpawnedProcess {
+        session: handle,
+        stdout_rx,
+        stderr_rx,
+        exit_rx,
+    }
+}
+
+/// Forwards bytes appended to `log_path` after `offset` until the process has
+/// exited and everything it wrote has been read, rotating the log whenever it
+/// has grown past [`MAX_LOG_BYTES`] and everything in it has been forwarded.
+async fn tail_log(
+    log_path: PathBuf,
+    offset: u64,
+    exited: Arc<AtomicBool>,
+    output_tx: mpsc::Sender<Vec<u8>>,
+) {
+    let Ok(mut file) = tokio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&log_path)
+        .await
+    else {
+        return;
+    };
+    if file.seek(SeekFrom::Start(offset)).await.is_err() {
+        return;
+    }
+    let mut position = offset;
+    let mut rotate_at = MAX_LOG_BYTES;
+    let mut buf = vec![0u8; 8_192];
+    loop {
+        // Read the flag before reading, so output written just before the
+        // exit is still picked up by one more pass.
+        let finished = exited.load(Ordering::SeqCst);
+        match file.read(&mut buf).await {
+            Ok(0) if finished => break,
+            Ok(0) => {
+                if position >= rotate_at {
+                    match rotate_log(&log_path, &mut file).await {
+                        Ok(()) => position = 0,
+                        // Retry once as much again has been written.
+                        Err(_) => rotate_at = position.saturating_add(MAX_LOG_BYTES),
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+            Ok(n) => {
+                position += n as u64;
+                if output_tx.send(buf[..n].to_vec()).await.is_err() {
+                    break;
+                }
+            }
+            Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+}
+
+/// Copies the log to [`rotated_log_path`] and truncates it, like logrotate's
+/// `copytruncate`: the child keeps writing through its append-mode descriptor,
+/// so its next write lands at the start of the emptied file. Output written
+/// between the copy and the truncation is lost.
+async fn rotate_log(log_path: &Path, file: &mut tokio::fs::File) -> io::Result<()> {
+    tokio::fs::copy(log_path, rotated_log_path(log_path)).await?;
+    file.set_len(0).await?;
+    file.seek(SeekFrom::Start(0)).await?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#[cfg(unix)]
+pub mod detached;
 pub mod pipe;
 mod process;
 pub mod process_group;
@@ -62,6 +62,7 @@ pub struct ProcessHandle {
     wait_handle: StdMutex<Option<JoinHandle<()>>>,
     exit_status: Arc<AtomicBool>,
     exit_code: Arc<StdMutex<Option<i32>>>,
+    pid: Option<u32>,
     // PtyHandles must be preserved because the process will receive Control+C if the
     // slave is closed
     _pty_handles: StdMutex<Option<PtyHandles>>,
@@ -95,10 +96,21 @@ impl ProcessHandle {
             wait_handle: StdMutex::new(Some(wait_handle)),
             exit_status,
             exit_code,
+            pid: None,
             _pty_handles: StdMutex::new(pty_handles),
         }
     }
 
+    pub(crate) fn with_pid(mut self, pid: u32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// PID of the child, for processes that can be attached to again later.
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
     /// Returns a channel sender for writing raw bytes to the child stdin.
     pub fn writer_sender(&self) -> mpsc::Sender<Vec<u8>> {
         if let Ok(writer_tx) = self.writer_tx.lock() {
@@ -151,6 +163,14 @@ impl ProcessHandle {
         }
     }
 
+    /// Forgets how to kill the child, so terminating or dropping the handle
+    /// afterwards only stops the helper tasks and leaves the child running.
+    pub fn disown(&self) {
+        if let Ok(mut killer_opt) = self.killer.lock() {
+            killer_opt.take();
+        }
+    }
+
     /// Attempts to kill the child and abort helper tasks.
     pub fn terminate(&self) {
         self.request_terminate();
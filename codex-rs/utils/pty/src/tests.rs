@@ -3,12 +3,12 @@ use std::path::Path;
 
 use pretty_assertions::assert_eq;
 
+use crate::SpawnedProcess;
+use crate::TerminalSize;
 use crate::combine_output_receivers;
 use crate::spawn_pipe_process;
 use crate::spawn_pipe_process_no_stdin;
 use crate::spawn_pty_process;
-use crate::SpawnedProcess;
-use crate::TerminalSize;
 
 fn find_python() -> Option<String> {
     for candidate in ["python3", "python"] {
@@ -552,3 +552,111 @@ async fn pty_terminate_kills_background_children_in_same_process_group() -> anyh
 
     Ok(())
 }
+
+#[cfg(unix)]
+fn detached_log_path(name: &str) -> std::path::PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("codex-detached-{}-{name}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn detached_process_writes_output_to_its_log() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let log_path = detached_log_path("output");
+    let (program, args) = shell_command("echo out; echo err >&2; exit 3");
+    let spawned =
+        crate::detached::spawn_process(&program, &args, Path::new("."), &env_map, &None, &log_path)
+            .await?;
+    let SpawnedProcess {
+        session,
+        stdout_rx,
+        exit_rx,
+        ..
+    } = spawned;
+    assert!(session.pid().is_some());
+
+    // The output channel closes once the process has exited and the whole log
+    // has been forwarded.
+    let output = tokio::time::timeout(
+        tokio::time::Duration::from_secs(5),
+        collect_split_output(stdout_rx),
+    )
+    .await?;
+    assert_eq!(exit_rx.await?, 3);
+    assert_eq!(String::from_utf8_lossy(&output), "out\nerr\n");
+    assert_eq!(std::fs::read_to_string(&log_path)?, "out\nerr\n");
+
+    let _ = std::fs::remove_file(&log_path);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn detached_log_is_rotated_once_it_outgrows_the_cap() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let log_path = detached_log_path("rotate");
+    let rotated_path = crate::detached::rotated_log_path(&log_path);
+    let oversize = crate::detached::MAX_LOG_BYTES + 1;
+    let (program, args) = shell_command(&format!(
+        "head -c {oversize} /dev/zero; sleep 1; echo after"
+    ));
+    let spawned =
+        crate::detached::spawn_process(&program, &args, Path::new("."), &env_map, &None, &log_path)
+            .await?;
+    let SpawnedProcess {
+        session: _session,
+        stdout_rx,
+        ..
+    } = spawned;
+
+    let output = tokio::time::timeout(
+        tokio::time::Duration::from_secs(10),
+        collect_split_output(stdout_rx),
+    )
+    .await?;
+
+    assert_eq!(output.len() as u64, oversize + "after\n".len() as u64);
+    assert_eq!(std::fs::metadata(&rotated_path)?.len(), oversize);
+    assert_eq!(std::fs::read_to_string(&log_path)?, "after\n");
+    let _ = std::fs::remove_file(&log_path);
+    let _ = std::fs::remove_file(&rotated_path);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn disowned_detached_process_survives_and_can_be_reattached() -> anyhow::Result<()> {
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let log_path = detached_log_path("reattach");
+    let (program, args) = shell_command("sleep 1000");
+    let spawned =
+        crate::detached::spawn_process(&program, &args, Path::new("."), &env_map, &None, &log_path)
+            .await?;
+    let pid = spawned.session.pid().expect("detached process has a pid");
+    spawned.session.disown();
+    drop(spawned);
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let start_time = crate::detached::process_start_time(pid);
+    assert!(crate::detached::is_process_alive(pid, start_time));
+    if let Some(start_time) = start_time {
+        assert!(!crate::detached::is_process_alive(
+            pid,
+            Some(start_time + 1)
+        ));
+        assert!(crate::detached::attach_process(pid, Some(start_time + 1), &log_path).is_err());
+    }
+
+    let attached = crate::detached::attach_process(pid, start_time, &log_path)?;
+    let (session, _output_rx, exit_rx) = combine_spawned_output(attached);
+    session.request_terminate();
+    let exited = tokio::time::timeout(tokio::time::Duration::from_secs(3), exit_rx).await;
+
+    assert!(exited.is_ok(), "detached process {pid} survived terminate");
+    assert!(!crate::detached::is_process_alive(pid, start_time));
+    assert_eq!(session.exit_code(), None);
+    let _ = std::fs::remove_file(&log_path);
+    Ok(())
+}